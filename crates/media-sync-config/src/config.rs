@@ -15,10 +15,61 @@ pub struct Config {
     #[serde(default)]
     pub scheduler: Option<SchedulerConfig>,
     #[serde(default)]
+    pub credentials: CredentialsConfig,
+    #[serde(default)]
+    pub id_matching: IdMatchingConfig,
+    #[serde(default)]
+    pub notifications: NotificationsConfig,
+    #[serde(default)]
     #[cfg(feature = "browser-debug")]
     pub browser_debug: Option<DebugConfig>,
 }
 
+/// Where tokens and passwords are kept at rest
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum CredentialBackendKind {
+    /// `credentials.toml` in the config directory
+    #[default]
+    File,
+    /// The OS keychain (requires the `keyring` build feature)
+    Keyring,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CredentialsConfig {
+    #[serde(default)]
+    pub backend: CredentialBackendKind,
+}
+
+/// Where the daemon reports each finished sync (`[notifications]`)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NotificationsConfig {
+    /// POST a JSON summary of each run to this URL
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook_url: Option<String>,
+    /// Run this shell command after each run, with the same summary on stdin
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    /// Give up on the webhook or command after this many seconds
+    #[serde(default = "default_notification_timeout_seconds")]
+    pub timeout_seconds: u64,
+}
+
+impl Default for NotificationsConfig {
+    fn default() -> Self {
+        Self {
+            webhook_url: None,
+            command: None,
+            timeout_seconds: default_notification_timeout_seconds(),
+        }
+    }
+}
+
+fn default_notification_timeout_seconds() -> u64 {
+    10
+}
+
 /// Status mapping configuration for converting between service-native and normalized statuses
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct StatusMapping {
@@ -38,6 +89,40 @@ pub struct TraktConfig {
     pub client_secret: String,
     #[serde(default = "default_trakt_status_mapping")]
     pub status_mapping: StatusMapping,
+    /// Collect the titles of comments you liked on Trakt as ratings or tagged watchlist items.
+    /// Off unless set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub likes_as: Option<TraktLikesAs>,
+    /// Named credential set to use (`totalrecall config trakt --account NAME`); the default
+    /// account's credentials when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account: Option<String>,
+    #[serde(default)]
+    pub retry: RetryConfig,
+    #[serde(default)]
+    pub rate_limit: RateLimitConfig,
+    /// Items per request of watchlist, rating and history writes. Larger writes are split
+    /// and sent one chunk after another.
+    #[serde(default = "default_trakt_batch_size")]
+    pub batch_size: usize,
+}
+
+/// Items per Trakt sync request (`trakt.batch_size`)
+pub const DEFAULT_TRAKT_BATCH_SIZE: usize = 100;
+
+fn default_trakt_batch_size() -> usize {
+    DEFAULT_TRAKT_BATCH_SIZE
+}
+
+/// What a Trakt like stands for (`trakt.likes_as`). Trakt likes are on comments; the
+/// movie or show a liked comment is about counts as liked.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TraktLikesAs {
+    /// A 10/10 Trakt rating for liked titles you haven't rated
+    Rating,
+    /// A Trakt watchlist item tagged `liked` (existing watchlist items just gain the tag)
+    Tag,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -47,6 +132,76 @@ pub struct SimklConfig {
     pub client_secret: String,
     #[serde(default = "default_simkl_status_mapping")]
     pub status_mapping: StatusMapping,
+    /// Named credential set to use (`totalrecall config simkl --account NAME`); the default
+    /// account's credentials when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account: Option<String>,
+    #[serde(default)]
+    pub retry: RetryConfig,
+    #[serde(default)]
+    pub rate_limit: RateLimitConfig,
+}
+
+/// Retries of writes that fail with a transient error (429, 5xx, a dropped connection)
+/// (`trakt.retry`, `simkl.retry`)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RetryConfig {
+    /// Attempts per request, the first included; 1 disables retries
+    #[serde(default = "default_retry_max_attempts")]
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled for each further one. A `Retry-After`
+    /// header from the server is used instead when present.
+    #[serde(default = "default_retry_base_delay_ms")]
+    pub base_delay_ms: u64,
+    /// Random variation of each delay, as a fraction of it (0.2 = ±20%)
+    #[serde(default = "default_retry_jitter")]
+    pub jitter: f64,
+}
+
+fn default_retry_max_attempts() -> u32 {
+    3
+}
+
+fn default_retry_base_delay_ms() -> u64 {
+    1_000
+}
+
+fn default_retry_jitter() -> f64 {
+    0.2
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_retry_max_attempts(),
+            base_delay_ms: default_retry_base_delay_ms(),
+            jitter: default_retry_jitter(),
+        }
+    }
+}
+
+/// Pacing of writes, so large pushes don't trip the API's rate limit
+/// (`trakt.rate_limit`, `simkl.rate_limit`). Unset `requests_per_second` means unpaced.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RateLimitConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requests_per_second: Option<f64>,
+    /// Requests that may go out back to back before pacing starts
+    #[serde(default = "default_rate_limit_burst")]
+    pub burst: u32,
+}
+
+fn default_rate_limit_burst() -> u32 {
+    1
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            requests_per_second: None,
+            burst: default_rate_limit_burst(),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -59,6 +214,20 @@ pub struct SourceConfig {
     pub tmdb: Option<TmdbConfig>,
     #[serde(default)]
     pub netflix: Option<NetflixConfig>,
+    #[serde(default)]
+    pub letterboxd: Option<LetterboxdConfig>,
+    #[serde(default)]
+    pub anilist: Option<AniListConfig>,
+    #[serde(default)]
+    pub file: Option<FileConfig>,
+    /// Give up on an API request of Trakt, Simkl or Plex after this many seconds;
+    /// requests wait as long as the server takes when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_timeout_secs: Option<u64>,
+    /// Media types each source contributes to resolution, by source name
+    /// (`plex = ["movie"]`); sources left out contribute everything
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub media_types: HashMap<String, Vec<MediaTypeFilter>>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -67,6 +236,14 @@ pub struct ImdbConfig {
     pub username: String,
     #[serde(default = "default_imdb_status_mapping")]
     pub status_mapping: StatusMapping,
+    /// Upgrade show-level check-ins to the episode that aired just before them,
+    /// looked up by air date through a source that lists episodes (Trakt)
+    #[serde(default)]
+    pub resolve_episode_checkins: bool,
+    /// Named credential set to use (`totalrecall config imdb --account NAME`); the default
+    /// account's credentials when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -75,11 +252,66 @@ pub struct PlexConfig {
     pub server_url: String,
     #[serde(default = "default_plex_status_mapping")]
     pub status_mapping: StatusMapping,
+    /// Quarantine items Plex couldn't match (no GUIDs) at collection time instead of
+    /// collecting them without IDs
+    #[serde(default)]
+    pub skip_unmatched: bool,
+    /// Scales for media types rated differently than movies (`[sources.plex.rating_scales]`)
+    #[serde(default)]
+    pub rating_scales: RatingScales,
+    /// Library labels read as statuses (`[sources.plex.label_status_map]`, e.g.
+    /// `Watchlist = "Watchlist"`), for libraries that use labels instead of the cloud watchlist
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub label_status_map: HashMap<String, media_sync_models::NormalizedStatus>,
+    /// Named credential set to use (`totalrecall config plex --account NAME`); the default
+    /// account's credentials when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account: Option<String>,
+}
+
+/// Highest rating per media type, for sources where shows or episodes are rated on a
+/// different scale than movies. Unset types keep the source's own scale.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RatingScales {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub movie: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub show: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub episode: Option<u8>,
+}
+
+impl RatingScales {
+    /// The scale configured for `media_type`, if any
+    pub fn get(&self, media_type: &media_sync_models::MediaType) -> Option<u8> {
+        match media_type {
+            media_sync_models::MediaType::Movie => self.movie,
+            media_sync_models::MediaType::Show => self.show,
+            media_sync_models::MediaType::Episode { .. } => self.episode,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TmdbConfig {
+    #[serde(default)]
     pub enabled: bool,
+    /// TMDB API base URL, for users behind a mirror or proxy
+    #[serde(default = "default_tmdb_base_url")]
+    pub base_url: String,
+    /// v3 API key or v4 read access token; lookups are sent without one if unset
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// Look up titles no configured source could resolve on TMDB
+    #[serde(default = "default_true")]
+    pub id_lookup: bool,
+}
+
+/// Official TMDB v3 API endpoint
+pub const DEFAULT_TMDB_BASE_URL: &str = "https://api.themoviedb.org/3";
+
+fn default_tmdb_base_url() -> String {
+    DEFAULT_TMDB_BASE_URL.to_string()
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -87,6 +319,130 @@ pub struct NetflixConfig {
     pub enabled: bool,
 }
 
+/// Letterboxd has no API for personal data, so it is read from the data export
+/// (letterboxd.com → Settings → Data → Export your data)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LetterboxdConfig {
+    pub enabled: bool,
+    /// The export zip, the folder it was unpacked into, or a folder of export zips
+    /// (the newest is read)
+    pub export_path: String,
+}
+
+/// AniList anime list (`totalrecall config anilist`). A public list is read with just
+/// the username; writing needs an access token, kept in the credentials.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AniListConfig {
+    pub enabled: bool,
+    /// Whose list is read; the access token's user when empty
+    #[serde(default)]
+    pub username: String,
+    /// Scale scores are read and written on: 100 (AniList's raw scores) or 10
+    #[serde(default = "default_anilist_score_scale")]
+    pub score_scale: u8,
+    /// Named credential set to use (`totalrecall config anilist --account NAME`); the default
+    /// account's credentials when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account: Option<String>,
+}
+
+fn default_anilist_score_scale() -> u8 {
+    100
+}
+
+/// A directory of NDJSON files (`watchlist.ndjson`, `ratings.ndjson`, ...) that is
+/// synced like any other source, e.g. as a backup
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileConfig {
+    pub enabled: bool,
+    pub path: String,
+}
+
+/// A media type a source's data is filtered to (`media_types`)
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum MediaTypeFilter {
+    Movie,
+    /// Shows and their episodes
+    Show,
+    /// Episodes only
+    Episode,
+}
+
+impl MediaTypeFilter {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Movie => "movie",
+            Self::Show => "show",
+            Self::Episode => "episode",
+        }
+    }
+
+    pub fn matches(&self, media_type: &media_sync_models::MediaType) -> bool {
+        use media_sync_models::MediaType;
+        matches!(
+            (self, media_type),
+            (Self::Movie, MediaType::Movie)
+                | (Self::Show, MediaType::Show | MediaType::Episode { .. })
+                | (Self::Episode, MediaType::Episode { .. })
+        )
+    }
+}
+
+/// Weights for choosing between several results of a title search (remakes,
+/// same-named films). The highest total wins; ties go to the search's own order.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IdMatchingConfig {
+    /// Added for a candidate released in the searched year
+    #[serde(default = "default_year_match_weight")]
+    pub year_match_weight: f64,
+    /// Added in full for the most popular candidate (Trakt votes, TMDB popularity)
+    /// and in proportion for the others
+    #[serde(default = "default_popularity_weight")]
+    pub popularity_weight: f64,
+    /// Days a resolved ID stays in the ID cache before it is resolved again (0 = forever)
+    #[serde(default = "default_id_cache_ttl_days")]
+    pub cache_ttl_days: u32,
+    /// Match cached titles ignoring case, punctuation and leading articles when the
+    /// exact title and year aren't cached
+    #[serde(default = "default_true")]
+    pub fuzzy_titles: bool,
+    /// Years a fuzzy title match may be off by (release-date conventions differ)
+    #[serde(default = "default_year_tolerance")]
+    pub year_tolerance: u32,
+}
+
+impl Default for IdMatchingConfig {
+    fn default() -> Self {
+        Self {
+            year_match_weight: default_year_match_weight(),
+            popularity_weight: default_popularity_weight(),
+            cache_ttl_days: default_id_cache_ttl_days(),
+            fuzzy_titles: true,
+            year_tolerance: default_year_tolerance(),
+        }
+    }
+}
+
+/// Days an ID cache entry is trusted (`id_matching.cache_ttl_days`)
+pub const DEFAULT_ID_CACHE_TTL_DAYS: u32 = 90;
+
+fn default_id_cache_ttl_days() -> u32 {
+    DEFAULT_ID_CACHE_TTL_DAYS
+}
+
+fn default_year_match_weight() -> f64 {
+    10.0
+}
+
+fn default_popularity_weight() -> f64 {
+    1.0
+}
+
+fn default_year_tolerance() -> u32 {
+    1
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ResolutionConfig {
     // Global defaults (used for ratings and watchlist)
@@ -96,6 +452,11 @@ pub struct ResolutionConfig {
     #[serde(default)]
     pub source_preference: Vec<String>,  // Required ordered list: ["trakt", "imdb"] for conflict resolution (optional during deserialization, must be set via prompt)
     
+    /// Order sources are collected in, independent of resolution priority.
+    /// Empty means `source_preference` order.
+    #[serde(default)]
+    pub fetch_order: Vec<String>,
+    
     #[serde(default = "default_timestamp_tolerance_seconds")]
     pub timestamp_tolerance_seconds: i64,
     
@@ -105,6 +466,80 @@ pub struct ResolutionConfig {
     
     #[serde(default)]
     pub watchlist_strategy: Option<ResolutionStrategy>,
+    
+    // Per-data-type source priority (override source_preference). Sources left out
+    // follow in source_preference order; empty means source_preference.
+    #[serde(default)]
+    pub watchlist_preference: Vec<String>,
+    
+    #[serde(default)]
+    pub ratings_preference: Vec<String>,
+    
+    #[serde(default)]
+    pub reviews_preference: Vec<String>,
+    
+    #[serde(default)]
+    pub watch_history_preference: Vec<String>,
+}
+
+impl ResolutionConfig {
+    /// Sources in the order they are collected: `fetch_order` first, then any remaining
+    /// sources from `source_preference` in preference order
+    pub fn collection_order(&self) -> Vec<String> {
+        let mut order: Vec<String> = Vec::with_capacity(self.source_preference.len());
+        for source in self.fetch_order.iter().chain(&self.source_preference) {
+            if !order.contains(source) {
+                order.push(source.clone());
+            }
+        }
+        order
+    }
+
+    /// Source priority for one data type (`watchlist`, `ratings`, `reviews` or
+    /// `watch_history`): its `*_preference` override first, then any remaining sources
+    /// from `source_preference` in preference order
+    pub fn preference_for(&self, data_type: &str) -> Vec<String> {
+        let overrides = self.preference_overrides()
+            .into_iter()
+            .find(|(field, _)| field.strip_suffix("_preference") == Some(data_type))
+            .map(|(_, sources)| sources)
+            .unwrap_or_default();
+        let mut order: Vec<String> = Vec::with_capacity(self.source_preference.len());
+        for source in overrides.iter().chain(&self.source_preference) {
+            if !order.contains(source) {
+                order.push(source.clone());
+            }
+        }
+        order
+    }
+
+    /// The per-data-type preferences that are set, as `(field, sources)`
+    pub fn preference_overrides(&self) -> Vec<(&'static str, &[String])> {
+        [
+            ("watchlist_preference", self.watchlist_preference.as_slice()),
+            ("ratings_preference", self.ratings_preference.as_slice()),
+            ("reviews_preference", self.reviews_preference.as_slice()),
+            ("watch_history_preference", self.watch_history_preference.as_slice()),
+        ]
+        .into_iter()
+        .filter(|(_, sources)| !sources.is_empty())
+        .collect()
+    }
+
+    /// Keep only the sources `keep` accepts, in `source_preference`, `fetch_order` and
+    /// the per-data-type preferences
+    pub fn retain_sources(&mut self, keep: impl Fn(&str) -> bool) {
+        for preference in [
+            &mut self.source_preference,
+            &mut self.fetch_order,
+            &mut self.watchlist_preference,
+            &mut self.ratings_preference,
+            &mut self.reviews_preference,
+            &mut self.watch_history_preference,
+        ] {
+            preference.retain(|source| keep(source));
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -113,6 +548,8 @@ pub enum ResolutionStrategy {
     Oldest,
     Preference,
     Merge,
+    /// The latest timestamp wins outright; the source preference only breaks exact ties
+    MostRecent,
 }
 
 fn default_resolution_strategy() -> ResolutionStrategy {
@@ -128,9 +565,14 @@ impl Default for ResolutionConfig {
         Self {
             strategy: default_resolution_strategy(),
             source_preference: Vec::new(),  // Empty by default - must be set explicitly
+            fetch_order: Vec::new(),
             timestamp_tolerance_seconds: default_timestamp_tolerance_seconds(),
             ratings_strategy: None,
             watchlist_strategy: None,
+            watchlist_preference: Vec::new(),
+            ratings_preference: Vec::new(),
+            reviews_preference: Vec::new(),
+            watch_history_preference: Vec::new(),
         }
     }
 }
@@ -145,12 +587,272 @@ pub struct SyncOptions {
     pub sync_reviews: bool,
     #[serde(default = "default_true")]
     pub sync_watch_history: bool,
+    /// Sync named custom lists between the sources that keep them
+    #[serde(default)]
+    pub sync_lists: bool,
+    /// Add owned titles (Plex libraries, Trakt collection) to the writable collections
+    #[serde(default)]
+    pub sync_collection: bool,
+    /// Bring show progress (last watched episode) up to the furthest any source reached
+    #[serde(default)]
+    pub sync_show_progress: bool,
+    /// Never remove anything from a source; removals are only logged
+    #[serde(default)]
+    pub never_remove: bool,
+    /// Abandon a source whose collection takes longer than this many seconds, reporting
+    /// it as an error, so one hung source can't stall the run; no limit when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collect_timeout_secs: Option<u64>,
     #[serde(default)]
     pub remove_watched_from_watchlists: bool,
     #[serde(default)]
     pub mark_rated_as_watched: bool,
     #[serde(default)]
     pub remove_watchlist_items_older_than_days: Option<u32>,
+    #[serde(default)]
+    pub watch_history: WatchHistoryOptions,
+    #[serde(default)]
+    pub watchlist: WatchlistOptions,
+    #[serde(default)]
+    pub ratings: RatingsOptions,
+    #[serde(default)]
+    pub reviews: ReviewsOptions,
+    /// Reviews shorter than this many characters are not distributed to any target; 0 means
+    /// no minimum. Unset, only IMDB gets [`DEFAULT_MIN_REVIEW_LENGTH`].
+    #[serde(default)]
+    pub min_review_length: Option<usize>,
+    #[serde(default)]
+    pub removals: RemovalsOptions,
+    /// Items tagged with any of these in their source are left out of distribution
+    #[serde(default)]
+    pub exclude_tags: Vec<String>,
+    #[serde(default)]
+    pub lock: SyncLockOptions,
+    #[serde(default)]
+    pub hub: HubOptions,
+}
+
+/// Shortest review sent to IMDB unless `sync.min_review_length` says otherwise; IMDB
+/// rejects reviews under 600 characters
+pub const DEFAULT_MIN_REVIEW_LENGTH: usize = 600;
+
+impl SyncOptions {
+    /// The review length minimum in effect for `target`, None when reviews of any length go out
+    pub fn min_review_length(&self, target: &str) -> Option<usize> {
+        match self.min_review_length {
+            Some(min) => Some(min).filter(|min| *min > 0),
+            None => Some(DEFAULT_MIN_REVIEW_LENGTH).filter(|_| target == "imdb"),
+        }
+    }
+
+    /// Effective mode for creating watch history entries from ratings.
+    /// An explicit `watch_history.from_ratings` wins; otherwise the legacy
+    /// `mark_rated_as_watched` flag maps to `AllNonShow` (on) or `Off`.
+    pub fn from_ratings_mode(&self) -> FromRatingsMode {
+        match self.watch_history.from_ratings {
+            Some(mode) => mode,
+            None if self.mark_rated_as_watched => FromRatingsMode::AllNonShow,
+            None => FromRatingsMode::Off,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct WatchlistOptions {
+    /// Maximum watchlist additions per target source in one sync; the rest are
+    /// deferred to later runs. Separate from IMDB's 10,000 item limit.
+    #[serde(default)]
+    pub max_items_to_add_per_run: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct RatingsOptions {
+    /// Keep ratings source-local: never push one source's rating to another
+    #[serde(default)]
+    pub no_cross_sync: bool,
+    /// Date given to ratings a source returns without one (Plex, some Simkl items)
+    #[serde(default)]
+    pub default_date: DefaultRatingDate,
+}
+
+/// Date for ratings collected without one, so date comparisons don't depend on when
+/// each item happened to be parsed
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum DefaultRatingDate {
+    /// When the source was fetched; one time for every undated rating in the fetch
+    #[default]
+    #[serde(alias = "source_import_time")]
+    SourceImportTime,
+    /// 1970-01-01, so undated ratings lose every "most recent" comparison
+    Epoch,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReviewsOptions {
+    /// Remove emoji from review text before it is sent to any target
+    #[serde(default)]
+    pub strip_emoji: bool,
+    /// Targets that get emoji removed even when `strip_emoji` is off (e.g. `["imdb"]`)
+    #[serde(default)]
+    pub strip_emoji_for: Vec<String>,
+    /// Text that replaces each run of emoji instead of dropping it
+    #[serde(default)]
+    pub emoji_replacement: String,
+    /// Minutes after a review is written during which the same text is not written to that
+    /// target again, since Trakt takes a while to return new reviews. 0 turns this off.
+    #[serde(default = "default_indexing_grace_minutes")]
+    pub indexing_grace_minutes: u64,
+}
+
+fn default_indexing_grace_minutes() -> u64 {
+    15
+}
+
+impl Default for ReviewsOptions {
+    fn default() -> Self {
+        Self {
+            strip_emoji: false,
+            strip_emoji_for: Vec::new(),
+            emoji_replacement: String::new(),
+            indexing_grace_minutes: default_indexing_grace_minutes(),
+        }
+    }
+}
+
+impl ReviewsOptions {
+    /// Whether emoji are stripped from reviews sent to the given target
+    pub fn strips_emoji_for(&self, target: &str) -> bool {
+        self.strip_emoji || self.strip_emoji_for.iter().any(|t| t.eq_ignore_ascii_case(target))
+    }
+}
+
+/// What a sync does when another sync already holds the lock
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum LockConflict {
+    /// Exit with "another sync is running"
+    #[default]
+    Exit,
+    /// Wait for the other sync to finish
+    Wait,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SyncLockOptions {
+    #[serde(default)]
+    pub on_conflict: LockConflict,
+    /// Give up waiting after this many seconds (`on_conflict = "wait"`; waits forever if unset)
+    #[serde(default)]
+    pub wait_timeout_seconds: Option<u64>,
+}
+
+/// Name of the synthetic source holding the resolved data of the last sync
+pub const HUB_SOURCE: &str = "hub";
+
+/// A source totalrecall syncs with
+#[derive(Debug, Clone, Copy)]
+pub struct SourceInfo {
+    pub name: &'static str,
+    /// Read from an export or backup file rather than a live account
+    pub snapshot: bool,
+}
+
+/// Every source, in the order a derived `source_preference` lists them
+pub const SOURCES: [SourceInfo; 7] = [
+    SourceInfo { name: "trakt", snapshot: false },
+    SourceInfo { name: "simkl", snapshot: false },
+    SourceInfo { name: "imdb", snapshot: false },
+    SourceInfo { name: "plex", snapshot: false },
+    SourceInfo { name: "letterboxd", snapshot: true },
+    SourceInfo { name: "anilist", snapshot: false },
+    SourceInfo { name: "file", snapshot: true },
+];
+
+/// Names of all [`SOURCES`], in order
+pub fn source_names() -> impl Iterator<Item = &'static str> {
+    SOURCES.iter().map(|source| source.name)
+}
+
+/// Whether `name` is one of [`SOURCES`]
+pub fn is_known_source(name: &str) -> bool {
+    source_names().any(|source| source == name)
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct HubOptions {
+    /// Save each sync's resolved data to the collect cache as the synthetic `hub`
+    /// source, which `totalrecall sync --from-hub` distributes without resolving again
+    #[serde(default)]
+    pub export: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RemovalsOptions {
+    /// Sources whose watchlist removals (items marked Dropped, or gone since the last
+    /// sync) are removed from every other source
+    #[serde(default = "default_authoritative_removal_sources")]
+    pub authoritative_sources: Vec<String>,
+}
+
+fn default_authoritative_removal_sources() -> Vec<String> {
+    vec!["simkl".to_string()]
+}
+
+impl Default for RemovalsOptions {
+    fn default() -> Self {
+        Self {
+            authoritative_sources: default_authoritative_removal_sources(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct WatchHistoryOptions {
+    /// Which rated items get a watch history entry when none exists yet
+    #[serde(default)]
+    pub from_ratings: Option<FromRatingsMode>,
+    /// Send partial plays to sources that track playback progress (Trakt scrobble)
+    /// instead of dropping them
+    #[serde(default)]
+    pub sync_progress: bool,
+    /// Send watch history one watched year at a time, oldest first, moving the sync
+    /// timestamp after each year so a push that fails midway resumes from there
+    #[serde(default)]
+    pub chunk_by_year: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum FromRatingsMode {
+    /// Never create watch history from ratings
+    Off,
+    /// Only rated movies are marked as watched
+    MoviesOnly,
+    /// Rated movies and episodes are marked as watched (shows, and episodes without
+    /// season and episode numbers, are skipped)
+    AllNonShow,
+}
+
+impl FromRatingsMode {
+    pub fn is_enabled(&self) -> bool {
+        !matches!(self, FromRatingsMode::Off)
+    }
+
+    /// Whether a rating of the given media type should produce a watch history entry
+    pub fn applies_to(&self, media_type: &media_sync_models::MediaType) -> bool {
+        use media_sync_models::MediaType;
+        match self {
+            FromRatingsMode::Off => false,
+            FromRatingsMode::MoviesOnly => matches!(media_type, MediaType::Movie),
+            FromRatingsMode::AllNonShow => match media_type {
+                MediaType::Movie => true,
+                // A show has no single play to mark, and a placeholder episode no place in it
+                MediaType::Episode { .. } => !media_type.is_placeholder_episode(),
+                MediaType::Show => false,
+            },
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -165,6 +867,62 @@ pub struct SchedulerConfig {
     /// If false, full sync will only run if no sync timestamps exist (first run)
     #[serde(default = "default_false")]
     pub force_full_sync_on_startup: bool,
+    /// Only sync between these times in `timezone` (`"01:00-06:00"`); runs due outside the window
+    /// wait for its next start. A window may wrap past midnight (`"22:00-02:00"`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_hours: Option<String>,
+}
+
+impl SchedulerConfig {
+    /// The parsed `allowed_hours`, if set
+    pub fn sync_window(&self) -> anyhow::Result<Option<SyncWindow>> {
+        self.allowed_hours.as_deref().map(SyncWindow::parse).transpose()
+    }
+
+    /// The parsed `timezone` (an IANA name such as `"Europe/Berlin"`)
+    pub fn time_zone(&self) -> anyhow::Result<chrono_tz::Tz> {
+        self.timezone.parse::<chrono_tz::Tz>()
+            .map_err(|_| anyhow::anyhow!("scheduler.timezone must be an IANA time zone like \"Europe/Berlin\", got \"{}\"", self.timezone))
+    }
+}
+
+/// Daily time range the daemon may sync in (`scheduler.allowed_hours`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyncWindow {
+    pub start: chrono::NaiveTime,
+    pub end: chrono::NaiveTime,
+}
+
+impl SyncWindow {
+    /// Parse `"HH:MM-HH:MM"`
+    pub fn parse(value: &str) -> anyhow::Result<Self> {
+        let invalid = || anyhow::anyhow!("scheduler.allowed_hours must look like \"01:00-06:00\", got \"{}\"", value);
+        let (start, end) = value.split_once('-').ok_or_else(invalid)?;
+        let parse_time = |time: &str| chrono::NaiveTime::parse_from_str(time.trim(), "%H:%M").map_err(|_| invalid());
+        let window = Self { start: parse_time(start)?, end: parse_time(end)? };
+        if window.start == window.end {
+            return Err(anyhow::anyhow!("scheduler.allowed_hours \"{}\" is empty", value));
+        }
+        Ok(window)
+    }
+
+    pub fn contains(&self, time: chrono::NaiveTime) -> bool {
+        if self.start < self.end {
+            time >= self.start && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+
+    /// How long from `now` until the window opens; zero when it is open
+    pub fn wait_from(&self, now: chrono::NaiveDateTime) -> chrono::Duration {
+        if self.contains(now.time()) {
+            return chrono::Duration::zero();
+        }
+        let today = now.date().and_time(self.start);
+        let next_start = if today > now { today } else { today + chrono::Duration::days(1) };
+        next_start - now
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -202,6 +960,7 @@ pub fn default_scheduler_config() -> SchedulerConfig {
         timezone: default_timezone(),
         run_on_startup: default_true(),
         force_full_sync_on_startup: default_false(),
+        allowed_hours: None,
     }
 }
 
@@ -301,6 +1060,12 @@ pub fn default_plex_status_mapping() -> StatusMapping {
     StatusMapping { to_normalized, from_normalized }
 }
 
+/// Whether `name` can name a credential account (`<source>.account`): letters, digits,
+/// '-' and '_', as it becomes part of credential keys
+pub fn is_valid_account_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
 impl Config {
     pub fn load_from_file(path: &PathBuf) -> anyhow::Result<Self> {
         let content = std::fs::read_to_string(path)?;
@@ -328,9 +1093,104 @@ impl Config {
             return Err(anyhow::anyhow!("source_preference is required and cannot be empty"));
         }
         
-        let valid_sources = ["trakt", "imdb", "plex", "simkl"];
+        if let Some(scheduler) = &self.scheduler {
+            scheduler.sync_window()?;
+        }
+
+        if self.sync.watchlist.max_items_to_add_per_run == Some(0) {
+            return Err(anyhow::anyhow!("sync.watchlist.max_items_to_add_per_run must be greater than 0 (omit it to disable the cap)"));
+        }
+        
+        if let Some(plex) = &self.sources.plex {
+            let scales = &plex.rating_scales;
+            if [scales.movie, scales.show, scales.episode].into_iter().flatten().any(|scale| !(1..=10).contains(&scale)) {
+                return Err(anyhow::anyhow!("sources.plex.rating_scales values must be between 1 and 10"));
+            }
+        }
+        
+        let retries = [
+            ("trakt", self.trakt.as_ref().map(|trakt| &trakt.retry)),
+            ("simkl", self.simkl.as_ref().map(|simkl| &simkl.retry)),
+        ];
+        for (source, retry) in retries {
+            let Some(retry) = retry else { continue };
+            if retry.max_attempts == 0 {
+                return Err(anyhow::anyhow!("{}.retry.max_attempts must be at least 1 (1 disables retries)", source));
+            }
+            if !(0.0..=1.0).contains(&retry.jitter) {
+                return Err(anyhow::anyhow!("{}.retry.jitter must be between 0 and 1", source));
+            }
+        }
+        
+        let rate_limits = [
+            ("trakt", self.trakt.as_ref().map(|trakt| &trakt.rate_limit)),
+            ("simkl", self.simkl.as_ref().map(|simkl| &simkl.rate_limit)),
+        ];
+        for (source, rate_limit) in rate_limits {
+            let Some(rate_limit) = rate_limit else { continue };
+            if rate_limit.requests_per_second.is_some_and(|rps| !(rps > 0.0 && rps.is_finite())) {
+                return Err(anyhow::anyhow!("{}.rate_limit.requests_per_second must be greater than 0", source));
+            }
+            if rate_limit.burst == 0 {
+                return Err(anyhow::anyhow!("{}.rate_limit.burst must be at least 1", source));
+            }
+        }
+        
+        if self.trakt.as_ref().is_some_and(|trakt| trakt.batch_size == 0) {
+            return Err(anyhow::anyhow!("trakt.batch_size must be at least 1"));
+        }
+        
+        if let Some(anilist) = &self.sources.anilist {
+            if anilist.score_scale != 10 && anilist.score_scale != 100 {
+                return Err(anyhow::anyhow!("sources.anilist.score_scale must be 10 or 100"));
+            }
+        }
+        
+        for (source, media_types) in &self.sources.media_types {
+            if !is_known_source(source) {
+                return Err(anyhow::anyhow!("Unknown source '{}' in sources.media_types", source));
+            }
+            if media_types.is_empty() {
+                return Err(anyhow::anyhow!("sources.media_types.{} must list at least one media type (omit it to collect all)", source));
+            }
+        }
+        
+        for (source, account) in self.selected_accounts() {
+            if !is_valid_account_name(account) {
+                return Err(anyhow::anyhow!("Invalid {}.account '{}': use letters, digits, '-' and '_'", source, account));
+            }
+        }
+        
+        // Not snapshot sources: a stale export or backup would remove titles added since
+        // it was taken
+        for source in &self.sync.removals.authoritative_sources {
+            if !SOURCES.iter().any(|known| known.name == source && !known.snapshot) {
+                return Err(anyhow::anyhow!("Invalid source in sync.removals.authoritative_sources: {}", source));
+            }
+        }
+        
+        for (idx, source) in self.resolution.fetch_order.iter().enumerate() {
+            if !self.resolution.source_preference.contains(source) {
+                return Err(anyhow::anyhow!("Source '{}' is in fetch_order but not in source_preference", source));
+            }
+            if self.resolution.fetch_order[..idx].contains(source) {
+                return Err(anyhow::anyhow!("Source '{}' is listed more than once in fetch_order", source));
+            }
+        }
+        
+        for (field, sources) in self.resolution.preference_overrides() {
+            for (idx, source) in sources.iter().enumerate() {
+                if !self.resolution.source_preference.contains(source) {
+                    return Err(anyhow::anyhow!("Source '{}' is in {} but not in source_preference", source, field));
+                }
+                if sources[..idx].contains(source) {
+                    return Err(anyhow::anyhow!("Source '{}' is listed more than once in {}", source, field));
+                }
+            }
+        }
+        
         for source in &self.resolution.source_preference {
-            if !valid_sources.contains(&source.as_str()) {
+            if !is_known_source(source) {
                 return Err(anyhow::anyhow!("Invalid source in source_preference: {}", source));
             }
             
@@ -375,6 +1235,33 @@ impl Config {
                         return Err(anyhow::anyhow!("Plex is in source_preference but is not enabled"));
                     }
                 }
+                "letterboxd" => {
+                    let letterboxd = self.sources.letterboxd.as_ref()
+                        .ok_or_else(|| anyhow::anyhow!("Letterboxd is in source_preference but is not configured"))?;
+                    if !letterboxd.enabled {
+                        return Err(anyhow::anyhow!("Letterboxd is in source_preference but is not enabled"));
+                    }
+                    if letterboxd.export_path.is_empty() {
+                        return Err(anyhow::anyhow!("Letterboxd is in source_preference but export_path is not configured"));
+                    }
+                }
+                "anilist" => {
+                    let anilist = self.sources.anilist.as_ref()
+                        .ok_or_else(|| anyhow::anyhow!("AniList is in source_preference but is not configured"))?;
+                    if !anilist.enabled {
+                        return Err(anyhow::anyhow!("AniList is in source_preference but is not enabled"));
+                    }
+                }
+                "file" => {
+                    let file = self.sources.file.as_ref()
+                        .ok_or_else(|| anyhow::anyhow!("File is in source_preference but is not configured"))?;
+                    if !file.enabled {
+                        return Err(anyhow::anyhow!("File is in source_preference but is not enabled"));
+                    }
+                    if file.path.is_empty() {
+                        return Err(anyhow::anyhow!("File is in source_preference but path is not configured"));
+                    }
+                }
                 _ => {}
             }
         }
@@ -382,6 +1269,46 @@ impl Config {
         Ok(())
     }
 
+    /// Named credential accounts selected per source, as `(source, account)`
+    pub fn selected_accounts(&self) -> Vec<(&'static str, &str)> {
+        [
+            ("trakt", self.trakt.as_ref().and_then(|trakt| trakt.account.as_deref())),
+            ("simkl", self.simkl.as_ref().and_then(|simkl| simkl.account.as_deref())),
+            ("imdb", self.sources.imdb.as_ref().and_then(|imdb| imdb.account.as_deref())),
+            ("plex", self.sources.plex.as_ref().and_then(|plex| plex.account.as_deref())),
+            ("anilist", self.sources.anilist.as_ref().and_then(|anilist| anilist.account.as_deref())),
+        ]
+        .into_iter()
+        .filter_map(|(source, account)| Some((source, account?)))
+        .collect()
+    }
+
+    /// Media types each source is filtered to (`[sources.media_types]`)
+    pub fn media_type_filters(&self) -> HashMap<String, Vec<MediaTypeFilter>> {
+        self.sources.media_types.clone()
+    }
+
+    /// TMDB API base URL from `[sources.tmdb] base_url`, or the official endpoint
+    pub fn tmdb_base_url(&self) -> &str {
+        self.sources.tmdb.as_ref()
+            .map(|tmdb| tmdb.base_url.as_str())
+            .filter(|url| !url.is_empty())
+            .unwrap_or(DEFAULT_TMDB_BASE_URL)
+    }
+
+    /// TMDB API key from `[sources.tmdb] api_key`, if one is set
+    pub fn tmdb_api_key(&self) -> Option<&str> {
+        self.sources.tmdb.as_ref()
+            .and_then(|tmdb| tmdb.api_key.as_deref())
+            .map(str::trim)
+            .filter(|key| !key.is_empty())
+    }
+
+    /// Whether TMDB is the fallback title lookup (`[sources.tmdb] id_lookup`, on by default)
+    pub fn tmdb_id_lookup(&self) -> bool {
+        self.sources.tmdb.as_ref().is_none_or(|tmdb| tmdb.id_lookup)
+    }
+
     pub fn is_trakt_configured(&self) -> bool {
         if let Some(ref trakt) = self.trakt {
             trakt.enabled
@@ -394,47 +1321,33 @@ impl Config {
         }
     }
 
-    /// Get list of configured and enabled services
-    pub fn get_configured_services(&self) -> Vec<String> {
-        let mut services = Vec::new();
-        
-        // Check Trakt
-        if let Some(ref trakt) = self.trakt {
-            if trakt.enabled 
-                && !trakt.client_id.is_empty() 
-                && trakt.client_id != "YOUR_CLIENT_ID"
-                && !trakt.client_secret.is_empty()
-                && trakt.client_secret != "YOUR_CLIENT_SECRET" {
-                services.push("trakt".to_string());
-            }
-        }
-        
-        // Check Simkl
-        if let Some(simkl) = &self.simkl {
-            if simkl.enabled 
-                && !simkl.client_id.is_empty() 
-                && simkl.client_id != "YOUR_CLIENT_ID"
-                && !simkl.client_secret.is_empty()
-                && simkl.client_secret != "YOUR_CLIENT_SECRET" {
-                services.push("simkl".to_string());
-            }
-        }
-        
-        // Check IMDB
-        if let Some(imdb) = &self.sources.imdb {
-            if imdb.enabled && !imdb.username.is_empty() {
-                services.push("imdb".to_string());
-            }
-        }
-        
-        // Check Plex
-        if let Some(plex) = &self.sources.plex {
-            if plex.enabled && !plex.server_url.is_empty() {
-                services.push("plex".to_string());
-            }
+    /// Whether `source` is enabled and set up well enough to pass `validate`
+    pub fn is_source_configured(&self, source: &str) -> bool {
+        match source {
+            "trakt" => self.is_trakt_configured(),
+            "simkl" => self.simkl.as_ref().is_some_and(|simkl| {
+                simkl.enabled
+                    && !simkl.client_id.is_empty()
+                    && simkl.client_id != "YOUR_CLIENT_ID"
+                    && !simkl.client_secret.is_empty()
+                    && simkl.client_secret != "YOUR_CLIENT_SECRET"
+            }),
+            "imdb" => self.sources.imdb.as_ref().is_some_and(|imdb| imdb.enabled),
+            "plex" => self.sources.plex.as_ref().is_some_and(|plex| plex.enabled),
+            "letterboxd" => self.sources.letterboxd.as_ref().is_some_and(|letterboxd| letterboxd.enabled && !letterboxd.export_path.is_empty()),
+            "anilist" => self.sources.anilist.as_ref().is_some_and(|anilist| anilist.enabled),
+            "file" => self.sources.file.as_ref().is_some_and(|file| file.enabled && !file.path.is_empty()),
+            _ => false,
         }
-        
-        services
+    }
+
+    /// `source_preference` for a config that has none: the configured sources, in
+    /// [`SOURCES`] order
+    pub fn default_source_preference(&self) -> Vec<String> {
+        source_names()
+            .filter(|source| self.is_source_configured(source))
+            .map(str::to_string)
+            .collect()
     }
 }
 
@@ -452,6 +1365,11 @@ mod tests {
                 client_id: "test_id".to_string(),
                 client_secret: "test_secret".to_string(),
                 status_mapping: default_trakt_status_mapping(),
+                likes_as: None,
+                account: None,
+                retry: Default::default(),
+                rate_limit: Default::default(),
+                batch_size: DEFAULT_TRAKT_BATCH_SIZE,
             }),
             simkl: None,
             resolution: ResolutionConfig {
@@ -463,17 +1381,39 @@ mod tests {
                 plex: None,
                 tmdb: None,
                 netflix: None,
+                letterboxd: None,
+                anilist: None,
+                file: None,
+                request_timeout_secs: None,
+                media_types: HashMap::new(),
             },
             sync: SyncOptions {
                 sync_watchlist: true,
                 sync_ratings: true,
                 sync_reviews: false,
                 sync_watch_history: true,
+                sync_lists: false,
+                sync_collection: false,
+                sync_show_progress: false,
+                never_remove: false,
+                collect_timeout_secs: None,
                 remove_watched_from_watchlists: false,
                 mark_rated_as_watched: false,
                 remove_watchlist_items_older_than_days: None,
+                watch_history: WatchHistoryOptions::default(),
+                watchlist: WatchlistOptions::default(),
+                ratings: RatingsOptions::default(),
+                reviews: ReviewsOptions::default(),
+                min_review_length: None,
+                removals: RemovalsOptions::default(),
+                exclude_tags: Vec::new(),
+                lock: SyncLockOptions::default(),
+                hub: HubOptions::default(),
             },
             scheduler: None,
+            credentials: CredentialsConfig::default(),
+            id_matching: IdMatchingConfig::default(),
+            notifications: NotificationsConfig::default(),
         };
 
         let path = file.path().to_path_buf();
@@ -494,6 +1434,11 @@ mod tests {
                 client_id: "YOUR_CLIENT_ID".to_string(),
                 client_secret: "YOUR_CLIENT_SECRET".to_string(),
                 status_mapping: default_trakt_status_mapping(),
+                likes_as: None,
+                account: None,
+                retry: Default::default(),
+                rate_limit: Default::default(),
+                batch_size: DEFAULT_TRAKT_BATCH_SIZE,
             }),
             simkl: None,
             resolution: ResolutionConfig {
@@ -505,17 +1450,39 @@ mod tests {
                 plex: None,
                 tmdb: None,
                 netflix: None,
+                letterboxd: None,
+                anilist: None,
+                file: None,
+                request_timeout_secs: None,
+                media_types: HashMap::new(),
             },
             sync: SyncOptions {
                 sync_watchlist: true,
                 sync_ratings: true,
                 sync_reviews: true,
                 sync_watch_history: true,
+                sync_lists: false,
+                sync_collection: false,
+                sync_show_progress: false,
+                never_remove: false,
+                collect_timeout_secs: None,
                 remove_watched_from_watchlists: false,
                 mark_rated_as_watched: false,
                 remove_watchlist_items_older_than_days: None,
+                watch_history: WatchHistoryOptions::default(),
+                watchlist: WatchlistOptions::default(),
+                ratings: RatingsOptions::default(),
+                reviews: ReviewsOptions::default(),
+                min_review_length: None,
+                removals: RemovalsOptions::default(),
+                exclude_tags: Vec::new(),
+                lock: SyncLockOptions::default(),
+                hub: HubOptions::default(),
             },
             scheduler: None,
+            credentials: CredentialsConfig::default(),
+            id_matching: IdMatchingConfig::default(),
+            notifications: NotificationsConfig::default(),
         };
 
         assert!(config.validate().is_err());
@@ -529,9 +1496,91 @@ mod tests {
                 to_normalized: std::collections::HashMap::new(),
                 from_normalized: std::collections::HashMap::new(),
             },
+            likes_as: None,
+            account: None,
+            retry: Default::default(),
+            rate_limit: Default::default(),
+            batch_size: DEFAULT_TRAKT_BATCH_SIZE,
         });
         assert!(config.validate().is_ok());
         assert!(config.is_trakt_configured());
+
+        // A missing preference is derived from the enabled sources
+        config.resolution.source_preference = Vec::new();
+        assert!(config.validate().is_err());
+        config.resolution.source_preference = config.default_source_preference();
+        assert_eq!(config.resolution.source_preference, vec!["trakt"]);
+        assert!(config.validate().is_ok());
+
+        config.sync.watchlist.max_items_to_add_per_run = Some(0);
+        assert!(config.validate().is_err());
+        config.sync.watchlist.max_items_to_add_per_run = Some(500);
+        assert!(config.validate().is_ok());
+
+        config.resolution.fetch_order = vec!["plex".to_string()];
+        assert!(config.validate().is_err());
+        config.resolution.fetch_order = vec!["trakt".to_string(), "trakt".to_string()];
+        assert!(config.validate().is_err());
+        config.resolution.fetch_order = vec!["trakt".to_string()];
+        assert!(config.validate().is_ok());
+
+        config.sync.removals.authoritative_sources = vec!["letterboxd".to_string()];
+        assert!(config.validate().is_err());
+        config.sync.removals.authoritative_sources = vec!["trakt".to_string()];
+        assert!(config.validate().is_ok());
+
+        // Not a removal authority, but a source like any other
+        config.resolution.source_preference.push("letterboxd".to_string());
+        assert!(config.validate().is_err());
+        config.sources.letterboxd = Some(LetterboxdConfig { enabled: true, export_path: "/config/letterboxd".to_string() });
+        assert!(config.validate().is_ok());
+
+        config.resolution.source_preference.push("file".to_string());
+        assert!(config.validate().is_err());
+        config.sources.file = Some(FileConfig { enabled: true, path: "/config/backup".to_string() });
+        assert!(config.validate().is_ok());
+        config.resolution.source_preference.push("anilist".to_string());
+        assert!(config.validate().is_err());
+        config.sources.anilist = Some(AniListConfig {
+            enabled: true,
+            username: "someone".to_string(),
+            score_scale: 7,
+            account: None,
+        });
+        assert!(config.validate().is_err());
+        config.sources.anilist.as_mut().unwrap().score_scale = 10;
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_collection_order() {
+        let mut resolution = ResolutionConfig {
+            source_preference: vec!["imdb".to_string(), "trakt".to_string(), "plex".to_string()],
+            ..ResolutionConfig::default()
+        };
+        assert_eq!(resolution.collection_order(), resolution.source_preference);
+
+        resolution.fetch_order = vec!["plex".to_string()];
+        assert_eq!(resolution.collection_order(), vec!["plex", "imdb", "trakt"]);
+
+        assert_eq!(resolution.preference_for("watch_history"), resolution.source_preference);
+        resolution.watch_history_preference = vec!["trakt".to_string()];
+        assert_eq!(resolution.preference_for("watch_history"), vec!["trakt", "imdb", "plex"]);
+        assert_eq!(resolution.preference_for("ratings"), resolution.source_preference);
+    }
+
+    #[test]
+    fn test_retain_sources() {
+        let mut resolution = ResolutionConfig {
+            source_preference: vec!["imdb".to_string(), "trakt".to_string(), "plex".to_string()],
+            fetch_order: vec!["plex".to_string()],
+            ratings_preference: vec!["plex".to_string(), "imdb".to_string()],
+            ..ResolutionConfig::default()
+        };
+        resolution.retain_sources(|source| source != "plex");
+        assert_eq!(resolution.source_preference, vec!["imdb", "trakt"]);
+        assert!(resolution.fetch_order.is_empty());
+        assert_eq!(resolution.ratings_preference, vec!["imdb"]);
     }
 
     #[test]
@@ -541,17 +1590,178 @@ mod tests {
             sync_ratings: true,
             sync_reviews: true,
             sync_watch_history: true,
+            sync_lists: false,
+            sync_collection: false,
+            sync_show_progress: false,
+            never_remove: false,
+            collect_timeout_secs: None,
             remove_watched_from_watchlists: false,
             mark_rated_as_watched: false,
             remove_watchlist_items_older_than_days: None,
+            watch_history: WatchHistoryOptions::default(),
+            watchlist: WatchlistOptions::default(),
+            ratings: RatingsOptions::default(),
+            reviews: ReviewsOptions::default(),
+            min_review_length: None,
+            removals: RemovalsOptions::default(),
+            exclude_tags: Vec::new(),
+            lock: SyncLockOptions::default(),
+            hub: HubOptions::default(),
         };
         assert_eq!(options.sync_watchlist, true);
         assert_eq!(options.sync_ratings, true);
         assert_eq!(options.sync_reviews, true);
         assert_eq!(options.sync_watch_history, true);
         assert_eq!(options.remove_watched_from_watchlists, false);
+        assert_eq!(options.ratings.default_date, DefaultRatingDate::SourceImportTime);
         assert_eq!(options.mark_rated_as_watched, false);
         assert_eq!(options.remove_watchlist_items_older_than_days, None);
+        assert_eq!(options.from_ratings_mode(), FromRatingsMode::Off);
+        assert_eq!(options.min_review_length("imdb"), Some(DEFAULT_MIN_REVIEW_LENGTH));
+        assert_eq!(options.min_review_length("simkl"), None);
+        let options = SyncOptions { min_review_length: Some(20), ..options };
+        assert_eq!((options.min_review_length("imdb"), options.min_review_length("simkl")), (Some(20), Some(20)));
+        assert_eq!(SyncOptions { min_review_length: Some(0), ..options }.min_review_length("imdb"), None);
+    }
+
+    #[test]
+    fn test_from_ratings_mode_legacy_fallback() {
+        let mut options: SyncOptions = toml::from_str("mark_rated_as_watched = true").unwrap();
+        assert_eq!(options.from_ratings_mode(), FromRatingsMode::AllNonShow);
+
+        options.mark_rated_as_watched = false;
+        assert_eq!(options.from_ratings_mode(), FromRatingsMode::Off);
+
+        // Explicit setting overrides the legacy flag
+        options.mark_rated_as_watched = true;
+        options.watch_history.from_ratings = Some(FromRatingsMode::Off);
+        assert_eq!(options.from_ratings_mode(), FromRatingsMode::Off);
+    }
+
+    #[test]
+    fn test_tmdb_base_url() {
+        let sources: SourceConfig = toml::from_str("").unwrap();
+        assert!(sources.tmdb.is_none());
+
+        let sources: SourceConfig = toml::from_str("[tmdb]").unwrap();
+        assert_eq!(sources.tmdb.unwrap().base_url, DEFAULT_TMDB_BASE_URL);
+
+        let sources: SourceConfig = toml::from_str("[tmdb]\nbase_url = \"https://tmdb.example.org/3\"").unwrap();
+        let tmdb = sources.tmdb.unwrap();
+        assert!(!tmdb.enabled);
+        assert_eq!(tmdb.base_url, "https://tmdb.example.org/3");
+        assert!(tmdb.id_lookup);
+        assert_eq!(tmdb.api_key, None);
+
+        let sources: SourceConfig = toml::from_str("[tmdb]\napi_key = \"abc123\"\nid_lookup = false").unwrap();
+        let tmdb = sources.tmdb.unwrap();
+        assert_eq!(tmdb.api_key.as_deref(), Some("abc123"));
+        assert!(!tmdb.id_lookup);
+    }
+
+    #[test]
+    fn test_ratings_no_cross_sync_parsing() {
+        let options: SyncOptions = toml::from_str("").unwrap();
+        assert!(!options.ratings.no_cross_sync);
+
+        let options: SyncOptions = toml::from_str("[ratings]\nno_cross_sync = true").unwrap();
+        assert!(options.ratings.no_cross_sync);
+    }
+
+    #[test]
+    fn test_reviews_strip_emoji_targets() {
+        let options: SyncOptions = toml::from_str("").unwrap();
+        assert!(!options.reviews.strips_emoji_for("imdb"));
+
+        let options: SyncOptions = toml::from_str("[reviews]\nstrip_emoji_for = [\"imdb\"]").unwrap();
+        assert!(options.reviews.strips_emoji_for("IMDB"));
+        assert!(!options.reviews.strips_emoji_for("trakt"));
+
+        let options: SyncOptions = toml::from_str("[reviews]\nstrip_emoji = true").unwrap();
+        assert!(options.reviews.strips_emoji_for("trakt"));
+        assert_eq!(options.reviews.indexing_grace_minutes, 15);
+    }
+
+    #[test]
+    fn test_sync_window_wraps_midnight() {
+        let at = |h, m| chrono::NaiveDate::from_ymd_opt(2024, 3, 1).unwrap().and_hms_opt(h, m, 0).unwrap();
+
+        let night = SyncWindow::parse("01:00-06:00").unwrap();
+        assert_eq!(night.wait_from(at(3, 0)), chrono::Duration::zero());
+        assert_eq!(night.wait_from(at(0, 30)), chrono::Duration::minutes(30));
+        // 06:00 is past the window, so the next run is tomorrow at 01:00
+        assert_eq!(night.wait_from(at(6, 0)), chrono::Duration::hours(19));
+
+        let late = SyncWindow::parse("22:00 - 02:00").unwrap();
+        assert!(late.contains(at(23, 0).time()) && late.contains(at(1, 59).time()));
+        assert_eq!(late.wait_from(at(12, 0)), chrono::Duration::hours(10));
+
+        assert!(SyncWindow::parse("1am-6am").is_err());
+        assert!(SyncWindow::parse("05:00-05:00").is_err());
+    }
+
+    #[test]
+    fn test_scheduler_time_zone() {
+        let mut scheduler = default_scheduler_config();
+        scheduler.timezone = "America/New_York".to_string();
+        assert_eq!(scheduler.time_zone().unwrap(), chrono_tz::America::New_York);
+
+        scheduler.timezone = "Mars/Olympus_Mons".to_string();
+        assert!(scheduler.time_zone().is_err());
+    }
+
+    #[test]
+    fn test_removals_authoritative_sources() {
+        let options: SyncOptions = toml::from_str("").unwrap();
+        assert_eq!(options.removals.authoritative_sources, vec!["simkl"]);
+
+        let options: SyncOptions = toml::from_str("[removals]\nauthoritative_sources = [\"trakt\"]").unwrap();
+        assert_eq!(options.removals.authoritative_sources, vec!["trakt"]);
+    }
+
+    #[test]
+    fn test_lock_options_parsing() {
+        let options: SyncOptions = toml::from_str("").unwrap();
+        assert_eq!(options.lock.on_conflict, LockConflict::Exit);
+
+        let options: SyncOptions = toml::from_str("[lock]\non_conflict = \"wait\"\nwait_timeout_seconds = 600").unwrap();
+        assert_eq!(options.lock.on_conflict, LockConflict::Wait);
+        assert_eq!(options.lock.wait_timeout_seconds, Some(600));
+    }
+
+    #[test]
+    fn test_from_ratings_mode_parsing() {
+        let options: SyncOptions = toml::from_str("[watch_history]\nfrom_ratings = \"movies-only\"").unwrap();
+        assert_eq!(options.from_ratings_mode(), FromRatingsMode::MoviesOnly);
+
+        let options: SyncOptions = toml::from_str("[watch_history]\nfrom_ratings = \"all-non-show\"").unwrap();
+        assert_eq!(options.from_ratings_mode(), FromRatingsMode::AllNonShow);
+
+        let options: SyncOptions = toml::from_str("[watch_history]\nfrom_ratings = \"off\"").unwrap();
+        assert_eq!(options.from_ratings_mode(), FromRatingsMode::Off);
+        assert!(!options.watch_history.sync_progress);
+
+        let options: SyncOptions = toml::from_str("[watch_history]\nsync_progress = true").unwrap();
+        assert!(options.watch_history.sync_progress);
+    }
+
+    #[test]
+    fn test_from_ratings_mode_applies_to() {
+        use media_sync_models::MediaType;
+        let episode = MediaType::Episode { season: 1, episode: 2 };
+
+        assert!(!FromRatingsMode::Off.applies_to(&MediaType::Movie));
+        assert!(!FromRatingsMode::Off.applies_to(&episode));
+        assert!(!FromRatingsMode::Off.applies_to(&MediaType::Show));
+
+        assert!(FromRatingsMode::MoviesOnly.applies_to(&MediaType::Movie));
+        assert!(!FromRatingsMode::MoviesOnly.applies_to(&episode));
+        assert!(!FromRatingsMode::MoviesOnly.applies_to(&MediaType::Show));
+
+        assert!(FromRatingsMode::AllNonShow.applies_to(&MediaType::Movie));
+        assert!(FromRatingsMode::AllNonShow.applies_to(&episode));
+        assert!(!FromRatingsMode::AllNonShow.applies_to(&MediaType::Show));
+        assert!(!FromRatingsMode::AllNonShow.applies_to(&MediaType::Episode { season: 0, episode: 0 }));
     }
 }
 
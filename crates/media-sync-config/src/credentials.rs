@@ -1,60 +1,304 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use toml;
 
+use crate::config::{CredentialBackendKind, CredentialsConfig};
+
+/// Keys holding secrets; the keyring backend keeps these in the OS keychain and
+/// `config export` leaves them out unless asked to include them
+pub(crate) const SECRET_KEYS: [&str; 7] = [
+    "imdb_password",
+    "trakt_access_token",
+    "trakt_refresh_token",
+    "simkl_access_token",
+    "simkl_refresh_token",
+    "plex_token",
+    "anilist_access_token",
+];
+
+/// Whether `key` holds a secret, for the default account or a named one (`key@account`)
+#[cfg(feature = "keyring")]
+fn is_secret_key(key: &str) -> bool {
+    let base = key.split_once('@').map_or(key, |(base, _)| base);
+    SECRET_KEYS.contains(&base)
+}
+
+/// Service name the keyring backend stores entries under
+#[cfg(feature = "keyring")]
+const KEYRING_SERVICE: &str = "totalrecall";
+
 #[derive(Debug, Serialize, Deserialize, Default)]
 struct CredentialsData {
     #[serde(flatten)]
     data: HashMap<String, String>,
 }
 
-pub struct CredentialStore {
+/// Where a [`CredentialStore`] keeps its values at rest
+pub trait CredentialBackend: Send + Sync {
+    fn load(&self) -> Result<HashMap<String, String>>;
+    fn save(&self, credentials: &HashMap<String, String>) -> Result<()>;
+}
+
+/// Everything in `credentials.toml` (the default)
+pub struct FileBackend {
     path: PathBuf,
-    credentials: HashMap<String, String>,
 }
 
-impl CredentialStore {
+impl FileBackend {
     pub fn new(path: PathBuf) -> Self {
-        Self {
-            path,
-            credentials: HashMap::new(),
-        }
+        Self { path }
     }
+}
 
-    pub fn load(&mut self) -> Result<()> {
-        if self.path.exists() {
-            let content = std::fs::read_to_string(&self.path)?;
-            let creds_data: CredentialsData = toml::from_str(&content)?;
-            self.credentials = creds_data.data;
+impl CredentialBackend for FileBackend {
+    fn load(&self) -> Result<HashMap<String, String>> {
+        if !self.path.exists() {
+            return Ok(HashMap::new());
         }
-        Ok(())
+        let content = std::fs::read_to_string(&self.path)?;
+        let creds_data: CredentialsData = toml::from_str(&content)?;
+        Ok(creds_data.data)
     }
 
-    pub fn save(&self) -> Result<()> {
+    fn save(&self, credentials: &HashMap<String, String>) -> Result<()> {
         if let Some(parent) = self.path.parent() {
             std::fs::create_dir_all(parent)?;
         }
         let creds_data = CredentialsData {
-            data: self.credentials.clone(),
+            data: credentials.clone(),
         };
         let content = toml::to_string_pretty(&creds_data)?;
         std::fs::write(&self.path, content)?;
         Ok(())
     }
+}
+
+/// Tokens and passwords in the OS keychain (Secret Service, macOS Keychain or Windows
+/// Credential Manager); sync timestamps and other state stay in `credentials.toml`.
+/// Secrets still in the file from the file backend are used and moved to the keychain
+/// on the next save.
+pub struct KeyringBackend {
+    file: FileBackend,
+    /// Secret keys kept in the keychain: the defaults and those of the accounts in use
+    #[cfg_attr(not(feature = "keyring"), allow(dead_code))]
+    secret_keys: Vec<String>,
+}
+
+impl KeyringBackend {
+    pub fn new(path: PathBuf) -> Self {
+        let accounts = configured_accounts(&path);
+        let mut secret_keys: Vec<String> = SECRET_KEYS.iter().map(|key| key.to_string()).collect();
+        for (source, account) in &accounts {
+            secret_keys.extend(SECRET_KEYS.iter()
+                .filter(|key| key.starts_with(&format!("{}_", source)))
+                .map(|key| format!("{}@{}", key, account)));
+        }
+        Self { file: FileBackend::new(path), secret_keys }
+    }
+}
+
+#[cfg(feature = "keyring")]
+impl CredentialBackend for KeyringBackend {
+    fn load(&self) -> Result<HashMap<String, String>> {
+        let mut credentials = self.file.load()?;
+        for key in &self.secret_keys {
+            let entry = keyring::Entry::new(KEYRING_SERVICE, key)
+                .map_err(|e| anyhow!("Failed to open keychain entry {}: {}", key, e))?;
+            match entry.get_password() {
+                Ok(value) => {
+                    credentials.insert(key.to_string(), value);
+                }
+                Err(keyring::Error::NoEntry) => {}
+                Err(e) => return Err(anyhow!("Failed to read {} from the keychain: {}", key, e)),
+            }
+        }
+        Ok(credentials)
+    }
+
+    fn save(&self, credentials: &HashMap<String, String>) -> Result<()> {
+        for key in &self.secret_keys {
+            let entry = keyring::Entry::new(KEYRING_SERVICE, key)
+                .map_err(|e| anyhow!("Failed to open keychain entry {}: {}", key, e))?;
+            let result = match credentials.get(key) {
+                Some(value) => entry.set_password(value),
+                None => match entry.delete_credential() {
+                    Err(keyring::Error::NoEntry) => Ok(()),
+                    other => other,
+                },
+            };
+            result.map_err(|e| anyhow!("Failed to write {} to the keychain: {}", key, e))?;
+        }
+        let file_only = credentials.iter()
+            .filter(|(key, _)| !is_secret_key(key))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        self.file.save(&file_only)
+    }
+}
+
+#[cfg(not(feature = "keyring"))]
+impl CredentialBackend for KeyringBackend {
+    fn load(&self) -> Result<HashMap<String, String>> {
+        Err(self.unavailable())
+    }
+
+    fn save(&self, _credentials: &HashMap<String, String>) -> Result<()> {
+        Err(self.unavailable())
+    }
+}
+
+#[cfg(not(feature = "keyring"))]
+impl KeyringBackend {
+    fn unavailable(&self) -> anyhow::Error {
+        anyhow!(
+            "credentials.backend = \"keyring\" (set next to {}) needs totalrecall built with the `keyring` feature",
+            self.file.path.display()
+        )
+    }
+}
+
+/// The parts of the config.toml next to the credentials file that the store reads
+#[derive(Deserialize, Default)]
+struct SiblingConfig {
+    #[serde(default)]
+    credentials: CredentialsConfig,
+    #[serde(default)]
+    trakt: Option<AccountSelection>,
+    #[serde(default)]
+    simkl: Option<AccountSelection>,
+    #[serde(default)]
+    sources: SiblingSources,
+}
+
+#[derive(Deserialize, Default)]
+struct SiblingSources {
+    #[serde(default)]
+    imdb: Option<AccountSelection>,
+    #[serde(default)]
+    plex: Option<AccountSelection>,
+    #[serde(default)]
+    anilist: Option<AccountSelection>,
+}
+
+#[derive(Deserialize)]
+struct AccountSelection {
+    #[serde(default)]
+    account: Option<String>,
+}
+
+fn sibling_config(credentials_file: &Path) -> SiblingConfig {
+    std::fs::read_to_string(credentials_file.with_file_name("config.toml")).ok()
+        .and_then(|content| toml::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// `[credentials] backend` from the config.toml next to the credentials file
+fn configured_backend(credentials_file: &Path) -> CredentialBackendKind {
+    sibling_config(credentials_file).credentials.backend
+}
+
+/// Named accounts selected in the config.toml next to the credentials file, by source
+fn configured_accounts(credentials_file: &Path) -> HashMap<String, String> {
+    let config = sibling_config(credentials_file);
+    [
+        ("trakt", config.trakt),
+        ("simkl", config.simkl),
+        ("imdb", config.sources.imdb),
+        ("plex", config.sources.plex),
+        ("anilist", config.sources.anilist),
+    ]
+    .into_iter()
+    .filter_map(|(source, selection)| Some((source.to_string(), selection?.account?)))
+    .collect()
+}
+
+/// Credentials and sync state, by key. A source can use a named account (`trakt.account`
+/// in config.toml): its keys (those starting with `<source>_`) are then stored as
+/// `<key>@<account>`, so several accounts' tokens live side by side and each keeps its
+/// own sync timestamps.
+pub struct CredentialStore {
+    backend: Box<dyn CredentialBackend>,
+    credentials: HashMap<String, String>,
+    /// Named account in use, by source; sources without one use the unsuffixed keys
+    accounts: HashMap<String, String>,
+}
+
+impl CredentialStore {
+    /// Store backed by `path`, using the backend selected in the config.toml beside it
+    pub fn new(path: PathBuf) -> Self {
+        let accounts = configured_accounts(&path);
+        let backend: Box<dyn CredentialBackend> = match configured_backend(&path) {
+            CredentialBackendKind::File => Box::new(FileBackend::new(path)),
+            CredentialBackendKind::Keyring => Box::new(KeyringBackend::new(path)),
+        };
+        Self { accounts, ..Self::with_backend(backend) }
+    }
+
+    pub fn with_backend(backend: Box<dyn CredentialBackend>) -> Self {
+        Self {
+            backend,
+            credentials: HashMap::new(),
+            accounts: HashMap::new(),
+        }
+    }
+
+    /// Use the named `account` for `source`'s keys, or the default account with `None`
+    pub fn select_account(&mut self, source: &str, account: Option<String>) {
+        match account {
+            Some(account) => self.accounts.insert(source.to_string(), account),
+            None => self.accounts.remove(source),
+        };
+    }
+
+    /// Fails when `source` uses a named account that has nothing stored yet, which is
+    /// a typo in config.toml or an account that was never signed in to
+    pub fn check_account(&self, source: &str) -> Result<()> {
+        let Some(account) = self.accounts.get(source) else {
+            return Ok(());
+        };
+        let prefix = format!("{}_", source);
+        let suffix = format!("@{}", account);
+        if self.credentials.keys().any(|key| key.starts_with(&prefix) && key.ends_with(&suffix)) {
+            return Ok(());
+        }
+        Err(anyhow!(
+            "No credentials for {} account '{}'. Run 'totalrecall config {} --account {}' to sign in to it",
+            source, account, source, account
+        ))
+    }
+
+    /// `key` as stored, suffixed with the account its source uses
+    fn stored_key(&self, key: &str) -> String {
+        if !key.contains('@') {
+            if let Some((_, account)) = self.accounts.iter().find(|(source, _)| key.starts_with(&format!("{}_", source))) {
+                return format!("{}@{}", key, account);
+            }
+        }
+        key.to_string()
+    }
+
+    pub fn load(&mut self) -> Result<()> {
+        self.credentials = self.backend.load()?;
+        Ok(())
+    }
+
+    pub fn save(&self) -> Result<()> {
+        self.backend.save(&self.credentials)
+    }
 
     pub fn get(&self, key: &str) -> Option<&String> {
-        self.credentials.get(key)
+        self.credentials.get(&self.stored_key(key))
     }
 
     pub fn set(&mut self, key: String, value: String) {
-        self.credentials.insert(key, value);
+        self.credentials.insert(self.stored_key(&key), value);
     }
 
     pub fn remove(&mut self, key: &str) {
-        self.credentials.remove(key);
+        self.credentials.remove(&self.stored_key(key));
     }
 
     // Convenience methods for specific credentials
@@ -141,6 +385,14 @@ impl CredentialStore {
         self.set("plex_token".to_string(), token);
     }
 
+    pub fn get_anilist_access_token(&self) -> Option<&String> {
+        self.get("anilist_access_token")
+    }
+
+    pub fn set_anilist_access_token(&mut self, token: String) {
+        self.set("anilist_access_token".to_string(), token);
+    }
+
     // Generic timestamp storage methods
     pub fn get_last_sync_timestamp(&self, source: &str, data_type: &str) -> Option<DateTime<Utc>> {
         let key = format!("{}_last_sync_{}", source, data_type);
@@ -154,19 +406,27 @@ impl CredentialStore {
         self.set(key, timestamp.to_rfc3339());
     }
 
-    // Simkl-specific: Store full activities JSON for comparison
-    pub fn get_simkl_last_activities(&self) -> Option<String> {
-        self.get("simkl_last_activities").cloned()
+    // Simkl-specific: activities JSON as of the last successful fetch of each data type
+    pub fn get_simkl_last_activities(&self, data_type: &str) -> Option<String> {
+        self.get(&format!("simkl_last_activities_{}", data_type)).cloned()
     }
 
-    pub fn set_simkl_last_activities(&mut self, activities_json: String) {
-        self.set("simkl_last_activities".to_string(), activities_json);
+    pub fn set_simkl_last_activities(&mut self, data_type: &str, activities_json: String) {
+        self.set(format!("simkl_last_activities_{}", data_type), activities_json);
     }
 
     // Helper method to get all keys (for clearing timestamps)
     pub fn get_all_keys(&self) -> Vec<String> {
         self.credentials.keys().cloned().collect()
     }
+
+    /// Remove the keys, as stored and across all accounts, that match `filter`;
+    /// returns how many were removed
+    pub fn remove_matching(&mut self, filter: impl Fn(&str) -> bool) -> usize {
+        let before = self.credentials.len();
+        self.credentials.retain(|key, _| !filter(key));
+        before - self.credentials.len()
+    }
 }
 
 #[cfg(test)]
@@ -207,6 +467,48 @@ mod tests {
         assert!((loaded_expires - expires).num_seconds().abs() < 2);
     }
 
+    #[test]
+    fn test_backend_is_selected_by_sibling_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let credentials_file = dir.path().join("credentials.toml");
+        assert_eq!(configured_backend(&credentials_file), CredentialBackendKind::File);
+
+        std::fs::write(dir.path().join("config.toml"), "[credentials]\nbackend = \"keyring\"\n").unwrap();
+        assert_eq!(configured_backend(&credentials_file), CredentialBackendKind::Keyring);
+    }
+
+    #[test]
+    fn test_named_accounts_are_stored_side_by_side() {
+        let dir = tempfile::tempdir().unwrap();
+        let credentials_file = dir.path().join("credentials.toml");
+        let mut store = CredentialStore::new(credentials_file.clone());
+        store.set_trakt_access_token("mine".to_string());
+        store.set_plex_token("plex".to_string());
+        store.save().unwrap();
+
+        std::fs::write(dir.path().join("config.toml"), "[trakt]\nenabled = true\naccount = \"family\"\n").unwrap();
+        let mut store = CredentialStore::new(credentials_file.clone());
+        store.load().unwrap();
+        assert_eq!(store.get_trakt_access_token(), None);
+        assert!(store.check_account("trakt").is_err());
+        // Other sources keep the default account
+        assert_eq!(store.get_plex_token(), Some(&"plex".to_string()));
+        assert!(store.check_account("plex").is_ok());
+
+        store.set_trakt_access_token("family".to_string());
+        store.set_last_sync_timestamp("trakt", "ratings", Utc::now());
+        store.save().unwrap();
+        let mut store = CredentialStore::new(credentials_file);
+        store.load().unwrap();
+        assert!(store.check_account("trakt").is_ok());
+        assert_eq!(store.get_trakt_access_token(), Some(&"family".to_string()));
+        assert_eq!(store.get("trakt_access_token@family"), Some(&"family".to_string()));
+
+        store.select_account("trakt", None);
+        assert_eq!(store.get_trakt_access_token(), Some(&"mine".to_string()));
+        assert_eq!(store.remove_matching(|key| key.contains("_last_sync_")), 1);
+    }
+
     #[test]
     fn test_credential_store_remove() {
         let mut store = CredentialStore::new(PathBuf::from("/tmp/test"));
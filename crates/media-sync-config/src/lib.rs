@@ -1,7 +1,9 @@
 pub mod config;
 pub mod credentials;
 pub mod paths;
+pub mod portable;
 
-pub use config::{Config, ImdbConfig, PlexConfig, ResolutionConfig, ResolutionStrategy, SchedulerConfig, SimklConfig, SourceConfig, StatusMapping, SyncOptions, TraktConfig, default_imdb_status_mapping, default_plex_status_mapping, default_scheduler_config, default_simkl_status_mapping, default_trakt_status_mapping};
-pub use credentials::CredentialStore;
+pub use config::{AniListConfig, Config, CredentialBackendKind, CredentialsConfig, DefaultRatingDate, FileConfig, FromRatingsMode, HubOptions, IdMatchingConfig, ImdbConfig, LetterboxdConfig, LockConflict, MediaTypeFilter, NotificationsConfig, PlexConfig, RateLimitConfig, RatingScales, RatingsOptions, RemovalsOptions, ResolutionConfig, ReviewsOptions, ResolutionStrategy, RetryConfig, SchedulerConfig, SimklConfig, SourceConfig, StatusMapping, SyncLockOptions, SyncOptions, SyncWindow, TraktConfig, TraktLikesAs, WatchHistoryOptions, WatchlistOptions, DEFAULT_ID_CACHE_TTL_DAYS, DEFAULT_MIN_REVIEW_LENGTH, DEFAULT_TMDB_BASE_URL, DEFAULT_TRAKT_BATCH_SIZE, HUB_SOURCE, SOURCES, SourceInfo, default_imdb_status_mapping, default_plex_status_mapping, default_scheduler_config, default_simkl_status_mapping, default_trakt_status_mapping, is_known_source, is_valid_account_name, source_names};
+pub use credentials::{CredentialBackend, CredentialStore, FileBackend, KeyringBackend};
 pub use paths::{PathManager, container_base_path};
+pub use portable::{BundleSecrets, ConfigBundle, SecretsExport};
@@ -9,6 +9,7 @@ pub fn container_base_path() -> PathBuf {
         .unwrap_or_else(|_| PathBuf::from("/app"))
 }
 
+#[derive(Debug, Clone)]
 pub struct PathManager {
     config_dir: PathBuf,
     data_dir: PathBuf,
@@ -29,10 +30,15 @@ impl PathManager {
     }
 
     pub fn from_docker_env() -> Self {
-        let base = container_base_path();
         // In containers, match the default structure: config files at base level, data/logs in subdirs
+        Self::with_base_dir(container_base_path())
+    }
+
+    /// Everything under `base`: config files directly in it, data and logs in subdirs
+    pub fn with_base_dir(base: impl Into<PathBuf>) -> Self {
+        let base = base.into();
         Self {
-            config_dir: base.clone(),  // Config files go directly in base path
+            config_dir: base.clone(),
             data_dir: base.join("data"),
             log_dir: base.join("logs"),
         }
@@ -62,10 +68,39 @@ impl PathManager {
         self.cache_dir().join("distribute")
     }
 
+    pub fn cache_resolve_dir(&self) -> PathBuf {
+        self.cache_dir().join("resolve")
+    }
+
     pub fn cache_id_dir(&self) -> PathBuf {
         self.cache_dir().join("id")
     }
 
+    /// ETags and bodies of source API responses, for conditional GETs
+    pub fn cache_http_dir(&self) -> PathBuf {
+        self.cache_dir().join("http")
+    }
+
+    /// Links between reviews and the copies a sync wrote to other sources
+    pub fn cache_review_links_dir(&self) -> PathBuf {
+        self.cache_dir().join("review_links")
+    }
+
+    /// Summary of the daemon's latest run, for monitoring
+    pub fn cache_metrics_file(&self) -> PathBuf {
+        self.cache_dir().join("metrics.json")
+    }
+
+    /// Conflict report written by `sync --report-conflicts`
+    pub fn cache_conflicts_file(&self) -> PathBuf {
+        self.cache_resolve_dir().join("conflicts.json")
+    }
+
+    /// Writes that failed in earlier runs, for `sync --retry-failures`
+    pub fn cache_failures_file(&self) -> PathBuf {
+        self.cache_distribute_dir().join("failures.json")
+    }
+
     pub fn cache_csv_dir(&self, source: &str) -> PathBuf {
         self.cache_dir().join("csv").join(source)
     }
@@ -82,6 +117,11 @@ impl PathManager {
         self.log_dir.join("totalrecall.log")
     }
 
+    /// Held while a sync runs, so overlapping runs don't share the cache
+    pub fn sync_lock_file(&self) -> PathBuf {
+        self.data_dir.join("sync.lock")
+    }
+
     pub fn ensure_directories(&self) -> Result<()> {
         std::fs::create_dir_all(&self.config_dir)?;
         std::fs::create_dir_all(&self.data_dir)?;
@@ -0,0 +1,262 @@
+//! Portable config bundles for `totalrecall config export` and `config import`.
+//!
+//! A bundle is a TOML file holding the full `config.toml` under `[config]` and,
+//! optionally, the secrets under `[secrets]`: the Trakt and Simkl client secrets and the
+//! tokens and IMDB password from the credential store. Without secrets the client
+//! secrets in `[config]` are blanked, which makes the file safe to share for support.
+//! Secrets are either plaintext, flagged as such in the file, or encrypted with a
+//! passphrase (PBKDF2-HMAC-SHA256 key, AES-256-GCM).
+
+use anyhow::{anyhow, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::num::NonZeroU32;
+use std::path::Path;
+
+use crate::config::Config;
+use crate::credentials::{CredentialStore, SECRET_KEYS};
+
+/// Bundle format written by this version
+pub const BUNDLE_VERSION: u32 = 1;
+
+#[cfg(not(test))]
+const KDF_ITERATIONS: u32 = 600_000;
+/// Keeps the tests fast; the iteration count is stored in each bundle
+#[cfg(test)]
+const KDF_ITERATIONS: u32 = 1_000;
+const SALT_LEN: usize = 16;
+const TRAKT_CLIENT_SECRET: &str = "trakt.client_secret";
+const SIMKL_CLIENT_SECRET: &str = "simkl.client_secret";
+
+/// What `config export` does with secrets
+pub enum SecretsExport<'a> {
+    Exclude,
+    Plaintext,
+    /// Encrypted with this passphrase
+    Encrypted(&'a str),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConfigBundle {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secrets: Option<BundleSecrets>,
+    pub config: Config,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum BundleSecrets {
+    Plaintext {
+        warning: String,
+        values: BTreeMap<String, String>,
+    },
+    /// `values` as TOML, sealed with a key derived from the passphrase (all base64)
+    Encrypted {
+        kdf_iterations: u32,
+        salt: String,
+        nonce: String,
+        ciphertext: String,
+    },
+}
+
+impl ConfigBundle {
+    /// Bundle `config`, taking secrets from it and from `credentials` unless excluded
+    pub fn export(config: &Config, credentials: &CredentialStore, secrets: SecretsExport) -> Result<Self> {
+        // Config isn't Clone; a TOML round trip copies exactly what config.toml would hold
+        let mut config: Config = toml::from_str(&toml::to_string(config)?)?;
+        let mut values = BTreeMap::new();
+        if let Some(trakt) = config.trakt.as_mut() {
+            values.insert(TRAKT_CLIENT_SECRET.to_string(), std::mem::take(&mut trakt.client_secret));
+        }
+        if let Some(simkl) = config.simkl.as_mut() {
+            values.insert(SIMKL_CLIENT_SECRET.to_string(), std::mem::take(&mut simkl.client_secret));
+        }
+        for key in SECRET_KEYS {
+            if let Some(value) = credentials.get(key) {
+                values.insert(key.to_string(), value.clone());
+            }
+        }
+        values.retain(|_, value| !value.is_empty());
+
+        let secrets = match secrets {
+            SecretsExport::Exclude => None,
+            SecretsExport::Plaintext => Some(BundleSecrets::Plaintext {
+                warning: "These secrets are not encrypted. Anyone with this file can use your accounts.".to_string(),
+                values,
+            }),
+            SecretsExport::Encrypted(passphrase) => Some(encrypt(&values, passphrase)?),
+        };
+        Ok(Self {
+            version: BUNDLE_VERSION,
+            exported_at: Utc::now(),
+            secrets,
+            config,
+        })
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        matches!(self.secrets, Some(BundleSecrets::Encrypted { .. }))
+    }
+
+    /// Split into the config, with client secrets restored, and the credential store
+    /// entries to import. `passphrase` is required for encrypted secrets.
+    pub fn into_parts(self, passphrase: Option<&str>) -> Result<(Config, BTreeMap<String, String>)> {
+        if self.version > BUNDLE_VERSION {
+            return Err(anyhow!(
+                "Bundle format version {} is newer than this TotalRecall supports ({})",
+                self.version,
+                BUNDLE_VERSION
+            ));
+        }
+        let mut values = match self.secrets {
+            None => BTreeMap::new(),
+            Some(BundleSecrets::Plaintext { values, .. }) => values,
+            Some(encrypted @ BundleSecrets::Encrypted { .. }) => {
+                let passphrase = passphrase.ok_or_else(|| anyhow!("The secrets in this bundle are encrypted; a passphrase is required"))?;
+                decrypt(&encrypted, passphrase)?
+            }
+        };
+
+        let mut config = self.config;
+        if let (Some(trakt), Some(secret)) = (config.trakt.as_mut(), values.remove(TRAKT_CLIENT_SECRET)) {
+            trakt.client_secret = secret;
+        }
+        if let (Some(simkl), Some(secret)) = (config.simkl.as_mut(), values.remove(SIMKL_CLIENT_SECRET)) {
+            simkl.client_secret = secret;
+        }
+        values.retain(|key, _| SECRET_KEYS.contains(&key.as_str()));
+        Ok((config, values))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let header = match &self.secrets {
+            None => "# TotalRecall config export without secrets (client secrets and tokens are blank)\n",
+            Some(BundleSecrets::Plaintext { .. }) => "# TotalRecall config export WITH PLAINTEXT SECRETS - keep this file private\n",
+            Some(BundleSecrets::Encrypted { .. }) => "# TotalRecall config export with secrets encrypted by a passphrase\n",
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, format!("{}{}", header, toml::to_string_pretty(self)?))
+            .map_err(|e| anyhow!("Failed to write {}: {}", path.display(), e))
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("Failed to read {}: {}", path.display(), e))?;
+        toml::from_str(&content)
+            .map_err(|e| anyhow!("{} is not a TotalRecall config export: {}", path.display(), e))
+    }
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], iterations: u32) -> Result<LessSafeKey> {
+    let iterations = NonZeroU32::new(iterations).ok_or_else(|| anyhow!("Invalid key derivation iterations"))?;
+    let mut key = [0u8; 32];
+    ring::pbkdf2::derive(ring::pbkdf2::PBKDF2_HMAC_SHA256, iterations, salt, passphrase.as_bytes(), &mut key);
+    let key = UnboundKey::new(&AES_256_GCM, &key).map_err(|_| anyhow!("Failed to create encryption key"))?;
+    Ok(LessSafeKey::new(key))
+}
+
+fn encrypt(values: &BTreeMap<String, String>, passphrase: &str) -> Result<BundleSecrets> {
+    if passphrase.is_empty() {
+        return Err(anyhow!("The passphrase cannot be empty"));
+    }
+    let rng = SystemRandom::new();
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    rng.fill(&mut salt).map_err(|_| anyhow!("Failed to generate a salt"))?;
+    rng.fill(&mut nonce).map_err(|_| anyhow!("Failed to generate a nonce"))?;
+
+    let key = derive_key(passphrase, &salt, KDF_ITERATIONS)?;
+    let mut data = toml::to_string(values)?.into_bytes();
+    key.seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::empty(), &mut data)
+        .map_err(|_| anyhow!("Failed to encrypt secrets"))?;
+    Ok(BundleSecrets::Encrypted {
+        kdf_iterations: KDF_ITERATIONS,
+        salt: BASE64.encode(salt),
+        nonce: BASE64.encode(nonce),
+        ciphertext: BASE64.encode(data),
+    })
+}
+
+fn decrypt(secrets: &BundleSecrets, passphrase: &str) -> Result<BTreeMap<String, String>> {
+    let BundleSecrets::Encrypted { kdf_iterations, salt, nonce, ciphertext } = secrets else {
+        return Err(anyhow!("Secrets are not encrypted"));
+    };
+    let decode = |field: &str, value: &str| BASE64.decode(value).map_err(|e| anyhow!("Invalid {} in bundle: {}", field, e));
+    let salt = decode("salt", salt)?;
+    let nonce: [u8; NONCE_LEN] = decode("nonce", nonce)?
+        .try_into()
+        .map_err(|_| anyhow!("Invalid nonce in bundle"))?;
+    let mut data = decode("ciphertext", ciphertext)?;
+
+    let key = derive_key(passphrase, &salt, *kdf_iterations)?;
+    let plaintext = key.open_in_place(Nonce::assume_unique_for_key(nonce), Aad::empty(), &mut data)
+        .map_err(|_| anyhow!("Wrong passphrase, or the secrets in the bundle were modified"))?;
+    let plaintext = std::str::from_utf8(plaintext).map_err(|e| anyhow!("Invalid secrets in bundle: {}", e))?;
+    Ok(toml::from_str(plaintext)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TraktConfig;
+    use crate::credentials::FileBackend;
+
+    fn config_with_trakt() -> Config {
+        let mut config: Config = toml::from_str("[sources]\n[sync]\n").unwrap();
+        config.trakt = Some(TraktConfig {
+            enabled: true,
+            client_id: "client-id".to_string(),
+            client_secret: "client-secret".to_string(),
+            status_mapping: crate::config::default_trakt_status_mapping(),
+            likes_as: None,
+            account: None,
+            retry: Default::default(),
+            rate_limit: Default::default(),
+            batch_size: crate::config::DEFAULT_TRAKT_BATCH_SIZE,
+        });
+        config
+    }
+
+    #[test]
+    fn test_bundle_round_trips_secrets_only_when_included() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut credentials = CredentialStore::with_backend(Box::new(FileBackend::new(dir.path().join("credentials.toml"))));
+        credentials.set_trakt_access_token("access-token".to_string());
+        credentials.set_last_sync_timestamp("trakt", "ratings", Utc::now());
+        let config = config_with_trakt();
+        let path = dir.path().join("export.toml");
+
+        // Without secrets nothing secret is written
+        ConfigBundle::export(&config, &credentials, SecretsExport::Exclude).unwrap().save(&path).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(!content.contains("client-secret") && !content.contains("access-token"));
+        let (imported, values) = ConfigBundle::load(&path).unwrap().into_parts(None).unwrap();
+        assert_eq!(imported.trakt.unwrap().client_secret, "");
+        assert!(values.is_empty());
+
+        // Encrypted: unreadable in the file, restored with the passphrase only
+        ConfigBundle::export(&config, &credentials, SecretsExport::Encrypted("hunter2")).unwrap().save(&path).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(!content.contains("client-secret") && !content.contains("access-token"));
+        let bundle = ConfigBundle::load(&path).unwrap();
+        assert!(bundle.is_encrypted());
+        assert!(ConfigBundle::load(&path).unwrap().into_parts(Some("wrong")).is_err());
+        let (imported, values) = bundle.into_parts(Some("hunter2")).unwrap();
+        assert_eq!(imported.trakt.unwrap().client_secret, "client-secret");
+        assert_eq!(values.get("trakt_access_token").map(String::as_str), Some("access-token"));
+        assert_eq!(values.len(), 1);
+
+        // Plaintext is flagged in the file
+        ConfigBundle::export(&config, &credentials, SecretsExport::Plaintext).unwrap().save(&path).unwrap();
+        assert!(std::fs::read_to_string(&path).unwrap().starts_with("# TotalRecall config export WITH PLAINTEXT SECRETS"));
+    }
+}
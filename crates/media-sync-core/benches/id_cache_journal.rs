@@ -0,0 +1,62 @@
+//! Saving the ID cache during a sync: full rewrites vs the journal.
+//!
+//! Starts from a saved cache of 10,000 entries and resolves another 10,000,
+//! saving every 500 as a sync does between batches. Run with
+//! `cargo bench -p media-sync-core --bench id_cache_journal`.
+
+use std::time::{Duration, Instant};
+
+use media_sync_core::id_cache::IdCache;
+use media_sync_core::id_cache_storage::IdCacheStorage;
+use media_sync_models::{MediaIds, MediaType};
+
+const CACHED: usize = 10_000;
+const RESOLVED: usize = 10_000;
+const SAVE_EVERY: usize = 500;
+
+fn entry(n: usize) -> MediaIds {
+    MediaIds {
+        imdb_id: Some(format!("tt{:07}", n)),
+        trakt_id: Some(n as u64),
+        tmdb_id: Some(n as u32),
+        title: Some(format!("Title {}", n)),
+        year: Some(1950 + (n % 75) as u32),
+        media_type: Some(MediaType::Movie),
+        ..Default::default()
+    }
+}
+
+/// Time the saves of one simulated sync; `journal` appends, otherwise every save rewrites the cache
+fn run(journal: bool) -> Duration {
+    let dir = tempfile::tempdir().unwrap();
+    let mut storage = IdCacheStorage::new(dir.path());
+    let mut cache = IdCache::new();
+    for n in 0..CACHED {
+        cache.insert(entry(n));
+    }
+    storage.save(&cache).unwrap();
+    cache.take_changed();
+
+    let mut elapsed = Duration::ZERO;
+    for n in CACHED..CACHED + RESOLVED {
+        cache.insert(entry(n));
+        if (n + 1) % SAVE_EVERY == 0 {
+            let start = Instant::now();
+            if journal {
+                storage.append(&cache.take_changed()).unwrap();
+            } else {
+                storage.save(&cache).unwrap();
+            }
+            elapsed += start.elapsed();
+        }
+    }
+    elapsed
+}
+
+fn main() {
+    let full = run(false);
+    let journal = run(true);
+    println!("{} saves of {} new entries over a {}-entry cache:", RESOLVED / SAVE_EVERY, SAVE_EVERY, CACHED);
+    println!("  full rewrites: {:?}", full);
+    println!("  journal:       {:?} ({:.1}x faster)", journal, full.as_secs_f64() / journal.as_secs_f64());
+}
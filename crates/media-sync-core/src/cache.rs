@@ -1,26 +1,147 @@
 use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
 use media_sync_config::PathManager;
 use media_sync_models::{Rating, Review, WatchHistory, WatchlistItem, ExcludedItem};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::path::PathBuf;
 use tracing::{debug, info, warn};
 
+use crate::dry_run_compare::DryRunFormat;
+use crate::resolution::ResolvedData;
+
 #[derive(Clone)]
 pub struct CacheManager {
+    paths: PathManager,
     collect_dir: PathBuf,
     distribute_dir: PathBuf,
+    resolve_dir: PathBuf,
+}
+
+/// How long a sent review counts as present on its target. Trakt takes minutes to list
+/// a new comment, so a sync right after another would send it again.
+pub const REVIEW_SUBMISSION_TTL_MINUTES: i64 = 30;
+
+/// A review sent to a target, in `distribute/{source}/submitted_reviews.json`
+#[derive(Serialize, Deserialize)]
+struct SubmittedReview {
+    imdb_id: String,
+    content_hash: String,
+    submitted_at: DateTime<Utc>,
+}
+
+/// Items the cache writes sorted by IMDB ID, then date, so that saving the same data
+/// twice produces the same file whatever order a source returned it in
+pub trait CacheOrder {
+    fn cache_key(&self) -> (&str, Option<DateTime<Utc>>);
+}
+
+impl CacheOrder for WatchlistItem {
+    fn cache_key(&self) -> (&str, Option<DateTime<Utc>>) {
+        (&self.imdb_id, Some(self.date_added))
+    }
+}
+
+impl CacheOrder for Rating {
+    fn cache_key(&self) -> (&str, Option<DateTime<Utc>>) {
+        (&self.imdb_id, Some(self.date_added))
+    }
+}
+
+impl CacheOrder for Review {
+    fn cache_key(&self) -> (&str, Option<DateTime<Utc>>) {
+        (&self.imdb_id, Some(self.date_added))
+    }
+}
+
+impl CacheOrder for WatchHistory {
+    fn cache_key(&self) -> (&str, Option<DateTime<Utc>>) {
+        (&self.imdb_id, Some(self.watched_at))
+    }
+}
+
+impl CacheOrder for ExcludedItem {
+    fn cache_key(&self) -> (&str, Option<DateTime<Utc>>) {
+        (self.imdb_id.as_deref().unwrap_or_default(), self.date_added)
+    }
+}
+
+/// `data` as JSON values in cache order. Items with the same key are ordered by their
+/// JSON, and converting to `serde_json::Value` sorts the keys of every object, so the
+/// result depends only on the items, not on their order or on any map's iteration order.
+fn stable_values<T>(data: &[T]) -> Result<Vec<serde_json::Value>>
+where
+    T: Serialize + CacheOrder,
+{
+    let mut values = data.iter()
+        .map(|item| Ok((item.cache_key(), serde_json::to_value(item)?)))
+        .collect::<Result<Vec<_>, serde_json::Error>>()
+        .map_err(|e| anyhow!("Failed to serialize cache data: {}", e))?;
+    values.sort_by(|(a_key, a), (b_key, b)| a_key.cmp(b_key).then_with(|| a.to_string().cmp(&b.to_string())));
+    Ok(values.into_iter().map(|(_, value)| value).collect())
+}
+
+/// Resolution output from the previous run, tagged with the hash of its inputs
+#[derive(Serialize, Deserialize)]
+struct CachedResolution {
+    input_hash: String,
+    resolved: ResolvedData,
 }
 
 impl CacheManager {
     pub fn new(path_manager: &PathManager) -> Result<Self> {
         let collect_dir = path_manager.cache_collect_dir();
         let distribute_dir = path_manager.cache_distribute_dir();
+        let resolve_dir = path_manager.cache_resolve_dir();
         std::fs::create_dir_all(&collect_dir)?;
         std::fs::create_dir_all(&distribute_dir)?;
-        Ok(Self { collect_dir, distribute_dir })
+        Ok(Self { paths: path_manager.clone(), collect_dir, distribute_dir, resolve_dir })
+    }
+
+    /// Paths the cache was created under, for the files kept next to it
+    pub fn paths(&self) -> &PathManager {
+        &self.paths
+    }
+
+    fn get_resolved_path(&self) -> PathBuf {
+        self.resolve_dir.join("resolved.json")
+    }
+
+    /// Previous resolution output, if it was produced from inputs with the same hash
+    pub fn load_resolved(&self, input_hash: &str) -> Option<ResolvedData> {
+        let path = self.get_resolved_path();
+        let content = std::fs::read_to_string(&path).ok()?;
+        match serde_json::from_str::<CachedResolution>(&content) {
+            Ok(cached) if cached.input_hash == input_hash => Some(cached.resolved),
+            Ok(_) => {
+                debug!("Resolved data cache is stale (input hash changed)");
+                None
+            }
+            Err(e) => {
+                warn!("Ignoring unreadable resolved data cache {}: {}", path.display(), e);
+                None
+            }
+        }
     }
 
-    fn get_cache_path(&self, source: &str, data_type: &str) -> PathBuf {
+    pub fn save_resolved(&self, input_hash: &str, resolved: &ResolvedData) -> Result<()> {
+        std::fs::create_dir_all(&self.resolve_dir)?;
+        let cached = CachedResolution {
+            input_hash: input_hash.to_string(),
+            resolved: resolved.clone(),
+        };
+        let json = serde_json::to_string(&cached)
+            .map_err(|e| anyhow!("Failed to serialize resolved data: {}", e))?;
+        std::fs::write(self.get_resolved_path(), json)
+            .map_err(|e| anyhow!("Failed to write resolved data cache: {}", e))
+    }
+
+    /// Directory holding one source's collect cache, one `{data_type}.json` per data type
+    pub fn collect_source_dir(&self, source: &str) -> PathBuf {
+        self.collect_dir.join(source)
+    }
+
+    pub fn get_cache_path(&self, source: &str, data_type: &str) -> PathBuf {
         self.collect_dir.join(source).join(format!("{}.json", data_type))
     }
 
@@ -32,6 +153,118 @@ impl CacheManager {
         self.get_cache_path(source, data_type).exists()
     }
 
+    /// Delete one collect cache file, so the next collect fetches that data type in full
+    pub fn discard_source_data(&self, source: &str, data_type: &str) -> Result<()> {
+        let cache_path = self.get_cache_path(source, data_type);
+        if cache_path.exists() {
+            std::fs::remove_file(&cache_path)
+                .map_err(|e| anyhow!("Failed to delete cache file {}: {}", cache_path.display(), e))?;
+        }
+        Ok(())
+    }
+
+    fn distribution_hash_path(&self) -> PathBuf {
+        self.distribute_dir.join("unchanged_inputs.hash")
+    }
+
+    /// Input hash of the last distribution that had nothing to write, if any
+    pub fn load_distribution_hash(&self) -> Option<String> {
+        std::fs::read_to_string(self.distribution_hash_path()).ok()
+    }
+
+    /// Record the input hash of a distribution that had nothing to write, or with None
+    /// forget it, so the next run distributes
+    pub fn save_distribution_hash(&self, hash: Option<&str>) -> Result<()> {
+        let path = self.distribution_hash_path();
+        match hash {
+            Some(hash) => std::fs::write(&path, hash)
+                .map_err(|e| anyhow!("Failed to write {}: {}", path.display(), e)),
+            None if path.exists() => std::fs::remove_file(&path)
+                .map_err(|e| anyhow!("Failed to delete {}: {}", path.display(), e)),
+            None => Ok(()),
+        }
+    }
+
+    fn watchlist_snapshot_path(&self, source: &str) -> PathBuf {
+        self.distribute_dir.join(source).join("watchlist_snapshot.json")
+    }
+
+    /// `source`'s watchlist as of the last sync that distributed everywhere, if recorded
+    pub fn load_watchlist_snapshot(&self, source: &str) -> Option<Vec<WatchlistItem>> {
+        let path = self.watchlist_snapshot_path(source);
+        let content = std::fs::read_to_string(&path).ok()?;
+        serde_json::from_str(&content)
+            .map_err(|e| warn!("Ignoring unreadable watchlist snapshot {}: {}", path.display(), e))
+            .ok()
+    }
+
+    pub fn save_watchlist_snapshot(&self, source: &str, watchlist: &[WatchlistItem]) -> Result<()> {
+        let path = self.watchlist_snapshot_path(source);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, serde_json::to_string(&stable_values(watchlist)?)?)
+            .map_err(|e| anyhow!("Failed to write {}: {}", path.display(), e))
+    }
+
+    fn ratings_validated_path(&self, source: &str) -> PathBuf {
+        self.collect_source_dir(source).join("ratings.validated")
+    }
+
+    /// Whether `source`'s cached ratings were checked by this version of totalrecall
+    pub fn ratings_validated(&self, source: &str) -> bool {
+        std::fs::read_to_string(self.ratings_validated_path(source))
+            .is_ok_and(|version| version.trim() == env!("CARGO_PKG_VERSION"))
+    }
+
+    pub fn mark_ratings_validated(&self, source: &str) -> Result<()> {
+        std::fs::create_dir_all(self.collect_source_dir(source))?;
+        std::fs::write(self.ratings_validated_path(source), env!("CARGO_PKG_VERSION"))
+            .map_err(|e| anyhow!("Failed to record ratings check for {}: {}", source, e))
+    }
+
+    fn submitted_reviews_path(&self, source: &str) -> PathBuf {
+        self.distribute_dir.join(source).join("submitted_reviews.json")
+    }
+
+    /// Reviews sent to `source` within `REVIEW_SUBMISSION_TTL_MINUTES` of `now`
+    fn submitted_reviews(&self, source: &str, now: DateTime<Utc>) -> Vec<SubmittedReview> {
+        let cutoff = now - chrono::Duration::minutes(REVIEW_SUBMISSION_TTL_MINUTES);
+        std::fs::read_to_string(self.submitted_reviews_path(source))
+            .ok()
+            .and_then(|content| serde_json::from_str::<Vec<SubmittedReview>>(&content).ok())
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|submitted| submitted.submitted_at > cutoff)
+            .collect()
+    }
+
+    /// (IMDB ID, `review_content_hash`) of the reviews sent to `source` in the last
+    /// `REVIEW_SUBMISSION_TTL_MINUTES`, which its API may not list yet
+    pub fn recently_submitted_reviews(&self, source: &str) -> HashSet<(String, String)> {
+        self.submitted_reviews(source, Utc::now())
+            .into_iter()
+            .map(|submitted| (submitted.imdb_id, submitted.content_hash))
+            .collect()
+    }
+
+    /// Record that `reviews` were just sent to `source`, dropping expired entries
+    pub fn record_submitted_reviews(&self, source: &str, reviews: &[Review]) -> Result<()> {
+        let now = Utc::now();
+        let mut submitted = self.submitted_reviews(source, now);
+        submitted.extend(reviews.iter().filter(|review| !review.imdb_id.is_empty()).map(|review| SubmittedReview {
+            imdb_id: review.imdb_id.clone(),
+            content_hash: crate::diff::review_content_hash(&review.content),
+            submitted_at: now,
+        }));
+        let path = self.submitted_reviews_path(source);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, serde_json::to_string(&submitted)?)
+            .map_err(|e| anyhow!("Failed to record submitted reviews for {}: {}", source, e))
+    }
+
     pub fn load_watchlist(&self, source: &str) -> Result<Option<Vec<WatchlistItem>>> {
         self.load_source_data(source, "watchlist")
     }
@@ -78,6 +311,22 @@ impl CacheManager {
         self.save_source_data(source, "excluded", data)
     }
 
+    /// Read a collect cache file as raw JSON values for inspection.
+    /// Unlike `load_source_data`, a corrupted file is reported as an error and left in place.
+    pub fn peek_source_data(&self, source: &str, data_type: &str) -> Result<Option<Vec<serde_json::Value>>> {
+        let cache_path = self.get_cache_path(source, data_type);
+
+        if !cache_path.exists() {
+            return Ok(None);
+        }
+
+        let content = std::fs::read_to_string(&cache_path)
+            .map_err(|e| anyhow!("Failed to read cache file {}: {}", cache_path.display(), e))?;
+        let data = serde_json::from_str::<Vec<serde_json::Value>>(&content)
+            .map_err(|e| anyhow!("Failed to parse cache file {}: {}", cache_path.display(), e))?;
+        Ok(Some(data))
+    }
+
     fn load_source_data<T>(&self, source: &str, data_type: &str) -> Result<Option<Vec<T>>>
     where
         T: for<'de> Deserialize<'de>,
@@ -117,7 +366,7 @@ impl CacheManager {
 
     fn save_source_data<T>(&self, source: &str, data_type: &str, data: &[T]) -> Result<()>
     where
-        T: Serialize,
+        T: Serialize + CacheOrder,
     {
         let cache_path = self.get_cache_path(source, data_type);
         
@@ -126,7 +375,7 @@ impl CacheManager {
             std::fs::create_dir_all(parent)?;
         }
         
-        match serde_json::to_string_pretty(data) {
+        match stable_values(data).and_then(|values| Ok(serde_json::to_string_pretty(&values)?)) {
             Ok(json) => {
                 match std::fs::write(&cache_path, json) {
                     Ok(_) => {
@@ -148,7 +397,7 @@ impl CacheManager {
 
     pub fn save_distribute_data<T>(&self, source: &str, data_type: &str, data: &[T]) -> Result<()>
     where
-        T: Serialize,
+        T: Serialize + CacheOrder,
     {
         let distribute_path = self.get_distribute_path(source, data_type);
         
@@ -157,7 +406,7 @@ impl CacheManager {
             std::fs::create_dir_all(parent)?;
         }
         
-        match serde_json::to_string_pretty(data) {
+        match stable_values(data).and_then(|values| Ok(serde_json::to_string_pretty(&values)?)) {
             Ok(json) => {
                 match std::fs::write(&distribute_path, json) {
                     Ok(_) => {
@@ -177,6 +426,37 @@ impl CacheManager {
         }
     }
 
+    /// Write a dry run's `data` for `data_type` in `format`, replacing a file of the other
+    /// format from an earlier dry run
+    pub fn save_dry_run_data<T>(&self, source: &str, data_type: &str, data: &[T], format: DryRunFormat) -> Result<()>
+    where
+        T: Serialize + CacheOrder,
+    {
+        for other in DryRunFormat::ALL.into_iter().filter(|other| *other != format) {
+            let stale = self.distribute_dir.join(source).join(format!("{}.{}", data_type, other.extension()));
+            if stale.exists() {
+                std::fs::remove_file(&stale)?;
+            }
+        }
+        if format == DryRunFormat::Json {
+            return self.save_distribute_data(source, data_type, data);
+        }
+
+        let path = self.distribute_dir.join(source).join(format!("{}.{}", data_type, format.extension()));
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut lines = String::new();
+        for value in stable_values(data)? {
+            lines.push_str(&value.to_string());
+            lines.push('\n');
+        }
+        std::fs::write(&path, lines)
+            .map_err(|e| anyhow!("Failed to write distribute data: {}", e))?;
+        debug!("Distribute data saved: {} {} (saved {} items)", source, data_type, data.len());
+        Ok(())
+    }
+
     pub fn load_distribute_data<T>(&self, source: &str, data_type: &str) -> Result<Option<Vec<T>>>
     where
         T: for<'de> Deserialize<'de>,
@@ -225,8 +505,73 @@ impl CacheManager {
             std::fs::create_dir_all(&self.distribute_dir)?;
             info!("Cleared distribute cache directory: {:?}", self.distribute_dir);
         }
+        if self.resolve_dir.exists() {
+            std::fs::remove_dir_all(&self.resolve_dir)?;
+            info!("Cleared resolve cache directory: {:?}", self.resolve_dir);
+        }
         Ok(())
     }
 }
 
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use media_sync_models::{MediaType, RatingSource};
+
+    fn rating(imdb_id: &str, rating: u8, day: u32, source: RatingSource) -> Rating {
+        Rating {
+            imdb_id: imdb_id.to_string(),
+            ids: None,
+            rating,
+            rating_f64: None,
+            date_added: Utc.with_ymd_and_hms(2024, 1, day, 0, 0, 0).unwrap(),
+            media_type: MediaType::Movie,
+            source,
+            inferred: false,
+        }
+    }
+
+    #[test]
+    fn test_saving_the_same_data_in_any_order_writes_identical_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache_manager = CacheManager::new(&PathManager::with_base_dir(dir.path())).unwrap();
+        let ratings = vec![
+            rating("tt0133093", 9, 2, RatingSource::Trakt),
+            rating("tt0113277", 8, 1, RatingSource::Trakt),
+            rating("tt0113277", 7, 1, RatingSource::Imdb),
+            rating("tt0133093", 6, 1, RatingSource::Trakt),
+        ];
+
+        cache_manager.save_ratings("trakt", &ratings).unwrap();
+        let first = std::fs::read(cache_manager.get_cache_path("trakt", "ratings")).unwrap();
+
+        let reversed: Vec<Rating> = ratings.iter().rev().cloned().collect();
+        cache_manager.save_ratings("trakt", &reversed).unwrap();
+        let second = std::fs::read(cache_manager.get_cache_path("trakt", "ratings")).unwrap();
+        assert_eq!(first, second);
+
+        let loaded = cache_manager.load_ratings("trakt").unwrap().unwrap();
+        let order: Vec<(&str, u8)> = loaded.iter().map(|r| (r.imdb_id.as_str(), r.rating)).collect();
+        assert_eq!(order, vec![("tt0113277", 7), ("tt0113277", 8), ("tt0133093", 6), ("tt0133093", 9)]);
+
+        cache_manager.save_ratings("trakt", &ratings[1..]).unwrap();
+        assert_ne!(std::fs::read(cache_manager.get_cache_path("trakt", "ratings")).unwrap(), first);
+    }
+
+    #[test]
+    fn test_distribution_hash_is_recorded_and_forgotten() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache_manager = CacheManager::new(&PathManager::with_base_dir(dir.path())).unwrap();
+        assert_eq!(cache_manager.load_distribution_hash(), None);
+
+        cache_manager.save_distribution_hash(Some("0123456789abcdef")).unwrap();
+        assert_eq!(cache_manager.load_distribution_hash().as_deref(), Some("0123456789abcdef"));
+
+        cache_manager.save_distribution_hash(None).unwrap();
+        cache_manager.save_distribution_hash(None).unwrap();
+        assert_eq!(cache_manager.load_distribution_hash(), None);
+    }
+}
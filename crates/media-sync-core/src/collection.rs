@@ -0,0 +1,82 @@
+//! Collection sync (`sync_collection` / `sync --collection`).
+//!
+//! A collection is what the user owns, dated by `collected_at`. It is kept apart from
+//! watch history: owning a title says nothing about having watched it. The resolved
+//! collection is the union of every source's items, each dated by the earliest source
+//! that has it. Sync only adds, and only to sources whose collection is writable.
+
+use media_sync_models::CollectionItem;
+
+fn same_item(a: &CollectionItem, b: &CollectionItem) -> bool {
+    crate::lists::same_title((&a.imdb_id, &a.ids, &a.media_type), (&b.imdb_id, &b.ids, &b.media_type))
+}
+
+/// Merge the collections of every source, given in `source_preference` order
+pub fn resolve_collection(sources: &[(String, Vec<CollectionItem>)]) -> Vec<CollectionItem> {
+    let mut resolved: Vec<CollectionItem> = Vec::new();
+    for (_, items) in sources {
+        for item in items {
+            match resolved.iter_mut().find(|existing| same_item(existing, item)) {
+                Some(existing) => {
+                    existing.collected_at = existing.collected_at.min(item.collected_at);
+                    if existing.imdb_id.is_empty() && !item.imdb_id.is_empty() {
+                        existing.imdb_id = item.imdb_id.clone();
+                    }
+                    if existing.ids.is_none() {
+                        existing.ids = item.ids.clone();
+                    }
+                }
+                None => resolved.push(item.clone()),
+            }
+        }
+    }
+    resolved
+}
+
+/// The items of `resolved` that `existing` (one source's collection) lacks
+pub fn missing_from(resolved: &[CollectionItem], existing: &[CollectionItem]) -> Vec<CollectionItem> {
+    resolved.iter()
+        .filter(|item| !existing.iter().any(|own| same_item(own, item)))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use media_sync_models::{MediaIds, MediaType};
+
+    fn item(imdb_id: &str, source: &str, day: u32) -> CollectionItem {
+        CollectionItem {
+            imdb_id: imdb_id.to_string(),
+            ids: None,
+            title: imdb_id.to_string(),
+            year: None,
+            media_type: MediaType::Movie,
+            collected_at: Utc.with_ymd_and_hms(2024, 1, day, 0, 0, 0).unwrap(),
+            source: source.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_collection_is_dated_by_the_earliest_source() {
+        let mut by_tmdb = item("", "plex", 9);
+        by_tmdb.ids = Some(MediaIds { tmdb_id: Some(949), ..Default::default() });
+        let mut heat = item("tt0113277", "trakt", 20);
+        heat.ids = Some(MediaIds { tmdb_id: Some(949), ..Default::default() });
+        let sources = vec![
+            ("trakt".to_string(), vec![heat]),
+            ("plex".to_string(), vec![item("tt0113277", "plex", 5), by_tmdb, item("tt2", "plex", 7)]),
+        ];
+
+        let resolved = resolve_collection(&sources);
+        assert_eq!(resolved.len(), 2);
+        assert_eq!(resolved[0].collected_at, Utc.with_ymd_and_hms(2024, 1, 5, 0, 0, 0).unwrap());
+
+        let missing = missing_from(&resolved, &sources[0].1);
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].imdb_id, "tt2");
+        assert!(missing_from(&resolved, &sources[1].1).is_empty());
+    }
+}
@@ -0,0 +1,275 @@
+//! Conflict report of resolution, written to `cache/resolve/conflicts.json`.
+//!
+//! Resolution picks one rating and one watchlist status per title. Where the sources
+//! disagreed, the report lists every source's value and the source that won, along with
+//! titles a single source lists more than once, so the choices can be checked before
+//! syncing unattended. The file is rewritten whenever resolution runs.
+//!
+//! With `sync --report-conflicts` it also lists review conflicts. Reviews are merged,
+//! never resolved to one winner, so two sources with different reviews of the same title
+//! both end up everywhere. Those titles are listed with every source's full text and
+//! length so they can be settled by hand. Reviews that only differ in case, whitespace,
+//! punctuation or emoji (a synced copy as the target stored it) are the same review and
+//! not reported.
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use media_sync_models::{Rating, Review, WatchlistItem};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::id_matching::match_by_any_id;
+use crate::resolution::{group_matching, SourceData};
+use crate::review_text::comparable_text;
+
+/// What resolution decided where the sources disagreed
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ResolutionReport {
+    pub rating_conflicts: Vec<ResolvedConflict>,
+    /// Watchlist status conflicts. The `Merge` watchlist strategy keeps no single winner
+    /// per title, so it reports no watchlist conflicts or duplicates.
+    pub status_conflicts: Vec<ResolvedConflict>,
+    pub duplicate_ids: Vec<DuplicateId>,
+    /// With `--report-conflicts`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub review_conflicts: Vec<ConflictRecord>,
+}
+
+/// A title with different values on different sources and the one resolution kept
+#[derive(Debug, Clone, Serialize)]
+pub struct ResolvedConflict {
+    pub imdb_id: String,
+    pub candidates: Vec<CandidateValue>,
+    /// Source whose value won
+    pub winner: String,
+}
+
+/// One source's value in a `ResolvedConflict`
+#[derive(Debug, Clone, Serialize)]
+pub struct CandidateValue {
+    pub source: String,
+    pub value: serde_json::Value,
+    pub date_added: DateTime<Utc>,
+}
+
+/// A title one source lists `count` times
+#[derive(Debug, Clone, Serialize)]
+pub struct DuplicateId {
+    pub source: String,
+    pub data_type: String,
+    pub imdb_id: String,
+    pub count: usize,
+}
+
+impl ResolutionReport {
+    /// A group of matching ratings, resolved to `winner`
+    pub(crate) fn record_ratings(&mut self, candidates: &[(&str, &Rating)], winner: &Rating) {
+        self.record_duplicates("ratings", candidates, |rating| &rating.imdb_id);
+        if let Some(conflict) = resolved_conflict(candidates, winner, |rating| Some(serde_json::json!(rating.rating)), |rating| rating.date_added, |rating| &rating.imdb_id) {
+            self.rating_conflicts.push(conflict);
+        }
+    }
+
+    /// A group of matching watchlist items, resolved to `winner`. Items without a status
+    /// don't take part in a status conflict.
+    pub(crate) fn record_watchlist(&mut self, candidates: &[(&str, &WatchlistItem)], winner: &WatchlistItem) {
+        self.record_duplicates("watchlist", candidates, |item| &item.imdb_id);
+        let status = |item: &WatchlistItem| item.status.as_ref().and_then(|status| serde_json::to_value(status).ok());
+        if let Some(conflict) = resolved_conflict(candidates, winner, status, |item| item.date_added, |item| &item.imdb_id) {
+            self.status_conflicts.push(conflict);
+        }
+    }
+
+    fn record_duplicates<T>(&mut self, data_type: &str, candidates: &[(&str, &T)], imdb_id: impl Fn(&T) -> &str) {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for (source, _) in candidates {
+            *counts.entry(source).or_default() += 1;
+        }
+        let mut duplicated: Vec<(&str, usize)> = counts.into_iter().filter(|(_, count)| *count > 1).collect();
+        duplicated.sort();
+        for (source, count) in duplicated {
+            self.duplicate_ids.push(DuplicateId {
+                source: source.to_string(),
+                data_type: data_type.to_string(),
+                imdb_id: group_imdb_id(candidates, &imdb_id),
+                count,
+            });
+        }
+    }
+
+    /// Order every list by IMDB ID, so reports of the same data compare equal
+    pub(crate) fn sort(&mut self) {
+        self.rating_conflicts.sort_by(|a, b| a.imdb_id.cmp(&b.imdb_id));
+        self.status_conflicts.sort_by(|a, b| a.imdb_id.cmp(&b.imdb_id));
+        self.duplicate_ids.sort_by(|a, b| (&a.data_type, &a.imdb_id, &a.source).cmp(&(&b.data_type, &b.imdb_id, &b.source)));
+    }
+}
+
+/// The conflict in a group of matching items, when their values differ. The winner is
+/// the first candidate equal to `winner`.
+fn resolved_conflict<T: PartialEq>(
+    candidates: &[(&str, &T)],
+    winner: &T,
+    value: impl Fn(&T) -> Option<serde_json::Value>,
+    date_added: impl Fn(&T) -> DateTime<Utc>,
+    imdb_id: impl Fn(&T) -> &str,
+) -> Option<ResolvedConflict> {
+    let values: Vec<CandidateValue> = candidates.iter()
+        .filter_map(|(source, item)| value(item).map(|value| CandidateValue {
+            source: source.to_string(),
+            value,
+            date_added: date_added(item),
+        }))
+        .collect();
+    if values.iter().all(|candidate| candidate.value == values[0].value) {
+        return None;
+    }
+    Some(ResolvedConflict {
+        imdb_id: group_imdb_id(candidates, &imdb_id),
+        candidates: values,
+        winner: candidates.iter()
+            .find(|(_, item)| *item == winner)
+            .map(|(source, _)| source.to_string())
+            .unwrap_or_default(),
+    })
+}
+
+fn group_imdb_id<T>(candidates: &[(&str, &T)], imdb_id: impl Fn(&T) -> &str) -> String {
+    candidates.iter()
+        .map(|(_, item)| imdb_id(item))
+        .find(|imdb_id| !imdb_id.is_empty())
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// One title the sources disagree on
+#[derive(Debug, Clone, Serialize)]
+pub struct ConflictRecord {
+    pub imdb_id: String,
+    pub data_type: String,
+    pub entries: Vec<ConflictEntry>,
+}
+
+/// One source's version in a conflict
+#[derive(Debug, Clone, Serialize)]
+pub struct ConflictEntry {
+    pub source: String,
+    /// Length of `text` in characters
+    pub length: usize,
+    pub text: String,
+    pub date_added: DateTime<Utc>,
+}
+
+impl ConflictEntry {
+    /// `text` cut to `max_chars` characters, with an ellipsis when cut
+    pub fn preview(&self, max_chars: usize) -> String {
+        if self.length <= max_chars {
+            return self.text.clone();
+        }
+        let cut: String = self.text.chars().take(max_chars).collect();
+        format!("{}…", cut.trim_end())
+    }
+}
+
+/// Titles reviewed by more than one source with substantively different texts
+pub fn review_conflicts(source_data: &[(&str, &SourceData)]) -> Vec<ConflictRecord> {
+    let reviews: Vec<(&str, &Review)> = source_data.iter()
+        .flat_map(|(source, data)| data.reviews.iter().map(move |review| (*source, review)))
+        .collect();
+
+    let mut records: Vec<ConflictRecord> = group_matching(&reviews, same_title)
+        .into_iter()
+        .filter(|group| {
+            let first = comparable_text(&group[0].1.content);
+            group.iter().any(|(source, _)| *source != group[0].0)
+                && group.iter().any(|(_, review)| comparable_text(&review.content) != first)
+        })
+        .map(|group| ConflictRecord {
+            imdb_id: group.iter()
+                .map(|(_, review)| review.imdb_id.clone())
+                .find(|imdb_id| !imdb_id.is_empty())
+                .unwrap_or_default(),
+            data_type: "reviews".to_string(),
+            entries: group.into_iter()
+                .map(|(source, review)| ConflictEntry {
+                    source: source.to_string(),
+                    length: review.content.chars().count(),
+                    text: review.content.clone(),
+                    date_added: review.date_added,
+                })
+                .collect(),
+        })
+        .collect();
+    records.sort_by(|a, b| a.imdb_id.cmp(&b.imdb_id));
+    records
+}
+
+/// Write `report` as the report for this run, replacing the previous one
+pub fn save_conflict_report(path: &Path, report: &ResolutionReport) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_string_pretty(report)
+        .map_err(|e| anyhow!("Failed to serialize conflict report: {}", e))?;
+    std::fs::write(path, json)
+        .map_err(|e| anyhow!("Failed to write conflict report {}: {}", path.display(), e))
+}
+
+fn same_title(a: &Review, b: &Review) -> bool {
+    if !a.imdb_id.is_empty() && a.imdb_id == b.imdb_id {
+        return true;
+    }
+    match (&a.ids, &b.ids) {
+        (Some(a_ids), Some(b_ids)) => match_by_any_id(a_ids, b_ids),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use media_sync_models::MediaType;
+
+    fn review(imdb_id: &str, source: &str, content: &str) -> Review {
+        Review {
+            imdb_id: imdb_id.to_string(),
+            ids: None,
+            content: content.to_string(),
+            date_added: Utc::now(),
+            media_type: MediaType::Movie,
+            source: source.to_string(),
+            is_spoiler: false,
+            rating: None,
+            review_id: None,
+        }
+    }
+
+    fn source(reviews: Vec<Review>) -> SourceData {
+        SourceData { watchlist: vec![], ratings: vec![], reviews, watch_history: vec![] }
+    }
+
+    #[test]
+    fn test_only_substantively_different_reviews_conflict() {
+        let imdb = source(vec![
+            review("tt0111161", "imdb", "Hope is a good thing.\n\nMaybe the best of things."),
+            review("tt0068646", "imdb", "An offer I couldn't refuse 🍝"),
+            review("tt0133093", "imdb", "There is no spoon."),
+        ]);
+        let trakt = source(vec![
+            review("tt0111161", "trakt", "Overlong, but the ending earns it."),
+            // The same review as Trakt stored it
+            review("tt0068646", "trakt", "An offer I couldn't refuse"),
+        ]);
+
+        let conflicts = review_conflicts(&[("imdb", &imdb), ("trakt", &trakt)]);
+        assert_eq!(conflicts.len(), 1);
+        let conflict = &conflicts[0];
+        assert_eq!(conflict.imdb_id, "tt0111161");
+        assert_eq!(conflict.entries.len(), 2);
+        assert_eq!(conflict.entries[0].source, "imdb");
+        assert_eq!(conflict.entries[0].length, 48);
+        assert_eq!(conflict.entries[1].text, "Overlong, but the ending earns it.");
+        assert_eq!(conflict.entries[0].preview(11), "Hope is a g…");
+    }
+}
@@ -83,9 +83,17 @@ where
     filtered
 }
 
+/// AniList and MAL IDs as match keys (`anilist:21`, `mal:21`), for items without an IMDB ID
+fn anime_match_keys(ids: &media_sync_models::MediaIds) -> impl Iterator<Item = String> {
+    [
+        ids.anilist_id.map(|id| format!("anilist:{}", id)),
+        ids.mal_id.map(|id| format!("mal:{}", id)),
+    ].into_iter().flatten()
+}
+
 /// Filter items from source that are not present in target based on IMDB_ID
 /// This is equivalent to the Python `filter_items()` function
-/// Now also checks MediaIds as fallback when imdb_id is empty
+/// Items without an IMDB ID are matched on the IMDB, AniList or MAL ID of their MediaIds
 pub fn filter_items_by_imdb_id<T>(source: &[T], target: &[T]) -> Vec<T>
 where
     T: Clone + GetImdbId + GetMediaIds,
@@ -103,6 +111,11 @@ where
             }
         })
         .collect();
+    let target_anime_ids: std::collections::HashSet<String> = target
+        .iter()
+        .filter_map(|item| item.get_media_ids())
+        .flat_map(|ids| anime_match_keys(&ids).collect::<Vec<_>>())
+        .collect();
 
     debug!(
         "filter_items_by_imdb_id: source_count={}, target_count={}, target_unique_ids={}",
@@ -131,26 +144,10 @@ where
         if id.is_empty() {
             if let Some(media_ids) = item.get_media_ids() {
                 if !media_ids.is_empty() {
-                    // Check if any ID from MediaIds matches target
-                    let mut found_match = false;
-                    if let Some(imdb) = &media_ids.imdb_id {
-                        if target_ids.contains(imdb) {
-                            found_match = true;
-                        }
-                    }
-                    // Also check other ID types by converting to string format
-                    if !found_match {
-                        for _id_str in [
-                            media_ids.trakt_id.map(|id| format!("trakt:{}", id)),
-                            media_ids.simkl_id.map(|id| format!("simkl:{}", id)),
-                            media_ids.tmdb_id.map(|id| format!("tmdb:{}", id)),
-                            media_ids.tvdb_id.map(|id| format!("tvdb:{}", id)),
-                            media_ids.slug.clone(),
-                        ].into_iter().flatten() {
-                            // For cross-ID matching, we'd need IdResolver, but for now just check imdb
-                            // This is a simplified version - full implementation would use IdResolver
-                        }
-                    }
+                    // Check the IMDB ID of the MediaIds, then the AniList and MAL IDs (anime
+                    // sources only have those)
+                    let found_match = media_ids.imdb_id.as_ref().is_some_and(|imdb| target_ids.contains(imdb))
+                        || anime_match_keys(&media_ids).any(|key| target_anime_ids.contains(&key));
                     if found_match {
                         skipped_existing += 1;
                         continue;
@@ -346,11 +343,43 @@ impl GetMediaIds for media_sync_models::Review {
     }
 }
 
+/// First 100 characters plus total length of the normalized content. Cut on a
+/// char boundary so multi-byte text (accents, emoji) can't split a character.
+fn review_content_key(content: &str) -> String {
+    let content = normalize_review_content(content);
+    let prefix: String = content.chars().take(100).collect();
+    format!("{}:{}", prefix, content.len())
+}
+
+/// Review text with LF line endings and no trailing whitespace on any line, since IMDB
+/// and Trakt return the same review with CRLF and LF endings
+fn normalize_review_content(content: &str) -> String {
+    content.replace("\r\n", "\n")
+        .replace('\r', "\n")
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_end()
+        .to_string()
+}
+
+/// Hash of `review_content_key`, for the recently submitted reviews ledger
+pub fn review_content_hash(content: &str) -> String {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    review_content_key(content).hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
 /// Filter reviews from source that are not present in target based on IMDB_ID and content similarity
-/// This prevents duplicate reviews when the same review content exists for the same movie
+/// This prevents duplicate reviews when the same review content exists for the same movie.
+/// `recently_submitted` holds (IMDB ID, `review_content_hash`) of reviews sent to the target
+/// moments ago, which count as present even before the target's API lists them.
 pub fn filter_reviews_by_imdb_id_and_content(
     source: &[media_sync_models::Review],
     target: &[media_sync_models::Review],
+    recently_submitted: &std::collections::HashSet<(String, String)>,
 ) -> Vec<media_sync_models::Review> {
     use tracing::debug;
     
@@ -364,12 +393,7 @@ pub fn filter_reviews_by_imdb_id_and_content(
                 return None;
             }
             // Create a content key: first 100 chars + total length (to catch content differences)
-            let content_key = if review.content.len() > 100 {
-                format!("{}:{}", &review.content[..100], review.content.len())
-            } else {
-                format!("{}:{}", review.content, review.content.len())
-            };
-            Some((id, content_key))
+            Some((id, review_content_key(&review.content)))
         })
         .collect();
 
@@ -392,11 +416,7 @@ pub fn filter_reviews_by_imdb_id_and_content(
         }
         
         // Create content key for this review
-        let content_key = if review.content.len() > 100 {
-            format!("{}:{}", &review.content[..100], review.content.len())
-        } else {
-            format!("{}:{}", review.content, review.content.len())
-        };
+        let content_key = review_content_key(&review.content);
         
         // Check if this (imdb_id, content) combination already exists
         if target_keys.contains(&(id.clone(), content_key)) {
@@ -409,6 +429,14 @@ pub fn filter_reviews_by_imdb_id_and_content(
             }
             continue;
         }
+        if recently_submitted.contains(&(id.clone(), review_content_hash(&review.content))) {
+            skipped_existing += 1;
+            debug!(
+                "filter_reviews_by_imdb_id_and_content: Skipping review (submitted moments ago) imdb_id={}",
+                id
+            );
+            continue;
+        }
         
         filtered.push(review.clone());
         if filtered.len() <= 5 {
@@ -450,26 +478,30 @@ impl GetMediaIds for media_sync_models::WatchHistory {
     }
 }
 
+/// Key a rating is matched on: its IMDB ID, or its AniList or MAL ID for anime without one
+fn rating_match_key(rating: &media_sync_models::Rating) -> Option<String> {
+    if !rating.imdb_id.is_empty() {
+        return Some(rating.imdb_id.clone());
+    }
+    anime_match_keys(rating.ids.as_ref()?).next()
+}
+
 /// Filter ratings that are new or have changed values
 /// Returns ratings from source that either:
 /// - Don't exist in target (new ratings)
 /// - Exist in target but have different rating values (changed ratings)
+///
+/// Ratings are matched by IMDB ID, or by AniList or MAL ID when they have no IMDB ID.
 pub fn filter_ratings_by_imdb_id_and_value(
     source: &[media_sync_models::Rating],
     target: &[media_sync_models::Rating],
 ) -> Vec<media_sync_models::Rating> {
     use tracing::debug;
     
-    // Build map of target ratings by IMDB ID
+    // Build map of target ratings by IMDB ID (or AniList ID)
     let target_ratings: std::collections::HashMap<String, u8> = target
         .iter()
-        .filter_map(|rating| {
-            if rating.imdb_id.is_empty() {
-                None
-            } else {
-                Some((rating.imdb_id.clone(), rating.rating))
-            }
-        })
+        .filter_map(|rating| Some((rating_match_key(rating)?, rating.rating)))
         .collect();
     
     let mut filtered = Vec::new();
@@ -477,19 +509,19 @@ pub fn filter_ratings_by_imdb_id_and_value(
     let mut skipped_empty = 0;
     
     for rating in source {
-        if rating.imdb_id.is_empty() {
+        let Some(key) = rating_match_key(rating) else {
             skipped_empty += 1;
             continue;
-        }
+        };
         
-        match target_ratings.get(&rating.imdb_id) {
+        match target_ratings.get(&key) {
             None => {
                 // New rating - doesn't exist in target
                 filtered.push(rating.clone());
                 if filtered.len() <= 5 {
                     debug!(
-                        "filter_ratings_by_imdb_id_and_value: Adding new rating imdb_id={}, rating={}",
-                        rating.imdb_id, rating.rating
+                        "filter_ratings_by_imdb_id_and_value: Adding new rating id={}, rating={}",
+                        key, rating.rating
                     );
                 }
             }
@@ -499,8 +531,8 @@ pub fn filter_ratings_by_imdb_id_and_value(
                     filtered.push(rating.clone());
                     if filtered.len() <= 5 {
                         debug!(
-                            "filter_ratings_by_imdb_id_and_value: Adding changed rating imdb_id={}, old_rating={}, new_rating={}",
-                            rating.imdb_id, existing_rating, rating.rating
+                            "filter_ratings_by_imdb_id_and_value: Adding changed rating id={}, old_rating={}, new_rating={}",
+                            key, existing_rating, rating.rating
                         );
                     }
                 } else {
@@ -508,8 +540,8 @@ pub fn filter_ratings_by_imdb_id_and_value(
                     skipped_unchanged += 1;
                     if skipped_unchanged <= 5 {
                         debug!(
-                            "filter_ratings_by_imdb_id_and_value: Skipping unchanged rating imdb_id={}, rating={}",
-                            rating.imdb_id, rating.rating
+                            "filter_ratings_by_imdb_id_and_value: Skipping unchanged rating id={}, rating={}",
+                            key, rating.rating
                         );
                     }
                 }
@@ -533,7 +565,7 @@ pub fn filter_ratings_by_imdb_id_and_value(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use media_sync_models::{MediaType, Rating, RatingSource, WatchlistItem};
+    use media_sync_models::{MediaType, Rating, RatingSource, WatchHistory, WatchlistItem};
     use chrono::Utc;
 
     fn create_watchlist_item(imdb_id: &str, title: &str) -> WatchlistItem {
@@ -546,6 +578,7 @@ mod tests {
             date_added: Utc::now(),
             source: "test".to_string(),
             status: None,
+            tags: Vec::new(),
         }
     }
 
@@ -554,9 +587,11 @@ mod tests {
             imdb_id: imdb_id.to_string(),
             ids: None,
             rating,
+            rating_f64: None,
             date_added: Utc::now(),
             media_type: MediaType::Movie,
             source: RatingSource::Imdb,
+            inferred: false,
         }
     }
 
@@ -669,5 +704,100 @@ mod tests {
         assert_eq!(filtered[0].imdb_id, "tt002");
         assert_eq!(filtered[1].imdb_id, "tt003");
     }
+
+    #[test]
+    fn test_filter_reviews_with_emoji_at_key_boundary() {
+        let review = |imdb_id: &str, content: String| media_sync_models::Review {
+            imdb_id: imdb_id.to_string(),
+            ids: None,
+            content,
+            date_added: Utc::now(),
+            media_type: MediaType::Movie,
+            source: "test".to_string(),
+            is_spoiler: false,
+            rating: None,
+            review_id: None,
+        };
+        // The emoji straddles byte 100, which used to panic when slicing the content key
+        let content = format!("{}🎬 and more", "a".repeat(99));
+        let source = vec![review("tt001", content.clone()), review("tt002", content.clone())];
+        let target = vec![review("tt001", content)];
+
+        let filtered = filter_reviews_by_imdb_id_and_content(&source, &target, &Default::default());
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].imdb_id, "tt002");
+    }
+
+    #[test]
+    fn test_filter_reviews_ignores_crlf_and_trailing_whitespace() {
+        let review = |imdb_id: &str, content: &str| media_sync_models::Review {
+            imdb_id: imdb_id.to_string(),
+            ids: None,
+            content: content.to_string(),
+            date_added: Utc::now(),
+            media_type: MediaType::Movie,
+            source: "test".to_string(),
+            is_spoiler: false,
+            rating: None,
+            review_id: None,
+        };
+        // IMDB returns the review with CRLF endings, Trakt with LF
+        let source = vec![
+            review("tt001", "Great film.\r\n\r\nThe ending drags.  \r\n"),
+            review("tt002", "Great film.\r\nThe ending drags."),
+        ];
+        let target = vec![
+            review("tt001", "Great film.\n\nThe ending drags."),
+            review("tt002", "Great film.\n\nThe ending drags."),
+        ];
+
+        // A blank line still counts as a difference
+        let filtered = filter_reviews_by_imdb_id_and_content(&source, &target, &Default::default());
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].imdb_id, "tt002");
+    }
+
+    #[test]
+    fn test_ratings_without_imdb_id_match_on_anilist_id() {
+        let anime = |anilist_id: u64, rating: u8| Rating {
+            ids: Some(media_sync_models::MediaIds { anilist_id: Some(anilist_id), ..Default::default() }),
+            ..create_rating("", rating)
+        };
+        let source = vec![anime(21, 9), anime(1535, 8), anime(5114, 10), create_rating("", 7)];
+        let target = vec![anime(21, 9), anime(1535, 6)];
+
+        let filtered = filter_ratings_by_imdb_id_and_value(&source, &target);
+        let kept: Vec<_> = filtered.iter().map(|rating| rating.ids.as_ref().unwrap().anilist_id).collect();
+        assert_eq!(kept, vec![Some(1535), Some(5114)]);
+    }
+
+    #[test]
+    fn test_items_without_imdb_id_match_on_anilist_or_mal_id() {
+        let anime = |anilist_id: Option<u64>, mal_id: Option<u64>| WatchlistItem {
+            ids: Some(media_sync_models::MediaIds { anilist_id, mal_id, ..Default::default() }),
+            ..create_watchlist_item("", "Anime")
+        };
+        let source = vec![anime(Some(21), None), anime(Some(1535), Some(1535)), anime(Some(5114), None)];
+        let target = vec![anime(Some(21), Some(21)), anime(None, Some(1535))];
+
+        let filtered = filter_items_by_imdb_id(&source, &target);
+        let kept: Vec<_> = filtered.iter().map(|item| item.ids.as_ref().unwrap().anilist_id).collect();
+        assert_eq!(kept, vec![Some(5114)]);
+
+        let history = |anilist_id: u64| WatchHistory {
+            imdb_id: String::new(),
+            ids: Some(media_sync_models::MediaIds { anilist_id: Some(anilist_id), ..Default::default() }),
+            title: None,
+            year: None,
+            watched_at: Utc::now(),
+            media_type: MediaType::Show,
+            source: "anilist".to_string(),
+            watched_percent: None,
+            show_ids: None,
+        };
+        let missing = filter_items_by_imdb_id(&[history(21), history(5114)], &[history(21)]);
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].ids.as_ref().unwrap().anilist_id, Some(5114));
+    }
 }
 
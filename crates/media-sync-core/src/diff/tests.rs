@@ -14,6 +14,7 @@ mod tests {
             date_added: Utc::now(),
             source: "test".to_string(),
             status: None,
+            tags: Vec::new(),
         }
     }
 
@@ -25,6 +26,7 @@ mod tests {
             date_added: Utc::now(),
             media_type: MediaType::Movie,
             source: RatingSource::Imdb,
+            inferred: false,
         }
     }
 
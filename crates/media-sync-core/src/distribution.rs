@@ -2,16 +2,80 @@
 // Handles filtering, transformation, normalization, and incremental sync
 
 use anyhow::Result;
-use chrono::{DateTime, Timelike, Utc};
+use chrono::{DateTime, Datelike, Timelike, Utc};
 use media_sync_config::{CredentialStore, PathManager};
-use media_sync_models::{Rating, RatingSource, Review, WatchHistory, WatchlistItem, NormalizedStatus, MediaType, ExcludedItem};
+use media_sync_models::{MediaIds, Rating, RatingSource, Review, WatchHistory, WatchlistItem, NormalizedStatus, MediaType, ExcludedItem};
 use std::sync::Mutex;
 use std::collections::HashMap;
-use tracing::{info, warn};
+use tracing::{debug, info, warn};
 use crate::diff::{filter_items_by_imdb_id, filter_ratings_by_imdb_id_and_value, filter_reviews_by_imdb_id_and_content};
 use crate::resolution::SourceData;
 use crate::cache::CacheManager;
 
+/// Highest value of the 1-10 scale ratings are normalized to before resolution.
+/// `prepare_ratings` works on this scale; ratings are converted to the target's
+/// `native_rating_scale` only when they are sent.
+pub const NORMALIZED_RATING_SCALE: u8 = 10;
+
+/// Clamp ratings into `1..=scale`, warning about each one that was out of range, so a
+/// bad value is corrected instead of making the target's API reject the whole batch.
+/// Returns how many ratings were clamped.
+pub fn clamp_ratings_to_scale(ratings: &mut [Rating], scale: u8, target_source: &str) -> usize {
+    let mut clamped = 0;
+    for rating in ratings.iter_mut() {
+        let value = rating.value().clamp(1.0, scale as f64);
+        if value != rating.value() {
+            warn!(
+                imdb_id = %rating.imdb_id,
+                target = target_source,
+                "Rating {} is outside {}'s 1-{} scale, sending {} instead",
+                rating.value(),
+                target_source,
+                scale,
+                value
+            );
+            rating.set_value(value);
+            clamped += 1;
+        }
+    }
+    clamped
+}
+
+/// Most items IMDB keeps in a watchlist or in the check-ins list; it rejects additions
+/// past it
+pub const IMDB_ITEM_LIMIT: usize = 10_000;
+
+/// Keep the oldest of `items` that fit next to the `existing` ones under `limit`, and
+/// return the newer ones that don't, oldest first
+pub fn fit_to_limit<T>(
+    items: &mut Vec<T>,
+    existing: usize,
+    limit: usize,
+    get_timestamp: impl Fn(&T) -> DateTime<Utc>,
+) -> Vec<T> {
+    let capacity = limit.saturating_sub(existing);
+    if items.len() <= capacity {
+        return Vec::new();
+    }
+    items.sort_by_key(|item| get_timestamp(item));
+    items.split_off(capacity)
+}
+
+/// Split watch history into one batch per watched year, oldest year first, so a
+/// `watch_history.chunk_by_year` push that fails midway has only sent whole years
+pub fn chunk_watch_history_by_year(items: &[WatchHistory]) -> Vec<Vec<WatchHistory>> {
+    let mut sorted = items.to_vec();
+    sorted.sort_by_key(|item| item.watched_at);
+    let mut chunks: Vec<Vec<WatchHistory>> = Vec::new();
+    for item in sorted {
+        match chunks.last_mut() {
+            Some(chunk) if chunk[0].watched_at.year() == item.watched_at.year() => chunk.push(item),
+            _ => chunks.push(vec![item]),
+        }
+    }
+    chunks
+}
+
 /// Result type for watchlist preparation (can split into watchlist + watch_history)
 #[derive(Debug, Clone)]
 pub struct DistributionResult<T, U> {
@@ -81,13 +145,31 @@ pub trait DistributionStrategy: Send + Sync {
         data_type: &str,
         items_synced: usize,
     ) -> Result<()>;
+
+    /// Called after each year of a chunked watch history push, with the latest
+    /// `watched_at` sent so far, so the next run resumes after it if a later year fails.
+    /// A stored timestamp later than `watched_at` is kept.
+    fn on_watch_history_synced_up_to(&self, _watched_at: DateTime<Utc>) -> Result<()> {
+        Ok(())
+    }
+
+    /// Items the prepare calls since the last call left out because the target has no
+    /// room for them (IMDB's list limits), for the `over_limit` distribute file
+    fn take_over_limit(&self) -> Vec<ExcludedItem> {
+        Vec::new()
+    }
 }
 
+/// Held while a strategy reloads, updates and saves the credentials file
+static SYNC_TIMESTAMP_SAVE: Mutex<()> = Mutex::new(());
+
 /// Default strategy: incremental sync + deduplication, no transformation
 pub struct DefaultDistributionStrategy {
     cred_store: Mutex<CredentialStore>,
     target_source: String,
     cache_manager: Option<CacheManager>,
+    /// Lower bound used instead of the saved sync timestamps (`sync --since`)
+    since: Option<DateTime<Utc>>,
 }
 
 impl DefaultDistributionStrategy {
@@ -99,6 +181,7 @@ impl DefaultDistributionStrategy {
             cred_store: Mutex::new(cred_store),
             target_source: target_source.to_string(),
             cache_manager: None,
+            since: None,
         })
     }
     
@@ -107,6 +190,13 @@ impl DefaultDistributionStrategy {
         self
     }
     
+    /// Send only items dated `since` or later, whatever the saved timestamps and
+    /// `force_full_sync` say
+    pub fn with_since(mut self, since: Option<DateTime<Utc>>) -> Self {
+        self.since = since;
+        self
+    }
+    
     /// Apply incremental sync timestamp filtering
     /// Returns (included_items, excluded_items)
     fn apply_incremental_sync_filter<T>(
@@ -117,11 +207,12 @@ impl DefaultDistributionStrategy {
         force_full_sync: bool,
         get_timestamp: impl Fn(&T) -> Option<DateTime<Utc>>,
     ) -> Result<(Vec<T>, Vec<T>)> {
-        if force_full_sync {
-            return Ok((items, Vec::new()));
-        }
-        
-        let last_sync = self.cred_store.lock().unwrap().get_last_sync_timestamp(target_source, data_type);
+        // An explicit since wins over both the saved timestamp and a forced full sync
+        let last_sync = match self.since {
+            Some(since) => Some(since),
+            None if force_full_sync => return Ok((items, Vec::new())),
+            None => self.cred_store.lock().unwrap().get_last_sync_timestamp(target_source, data_type),
+        };
         Ok(Self::filter_by_timestamp(items, last_sync, get_timestamp))
     }
     
@@ -156,7 +247,7 @@ impl DefaultDistributionStrategy {
                     if let Err(e) = cache_manager.save_excluded(&source, &excluded) {
                         warn!("Failed to save excluded items for {} {} to cache: {}", source, data_type, e);
                     } else {
-                        info!("Saved {} excluded items for {} {} to cache ({})", excluded.len(), source, data_type, reason);
+                        debug!("Saved {} excluded items for {} {} to cache ({})", excluded.len(), source, data_type, reason);
                     }
                 }
             }
@@ -211,8 +302,40 @@ impl DefaultDistributionStrategy {
         target_source: &str,
         data_type: &str,
     ) -> Result<()> {
+        self.set_sync_timestamp(target_source, data_type, Utc::now())
+    }
+
+    fn set_sync_timestamp(
+        &self,
+        target_source: &str,
+        data_type: &str,
+        timestamp: DateTime<Utc>,
+    ) -> Result<()> {
+        // Targets are written concurrently, each strategy with its own store, so start from
+        // the file as the others left it instead of as it was when this one was created
+        let _saving = SYNC_TIMESTAMP_SAVE.lock().unwrap();
+        let mut cred_store = self.cred_store.lock().unwrap();
+        cred_store.load()?;
+        cred_store.set_last_sync_timestamp(target_source, data_type, timestamp);
+        cred_store.save()?;
+        Ok(())
+    }
+
+    /// Move a sync timestamp forward to `timestamp`, leaving a later one alone, so a
+    /// chunked push of older years doesn't make the next run resend what was sent since
+    fn advance_sync_timestamp(
+        &self,
+        target_source: &str,
+        data_type: &str,
+        timestamp: DateTime<Utc>,
+    ) -> Result<()> {
+        let _saving = SYNC_TIMESTAMP_SAVE.lock().unwrap();
         let mut cred_store = self.cred_store.lock().unwrap();
-        cred_store.set_last_sync_timestamp(target_source, data_type, Utc::now());
+        cred_store.load()?;
+        if cred_store.get_last_sync_timestamp(target_source, data_type).is_some_and(|existing| existing >= timestamp) {
+            return Ok(());
+        }
+        cred_store.set_last_sync_timestamp(target_source, data_type, timestamp);
         cred_store.save()?;
         Ok(())
     }
@@ -285,7 +408,7 @@ impl DistributionStrategy for DefaultDistributionStrategy {
         
         // For deduplication, we can't easily reconstruct excluded items, but we log the count
         if excluded_dedup_count > 0 {
-            info!("Deduplication filtered out {} watchlist items (already exist in target)", excluded_dedup_count);
+            debug!("Deduplication filtered out {} watchlist items (already exist in target)", excluded_dedup_count);
         }
         
         // 4. Filter out watched items if remove_watched_from_watchlists is enabled
@@ -356,6 +479,8 @@ impl DistributionStrategy for DefaultDistributionStrategy {
                 RatingSource::Imdb => "imdb",
                 RatingSource::Netflix => "netflix",
                 RatingSource::Tmdb => "tmdb",
+                RatingSource::Letterboxd => "letterboxd",
+                RatingSource::AniList => "anilist",
             };
             ExcludedItem {
                 title: None, // Ratings don't have titles
@@ -379,6 +504,8 @@ impl DistributionStrategy for DefaultDistributionStrategy {
                 RatingSource::Imdb => "imdb",
                 RatingSource::Netflix => "netflix",
                 RatingSource::Tmdb => "tmdb",
+                RatingSource::Letterboxd => "letterboxd",
+                RatingSource::AniList => "anilist",
             };
             if item_source == target_source {
                 excluded_source.push(item.clone());
@@ -396,6 +523,8 @@ impl DistributionStrategy for DefaultDistributionStrategy {
                 RatingSource::Imdb => "imdb",
                 RatingSource::Netflix => "netflix",
                 RatingSource::Tmdb => "tmdb",
+                RatingSource::Letterboxd => "letterboxd",
+                RatingSource::AniList => "anilist",
             };
             ExcludedItem {
                 title: None, // Ratings don't have titles
@@ -408,13 +537,16 @@ impl DistributionStrategy for DefaultDistributionStrategy {
             }
         });
         
-        // 3. Apply IMDB ID + value deduplication
+        // 3. Clamp values outside the rating scale
+        clamp_ratings_to_scale(&mut filtered, NORMALIZED_RATING_SCALE, target_source);
+        
+        // 4. Apply IMDB ID + value deduplication
         let before_dedup = filtered.len();
         let result = filter_ratings_by_imdb_id_and_value(&filtered, &existing.ratings);
         let excluded_dedup_count = before_dedup - result.len();
         
         if excluded_dedup_count > 0 {
-            info!("Deduplication filtered out {} ratings (already exist in target)", excluded_dedup_count);
+            debug!("Deduplication filtered out {} ratings (already exist in target)", excluded_dedup_count);
         }
         
         Ok(result)
@@ -473,13 +605,17 @@ impl DistributionStrategy for DefaultDistributionStrategy {
             }
         });
         
-        // 3. Apply IMDB ID + content deduplication
+        // 3. Apply IMDB ID + content deduplication, counting reviews sent moments ago as
+        // present while the target's API catches up
         let before_dedup = filtered.len();
-        let result = filter_reviews_by_imdb_id_and_content(&filtered, &existing.reviews);
+        let recently_submitted = self.cache_manager.as_ref()
+            .map(|cache_manager| cache_manager.recently_submitted_reviews(target_source))
+            .unwrap_or_default();
+        let result = filter_reviews_by_imdb_id_and_content(&filtered, &existing.reviews, &recently_submitted);
         let excluded_dedup_count = before_dedup - result.len();
         
         if excluded_dedup_count > 0 {
-            info!("Deduplication filtered out {} reviews (already exist in target)", excluded_dedup_count);
+            debug!("Deduplication filtered out {} reviews (already exist in target)", excluded_dedup_count);
         }
         
         Ok(result)
@@ -547,7 +683,7 @@ impl DistributionStrategy for DefaultDistributionStrategy {
         let excluded_dedup_count = before_dedup - result.len();
         
         if excluded_dedup_count > 0 {
-            info!("Deduplication filtered out {} watch history items (already exist in target)", excluded_dedup_count);
+            debug!("Deduplication filtered out {} watch history items (already exist in target)", excluded_dedup_count);
         }
         
         Ok(result)
@@ -561,6 +697,10 @@ impl DistributionStrategy for DefaultDistributionStrategy {
         // Update sync timestamp after successful sync
         self.update_sync_timestamp(self.target_source_name(), data_type)
     }
+
+    fn on_watch_history_synced_up_to(&self, watched_at: DateTime<Utc>) -> Result<()> {
+        self.advance_sync_timestamp(self.target_source_name(), "watch_history", watched_at)
+    }
 }
 
 
@@ -581,6 +721,11 @@ impl TraktDistributionStrategy {
         self
     }
     
+    pub fn with_since(mut self, since: Option<DateTime<Utc>>) -> Self {
+        self.base = self.base.with_since(since);
+        self
+    }
+    
     fn split_by_status(items: &[WatchlistItem]) -> (Vec<WatchlistItem>, Vec<WatchHistory>) {
         let mut watchlist_items = Vec::new();
         let mut watch_history_items = Vec::new();
@@ -598,6 +743,8 @@ impl TraktDistributionStrategy {
                             watched_at: item.date_added,
                             media_type: item.media_type.clone(),
                             source: item.source.clone(), // Preserve original source, don't hardcode target source
+                            watched_percent: None,
+                            show_ids: None,
                         });
                     }
                 }
@@ -683,25 +830,50 @@ impl DistributionStrategy for TraktDistributionStrategy {
     ) -> Result<()> {
         self.base.on_sync_complete(data_type, items_synced)
     }
+
+    fn on_watch_history_synced_up_to(&self, watched_at: DateTime<Utc>) -> Result<()> {
+        self.base.on_watch_history_synced_up_to(watched_at)
+    }
 }
 
-/// IMDB-specific: converts watchlist items with Watching/Completed status to check-ins
+/// IMDB-specific: converts watchlist items with Watching/Completed status to check-ins,
+/// and keeps the watchlist and check-ins within IMDB's item limit
 pub struct ImdbDistributionStrategy {
     base: DefaultDistributionStrategy,
+    /// Items IMDB takes per list (`IMDB_ITEM_LIMIT`)
+    item_limit: usize,
+    /// Check-ins made from watchlist items, counted against the limit for watch history
+    planned_checkins: Mutex<usize>,
+    /// Additions left out because a list is full, until `take_over_limit`
+    over_limit: Mutex<Vec<ExcludedItem>>,
 }
 
 impl ImdbDistributionStrategy {
     pub fn new() -> Result<Self> {
         Ok(Self {
             base: DefaultDistributionStrategy::new("imdb")?,
+            item_limit: IMDB_ITEM_LIMIT,
+            planned_checkins: Mutex::new(0),
+            over_limit: Mutex::new(Vec::new()),
         })
     }
     
+    /// Items IMDB takes per list, instead of `IMDB_ITEM_LIMIT`
+    pub fn with_item_limit(mut self, item_limit: usize) -> Self {
+        self.item_limit = item_limit;
+        self
+    }
+    
     pub fn with_cache_manager(mut self, cache_manager: CacheManager) -> Self {
         self.base = self.base.with_cache_manager(cache_manager);
         self
     }
     
+    pub fn with_since(mut self, since: Option<DateTime<Utc>>) -> Self {
+        self.base = self.base.with_since(since);
+        self
+    }
+    
     fn transform_to_checkins(items: &[WatchlistItem]) -> Vec<WatchHistory> {
         items.iter()
             .filter_map(|item| {
@@ -715,6 +887,8 @@ impl ImdbDistributionStrategy {
                             watched_at: item.date_added,
                             media_type: item.media_type.clone(),
                             source: item.source.clone(), // Preserve original source, don't hardcode target source
+                            watched_percent: None,
+                            show_ids: None,
                         })
                     }
                     _ => None,
@@ -722,6 +896,59 @@ impl ImdbDistributionStrategy {
             })
             .collect()
     }
+    
+    /// Leave out the additions that don't fit in an IMDB list already holding `existing`
+    /// items, keeping the oldest, and record the rest as over the limit
+    fn apply_item_limit<T>(
+        &self,
+        items: &mut Vec<T>,
+        existing: usize,
+        list: &str,
+        get_timestamp: impl Fn(&T) -> DateTime<Utc>,
+        get_excluded_item: impl Fn(&T) -> ExcludedItem,
+    ) {
+        let dropped = fit_to_limit(items, existing, self.item_limit, get_timestamp);
+        if dropped.is_empty() {
+            return;
+        }
+        warn!(
+            "IMDB {} holds at most {} items and has {}: adding the oldest {}, leaving out {} (listed in distribute/imdb/over_limit.json)",
+            list,
+            self.item_limit,
+            existing,
+            items.len(),
+            dropped.len()
+        );
+        let reason = format!("IMDB {} limit of {} items reached", list, self.item_limit);
+        self.over_limit.lock().unwrap().extend(dropped.iter().map(|item| ExcludedItem {
+            reason: reason.clone(),
+            ..get_excluded_item(item)
+        }));
+    }
+    
+    fn excluded_watchlist_item(item: &WatchlistItem) -> ExcludedItem {
+        ExcludedItem {
+            title: Some(item.title.clone()),
+            imdb_id: if item.imdb_id.is_empty() { None } else { Some(item.imdb_id.clone()) },
+            rating_key: None,
+            media_type: format!("{:?}", item.media_type),
+            reason: String::new(),
+            source: item.source.clone(),
+            date_added: Some(item.date_added),
+        }
+    }
+    
+    fn excluded_watch_history(item: &WatchHistory) -> ExcludedItem {
+        ExcludedItem {
+            title: item.title.clone(),
+            imdb_id: if item.imdb_id.is_empty() { None } else { Some(item.imdb_id.clone()) },
+            rating_key: None,
+            media_type: format!("{:?}", item.media_type),
+            reason: String::new(),
+            source: item.source.clone(),
+            date_added: None,
+        }
+    }
 }
 
 impl DistributionStrategy for ImdbDistributionStrategy {
@@ -744,11 +971,11 @@ impl DistributionStrategy for ImdbDistributionStrategy {
         let checkins = Self::transform_to_checkins(&base_result.for_watchlist);
         
         // 3. Filter check-ins against existing watch_history
-        let filtered_checkins = filter_items_by_imdb_id(&checkins, &existing.watch_history);
+        let mut filtered_checkins = filter_items_by_imdb_id(&checkins, &existing.watch_history);
         
         // 4. Filter watchlist items (remove those that became check-ins)
         // Note: watched items are already filtered in base.prepare_watchlist if remove_watched_from_watchlists is enabled
-        let watchlist_items: Vec<_> = base_result.for_watchlist.iter()
+        let mut watchlist_items: Vec<_> = base_result.for_watchlist.iter()
             .filter(|item| {
                 item.status.as_ref()
                     .map(|s| matches!(s, NormalizedStatus::Watchlist))
@@ -757,6 +984,11 @@ impl DistributionStrategy for ImdbDistributionStrategy {
             .cloned()
             .collect();
         
+        // 5. Keep both lists within IMDB's limit
+        self.apply_item_limit(&mut watchlist_items, existing.watchlist.len(), "watchlist", |item| item.date_added, Self::excluded_watchlist_item);
+        self.apply_item_limit(&mut filtered_checkins, existing.watch_history.len(), "watch history", |item| item.watched_at, Self::excluded_watch_history);
+        *self.planned_checkins.lock().unwrap() = filtered_checkins.len();
+        
         Ok(DistributionResult {
             for_watchlist: watchlist_items,
             for_watch_history: filtered_checkins,
@@ -787,7 +1019,11 @@ impl DistributionStrategy for ImdbDistributionStrategy {
         existing: &SourceData,
         force_full_sync: bool,
     ) -> Result<Vec<WatchHistory>> {
-        self.base.prepare_watch_history(items, existing, force_full_sync)
+        let mut prepared = self.base.prepare_watch_history(items, existing, force_full_sync)?;
+        // Check-ins from the watchlist take their room first
+        let planned_checkins = *self.planned_checkins.lock().unwrap();
+        self.apply_item_limit(&mut prepared, existing.watch_history.len() + planned_checkins, "watch history", |item| item.watched_at, Self::excluded_watch_history);
+        Ok(prepared)
     }
     
     fn on_sync_complete(
@@ -797,19 +1033,40 @@ impl DistributionStrategy for ImdbDistributionStrategy {
     ) -> Result<()> {
         self.base.on_sync_complete(data_type, items_synced)
     }
+
+    fn on_watch_history_synced_up_to(&self, watched_at: DateTime<Utc>) -> Result<()> {
+        self.base.on_watch_history_synced_up_to(watched_at)
+    }
+
+    fn take_over_limit(&self) -> Vec<ExcludedItem> {
+        std::mem::take(&mut *self.over_limit.lock().unwrap())
+    }
 }
 
 /// Simkl-specific: no incremental sync (has native), but still needs deduplication
 pub struct SimklDistributionStrategy {
     target_source: String,
+    /// Only items dated this or later are sent (`sync --since`)
+    since: Option<DateTime<Utc>>,
 }
 
 impl SimklDistributionStrategy {
     pub fn new() -> Result<Self> {
         Ok(Self {
             target_source: "simkl".to_string(),
+            since: None,
         })
     }
+    
+    pub fn with_since(mut self, since: Option<DateTime<Utc>>) -> Self {
+        self.since = since;
+        self
+    }
+    
+    /// Drop items dated before `since`, compared as the other strategies do
+    fn filter_since<T>(&self, items: Vec<T>, get_timestamp: impl Fn(&T) -> Option<DateTime<Utc>>) -> Vec<T> {
+        DefaultDistributionStrategy::filter_by_timestamp(items, self.since, get_timestamp).0
+    }
 }
 
 impl DistributionStrategy for SimklDistributionStrategy {
@@ -827,10 +1084,11 @@ impl DistributionStrategy for SimklDistributionStrategy {
     ) -> Result<DistributionResult<WatchlistItem, WatchHistory>> {
         // 1. Filter out items that came from the target source (they already exist there)
         let target_source = self.target_source_name();
-        let mut filtered_by_source: Vec<_> = items.iter()
+        let filtered_by_source: Vec<_> = items.iter()
             .filter(|item| item.source != target_source)
             .cloned()
             .collect();
+        let filtered_by_source = self.filter_since(filtered_by_source, |item| Some(item.date_added));
         
         // 2. Only deduplication, no incremental sync (Simkl handles it natively)
         let mut deduped = filter_items_by_imdb_id(&filtered_by_source, &existing.watchlist);
@@ -866,7 +1124,7 @@ impl DistributionStrategy for SimklDistributionStrategy {
     ) -> Result<Vec<Rating>> {
         // 1. Filter out items that came from the target source (they already exist there)
         let target_source = self.target_source_name();
-        let filtered_by_source: Vec<_> = items.iter()
+        let mut filtered_by_source: Vec<_> = items.iter()
             .filter(|item| {
                 // Convert RatingSource enum to lowercase string for comparison
                 let item_source = match &item.source {
@@ -875,13 +1133,19 @@ impl DistributionStrategy for SimklDistributionStrategy {
                     RatingSource::Imdb => "imdb",
                     RatingSource::Netflix => "netflix",
                     RatingSource::Tmdb => "tmdb",
+                    RatingSource::Letterboxd => "letterboxd",
+                    RatingSource::AniList => "anilist",
                 };
                 item_source != target_source
             })
             .cloned()
             .collect();
+        filtered_by_source = self.filter_since(filtered_by_source, |item| Some(item.date_added));
         
-        // 2. Only deduplication
+        // 2. Clamp values outside the rating scale
+        clamp_ratings_to_scale(&mut filtered_by_source, NORMALIZED_RATING_SCALE, target_source);
+        
+        // 3. Only deduplication
         Ok(filter_ratings_by_imdb_id_and_value(&filtered_by_source, &existing.ratings))
     }
     
@@ -897,9 +1161,10 @@ impl DistributionStrategy for SimklDistributionStrategy {
             .filter(|item| item.source != target_source)
             .cloned()
             .collect();
+        let filtered_by_source = self.filter_since(filtered_by_source, |item| Some(item.date_added));
         
         // 2. Only deduplication
-        Ok(filter_reviews_by_imdb_id_and_content(&filtered_by_source, &existing.reviews))
+        Ok(filter_reviews_by_imdb_id_and_content(&filtered_by_source, &existing.reviews, &Default::default()))
     }
     
     fn prepare_watch_history(
@@ -914,6 +1179,7 @@ impl DistributionStrategy for SimklDistributionStrategy {
             .filter(|item| item.source != target_source)
             .cloned()
             .collect();
+        let filtered_by_source = self.filter_since(filtered_by_source, |item| Some(item.watched_at));
         
         // 2. Only deduplication
         Ok(filter_items_by_imdb_id(&filtered_by_source, &existing.watch_history))
@@ -946,6 +1212,11 @@ impl PlexDistributionStrategy {
         self
     }
     
+    pub fn with_since(mut self, since: Option<DateTime<Utc>>) -> Self {
+        self.base = self.base.with_since(since);
+        self
+    }
+    
     fn split_by_status(items: &[WatchlistItem]) -> (Vec<WatchlistItem>, Vec<WatchHistory>) {
         let mut watchlist_items = Vec::new();
         let mut watch_history_items = Vec::new();
@@ -966,6 +1237,8 @@ impl PlexDistributionStrategy {
                         watched_at: item.date_added,
                         media_type: item.media_type.clone(),
                         source: item.source.clone(), // Preserve original source, don't hardcode target source
+                        watched_percent: None,
+                        show_ids: None,
                     });
                 }
                 _ => {
@@ -1045,5 +1318,411 @@ impl DistributionStrategy for PlexDistributionStrategy {
     ) -> Result<()> {
         self.base.on_sync_complete(data_type, items_synced)
     }
+
+    fn on_watch_history_synced_up_to(&self, watched_at: DateTime<Utc>) -> Result<()> {
+        self.base.on_watch_history_synced_up_to(watched_at)
+    }
+}
+
+/// Letterboxd-specific: only watchlist additions can be written (to an import file), so
+/// ratings, reviews and watch history are never prepared, and items already watched
+/// (Completed/Watching) are not added to the watchlist
+pub struct LetterboxdDistributionStrategy {
+    base: DefaultDistributionStrategy,
+}
+
+impl LetterboxdDistributionStrategy {
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            base: DefaultDistributionStrategy::new("letterboxd")?,
+        })
+    }
+    
+    pub fn with_cache_manager(mut self, cache_manager: CacheManager) -> Self {
+        self.base = self.base.with_cache_manager(cache_manager);
+        self
+    }
+    
+    pub fn with_since(mut self, since: Option<DateTime<Utc>>) -> Self {
+        self.base = self.base.with_since(since);
+        self
+    }
 }
 
+impl DistributionStrategy for LetterboxdDistributionStrategy {
+    fn target_source_name(&self) -> &str {
+        self.base.target_source_name()
+    }
+    
+    fn prepare_watchlist(
+        &self,
+        items: &[WatchlistItem],
+        existing: &SourceData,
+        force_full_sync: bool,
+        resolved_watch_history: &[WatchHistory],
+        remove_watched_from_watchlists: bool,
+    ) -> Result<DistributionResult<WatchlistItem, WatchHistory>> {
+        let base_result = self.base.prepare_watchlist(items, existing, force_full_sync, resolved_watch_history, remove_watched_from_watchlists)?;
+        let for_watchlist = base_result.for_watchlist.into_iter()
+            .filter(|item| matches!(item.status, None | Some(NormalizedStatus::Watchlist)))
+            .collect();
+        Ok(DistributionResult {
+            for_watchlist,
+            for_watch_history: Vec::new(),
+        })
+    }
+    
+    fn prepare_ratings(
+        &self,
+        items: &[Rating],
+        _existing: &SourceData,
+        _force_full_sync: bool,
+    ) -> Result<Vec<Rating>> {
+        debug!("Letterboxd ratings can't be written; skipping {} ratings", items.len());
+        Ok(Vec::new())
+    }
+    
+    fn prepare_reviews(
+        &self,
+        _items: &[Review],
+        _existing: &SourceData,
+        _force_full_sync: bool,
+    ) -> Result<Vec<Review>> {
+        Ok(Vec::new())
+    }
+    
+    fn prepare_watch_history(
+        &self,
+        items: &[WatchHistory],
+        _existing: &SourceData,
+        _force_full_sync: bool,
+    ) -> Result<Vec<WatchHistory>> {
+        debug!("Letterboxd watch history can't be written; skipping {} plays", items.len());
+        Ok(Vec::new())
+    }
+    
+    fn on_sync_complete(
+        &self,
+        data_type: &str,
+        items_synced: usize,
+    ) -> Result<()> {
+        self.base.on_sync_complete(data_type, items_synced)
+    }
+}
+
+/// Whether AniList can place a title: it has an AniList ID, or a MAL ID to look one up by
+fn has_anilist_id(ids: Option<&MediaIds>) -> bool {
+    ids.is_some_and(|ids| ids.anilist_id.is_some() || ids.mal_id.is_some())
+}
+
+/// AniList-specific: titles are matched by AniList (or MAL) ID, so only items carrying one
+/// are prepared; any other resolved movie or show isn't on AniList. Episodes count by their
+/// series' ID and only in season 1, the way AniList numbers them; reviews can't be written.
+pub struct AniListDistributionStrategy {
+    base: DefaultDistributionStrategy,
+}
+
+impl AniListDistributionStrategy {
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            base: DefaultDistributionStrategy::new("anilist")?,
+        })
+    }
+    
+    pub fn with_cache_manager(mut self, cache_manager: CacheManager) -> Self {
+        self.base = self.base.with_cache_manager(cache_manager);
+        self
+    }
+    
+    pub fn with_since(mut self, since: Option<DateTime<Utc>>) -> Self {
+        self.base = self.base.with_since(since);
+        self
+    }
+
+    fn on_anilist(item: &WatchHistory) -> bool {
+        match item.media_type {
+            MediaType::Episode { season: 1, .. } => has_anilist_id(item.show_ids.as_ref()),
+            MediaType::Episode { .. } => false,
+            _ => has_anilist_id(item.ids.as_ref()),
+        }
+    }
+}
+
+impl DistributionStrategy for AniListDistributionStrategy {
+    fn target_source_name(&self) -> &str {
+        self.base.target_source_name()
+    }
+    
+    fn prepare_watchlist(
+        &self,
+        items: &[WatchlistItem],
+        existing: &SourceData,
+        force_full_sync: bool,
+        resolved_watch_history: &[WatchHistory],
+        remove_watched_from_watchlists: bool,
+    ) -> Result<DistributionResult<WatchlistItem, WatchHistory>> {
+        let base_result = self.base.prepare_watchlist(items, existing, force_full_sync, resolved_watch_history, remove_watched_from_watchlists)?;
+        Ok(DistributionResult {
+            for_watchlist: base_result.for_watchlist.into_iter()
+                .filter(|item| has_anilist_id(item.ids.as_ref()))
+                .collect(),
+            for_watch_history: base_result.for_watch_history.into_iter()
+                .filter(Self::on_anilist)
+                .collect(),
+        })
+    }
+    
+    fn prepare_ratings(
+        &self,
+        items: &[Rating],
+        existing: &SourceData,
+        force_full_sync: bool,
+    ) -> Result<Vec<Rating>> {
+        let ratings = self.base.prepare_ratings(items, existing, force_full_sync)?;
+        Ok(ratings.into_iter()
+            .filter(|rating| !matches!(rating.media_type, MediaType::Episode { .. }) && has_anilist_id(rating.ids.as_ref()))
+            .collect())
+    }
+    
+    fn prepare_reviews(
+        &self,
+        _items: &[Review],
+        _existing: &SourceData,
+        _force_full_sync: bool,
+    ) -> Result<Vec<Review>> {
+        Ok(Vec::new())
+    }
+    
+    fn prepare_watch_history(
+        &self,
+        items: &[WatchHistory],
+        existing: &SourceData,
+        force_full_sync: bool,
+    ) -> Result<Vec<WatchHistory>> {
+        let history = self.base.prepare_watch_history(items, existing, force_full_sync)?;
+        Ok(history.into_iter().filter(Self::on_anilist).collect())
+    }
+    
+    fn on_sync_complete(
+        &self,
+        data_type: &str,
+        items_synced: usize,
+    ) -> Result<()> {
+        self.base.on_sync_complete(data_type, items_synced)
+    }
+
+    fn on_watch_history_synced_up_to(&self, watched_at: DateTime<Utc>) -> Result<()> {
+        self.base.on_watch_history_synced_up_to(watched_at)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rating(imdb_id: &str, value: u8) -> Rating {
+        Rating {
+            imdb_id: imdb_id.to_string(),
+            ids: None,
+            rating: value,
+            rating_f64: None,
+            date_added: Utc::now(),
+            media_type: MediaType::Movie,
+            source: RatingSource::Trakt,
+            inferred: false,
+        }
+    }
+
+    #[test]
+    fn test_out_of_range_ratings_are_clamped_not_sent_raw() {
+        let strategy = SimklDistributionStrategy::new().unwrap();
+        let items = vec![rating("tt0000001", 12), rating("tt0000002", 0), rating("tt0000003", 7)];
+        let existing = SourceData {
+            watchlist: Vec::new(),
+            ratings: vec![rating("tt0000001", 10)],
+            reviews: Vec::new(),
+            watch_history: Vec::new(),
+        };
+
+        // 12 becomes 10, which the target already has, so only the other two are sent
+        let prepared = strategy.prepare_ratings(&items, &existing, false).unwrap();
+        let values: Vec<(&str, u8)> = prepared.iter().map(|r| (r.imdb_id.as_str(), r.rating)).collect();
+        assert_eq!(values, vec![("tt0000002", 1), ("tt0000003", 7)]);
+
+        let mut native = vec![rating("tt0000001", 9), rating("tt0000002", 5)];
+        assert_eq!(clamp_ratings_to_scale(&mut native, 5, "letterboxd"), 1);
+        assert_eq!(native[0].rating, 5);
+        assert_eq!(native[1].rating, 5);
+    }
+
+    #[test]
+    fn test_anilist_gets_only_titles_with_an_anilist_or_mal_id() {
+        let with_ids = |imdb_id: &str, anilist_id: Option<u64>, mal_id: Option<u64>| Rating {
+            ids: Some(MediaIds { anilist_id, mal_id, ..MediaIds::default() }),
+            ..rating(imdb_id, 8)
+        };
+        let items = vec![
+            with_ids("", Some(16498), None),
+            with_ids("", None, Some(199)),
+            rating("tt0133093", 9),
+        ];
+        let existing = SourceData {
+            watchlist: Vec::new(),
+            ratings: Vec::new(),
+            reviews: Vec::new(),
+            watch_history: Vec::new(),
+        };
+
+        let strategy = AniListDistributionStrategy::new().unwrap();
+        let prepared = strategy.prepare_ratings(&items, &existing, true).unwrap();
+        let ids: Vec<_> = prepared.iter().map(|r| r.ids.as_ref().and_then(|ids| ids.anilist_id.or(ids.mal_id))).collect();
+        assert_eq!(ids, vec![Some(16498), Some(199)]);
+    }
+
+    #[test]
+    fn test_since_bounds_what_is_sent_even_with_force_full_sync() {
+        let since = "2024-06-01T12:00:00Z".parse().unwrap();
+        let dated = |imdb_id: &str, at: &str| Rating { date_added: at.parse().unwrap(), ..rating(imdb_id, 8) };
+        // Midnight is a date without a time (IMDB exports), so it counts as the same day
+        let items = vec![
+            dated("tt0000001", "2024-05-31T23:00:00Z"),
+            dated("tt0000002", "2024-06-01T00:00:00Z"),
+            dated("tt0000003", "2024-06-03T08:00:00Z"),
+        ];
+        let existing = SourceData {
+            watchlist: Vec::new(),
+            ratings: Vec::new(),
+            reviews: Vec::new(),
+            watch_history: Vec::new(),
+        };
+        let ids = |prepared: Vec<Rating>| prepared.into_iter().map(|r| r.imdb_id).collect::<Vec<_>>();
+
+        let strategy = DefaultDistributionStrategy::new("kodi").unwrap().with_since(Some(since));
+        assert_eq!(ids(strategy.prepare_ratings(&items, &existing, true).unwrap()), vec!["tt0000002", "tt0000003"]);
+        let simkl = SimklDistributionStrategy::new().unwrap().with_since(Some(since));
+        assert_eq!(ids(simkl.prepare_ratings(&items, &existing, true).unwrap()), vec!["tt0000002", "tt0000003"]);
+
+        // Without it, a forced full sync sends everything
+        let strategy = DefaultDistributionStrategy::new("kodi").unwrap();
+        assert_eq!(strategy.prepare_ratings(&items, &existing, true).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_imdb_additions_past_the_item_limit_are_left_out_oldest_kept() {
+        let item = |imdb_id: &str, day: u32, status: NormalizedStatus| WatchlistItem {
+            imdb_id: imdb_id.to_string(),
+            ids: None,
+            title: imdb_id.to_string(),
+            year: None,
+            media_type: MediaType::Movie,
+            date_added: format!("2024-01-{:02}T12:00:00Z", day).parse().unwrap(),
+            source: "trakt".to_string(),
+            status: Some(status),
+            tags: Vec::new(),
+        };
+        let watched = |imdb_id: &str, day: u32| WatchHistory {
+            imdb_id: imdb_id.to_string(),
+            ids: None,
+            title: None,
+            year: None,
+            watched_at: format!("2024-02-{:02}T20:00:00Z", day).parse().unwrap(),
+            media_type: MediaType::Movie,
+            source: "trakt".to_string(),
+            watched_percent: None,
+            show_ids: None,
+        };
+        let existing = SourceData {
+            watchlist: vec![item("tt0000099", 1, NormalizedStatus::Watchlist)],
+            ratings: Vec::new(),
+            reviews: Vec::new(),
+            watch_history: vec![watched("tt0000098", 1)],
+        };
+        let strategy = ImdbDistributionStrategy::new().unwrap().with_item_limit(3);
+        let ids = |items: &[WatchlistItem]| items.iter().map(|i| i.imdb_id.clone()).collect::<Vec<_>>();
+
+        let items = vec![
+            item("tt0000004", 4, NormalizedStatus::Watchlist),
+            item("tt0000002", 2, NormalizedStatus::Watchlist),
+            item("tt0000003", 3, NormalizedStatus::Watchlist),
+            item("tt0000005", 5, NormalizedStatus::Completed),
+        ];
+        let result = strategy.prepare_watchlist(&items, &existing, true, &[], false).unwrap();
+        assert_eq!(ids(&result.for_watchlist), vec!["tt0000002", "tt0000003"]);
+        assert_eq!(result.for_watch_history.len(), 1);
+
+        // The check-in takes one of the two free check-in slots
+        let history = vec![watched("tt0000007", 7), watched("tt0000006", 6)];
+        let prepared = strategy.prepare_watch_history(&history, &existing, true).unwrap();
+        assert_eq!(prepared.iter().map(|h| h.imdb_id.as_str()).collect::<Vec<_>>(), vec!["tt0000006"]);
+
+        let over_limit = strategy.take_over_limit();
+        let dropped: Vec<(Option<&str>, &str)> = over_limit.iter()
+            .map(|item| (item.imdb_id.as_deref(), item.reason.as_str()))
+            .collect();
+        assert_eq!(dropped, vec![
+            (Some("tt0000004"), "IMDB watchlist limit of 3 items reached"),
+            (Some("tt0000007"), "IMDB watch history limit of 3 items reached"),
+        ]);
+        assert!(strategy.take_over_limit().is_empty());
+    }
+
+    #[test]
+    fn test_watch_history_chunks_by_year_oldest_first() {
+        let watched = |imdb_id: &str, at: &str| WatchHistory {
+            imdb_id: imdb_id.to_string(),
+            ids: None,
+            title: None,
+            year: None,
+            watched_at: at.parse().unwrap(),
+            media_type: MediaType::Movie,
+            source: "plex".to_string(),
+            watched_percent: None,
+            show_ids: None,
+        };
+        let items = vec![
+            watched("tt0000003", "2023-05-01T20:00:00Z"),
+            watched("tt0000001", "2021-12-31T23:00:00Z"),
+            watched("tt0000004", "2023-01-02T20:00:00Z"),
+            watched("tt0000002", "2021-01-01T10:00:00Z"),
+        ];
+
+        let chunks = chunk_watch_history_by_year(&items);
+        let ids: Vec<Vec<&str>> = chunks.iter()
+            .map(|chunk| chunk.iter().map(|item| item.imdb_id.as_str()).collect())
+            .collect();
+        assert_eq!(ids, vec![vec!["tt0000002", "tt0000001"], vec!["tt0000004", "tt0000003"]]);
+        assert!(chunk_watch_history_by_year(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_reviews_submitted_moments_ago_are_not_sent_again() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache_manager = CacheManager::new(&PathManager::with_base_dir(dir.path())).unwrap();
+        let review = |imdb_id: &str, content: &str| Review {
+            imdb_id: imdb_id.to_string(),
+            ids: None,
+            content: content.to_string(),
+            date_added: Utc::now(),
+            media_type: MediaType::Movie,
+            source: "imdb".to_string(),
+            is_spoiler: false,
+            rating: None,
+            review_id: None,
+        };
+        let items = vec![review("tt0000001", "Slow burn, worth it."), review("tt0000002", "Too long.")];
+        // Trakt's API doesn't list the review sent on the last run yet
+        let existing = SourceData { watchlist: Vec::new(), ratings: Vec::new(), reviews: Vec::new(), watch_history: Vec::new() };
+        let strategy = DefaultDistributionStrategy::new("trakt").unwrap().with_cache_manager(cache_manager.clone());
+        let ids = |prepared: Vec<Review>| prepared.into_iter().map(|r| r.imdb_id).collect::<Vec<_>>();
+        assert_eq!(ids(strategy.prepare_reviews(&items, &existing, true).unwrap()), vec!["tt0000001", "tt0000002"]);
+
+        cache_manager.record_submitted_reviews("trakt", &items[..1]).unwrap();
+        assert_eq!(ids(strategy.prepare_reviews(&items, &existing, true).unwrap()), vec!["tt0000002"]);
+
+        // An edited review is new content; other targets keep their own ledger
+        let edited = vec![review("tt0000001", "Slow burn, not worth it.")];
+        assert_eq!(ids(strategy.prepare_reviews(&edited, &existing, true).unwrap()), vec!["tt0000001"]);
+        let kodi = DefaultDistributionStrategy::new("kodi").unwrap().with_cache_manager(cache_manager);
+        assert_eq!(kodi.prepare_reviews(&items, &existing, true).unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,215 @@
+//! Dry-run comparison for `sync --dry-run --compare-previous`.
+//!
+//! A dry run writes what it would send to each source to `cache/distribute/<source>/`,
+//! one file per data type (`.json` or `.ndjson`, see [`DryRunFormat`]), and skips data
+//! types with nothing to send. Before the new dry run, the last one's files are moved to
+//! `cache/distribute/previous/<source>/`; afterwards the two are compared per source and
+//! data type, whatever format each was written in. An item is identified by
+//! its IMDB ID (or title and year without one), media type and the value being written
+//! (rating, watched time, review text), so a changed rating shows as one item no longer
+//! planned and one newly planned.
+
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// The data type files a dry run writes per source
+pub const DRY_RUN_DATA_TYPES: [&str; 6] = [
+    "watchlist",
+    "watchlist_to_history",
+    "ratings",
+    "reviews",
+    "watch_history",
+    "removal_list",
+];
+
+/// How a dry run writes its files (`sync --dry-run-format`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DryRunFormat {
+    /// One JSON array per file, pretty-printed (`<data_type>.json`)
+    #[default]
+    Json,
+    /// One JSON item per line (`<data_type>.ndjson`)
+    Ndjson,
+}
+
+impl DryRunFormat {
+    pub const ALL: [DryRunFormat; 2] = [DryRunFormat::Json, DryRunFormat::Ndjson];
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            DryRunFormat::Json => "json",
+            DryRunFormat::Ndjson => "ndjson",
+        }
+    }
+}
+
+/// Fields that, with the title's identity, tell planned items apart
+const VALUE_FIELDS: [&str; 3] = ["rating", "watched_at", "content"];
+
+/// What changed in one source's plan for one data type
+#[derive(Debug, Clone, Serialize)]
+pub struct DryRunDelta {
+    pub source: String,
+    pub data_type: String,
+    /// Planned now, not in the previous dry run
+    pub added: Vec<Value>,
+    /// Planned in the previous dry run, not any more
+    pub removed: Vec<Value>,
+}
+
+fn previous_dir(distribute_dir: &Path) -> PathBuf {
+    distribute_dir.join("previous")
+}
+
+/// Move the last dry run's files for `sources` to `previous/`, replacing what was there.
+/// Call before the new dry run, which then writes only what it plans.
+pub fn rotate_dry_run_output(distribute_dir: &Path, sources: &[String]) -> Result<()> {
+    for source in sources {
+        let current = distribute_dir.join(source);
+        let previous = previous_dir(distribute_dir).join(source);
+        std::fs::create_dir_all(&previous)?;
+        for data_type in DRY_RUN_DATA_TYPES {
+            for format in DryRunFormat::ALL {
+                let file = format!("{}.{}", data_type, format.extension());
+                let previous_file = previous.join(&file);
+                if previous_file.exists() {
+                    std::fs::remove_file(&previous_file)?;
+                }
+                let current_file = current.join(&file);
+                if current_file.exists() {
+                    std::fs::rename(&current_file, &previous_file)
+                        .map_err(|e| anyhow!("Failed to move {} to {}: {}", current_file.display(), previous_file.display(), e))?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Compare the dry run just written for `sources` with the one in `previous/`. Only
+/// source and data type pairs that changed are returned.
+pub fn compare_dry_run_output(distribute_dir: &Path, sources: &[String]) -> Result<Vec<DryRunDelta>> {
+    let mut deltas = Vec::new();
+    for source in sources {
+        for data_type in DRY_RUN_DATA_TYPES {
+            let current = load_items(&distribute_dir.join(source), data_type)?;
+            let previous = load_items(&previous_dir(distribute_dir).join(source), data_type)?;
+            let (added, removed) = item_delta(previous, current);
+            if !added.is_empty() || !removed.is_empty() {
+                deltas.push(DryRunDelta {
+                    source: source.clone(),
+                    data_type: data_type.to_string(),
+                    added,
+                    removed,
+                });
+            }
+        }
+    }
+    Ok(deltas)
+}
+
+/// Items of `current` not in `previous`, and of `previous` not in `current`
+fn item_delta(previous: Vec<Value>, current: Vec<Value>) -> (Vec<Value>, Vec<Value>) {
+    let keyed = |items: Vec<Value>| items.into_iter().map(|item| (item_key(&item), item)).collect::<BTreeMap<_, _>>();
+    let mut previous = keyed(previous);
+    let added = keyed(current).into_iter()
+        .filter_map(|(key, item)| previous.remove(&key).is_none().then_some(item))
+        .collect();
+    (added, previous.into_values().collect())
+}
+
+fn item_key(item: &Value) -> String {
+    let field = |name: &str| match item.get(name) {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+    };
+    let imdb_id = field("imdb_id");
+    let title = if imdb_id.is_empty() { format!("{} ({})", field("title"), field("year")) } else { imdb_id };
+    let mut key = vec![title, field("media_type")];
+    key.extend(VALUE_FIELDS.iter().map(|name| field(name)));
+    key.join("|")
+}
+
+/// The items of `data_type` in `dir`, in whichever format they were written
+fn load_items(dir: &Path, data_type: &str) -> Result<Vec<Value>> {
+    let mut items = Vec::new();
+    for format in DryRunFormat::ALL {
+        let path = dir.join(format!("{}.{}", data_type, format.extension()));
+        if !path.exists() {
+            continue;
+        }
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| anyhow!("Failed to read {}: {}", path.display(), e))?;
+        let parsed: Result<Vec<Value>, _> = match format {
+            DryRunFormat::Json => serde_json::from_str(&content),
+            DryRunFormat::Ndjson => content.lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(serde_json::from_str)
+                .collect(),
+        };
+        items.extend(parsed.map_err(|e| anyhow!("Failed to parse {}: {}", path.display(), e))?);
+    }
+    Ok(items)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_compare_reports_only_the_delta() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |data_type: &str, items: Value| {
+            std::fs::create_dir_all(dir.path().join("plex")).unwrap();
+            std::fs::write(dir.path().join("plex").join(format!("{}.json", data_type)), items.to_string()).unwrap();
+        };
+        let sources = vec!["plex".to_string()];
+
+        write("ratings", json!([
+            { "imdb_id": "tt0111161", "media_type": "Movie", "rating": 9 },
+            { "imdb_id": "tt0068646", "media_type": "Movie", "rating": 7 },
+        ]));
+        write("watchlist", json!([{ "imdb_id": "", "title": "Heat", "year": 1995, "media_type": "Movie" }]));
+        rotate_dry_run_output(dir.path(), &sources).unwrap();
+        assert!(!dir.path().join("plex/ratings.json").exists());
+
+        // The rating for tt0068646 changed; the watchlist has nothing left to send
+        write("ratings", json!([
+            { "imdb_id": "tt0111161", "media_type": "Movie", "rating": 9 },
+            { "imdb_id": "tt0068646", "media_type": "Movie", "rating": 8 },
+        ]));
+        let deltas = compare_dry_run_output(dir.path(), &sources).unwrap();
+        assert_eq!(deltas.len(), 2);
+        assert_eq!(deltas[0].data_type, "watchlist");
+        assert!(deltas[0].added.is_empty());
+        assert_eq!(deltas[0].removed.len(), 1);
+        assert_eq!(deltas[1].data_type, "ratings");
+        assert_eq!(deltas[1].added, vec![json!({ "imdb_id": "tt0068646", "media_type": "Movie", "rating": 8 })]);
+        assert_eq!(deltas[1].removed[0]["rating"], 7);
+    }
+
+    #[test]
+    fn test_ndjson_dry_run_compares_with_a_json_one() {
+        let dir = tempfile::tempdir().unwrap();
+        let plex = dir.path().join("plex");
+        std::fs::create_dir_all(&plex).unwrap();
+        let sources = vec!["plex".to_string()];
+
+        std::fs::write(plex.join("ratings.json"), json!([{ "imdb_id": "tt0111161", "media_type": "Movie", "rating": 9 }]).to_string()).unwrap();
+        rotate_dry_run_output(dir.path(), &sources).unwrap();
+        std::fs::write(plex.join("ratings.ndjson"), concat!(
+            r#"{"imdb_id":"tt0068646","media_type":"Movie","rating":8}"#, "\n",
+            r#"{"imdb_id":"tt0111161","media_type":"Movie","rating":9}"#, "\n",
+        )).unwrap();
+
+        let deltas = compare_dry_run_output(dir.path(), &sources).unwrap();
+        assert_eq!(deltas.len(), 1);
+        assert_eq!(deltas[0].added, vec![json!({ "imdb_id": "tt0068646", "media_type": "Movie", "rating": 8 })]);
+        assert!(deltas[0].removed.is_empty());
+    }
+}
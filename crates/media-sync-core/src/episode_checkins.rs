@@ -0,0 +1,122 @@
+//! Episode resolution for show-level IMDB check-ins (`imdb.resolve_episode_checkins`).
+//!
+//! A check-in made from a series page is recorded against the show, so it is collected
+//! as a `MediaType::Show` item and dropped from history. Check-ins usually happen as an
+//! episode airs: when exactly one episode first aired in the window before the check-in,
+//! the item is upgraded to that episode. Anything less certain is left as a show.
+
+use chrono::{DateTime, Duration, Utc};
+use media_sync_models::{MediaIds, MediaType, WatchHistory};
+use media_sync_sources::{AiredEpisode, EpisodeLookup};
+use std::collections::HashMap;
+use tracing::{debug, info, warn};
+
+/// How long after an episode airs a check-in is still attributed to it. Covers US
+/// evening air times falling on the next UTC day.
+const CHECKIN_WINDOW_HOURS: i64 = 36;
+
+/// The one episode that aired in the window before `watched_at`, if there is exactly one
+pub fn match_aired_episode(episodes: &[AiredEpisode], watched_at: DateTime<Utc>) -> Option<&AiredEpisode> {
+    let window_start = watched_at - Duration::hours(CHECKIN_WINDOW_HOURS);
+    let mut candidates = episodes.iter()
+        .filter(|episode| episode.first_aired <= watched_at && episode.first_aired > window_start);
+    let first = candidates.next()?;
+    // Double bills and binge drops are ambiguous
+    candidates.next().is_none().then_some(first)
+}
+
+/// Upgrade the show-level items in `history` to episodes where `lookup` makes it
+/// unambiguous. Returns the number of items upgraded.
+pub async fn resolve_episode_checkins(history: &mut [WatchHistory], lookup: &dyn EpisodeLookup) -> usize {
+    let mut episodes_by_show: HashMap<String, Vec<AiredEpisode>> = HashMap::new();
+    let mut shows = 0;
+    let mut resolved = 0;
+
+    for item in history.iter_mut().filter(|item| item.media_type == MediaType::Show) {
+        shows += 1;
+        let Some(show_id) = show_lookup_id(item) else {
+            continue;
+        };
+        if !episodes_by_show.contains_key(&show_id) {
+            let episodes = match lookup.aired_episodes(&show_id).await {
+                Ok(episodes) => episodes,
+                Err(e) => {
+                    warn!("Could not fetch episodes of show {} to resolve check-ins: {}", show_id, e);
+                    Vec::new()
+                }
+            };
+            episodes_by_show.insert(show_id.clone(), episodes);
+        }
+
+        let Some(episode) = match_aired_episode(&episodes_by_show[&show_id], item.watched_at) else {
+            debug!("Check-in for show {} at {} doesn't match a single episode, leaving it as a show", show_id, item.watched_at);
+            continue;
+        };
+        upgrade_to_episode(item, episode);
+        resolved += 1;
+    }
+
+    if shows > 0 {
+        info!("Resolved {} of {} show-level check-ins to episodes", resolved, shows);
+    }
+    resolved
+}
+
+fn show_lookup_id(item: &WatchHistory) -> Option<String> {
+    item.ids.as_ref()
+        .and_then(|ids| ids.trakt_id)
+        .map(|id| id.to_string())
+        .or_else(|| (!item.imdb_id.is_empty()).then(|| item.imdb_id.clone()))
+}
+
+fn upgrade_to_episode(item: &mut WatchHistory, episode: &AiredEpisode) {
+    let show_ids = item.ids.take().unwrap_or_else(|| MediaIds {
+        imdb_id: Some(item.imdb_id.clone()),
+        ..Default::default()
+    });
+    item.imdb_id = episode.ids.imdb_id.clone().unwrap_or_default();
+    item.ids = Some(episode.ids.clone());
+    if let (Some(show_title), Some(episode_title)) = (&item.title, &episode.title) {
+        item.title = Some(format!("{}: {}", show_title, episode_title));
+    }
+    item.media_type = MediaType::Episode {
+        season: episode.season,
+        episode: episode.episode,
+    };
+    item.show_ids = Some(show_ids);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn aired(season: u32, episode: u32, first_aired: DateTime<Utc>) -> AiredEpisode {
+        AiredEpisode {
+            season,
+            episode,
+            title: None,
+            ids: MediaIds::default(),
+            first_aired,
+        }
+    }
+
+    #[test]
+    fn test_checkin_matches_only_a_single_recent_episode() {
+        let episodes = vec![
+            aired(1, 1, Utc.with_ymd_and_hms(2024, 1, 8, 2, 0, 0).unwrap()),
+            aired(1, 2, Utc.with_ymd_and_hms(2024, 1, 15, 2, 0, 0).unwrap()),
+            // Two-part finale on the same night
+            aired(1, 3, Utc.with_ymd_and_hms(2024, 1, 22, 2, 0, 0).unwrap()),
+            aired(1, 4, Utc.with_ymd_and_hms(2024, 1, 22, 3, 0, 0).unwrap()),
+        ];
+
+        let match_at = |checkin| match_aired_episode(&episodes, checkin).map(|e| (e.season, e.episode));
+        // Evening of the air date in the US, the next day in UTC
+        assert_eq!(match_at(Utc.with_ymd_and_hms(2024, 1, 15, 4, 30, 0).unwrap()), Some((1, 2)));
+        // Before anything aired, or days later: too uncertain
+        assert_eq!(match_at(Utc.with_ymd_and_hms(2024, 1, 7, 23, 0, 0).unwrap()), None);
+        assert_eq!(match_at(Utc.with_ymd_and_hms(2024, 1, 12, 20, 0, 0).unwrap()), None);
+        assert_eq!(match_at(Utc.with_ymd_and_hms(2024, 1, 22, 4, 0, 0).unwrap()), None);
+    }
+}
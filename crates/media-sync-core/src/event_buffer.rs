@@ -0,0 +1,330 @@
+use anyhow::{anyhow, Result};
+use media_sync_config::PathManager;
+use media_sync_models::{Rating, WatchHistory};
+use media_sync_sources::plex::WebhookEvent;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+use crate::plan::SourcePlan;
+use crate::resolution::SourceData;
+
+/// How often a push waits for another writer of the same buffer
+const LOCK_POLL_INTERVAL: Duration = Duration::from_millis(10);
+/// A writer holds the buffer lock for one read and write; a lock this old was left by a
+/// process that died holding it
+const STALE_LOCK_AGE: Duration = Duration::from_secs(10);
+/// Give up waiting for the buffer lock after this long
+const LOCK_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Events received between syncs, waiting to be merged into the next collect phase
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct BufferedEvents {
+    #[serde(default)]
+    pub watch_history: Vec<WatchHistory>,
+    #[serde(default)]
+    pub ratings: Vec<Rating>,
+}
+
+impl BufferedEvents {
+    pub fn is_empty(&self) -> bool {
+        self.watch_history.is_empty() && self.ratings.is_empty()
+    }
+}
+
+/// On-disk buffer for pushed events (e.g. Plex webhooks), stored as
+/// `cache/events/{source}.json` until a sync consumes them.
+#[derive(Clone)]
+pub struct EventBuffer {
+    events_dir: PathBuf,
+}
+
+impl EventBuffer {
+    pub fn new(path_manager: &PathManager) -> Self {
+        Self {
+            events_dir: path_manager.cache_dir().join("events"),
+        }
+    }
+
+    fn get_buffer_path(&self, source: &str) -> PathBuf {
+        self.events_dir.join(format!("{}.json", source))
+    }
+
+    pub fn load(&self, source: &str) -> Result<BufferedEvents> {
+        let path = self.get_buffer_path(source);
+        if !path.exists() {
+            return Ok(BufferedEvents::default());
+        }
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| anyhow!("Failed to read event buffer {}: {}", path.display(), e))?;
+        serde_json::from_str(&content)
+            .map_err(|e| anyhow!("Failed to parse event buffer {}: {}", path.display(), e))
+    }
+
+    /// Write the buffer through a temporary file, so a reader never sees half of it
+    fn save(&self, source: &str, events: &BufferedEvents) -> Result<()> {
+        std::fs::create_dir_all(&self.events_dir)?;
+        let path = self.get_buffer_path(source);
+        let temp_path = path.with_extension("json.tmp");
+        let json = serde_json::to_string_pretty(events)?;
+        std::fs::write(&temp_path, json)
+            .and_then(|_| std::fs::rename(&temp_path, &path))
+            .map_err(|e| anyhow!("Failed to write event buffer {}: {}", path.display(), e))
+    }
+
+    /// Take the source's buffer lock, so that webhooks arriving together and a sync
+    /// clearing the buffer don't overwrite each other's changes. Waits on the tokio timer,
+    /// so a busy lock doesn't block the runtime thread.
+    async fn lock(&self, source: &str) -> Result<BufferLock> {
+        std::fs::create_dir_all(&self.events_dir)?;
+        let path = self.events_dir.join(format!("{}.lock", source));
+        let started = Instant::now();
+        loop {
+            match std::fs::OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(_) => return Ok(BufferLock { path }),
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                    let age = std::fs::metadata(&path)
+                        .and_then(|metadata| metadata.modified())
+                        .ok()
+                        .and_then(|modified| modified.elapsed().ok());
+                    if age.is_some_and(|age| age > STALE_LOCK_AGE) {
+                        warn!("Removing stale {} event buffer lock {}", source, path.display());
+                        let _ = std::fs::remove_file(&path);
+                        continue;
+                    }
+                    if started.elapsed() > LOCK_TIMEOUT {
+                        return Err(anyhow!("Timed out waiting for event buffer lock {}", path.display()));
+                    }
+                    tokio::time::sleep(LOCK_POLL_INTERVAL).await;
+                }
+                Err(e) => return Err(anyhow!("Failed to create event buffer lock {}: {}", path.display(), e)),
+            }
+        }
+    }
+
+    /// Append an event to the source's buffer. Returns false if the event was ignored.
+    pub async fn push(&self, source: &str, event: WebhookEvent) -> Result<bool> {
+        if let WebhookEvent::Ignored(reason) = &event {
+            debug!("Ignoring {} event: {}", source, reason);
+            return Ok(false);
+        }
+        let _lock = self.lock(source).await?;
+        let mut events = self.load(source)?;
+        match event {
+            WebhookEvent::Watched(mut history) => {
+                history.normalize_ids();
+                // Only the latest position of an unfinished play is kept, and finishing it drops it
+                events.watch_history.retain(|h| {
+                    !(h.is_partial()
+                        && h.media_type == history.media_type
+                        && same_item(&h.imdb_id, &h.ids, &history.imdb_id, &history.ids))
+                });
+                events.watch_history.push(*history);
+            }
+            WebhookEvent::Rated(mut rating) => {
+                rating.normalize_ids();
+                // A newer rating for the same item replaces the buffered one
+                events.ratings.retain(|r| !same_item(&r.imdb_id, &r.ids, &rating.imdb_id, &rating.ids));
+                events.ratings.push(*rating);
+            }
+            WebhookEvent::Ignored(_) => return Ok(false),
+        }
+        self.save(source, &events)?;
+        Ok(true)
+    }
+
+    /// Merge buffered events into freshly collected source data.
+    /// Watch history entries already present (same item, same day) are skipped;
+    /// buffered ratings win over collected ones when they are newer.
+    pub fn merge_into(&self, source: &str, data: &mut SourceData, merge_watch_history: bool, merge_ratings: bool) -> Result<usize> {
+        let events = self.load(source)?;
+        if events.is_empty() {
+            return Ok(0);
+        }
+
+        let mut merged = 0;
+        if merge_watch_history {
+            for history in events.watch_history {
+                let exists = data.watch_history.iter().any(|h| {
+                    same_item(&h.imdb_id, &h.ids, &history.imdb_id, &history.ids)
+                        && h.media_type == history.media_type
+                        && h.watched_at.date_naive() == history.watched_at.date_naive()
+                });
+                if !exists {
+                    data.watch_history.push(history);
+                    merged += 1;
+                }
+            }
+        }
+        if merge_ratings {
+            for rating in events.ratings {
+                match data.ratings.iter_mut().find(|r| same_item(&r.imdb_id, &r.ids, &rating.imdb_id, &rating.ids)) {
+                    Some(existing) if existing.date_added >= rating.date_added => {}
+                    Some(existing) => {
+                        *existing = rating;
+                        merged += 1;
+                    }
+                    None => {
+                        data.ratings.push(rating);
+                        merged += 1;
+                    }
+                }
+            }
+        }
+
+        if merged > 0 {
+            info!("Merged {} buffered {} events into collected data", merged, source);
+        }
+        Ok(merged)
+    }
+
+    /// Drop the buffered events that every target now has: those of the data types this
+    /// run merged and distributed, except ones matching an item of `unwritten`, the writes
+    /// that failed or were deferred in this run. Returns how many events were dropped.
+    pub async fn clear_written(&self, source: &str, unwritten: &[SourcePlan], clear_watch_history: bool, clear_ratings: bool) -> Result<usize> {
+        let _lock = self.lock(source).await?;
+        let mut events = self.load(source)?;
+        let before = events.watch_history.len() + events.ratings.len();
+
+        events.watch_history.retain(|history| {
+            !clear_watch_history || unwritten.iter()
+                .flat_map(|plan| plan.watch_history_add.iter().chain(&plan.watchlist_to_history).chain(&plan.playback_progress))
+                .any(|h| {
+                    same_item(&h.imdb_id, &h.ids, &history.imdb_id, &history.ids)
+                        && h.watched_at.date_naive() == history.watched_at.date_naive()
+                })
+        });
+        events.ratings.retain(|rating| {
+            !clear_ratings || unwritten.iter()
+                .flat_map(|plan| &plan.ratings)
+                .any(|r| same_item(&r.imdb_id, &r.ids, &rating.imdb_id, &rating.ids))
+        });
+
+        let cleared = before - events.watch_history.len() - events.ratings.len();
+        if events.is_empty() {
+            let path = self.get_buffer_path(source);
+            if path.exists() {
+                std::fs::remove_file(&path)?;
+            }
+        } else {
+            info!("Keeping {} buffered {} events that not every target has yet", events.watch_history.len() + events.ratings.len(), source);
+            self.save(source, &events)?;
+        }
+        if cleared > 0 {
+            debug!("Cleared {} written {} events", cleared, source);
+        }
+        Ok(cleared)
+    }
+}
+
+/// A source's buffer lock file, removed when dropped
+struct BufferLock {
+    path: PathBuf,
+}
+
+impl Drop for BufferLock {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Match on IMDB ID when both sides have one, otherwise on Plex rating key
+fn same_item(
+    imdb_a: &str,
+    ids_a: &Option<media_sync_models::MediaIds>,
+    imdb_b: &str,
+    ids_b: &Option<media_sync_models::MediaIds>,
+) -> bool {
+    if !imdb_a.is_empty() && !imdb_b.is_empty() {
+        return imdb_a == imdb_b;
+    }
+    let key_a = ids_a.as_ref().and_then(|i| i.plex_rating_key.as_ref());
+    let key_b = ids_b.as_ref().and_then(|i| i.plex_rating_key.as_ref());
+    matches!((key_a, key_b), (Some(a), Some(b)) if a == b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use media_sync_models::{MediaType, RatingSource};
+
+    fn rating(imdb_id: &str) -> Rating {
+        Rating {
+            imdb_id: imdb_id.to_string(),
+            ids: None,
+            rating: 8,
+            rating_f64: None,
+            date_added: Utc::now(),
+            media_type: MediaType::Movie,
+            source: RatingSource::Plex,
+            inferred: false,
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_pushes_are_all_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        let buffer = EventBuffer::new(&PathManager::with_base_dir(dir.path()));
+
+        let pushes: Vec<_> = (0..8)
+            .map(|i| {
+                let buffer = buffer.clone();
+                tokio::spawn(async move { buffer.push("plex", WebhookEvent::Rated(Box::new(rating(&format!("tt000000{}", i))))).await.unwrap() })
+            })
+            .collect();
+        for push in pushes {
+            assert!(push.await.unwrap());
+        }
+
+        assert_eq!(buffer.load("plex").unwrap().ratings.len(), 8);
+        assert!(!dir.path().join("data/cache/events/plex.lock").exists());
+    }
+
+    #[tokio::test]
+    async fn test_clear_written_keeps_events_a_target_failed_or_deferred() {
+        let dir = tempfile::tempdir().unwrap();
+        let buffer = EventBuffer::new(&PathManager::with_base_dir(dir.path()));
+        for imdb_id in ["tt0133093", "tt0113277", "tt0110912"] {
+            buffer.push("plex", WebhookEvent::Rated(Box::new(rating(imdb_id)))).await.unwrap();
+        }
+
+        let mut failed = SourcePlan::new("trakt");
+        failed.ratings = vec![rating("tt0113277")];
+        let mut deferred = SourcePlan::new("simkl");
+        deferred.ratings = vec![rating("tt0110912")];
+        assert_eq!(buffer.clear_written("plex", &[failed, deferred], true, true).await.unwrap(), 1);
+        let kept: Vec<String> = buffer.load("plex").unwrap().ratings.into_iter().map(|r| r.imdb_id).collect();
+        assert_eq!(kept, vec!["tt0113277", "tt0110912"]);
+
+        assert_eq!(buffer.clear_written("plex", &[], true, true).await.unwrap(), 2);
+        assert!(buffer.load("plex").unwrap().is_empty());
+        assert!(!dir.path().join("data/cache/events/plex.json").exists());
+    }
+
+    #[tokio::test]
+    async fn test_clear_written_keeps_data_types_not_synced() {
+        let dir = tempfile::tempdir().unwrap();
+        let buffer = EventBuffer::new(&PathManager::with_base_dir(dir.path()));
+        buffer.push("plex", WebhookEvent::Rated(Box::new(rating("tt0133093")))).await.unwrap();
+        let watched = WatchHistory {
+            imdb_id: "tt0113277".to_string(),
+            ids: None,
+            title: Some("Heat".to_string()),
+            year: Some(1995),
+            watched_at: Utc::now(),
+            media_type: MediaType::Movie,
+            source: "plex".to_string(),
+            watched_percent: None,
+            show_ids: None,
+        };
+        buffer.push("plex", WebhookEvent::Watched(Box::new(watched))).await.unwrap();
+
+        // A ratings-only run leaves the buffered watch for a run that syncs history
+        assert_eq!(buffer.clear_written("plex", &[], false, true).await.unwrap(), 1);
+        let events = buffer.load("plex").unwrap();
+        assert!(events.ratings.is_empty());
+        assert_eq!(events.watch_history.len(), 1);
+    }
+}
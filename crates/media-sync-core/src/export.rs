@@ -0,0 +1,322 @@
+//! Resolved data in Trakt's sync JSON, for `totalrecall export --format trakt`.
+//!
+//! The resolved watch history and ratings are written in the shape of the bodies of
+//! Trakt's `/sync/history` and `/sync/ratings`, so tools with a Trakt importer (Kodi and
+//! Emby add-ons, ...) can take them in. The plays of a title are gathered into one entry
+//! with a `watched_at` array. Episodes whose series IDs are known are nested under their
+//! show by season; the others, and all episode ratings, are listed by their own IDs under
+//! `episodes`. Items without any ID can't be matched by an importer and are left out.
+
+use chrono::{DateTime, Utc};
+use media_sync_models::{MediaIds, MediaType, Rating, WatchHistory};
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::resolution::ResolvedData;
+
+/// Trakt's `ids` object
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize)]
+pub struct TraktIds {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trakt: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slug: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub imdb: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tmdb: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tvdb: Option<u32>,
+}
+
+impl TraktIds {
+    fn new(imdb_id: &str, ids: Option<&MediaIds>) -> Self {
+        Self {
+            trakt: ids.and_then(|ids| ids.trakt_id),
+            slug: ids.and_then(|ids| ids.slug.clone()),
+            imdb: Some(imdb_id.to_string())
+                .filter(|imdb_id| !imdb_id.is_empty())
+                .or_else(|| ids.and_then(|ids| ids.imdb_id.clone())),
+            tmdb: ids.and_then(|ids| ids.tmdb_id),
+            tvdb: ids.and_then(|ids| ids.tvdb_id),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// A movie, show or episode with its plays or its rating
+#[derive(Debug, Clone, Serialize)]
+pub struct TraktEntry {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub year: Option<u32>,
+    pub ids: TraktIds,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub watched_at: Vec<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rating: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rated_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub seasons: Vec<TraktSeason>,
+}
+
+impl TraktEntry {
+    fn new(ids: TraktIds, title: Option<String>, year: Option<u32>) -> Self {
+        Self { title, year, ids, watched_at: Vec::new(), rating: None, rated_at: None, seasons: Vec::new() }
+    }
+
+    fn add_episode_play(&mut self, season: u32, episode: u32, watched_at: DateTime<Utc>) {
+        let season = match self.seasons.iter().position(|s| s.number == season) {
+            Some(position) => &mut self.seasons[position],
+            None => {
+                self.seasons.push(TraktSeason { number: season, episodes: Vec::new() });
+                self.seasons.last_mut().unwrap()
+            }
+        };
+        let episode = match season.episodes.iter().position(|e| e.number == episode) {
+            Some(position) => &mut season.episodes[position],
+            None => {
+                season.episodes.push(TraktEpisode { number: episode, watched_at: Vec::new() });
+                season.episodes.last_mut().unwrap()
+            }
+        };
+        episode.watched_at.push(watched_at);
+    }
+
+    /// Plays oldest first, seasons and episodes in order
+    fn sort(&mut self) {
+        self.watched_at.sort();
+        self.seasons.sort_by_key(|season| season.number);
+        for season in &mut self.seasons {
+            season.episodes.sort_by_key(|episode| episode.number);
+            for episode in &mut season.episodes {
+                episode.watched_at.sort();
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TraktSeason {
+    pub number: u32,
+    pub episodes: Vec<TraktEpisode>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TraktEpisode {
+    pub number: u32,
+    pub watched_at: Vec<DateTime<Utc>>,
+}
+
+/// The body of one Trakt sync request
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TraktItems {
+    pub movies: Vec<TraktEntry>,
+    pub shows: Vec<TraktEntry>,
+    pub episodes: Vec<TraktEntry>,
+}
+
+impl TraktItems {
+    pub fn len(&self) -> usize {
+        self.movies.len() + self.shows.len() + self.episodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Resolved watch history and ratings as Trakt sync JSON
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TraktExport {
+    pub history: TraktItems,
+    pub ratings: TraktItems,
+    /// Plays and ratings left out for having no ID
+    #[serde(skip)]
+    pub skipped: usize,
+}
+
+impl TraktExport {
+    pub fn from_resolved(resolved: &ResolvedData) -> Self {
+        let mut export = Self::default();
+        export.add_history(&resolved.watch_history);
+        export.add_ratings(&resolved.ratings);
+        export
+    }
+
+    fn add_history(&mut self, history: &[WatchHistory]) {
+        let mut movies = Entries::default();
+        let mut shows = Entries::default();
+        let mut episodes = Entries::default();
+        for item in history.iter().filter(|item| !item.is_partial()) {
+            let ids = TraktIds::new(&item.imdb_id, item.ids.as_ref());
+            match item.media_type {
+                MediaType::Movie | MediaType::Show if ids.is_empty() => self.skipped += 1,
+                MediaType::Movie => movies.get(ids, item.title.clone(), item.year).watched_at.push(item.watched_at),
+                MediaType::Show => shows.get(ids, item.title.clone(), item.year).watched_at.push(item.watched_at),
+                MediaType::Episode { season, episode } => {
+                    let show_ids = item.show_ids.as_ref()
+                        .map(|show_ids| TraktIds::new("", Some(show_ids)))
+                        .filter(|show_ids| !show_ids.is_empty());
+                    match show_ids {
+                        Some(show_ids) => {
+                            let show = item.show_ids.as_ref().unwrap();
+                            shows.get(show_ids, show.title.clone(), show.year)
+                                .add_episode_play(season, episode, item.watched_at);
+                        }
+                        None if ids.is_empty() => self.skipped += 1,
+                        None => episodes.get(ids, None, None).watched_at.push(item.watched_at),
+                    }
+                }
+            }
+        }
+        self.history = TraktItems { movies: movies.into_sorted(), shows: shows.into_sorted(), episodes: episodes.into_sorted() };
+    }
+
+    fn add_ratings(&mut self, ratings: &[Rating]) {
+        for rating in ratings {
+            let ids = TraktIds::new(&rating.imdb_id, rating.ids.as_ref());
+            if ids.is_empty() {
+                self.skipped += 1;
+                continue;
+            }
+            let (title, year) = match rating.media_type {
+                MediaType::Episode { .. } => (None, None),
+                _ => (rating.ids.as_ref().and_then(|ids| ids.title.clone()), rating.ids.as_ref().and_then(|ids| ids.year)),
+            };
+            let mut entry = TraktEntry::new(ids, title, year);
+            entry.rating = Some(rating.rating);
+            entry.rated_at = (!rating.is_undated()).then_some(rating.date_added);
+            match rating.media_type {
+                MediaType::Movie => self.ratings.movies.push(entry),
+                MediaType::Show => self.ratings.shows.push(entry),
+                MediaType::Episode { .. } => self.ratings.episodes.push(entry),
+            }
+        }
+    }
+}
+
+/// Entries of one kind in order of first appearance, one per title
+#[derive(Default)]
+struct Entries {
+    entries: Vec<TraktEntry>,
+    index: HashMap<TraktIds, usize>,
+}
+
+impl Entries {
+    fn get(&mut self, ids: TraktIds, title: Option<String>, year: Option<u32>) -> &mut TraktEntry {
+        let entries = &mut self.entries;
+        let position = *self.index.entry(ids.clone()).or_insert_with(|| {
+            entries.push(TraktEntry::new(ids, title, year));
+            entries.len() - 1
+        });
+        &mut self.entries[position]
+    }
+
+    fn into_sorted(mut self) -> Vec<TraktEntry> {
+        self.entries.iter_mut().for_each(TraktEntry::sort);
+        self.entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use media_sync_models::RatingSource;
+    use serde_json::json;
+
+    fn play(imdb_id: &str, media_type: MediaType, day: u32) -> WatchHistory {
+        WatchHistory {
+            imdb_id: imdb_id.to_string(),
+            ids: None,
+            title: Some("The Matrix".to_string()),
+            year: Some(1999),
+            watched_at: Utc.with_ymd_and_hms(2024, 1, day, 20, 0, 0).unwrap(),
+            media_type,
+            source: "plex".to_string(),
+            watched_percent: None,
+            show_ids: None,
+        }
+    }
+
+    #[test]
+    fn test_plays_are_grouped_and_episodes_nested_by_season() {
+        let breaking_bad = MediaIds {
+            imdb_id: Some("tt0903747".to_string()),
+            title: Some("Breaking Bad".to_string()),
+            year: Some(2008),
+            ..MediaIds::new()
+        };
+        let episode = |imdb_id: &str, season, number, day, show_ids: Option<MediaIds>| WatchHistory {
+            show_ids,
+            ..play(imdb_id, MediaType::Episode { season, episode: number }, day)
+        };
+        let resolved = ResolvedData {
+            watchlist: vec![],
+            ratings: vec![Rating {
+                imdb_id: "tt0133093".to_string(),
+                ids: None,
+                rating: 9,
+                rating_f64: None,
+                date_added: Rating::UNDATED,
+                media_type: MediaType::Movie,
+                source: RatingSource::Trakt,
+                inferred: false,
+            }],
+            reviews: vec![],
+            watch_history: vec![
+                play("tt0133093", MediaType::Movie, 5),
+                play("tt0133093", MediaType::Movie, 2),
+                episode("tt0959621", 1, 1, 3, Some(breaking_bad.clone())),
+                episode("tt1054724", 2, 1, 4, Some(breaking_bad.clone())),
+                episode("tt1054725", 1, 2, 3, Some(breaking_bad)),
+                episode("tt2301451", 5, 16, 6, None),
+                play("", MediaType::Movie, 1),
+            ],
+            playback_progress: vec![],
+        };
+
+        let export = TraktExport::from_resolved(&resolved);
+
+        assert_eq!(export.skipped, 1);
+        assert_eq!(
+            serde_json::to_value(&export).unwrap(),
+            json!({
+                "history": {
+                    "movies": [{
+                        "title": "The Matrix",
+                        "year": 1999,
+                        "ids": { "imdb": "tt0133093" },
+                        "watched_at": ["2024-01-02T20:00:00Z", "2024-01-05T20:00:00Z"],
+                    }],
+                    "shows": [{
+                        "title": "Breaking Bad",
+                        "year": 2008,
+                        "ids": { "imdb": "tt0903747" },
+                        "seasons": [
+                            { "number": 1, "episodes": [
+                                { "number": 1, "watched_at": ["2024-01-03T20:00:00Z"] },
+                                { "number": 2, "watched_at": ["2024-01-03T20:00:00Z"] },
+                            ] },
+                            { "number": 2, "episodes": [
+                                { "number": 1, "watched_at": ["2024-01-04T20:00:00Z"] },
+                            ] },
+                        ],
+                    }],
+                    "episodes": [{ "ids": { "imdb": "tt2301451" }, "watched_at": ["2024-01-06T20:00:00Z"] }],
+                },
+                "ratings": {
+                    "movies": [{ "ids": { "imdb": "tt0133093" }, "rating": 9 }],
+                    "shows": [],
+                    "episodes": [],
+                },
+            })
+        );
+    }
+}
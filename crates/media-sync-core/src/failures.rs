@@ -0,0 +1,92 @@
+//! Failed writes for `sync --retry-failures`.
+//!
+//! When a write to a target fails, the items it carried are recorded per source in
+//! `cache/distribute/failures.json`, in the layout of a [`SyncPlan`]. Every run that
+//! writes to a source replaces that source's entry with what failed this time, so items
+//! that went through on a retry drop out, while sources that weren't written to (not
+//! configured, failed to authenticate, `--no-write`) keep theirs. `--retry-failures`
+//! applies the file like `totalrecall apply`, without collecting or resolving again.
+
+use anyhow::Result;
+use std::path::Path;
+
+use crate::plan::{SourcePlan, SyncPlan};
+
+/// Replace the recorded failures of every source in `attempted` with its new failures.
+/// The file is removed once no source has any.
+pub fn record_failures(path: &Path, attempted: Vec<SourcePlan>) -> Result<()> {
+    let mut sources = load_failures(path)?
+        .map(|plan| plan.sources)
+        .unwrap_or_default();
+    sources.retain(|recorded| !attempted.iter().any(|plan| plan.source == recorded.source));
+    sources.extend(attempted.into_iter().filter(|plan| !plan.is_empty()));
+
+    if sources.is_empty() {
+        if path.exists() {
+            std::fs::remove_file(path)?;
+        }
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    SyncPlan::new(sources).save(path)
+}
+
+/// The failed writes recorded by earlier runs, if any
+pub fn load_failures(path: &Path) -> Result<Option<SyncPlan>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    SyncPlan::load(path).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use media_sync_models::{MediaType, Rating, RatingSource};
+
+    fn failed_ratings(source: &str, imdb_ids: &[&str]) -> SourcePlan {
+        SourcePlan {
+            ratings: imdb_ids.iter()
+                .map(|imdb_id| Rating {
+                    imdb_id: imdb_id.to_string(),
+                    ids: None,
+                    rating: 8,
+                    rating_f64: None,
+                    date_added: Utc::now(),
+                    media_type: MediaType::Movie,
+                    source: RatingSource::Trakt,
+                    inferred: false,
+                })
+                .collect(),
+            ..SourcePlan::new(source)
+        }
+    }
+
+    #[test]
+    fn test_retried_sources_replace_their_failures() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("distribute").join("failures.json");
+
+        record_failures(&path, vec![
+            failed_ratings("simkl", &["tt0133093", "tt0068646"]),
+            failed_ratings("trakt", &["tt0111161"]),
+            failed_ratings("plex", &[]),
+        ]).unwrap();
+        let recorded = load_failures(&path).unwrap().unwrap();
+        assert_eq!(recorded.sources.len(), 2);
+
+        // Simkl is retried and one rating fails again; Trakt isn't written to
+        record_failures(&path, vec![failed_ratings("simkl", &["tt0068646"])]).unwrap();
+        let recorded = load_failures(&path).unwrap().unwrap();
+        assert_eq!(recorded.total_items(), 2);
+        assert_eq!(recorded.sources[0].source, "simkl");
+        assert_eq!(recorded.sources[0].ratings[0].imdb_id, "tt0068646");
+
+        record_failures(&path, vec![SourcePlan::new("simkl"), SourcePlan::new("trakt")]).unwrap();
+        assert!(load_failures(&path).unwrap().is_none());
+        assert!(!path.exists());
+    }
+}
@@ -1,10 +1,22 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use chrono::{DateTime, Utc};
 use media_sync_models::{MediaIds, MediaType};
+use serde::{Deserialize, Serialize};
 
 /// Key for title/year lookups: (title_lowercase, year, media_type_string)
 type TitleYearKey = (String, Option<u32>, String);
 
+/// Key for fuzzy title lookups: (normalized title, media_type_string)
+type NormalizedTitleKey = (String, String);
+
+/// A cache entry with when it was resolved, as saved to disk
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedIds {
+    pub ids: MediaIds,
+    pub cached_at: DateTime<Utc>,
+}
+
 /// In-memory ID cache with multi-index structure
 /// 
 /// Provides O(1) lookups by any ID type while avoiding duplication.
@@ -20,13 +32,27 @@ pub struct IdCache {
     by_tvdb: HashMap<u32, Arc<MediaIds>>,
     by_slug: HashMap<String, Arc<MediaIds>>,
     by_plex_rating_key: HashMap<String, Arc<MediaIds>>,
+    by_anilist: HashMap<u64, Arc<MediaIds>>,
     
     /// Title/year index for efficient title-based lookups
     /// Key: (title_lowercase, year, media_type_string)
     by_title_year: HashMap<TitleYearKey, Arc<MediaIds>>,
     
+    /// Entries by normalized title (see `id_matching::normalize_title`), any year
+    by_normalized_title: HashMap<NormalizedTitleKey, Vec<Arc<MediaIds>>>,
+    
+    /// When each entry (by IMDB ID) was added or last changed
+    cached_at: HashMap<String, DateTime<Utc>>,
+    
     /// Track dirty state for incremental saves
     dirty: bool,
+    
+    /// IMDB IDs of entries inserted or updated since the last `take_changed`
+    changed: HashSet<String>,
+    
+    /// An entry was invalidated since the last save. The journal only adds, so only a
+    /// full save drops it from disk.
+    invalidated: bool,
 }
 
 impl IdCache {
@@ -39,8 +65,13 @@ impl IdCache {
             by_tvdb: HashMap::new(),
             by_slug: HashMap::new(),
             by_plex_rating_key: HashMap::new(),
+            by_anilist: HashMap::new(),
             by_title_year: HashMap::new(),
+            by_normalized_title: HashMap::new(),
+            cached_at: HashMap::new(),
             dirty: false,
+            changed: HashSet::new(),
+            invalidated: false,
         }
     }
     
@@ -54,15 +85,29 @@ impl IdCache {
         (Self::normalize_title(title), year, format!("{:?}", media_type))
     }
     
-    /// Insert or update IDs (merges with existing if found)
+    /// Insert or update IDs (merges with existing if found). The entry's age restarts
+    /// only when this adds or changes something, so seeing the same IDs again doesn't
+    /// keep a wrong match from expiring.
     pub fn insert(&mut self, ids: MediaIds) {
+        self.insert_entry(ids, None);
+    }
+    
+    /// Insert IDs resolved at `cached_at` (loading a saved cache)
+    pub fn insert_cached(&mut self, entry: CachedIds) {
+        self.insert_entry(entry.ids, Some(entry.cached_at));
+    }
+    
+    fn insert_entry(&mut self, ids: MediaIds, cached_at: Option<DateTime<Utc>>) {
         // Find existing entry by any matching ID
         let existing = self.find_existing(&ids);
         
-        let canonical = if let Some(existing) = existing {
+        let canonical = if let Some(existing) = existing.clone() {
             // Merge with existing
             let mut merged = (*existing).clone();
             merged.merge(&ids);
+            if cached_at.is_none() && merged == *existing {
+                return;
+            }
             Arc::new(merged)
         } else {
             Arc::new(ids)
@@ -71,6 +116,8 @@ impl IdCache {
         // Update all indices
         if let Some(ref imdb) = canonical.imdb_id {
             self.by_imdb.insert(imdb.clone(), canonical.clone());
+            self.cached_at.insert(imdb.clone(), cached_at.unwrap_or_else(Utc::now));
+            self.changed.insert(imdb.clone());
         }
         if let Some(trakt) = canonical.trakt_id {
             self.by_trakt.insert(trakt, canonical.clone());
@@ -90,12 +137,21 @@ impl IdCache {
         if let Some(ref plex_rating_key) = canonical.plex_rating_key {
             self.by_plex_rating_key.insert(plex_rating_key.clone(), canonical.clone());
         }
+        if let Some(anilist) = canonical.anilist_id {
+            self.by_anilist.insert(anilist, canonical.clone());
+        }
         
         // Update title/year index if metadata is available
         if let (Some(ref title), Some(ref media_type)) = (&canonical.title, &canonical.media_type) {
             let key = Self::make_title_key(title, canonical.year, media_type);
             self.by_title_year.insert(key, canonical.clone());
         }
+        if let Some(key) = existing.as_ref().and_then(|existing| Self::make_normalized_title_key(existing)) {
+            if let Some(entries) = self.by_normalized_title.get_mut(&key) {
+                entries.retain(|ids| !Arc::ptr_eq(ids, existing.as_ref().unwrap()));
+            }
+        }
+        self.index_normalized_title(&canonical);
         
         self.dirty = true;
     }
@@ -134,6 +190,12 @@ impl IdCache {
             }
         }
         
+        if let Some(anilist_id) = id.strip_prefix("anilist:").and_then(|s| s.parse().ok()) {
+            if let Some(ids) = self.by_anilist.get(&anilist_id) {
+                return Some(ids.clone());
+            }
+        }
+        
         // Try slug (direct match)
         if let Some(ids) = self.by_slug.get(id) {
             return Some(ids.clone());
@@ -152,6 +214,46 @@ impl IdCache {
         None
     }
     
+    fn make_normalized_title_key(ids: &MediaIds) -> Option<NormalizedTitleKey> {
+        let (title, media_type) = (ids.title.as_ref()?, ids.media_type.as_ref()?);
+        Some((crate::id_matching::normalize_title(title), format!("{:?}", media_type)))
+    }
+
+    fn index_normalized_title(&mut self, ids: &Arc<MediaIds>) {
+        if let Some(key) = Self::make_normalized_title_key(ids) {
+            self.by_normalized_title.entry(key).or_default().push(ids.clone());
+        }
+    }
+
+    /// Find by normalized title, with a year at most `year_tolerance` years off. The
+    /// closest year wins and the year difference is returned with it; a tie between
+    /// different entries, or several entries for an unknown year, matches nothing.
+    pub fn find_by_normalized_title(
+        &self,
+        title: &str,
+        year: Option<u32>,
+        media_type: &MediaType,
+        year_tolerance: u32,
+    ) -> Option<(Arc<MediaIds>, u32)> {
+        let key = (crate::id_matching::normalize_title(title), format!("{:?}", media_type));
+        let entries = self.by_normalized_title.get(&key)?;
+        let Some(year) = year else {
+            return match entries.as_slice() {
+                [only] => Some((only.clone(), 0)),
+                _ => None,
+            };
+        };
+        let candidates: Vec<(&Arc<MediaIds>, u32)> = entries.iter()
+            .filter_map(|ids| Some((ids, ids.year?.abs_diff(year))))
+            .filter(|(_, difference)| *difference <= year_tolerance)
+            .collect();
+        let closest = candidates.iter().map(|(_, difference)| *difference).min()?;
+        match candidates.iter().filter(|(_, difference)| *difference == closest).collect::<Vec<_>>().as_slice() {
+            [(ids, difference)] => Some(((*ids).clone(), *difference)),
+            _ => None,
+        }
+    }
+    
     /// Find by title and year - O(1) lookup
     /// 
     /// Returns the first matching entry found in the title/year index.
@@ -199,15 +301,81 @@ impl IdCache {
                 return Some(existing.clone());
             }
         }
+        if let Some(anilist) = ids.anilist_id {
+            if let Some(existing) = self.by_anilist.get(&anilist) {
+                return Some(existing.clone());
+            }
+        }
         None
     }
     
+    /// When the entry for `imdb_id` was added or last changed
+    pub fn cached_at(&self, imdb_id: &str) -> Option<DateTime<Utc>> {
+        self.cached_at.get(imdb_id).copied()
+    }
+    
+    /// Forget the entry for `imdb_id` under every ID it has, so the title is resolved
+    /// again. Returns the removed IDs.
+    pub fn invalidate(&mut self, imdb_id: &str) -> Option<MediaIds> {
+        let entry = self.by_imdb.remove(imdb_id)?;
+        let is_entry = |ids: &Arc<MediaIds>| Arc::ptr_eq(ids, &entry);
+        self.by_trakt.retain(|_, ids| !is_entry(ids));
+        self.by_simkl.retain(|_, ids| !is_entry(ids));
+        self.by_tmdb.retain(|_, ids| !is_entry(ids));
+        self.by_tvdb.retain(|_, ids| !is_entry(ids));
+        self.by_slug.retain(|_, ids| !is_entry(ids));
+        self.by_plex_rating_key.retain(|_, ids| !is_entry(ids));
+        self.by_anilist.retain(|_, ids| !is_entry(ids));
+        self.by_title_year.retain(|_, ids| !is_entry(ids));
+        self.by_normalized_title.values_mut().for_each(|entries| entries.retain(|ids| !is_entry(ids)));
+        self.cached_at.remove(imdb_id);
+        self.changed.remove(imdb_id);
+        self.dirty = true;
+        self.invalidated = true;
+        Some((*entry).clone())
+    }
+    
+    /// Invalidate every entry added or last changed before `cutoff`; returns how many
+    pub fn invalidate_older_than(&mut self, cutoff: DateTime<Utc>) -> usize {
+        let expired: Vec<String> = self.by_imdb.keys()
+            .filter(|imdb| self.cached_at(imdb).is_none_or(|cached_at| cached_at < cutoff))
+            .cloned()
+            .collect();
+        for imdb in &expired {
+            self.invalidate(imdb);
+        }
+        expired.len()
+    }
+    
     pub fn is_dirty(&self) -> bool {
         self.dirty
     }
     
     pub fn mark_clean(&mut self) {
         self.dirty = false;
+        self.invalidated = false;
+    }
+    
+    /// Whether an entry was invalidated since the last save, so journaling the changes
+    /// would leave it on disk
+    pub fn has_invalidations(&self) -> bool {
+        self.invalidated
+    }
+    
+    /// Entries inserted or updated since the last call, for journaling
+    pub fn take_changed(&mut self) -> Vec<CachedIds> {
+        let changed: Vec<String> = self.changed.drain().collect();
+        changed.iter()
+            .filter_map(|imdb| self.cached_entry(imdb))
+            .collect()
+    }
+    
+    fn cached_entry(&self, imdb_id: &str) -> Option<CachedIds> {
+        let ids = self.by_imdb.get(imdb_id)?;
+        Some(CachedIds {
+            ids: (**ids).clone(),
+            cached_at: self.cached_at(imdb_id).unwrap_or_else(Utc::now),
+        })
     }
     
     pub fn len(&self) -> usize {
@@ -224,27 +392,29 @@ impl IdCache {
     pub fn rebuild_title_year_index(&mut self) {
         // Clear existing index
         self.by_title_year.clear();
+        self.by_normalized_title.clear();
         
         // Rebuild from all entries in the cache
-        for ids in self.by_imdb.values() {
+        let entries: Vec<Arc<MediaIds>> = self.by_imdb.values().cloned().collect();
+        for ids in entries {
             if let (Some(ref title), Some(ref media_type)) = (&ids.title, &ids.media_type) {
                 let key = Self::make_title_key(title, ids.year, media_type);
                 self.by_title_year.insert(key, ids.clone());
             }
+            self.index_normalized_title(&ids);
         }
     }
     
     /// Get all entries as a vector (for serialization)
-    pub fn all_entries(&self) -> Vec<MediaIds> {
+    pub fn all_entries(&self) -> Vec<CachedIds> {
         // Use a HashSet to deduplicate by IMDB ID
-        use std::collections::HashSet;
         let mut seen = HashSet::new();
         let mut result = Vec::new();
         
         for ids in self.by_imdb.values() {
             if let Some(ref imdb) = ids.imdb_id {
                 if seen.insert(imdb.clone()) {
-                    result.push((**ids).clone());
+                    result.extend(self.cached_entry(imdb));
                 }
             }
         }
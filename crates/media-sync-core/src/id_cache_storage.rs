@@ -1,30 +1,68 @@
 use anyhow::Result;
 use bincode::{serialize, deserialize};
 use flate2::{Compression, write::GzEncoder, read::GzDecoder};
-use std::io::{Write, Read};
+use std::io::{BufRead, BufReader, Write, Read};
 use std::path::{Path, PathBuf};
+use chrono::{DateTime, Utc};
 use tracing::{info, debug, warn};
-use crate::id_cache::IdCache;
+use crate::id_cache::{CachedIds, IdCache};
+
+/// Starts the (decompressed) cache file since entries carry their age. Older files are
+/// a bare list of IDs.
+const SNAPSHOT_HEADER: &[u8] = b"IDC2";
 
 /// Efficient storage for ID cache
 /// 
 /// Uses binary format (bincode) with optional gzip compression for fast
 /// serialization and reduced storage size.
+///
+/// Entries added between full saves are appended to a journal next to the cache
+/// file (one JSON entry per line), so saving during a sync costs only the new
+/// entries. Loading replays the journal over the cache file; a full save compacts
+/// the journal into the cache file and removes it.
+///
+/// Entries saved before they carried an age get the time their file was last written.
 pub struct IdCacheStorage {
     cache_path: PathBuf,
+    journal_path: PathBuf,
     use_compression: bool,
+    /// Entries in the journal that aren't in the cache file yet
+    journal_entries: usize,
 }
 
 impl IdCacheStorage {
     pub fn new(cache_id_dir: &Path) -> Self {
         Self {
             cache_path: cache_id_dir.join("id_mappings.bin"),
+            journal_path: cache_id_dir.join("id_mappings.journal"),
             use_compression: true, // Enable by default for large caches
+            journal_entries: 0,
+        }
+    }
+    
+    /// Create the cache directory if needed and check that it accepts writes
+    pub fn ensure_writable(&self) -> Result<()> {
+        if let Some(parent) = self.cache_path.parent() {
+            std::fs::create_dir_all(parent)?;
         }
+        let probe_path = self.cache_path.with_extension("probe");
+        std::fs::write(&probe_path, b"")?;
+        std::fs::remove_file(&probe_path)?;
+        Ok(())
+    }
+    
+    /// Load cache from disk, replaying the journal over the cache file
+    pub fn load(&mut self) -> Result<IdCache> {
+        let mut cache = self.load_snapshot()?;
+        self.journal_entries = self.replay_journal(&mut cache)?;
+        // Everything loaded is already on disk
+        cache.take_changed();
+        cache.mark_clean();
+        Ok(cache)
     }
     
-    /// Load cache from disk
-    pub fn load(&self) -> Result<IdCache> {
+    /// Load the cache file without the journal
+    fn load_snapshot(&self) -> Result<IdCache> {
         if !self.cache_path.exists() {
             debug!("ID cache file does not exist, creating new cache");
             return Ok(IdCache::new());
@@ -42,9 +80,16 @@ impl IdCacheStorage {
             data
         };
         
-        // Try to deserialize as Vec<MediaIds> first, then rebuild cache
+        // Try to deserialize the entries, then rebuild cache
         // If deserialization fails (e.g., format changed), start with empty cache
-        let entries: Vec<media_sync_models::MediaIds> = match deserialize(&decoded) {
+        let parsed = match decoded.strip_prefix(SNAPSHOT_HEADER) {
+            Some(encoded) => deserialize::<Vec<CachedIds>>(encoded),
+            None => deserialize::<Vec<media_sync_models::MediaIds>>(&decoded).map(|entries| {
+                let cached_at = modified_at(&self.cache_path);
+                entries.into_iter().map(|ids| CachedIds { ids, cached_at }).collect()
+            }),
+        };
+        let entries: Vec<CachedIds> = match parsed {
             Ok(entries) => entries,
             Err(e) => {
                 // Cache format is incompatible (likely due to schema changes)
@@ -68,11 +113,11 @@ impl IdCacheStorage {
         
         let mut cache = IdCache::new();
         let mut entries_with_metadata = 0;
-        for ids in entries {
-            if ids.title.is_some() && ids.media_type.is_some() {
+        for entry in entries {
+            if entry.ids.title.is_some() && entry.ids.media_type.is_some() {
                 entries_with_metadata += 1;
             }
-            cache.insert(ids);
+            cache.insert_cached(entry);
         }
         
         // Rebuild title/year index to ensure all entries with metadata are indexed
@@ -104,8 +149,76 @@ impl IdCacheStorage {
         Ok(cache)
     }
     
-    /// Save cache to disk
-    pub fn save(&self, cache: &IdCache) -> Result<()> {
+    /// Insert the journal's entries into `cache`; returns how many were replayed.
+    /// A truncated last line (a write cut short) is skipped.
+    fn replay_journal(&self, cache: &mut IdCache) -> Result<usize> {
+        if !self.journal_path.exists() {
+            return Ok(0);
+        }
+        
+        let start = std::time::Instant::now();
+        let reader = BufReader::new(std::fs::File::open(&self.journal_path)?);
+        let legacy_cached_at = modified_at(&self.journal_path);
+        let mut replayed = 0;
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry = serde_json::from_str::<CachedIds>(&line).or_else(|e| {
+                serde_json::from_str::<media_sync_models::MediaIds>(&line)
+                    .map(|ids| CachedIds { ids, cached_at: legacy_cached_at })
+                    .map_err(|_| e)
+            });
+            match entry {
+                Ok(entry) => {
+                    cache.insert_cached(entry);
+                    replayed += 1;
+                }
+                Err(e) => warn!("Skipping unreadable ID cache journal entry: {}", e),
+            }
+        }
+        
+        debug!("Replayed {} ID cache journal entries in {:?}", replayed, start.elapsed());
+        Ok(replayed)
+    }
+    
+    /// Append entries to the journal without rewriting the cache file
+    pub fn append(&mut self, entries: &[CachedIds]) -> Result<()> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+        let start = std::time::Instant::now();
+        
+        let mut lines = String::new();
+        for entry in entries {
+            lines.push_str(&serde_json::to_string(entry)?);
+            lines.push('\n');
+        }
+        let mut journal = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.journal_path)?;
+        journal.write_all(lines.as_bytes())?;
+        self.journal_entries += entries.len();
+        
+        debug!(
+            "Journaled {} ID cache entries ({} pending compaction) in {:?}",
+            entries.len(),
+            self.journal_entries,
+            start.elapsed()
+        );
+        
+        Ok(())
+    }
+    
+    /// Entries in the journal that a full save would compact into the cache file
+    pub fn journal_entries(&self) -> usize {
+        self.journal_entries
+    }
+    
+    /// Save cache to disk, compacting the journal into the cache file
+    pub fn save(&mut self, cache: &IdCache) -> Result<()> {
         let start = std::time::Instant::now();
         
         // Ensure parent directory exists
@@ -117,7 +230,8 @@ impl IdCacheStorage {
         let entries = cache.all_entries();
         
         // Serialize to binary
-        let serialized = serialize(&entries)?;
+        let mut serialized = SNAPSHOT_HEADER.to_vec();
+        serialized.extend(serialize(&entries)?);
         
         let encoded = if self.use_compression {
             let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
@@ -132,6 +246,12 @@ impl IdCacheStorage {
         std::fs::write(&temp_path, encoded)?;
         std::fs::rename(&temp_path, &self.cache_path)?;
         
+        // The cache file now holds everything in the journal
+        if self.journal_path.exists() {
+            std::fs::remove_file(&self.journal_path)?;
+        }
+        self.journal_entries = 0;
+        
         info!(
             "Saved ID cache: {} entries in {:?}",
             cache.len(),
@@ -155,9 +275,16 @@ impl IdCacheStorage {
         self.use_compression = use_compression;
     }
     
-    /// Check if cache file exists
+    /// Check if cache file or journal exists
     pub fn cache_exists(&self) -> bool {
-        self.cache_path.exists()
+        self.cache_path.exists() || self.journal_path.exists()
     }
 }
 
+/// When `path` was last written, for entries saved without their own time
+fn modified_at(path: &Path) -> DateTime<Utc> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .map(DateTime::<Utc>::from)
+        .unwrap_or_else(|_| Utc::now())
+}
@@ -1,9 +1,10 @@
 use anyhow::Result;
+use async_trait::async_trait;
 use futures::future::BoxFuture;
 use futures::stream::{FuturesUnordered, StreamExt};
 use futures::FutureExt;
 use media_sync_models::{MediaIds, MediaType};
-use media_sync_sources::{MediaSource, SourceError};
+use media_sync_sources::{IdLookupProvider, MediaSource, SourceError};
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, SystemTime};
@@ -26,11 +27,19 @@ pub struct IdLookupService {
     
     /// Time window to skip searches (default: 7 days)
     search_cooldown: Duration,
+
+    /// Providers that aren't sources (TMDB), asked in order when the sources' providers
+    /// found no IMDB ID
+    fallback_providers: Vec<Arc<dyn IdLookupProvider>>,
 }
 
 impl IdLookupService {
-    /// Create a new lookup service from available sources
-    pub async fn new(sources: &[Arc<RwLock<Box<dyn MediaSource<Error = SourceError>>>>]) -> Self {
+    /// Create a new lookup service from available sources, with `fallback_providers`
+    /// asked after them
+    pub async fn new(
+        sources: &[Arc<RwLock<Box<dyn MediaSource<Error = SourceError>>>>],
+        fallback_providers: Vec<Arc<dyn IdLookupProvider>>,
+    ) -> Self {
         let mut providers: Vec<(String, u8)> = Vec::new();
         
         for source in sources {
@@ -53,8 +62,11 @@ impl IdLookupService {
         // Sort by priority (highest first)
         providers.sort_by(|a, b| b.1.cmp(&a.1));
         
-        if providers.is_empty() {
-            warn!("ID lookup service: No lookup providers available! ID resolution by title will not work. Ensure at least one source (Plex, Trakt, or Simkl) is authenticated.");
+        if providers.is_empty() && fallback_providers.is_empty() {
+            warn!("ID lookup service: No lookup providers available! ID resolution by title will not work. Ensure at least one source (Plex, Trakt, or Simkl) is authenticated, or set [sources.tmdb] api_key.");
+        } else if providers.is_empty() {
+            debug!("ID lookup service: No source lookup providers available, using only {:?}",
+                   fallback_providers.iter().map(|provider| provider.lookup_provider_name()).collect::<Vec<_>>());
         } else {
             debug!("ID lookup service: {} provider(s) available: {:?}", 
                    providers.len(), 
@@ -65,6 +77,50 @@ impl IdLookupService {
             providers,
             search_timestamps: Arc::new(RwLock::new(HashMap::new())),
             search_cooldown: Duration::from_secs(7 * 24 * 3600), // 7 days
+            fallback_providers,
+        }
+    }
+
+    /// Record a search for `cache_key`; false if the same search ran within the cooldown
+    async fn start_search(&self, cache_key: &str) -> bool {
+        let mut timestamps = self.search_timestamps.write().await;
+        let recent = timestamps.get(cache_key)
+            .and_then(|last_search| SystemTime::now().duration_since(*last_search).ok())
+            .is_some_and(|elapsed| elapsed < self.search_cooldown);
+        if !recent {
+            timestamps.insert(cache_key.to_string(), SystemTime::now());
+        }
+        !recent
+    }
+
+    /// Ask the fallback providers in order until one finds `required_id_type`, merging
+    /// what they find into `ids`. Their failures are only logged.
+    async fn lookup_fallback_ids(
+        &self,
+        ids: &mut MediaIds,
+        title: &str,
+        year: Option<u32>,
+        media_type: &MediaType,
+        required_id_type: &str,
+    ) {
+        for provider in &self.fallback_providers {
+            let provider_name = provider.lookup_provider_name();
+            if !self.start_search(&Self::make_cache_key(provider_name, title, year, media_type)).await {
+                debug!("ID lookup: Skipping {} fallback search for '{}' (year: {:?}) - searched within the 7-day cooldown",
+                       provider_name, title, year);
+                continue;
+            }
+            debug!("ID lookup: Executing {} fallback search for '{}' (year: {:?}, type: {:?})", provider_name, title, year, media_type);
+            match provider.lookup_ids(title, year, media_type).await {
+                Ok(Some(found)) => {
+                    ids.merge(&found);
+                    if Self::has_required_id(ids, required_id_type) {
+                        return;
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => debug!("ID lookup via {} failed for '{}' (year: {:?}): {}", provider_name, title, year, e),
+            }
         }
     }
     
@@ -119,7 +175,7 @@ impl IdLookupService {
         }
         
         // Check if any providers are available
-        if self.providers.is_empty() {
+        if self.providers.is_empty() && self.fallback_providers.is_empty() {
             warn!("ID lookup: No providers available for '{}' (year: {:?}, type: {:?}). Cannot perform title-based lookup. Ensure at least one source (Plex, Trakt, or Simkl) is authenticated.", 
                   title, year, media_type);
             debug!("ID lookup: Provider list is empty - no lookup providers registered");
@@ -276,6 +332,9 @@ impl IdLookupService {
             return Ok((ids, Some(additional_rx)));
         }
         
+        // No source found the required ID - try the fallback providers
+        self.lookup_fallback_ids(&mut merged_ids, title, year, media_type, required_id_type).await;
+
         // No required ID found - return merged results
         // Send remaining results to channel for background processing
         for ids in remaining_results {
@@ -292,9 +351,11 @@ impl IdLookupService {
         Ok((merged_ids, None))
     }
     
-    /// Get list of available lookup providers
+    /// Get list of available lookup providers, fallback providers last
     pub fn available_providers(&self) -> Vec<&str> {
-        self.providers.iter().map(|(name, _)| name.as_str()).collect()
+        self.providers.iter().map(|(name, _)| name.as_str())
+            .chain(self.fallback_providers.iter().map(|provider| provider.lookup_provider_name()))
+            .collect()
     }
     
     /// Look up title, year, and IDs by IMDB ID (reverse lookup)
@@ -317,7 +378,7 @@ impl IdLookupService {
         media_type: &MediaType,
     ) -> Result<Option<(String, Option<u32>, MediaIds)>> {
         // Check if any providers are available
-        if self.providers.is_empty() {
+        if self.providers.is_empty() && self.fallback_providers.is_empty() {
             debug!("ID reverse lookup: No providers available for imdb_id={}, type={:?}", imdb_id, media_type);
             return Ok(None);
         }
@@ -355,8 +416,43 @@ impl IdLookupService {
                 }
             }
         }
+
+        for provider in &self.fallback_providers {
+            match provider.lookup_by_imdb_id(imdb_id, media_type).await {
+                Ok(Some(found)) => return Ok(Some(found)),
+                Ok(None) => {}
+                Err(e) => debug!("ID reverse lookup via {} failed for imdb_id={}: {}", provider.lookup_provider_name(), imdb_id, e),
+            }
+        }
         
         Ok(None)
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use media_sync_sources::test_support::serve_json_routes;
+    use media_sync_sources::TmdbLookupProvider;
+
+    #[tokio::test]
+    async fn test_fallback_provider_resolves_without_any_source() {
+        let (base, server) = serve_json_routes(vec![
+            ("/3/search/tv", r#"{"results": [{"id": 1399, "name": "Game of Thrones", "first_air_date": "2011-04-17"}]}"#),
+            ("/3/tv/1399/external_ids", r#"{"imdb_id": "tt0944947", "tvdb_id": 121361}"#),
+        ]);
+        let tmdb: Arc<dyn IdLookupProvider> = Arc::new(TmdbLookupProvider::new(&format!("{}/3", base), Some("secret")));
+        let service = IdLookupService::new(&[], vec![tmdb]).await;
+        assert_eq!(service.available_providers(), vec!["tmdb"]);
+
+        let (ids, _) = service.lookup_ids(&[], "Game of Thrones", Some(2011), &MediaType::Show, None, None).await.unwrap();
+        assert_eq!(ids.imdb_id.as_deref(), Some("tt0944947"));
+        assert_eq!(ids.tvdb_id, Some(121361));
+        assert_eq!(server.join().unwrap().len(), 2);
+
+        // The same search isn't sent again within the cooldown
+        let (ids, _) = service.lookup_ids(&[], "Game of Thrones", Some(2011), &MediaType::Show, None, None).await.unwrap();
+        assert!(ids.is_empty());
+    }
+}
@@ -38,6 +38,18 @@ pub fn match_by_any_id(ids1: &MediaIds, ids2: &MediaIds) -> bool {
         }
     }
     
+    if let (Some(anilist1), Some(anilist2)) = (ids1.anilist_id, ids2.anilist_id) {
+        if anilist1 == anilist2 {
+            return true;
+        }
+    }
+    
+    if let (Some(mal1), Some(mal2)) = (ids1.mal_id, ids2.mal_id) {
+        if mal1 == mal2 {
+            return true;
+        }
+    }
+    
     if let (Some(ref slug1), Some(ref slug2)) = (&ids1.slug, &ids2.slug) {
         if slug1 == slug2 {
             return true;
@@ -114,6 +126,15 @@ where
                     }
                 }
             }
+            
+            if let Some(anilist) = item_ids.anilist_id {
+                let anilist_str = format!("anilist:{}", anilist);
+                if let Some(cached) = id_resolver.find_by_any_id(&anilist_str) {
+                    if match_by_any_id(&cached, &candidate_ids) {
+                        return Some(index);
+                    }
+                }
+            }
         }
     }
     
@@ -181,6 +202,7 @@ where
                         item_ids.simkl_id.map(|id| format!("simkl:{}", id)),
                         item_ids.tmdb_id.map(|id| format!("tmdb:{}", id)),
                         item_ids.tvdb_id.map(|id| format!("tvdb:{}", id)),
+                        item_ids.anilist_id.map(|id| format!("anilist:{}", id)),
                         item_ids.slug.clone(),
                     ].into_iter().flatten() {
                         if let Some(cached) = id_resolver.find_by_any_id(&id_str) {
@@ -206,3 +228,6 @@ where
 }
 
 
+
+/// Title for fuzzy matching (shared with search disambiguation)
+pub use media_sync_sources::disambiguation::normalize_title;
@@ -1,10 +1,10 @@
 use anyhow::Result;
 use media_sync_models::{MediaIds, MediaType};
-use media_sync_sources::{MediaSource, SourceError};
+use media_sync_sources::{MediaSource, SourceError, TmdbLookupProvider};
 use std::path::Path;
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use tracing::{debug, trace, warn};
+use tracing::{debug, info, trace, warn};
 use crate::id_cache::IdCache;
 use crate::id_cache_storage::IdCacheStorage;
 use crate::id_lookup::IdLookupService;
@@ -12,18 +12,65 @@ use crate::id_lookup::IdLookupService;
 /// Configuration for ID resolver behavior
 #[derive(Clone)]
 pub struct IdResolverConfig {
-    /// Save incrementally (only changes) or full save
+    /// Save incrementally (journal only changes) or full save
     pub incremental_saves: bool,
     
-    /// Full save interval (every N inserts, 0 = always incremental)
+    /// Compact the journal into a full save once it holds this many entries (0 = never)
     pub full_save_interval: usize,
+    
+    /// Cached entries older than this are resolved again instead of trusted (None = kept forever)
+    pub cache_ttl: Option<chrono::Duration>,
+
+    /// Asked for titles the sources' lookup providers couldn't resolve
+    pub tmdb_lookup: Option<TmdbLookupProvider>,
+
+    /// Fall back to matching cached titles ignoring case, punctuation and leading articles
+    pub fuzzy_titles: bool,
+
+    /// Years a fuzzy title match may be off by
+    pub year_tolerance: u32,
 }
 
 impl Default for IdResolverConfig {
     fn default() -> Self {
         Self {
             incremental_saves: true,
-            full_save_interval: 0, // Always incremental by default
+            full_save_interval: 5_000,
+            cache_ttl: Self::cache_ttl_days(media_sync_config::DEFAULT_ID_CACHE_TTL_DAYS),
+            tmdb_lookup: None,
+            fuzzy_titles: true,
+            year_tolerance: 1,
+        }
+    }
+}
+
+impl IdResolverConfig {
+    /// `id_matching.cache_ttl_days` as a TTL; 0 days keeps entries forever
+    pub fn cache_ttl_days(days: u32) -> Option<chrono::Duration> {
+        (days > 0).then(|| chrono::Duration::days(days as i64))
+    }
+}
+
+impl From<&media_sync_config::IdMatchingConfig> for IdResolverConfig {
+    fn from(config: &media_sync_config::IdMatchingConfig) -> Self {
+        Self {
+            cache_ttl: Self::cache_ttl_days(config.cache_ttl_days),
+            fuzzy_titles: config.fuzzy_titles,
+            year_tolerance: config.year_tolerance,
+            ..Self::default()
+        }
+    }
+}
+
+/// `[id_matching]`, plus the TMDB fallback lookup from `[sources.tmdb]`
+impl From<&media_sync_config::Config> for IdResolverConfig {
+    fn from(config: &media_sync_config::Config) -> Self {
+        let tmdb_lookup = config.tmdb_id_lookup()
+            .then(|| TmdbLookupProvider::from_config(config))
+            .flatten();
+        Self {
+            tmdb_lookup,
+            ..Self::from(&config.id_matching)
         }
     }
 }
@@ -38,42 +85,70 @@ pub struct IdResolver {
     /// In-memory cache with multi-index structure
     cache: IdCache,
     
-    /// Storage layer
-    storage: IdCacheStorage,
+    /// Storage layer (None when the cache dir is unusable and the cache is in-memory only)
+    storage: Option<IdCacheStorage>,
     
     /// Lookup service
     lookup_service: IdLookupService,
     
     /// Configuration
     config: IdResolverConfig,
-    
-    /// Track number of inserts since last full save
-    inserts_since_save: usize,
 }
 
 impl IdResolver {
+    /// Creates `cache_dir` if it is missing. If the directory can't be created or written,
+    /// or the existing cache can't be read, the resolver falls back to an in-memory cache
+    /// for this run instead of failing the sync.
     pub async fn new(
         cache_dir: &Path,
         sources: &[Arc<RwLock<Box<dyn MediaSource<Error = SourceError>>>>],
         config: IdResolverConfig,
     ) -> Result<Self> {
-        let storage = IdCacheStorage::new(cache_dir);
+        let mut storage = IdCacheStorage::new(cache_dir);
         
-        // Load cache (lazy - only if file exists)
-        let cache = if storage.cache_exists() {
-            storage.load()?
-        } else {
-            IdCache::new()
+        let (cache, storage) = match storage.ensure_writable() {
+            Err(e) => {
+                warn!(
+                    "ID cache directory {} is not writable ({}); using an in-memory ID cache for this sync",
+                    cache_dir.display(),
+                    e
+                );
+                (IdCache::new(), None)
+            }
+            // Load cache (lazy - only if file exists)
+            Ok(()) if !storage.cache_exists() => (IdCache::new(), Some(storage)),
+            Ok(()) => match storage.load() {
+                Ok(mut cache) => {
+                    if let Some(ttl) = config.cache_ttl {
+                        let expired = cache.invalidate_older_than(chrono::Utc::now() - ttl);
+                        if expired > 0 {
+                            info!("{} ID cache entries are older than {} days and will be resolved again", expired, ttl.num_days());
+                        }
+                    }
+                    (cache, Some(storage))
+                }
+                Err(e) => {
+                    // Don't overwrite a cache we couldn't read
+                    warn!(
+                        "Failed to read ID cache in {} ({}); using an in-memory ID cache for this sync",
+                        cache_dir.display(),
+                        e
+                    );
+                    (IdCache::new(), None)
+                }
+            },
         };
         
-        let lookup_service = IdLookupService::new(sources).await;
+        let fallback_providers = config.tmdb_lookup.iter()
+            .map(|tmdb| Arc::new(tmdb.clone()) as Arc<dyn media_sync_sources::IdLookupProvider>)
+            .collect();
+        let lookup_service = IdLookupService::new(sources, fallback_providers).await;
         
         Ok(Self {
             cache,
             storage,
             lookup_service,
             config,
-            inserts_since_save: 0,
         })
     }
     
@@ -110,8 +185,7 @@ impl IdResolver {
         // Step 2: If IDs missing, check persistent cache by title/year first
         if ids.is_empty() || ids.imdb_id.is_none() {
             // Check persistent cache by title/year before doing external lookup
-            if let Some(cached) = self.cache.find_by_title_year(title, year, media_type) {
-                tracing::trace!("ID resolver: Found '{}' (year: {:?}) in persistent cache by title/year, using cached IDs", title, year);
+            if let Some(cached) = self.find_cached_by_title(title, year, media_type) {
                 return Ok(((*cached).clone(), None));
             }
             
@@ -170,7 +244,6 @@ impl IdResolver {
                                 }
                                 // Re-insert immediately with metadata to update the title/year index
                                 self.cache.insert(merged.clone());
-                                self.inserts_since_save += 1;
                                 ids = merged;
                                 cached_ids_found = true;
                                 tracing::trace!("ID resolver: Found '{}' in cache (via imdb_id={}) after external lookup, updating with metadata", title, imdb);
@@ -196,7 +269,6 @@ impl IdResolver {
                                     }
                                     // Re-insert immediately with metadata to update the title/year index
                                     self.cache.insert(merged.clone());
-                                    self.inserts_since_save += 1;
                                     ids = merged;
                                     cached_ids_found = true;
                                     tracing::trace!("ID resolver: Found '{}' in cache (via trakt_id={}) after external lookup, updating with metadata", title, trakt_id);
@@ -222,7 +294,6 @@ impl IdResolver {
                                     }
                                     // Re-insert immediately with metadata to update the title/year index
                                     self.cache.insert(merged.clone());
-                                    self.inserts_since_save += 1;
                                     ids = merged;
                                     cached_ids_found = true;
                                     tracing::trace!("ID resolver: Found '{}' in cache (via tmdb_id={}) after external lookup, updating with metadata", title, tmdb_id);
@@ -253,7 +324,6 @@ impl IdResolver {
                             ids_with_metadata.year = year;
                             ids_with_metadata.media_type = Some(media_type.clone());
                             self.cache.insert(ids_with_metadata);
-                            self.inserts_since_save += 1;
                         }
                     }
                     
@@ -279,13 +349,35 @@ impl IdResolver {
                 ids_with_metadata.year = year;
                 ids_with_metadata.media_type = Some(media_type.clone());
                 self.cache.insert(ids_with_metadata);
-                self.inserts_since_save += 1;
             }
         }
         
         Ok((ids, None))
     }
     
+    /// Cached IDs for a title: the exact title and year, else (with `fuzzy_titles`) the
+    /// normalized title within `year_tolerance` years. Exact matches always win.
+    fn find_cached_by_title(&self, title: &str, year: Option<u32>, media_type: &MediaType) -> Option<Arc<MediaIds>> {
+        if let Some(cached) = self.cache.find_by_title_year(title, year, media_type) {
+            debug!("ID resolver: '{}' (year: {:?}) matched the cache by exact title and year", title, year);
+            return Some(cached);
+        }
+        if !self.config.fuzzy_titles {
+            return None;
+        }
+        let (cached, year_difference) = self.cache.find_by_normalized_title(title, year, media_type, self.config.year_tolerance)?;
+        debug!(
+            "ID resolver: '{}' (year: {:?}) matched cached '{}' (year: {:?}, {:?}) by normalized title, {}",
+            title,
+            year,
+            cached.title.as_deref().unwrap_or_default(),
+            cached.year,
+            cached.imdb_id,
+            if year_difference == 0 { "same year".to_string() } else { format!("year off by {}", year_difference) }
+        );
+        Some(cached)
+    }
+    
     /// Find MediaIds by any ID type
     pub fn find_by_any_id(&self, id: &str) -> Option<MediaIds> {
         self.cache.find_by_any_id(id).map(|arc| (*arc).clone())
@@ -323,7 +415,6 @@ impl IdResolver {
                 }
             }
             self.cache.insert(ids);
-            self.inserts_since_save += 1;
         }
     }
     
@@ -332,23 +423,28 @@ impl IdResolver {
         if !self.cache.is_dirty() {
             return Ok(());
         }
-        
-        // Determine if we should do a full save
-        let should_full_save = if self.config.full_save_interval > 0 {
-            self.inserts_since_save >= self.config.full_save_interval
-        } else {
-            false // Always incremental if interval is 0
+        let storage = match self.storage {
+            Some(ref mut storage) => storage,
+            None => {
+                trace!("ID cache is in-memory only, skipping save");
+                return Ok(());
+            }
         };
         
+        let changed = self.cache.take_changed();
+        // Replaying the journal merges into the saved entry, so an invalidated entry
+        // would come back with its stale IDs; only a full save drops it
+        let should_full_save = !self.config.incremental_saves
+            || self.cache.has_invalidations()
+            || (self.config.full_save_interval > 0
+                && storage.journal_entries() + changed.len() >= self.config.full_save_interval);
+        
         if should_full_save {
-            self.storage.save(&self.cache)?;
-            self.inserts_since_save = 0;
-        } else if self.config.incremental_saves {
-            // For incremental saves, we still do a full save (simplified implementation)
-            // In a more advanced implementation, we could track modified entries
-            self.storage.save(&self.cache)?;
-        } else {
-            self.storage.save(&self.cache)?;
+            storage.save(&self.cache)?;
+        } else if let Err(e) = storage.append(&changed) {
+            // The changes aren't on disk yet, so fall back to writing everything
+            warn!("Failed to append to ID cache journal ({}); saving the full cache", e);
+            storage.save(&self.cache)?;
         }
         
         self.cache.mark_clean();
@@ -360,6 +456,11 @@ impl IdResolver {
         (self.cache.len(), self.cache.is_dirty())
     }
     
+    /// Whether the cache is saved to disk (false after falling back to in-memory)
+    pub fn is_persistent(&self) -> bool {
+        self.storage.is_some()
+    }
+    
     /// Look up title, year, and IDs by IMDB ID (reverse lookup)
     /// 
     /// This is used when we have an IMDB ID but need the title/year for discover provider searches.
@@ -386,7 +487,6 @@ impl IdResolver {
                 ids.year = year;
                 ids.media_type = Some(media_type.clone());
                 self.cache.insert(ids.clone());
-                self.inserts_since_save += 1;
                 
                 tracing::trace!("ID reverse lookup: Found '{}' (year: {:?}) via external lookup for imdb_id={}", title, year, imdb_id);
                 Ok(Some((title, year, ids)))
@@ -408,3 +508,178 @@ impl IdResolver {
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_new_creates_missing_cache_dir() {
+        let temp = tempfile::tempdir().unwrap();
+        let cache_dir = temp.path().join("cache").join("id");
+
+        let resolver = IdResolver::new(&cache_dir, &[], IdResolverConfig::default()).await.unwrap();
+        assert!(resolver.is_persistent());
+        assert!(cache_dir.is_dir());
+    }
+
+    #[tokio::test]
+    async fn test_new_falls_back_to_memory_when_dir_unusable() {
+        // A regular file where the cache directory should be can never be created as a dir
+        let temp = tempfile::tempdir().unwrap();
+        let blocker = temp.path().join("cache");
+        std::fs::write(&blocker, b"").unwrap();
+
+        let mut resolver = IdResolver::new(&blocker.join("id"), &[], IdResolverConfig::default()).await.unwrap();
+        assert!(!resolver.is_persistent());
+
+        resolver.cache_ids_with_metadata(
+            MediaIds { imdb_id: Some("tt0113277".to_string()), ..Default::default() },
+            Some("Heat"),
+            Some(1995),
+            Some(&MediaType::Movie),
+        );
+        assert!(resolver.save_if_dirty().is_ok());
+        assert_eq!(resolver.cache_stats().0, 1);
+    }
+
+    #[tokio::test]
+    async fn test_titles_match_fuzzily_within_the_year_tolerance() {
+        let temp = tempfile::tempdir().unwrap();
+        let mut resolver = IdResolver::new(temp.path(), &[], IdResolverConfig::default()).await.unwrap();
+        for (imdb_id, title, year) in [("tt0133093", "The Matrix", 1999), ("tt0113277", "Heat", 1995), ("tt0000002", "Heat!", 1996)] {
+            resolver.cache_ids_with_metadata(
+                MediaIds { imdb_id: Some(imdb_id.to_string()), ..Default::default() },
+                Some(title),
+                Some(year),
+                Some(&MediaType::Movie),
+            );
+        }
+        let cached = |resolver: &IdResolver, title: &str, year: u32| {
+            resolver.find_cached_by_title(title, Some(year), &MediaType::Movie).and_then(|ids| ids.imdb_id.clone())
+        };
+
+        assert_eq!(cached(&resolver, "Matrix, The", 1999).as_deref(), Some("tt0133093"));
+        assert_eq!(cached(&resolver, "the matrix", 2000).as_deref(), Some("tt0133093"));
+        assert_eq!(cached(&resolver, "The Matrix", 2001), None);
+        // An exact title and year match wins; otherwise the closest year does
+        assert_eq!(cached(&resolver, "Heat", 1995).as_deref(), Some("tt0113277"));
+        assert_eq!(cached(&resolver, "Heat", 1996).as_deref(), Some("tt0000002"));
+        assert_eq!(cached(&resolver, "HEAT", 1994).as_deref(), Some("tt0113277"));
+
+        let strict = IdResolverConfig { fuzzy_titles: false, ..Default::default() };
+        let mut resolver = IdResolver::new(temp.path(), &[], strict).await.unwrap();
+        resolver.cache_ids_with_metadata(
+            MediaIds { imdb_id: Some("tt0133093".to_string()), ..Default::default() },
+            Some("The Matrix"),
+            Some(1999),
+            Some(&MediaType::Movie),
+        );
+        assert_eq!(cached(&resolver, "Matrix, The", 1999), None);
+    }
+
+    #[tokio::test]
+    async fn test_saves_journal_new_entries_until_compaction() {
+        let temp = tempfile::tempdir().unwrap();
+        let cache_dir = temp.path().join("id");
+        let config = IdResolverConfig { incremental_saves: true, full_save_interval: 3, ..Default::default() };
+        let cache = |resolver: &mut IdResolver, imdb_id: &str, title: &str| {
+            resolver.cache_ids_with_metadata(
+                MediaIds { imdb_id: Some(imdb_id.to_string()), ..Default::default() },
+                Some(title),
+                Some(1995),
+                Some(&MediaType::Movie),
+            );
+        };
+
+        let mut resolver = IdResolver::new(&cache_dir, &[], config.clone()).await.unwrap();
+        cache(&mut resolver, "tt0113277", "Heat");
+        resolver.save_if_dirty().unwrap();
+        cache(&mut resolver, "tt0114369", "Se7en");
+        resolver.save_if_dirty().unwrap();
+        assert!(!cache_dir.join("id_mappings.bin").exists());
+        assert!(cache_dir.join("id_mappings.journal").exists());
+
+        // A new run replays the journal
+        let mut resolver = IdResolver::new(&cache_dir, &[], config).await.unwrap();
+        assert_eq!(resolver.cache_stats(), (2, false));
+        assert!(resolver.find_by_any_id("tt0114369").is_some());
+
+        // The third journaled entry reaches the interval and compacts
+        cache(&mut resolver, "tt0112641", "Casino");
+        resolver.save_if_dirty().unwrap();
+        assert!(cache_dir.join("id_mappings.bin").exists());
+        assert!(!cache_dir.join("id_mappings.journal").exists());
+    }
+
+    #[tokio::test]
+    async fn test_expired_and_invalidated_entries_are_resolved_again() {
+        use crate::id_cache::CachedIds;
+
+        let temp = tempfile::tempdir().unwrap();
+        let cache_dir = temp.path().join("id");
+        let entry = |imdb_id: &str, title: &str, age_days: i64| CachedIds {
+            ids: MediaIds {
+                imdb_id: Some(imdb_id.to_string()),
+                title: Some(title.to_string()),
+                year: Some(1995),
+                media_type: Some(MediaType::Movie),
+                ..Default::default()
+            },
+            cached_at: chrono::Utc::now() - chrono::Duration::days(age_days),
+        };
+        let mut storage = IdCacheStorage::new(&cache_dir);
+        let mut cache = IdCache::new();
+        cache.insert_cached(entry("tt0113277", "Heat", 200));
+        cache.insert_cached(entry("tt0114369", "Se7en", 10));
+        cache.insert_cached(entry("tt0112641", "Casino", 10));
+        storage.save(&cache).unwrap();
+
+        let config = IdResolverConfig { cache_ttl: IdResolverConfig::cache_ttl_days(90), ..Default::default() };
+        let resolver = IdResolver::new(&cache_dir, &[], config.clone()).await.unwrap();
+        assert!(resolver.find_by_any_id("tt0113277").is_none());
+        assert!(resolver.find_by_any_id("tt0114369").is_some());
+
+        // Invalidating one title removes it from the saved cache, under every index
+        let mut cache = storage.load().unwrap();
+        assert!(cache.invalidate("tt0114369").is_some());
+        assert!(cache.find_by_title_year("Se7en", Some(1995), &MediaType::Movie).is_none());
+        storage.save(&cache).unwrap();
+
+        let resolver = IdResolver::new(&cache_dir, &[], config).await.unwrap();
+        assert!(resolver.find_by_any_id("tt0114369").is_none());
+        assert!(resolver.find_by_any_id("tt0112641").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_an_expired_entry_resolved_again_drops_its_stale_ids() {
+        use crate::id_cache::CachedIds;
+
+        let temp = tempfile::tempdir().unwrap();
+        let cache_dir = temp.path().join("id");
+        let mut storage = IdCacheStorage::new(&cache_dir);
+        let mut cache = IdCache::new();
+        cache.insert_cached(CachedIds {
+            ids: MediaIds { imdb_id: Some("tt0113277".to_string()), tmdb_id: Some(1), ..Default::default() },
+            cached_at: chrono::Utc::now() - chrono::Duration::days(200),
+        });
+        storage.save(&cache).unwrap();
+
+        // The expired entry is resolved again without its wrong TMDB ID and journaled
+        let config = IdResolverConfig {
+            cache_ttl: IdResolverConfig::cache_ttl_days(90),
+            incremental_saves: true,
+            full_save_interval: 100,
+            ..Default::default()
+        };
+        let mut resolver = IdResolver::new(&cache_dir, &[], config.clone()).await.unwrap();
+        assert!(resolver.find_by_any_id("tt0113277").is_none());
+        resolver.cache_ids(MediaIds { imdb_id: Some("tt0113277".to_string()), trakt_id: Some(117), ..Default::default() });
+        resolver.save_if_dirty().unwrap();
+
+        let resolver = IdResolver::new(&cache_dir, &[], config).await.unwrap();
+        let ids = resolver.find_by_any_id("tt0113277").unwrap();
+        assert_eq!((ids.trakt_id, ids.tmdb_id), (Some(117), None));
+        assert!(resolver.find_by_any_id("tmdb:1").is_none());
+    }
+}
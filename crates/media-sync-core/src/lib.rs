@@ -8,10 +8,38 @@ pub mod id_cache_storage;
 pub mod id_lookup;
 pub mod id_resolver;
 pub mod id_matching;
+pub mod event_buffer;
+pub mod review_text;
+pub mod review_links;
+pub mod episode_checkins;
+pub mod conflicts;
+pub mod dry_run_compare;
+pub mod failures;
+pub mod plan;
+pub mod prune;
+pub mod reconcile;
+pub mod source_diff;
+pub mod metrics;
+pub mod lock;
+pub mod notify;
+pub mod lists;
+pub mod collection;
+pub mod show_progress;
+pub mod progress;
+pub mod export;
 
 pub use diff::{filter_items_by_imdb_id, filter_missing_imdb_ids, remove_duplicates_by_imdb_id, filter_reviews_by_imdb_id_and_content, filter_ratings_by_imdb_id_and_value};
 
-pub use sync::{SyncOrchestrator, SyncResult, SyncOptions};
+pub use sync::{SyncOrchestrator, SyncResult, SyncOptions, SourceOutcome, parse_since, EXIT_PARTIAL, EXIT_FAILED, EXIT_CONFIG};
 pub use resolution::{SourceData, ResolvedData, resolve_all_conflicts};
 pub use cache::CacheManager;
+pub use event_buffer::{EventBuffer, BufferedEvents};
+pub use plan::{SyncPlan, SourcePlan, SYNC_PLAN_VERSION};
+pub use reconcile::ReconcileReport;
+pub use source_diff::{SourceDiff, RatingConflict};
+pub use export::TraktExport;
+pub use metrics::{MetricsAccumulator, RunMetrics, SourceMetrics};
+pub use lock::SyncLock;
+pub use notify::{Notifier, RunNotification, RunStatus};
+pub use progress::{ProgressTracker, NoProgress};
 
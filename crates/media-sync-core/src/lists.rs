@@ -0,0 +1,136 @@
+//! Custom list sync (`sync_lists` / `sync --lists`).
+//!
+//! Lists are matched across sources by normalized name. The resolved list is the union
+//! of every source's items, named as the most preferred source names it. Sync only adds:
+//! an item removed from a list on one source is added back from the others.
+
+use media_sync_models::{CustomList, MediaIds, MediaType, WatchlistItem};
+
+/// Whether `a` and `b` are the same title, by IMDB ID or else by any shared ID
+fn same_item(a: &WatchlistItem, b: &WatchlistItem) -> bool {
+    same_title((&a.imdb_id, &a.ids, &a.media_type), (&b.imdb_id, &b.ids, &b.media_type))
+}
+
+/// Whether two `(imdb_id, ids, media_type)` are the same title, by IMDB ID or else by
+/// any shared ID
+pub(crate) fn same_title(
+    a: (&str, &Option<MediaIds>, &MediaType),
+    b: (&str, &Option<MediaIds>, &MediaType),
+) -> bool {
+    if !a.0.is_empty() && !b.0.is_empty() {
+        return a.0 == b.0;
+    }
+    if a.2 != b.2 {
+        return false;
+    }
+    match (a.1, b.1) {
+        (Some(x), Some(y)) => {
+            (x.trakt_id.is_some() && x.trakt_id == y.trakt_id)
+                || (x.tmdb_id.is_some() && x.tmdb_id == y.tmdb_id)
+                || (x.tvdb_id.is_some() && x.tvdb_id == y.tvdb_id)
+                || (x.anilist_id.is_some() && x.anilist_id == y.anilist_id)
+        }
+        _ => false,
+    }
+}
+
+/// Merge the lists of every source, given in `source_preference` order
+pub fn resolve_lists(sources: &[(String, Vec<CustomList>)]) -> Vec<CustomList> {
+    let mut resolved: Vec<CustomList> = Vec::new();
+    for (_, lists) in sources {
+        for list in lists {
+            let name = list.normalized_name();
+            let target = match resolved.iter().position(|r| r.normalized_name() == name) {
+                Some(index) => &mut resolved[index],
+                None => {
+                    resolved.push(CustomList { items: Vec::new(), ..list.clone() });
+                    resolved.last_mut().expect("just pushed")
+                }
+            };
+            if target.description.is_none() {
+                target.description = list.description.clone();
+            }
+            for item in &list.items {
+                if !target.items.iter().any(|existing| same_item(existing, item)) {
+                    target.items.push(item.clone());
+                }
+            }
+        }
+    }
+    resolved
+}
+
+/// The items of `resolved` that `existing` (one source's lists) lacks, by list.
+/// Lists the source already has keep its own name; lists it lacks entirely are included.
+pub fn missing_from(resolved: &[CustomList], existing: &[CustomList]) -> Vec<CustomList> {
+    resolved.iter()
+        .filter_map(|list| {
+            let own = existing.iter().find(|e| e.normalized_name() == list.normalized_name());
+            let items: Vec<WatchlistItem> = list.items.iter()
+                .filter(|item| !own.is_some_and(|own| own.items.iter().any(|o| same_item(o, item))))
+                .cloned()
+                .collect();
+            (!items.is_empty()).then(|| CustomList {
+                name: own.map_or_else(|| list.name.clone(), |own| own.name.clone()),
+                items,
+                ..list.clone()
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn item(imdb_id: &str) -> WatchlistItem {
+        WatchlistItem {
+            imdb_id: imdb_id.to_string(),
+            ids: None,
+            title: imdb_id.to_string(),
+            year: None,
+            media_type: MediaType::Movie,
+            date_added: Utc::now(),
+            source: "test".to_string(),
+            status: None,
+            tags: Vec::new(),
+        }
+    }
+
+    fn list(name: &str, source: &str, ids: &[&str]) -> CustomList {
+        CustomList {
+            name: name.to_string(),
+            description: None,
+            items: ids.iter().map(|id| item(id)).collect(),
+            source: source.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_lists_match_by_normalized_name() {
+        let sources = vec![
+            ("trakt".to_string(), vec![list("Halloween Picks", "trakt", &["tt1", "tt2"])]),
+            ("letterboxd".to_string(), vec![
+                list("halloween  picks", "letterboxd", &["tt2", "tt3"]),
+                list("Noir", "letterboxd", &["tt4"]),
+            ]),
+        ];
+        let resolved = resolve_lists(&sources);
+        assert_eq!(resolved.len(), 2);
+        assert_eq!(resolved[0].name, "Halloween Picks");
+        let ids: Vec<&str> = resolved[0].items.iter().map(|i| i.imdb_id.as_str()).collect();
+        assert_eq!(ids, ["tt1", "tt2", "tt3"]);
+
+        let missing = missing_from(&resolved, &sources[0].1);
+        assert_eq!(missing.len(), 2);
+        assert_eq!((missing[0].name.as_str(), missing[0].items.len()), ("Halloween Picks", 1));
+        assert_eq!(missing[0].items[0].imdb_id, "tt3");
+        assert_eq!(missing[1].name, "Noir");
+
+        let missing = missing_from(&resolved, &sources[1].1);
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].name, "halloween  picks");
+        assert_eq!(missing[0].items[0].imdb_id, "tt1");
+    }
+}
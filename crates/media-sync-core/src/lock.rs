@@ -0,0 +1,129 @@
+//! Single-instance sync lock.
+//!
+//! A sync holds a lock file in the data directory (`PathManager::sync_lock_file`)
+//! from start to finish, so a cron job and a manual run can't mutate the same
+//! cache or push the same writes twice. The file records the holder's PID and
+//! start time and is removed when the [`SyncLock`] is dropped. A process that is
+//! killed leaves the file behind; `totalrecall sync --force-unlock` removes it.
+
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use media_sync_config::{LockConflict, SyncLockOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+/// How often a waiting sync checks whether the lock was released
+const WAIT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Debug)]
+pub struct SyncLock {
+    path: PathBuf,
+}
+
+impl SyncLock {
+    /// Take the lock, or exit or wait per `options` when another sync holds it
+    pub async fn acquire(path: &Path, options: &SyncLockOptions) -> Result<Self> {
+        let started = Instant::now();
+        let mut logged_wait = false;
+        loop {
+            if let Some(lock) = Self::try_acquire(path)? {
+                return Ok(lock);
+            }
+
+            let holder = Self::holder(path).unwrap_or_else(|| "unknown process".to_string());
+            match options.on_conflict {
+                LockConflict::Exit => {
+                    return Err(anyhow!(
+                        "Another sync is running ({}). If it is not, remove the stale lock with `totalrecall sync --force-unlock`",
+                        holder
+                    ));
+                }
+                LockConflict::Wait => {
+                    if let Some(timeout) = options.wait_timeout_seconds {
+                        if started.elapsed() >= Duration::from_secs(timeout) {
+                            return Err(anyhow!(
+                                "Another sync is still running ({}) after waiting {}s (sync.lock.wait_timeout_seconds)",
+                                holder,
+                                timeout
+                            ));
+                        }
+                    }
+                    if !logged_wait {
+                        info!(operation = "sync_lock", "Another sync is running ({}), waiting for it to finish", holder);
+                        logged_wait = true;
+                    }
+                    tokio::time::sleep(WAIT_POLL_INTERVAL).await;
+                }
+            }
+        }
+    }
+
+    /// Take the lock if nobody holds it
+    pub fn try_acquire(path: &Path) -> Result<Option<Self>> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| anyhow!("Failed to create lock directory {}: {}", parent.display(), e))?;
+        }
+        let mut file = match std::fs::OpenOptions::new().write(true).create_new(true).open(path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => return Ok(None),
+            Err(e) => return Err(anyhow!("Failed to create sync lock {}: {}", path.display(), e)),
+        };
+        let lock = Self { path: path.to_path_buf() };
+        writeln!(file, "pid {} since {}", std::process::id(), Utc::now().to_rfc3339())
+            .map_err(|e| anyhow!("Failed to write sync lock {}: {}", path.display(), e))?;
+        Ok(Some(lock))
+    }
+
+    /// Remove a lock left behind by a sync that did not exit cleanly.
+    /// Returns whether there was one.
+    pub fn force_unlock(path: &Path) -> Result<bool> {
+        match std::fs::remove_file(path) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(anyhow!("Failed to remove sync lock {}: {}", path.display(), e)),
+        }
+    }
+
+    /// Who holds the lock, as recorded in the lock file
+    fn holder(path: &Path) -> Option<String> {
+        std::fs::read_to_string(path).ok()
+            .map(|content| content.trim().to_string())
+            .filter(|content| !content.is_empty())
+    }
+}
+
+impl Drop for SyncLock {
+    fn drop(&mut self) {
+        if let Err(e) = std::fs::remove_file(&self.path) {
+            warn!("Failed to release sync lock {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_second_sync_exits_or_times_out_while_locked() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data").join("sync.lock");
+
+        let lock = SyncLock::acquire(&path, &SyncLockOptions::default()).await.unwrap();
+        let err = SyncLock::acquire(&path, &SyncLockOptions::default()).await.unwrap_err();
+        assert!(err.to_string().contains("Another sync is running (pid "));
+
+        let wait = SyncLockOptions { on_conflict: LockConflict::Wait, wait_timeout_seconds: Some(0) };
+        assert!(SyncLock::acquire(&path, &wait).await.is_err());
+
+        drop(lock);
+        assert!(!path.exists());
+        let _lock = SyncLock::acquire(&path, &wait).await.unwrap();
+
+        assert!(SyncLock::force_unlock(&path).unwrap());
+        assert!(!SyncLock::force_unlock(&path).unwrap());
+    }
+}
@@ -0,0 +1,135 @@
+//! Per-run counts by source and data type.
+//!
+//! A sync records what it fetched from each source and what it added to, removed from,
+//! skipped for or failed to write to each one. The counts end up in
+//! `SyncResult::per_source`, the JSON output of `sync`, and the daemon's `metrics.json`,
+//! which monitoring (e.g. node_exporter's textfile collector or a cron check) can read.
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use media_sync_sources::DataType;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::sync::SyncResult;
+
+/// Counts for one data type of one source
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SourceMetrics {
+    pub source: String,
+    /// `watchlist`, `ratings`, `reviews` or `watch_history`
+    pub data_type: String,
+    /// Items collected from the source
+    pub fetched: usize,
+    /// Items written to the source
+    pub added: usize,
+    /// Items removed from the source (watchlist removals)
+    pub removed: usize,
+    /// Items held back: deferred watchlist additions, or partial plays the source can't take
+    pub skipped: usize,
+    /// Items whose write failed
+    pub errors: usize,
+}
+
+/// Collects `SourceMetrics` during a run
+#[derive(Debug, Default)]
+pub struct MetricsAccumulator {
+    entries: HashMap<(String, DataType), SourceMetrics>,
+}
+
+impl MetricsAccumulator {
+    /// The counts of `source` for `data_type`, zero until something is recorded
+    pub fn entry(&mut self, source: &str, data_type: DataType) -> &mut SourceMetrics {
+        self.entries.entry((source.to_string(), data_type))
+            .or_insert_with(|| SourceMetrics {
+                source: source.to_string(),
+                data_type: data_type.as_str().to_string(),
+                ..SourceMetrics::default()
+            })
+    }
+
+    /// Items written to any source, the run's `items_synced`
+    pub fn total_added(&self) -> usize {
+        self.entries.values().map(|metrics| metrics.added).sum()
+    }
+
+    /// Every recorded entry, sources in `order` (then the rest by name), data types in
+    /// `DataType::ALL` order
+    pub fn to_vec(&self, order: &[String]) -> Vec<SourceMetrics> {
+        let mut sources: Vec<&String> = self.entries.keys().map(|(source, _)| source).collect();
+        sources.sort_by_key(|source| (order.iter().position(|s| s == *source).unwrap_or(usize::MAX), source.as_str()));
+        sources.dedup();
+        sources.into_iter()
+            .flat_map(|source| {
+                DataType::ALL.iter()
+                    .filter_map(|data_type| self.entries.get(&(source.clone(), *data_type)).cloned())
+            })
+            .collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+/// Summary of the latest run, as written to `metrics.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunMetrics {
+    pub finished_at: DateTime<Utc>,
+    pub duration_seconds: f64,
+    pub success: bool,
+    pub items_synced: usize,
+    pub items_deferred: usize,
+    pub errors: usize,
+    pub per_source: Vec<SourceMetrics>,
+}
+
+impl RunMetrics {
+    pub fn from_result(result: &SyncResult) -> Self {
+        Self {
+            finished_at: Utc::now(),
+            duration_seconds: result.duration.as_secs_f64(),
+            success: result.errors.is_empty(),
+            items_synced: result.items_synced,
+            items_deferred: result.items_deferred,
+            errors: result.errors.len(),
+            per_source: result.per_source.clone(),
+        }
+    }
+
+    /// Replace `path` with this summary. Written aside and renamed, so a reader never
+    /// sees a half-written file.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| anyhow!("Failed to serialize run metrics: {}", e))?;
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, json)
+            .and_then(|_| std::fs::rename(&tmp, path))
+            .map_err(|e| anyhow!("Failed to write run metrics {}: {}", path.display(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accumulator_orders_by_source_preference_then_data_type() {
+        let mut metrics = MetricsAccumulator::default();
+        metrics.entry("plex", DataType::WatchHistory).added += 3;
+        metrics.entry("trakt", DataType::WatchHistory).fetched += 10;
+        metrics.entry("trakt", DataType::Watchlist).added += 2;
+        metrics.entry("trakt", DataType::Watchlist).errors += 1;
+
+        let order = vec!["trakt".to_string(), "plex".to_string()];
+        let all = metrics.to_vec(&order);
+        let keys: Vec<(&str, &str)> = all.iter().map(|m| (m.source.as_str(), m.data_type.as_str())).collect();
+        assert_eq!(keys, vec![("trakt", "watchlist"), ("trakt", "watch_history"), ("plex", "watch_history")]);
+        assert_eq!((all[0].added, all[0].errors), (2, 1));
+        assert_eq!(metrics.total_added(), 5);
+    }
+}
@@ -0,0 +1,246 @@
+//! Notifications of finished daemon runs.
+//!
+//! After each startup or scheduled sync the daemon sends a [`RunNotification`] to the
+//! `[notifications]` webhook (POSTed as JSON) and command (on stdin). Both run in the
+//! background and are abandoned after `timeout_seconds`, so a slow or failing endpoint
+//! never holds up syncing; failures are only logged.
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use media_sync_config::NotificationsConfig;
+use serde::{Deserialize, Serialize};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tracing::{debug, warn};
+
+use crate::sync::SyncResult;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunStatus {
+    Success,
+    /// The run had errors, or could not run at all
+    Failure,
+}
+
+/// Summary of one run, as sent to the webhook and command
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunNotification {
+    pub status: RunStatus,
+    pub finished_at: DateTime<Utc>,
+    pub duration_seconds: f64,
+    pub items_synced: usize,
+    pub items_deferred: usize,
+    pub errors: Vec<String>,
+}
+
+impl RunNotification {
+    pub fn from_result(result: &SyncResult) -> Self {
+        Self {
+            status: if result.errors.is_empty() { RunStatus::Success } else { RunStatus::Failure },
+            finished_at: Utc::now(),
+            duration_seconds: result.duration.as_secs_f64(),
+            items_synced: result.items_synced,
+            items_deferred: result.items_deferred,
+            errors: result.errors.clone(),
+        }
+    }
+
+    /// A run that failed outright after `duration`, without a `SyncResult`
+    pub fn from_error(error: &str, duration: Duration) -> Self {
+        Self {
+            status: RunStatus::Failure,
+            finished_at: Utc::now(),
+            duration_seconds: duration.as_secs_f64(),
+            items_synced: 0,
+            items_deferred: 0,
+            errors: vec![error.to_string()],
+        }
+    }
+}
+
+/// Sends `RunNotification`s as configured in `[notifications]`
+#[derive(Debug, Clone)]
+pub struct Notifier {
+    client: reqwest::Client,
+    webhook_url: Option<String>,
+    command: Option<String>,
+    timeout: Duration,
+}
+
+impl From<&NotificationsConfig> for Notifier {
+    fn from(config: &NotificationsConfig) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            webhook_url: config.webhook_url.clone().filter(|url| !url.trim().is_empty()),
+            command: config.command.clone().filter(|command| !command.trim().is_empty()),
+            timeout: Duration::from_secs(config.timeout_seconds),
+        }
+    }
+}
+
+impl Notifier {
+    pub fn is_enabled(&self) -> bool {
+        self.webhook_url.is_some() || self.command.is_some()
+    }
+
+    /// Send `notification` in the background and return at once
+    pub fn notify(&self, notification: RunNotification) {
+        if !self.is_enabled() {
+            return;
+        }
+        let notifier = self.clone();
+        tokio::spawn(async move {
+            if notifier.webhook_url.is_some() {
+                if let Err(e) = notifier.send_webhook(&notification).await {
+                    warn!("Sync notification webhook failed: {}", e);
+                }
+            }
+            if notifier.command.is_some() {
+                if let Err(e) = notifier.run_command(&notification).await {
+                    warn!("Sync notification command failed: {}", e);
+                }
+            }
+        });
+    }
+
+    /// POST `notification` to the webhook, if one is configured
+    pub async fn send_webhook(&self, notification: &RunNotification) -> Result<()> {
+        let Some(url) = &self.webhook_url else {
+            return Ok(());
+        };
+        let response = self.client.post(url)
+            .json(notification)
+            .timeout(self.timeout)
+            .send()
+            .await
+            .map_err(|e| anyhow!("POST to {} failed: {}", url, e))?;
+        if !response.status().is_success() {
+            return Err(anyhow!("{} answered {}", url, response.status()));
+        }
+        debug!("Sent sync notification to {}", url);
+        Ok(())
+    }
+
+    /// Run the command with `notification` as JSON on stdin, if one is configured.
+    /// It is killed if it outlasts the timeout.
+    pub async fn run_command(&self, notification: &RunNotification) -> Result<()> {
+        let Some(command) = &self.command else {
+            return Ok(());
+        };
+        let payload = serde_json::to_vec(notification)?;
+        let mut child = shell(command)
+            .env("TOTALRECALL_STATUS", if notification.status == RunStatus::Success { "success" } else { "failure" })
+            .env("TOTALRECALL_ITEMS_SYNCED", notification.items_synced.to_string())
+            .env("TOTALRECALL_ERRORS", notification.errors.len().to_string())
+            .stdin(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| anyhow!("Failed to start '{}': {}", command, e))?;
+
+        let run = async {
+            if let Some(mut stdin) = child.stdin.take() {
+                // A command that doesn't read its input closes the pipe early; that's fine
+                let _ = stdin.write_all(&payload).await;
+            }
+            child.wait().await
+        };
+        let status = tokio::time::timeout(self.timeout, run).await
+            .map_err(|_| anyhow!("'{}' did not finish within {}s", command, self.timeout.as_secs()))??;
+        if !status.success() {
+            return Err(anyhow!("'{}' exited with {}", command, status));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+fn shell(command: &str) -> tokio::process::Command {
+    let mut shell = tokio::process::Command::new("sh");
+    shell.arg("-c").arg(command);
+    shell
+}
+
+#[cfg(not(unix))]
+fn shell(command: &str) -> tokio::process::Command {
+    let mut shell = tokio::process::Command::new("cmd");
+    shell.arg("/C").arg(command);
+    shell
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use media_sync_sources::test_support::{serve, MockResponse};
+    use std::net::TcpListener;
+
+    /// Accept one request, answer `status_line` and return the request body
+    fn mock_server(status_line: &str) -> (String, std::thread::JoinHandle<String>) {
+        let (base, server) = serve(vec![MockResponse::new(status_line)]);
+        (format!("{}/hook", base), std::thread::spawn(move || server.join().unwrap().remove(0).body))
+    }
+
+    fn result(items_synced: usize, errors: Vec<String>) -> SyncResult {
+        SyncResult {
+            items_synced,
+            items_deferred: 2,
+            resolution_skipped: false,
+            duration: Duration::from_secs(42),
+            errors,
+            sources: Vec::new(),
+            per_source: Vec::new(),
+        }
+    }
+
+    fn notifier(webhook_url: Option<String>, command: Option<&str>, timeout_seconds: u64) -> Notifier {
+        Notifier::from(&NotificationsConfig {
+            webhook_url,
+            command: command.map(str::to_string),
+            timeout_seconds,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_webhook_receives_run_summary() {
+        let (url, server) = mock_server("204 No Content");
+        notifier(Some(url), None, 5).send_webhook(&RunNotification::from_result(&result(17, Vec::new()))).await.unwrap();
+        let payload: serde_json::Value = serde_json::from_str(&server.join().unwrap()).unwrap();
+        assert_eq!(payload["status"], "success");
+        assert_eq!(payload["items_synced"], 17);
+        assert_eq!(payload["items_deferred"], 2);
+        assert_eq!(payload["duration_seconds"], 42.0);
+
+        let (url, server) = mock_server("500 Internal Server Error");
+        let failed = RunNotification::from_result(&result(3, vec!["Failed to fetch trakt ratings".to_string()]));
+        assert!(notifier(Some(url), None, 5).send_webhook(&failed).await.is_err());
+        let payload: serde_json::Value = serde_json::from_str(&server.join().unwrap()).unwrap();
+        assert_eq!(payload["status"], "failure");
+        assert_eq!(payload["errors"][0], "Failed to fetch trakt ratings");
+    }
+
+    #[tokio::test]
+    async fn test_unresponsive_webhook_times_out() {
+        // Accepts the connection but never answers
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let start = std::time::Instant::now();
+        let sent = notifier(Some(url), None, 1).send_webhook(&RunNotification::from_error("boom", Duration::ZERO)).await;
+        assert!(sent.is_err());
+        assert!(start.elapsed() < Duration::from_secs(5));
+        drop(listener);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_command_gets_summary_on_stdin() {
+        let temp = tempfile::tempdir().unwrap();
+        let out = temp.path().join("notification.json");
+        let command = format!("cat > '{}' && test \"$TOTALRECALL_STATUS\" = success", out.display());
+        notifier(None, Some(&command), 5).run_command(&RunNotification::from_result(&result(5, Vec::new()))).await.unwrap();
+        let payload: RunNotification = serde_json::from_str(&std::fs::read_to_string(&out).unwrap()).unwrap();
+        assert_eq!((payload.status, payload.items_synced), (RunStatus::Success, 5));
+
+        assert!(notifier(None, Some("sleep 5"), 1).run_command(&RunNotification::from_error("boom", Duration::ZERO)).await.is_err());
+    }
+}
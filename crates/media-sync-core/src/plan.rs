@@ -0,0 +1,241 @@
+//! Reviewable sync plans.
+//!
+//! A [`SyncPlan`] holds every write a sync would make, per target source, after
+//! the distribution strategies have filtered and transformed the resolved data.
+//! `totalrecall plan` writes one to disk; `totalrecall apply <plan-file>` sends
+//! exactly its contents without collecting or resolving again, so a plan can be
+//! reviewed and hand-edited in between.
+//!
+//! The JSON layout is stable: sources are ordered by name and every list is
+//! ordered by IMDB ID. Ratings are stored on the normalized 1-10 scale and are
+//! converted to the target's native scale when applied.
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use media_sync_models::{Rating, Review, WatchHistory, WatchlistItem};
+use media_sync_sources::DataType;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Bumped when the plan layout changes incompatibly
+pub const SYNC_PLAN_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncPlan {
+    pub version: u32,
+    pub created_at: DateTime<Utc>,
+    pub sources: Vec<SourcePlan>,
+}
+
+/// Planned writes for one target source
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SourcePlan {
+    pub source: String,
+    #[serde(default)]
+    pub watchlist_add: Vec<WatchlistItem>,
+    #[serde(default)]
+    pub watchlist_remove: Vec<WatchlistItem>,
+    /// Watchlist items the target tracks as watch history (e.g. "watching" on Simkl)
+    #[serde(default)]
+    pub watchlist_to_history: Vec<WatchHistory>,
+    #[serde(default)]
+    pub ratings: Vec<Rating>,
+    #[serde(default)]
+    pub reviews: Vec<Review>,
+    #[serde(default)]
+    pub watch_history_add: Vec<WatchHistory>,
+    /// Partial plays sent as playback progress (`watch_history.sync_progress`)
+    #[serde(default)]
+    pub playback_progress: Vec<WatchHistory>,
+    /// Watchlist additions left out by `watchlist.max_items_to_add_per_run`
+    #[serde(default)]
+    pub watchlist_deferred: usize,
+    /// Writes of any data type left out by the run's `--max-items` budget
+    #[serde(default)]
+    pub deferred: usize,
+}
+
+impl SourcePlan {
+    pub fn new(source: &str) -> Self {
+        Self {
+            source: source.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Number of writes the plan makes to this source
+    pub fn total_items(&self) -> usize {
+        self.watchlist_add.len()
+            + self.watchlist_remove.len()
+            + self.watchlist_to_history.len()
+            + self.ratings.len()
+            + self.reviews.len()
+            + self.watch_history_add.len()
+            + self.playback_progress.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.total_items() == 0
+    }
+
+    /// Keep the first `budget` writes in the order they are sent and move the rest to
+    /// the returned plan, adding their count to `deferred`
+    pub fn truncate_to(&mut self, budget: usize) -> SourcePlan {
+        fn split<T>(items: &mut Vec<T>, budget: &mut usize) -> Vec<T> {
+            let kept = items.len().min(*budget);
+            *budget -= kept;
+            items.split_off(kept)
+        }
+
+        let mut budget = budget;
+        let dropped = SourcePlan {
+            source: self.source.clone(),
+            watchlist_add: split(&mut self.watchlist_add, &mut budget),
+            watchlist_to_history: split(&mut self.watchlist_to_history, &mut budget),
+            watchlist_remove: split(&mut self.watchlist_remove, &mut budget),
+            ratings: split(&mut self.ratings, &mut budget),
+            reviews: split(&mut self.reviews, &mut budget),
+            watch_history_add: split(&mut self.watch_history_add, &mut budget),
+            playback_progress: split(&mut self.playback_progress, &mut budget),
+            ..Self::default()
+        };
+        self.deferred += dropped.total_items();
+        dropped
+    }
+
+    /// Number of writes of each data type in the plan, leaving out those with none
+    pub fn counts_by_data_type(&self) -> Vec<(DataType, usize)> {
+        [
+            (DataType::Watchlist, self.watchlist_add.len() + self.watchlist_remove.len()),
+            (DataType::Ratings, self.ratings.len()),
+            (DataType::Reviews, self.reviews.len()),
+            (DataType::WatchHistory, self.watchlist_to_history.len() + self.watch_history_add.len() + self.playback_progress.len()),
+        ]
+        .into_iter()
+        .filter(|(_, count)| *count > 0)
+        .collect()
+    }
+
+    fn sort(&mut self) {
+        self.watchlist_add.sort_by(|a, b| a.imdb_id.cmp(&b.imdb_id));
+        self.watchlist_remove.sort_by(|a, b| a.imdb_id.cmp(&b.imdb_id));
+        self.watchlist_to_history.sort_by(|a, b| a.imdb_id.cmp(&b.imdb_id).then(a.watched_at.cmp(&b.watched_at)));
+        self.ratings.sort_by(|a, b| a.imdb_id.cmp(&b.imdb_id));
+        self.reviews.sort_by(|a, b| a.imdb_id.cmp(&b.imdb_id));
+        self.watch_history_add.sort_by(|a, b| a.imdb_id.cmp(&b.imdb_id).then(a.watched_at.cmp(&b.watched_at)));
+        self.playback_progress.sort_by(|a, b| a.imdb_id.cmp(&b.imdb_id).then(a.watched_at.cmp(&b.watched_at)));
+    }
+}
+
+impl SyncPlan {
+    pub fn new(mut sources: Vec<SourcePlan>) -> Self {
+        sources.sort_by(|a, b| a.source.cmp(&b.source));
+        for source in &mut sources {
+            source.sort();
+        }
+        Self {
+            version: SYNC_PLAN_VERSION,
+            created_at: Utc::now(),
+            sources,
+        }
+    }
+
+    pub fn total_items(&self) -> usize {
+        self.sources.iter().map(|s| s.total_items()).sum()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| anyhow!("Failed to serialize sync plan: {}", e))?;
+        std::fs::write(path, json)
+            .map_err(|e| anyhow!("Failed to write sync plan {}: {}", path.display(), e))
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("Failed to read sync plan {}: {}", path.display(), e))?;
+        let plan: SyncPlan = serde_json::from_str(&content)
+            .map_err(|e| anyhow!("Failed to parse sync plan {}: {}", path.display(), e))?;
+        if plan.version != SYNC_PLAN_VERSION {
+            return Err(anyhow!(
+                "Unsupported sync plan version {} in {} (expected {}); create a new plan",
+                plan.version,
+                path.display(),
+                SYNC_PLAN_VERSION
+            ));
+        }
+        Ok(plan)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use media_sync_models::{MediaType, RatingSource};
+
+    fn rating(imdb_id: &str, rating: u8) -> Rating {
+        Rating {
+            imdb_id: imdb_id.to_string(),
+            ids: None,
+            rating,
+            rating_f64: None,
+            date_added: Utc::now(),
+            media_type: MediaType::Movie,
+            source: RatingSource::Trakt,
+            inferred: false,
+        }
+    }
+
+    #[test]
+    fn test_plan_is_ordered_and_round_trips() {
+        let mut imdb = SourcePlan::new("imdb");
+        imdb.ratings = vec![rating("tt0133093", 9), rating("tt0113277", 8)];
+        let trakt = SourcePlan::new("trakt");
+
+        let plan = SyncPlan::new(vec![trakt, imdb]);
+        assert_eq!(plan.sources[0].source, "imdb");
+        assert_eq!(plan.sources[0].ratings[0].imdb_id, "tt0113277");
+        assert_eq!(plan.total_items(), 2);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("plan.json");
+        plan.save(&path).unwrap();
+        assert_eq!(SyncPlan::load(&path).unwrap(), plan);
+    }
+
+    #[test]
+    fn test_truncate_to_keeps_first_writes_and_counts_the_rest() {
+        let mut plan = SourcePlan::new("trakt");
+        plan.ratings = vec![rating("tt0133093", 9), rating("tt0113277", 8), rating("tt0110912", 7)];
+
+        assert!(plan.truncate_to(5).is_empty());
+        assert_eq!(plan.deferred, 0);
+
+        let dropped = plan.truncate_to(2);
+        assert_eq!(dropped.counts_by_data_type(), vec![(DataType::Ratings, 1)]);
+        assert_eq!(dropped.ratings[0].imdb_id, "tt0110912");
+        assert_eq!(plan.ratings.len(), 2);
+        assert_eq!(plan.ratings[1].imdb_id, "tt0113277");
+        assert_eq!(plan.deferred, 1);
+
+        assert_eq!(plan.truncate_to(0).counts_by_data_type(), vec![(DataType::Ratings, 2)]);
+        assert!(plan.is_empty());
+        assert_eq!(plan.deferred, 3);
+    }
+
+    #[test]
+    fn test_hand_edited_plan_may_omit_lists() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("plan.json");
+        std::fs::write(&path, r#"{
+            "version": 1,
+            "created_at": "2024-01-01T00:00:00Z",
+            "sources": [{ "source": "trakt", "ratings": [] }]
+        }"#).unwrap();
+        let plan = SyncPlan::load(&path).unwrap();
+        assert!(plan.sources[0].is_empty());
+
+        std::fs::write(&path, r#"{ "version": 99, "created_at": "2024-01-01T00:00:00Z", "sources": [] }"#).unwrap();
+        assert!(SyncPlan::load(&path).is_err());
+    }
+}
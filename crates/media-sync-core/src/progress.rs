@@ -0,0 +1,70 @@
+//! Progress of a sync for front ends.
+//!
+//! The orchestrator reports each phase of a run (collecting, resolving IDs, distributing)
+//! to a [`ProgressTracker`] as a count of sources done. Each phase also hands every
+//! source an observer, so the item progress they log through
+//! `media_sync_sources::ProgressTracker` (Plex writes, IMDB exports, ...) is forwarded
+//! to [`ProgressTracker::operation_progress`].
+
+use media_sync_sources::{MediaSource, ProgressObserver, SourceError};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Receives the progress of a sync
+pub trait ProgressTracker: Send + Sync {
+    /// A phase of `total` steps starts
+    fn start_phase(&self, name: &str, total: usize);
+
+    /// `n` more steps of the current phase are done
+    fn advance(&self, n: usize);
+
+    /// The current phase is done
+    fn finish_phase(&self);
+
+    /// `done` of `total` items of a source's `operation` are done
+    fn operation_progress(&self, _operation: &str, _done: usize, _total: usize) {}
+}
+
+/// Reports nothing; the orchestrator's default
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoProgress;
+
+impl ProgressTracker for NoProgress {
+    fn start_phase(&self, _name: &str, _total: usize) {}
+    fn advance(&self, _n: usize) {}
+    fn finish_phase(&self) {}
+}
+
+/// A source as the orchestrator holds it
+type SharedSource = Arc<RwLock<Box<dyn MediaSource<Error = SourceError>>>>;
+
+/// A phase of `tracker`: finishes the phase when dropped, so early returns finish it too
+pub(crate) struct Phase {
+    tracker: Arc<dyn ProgressTracker>,
+}
+
+impl Phase {
+    /// Start a phase of `total` steps, with `sources` reporting their item progress to `tracker`
+    pub(crate) async fn start(
+        tracker: &Arc<dyn ProgressTracker>,
+        sources: &[SharedSource],
+        name: &str,
+        total: usize,
+    ) -> Self {
+        tracker.start_phase(name, total);
+        let forward = tracker.clone();
+        let observer: ProgressObserver = Arc::new(move |operation: &str, done, total| {
+            forward.operation_progress(operation, done, total)
+        });
+        for source in sources {
+            source.write().await.set_progress_observer(Some(observer.clone()));
+        }
+        Self { tracker: tracker.clone() }
+    }
+}
+
+impl Drop for Phase {
+    fn drop(&mut self) {
+        self.tracker.finish_phase();
+    }
+}
@@ -0,0 +1,69 @@
+//! Watch history retention for `totalrecall prune-history`.
+//!
+//! Plays watched before a cutoff are dropped from a source's collect cache, and with
+//! `--remote` deleted from the source itself where it supports that (`HistoryPruning`).
+//! Sources that fetch only changes merge them into the cache, so a pruned cache stays
+//! small; sources that fetch their full history replace the cache on the next collect.
+//! Incremental distribution only sends plays newer than the last sync, so pruned plays
+//! are not pushed back by other sources unless a full sync is forced.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use media_sync_models::WatchHistory;
+
+use crate::cache::CacheManager;
+
+/// Drop plays watched before `cutoff`; returns how many were dropped
+pub fn prune_history_before(history: &mut Vec<WatchHistory>, cutoff: DateTime<Utc>) -> usize {
+    let before = history.len();
+    history.retain(|item| item.watched_at >= cutoff);
+    before - history.len()
+}
+
+/// Prune `source`'s cached watch history, or only count what would go with `dry_run`.
+/// A source without cached history has nothing to prune.
+pub fn prune_cached_history(cache_manager: &CacheManager, source: &str, cutoff: DateTime<Utc>, dry_run: bool) -> Result<usize> {
+    let Some(mut history) = cache_manager.load_watch_history(source)? else {
+        return Ok(0);
+    };
+    let pruned = prune_history_before(&mut history, cutoff);
+    if pruned > 0 && !dry_run {
+        cache_manager.save_watch_history(source, &history)?;
+    }
+    Ok(pruned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use media_sync_models::MediaType;
+
+    fn play(imdb_id: &str, watched_at: DateTime<Utc>) -> WatchHistory {
+        WatchHistory {
+            imdb_id: imdb_id.to_string(),
+            ids: None,
+            title: None,
+            year: None,
+            watched_at,
+            media_type: MediaType::Movie,
+            source: "trakt".to_string(),
+            watched_percent: None,
+            show_ids: None,
+        }
+    }
+
+    #[test]
+    fn test_prune_keeps_plays_from_the_cutoff_on() {
+        let cutoff = Utc.with_ymd_and_hms(2023, 1, 1, 0, 0, 0).unwrap();
+        let mut history = vec![
+            play("tt0111161", Utc.with_ymd_and_hms(2019, 6, 1, 20, 0, 0).unwrap()),
+            play("tt0068646", cutoff),
+            play("tt0111161", Utc.with_ymd_and_hms(2024, 2, 3, 21, 0, 0).unwrap()),
+        ];
+
+        assert_eq!(prune_history_before(&mut history, cutoff), 1);
+        assert_eq!(history.len(), 2);
+        assert!(history.iter().all(|item| item.watched_at >= cutoff));
+    }
+}
@@ -0,0 +1,133 @@
+//! Divergence report for `totalrecall reconcile`.
+//!
+//! After collecting and resolving like a sync, each source's data is compared with the
+//! resolved union: per data type, how many of the union's items the source is missing.
+//! Items are matched by IMDB ID, as in distribution, so items without one are not
+//! counted. A source is only compared for data types it supports and that are enabled.
+
+use media_sync_sources::DataType;
+use serde::Serialize;
+
+use crate::diff::{filter_items_by_imdb_id, GetImdbId, GetMediaIds};
+use crate::resolution::{ResolvedData, SourceData};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReconcileReport {
+    pub data_types: Vec<DataTypeReconciliation>,
+}
+
+/// One data type: the size of the resolved union and each source's gap to it
+#[derive(Debug, Clone, Serialize)]
+pub struct DataTypeReconciliation {
+    pub data_type: String,
+    pub total: usize,
+    pub sources: Vec<SourceReconciliation>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SourceReconciliation {
+    pub source: String,
+    pub missing: usize,
+    /// Share of the union the source is missing, 0-100
+    pub divergence_percent: f64,
+}
+
+impl ReconcileReport {
+    /// Compare each `(source, data types it is compared for, collected data)` with `resolved`
+    pub fn new(sources: &[(String, Vec<DataType>, &SourceData)], resolved: &ResolvedData, data_types: &[DataType]) -> Self {
+        let data_types = data_types.iter()
+            .map(|&data_type| {
+                let (total, sources) = match data_type {
+                    DataType::Watchlist => compare(sources, data_type, &resolved.watchlist, |data| &data.watchlist),
+                    DataType::Ratings => compare(sources, data_type, &resolved.ratings, |data| &data.ratings),
+                    DataType::Reviews => compare(sources, data_type, &resolved.reviews, |data| &data.reviews),
+                    DataType::WatchHistory => compare(sources, data_type, &resolved.watch_history, |data| &data.watch_history),
+                };
+                DataTypeReconciliation {
+                    data_type: data_type.to_string(),
+                    total,
+                    sources,
+                }
+            })
+            .collect();
+        Self { data_types }
+    }
+
+    /// Whether every compared source has every item
+    pub fn is_converged(&self) -> bool {
+        self.data_types.iter().flat_map(|d| &d.sources).all(|s| s.missing == 0)
+    }
+}
+
+fn compare<T>(
+    sources: &[(String, Vec<DataType>, &SourceData)],
+    data_type: DataType,
+    union: &[T],
+    items: impl Fn(&SourceData) -> &Vec<T>,
+) -> (usize, Vec<SourceReconciliation>)
+where
+    T: Clone + GetImdbId + GetMediaIds,
+{
+    let total = filter_items_by_imdb_id(union, &[]).len();
+    let sources = sources.iter()
+        .filter(|(_, compared, _)| compared.contains(&data_type))
+        .map(|(source, _, data)| {
+            let missing = filter_items_by_imdb_id(union, items(data)).len();
+            SourceReconciliation {
+                source: source.clone(),
+                missing,
+                divergence_percent: if total == 0 { 0.0 } else { missing as f64 * 100.0 / total as f64 },
+            }
+        })
+        .collect();
+    (total, sources)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use media_sync_models::{MediaType, Rating, RatingSource};
+
+    fn rating(imdb_id: &str) -> Rating {
+        Rating {
+            imdb_id: imdb_id.to_string(),
+            ids: None,
+            rating: 8,
+            rating_f64: None,
+            date_added: Utc::now(),
+            media_type: MediaType::Movie,
+            source: RatingSource::Trakt,
+            inferred: false,
+        }
+    }
+
+    #[test]
+    fn test_report_counts_missing_items_per_source() {
+        let source = |ratings| SourceData { watchlist: vec![], ratings, reviews: vec![], watch_history: vec![] };
+        let trakt = source(vec![rating("tt0133093"), rating("tt0068646")]);
+        let plex = source(vec![rating("tt0133093")]);
+        let resolved = ResolvedData {
+            watchlist: vec![],
+            ratings: vec![rating("tt0133093"), rating("tt0068646"), rating("tt0111161"), rating("")],
+            reviews: vec![],
+            watch_history: vec![],
+            playback_progress: vec![],
+        };
+        let sources = vec![
+            ("trakt".to_string(), DataType::ALL.to_vec(), &trakt),
+            ("plex".to_string(), vec![DataType::Ratings], &plex),
+        ];
+
+        let report = ReconcileReport::new(&sources, &resolved, &[DataType::Ratings, DataType::Reviews]);
+        let ratings = &report.data_types[0];
+        // The rating without an IMDB ID is not counted
+        assert_eq!(ratings.total, 3);
+        assert_eq!(ratings.sources[0].missing, 1);
+        assert_eq!(ratings.sources[1].missing, 2);
+        assert!((ratings.sources[1].divergence_percent - 66.67).abs() < 0.01);
+        // Plex isn't compared for reviews
+        assert_eq!(report.data_types[1].sources.len(), 1);
+        assert!(!report.is_converged());
+    }
+}
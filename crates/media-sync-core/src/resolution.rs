@@ -1,10 +1,15 @@
-use media_sync_models::{Rating, Review, WatchHistory, WatchlistItem};
-use media_sync_config::{ResolutionConfig, ResolutionStrategy};
+use media_sync_models::{MediaIds, MediaType, Rating, Review, WatchHistory, WatchlistItem};
+use media_sync_config::{MediaTypeFilter, ResolutionConfig, ResolutionStrategy};
+use media_sync_sources::DataType;
 use chrono::DateTime;
-use chrono::Utc;
+use chrono::{Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 use tracing::debug;
-use std::collections::HashSet;
+
+use crate::conflicts::ResolutionReport;
 
 pub struct SourceData {
     pub watchlist: Vec<WatchlistItem>,
@@ -13,34 +18,230 @@ pub struct SourceData {
     pub watch_history: Vec<WatchHistory>,
 }
 
-#[derive(Clone)]
+impl SourceData {
+    /// Drop the watchlist items, ratings, reviews and plays whose media type none of
+    /// `media_types` matches. Returns how many were dropped.
+    pub fn retain_media_types(&mut self, media_types: &[MediaTypeFilter]) -> usize {
+        let allowed = |media_type: &MediaType| media_types.iter().any(|filter| filter.matches(media_type));
+        let before = self.watchlist.len() + self.ratings.len() + self.reviews.len() + self.watch_history.len();
+        self.watchlist.retain(|item| allowed(&item.media_type));
+        self.ratings.retain(|rating| allowed(&rating.media_type));
+        self.reviews.retain(|review| allowed(&review.media_type));
+        self.watch_history.retain(|item| allowed(&item.media_type));
+        before - (self.watchlist.len() + self.ratings.len() + self.reviews.len() + self.watch_history.len())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ResolvedData {
     pub watchlist: Vec<WatchlistItem>,
     pub ratings: Vec<Rating>,
     pub reviews: Vec<Review>,
     pub watch_history: Vec<WatchHistory>,
+    /// Partial plays, kept apart from `watch_history` so nothing treats them as watched
+    #[serde(default)]
+    pub playback_progress: Vec<WatchHistory>,
 }
 
 /// Resolve conflicts across all sources using configurable strategy
+///
+/// The result does not depend on the order of `source_data`. For each data type, sources
+/// are first put in a canonical order (position in that type's preference, see
+/// `ResolutionConfig::preference_for`, then unlisted sources by name), and when candidates
+/// still tie after the strategy and tolerance rules, the winner is picked by that same
+/// order and then by lowest `imdb_id`.
 pub fn resolve_all_conflicts(
     source_data: &[(&str, &SourceData)],
     resolution_config: &ResolutionConfig,
 ) -> ResolvedData {
-    ResolvedData {
+    preview(source_data, resolution_config).0
+}
+
+/// `resolve_all_conflicts`, along with a report of the rating and watchlist status
+/// conflicts it decided and the titles a source lists more than once.
+///
+/// This is all of resolution's decision logic and nothing else: no cache reads or
+/// writes, no ID lookups, no clock. The same inputs always give the same result, so
+/// front ends and tests can show what a sync would resolve to without running one.
+/// Ratings are expected on the 1-10 scale, as the orchestrator normalizes them first.
+pub fn preview(
+    source_data: &[(&str, &SourceData)],
+    resolution_config: &ResolutionConfig,
+) -> (ResolvedData, ResolutionReport) {
+    let mut report = ResolutionReport::default();
+    let ordered_for = |data_type: DataType| {
+        let preference = resolution_config.preference_for(data_type.as_str());
+        let mut ordered = source_data.to_vec();
+        ordered.sort_by(|(a, _), (b, _)| source_rank(a, &preference).cmp(&source_rank(b, &preference)));
+        (ordered, preference)
+    };
+
+    let (history_sources, _) = ordered_for(DataType::WatchHistory);
+    let (playback_progress, watch_history) = resolve_watch_history(&history_sources)
+        .into_iter()
+        .partition(|entry| entry.is_partial());
+
+    let (watchlist_sources, watchlist_preference) = ordered_for(DataType::Watchlist);
+    let (ratings_sources, ratings_preference) = ordered_for(DataType::Ratings);
+    let (review_sources, _) = ordered_for(DataType::Reviews);
+    let resolved = ResolvedData {
         watchlist: resolve_watchlist(
-            source_data,
+            &watchlist_sources,
+            &watchlist_preference,
             resolution_config,
+            &mut report,
         ),
         ratings: resolve_ratings(
-            source_data,
+            &ratings_sources,
+            &ratings_preference,
             resolution_config,
+            &mut report,
         ),
         reviews: resolve_reviews(
-            source_data,
-        ),
-        watch_history: resolve_watch_history(
-            source_data,
+            &review_sources,
         ),
+        watch_history,
+        playback_progress,
+    };
+    report.sort();
+    (resolved, report)
+}
+
+/// Fingerprint of everything resolution depends on: the collected data of every source
+/// (in canonical name order), the resolution config and the crate version. Used to reuse the
+/// previous run's `ResolvedData` when nothing changed. Only compared against hashes written by
+/// the same binary, so the non-cryptographic std hasher is sufficient.
+pub fn resolution_input_hash(
+    source_data: &[(&str, &SourceData)],
+    resolution_config: &ResolutionConfig,
+) -> String {
+    let mut ordered = source_data.to_vec();
+    ordered.sort_by_key(|(name, _)| *name);
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    env!("CARGO_PKG_VERSION").hash(&mut hasher);
+    serde_json::to_string(resolution_config).unwrap_or_default().hash(&mut hasher);
+    for (name, data) in ordered {
+        name.hash(&mut hasher);
+        serde_json::to_string(&data.watchlist).unwrap_or_default().hash(&mut hasher);
+        serde_json::to_string(&data.ratings).unwrap_or_default().hash(&mut hasher);
+        serde_json::to_string(&data.reviews).unwrap_or_default().hash(&mut hasher);
+        serde_json::to_string(&data.watch_history).unwrap_or_default().hash(&mut hasher);
+    }
+    format!("{:016x}", hasher.finish())
+}
+
+/// Canonical ordering key for a source: its position in `preference`, with
+/// unlisted sources after all listed ones, ordered by name
+fn source_rank<'a>(source_name: &'a str, preference: &[String]) -> (usize, &'a str) {
+    let position = preference
+        .iter()
+        .position(|s| s == source_name)
+        .unwrap_or(usize::MAX);
+    (position, source_name)
+}
+
+/// Put conflict candidates in tie-break order (source rank, then imdb_id) so that a
+/// subsequent stable sort by timestamp leaves exact ties in a deterministic order
+fn sort_candidates_for_tie_break<T>(
+    candidates: &mut [(&str, &T)],
+    imdb_id: impl Fn(&T) -> &str,
+    preference: &[String],
+) {
+    candidates.sort_by(|(a_source, a), (b_source, b)| {
+        source_rank(a_source, preference)
+            .cmp(&source_rank(b_source, preference))
+            .then_with(|| imdb_id(a).cmp(imdb_id(b)))
+    });
+}
+
+/// How `a` compares to `b` in recency for `MostRecent`. A timestamp at exactly midnight
+/// is a date without a time (IMDB exports), so when either is one, only dates are
+/// compared, as `filter_by_timestamp` does.
+fn compare_recency(a: DateTime<Utc>, b: DateTime<Utc>) -> Ordering {
+    let is_date_only = |ts: DateTime<Utc>| ts.hour() == 0 && ts.minute() == 0 && ts.second() == 0;
+    if is_date_only(a) || is_date_only(b) {
+        a.date_naive().cmp(&b.date_naive())
+    } else {
+        a.cmp(&b)
+    }
+}
+
+/// The most recent of `candidates`, which are in tie-break order, so the first of
+/// equally recent candidates wins
+fn most_recent<'a, T>(candidates: &[(&str, &'a T)], timestamp: impl Fn(&T) -> DateTime<Utc>) -> &'a T {
+    let mut best = candidates[0].1;
+    for (_, candidate) in &candidates[1..] {
+        if compare_recency(timestamp(candidate), timestamp(best)) == Ordering::Greater {
+            best = candidate;
+        }
+    }
+    best
+}
+
+/// Group items that refer to the same title. Matching is transitive: an item joins a
+/// group when it matches any member, so an imdb-only and a tmdb-only entry end up
+/// together once another source has both IDs. Groups and their members keep the order
+/// of `items`.
+pub(crate) fn group_matching<'a, T>(
+    items: &[(&'a str, &'a T)],
+    matches: impl Fn(&T, &T) -> bool,
+) -> Vec<Vec<(&'a str, &'a T)>> {
+    let mut groups: Vec<Vec<(&'a str, &'a T)>> = Vec::new();
+    let mut assigned = vec![false; items.len()];
+
+    for idx in 0..items.len() {
+        if assigned[idx] {
+            continue;
+        }
+        assigned[idx] = true;
+        let mut members = vec![idx];
+
+        // Repeat until no more items join, since a later member can link earlier items
+        loop {
+            let mut added = false;
+            for other_idx in (idx + 1)..items.len() {
+                if !assigned[other_idx] && members.iter().any(|&m| matches(items[m].1, items[other_idx].1)) {
+                    assigned[other_idx] = true;
+                    members.push(other_idx);
+                    added = true;
+                }
+            }
+            if !added {
+                break;
+            }
+        }
+
+        members.sort_unstable();
+        groups.push(members.into_iter().map(|m| items[m]).collect());
+    }
+
+    groups
+}
+
+/// Union of the winner's IDs with every other candidate's, in candidate order. The
+/// winner's values are kept where sources disagree, so the result is deterministic.
+fn merge_candidate_ids<'a>(
+    winner: Option<&MediaIds>,
+    candidates: impl IntoIterator<Item = Option<&'a MediaIds>>,
+) -> Option<MediaIds> {
+    let mut merged = winner.cloned().unwrap_or_default();
+    for ids in candidates.into_iter().flatten() {
+        merged.merge(ids);
+    }
+    if merged.is_empty() {
+        winner.cloned()
+    } else {
+        Some(merged)
+    }
+}
+
+/// Fill an empty `imdb_id` from merged IDs so targets keyed on IMDB can match the item
+fn fill_imdb_id(imdb_id: &mut String, ids: &Option<MediaIds>) {
+    if imdb_id.is_empty() {
+        if let Some(imdb) = ids.as_ref().and_then(|ids| ids.imdb_id.clone()) {
+            *imdb_id = imdb;
+        }
     }
 }
 
@@ -97,7 +298,9 @@ fn watchlist_items_match(item1: &WatchlistItem, item2: &WatchlistItem) -> bool {
 
 fn resolve_ratings(
     source_data: &[(&str, &SourceData)],
+    preference: &[String],
     resolution_config: &ResolutionConfig,
+    report: &mut ResolutionReport,
 ) -> Vec<Rating> {
     // Build map of all ratings by any available ID
     // Use a two-pass approach: first group by key, then merge groups that match by any ID
@@ -111,31 +314,7 @@ fn resolve_ratings(
     }
     
     // Group ratings that match by any ID
-    let mut groups: Vec<Vec<(&str, &Rating)>> = Vec::new();
-    let mut assigned = std::collections::HashSet::new();
-    
-    for (idx, (source_name, rating)) in all_ratings.iter().enumerate() {
-        if assigned.contains(&idx) {
-            continue;
-        }
-        
-        let mut group = vec![(*source_name, *rating)];
-        assigned.insert(idx);
-        
-        // Find all other ratings that match this one
-        for (other_idx, (other_source, other_rating)) in all_ratings.iter().enumerate().skip(idx + 1) {
-            if assigned.contains(&other_idx) {
-                continue;
-            }
-            
-            if ratings_match(rating, other_rating) {
-                group.push((*other_source, *other_rating));
-                assigned.insert(other_idx);
-            }
-        }
-        
-        groups.push(group);
-    }
+    let groups = group_matching(&all_ratings, ratings_match);
     
     // Use per-type strategy if specified, otherwise global strategy
     let strategy = resolution_config.ratings_strategy
@@ -144,7 +323,11 @@ fn resolve_ratings(
     
     // Resolve each group
     let mut resolved = Vec::new();
-    for candidates in groups {
+    for mut candidates in groups {
+        // An inferred rating (a liked Trakt comment) only counts when nobody rated the title
+        if candidates.iter().any(|(_, rating)| !rating.inferred) {
+            candidates.retain(|(_, rating)| !rating.inferred);
+        }
         if candidates.len() == 1 {
             // Only one source has it, use that
             resolved.push(candidates[0].1.clone());
@@ -153,18 +336,16 @@ fn resolve_ratings(
             let mut resolved_rating = resolve_rating_conflict(
                 &candidates,
                 strategy,
+                preference,
                 resolution_config,
             );
+            report.record_ratings(&candidates, &resolved_rating);
             // Merge MediaIds from all candidates
-            let mut merged_ids = resolved_rating.ids.clone().unwrap_or_default();
-            for (_, rating) in &candidates {
-                if let Some(ref ids) = rating.ids {
-                    merged_ids.merge(ids);
-                }
-            }
-            if !merged_ids.is_empty() {
-                resolved_rating.ids = Some(merged_ids);
-            }
+            resolved_rating.ids = merge_candidate_ids(
+                resolved_rating.ids.as_ref(),
+                candidates.iter().map(|(_, rating)| rating.ids.as_ref()),
+            );
+            fill_imdb_id(&mut resolved_rating.imdb_id, &resolved_rating.ids);
             resolved.push(resolved_rating);
         }
     }
@@ -175,11 +356,17 @@ fn resolve_ratings(
 fn resolve_rating_conflict(
     candidates: &[(&str, &Rating)],
     strategy: &ResolutionStrategy,
+    preference: &[String],
     resolution_config: &ResolutionConfig,
 ) -> Rating {
-    // Sort by timestamp
+    // Sort by timestamp (stable, so exact ties keep tie-break order)
     let mut sorted = candidates.to_vec();
+    sort_candidates_for_tie_break(&mut sorted, |r: &Rating| r.imdb_id.as_str(), preference);
     match strategy {
+        ResolutionStrategy::MostRecent => {
+            // No tolerance window: only equal timestamps fall back to preference
+            return most_recent(&sorted, |rating| rating.date_added).clone();
+        }
         ResolutionStrategy::Newest => {
             sorted.sort_by_key(|(_, rating)| std::cmp::Reverse(rating.date_added));
         }
@@ -205,8 +392,8 @@ fn resolve_rating_conflict(
         
         if time_diff <= resolution_config.timestamp_tolerance_seconds {
             // Timestamps are within tolerance - use preference strategy
-            // Use first source from source_preference as fallback
-            for preferred_source in &resolution_config.source_preference {
+            // Use first source from the ratings preference as fallback
+            for preferred_source in preference {
                 if let Some(candidate) = sorted.iter().find(|(name, _)| name == preferred_source) {
                     return candidate.1.clone();
                 }
@@ -216,7 +403,7 @@ fn resolve_rating_conflict(
     
     // Timestamps differ significantly, or no preference match - use strategy
     match strategy {
-        ResolutionStrategy::Newest | ResolutionStrategy::Preference | ResolutionStrategy::Merge => {
+        ResolutionStrategy::Newest | ResolutionStrategy::Preference | ResolutionStrategy::Merge | ResolutionStrategy::MostRecent => {
             // Most recent (already sorted)
             sorted[0].1.clone()
         }
@@ -229,7 +416,9 @@ fn resolve_rating_conflict(
 
 fn resolve_watchlist(
     source_data: &[(&str, &SourceData)],
+    preference: &[String],
     resolution_config: &ResolutionConfig,
+    report: &mut ResolutionReport,
 ) -> Vec<WatchlistItem> {
     // Use per-type strategy if specified, otherwise global strategy
     let strategy = resolution_config.watchlist_strategy
@@ -248,14 +437,8 @@ fn resolve_watchlist(
                     let mut found_match = false;
                     for existing in &mut all_items {
                         if watchlist_items_match(existing, item) {
-                            // Merge MediaIds
-                            if let Some(ref item_ids) = item.ids {
-                                if let Some(ref mut existing_ids) = existing.ids {
-                                    existing_ids.merge(item_ids);
-                                } else {
-                                    existing.ids = Some(item_ids.clone());
-                                }
-                            }
+                            // Merge MediaIds, kept across the replacements below
+                            let merged_ids = merge_candidate_ids(existing.ids.as_ref(), [item.ids.as_ref()]);
                             
                             // Prefer item with status if the other doesn't have one
                             let existing_has_status = existing.status.is_some();
@@ -271,6 +454,8 @@ fn resolve_watchlist(
                                 // Both have status or both don't - keep most recent
                                 *existing = item.clone();
                             }
+                            existing.ids = merged_ids;
+                            fill_imdb_id(&mut existing.imdb_id, &existing.ids);
                             found_match = true;
                             break;
                         }
@@ -302,31 +487,7 @@ fn resolve_watchlist(
             }
             
             // Group items that match by any ID
-            let mut groups: Vec<Vec<(&str, &WatchlistItem)>> = Vec::new();
-            let mut assigned = HashSet::new();
-            
-            for (idx, (source_name, item)) in all_items.iter().enumerate() {
-                if assigned.contains(&idx) {
-                    continue;
-                }
-                
-                let mut group = vec![(*source_name, *item)];
-                assigned.insert(idx);
-                
-                // Find all other items that match this one
-                for (other_idx, (other_source, other_item)) in all_items.iter().enumerate().skip(idx + 1) {
-                    if assigned.contains(&other_idx) {
-                        continue;
-                    }
-                    
-                    if watchlist_items_match(item, other_item) {
-                        group.push((*other_source, *other_item));
-                        assigned.insert(other_idx);
-                    }
-                }
-                
-                groups.push(group);
-            }
+            let groups = group_matching(&all_items, watchlist_items_match);
             
             // Resolve each group (same logic as ratings)
             let mut resolved = Vec::new();
@@ -336,24 +497,22 @@ fn resolve_watchlist(
                 } else {
                     // Resolve conflict using same logic as ratings
                     let mut sorted = candidates.to_vec();
-                    sorted.sort_by_key(|(_, item)| std::cmp::Reverse(item.date_added));
+                    sort_candidates_for_tie_break(&mut sorted, |i: &WatchlistItem| i.imdb_id.as_str(), preference);
                     
                     // Apply timestamp tolerance and preference logic (similar to ratings)
                     let mut resolved_item = resolve_watchlist_conflict(
-                        &sorted,
+                        &mut sorted,
                         strategy,
+                        preference,
                         resolution_config,
                     );
+                    report.record_watchlist(&candidates, &resolved_item);
                     // Merge MediaIds from all candidates
-                    let mut merged_ids = resolved_item.ids.clone().unwrap_or_default();
-                    for (_, item) in candidates {
-                        if let Some(ref ids) = item.ids {
-                            merged_ids.merge(ids);
-                        }
-                    }
-                    if !merged_ids.is_empty() {
-                        resolved_item.ids = Some(merged_ids);
-                    }
+                    resolved_item.ids = merge_candidate_ids(
+                        resolved_item.ids.as_ref(),
+                        candidates.iter().map(|(_, item)| item.ids.as_ref()),
+                    );
+                    fill_imdb_id(&mut resolved_item.imdb_id, &resolved_item.ids);
                     resolved.push(resolved_item);
                 }
             }
@@ -363,11 +522,18 @@ fn resolve_watchlist(
     }
 }
 
+/// `sorted` comes in tie-break order
 fn resolve_watchlist_conflict(
-    sorted: &[(&str, &WatchlistItem)],
-    _strategy: &ResolutionStrategy,
+    sorted: &mut [(&str, &WatchlistItem)],
+    strategy: &ResolutionStrategy,
+    preference: &[String],
     resolution_config: &ResolutionConfig,
 ) -> WatchlistItem {
+    if let ResolutionStrategy::MostRecent = strategy {
+        return most_recent(sorted, |item| item.date_added).clone();
+    }
+    sorted.sort_by_key(|(_, item)| std::cmp::Reverse(item.date_added));
+    
     // Similar logic to resolve_rating_conflict but for WatchlistItem
     if sorted.len() > 1 {
         let first_time = sorted[0].1.date_added;
@@ -375,8 +541,8 @@ fn resolve_watchlist_conflict(
         let time_diff = (first_time - second_time).num_seconds().abs();
         
         if time_diff <= resolution_config.timestamp_tolerance_seconds {
-            // Use first source from source_preference as fallback
-            for preferred_source in &resolution_config.source_preference {
+            // Use first source from the watchlist preference as fallback
+            for preferred_source in preference {
                 if let Some(candidate) = sorted.iter().find(|(name, _)| name == preferred_source) {
                     return candidate.1.clone();
                 }
@@ -423,16 +589,13 @@ fn resolve_reviews(
     }
     
     // Deduplicate by matching any ID and content to avoid exact duplicates
+    // Duplicates still contribute their IDs to the kept review
     let mut deduplicated: Vec<Review> = Vec::new();
     for review in all_reviews {
-        let mut is_duplicate = false;
-        for existing in &deduplicated {
-            if reviews_match(&review, existing) {
-                is_duplicate = true;
-                break;
-            }
-        }
-        if !is_duplicate {
+        if let Some(existing) = deduplicated.iter_mut().find(|existing| reviews_match(&review, existing)) {
+            existing.ids = merge_candidate_ids(existing.ids.as_ref(), [review.ids.as_ref()]);
+            fill_imdb_id(&mut existing.imdb_id, &existing.ids);
+        } else {
             deduplicated.push(review);
         }
     }
@@ -479,22 +642,403 @@ fn resolve_watch_history(
     }
     
     // Deduplicate by matching any ID and watched_at - same item watched at same time
+    // Duplicates still contribute their IDs to the kept entry
     let mut deduplicated: Vec<WatchHistory> = Vec::new();
     for entry in all_history {
-        let mut is_duplicate = false;
-        for existing in &deduplicated {
-            if watch_history_match(&entry, existing) {
-                is_duplicate = true;
-                break;
-            }
-        }
-        if !is_duplicate {
+        if let Some(existing) = deduplicated.iter_mut().find(|existing| watch_history_match(&entry, existing)) {
+            existing.ids = merge_candidate_ids(existing.ids.as_ref(), [entry.ids.as_ref()]);
+            fill_imdb_id(&mut existing.imdb_id, &existing.ids);
+        } else {
             deduplicated.push(entry);
         }
     }
     
+    let mut deduplicated = reconcile_show_and_episode_grain(deduplicated);
+    
     // Sort by watched_at (most recent first)
     deduplicated.sort_by_key(|e| std::cmp::Reverse(e.watched_at));
     deduplicated
 }
 
+/// Whether `episode` belongs to the series `show` is a show-level entry for
+fn is_episode_of(episode: &WatchHistory, show: &WatchHistory) -> bool {
+    let Some(series_ids) = &episode.show_ids else {
+        return false;
+    };
+    if !show.imdb_id.is_empty() && series_ids.imdb_id.as_deref() == Some(show.imdb_id.as_str()) {
+        return true;
+    }
+    match &show.ids {
+        Some(ids) => crate::id_matching::match_by_any_id(series_ids, ids),
+        None => false,
+    }
+}
+
+/// One source may record "watched the show" where another records the individual
+/// episodes. Episodes are the finer grain, so a show-level entry is dropped when fully
+/// watched episodes of that series are present; its IDs are kept on those episodes'
+/// `show_ids`. Partial plays don't count as coverage.
+fn reconcile_show_and_episode_grain(history: Vec<WatchHistory>) -> Vec<WatchHistory> {
+    let (shows, mut rest): (Vec<WatchHistory>, Vec<WatchHistory>) = history
+        .into_iter()
+        .partition(|entry| entry.media_type == MediaType::Show);
+
+    for show in shows {
+        let mut covered = false;
+        for episode in rest.iter_mut() {
+            if matches!(episode.media_type, MediaType::Episode { .. }) && !episode.is_partial() && is_episode_of(episode, &show) {
+                covered = true;
+                let mut show_ids = show.ids.clone().unwrap_or_default();
+                if show_ids.imdb_id.is_none() && !show.imdb_id.is_empty() {
+                    show_ids.imdb_id = Some(show.imdb_id.clone());
+                }
+                episode.show_ids = merge_candidate_ids(episode.show_ids.as_ref(), [Some(&show_ids)]);
+            }
+        }
+        if covered {
+            debug!(
+                imdb_id = %show.imdb_id,
+                source = %show.source,
+                "Dropping show-level watch history entry covered by episode entries"
+            );
+        } else {
+            rest.push(show);
+        }
+    }
+    rest
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use media_sync_models::{MediaType, RatingSource};
+
+    fn source_data(source: &str, rating: u8) -> SourceData {
+        let date = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        SourceData {
+            watchlist: vec![WatchlistItem {
+                imdb_id: "tt0113277".to_string(),
+                ids: None,
+                title: format!("Heat ({})", source),
+                year: Some(1995),
+                media_type: MediaType::Movie,
+                date_added: date,
+                source: source.to_string(),
+                status: None,
+                tags: Vec::new(),
+            }],
+            ratings: vec![Rating {
+                imdb_id: "tt0113277".to_string(),
+                ids: None,
+                rating,
+                rating_f64: None,
+                date_added: date,
+                media_type: MediaType::Movie,
+                source: RatingSource::Trakt,
+                inferred: false,
+            }],
+            reviews: vec![],
+            watch_history: vec![WatchHistory {
+                imdb_id: "tt0113277".to_string(),
+                ids: None,
+                title: Some(format!("Heat ({})", source)),
+                year: Some(1995),
+                watched_at: date,
+                media_type: MediaType::Movie,
+                source: source.to_string(),
+                watched_percent: None,
+                show_ids: None,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_resolution_is_independent_of_source_order() {
+        let trakt = source_data("trakt", 7);
+        let simkl = source_data("simkl", 9);
+        let plex = source_data("plex", 5);
+
+        for source_preference in [vec![], vec!["plex".to_string()]] {
+            let config = ResolutionConfig {
+                source_preference,
+                ..ResolutionConfig::default()
+            };
+            let forward = resolve_all_conflicts(&[("trakt", &trakt), ("simkl", &simkl), ("plex", &plex)], &config);
+            let reversed = resolve_all_conflicts(&[("plex", &plex), ("simkl", &simkl), ("trakt", &trakt)], &config);
+            let shuffled = resolve_all_conflicts(&[("simkl", &simkl), ("plex", &plex), ("trakt", &trakt)], &config);
+
+            assert_eq!(forward, reversed);
+            assert_eq!(forward, shuffled);
+        }
+    }
+
+    #[test]
+    fn test_full_tie_prefers_listed_then_lexicographic_source() {
+        let trakt = source_data("trakt", 7);
+        let simkl = source_data("simkl", 9);
+
+        let config = ResolutionConfig::default();
+        let resolved = resolve_all_conflicts(&[("trakt", &trakt), ("simkl", &simkl)], &config);
+        assert_eq!(resolved.ratings[0].rating, 9);
+
+        let config = ResolutionConfig {
+            source_preference: vec!["trakt".to_string()],
+            ..ResolutionConfig::default()
+        };
+        let resolved = resolve_all_conflicts(&[("simkl", &simkl), ("trakt", &trakt)], &config);
+        assert_eq!(resolved.ratings[0].rating, 7);
+    }
+
+    #[test]
+    fn test_inferred_ratings_lose_to_any_real_rating() {
+        let mut liked = source_data("trakt", 10);
+        liked.ratings[0].inferred = true;
+        liked.ratings[0].date_added = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+        let imdb = source_data("imdb", 6);
+        let config = ResolutionConfig {
+            source_preference: vec!["trakt".to_string()],
+            ..ResolutionConfig::default()
+        };
+
+        // Newer and preferred, but only a like
+        let resolved = resolve_all_conflicts(&[("trakt", &liked), ("imdb", &imdb)], &config);
+        assert_eq!((resolved.ratings[0].rating, resolved.ratings[0].inferred), (6, false));
+
+        // With nothing else to go on, the like stands
+        let resolved = resolve_all_conflicts(&[("trakt", &liked)], &config);
+        assert_eq!(resolved.ratings[0].rating, 10);
+    }
+
+    #[test]
+    fn test_most_recent_ignores_tolerance_and_treats_midnight_as_date_only() {
+        let rated_at = |mut data: SourceData, date: DateTime<Utc>| {
+            data.ratings[0].date_added = date;
+            data
+        };
+        let config = ResolutionConfig {
+            strategy: ResolutionStrategy::MostRecent,
+            source_preference: vec!["trakt".to_string(), "imdb".to_string()],
+            ..ResolutionConfig::default()
+        };
+
+        // Ten minutes apart, well within the tolerance: the later one still wins
+        let trakt = rated_at(source_data("trakt", 7), Utc.with_ymd_and_hms(2024, 3, 1, 20, 0, 0).unwrap());
+        let imdb = rated_at(source_data("imdb", 9), Utc.with_ymd_and_hms(2024, 3, 1, 20, 10, 0).unwrap());
+        let resolved = resolve_all_conflicts(&[("trakt", &trakt), ("imdb", &imdb)], &config);
+        assert_eq!(resolved.ratings[0].rating, 9);
+
+        // Equal timestamps: preference breaks the tie
+        let imdb = rated_at(source_data("imdb", 9), Utc.with_ymd_and_hms(2024, 3, 1, 20, 0, 0).unwrap());
+        let resolved = resolve_all_conflicts(&[("imdb", &imdb), ("trakt", &trakt)], &config);
+        assert_eq!(resolved.ratings[0].rating, 7);
+
+        // An IMDB date on the same day is not older than the evening: a tie, so preference
+        let imdb = rated_at(source_data("imdb", 9), Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap());
+        let resolved = resolve_all_conflicts(&[("imdb", &imdb), ("trakt", &trakt)], &config);
+        assert_eq!(resolved.ratings[0].rating, 7);
+
+        // An IMDB date on a later day wins over an earlier precise time
+        let imdb = rated_at(source_data("imdb", 9), Utc.with_ymd_and_hms(2024, 3, 2, 0, 0, 0).unwrap());
+        let resolved = resolve_all_conflicts(&[("trakt", &trakt), ("imdb", &imdb)], &config);
+        assert_eq!(resolved.ratings[0].rating, 9);
+    }
+
+    #[test]
+    fn test_per_data_type_preference_overrides_source_preference() {
+        let trakt = source_data("trakt", 7);
+        let imdb = source_data("imdb", 9);
+        let config = ResolutionConfig {
+            source_preference: vec!["trakt".to_string(), "imdb".to_string()],
+            ratings_preference: vec!["imdb".to_string()],
+            ..ResolutionConfig::default()
+        };
+
+        let resolved = resolve_all_conflicts(&[("trakt", &trakt), ("imdb", &imdb)], &config);
+        assert_eq!(resolved.ratings[0].rating, 9);
+        assert_eq!(resolved.watchlist[0].title, "Heat (trakt)");
+        assert_eq!(resolved.watch_history[0].title.as_deref(), Some("Heat (trakt)"));
+    }
+
+    #[test]
+    fn test_report_lists_disagreements_with_their_winner() {
+        let trakt = source_data("trakt", 7);
+        let mut simkl = source_data("simkl", 9);
+        simkl.watchlist[0].status = Some(media_sync_models::NormalizedStatus::Watching);
+        let mut imdb = source_data("imdb", 7);
+        imdb.watchlist[0].status = Some(media_sync_models::NormalizedStatus::Completed);
+        // The same title twice in one export
+        imdb.ratings.push(imdb.ratings[0].clone());
+        let config = ResolutionConfig {
+            source_preference: vec!["simkl".to_string()],
+            ..ResolutionConfig::default()
+        };
+
+        let (resolved, report) = preview(&[("trakt", &trakt), ("simkl", &simkl), ("imdb", &imdb)], &config);
+        assert_eq!(resolved.ratings[0].rating, 9);
+
+        assert_eq!(report.rating_conflicts.len(), 1);
+        let conflict = &report.rating_conflicts[0];
+        assert_eq!(conflict.imdb_id, "tt0113277");
+        assert_eq!(conflict.winner, "simkl");
+        let values: Vec<(&str, u64)> = conflict.candidates.iter()
+            .map(|candidate| (candidate.source.as_str(), candidate.value.as_u64().unwrap()))
+            .collect();
+        assert_eq!(values, vec![("simkl", 9), ("imdb", 7), ("imdb", 7), ("trakt", 7)]);
+
+        // Trakt has no status, so only Simkl and IMDB disagree
+        assert_eq!(report.status_conflicts.len(), 1);
+        assert_eq!(report.status_conflicts[0].winner, "simkl");
+        assert_eq!(report.status_conflicts[0].candidates.len(), 2);
+
+        assert_eq!(report.duplicate_ids.len(), 1);
+        assert_eq!((report.duplicate_ids[0].source.as_str(), report.duplicate_ids[0].count), ("imdb", 2));
+    }
+
+    #[test]
+    fn test_resolution_input_hash_tracks_inputs_and_config() {
+        let trakt = source_data("trakt", 7);
+        let simkl = source_data("simkl", 9);
+        let config = ResolutionConfig::default();
+
+        let hash = resolution_input_hash(&[("trakt", &trakt), ("simkl", &simkl)], &config);
+        assert_eq!(hash, resolution_input_hash(&[("simkl", &simkl), ("trakt", &trakt)], &config));
+
+        let rerated = source_data("simkl", 8);
+        assert_ne!(hash, resolution_input_hash(&[("trakt", &trakt), ("simkl", &rerated)], &config));
+
+        let config = ResolutionConfig {
+            source_preference: vec!["trakt".to_string()],
+            ..ResolutionConfig::default()
+        };
+        assert_ne!(hash, resolution_input_hash(&[("trakt", &trakt), ("simkl", &simkl)], &config));
+    }
+
+    /// Give every item in `data` the given key and IDs
+    fn with_ids(mut data: SourceData, imdb_id: &str, ids: MediaIds) -> SourceData {
+        for rating in &mut data.ratings {
+            rating.imdb_id = imdb_id.to_string();
+            rating.ids = Some(ids.clone());
+        }
+        for entry in &mut data.watch_history {
+            entry.imdb_id = imdb_id.to_string();
+            entry.ids = Some(ids.clone());
+        }
+        data
+    }
+
+    #[test]
+    fn test_resolved_items_carry_ids_from_every_source() {
+        let imdb_only = MediaIds { imdb_id: Some("tt0113277".to_string()), ..MediaIds::default() };
+        let tmdb_only = MediaIds { tmdb_id: Some(949), ..MediaIds::default() };
+        let config = ResolutionConfig::default();
+
+        // Same key, different IDs: the duplicate's IDs are kept, not just the winner's
+        let imdb = with_ids(source_data("imdb", 8), "tt0113277", imdb_only.clone());
+        let plex = with_ids(source_data("plex", 8), "tt0113277", tmdb_only.clone());
+        for sources in [[("imdb", &imdb), ("plex", &plex)], [("plex", &plex), ("imdb", &imdb)]] {
+            let resolved = resolve_all_conflicts(&sources, &config);
+            assert_eq!(resolved.ratings.len(), 1);
+            let ids = resolved.ratings[0].ids.as_ref().unwrap();
+            assert_eq!((ids.imdb_id.as_deref(), ids.tmdb_id), (Some("tt0113277"), Some(949)));
+            assert_eq!(resolved.watch_history.len(), 1);
+            assert_eq!(resolved.watch_history[0].ids, resolved.ratings[0].ids);
+        }
+
+        // Nothing in common: only a source that has both IDs links the two
+        let plex = with_ids(source_data("plex", 8), "", tmdb_only);
+        let resolved = resolve_all_conflicts(&[("imdb", &imdb), ("plex", &plex)], &config);
+        assert_eq!(resolved.ratings.len(), 2);
+
+        let both = MediaIds { imdb_id: Some("tt0113277".to_string()), tmdb_id: Some(949), trakt_id: Some(1), ..MediaIds::default() };
+        let trakt = with_ids(source_data("trakt", 8), "tt0113277", both.clone());
+        let resolved = resolve_all_conflicts(&[("plex", &plex), ("imdb", &imdb), ("trakt", &trakt)], &config);
+        assert_eq!(resolved.ratings.len(), 1);
+        assert_eq!(resolved.ratings[0].imdb_id, "tt0113277");
+        assert_eq!(resolved.ratings[0].ids.as_ref(), Some(&both));
+    }
+
+    fn history(imdb_id: &str, media_type: MediaType, source: &str, day: u32) -> WatchHistory {
+        WatchHistory {
+            imdb_id: imdb_id.to_string(),
+            ids: None,
+            title: None,
+            year: None,
+            watched_at: Utc.with_ymd_and_hms(2024, 1, day, 20, 0, 0).unwrap(),
+            media_type,
+            source: source.to_string(),
+            watched_percent: None,
+            show_ids: None,
+        }
+    }
+
+    fn history_only(entries: Vec<WatchHistory>) -> SourceData {
+        SourceData { watchlist: vec![], ratings: vec![], reviews: vec![], watch_history: entries }
+    }
+
+    #[test]
+    fn test_show_watched_on_simkl_is_covered_by_trakt_episodes() {
+        // Breaking Bad as a whole on Simkl, identified by IMDB and Simkl IDs
+        let mut show = history("tt0903747", MediaType::Show, "simkl", 1);
+        show.ids = Some(MediaIds { imdb_id: Some("tt0903747".to_string()), simkl_id: Some(11121), ..MediaIds::default() });
+        // A show nobody has episodes of stays as it is
+        let other_show = history("tt0141842", MediaType::Show, "simkl", 1);
+        let simkl = history_only(vec![show, other_show]);
+
+        // Episodes 1-2 on Trakt, linked to the series by its Trakt and IMDB IDs
+        let series = MediaIds { imdb_id: Some("tt0903747".to_string()), trakt_id: Some(1388), ..MediaIds::default() };
+        let episodes = [("tt0959621", 1), ("tt1054724", 2)]
+            .into_iter()
+            .map(|(imdb_id, number)| {
+                let mut entry = history(imdb_id, MediaType::Episode { season: 1, episode: number }, "trakt", number + 1);
+                entry.show_ids = Some(series.clone());
+                entry
+            })
+            .collect();
+        let trakt = history_only(episodes);
+
+        let config = ResolutionConfig::default();
+        let resolved = resolve_all_conflicts(&[("simkl", &simkl), ("trakt", &trakt)], &config);
+        assert_eq!(resolved.watch_history.len(), 3);
+        assert!(!resolved.watch_history.iter().any(|e| e.imdb_id == "tt0903747"));
+        assert!(resolved.watch_history.iter().any(|e| e.imdb_id == "tt0141842"));
+        for episode in resolved.watch_history.iter().filter(|e| e.source == "trakt") {
+            let series = episode.show_ids.as_ref().unwrap();
+            assert_eq!((series.trakt_id, series.simkl_id), (Some(1388), Some(11121)));
+        }
+
+        // A partial play of an episode doesn't cover the show
+        let mut partial = history("tt0959621", MediaType::Episode { season: 1, episode: 1 }, "plex", 2);
+        partial.show_ids = Some(series);
+        partial.watched_percent = Some(40.0);
+        let plex = history_only(vec![partial]);
+        let resolved = resolve_all_conflicts(&[("simkl", &simkl), ("plex", &plex)], &config);
+        assert!(resolved.watch_history.iter().any(|e| e.imdb_id == "tt0903747"));
+        assert_eq!(resolved.playback_progress.len(), 1);
+    }
+
+    #[test]
+    fn test_movies_only_plex_config_drops_shows_and_episodes() {
+        let sources: media_sync_config::SourceConfig = serde_json::from_value(serde_json::json!({
+            "media_types": { "plex": ["movie"] },
+        })).unwrap();
+        let mut plex = source_data("plex", 8);
+        let mut show = plex.watchlist[0].clone();
+        show.media_type = MediaType::Show;
+        plex.watchlist.push(show);
+        let mut episode = plex.watch_history[0].clone();
+        episode.media_type = MediaType::Episode { season: 1, episode: 2 };
+        plex.watch_history.push(episode.clone());
+
+        let dropped = plex.retain_media_types(&sources.media_types["plex"]);
+        assert_eq!(dropped, 2);
+        assert!(plex.watchlist.iter().all(|item| item.media_type == MediaType::Movie));
+        assert!(plex.watch_history.iter().all(|item| item.media_type == MediaType::Movie));
+        assert_eq!((plex.watchlist.len(), plex.ratings.len(), plex.watch_history.len()), (1, 1, 1));
+
+        // "show" takes episodes along
+        let mut shows = SourceData { watchlist: vec![], ratings: vec![], reviews: vec![], watch_history: vec![episode] };
+        assert_eq!(shows.retain_media_types(&[MediaTypeFilter::Show]), 0);
+    }
+}
@@ -0,0 +1,271 @@
+//! Links between reviews and the copies a sync wrote to other sources.
+//!
+//! Review deduplication compares content, so a copy the target stored slightly
+//! differently (whitespace, emoji replacement) looks like a new review on the next run
+//! and gets pushed back to where it came from. Every review a target accepted is
+//! recorded here as a link from its origin (source and review ID) to the target and the
+//! ID the target stored it under. Before reviews are distributed, a review is dropped
+//! when it is a linked copy of one the target already has, or when its original was
+//! already written to the target.
+//!
+//! Links are kept in `cache/review_links/{target}.json`, one file per target so
+//! concurrent distribution never writes the same file. `--force-full-sync` ignores them.
+//!
+//! Links also keep the text written, for `sync.reviews.indexing_grace_minutes`: Trakt
+//! takes minutes to return a new review, so a sync soon after would not see it and
+//! write it again. Within the grace period a review with the same text is not written
+//! to that target again, even with `--force-full-sync`.
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Duration, Utc};
+use media_sync_models::{MediaIds, Review};
+use media_sync_sources::WrittenReview;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tracing::{debug, info, warn};
+
+use crate::id_matching::match_by_any_id;
+use crate::review_text::comparable_text;
+
+/// One review written to `target`, copied from `origin`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewLink {
+    pub imdb_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ids: Option<MediaIds>,
+    pub origin: String,
+    /// The origin's ID for the review, when it reports one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin_review_id: Option<String>,
+    pub target: String,
+    /// The target's ID for the copy, when it reports one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_review_id: Option<String>,
+    pub linked_at: DateTime<Utc>,
+    /// The text written to the target
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+}
+
+impl ReviewLink {
+    /// Whether `review`, read from the link's target, is the copy the link records: the
+    /// review with the copy's ID, or, when the target reports no IDs, any review of
+    /// the same title
+    fn is_copy(&self, review: &Review) -> bool {
+        match (&self.target_review_id, &review.review_id) {
+            (Some(copy_id), Some(review_id)) => copy_id == review_id,
+            _ => self.same_item(review),
+        }
+    }
+
+    fn same_item(&self, review: &Review) -> bool {
+        if !self.imdb_id.is_empty() && self.imdb_id == review.imdb_id {
+            return true;
+        }
+        match (&self.ids, &review.ids) {
+            (Some(link_ids), Some(review_ids)) => match_by_any_id(link_ids, review_ids),
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ReviewLinks {
+    links: Vec<ReviewLink>,
+}
+
+impl ReviewLinks {
+    /// Links for every target in `dir`; unreadable files are skipped with a warning
+    pub fn load(dir: &Path) -> Self {
+        let mut links = Vec::new();
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return Self { links };
+        };
+        for path in entries.flatten().map(|entry| entry.path()) {
+            if path.extension().is_some_and(|ext| ext == "json") {
+                match load_file(&path) {
+                    Ok(file_links) => links.extend(file_links),
+                    Err(e) => warn!("Ignoring review links {}: {}", path.display(), e),
+                }
+            }
+        }
+        Self { links }
+    }
+
+    /// Drop reviews that would echo a synced copy back to `target`, or write one again
+    pub fn filter_for_target(&self, reviews: Vec<Review>, target: &str) -> Vec<Review> {
+        let before = reviews.len();
+        let kept: Vec<Review> = reviews.into_iter()
+            .filter(|review| {
+                let linked = self.links.iter().any(|link| {
+                    // A copy of one of the target's own reviews
+                    let is_copy = link.origin == target && link.target == review.source && link.is_copy(review);
+                    // An original that was already written to the target
+                    let already_synced = link.origin == review.source
+                        && link.target == target
+                        && link.same_item(review)
+                        && (link.origin_review_id.is_none() || review.review_id.is_none() || link.origin_review_id == review.review_id);
+                    is_copy || already_synced
+                });
+                if linked {
+                    debug!("Skipping review for {} from {}: already linked to a review on {}", review.imdb_id, review.source, target);
+                }
+                !linked
+            })
+            .collect();
+        if kept.len() < before {
+            debug!("Skipped {} reviews for {} that are linked to reviews it already has", before - kept.len(), target);
+        }
+        kept
+    }
+
+    /// Drop reviews whose text was written to `target` less than `grace` ago, which the
+    /// target may not return yet
+    pub fn filter_recently_written(&self, reviews: Vec<Review>, target: &str, grace: Duration) -> Vec<Review> {
+        let since = Utc::now() - grace;
+        let recent: Vec<(&ReviewLink, String)> = self.links.iter()
+            .filter(|link| link.target == target && link.linked_at > since)
+            .filter_map(|link| link.content.as_deref().map(|content| (link, comparable_text(content))))
+            .collect();
+        if recent.is_empty() {
+            return reviews;
+        }
+
+        reviews.into_iter()
+            .filter(|review| {
+                let text = comparable_text(&review.content);
+                let written = recent.iter().find(|(link, content)| *content == text && link.same_item(review));
+                if let Some((link, _)) = written {
+                    info!(
+                        "Not writing review for {} to {} again: written at {} and may not be indexed yet (sync.reviews.indexing_grace_minutes)",
+                        review.imdb_id, target, link.linked_at
+                    );
+                }
+                written.is_none()
+            })
+            .collect()
+    }
+
+    /// Record the `written` of `reviews` (as returned by `set_reviews`) as written to
+    /// `target`
+    pub fn record(dir: &Path, target: &str, reviews: &[Review], written: &[WrittenReview]) -> Result<()> {
+        let path = target_path(dir, target);
+        let mut links = if path.exists() { load_file(&path)? } else { Vec::new() };
+        let now = Utc::now();
+        for copy in written {
+            let Some(review) = reviews.get(copy.index) else {
+                continue;
+            };
+            let link = ReviewLink {
+                imdb_id: review.imdb_id.clone(),
+                ids: review.ids.clone(),
+                origin: review.source.clone(),
+                origin_review_id: review.review_id.clone(),
+                target: target.to_string(),
+                target_review_id: copy.review_id.clone(),
+                linked_at: now,
+                content: Some(review.content.clone()),
+            };
+            // Re-linking the same original replaces the old link
+            links.retain(|existing| {
+                !(existing.origin == link.origin && existing.origin_review_id == link.origin_review_id && existing.same_item(review))
+            });
+            links.push(link);
+        }
+
+        std::fs::create_dir_all(dir)?;
+        let json = serde_json::to_string_pretty(&links)
+            .map_err(|e| anyhow!("Failed to serialize review links: {}", e))?;
+        std::fs::write(&path, json)
+            .map_err(|e| anyhow!("Failed to write review links {}: {}", path.display(), e))
+    }
+}
+
+fn target_path(dir: &Path, target: &str) -> PathBuf {
+    dir.join(format!("{}.json", target))
+}
+
+fn load_file(path: &Path) -> Result<Vec<ReviewLink>> {
+    let content = std::fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use media_sync_models::MediaType;
+
+    fn review(source: &str, review_id: &str, content: &str) -> Review {
+        Review {
+            imdb_id: "tt0111161".to_string(),
+            ids: None,
+            content: content.to_string(),
+            date_added: Utc::now(),
+            media_type: MediaType::Movie,
+            source: source.to_string(),
+            is_spoiler: false,
+            rating: None,
+            review_id: Some(review_id.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_review_synced_imdb_to_trakt_is_not_echoed_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let original = review("imdb", "rw0000001", "Hope is a good thing.\n\nMaybe the best of things.");
+
+        // First sync: nothing linked yet, the IMDB review goes to Trakt
+        let to_trakt = ReviewLinks::load(dir.path()).filter_for_target(vec![original.clone()], "trakt");
+        assert_eq!(to_trakt.len(), 1);
+        let written = [WrittenReview { index: 0, review_id: Some("12345".to_string()) }];
+        ReviewLinks::record(dir.path(), "trakt", &to_trakt, &written).unwrap();
+
+        // Next sync: Trakt stored it with different whitespace, so content dedup misses it
+        let copy = review("trakt", "12345", "Hope is a good thing. Maybe the best of things.");
+        let other = Review { imdb_id: "tt0068646".to_string(), ..review("trakt", "67890", "An offer I couldn't refuse.") };
+        // A review of the same title the user wrote on Trakt themselves
+        let own = review("trakt", "55555", "Rewatched it, still great.");
+        let links = ReviewLinks::load(dir.path());
+
+        let to_imdb = links.filter_for_target(vec![copy, other, own], "imdb");
+        let kept: Vec<&str> = to_imdb.iter().filter_map(|review| review.review_id.as_deref()).collect();
+        assert_eq!(kept, ["67890", "55555"]);
+        assert!(links.filter_for_target(vec![original], "trakt").is_empty());
+
+        // A different IMDB review of the same title is a new review
+        let second = review("imdb", "rw0000002", "Second viewing.");
+        assert_eq!(links.filter_for_target(vec![second], "trakt").len(), 1);
+    }
+
+    #[test]
+    fn test_only_written_reviews_are_linked() {
+        let dir = tempfile::tempdir().unwrap();
+        let accepted = review("imdb", "rw0000001", "Hope is a good thing.");
+        let rejected = Review { imdb_id: "tt0068646".to_string(), ..review("imdb", "rw0000002", "Too short.") };
+        let written = [WrittenReview { index: 0, review_id: None }];
+        ReviewLinks::record(dir.path(), "trakt", &[accepted.clone(), rejected.clone()], &written).unwrap();
+
+        let links = ReviewLinks::load(dir.path());
+        assert!(links.filter_for_target(vec![accepted], "trakt").is_empty());
+        assert_eq!(links.filter_for_target(vec![rejected], "trakt").len(), 1);
+    }
+
+    #[test]
+    fn test_recently_written_review_is_not_written_again() {
+        let dir = tempfile::tempdir().unwrap();
+        let original = review("imdb", "rw0000001", "Hope is a good thing.\n\nMaybe the best of things.");
+        ReviewLinks::record(dir.path(), "trakt", std::slice::from_ref(&original), &WrittenReview::all(std::slice::from_ref(&original))).unwrap();
+        let links = ReviewLinks::load(dir.path());
+
+        // The same text from another source, before Trakt returns the first copy
+        let same_text = review("simkl", "s1", "hope is a good thing. Maybe the best of things.");
+        let edited = review("imdb", "rw0000001", "Hope is the best of things.");
+        let kept = links.filter_recently_written(vec![original.clone(), same_text, edited], "trakt", Duration::minutes(15));
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].content, "Hope is the best of things.");
+
+        // Other targets and an elapsed grace period are unaffected
+        assert_eq!(links.filter_recently_written(vec![original.clone()], "simkl", Duration::minutes(15)).len(), 1);
+        assert_eq!(links.filter_recently_written(vec![original], "trakt", Duration::zero()).len(), 1);
+    }
+}
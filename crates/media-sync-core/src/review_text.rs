@@ -0,0 +1,189 @@
+//! Review text cleanup applied per target before reviews are distributed.
+//!
+//! Control characters (other than newlines and tabs) are always removed since
+//! no target accepts them. Emoji are removed, or replaced with
+//! `sync.reviews.emoji_replacement`, for targets listed in `sync.reviews`.
+//! Reviews are sanitized before deduplication so the content compared against
+//! the target's existing reviews is the content the target actually stores.
+//! Reviews shorter than `sync.min_review_length` are then left out.
+
+use media_sync_config::ReviewsOptions;
+use media_sync_models::Review;
+use tracing::warn;
+
+/// Emoji, pictographs and the joiners/selectors used to compose them
+fn is_emoji(c: char) -> bool {
+    matches!(c as u32,
+        0x1F000..=0x1FAFF     // emoticons, pictographs, transport, flags, skin tones
+        | 0x2600..=0x27BF     // misc symbols and dingbats
+        | 0x2B00..=0x2BFF     // arrows and stars (e.g. ⭐)
+        | 0xFE00..=0xFE0F     // variation selectors
+        | 0x200D              // zero width joiner
+        | 0x20E3              // combining enclosing keycap
+        | 0xE0020..=0xE007F   // tag characters (subdivision flags)
+    )
+}
+
+fn is_disallowed_control(c: char) -> bool {
+    c.is_control() && !matches!(c, '\n' | '\r' | '\t')
+}
+
+/// Clean review text. With `strip_emoji`, each run of emoji (so one ZWJ
+/// sequence or flag) becomes a single `replacement`, and the result is trimmed.
+pub fn sanitize_review_text(text: &str, strip_emoji: bool, replacement: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut in_emoji_run = false;
+    let mut stripped_emoji = false;
+
+    for c in text.chars() {
+        if is_disallowed_control(c) {
+            continue;
+        }
+        if strip_emoji && is_emoji(c) {
+            if !in_emoji_run {
+                out.push_str(replacement);
+                in_emoji_run = true;
+                stripped_emoji = true;
+            }
+            continue;
+        }
+        in_emoji_run = false;
+        out.push(c);
+    }
+
+    if stripped_emoji {
+        out.trim().to_string()
+    } else {
+        out
+    }
+}
+
+/// Lowercased words only, for telling whether two reviews say the same thing
+/// regardless of case, whitespace, punctuation or emoji
+pub(crate) fn comparable_text(text: &str) -> String {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Sanitize reviews for one target. Reviews left without any text are skipped
+/// and reported rather than sent, so they can't fail the target's batch.
+pub fn sanitize_reviews(reviews: &[Review], options: &ReviewsOptions, target: &str) -> Vec<Review> {
+    let strip_emoji = options.strips_emoji_for(target);
+    let mut sanitized = Vec::with_capacity(reviews.len());
+
+    for review in reviews {
+        let content = sanitize_review_text(&review.content, strip_emoji, &options.emoji_replacement);
+        if content.trim().is_empty() {
+            warn!(
+                "Skipping review for {} on {}: no text left after removing unsupported characters",
+                review.imdb_id, target
+            );
+            continue;
+        }
+        sanitized.push(Review { content, ..review.clone() });
+    }
+
+    sanitized
+}
+
+/// Remove reviews for `target` shorter than `min_length` characters (no minimum when
+/// None, see `SyncOptions::min_review_length`). Returns how many were removed.
+pub fn remove_short_reviews(reviews: &mut Vec<Review>, min_length: Option<usize>, target: &str) -> usize {
+    let Some(min_length) = min_length else {
+        return 0;
+    };
+    let mut removed = Vec::new();
+    reviews.retain(|review| {
+        let length = review.content.chars().count();
+        if length < min_length {
+            removed.push(format!("{} ({} chars)", review.imdb_id, length));
+        }
+        length >= min_length
+    });
+
+    if !removed.is_empty() {
+        warn!(
+            "Filtered out {} reviews for {} shorter than {} characters (sync.min_review_length), {} remaining. Reviews filtered: {:?}",
+            removed.len(), target, min_length, reviews.len(),
+            removed.iter().take(5).collect::<Vec<_>>()
+        );
+    }
+    removed.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use media_sync_models::MediaType;
+
+    fn review(imdb_id: &str, content: &str) -> Review {
+        Review {
+            imdb_id: imdb_id.to_string(),
+            ids: None,
+            content: content.to_string(),
+            date_added: Utc::now(),
+            media_type: MediaType::Movie,
+            source: "trakt".to_string(),
+            is_spoiler: false,
+            rating: None,
+            review_id: None,
+        }
+    }
+
+    #[test]
+    fn test_sanitize_review_text() {
+        let text = "Loved it 😍🔥\u{0007} Family night 👨\u{200D}👩\u{200D}👧 ⭐\u{FE0F}\nWould watch again";
+        assert_eq!(
+            sanitize_review_text(text, false, ""),
+            "Loved it 😍🔥 Family night 👨\u{200D}👩\u{200D}👧 ⭐\u{FE0F}\nWould watch again"
+        );
+        assert_eq!(
+            sanitize_review_text(text, true, ""),
+            "Loved it  Family night  \nWould watch again"
+        );
+        assert_eq!(
+            sanitize_review_text("Great 🎬🍿", true, "[emoji]"),
+            "Great [emoji]"
+        );
+        assert_eq!(sanitize_review_text("Café – naïve «ok»", true, ""), "Café – naïve «ok»");
+    }
+
+    #[test]
+    fn test_sanitize_reviews_skips_emoji_only_reviews_for_target() {
+        let reviews = vec![
+            review("tt0113277", "Heat 🔥🔥🔥 is a classic"),
+            review("tt0133093", "👍👍"),
+        ];
+        let options = ReviewsOptions {
+            strip_emoji_for: vec!["imdb".to_string()],
+            ..ReviewsOptions::default()
+        };
+
+        let for_imdb = sanitize_reviews(&reviews, &options, "imdb");
+        assert_eq!(for_imdb.len(), 1);
+        assert_eq!(for_imdb[0].content, "Heat  is a classic");
+
+        let for_trakt = sanitize_reviews(&reviews, &options, "trakt");
+        assert_eq!(for_trakt, reviews);
+    }
+
+    #[test]
+    fn test_remove_short_reviews_uses_the_configured_minimum() {
+        let reviews = vec![
+            review("tt0113277", &"A slow burn that pays off. ".repeat(30)),
+            review("tt0133093", "Short Trakt comment, still worth keeping"),
+        ];
+
+        let mut for_imdb = reviews.clone();
+        assert_eq!(remove_short_reviews(&mut for_imdb, Some(600), "imdb"), 1);
+        assert_eq!(for_imdb, reviews[..1]);
+
+        let mut unfiltered = reviews.clone();
+        assert_eq!(remove_short_reviews(&mut unfiltered, None, "trakt"), 0);
+        assert_eq!(unfiltered, reviews);
+    }
+}
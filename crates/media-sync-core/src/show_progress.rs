@@ -0,0 +1,94 @@
+//! Show progress sync (`sync_show_progress` / `sync --show-progress`).
+//!
+//! Progress is the last watched episode of each show, one entry per show. The resolved
+//! progress of a show is the furthest any source has reached, dated by the earliest
+//! source that reached it. Sync only moves a source forward: a source further into a
+//! show than the others is left alone.
+
+use media_sync_models::{MediaType, WatchProgress};
+
+fn same_show(a: &WatchProgress, b: &WatchProgress) -> bool {
+    crate::lists::same_title((&a.imdb_id, &a.ids, &MediaType::Show), (&b.imdb_id, &b.ids, &MediaType::Show))
+}
+
+/// Merge the progress of every source, given in `source_preference` order
+pub fn resolve_progress(sources: &[(String, Vec<WatchProgress>)]) -> Vec<WatchProgress> {
+    let mut resolved: Vec<WatchProgress> = Vec::new();
+    for (_, shows) in sources {
+        for show in shows {
+            match resolved.iter_mut().find(|existing| same_show(existing, show)) {
+                Some(existing) => {
+                    let (imdb_id, ids) = (existing.imdb_id.clone(), existing.ids.clone());
+                    if show.position() > existing.position()
+                        || (show.position() == existing.position() && show.watched_at < existing.watched_at)
+                    {
+                        *existing = show.clone();
+                    }
+                    if existing.imdb_id.is_empty() {
+                        existing.imdb_id = imdb_id;
+                    }
+                    if existing.ids.is_none() {
+                        existing.ids = ids;
+                    }
+                }
+                None => resolved.push(show.clone()),
+            }
+        }
+    }
+    resolved
+}
+
+/// The shows of `resolved` that `existing` (one source's progress) lacks or is behind on
+pub fn ahead_of(resolved: &[WatchProgress], existing: &[WatchProgress]) -> Vec<WatchProgress> {
+    resolved.iter()
+        .filter(|show| {
+            existing.iter()
+                .find(|own| same_show(own, show))
+                .is_none_or(|own| own.position() < show.position())
+        })
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use media_sync_models::MediaIds;
+
+    fn progress(imdb_id: &str, source: &str, season: u32, episode: u32, day: u32) -> WatchProgress {
+        WatchProgress {
+            imdb_id: imdb_id.to_string(),
+            ids: None,
+            title: None,
+            year: None,
+            last_watched_season: season,
+            last_watched_episode: episode,
+            watched_at: Utc.with_ymd_and_hms(2024, 1, day, 0, 0, 0).unwrap(),
+            source: source.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_progress_moves_sources_forward_only() {
+        let mut anime = progress("", "anilist", 1, 12, 3);
+        anime.ids = Some(MediaIds { anilist_id: Some(21), ..Default::default() });
+        let mut anime_on_simkl = progress("", "simkl", 1, 8, 2);
+        anime_on_simkl.ids = Some(MediaIds { anilist_id: Some(21), ..Default::default() });
+        let sources = vec![
+            ("trakt".to_string(), vec![progress("tt0903747", "trakt", 3, 4, 10)]),
+            ("simkl".to_string(), vec![progress("tt0903747", "simkl", 2, 13, 5), anime_on_simkl]),
+            ("anilist".to_string(), vec![anime]),
+        ];
+
+        let resolved = resolve_progress(&sources);
+        assert_eq!(resolved.len(), 2);
+        assert_eq!((resolved[0].position(), resolved[0].source.as_str()), ((3, 4), "trakt"));
+        assert_eq!(resolved[1].position(), (1, 12));
+
+        assert_eq!(ahead_of(&resolved, &sources[0].1).len(), 1);
+        let simkl: Vec<(u32, u32)> = ahead_of(&resolved, &sources[1].1).iter().map(WatchProgress::position).collect();
+        assert_eq!(simkl, [(3, 4), (1, 12)]);
+        assert_eq!(ahead_of(&resolved, &sources[2].1)[0].imdb_id, "tt0903747");
+    }
+}
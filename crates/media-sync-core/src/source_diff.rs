@@ -0,0 +1,134 @@
+//! Per-source differences for `totalrecall diff`.
+//!
+//! After collecting and resolving like a sync, each source is compared with the resolved
+//! data: which watchlist items and ratings it is missing, and which titles it rates
+//! differently from the resolved rating. Like `reconcile`, items are matched by IMDB ID,
+//! so items without one are left out, and a source is only compared for data types it
+//! supports and that are enabled.
+
+use media_sync_models::{MediaType, Rating, RatingSource, WatchlistItem};
+use media_sync_sources::DataType;
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::diff::filter_items_by_imdb_id;
+use crate::resolution::{ResolvedData, SourceData};
+
+/// What one source is missing or disagrees on, relative to the resolved data
+#[derive(Debug, Clone, Serialize)]
+pub struct SourceDiff {
+    pub source: String,
+    pub missing_watchlist: Vec<WatchlistItem>,
+    pub missing_ratings: Vec<Rating>,
+    pub rating_conflicts: Vec<RatingConflict>,
+}
+
+impl SourceDiff {
+    pub fn is_empty(&self) -> bool {
+        self.missing_watchlist.is_empty() && self.missing_ratings.is_empty() && self.rating_conflicts.is_empty()
+    }
+}
+
+/// A title the source rates differently from the resolved rating
+#[derive(Debug, Clone, Serialize)]
+pub struct RatingConflict {
+    pub imdb_id: String,
+    pub title: Option<String>,
+    pub media_type: MediaType,
+    /// The source's rating, on the normalized 1-10 scale
+    pub rating: u8,
+    pub resolved_rating: u8,
+    /// Where the resolved rating came from
+    pub resolved_source: RatingSource,
+}
+
+/// Compare each `(source, data types it is compared for, collected data)` with `resolved`
+pub fn diff_sources(sources: &[(String, Vec<DataType>, &SourceData)], resolved: &ResolvedData, data_types: &[DataType]) -> Vec<SourceDiff> {
+    let compared = |supported: &[DataType], data_type: DataType| data_types.contains(&data_type) && supported.contains(&data_type);
+    let resolved_ratings: HashMap<&str, &Rating> = resolved.ratings.iter()
+        .filter(|rating| !rating.imdb_id.is_empty())
+        .map(|rating| (rating.imdb_id.as_str(), rating))
+        .collect();
+
+    sources.iter()
+        .map(|(source, supported, data)| {
+            let missing_watchlist = if compared(supported, DataType::Watchlist) {
+                filter_items_by_imdb_id(&resolved.watchlist, &data.watchlist)
+            } else {
+                Vec::new()
+            };
+            let (missing_ratings, rating_conflicts) = if compared(supported, DataType::Ratings) {
+                let conflicts = data.ratings.iter()
+                    .filter_map(|rating| {
+                        let resolved = resolved_ratings.get(rating.imdb_id.as_str())
+                            .filter(|resolved| resolved.media_type == rating.media_type)?;
+                        (resolved.rating != rating.rating).then(|| RatingConflict {
+                            imdb_id: rating.imdb_id.clone(),
+                            title: rating.ids.as_ref().and_then(|ids| ids.title.clone()),
+                            media_type: rating.media_type.clone(),
+                            rating: rating.rating,
+                            resolved_rating: resolved.rating,
+                            resolved_source: resolved.source.clone(),
+                        })
+                    })
+                    .collect();
+                (filter_items_by_imdb_id(&resolved.ratings, &data.ratings), conflicts)
+            } else {
+                (Vec::new(), Vec::new())
+            };
+            SourceDiff {
+                source: source.clone(),
+                missing_watchlist,
+                missing_ratings,
+                rating_conflicts,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn rating(imdb_id: &str, value: u8, source: RatingSource) -> Rating {
+        Rating {
+            imdb_id: imdb_id.to_string(),
+            ids: None,
+            rating: value,
+            rating_f64: None,
+            date_added: Utc::now(),
+            media_type: MediaType::Movie,
+            source,
+            inferred: false,
+        }
+    }
+
+    #[test]
+    fn test_diff_lists_missing_ratings_and_conflicts() {
+        let source = |ratings| SourceData { watchlist: vec![], ratings, reviews: vec![], watch_history: vec![] };
+        let trakt = source(vec![rating("tt0133093", 9, RatingSource::Trakt), rating("tt0068646", 6, RatingSource::Trakt)]);
+        let plex = source(vec![rating("tt0133093", 9, RatingSource::Plex)]);
+        let resolved = ResolvedData {
+            watchlist: vec![],
+            ratings: vec![rating("tt0133093", 9, RatingSource::Trakt), rating("tt0068646", 8, RatingSource::Imdb)],
+            reviews: vec![],
+            watch_history: vec![],
+            playback_progress: vec![],
+        };
+        let sources = vec![
+            ("trakt".to_string(), DataType::ALL.to_vec(), &trakt),
+            ("plex".to_string(), vec![DataType::Ratings], &plex),
+        ];
+
+        let diffs = diff_sources(&sources, &resolved, &[DataType::Watchlist, DataType::Ratings]);
+        assert!(diffs[0].missing_ratings.is_empty());
+        assert_eq!(diffs[0].rating_conflicts.len(), 1);
+        let conflict = &diffs[0].rating_conflicts[0];
+        assert_eq!((conflict.imdb_id.as_str(), conflict.rating, conflict.resolved_rating), ("tt0068646", 6, 8));
+        assert_eq!(conflict.resolved_source, RatingSource::Imdb);
+        assert_eq!(diffs[1].missing_ratings.len(), 1);
+        assert!(diffs[1].rating_conflicts.is_empty());
+        assert!(!diffs[1].is_empty());
+    }
+}
@@ -1,19 +1,31 @@
 use anyhow::Result;
-use chrono::{DateTime, Timelike, Utc};
-use media_sync_config::PathManager;
-use media_sync_models::{MediaIds, Rating, Review, WatchHistory, WatchlistItem, NormalizedStatus};
-use media_sync_sources::{MediaSource, SourceError};
+use chrono::{DateTime, Datelike, Timelike, Utc};
+use media_sync_config::{DefaultRatingDate, PathManager, HUB_SOURCE};
+use media_sync_models::{CollectionItem, CustomList, ExcludedItem, MediaIds, MediaType, Rating, Review, WatchHistory, WatchProgress, WatchlistItem, NormalizedStatus};
+use media_sync_sources::{DataType, FileSource, MediaSource, SourceError};
 use serde::Serialize;
 use crate::cache::CacheManager;
+use crate::dry_run_compare::DryRunFormat;
+use crate::conflicts::{review_conflicts, save_conflict_report, ConflictRecord, ResolutionReport};
 use crate::diff::{filter_items_by_imdb_id, filter_missing_imdb_ids};
 use crate::resolution::{SourceData, ResolvedData};
-use crate::distribution::{DistributionStrategy, DistributionResult, DefaultDistributionStrategy, TraktDistributionStrategy, ImdbDistributionStrategy, SimklDistributionStrategy, PlexDistributionStrategy};
+use crate::distribution::{chunk_watch_history_by_year, clamp_ratings_to_scale, fit_to_limit, IMDB_ITEM_LIMIT, DistributionStrategy, DistributionResult, DefaultDistributionStrategy, TraktDistributionStrategy, ImdbDistributionStrategy, SimklDistributionStrategy, PlexDistributionStrategy, LetterboxdDistributionStrategy, AniListDistributionStrategy};
 use crate::id_resolver::{IdResolver, IdResolverConfig};
+use crate::event_buffer::{BufferedEvents, EventBuffer};
+use crate::failures::record_failures;
+use crate::plan::{SourcePlan, SyncPlan};
+use crate::lock::SyncLock;
+use crate::reconcile::ReconcileReport;
+use crate::source_diff::{diff_sources, SourceDiff};
+use crate::export::TraktExport;
+use crate::metrics::{MetricsAccumulator, SourceMetrics};
+use crate::progress::{Phase, ProgressTracker};
+use crate::review_links::ReviewLinks;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::{RwLock, Mutex};
 use futures::future::join_all;
-use tracing::{debug, info, instrument, trace, warn};
+use tracing::{debug, info, info_span, instrument, trace, warn, Instrument};
 
 /// Registry mapping source names to their indices in the sources vector
 struct SourceRegistry {
@@ -59,6 +71,31 @@ pub struct SyncOrchestrator {
     resolution_config: media_sync_config::ResolutionConfig,
     use_cache: std::collections::HashSet<String>,
     dry_run_sources: std::collections::HashSet<String>,
+    /// Per-source writes prepared by the last distribute phase
+    planned_sources: Vec<SourcePlan>,
+    /// Distribute the exported hub instead of resolving the collected data
+    from_hub: bool,
+    /// Report reviews the sources disagree on (`--report-conflicts`)
+    report_conflicts: bool,
+    /// Conflicts found by the last sync, when reported
+    conflicts: Vec<ConflictRecord>,
+    /// Check cached ratings against each source's scale even if this version already did
+    revalidate_cache: bool,
+    /// How dry-run files are written (`--dry-run-format`)
+    dry_run_format: DryRunFormat,
+    /// Media types each source contributes to resolution (`sources.media_types`); sources
+    /// not in it contribute everything
+    media_types: std::collections::HashMap<String, Vec<media_sync_config::MediaTypeFilter>>,
+    /// How resolved IDs are cached between runs
+    id_resolver_config: IdResolverConfig,
+    /// Errors of the current run by source, for `SyncResult::sources`
+    source_outcomes: std::sync::Mutex<std::collections::BTreeMap<String, SourceOutcome>>,
+    /// Counts of the current run by source and data type, for `SyncResult::per_source`
+    metrics: Arc<std::sync::Mutex<MetricsAccumulator>>,
+    /// Where the phases of a run are reported
+    progress: Arc<dyn ProgressTracker>,
+    /// Where caches, buffers and ledgers are kept
+    path_manager: PathManager,
 }
 
 #[derive(Debug, Clone, Default, Serialize)]
@@ -68,12 +105,124 @@ pub struct SyncOptions {
     pub sync_reviews: bool,
     pub sync_watch_history: bool,
     pub force_full_sync: bool,
+    /// Send only items dated at or after this instead of those since each source's last
+    /// sync (`--since`); wins over `force_full_sync`
+    pub since: Option<DateTime<Utc>>,
+    /// Prepare and report every write but send none (`--no-write`), whatever the dry-run settings
+    pub no_write: bool,
+    /// Warn about and leave out sources in `source_preference` that aren't configured,
+    /// instead of refusing to start (`--skip-unconfigured-sources`)
+    pub skip_unconfigured_sources: bool,
+    /// Sync named custom lists between the sources that keep them (`--lists`)
+    pub sync_lists: bool,
+    /// Add owned titles to the writable collections of the sources that keep one
+    /// (`--collection`); never touches watch history
+    pub sync_collection: bool,
+    /// Bring every show-progress source up to the furthest episode any source has
+    /// reached (`--show-progress`)
+    pub sync_show_progress: bool,
+    /// Only add and update: every watchlist removal is reported but none is sent
+    /// (`--never-remove`, `sync.never_remove`), whatever the removal settings
+    pub never_remove: bool,
+    /// Stop writing once this many writes were made across all sources and data types,
+    /// deferring the rest to a later run (`--max-items`)
+    pub max_items: Option<usize>,
+}
+
+/// Parse `--since`: a duration back from now such as `7d` or `36h`, a date such as
+/// `2024-01-01` (midnight UTC), or an RFC 3339 time
+pub fn parse_since(value: &str) -> std::result::Result<DateTime<Utc>, String> {
+    let value = value.trim();
+    if let Ok(date) = chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(date.and_hms_opt(0, 0, 0).unwrap().and_utc());
+    }
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    let duration = humantime::parse_duration(value).map_err(|_| {
+        format!("'{}' is neither a duration (e.g. 7d, 36h, 2weeks) nor a date (e.g. 2024-01-01)", value)
+    })?;
+    chrono::Duration::from_std(duration).ok()
+        .and_then(|duration| Utc::now().checked_sub_signed(duration))
+        .ok_or_else(|| format!("'{}' reaches too far back", value))
 }
 
 pub struct SyncResult {
     pub items_synced: usize,
+    /// Items held back by `watchlist.max_items_to_add_per_run` or `--max-items`, left for a later run
+    pub items_deferred: usize,
+    /// Resolution was reused from the previous run because its inputs were unchanged
+    pub resolution_skipped: bool,
     pub duration: Duration,
     pub errors: Vec<String>,
+    /// Each source in `source_preference` with the errors attributed to it
+    pub sources: Vec<SourceOutcome>,
+    /// What was fetched, added, removed, skipped and failed per source and data type
+    pub per_source: Vec<SourceMetrics>,
+}
+
+/// How one source fared in a run (`sync --strict` and the JSON output)
+#[derive(Debug, Clone, Serialize)]
+pub struct SourceOutcome {
+    pub source: String,
+    /// Authentication failed, so nothing was collected from or written to the source
+    pub auth_failed: bool,
+    pub errors: Vec<String>,
+}
+
+impl SourceOutcome {
+    pub fn is_ok(&self) -> bool {
+        !self.auth_failed && self.errors.is_empty()
+    }
+}
+
+/// Exit codes for `sync --strict`: some sources had errors
+pub const EXIT_PARTIAL: i32 = 1;
+/// Every source had errors, or the sync could not run
+pub const EXIT_FAILED: i32 = 2;
+/// Configuration could not be loaded or a source failed to authenticate
+pub const EXIT_CONFIG: i32 = 3;
+
+impl SyncResult {
+    /// The `sync --strict` exit code for this run; 0 when every source was ok
+    pub fn exit_code(&self) -> i32 {
+        if self.sources.iter().any(|source| source.auth_failed) {
+            EXIT_CONFIG
+        } else if !self.sources.is_empty() && self.sources.iter().all(|source| !source.is_ok()) {
+            EXIT_FAILED
+        } else if !self.errors.is_empty() || self.sources.iter().any(|source| !source.is_ok()) {
+            EXIT_PARTIAL
+        } else {
+            0
+        }
+    }
+}
+
+/// Add to a run's counts for one source and data type
+fn count(metrics: &std::sync::Mutex<MetricsAccumulator>, source: &str, data_type: DataType, update: impl FnOnce(&mut SourceMetrics)) {
+    update(metrics.lock().unwrap().entry(source, data_type));
+}
+
+/// What the distribution to every target of a run shares: its inputs, and the counters
+/// and ledgers the targets add to while they are written concurrently
+struct DistributionRun<'a> {
+    sources: &'a [Arc<RwLock<Box<dyn MediaSource<Error = SourceError>>>>],
+    sync_options: &'a SyncOptions,
+    config_sync_options: &'a Option<media_sync_config::SyncOptions>,
+    dry_run_sources: &'a std::collections::HashSet<String>,
+    resolved: &'a ResolvedData,
+    collected_data: &'a CollectedData,
+    removal_lists: &'a std::collections::HashMap<String, Vec<WatchlistItem>>,
+    cache_manager: &'a CacheManager,
+    metrics: &'a std::sync::Mutex<MetricsAccumulator>,
+    /// Items held back by the watchlist cap or the write budget
+    items_deferred: &'a Mutex<usize>,
+    /// Writes left of the run's --max-items budget
+    write_budget: &'a Mutex<Option<usize>>,
+    /// What the budget held back, so buffered events for it are kept
+    deferred: &'a tokio::sync::Mutex<Vec<SourcePlan>>,
+    /// Writes that failed, for `sync --retry-failures`
+    failures: &'a tokio::sync::Mutex<Vec<SourcePlan>>,
 }
 
 struct CollectedData {
@@ -93,12 +242,23 @@ struct DryRunData {
     reviews: Vec<Review>,
     watch_history: Vec<WatchHistory>,
     removal_list: Vec<WatchlistItem>, // Items that would be removed (watched or old)
+    over_limit: Vec<ExcludedItem>, // Additions left out because the target is full
 }
 
 impl SyncOrchestrator {
     pub fn new(
         sources: Vec<Box<dyn MediaSource<Error = SourceError>>>,
         resolution_config: media_sync_config::ResolutionConfig,
+    ) -> anyhow::Result<Self> {
+        Self::new_with_options(sources, resolution_config, SyncOptions::default())
+    }
+
+    /// Like `new`, but with `sync_options` in place from the start so that
+    /// `skip_unconfigured_sources` applies to the `source_preference` check
+    pub fn new_with_options(
+        sources: Vec<Box<dyn MediaSource<Error = SourceError>>>,
+        mut resolution_config: media_sync_config::ResolutionConfig,
+        sync_options: SyncOptions,
     ) -> anyhow::Result<Self> {
         // Validate source_preference
         if resolution_config.source_preference.is_empty() {
@@ -107,6 +267,10 @@ impl SyncOrchestrator {
         
         // Build registry from unwrapped sources (to get source names)
         let registry = SourceRegistry::new_from_unwrapped(&sources);
+
+        if sync_options.skip_unconfigured_sources {
+            Self::drop_unconfigured_sources(&mut resolution_config, &registry)?;
+        }
         
         // Validate that all sources in source_preference are configured
         for source_name in &resolution_config.source_preference {
@@ -117,6 +281,25 @@ impl SyncOrchestrator {
                 ));
             }
         }
+        for source_name in &resolution_config.fetch_order {
+            if !resolution_config.source_preference.contains(source_name) {
+                return Err(anyhow::anyhow!(
+                    "Source '{}' is in fetch_order but not in source_preference",
+                    source_name
+                ));
+            }
+        }
+        for (field, sources) in resolution_config.preference_overrides() {
+            for source_name in sources {
+                if !registry.contains(source_name) {
+                    return Err(anyhow::anyhow!(
+                        "Source '{}' is in {} but not provided in sources list",
+                        source_name,
+                        field
+                    ));
+                }
+            }
+        }
         
         // Wrap sources in Arc<RwLock<>>
         let sources: Vec<Arc<RwLock<Box<dyn MediaSource<Error = SourceError>>>>> = sources
@@ -127,19 +310,87 @@ impl SyncOrchestrator {
         Ok(Self {
             sources,
             registry,
-            sync_options: SyncOptions::default(),
+            sync_options,
             config_sync_options: None,
             resolution_config,
             use_cache: std::collections::HashSet::new(),
             dry_run_sources: std::collections::HashSet::new(),
+            planned_sources: Vec::new(),
+            from_hub: false,
+            report_conflicts: false,
+            conflicts: Vec::new(),
+            revalidate_cache: false,
+            dry_run_format: DryRunFormat::default(),
+            media_types: std::collections::HashMap::new(),
+            id_resolver_config: IdResolverConfig::default(),
+            source_outcomes: std::sync::Mutex::new(std::collections::BTreeMap::new()),
+            metrics: Arc::new(std::sync::Mutex::new(MetricsAccumulator::default())),
+            progress: Arc::new(crate::progress::NoProgress),
+            path_manager: PathManager::default(),
         })
     }
     
+    /// Remove sources that aren't configured from `source_preference`, `fetch_order` and
+    /// the per-data-type preferences, warning about each. Fails if none are left.
+    fn drop_unconfigured_sources(
+        resolution_config: &mut media_sync_config::ResolutionConfig,
+        registry: &SourceRegistry,
+    ) -> anyhow::Result<()> {
+        for source_name in &resolution_config.source_preference {
+            if !registry.contains(source_name) {
+                warn!(
+                    "Source '{}' is in source_preference but not configured; syncing without it",
+                    source_name
+                );
+            }
+        }
+        resolution_config.retain_sources(|source_name| registry.contains(source_name));
+        if resolution_config.source_preference.is_empty() {
+            return Err(anyhow::anyhow!("None of the sources in source_preference are configured"));
+        }
+        Ok(())
+    }
+
     pub fn with_resolution_config(mut self, config: media_sync_config::ResolutionConfig) -> Self {
         self.resolution_config = config;
         self
     }
 
+    /// Start a run's per-source outcomes: every source in `source_preference`, no errors
+    /// or counts yet
+    fn reset_source_outcomes(&self) {
+        self.metrics.lock().unwrap().clear();
+        let mut outcomes = self.source_outcomes.lock().unwrap();
+        outcomes.clear();
+        for source in &self.resolution_config.source_preference {
+            outcomes.insert(source.clone(), SourceOutcome { source: source.clone(), auth_failed: false, errors: Vec::new() });
+        }
+    }
+
+    /// Attribute `errors` to `source` in this run's outcomes
+    fn record_source_errors(&self, source: &str, errors: &[String], auth_failed: bool) {
+        let mut outcomes = self.source_outcomes.lock().unwrap();
+        let outcome = outcomes.entry(source.to_string())
+            .or_insert_with(|| SourceOutcome { source: source.to_string(), auth_failed: false, errors: Vec::new() });
+        outcome.auth_failed |= auth_failed;
+        outcome.errors.extend_from_slice(errors);
+    }
+
+    /// This run's counts, sources in `source_preference` order
+    fn run_metrics(&self) -> Vec<SourceMetrics> {
+        self.metrics.lock().unwrap().to_vec(&self.resolution_config.source_preference)
+    }
+
+    /// This run's outcomes in `source_preference` order
+    fn source_outcomes(&self) -> Vec<SourceOutcome> {
+        let outcomes = self.source_outcomes.lock().unwrap();
+        let mut ordered: Vec<SourceOutcome> = self.resolution_config.source_preference.iter()
+            .filter_map(|source| outcomes.get(source).cloned())
+            .collect();
+        ordered.extend(outcomes.values().filter(|outcome| !self.resolution_config.source_preference.contains(&outcome.source)).cloned());
+        ordered
+    }
+
     // get_source_by_name and get_source_mut_by_name removed due to lifetime issues
     // Use find_source_index and sources.get/get_mut directly instead
 
@@ -180,11 +431,70 @@ impl SyncOrchestrator {
         self
     }
 
+    /// Write dry-run files as JSON arrays or NDJSON (`--dry-run-format`)
+    pub fn with_dry_run_format(mut self, format: DryRunFormat) -> Self {
+        self.dry_run_format = format;
+        self
+    }
+
+    pub fn with_from_hub(mut self, from_hub: bool) -> Self {
+        self.from_hub = from_hub;
+        self
+    }
+
+    pub fn with_report_conflicts(mut self, report_conflicts: bool) -> Self {
+        self.report_conflicts = report_conflicts;
+        self
+    }
+
+    /// Check every source's cached ratings again (`--revalidate-cache`), not only once per version
+    pub fn with_revalidate_cache(mut self, revalidate_cache: bool) -> Self {
+        self.revalidate_cache = revalidate_cache;
+        self
+    }
+
+    /// Resolve only these media types of each source's collected data (`sources.media_types`)
+    pub fn with_media_types(mut self, media_types: std::collections::HashMap<String, Vec<media_sync_config::MediaTypeFilter>>) -> Self {
+        self.media_types = media_types;
+        self
+    }
+
+    /// Cache resolved IDs this way (e.g. the TTL from `id_matching.cache_ttl_days`)
+    pub fn with_id_resolver_config(mut self, config: IdResolverConfig) -> Self {
+        self.id_resolver_config = config;
+        self
+    }
+
+    /// Report the phases of each run, and the sources' progress within them, to `tracker`
+    pub fn with_progress_tracker(mut self, tracker: Arc<dyn ProgressTracker>) -> Self {
+        self.progress = tracker;
+        self
+    }
+
+    /// Keep caches, buffered events and ledgers under `path_manager` instead of the default paths
+    pub fn with_path_manager(mut self, path_manager: PathManager) -> Self {
+        self.path_manager = path_manager;
+        self
+    }
+
+    /// Review conflicts found by the last sync with `with_report_conflicts`
+    pub fn conflicts(&self) -> &[ConflictRecord] {
+        &self.conflicts
+    }
+
     /// Update the force_full_sync flag in sync options
     pub fn set_force_full_sync(&mut self, force: bool) {
         self.sync_options.force_full_sync = force;
     }
 
+    /// Hold the single-instance lock for the rest of a run (`sync.lock`)
+    async fn acquire_sync_lock(&self) -> Result<SyncLock> {
+        let options = self.config_sync_options.as_ref()
+            .map(|o| o.lock.clone())
+            .unwrap_or_default();
+        SyncLock::acquire(&self.path_manager.sync_lock_file(), &options).await
+    }
+
     pub fn enabled_sources(&self) -> Vec<&str> {
         // Return sources in source_preference order
         let mut sources = Vec::new();
@@ -194,25 +504,152 @@ impl SyncOrchestrator {
         sources
     }
 
-    #[instrument(skip(self))]
-    pub async fn sync(&mut self) -> Result<SyncResult> {
+    /// Collect, resolve and prepare every source's writes like `sync`, but send nothing.
+    /// The returned plan can be saved, reviewed and passed to [`Self::apply`].
+    pub async fn plan(&mut self) -> Result<(SyncPlan, SyncResult)> {
+        let all_sources = self.resolution_config.source_preference.iter()
+            .map(|s| s.to_lowercase())
+            .collect();
+        let dry_run_sources = std::mem::replace(&mut self.dry_run_sources, all_sources);
+        let result = self.sync().await;
+        self.dry_run_sources = dry_run_sources;
+        let result = result?;
+        Ok((SyncPlan::new(std::mem::take(&mut self.planned_sources)), result))
+    }
+
+    /// Send exactly the writes in `plan`, without collecting or resolving again.
+    /// A source that is not configured or fails to authenticate is skipped and reported.
+    #[instrument(skip(self, plan))]
+    pub async fn apply(&mut self, plan: &SyncPlan) -> Result<SyncResult> {
         let start = Instant::now();
         let mut errors = Vec::new();
+        let _lock = self.acquire_sync_lock().await?;
+        self.reset_source_outcomes();
 
-        info!(
-            operation = "sync_start",
-            sources = ?self.enabled_sources(),
-            "Starting sync operation (Collect → Resolve → Distribute)"
-        );
+        let path_manager = self.path_manager.clone();
+        let cache_manager = CacheManager::new(&path_manager)
+            .map_err(|e| anyhow::anyhow!("Failed to initialize cache manager: {}", e))?;
+        // Everything in the plan is sent; only data types a target can't receive are skipped
+        let plan_options = SyncOptions {
+            sync_watchlist: true,
+            sync_ratings: true,
+            sync_reviews: true,
+            sync_watch_history: true,
+            force_full_sync: self.sync_options.force_full_sync,
+            since: self.sync_options.since,
+            no_write: self.sync_options.no_write,
+            skip_unconfigured_sources: self.sync_options.skip_unconfigured_sources,
+            // Plans carry no lists, collections or show progress
+            sync_lists: false,
+            sync_collection: false,
+            sync_show_progress: false,
+            never_remove: self.sync_options.never_remove,
+            // A plan is sent as written
+            max_items: None,
+        };
+
+        // Targets don't read each other's writes, so every source's plan is sent at once
+        let this = &*self;
+        let source_futures = plan.sources.iter()
+            .filter(|source_plan| !source_plan.is_empty())
+            .map(|source_plan| {
+                this.apply_to_source(source_plan, &plan_options, &cache_manager)
+                    .instrument(Self::source_span(&source_plan.source))
+            });
+        let mut failures = Vec::new();
+        for (failed, source_errors) in join_all(source_futures).await {
+            failures.extend(failed);
+            errors.extend(source_errors);
+        }
+
+        self.save_failures(failures);
+        let items_synced = self.metrics.lock().unwrap().total_added();
+
+        Ok(SyncResult {
+            items_synced,
+            items_deferred: plan.sources.iter().map(|s| s.watchlist_deferred + s.deferred).sum(),
+            resolution_skipped: false,
+            duration: start.elapsed(),
+            errors,
+            sources: self.source_outcomes(),
+            per_source: self.run_metrics(),
+        })
+    }
+
+    /// Send one source's part of a plan: authenticate, then write through its
+    /// distribution strategy. Returns the writes that failed and the source's errors,
+    /// which are also recorded in its outcome.
+    async fn apply_to_source(
+        &self,
+        source_plan: &SourcePlan,
+        plan_options: &SyncOptions,
+        cache_manager: &CacheManager,
+    ) -> (Option<SourcePlan>, Vec<String>) {
+        let source_name = source_plan.source.as_str();
+        let source_arc = match self.find_source_index(source_name).and_then(|idx| self.sources.get(idx)) {
+            Some(source_arc) => source_arc.clone(),
+            None => {
+                let error = format!("Source '{}' in plan is not configured", source_name);
+                self.record_source_errors(source_name, std::slice::from_ref(&error), false);
+                return (None, vec![error]);
+            }
+        };
+
+        if let Err(e) = source_arc.write().await.as_mut().authenticate().await {
+            let error = format!("Failed to authenticate to {}: {}", source_name, e);
+            self.record_source_errors(source_name, std::slice::from_ref(&error), true);
+            return (None, vec![error]);
+        }
+
+        let strategy = match Self::create_distribution_strategy(source_name, cache_manager, None) {
+            Ok(strategy) => strategy,
+            Err(e) => {
+                let error = format!("Failed to create distribution strategy for {}: {}", source_name, e);
+                self.record_source_errors(source_name, std::slice::from_ref(&error), false);
+                return (None, vec![error]);
+            }
+        };
+        let sync_options = {
+            let source_guard = source_arc.read().await;
+            Self::gate_sync_options_for_source(plan_options, source_name, source_guard.supported_data_types())
+        };
+
+        let chunk_by_year = self.config_sync_options.as_ref()
+            .is_some_and(|opts| opts.watch_history.chunk_by_year);
+
+        info!(operation = "apply", source = source_name, items = source_plan.total_items(), "Applying plan to {}", source_name);
+        let errors_arc = Arc::new(tokio::sync::Mutex::new(Vec::<String>::new()));
+        let failed = Self::apply_source_plan(&source_arc, source_plan, &sync_options, chunk_by_year, strategy.as_ref(), cache_manager, &self.metrics, &errors_arc).await;
+        let source_errors = std::mem::take(&mut *errors_arc.lock().await);
+        self.record_source_errors(source_name, &source_errors, false);
+        (failed, source_errors)
+    }
 
-        // Authenticate sources in source_preference order (first source = fail-fast)
+    /// Update the failed-writes file for `sync --retry-failures` with the sources written to
+    fn save_failures(&self, attempted: Vec<SourcePlan>) {
+        if attempted.is_empty() {
+            return;
+        }
+        let failed: usize = attempted.iter().map(|plan| plan.total_items()).sum();
+        if failed > 0 {
+            info!("{} failed writes recorded; retry them with `totalrecall sync --retry-failures`", failed);
+        }
+        if let Err(e) = record_failures(&self.path_manager.cache_failures_file(), attempted) {
+            warn!("Failed to record failed writes: {}", e);
+        }
+    }
+
+    /// Authenticate sources in source_preference order. Failures are added to `errors`;
+    /// returns false when the first source fails, which stops the run.
+    async fn authenticate_sources(&self, errors: &mut Vec<String>) -> bool {
         for (idx, source_name) in self.resolution_config.source_preference.iter().enumerate() {
             let is_first = idx == 0;
             if let Some(source_index) = self.find_source_index(source_name) {
                 if let Some(source_arc) = self.sources.get(source_index) {
                     let mut source = source_arc.write().await;
-                    if let Err(e) = source.as_mut().authenticate().await {
+                    if let Err(e) = source.as_mut().authenticate().instrument(Self::source_span(source_name)).await {
                         let error_msg = format!("Failed to authenticate to {}: {}", source_name, e);
+                        self.record_source_errors(source_name, std::slice::from_ref(&error_msg), true);
                         errors.push(error_msg.clone());
                         tracing::error!(
                             operation = "auth",
@@ -223,23 +660,129 @@ impl SyncOrchestrator {
                             source_name
                         );
                         if is_first {
-                            return Ok(SyncResult {
-                                items_synced: 0,
-                                duration: start.elapsed(),
-                                errors,
-                            });
+                            return false;
                         }
                     }
                 } else {
-                    errors.push(format!("Source '{}' not found at index {}", source_name, source_index));
+                    let error = format!("Source '{}' not found at index {}", source_name, source_index);
+                    self.record_source_errors(source_name, std::slice::from_ref(&error), false);
+                    errors.push(error);
                 }
             } else {
-                errors.push(format!("Source '{}' not found in registry", source_name));
+                let error = format!("Source '{}' not found in registry", source_name);
+                self.record_source_errors(source_name, std::slice::from_ref(&error), false);
+                errors.push(error);
             }
         }
 
+        true
+    }
+
+    /// Collect and resolve like `sync`, then report how far each source is from the
+    /// resolved union. Nothing is distributed.
+    #[instrument(skip(self))]
+    pub async fn reconcile(&mut self) -> Result<(ReconcileReport, Vec<String>)> {
+        self.report_on_resolved(ReconcileReport { data_types: Vec::new() }, ReconcileReport::new).await
+    }
+
+    /// Collect and resolve like `sync`, then list what each source is missing and which
+    /// ratings it disagrees on. Nothing is distributed.
+    #[instrument(skip(self))]
+    pub async fn diff(&mut self) -> Result<(Vec<SourceDiff>, Vec<String>)> {
+        self.report_on_resolved(Vec::new(), diff_sources).await
+    }
+
+    /// Collect and resolve like `sync`, then put the resolved watch history and ratings in
+    /// Trakt's sync JSON. Nothing is distributed.
+    #[instrument(skip(self))]
+    pub async fn export_trakt(&mut self) -> Result<(TraktExport, Vec<String>)> {
+        self.report_on_resolved(TraktExport::default(), |_, resolved, _| TraktExport::from_resolved(resolved)).await
+    }
+
+    /// Collect and resolve, then build a report from each source's collected data, the data
+    /// types it is compared for and the resolved data. `empty` is returned when no source
+    /// could authenticate.
+    async fn report_on_resolved<R>(
+        &mut self,
+        empty: R,
+        report: impl FnOnce(&[(String, Vec<DataType>, &SourceData)], &ResolvedData, &[DataType]) -> R,
+    ) -> Result<(R, Vec<String>)> {
+        let mut errors = Vec::new();
+        let _lock = self.acquire_sync_lock().await?;
+        if !self.authenticate_sources(&mut errors).await {
+            return Ok((empty, errors));
+        }
+
+        let path_manager = self.path_manager.clone();
+        let cache_manager = Arc::new(CacheManager::new(&path_manager)
+            .map_err(|e| anyhow::anyhow!("Failed to initialize cache manager: {}", e))?);
+        let id_resolver = Arc::new(Mutex::new(IdResolver::new(
+            &path_manager.cache_id_dir(),
+            &self.sources,
+            self.id_resolver_config.clone(),
+        ).await.map_err(|e| anyhow::anyhow!("Failed to initialize ID resolver: {}", e))?));
+
+        let collected_data = self.collect_all_data(&mut errors, &cache_manager, &id_resolver).await
+            .map_err(|e| anyhow::anyhow!("Failed to collect data: {}", e))?;
+        let (resolved_data, _) = self.resolve_collected_data(&collected_data, &cache_manager).await;
+        if let Err(e) = id_resolver.lock().await.save_if_dirty() {
+            warn!("Failed to save ID resolver cache: {}", e);
+        }
+
+        let enabled: Vec<DataType> = DataType::ALL.into_iter()
+            .filter(|data_type| match data_type {
+                DataType::Watchlist => self.sync_options.sync_watchlist,
+                DataType::Ratings => self.sync_options.sync_ratings,
+                DataType::Reviews => self.sync_options.sync_reviews,
+                DataType::WatchHistory => self.sync_options.sync_watch_history,
+            })
+            .collect();
+        let mut sources = Vec::new();
+        for (source_name, data) in &collected_data.sources {
+            let Some(source_arc) = self.find_source_index(source_name).and_then(|idx| self.sources.get(idx)) else {
+                continue;
+            };
+            let supported = source_arc.read().await.supported_data_types().to_vec();
+            sources.push((source_name.clone(), supported, data));
+        }
+        let report = report(&sources, &resolved_data, &enabled);
+
+        for source_arc in &self.sources {
+            let mut source = source_arc.write().await;
+            if let Err(e) = source.as_mut().cleanup().await {
+                warn!("Failed to cleanup source {}: {}", source.source_name(), e);
+            }
+        }
+        Ok((report, errors))
+    }
+
+    #[instrument(skip(self))]
+    pub async fn sync(&mut self) -> Result<SyncResult> {
+        let start = Instant::now();
+        let mut errors = Vec::new();
+        let _lock = self.acquire_sync_lock().await?;
+        self.reset_source_outcomes();
+
+        info!(
+            operation = "sync_start",
+            sources = ?self.enabled_sources(),
+            "Starting sync operation (Collect → Resolve → Distribute)"
+        );
+
+        if !self.authenticate_sources(&mut errors).await {
+            return Ok(SyncResult {
+                items_synced: 0,
+                items_deferred: 0,
+                resolution_skipped: false,
+                duration: start.elapsed(),
+                errors,
+                sources: self.source_outcomes(),
+                per_source: self.run_metrics(),
+            });
+        }
+
         // PHASE 1: COLLECT - Fetch all data from all sources
-        let path_manager = PathManager::default();
+        let path_manager = self.path_manager.clone();
         let cache_manager = Arc::new(CacheManager::new(&path_manager)
             .map_err(|e| {
                 let error_msg = format!("Failed to initialize cache manager: {}", e);
@@ -247,11 +790,32 @@ impl SyncOrchestrator {
                 anyhow::anyhow!(error_msg)
             })?);
         
+        // With --from-hub, fail before collecting when there is no hub to distribute
+        let hub = if self.from_hub {
+            match Self::load_hub(&cache_manager).await {
+                Ok(hub) => Some(hub),
+                Err(e) => {
+                    errors.push(e.to_string());
+                    return Ok(SyncResult {
+                        items_synced: 0,
+                        items_deferred: 0,
+                        resolution_skipped: false,
+                        duration: start.elapsed(),
+                        errors,
+                        sources: self.source_outcomes(),
+                        per_source: self.run_metrics(),
+                    });
+                }
+            }
+        } else {
+            None
+        };
+        
         // Create ID resolver for resolving missing IDs (wrapped in Arc<Mutex<>> for thread-safe concurrent access)
         let id_resolver = Arc::new(Mutex::new(IdResolver::new(
             &path_manager.cache_id_dir(),
             &self.sources,
-            IdResolverConfig::default(),
+            self.id_resolver_config.clone(),
         ).await.map_err(|e| {
             let error_msg = format!("Failed to initialize ID resolver: {}", e);
             errors.push(error_msg.clone());
@@ -264,8 +828,12 @@ impl SyncOrchestrator {
                 errors.push(format!("Failed to collect data: {}", e));
                 return Ok(SyncResult {
                     items_synced: 0,
+                    items_deferred: 0,
+                    resolution_skipped: false,
                     duration: start.elapsed(),
                     errors,
+                    sources: self.source_outcomes(),
+                per_source: self.run_metrics(),
                 });
             }
         };
@@ -285,56 +853,55 @@ impl SyncOrchestrator {
                 data.reviews.len(),
                 data.watch_history.len()
             );
+            let fetched = [
+                (DataType::Watchlist, self.sync_options.sync_watchlist, data.watchlist.len()),
+                (DataType::Ratings, self.sync_options.sync_ratings, data.ratings.len()),
+                (DataType::Reviews, self.sync_options.sync_reviews, data.reviews.len()),
+                (DataType::WatchHistory, self.sync_options.sync_watch_history, data.watch_history.len()),
+            ];
+            for (data_type, enabled, len) in fetched {
+                if enabled {
+                    count(&self.metrics, name, data_type, |m| m.fetched += len);
+                }
+            }
         }
-        
-        // Normalize all ratings to 1-10 scale before resolution
-        // This ensures ratings from different sources are compared on the same scale
-        let mut normalized_source_data: Vec<(String, SourceData)> = Vec::new();
-        for (source_name, data) in &collected_data.sources {
-            // Find the source to get its normalizer
-            let source_index = self.find_source_index(source_name);
-            let normalized_ratings = if let Some(idx) = source_index {
-                if let Some(source_arc) = self.sources.get(idx) {
-                    let source_guard = source_arc.read().await;
-                    if let Some(normalizer) = source_guard.as_rating_normalization() {
-                        // Normalize each rating to 1-10 scale
-                        data.ratings.iter()
-                            .map(|r| {
-                                let normalized = normalizer.normalize_rating(r.rating as f64, 10);
-                                Rating { rating: normalized, ..r.clone() }
-                            })
-                            .collect()
-                    } else {
-                        // No normalizer - assume already 1-10 scale
-                        data.ratings.clone()
-                    }
-                } else {
-                    data.ratings.clone()
+
+        // The hub is already resolved; it never resolves against itself or the sources
+        let (mut resolved_data, resolution_skipped, mut report) = match hub {
+            Some(hub) => (hub, false, None),
+            None => {
+                let (resolved, report) = self.resolve_collected_data(&collected_data, &cache_manager).await;
+                if self.config_sync_options.as_ref().is_some_and(|options| options.hub.export) {
+                    Self::export_hub(&cache_manager, &resolved);
                 }
+                (resolved, report.is_none(), report)
+            }
+        };
+
+        if self.report_conflicts {
+            let sources: Vec<(&str, &SourceData)> = collected_data.sources.iter()
+                .map(|(name, data)| (name.as_str(), data))
+                .collect();
+            self.conflicts = review_conflicts(&sources);
+            report.get_or_insert_with(ResolutionReport::default).review_conflicts = self.conflicts.clone();
+        }
+        // A skipped resolution leaves the previous report, which came from the same inputs
+        if let Some(report) = &report {
+            let report_file = path_manager.cache_conflicts_file();
+            if let Err(e) = save_conflict_report(&report_file, report) {
+                warn!("{}", e);
             } else {
-                data.ratings.clone()
-            };
-            
-            normalized_source_data.push((
-                source_name.clone(),
-                SourceData {
-                    watchlist: data.watchlist.clone(),
-                    ratings: normalized_ratings,
-                    reviews: data.reviews.clone(),
-                    watch_history: data.watch_history.clone(),
-                }
-            ));
+                info!(
+                    "Wrote the conflict report to {} ({} rating, {} status and {} review conflicts, {} duplicate IDs)",
+                    report_file.display(),
+                    report.rating_conflicts.len(),
+                    report.status_conflicts.len(),
+                    report.review_conflicts.len(),
+                    report.duplicate_ids.len()
+                );
+            }
         }
         
-        let source_data_refs: Vec<(&str, &SourceData)> = normalized_source_data
-            .iter()
-            .map(|(name, data)| (name.as_str(), data))
-            .collect();
-        let mut resolved_data = crate::resolution::resolve_all_conflicts(
-            &source_data_refs,
-            &self.resolution_config,
-        );
-        
         // Log resolved data after resolution
         info!(
             "Resolved data counts: watchlist={}, ratings={}, reviews={}, watch_history={}",
@@ -350,12 +917,13 @@ impl SyncOrchestrator {
             warn!("Failed to save ID resolver cache after resolution phase: {}", e);
         }
 
-        // Advanced feature: Mark rated items as watched
+        // Advanced feature: Mark rated items as watched (watch_history.from_ratings)
         if let Some(ref config_sync_options) = self.config_sync_options {
-            if config_sync_options.mark_rated_as_watched && !resolved_data.ratings.is_empty() {
+            let from_ratings = config_sync_options.from_ratings_mode();
+            if from_ratings.is_enabled() && !resolved_data.ratings.is_empty() {
                 use std::collections::HashSet;
                 
-                info!("Running mark_rated_as_watched feature ({} resolved ratings available)", resolved_data.ratings.len());
+                info!("Creating watch history from ratings (mode={:?}, {} resolved ratings available)", from_ratings, resolved_data.ratings.len());
                 
                 // Build set of watched IMDB IDs from resolved watch history
                 use crate::diff::GetImdbId;
@@ -364,58 +932,70 @@ impl SyncOrchestrator {
                     .filter(|id| !id.is_empty()) // Filter out empty IDs
                     .collect();
                 
-                let mut items_marked = 0;
-                for rating in &resolved_data.ratings {
-                    // Skip shows (cannot be marked as watched on Trakt, and some sources have limitations)
-                    if matches!(rating.media_type, media_sync_models::media::MediaType::Show) {
-                        continue;
-                    }
-                    
-                    // Only mark if not already in watch history
-                    if !watched_ids.contains(&rating.imdb_id) {
-                        debug!(
-                            imdb_id = %rating.imdb_id,
-                            rating = rating.rating,
-                            media_type = ?rating.media_type,
-                            "Marking rated item as watched (mark_rated_as_watched feature)"
-                        );
-                        
-                        let history_item = WatchHistory {
-                            imdb_id: rating.imdb_id.clone(),
-                            ids: rating.ids.clone(),
-                            title: None,
-                            year: None,
-                            watched_at: rating.date_added,
-                            media_type: rating.media_type.clone(),
-                            source: "rated".to_string(),
-                        };
-                        
-                        resolved_data.watch_history.push(history_item);
-                        items_marked += 1;
-                    }
-                }
+                let marked = Self::watch_history_from_ratings(&resolved_data.ratings, &watched_ids, from_ratings);
+                let items_marked = marked.len();
+                resolved_data.watch_history.extend(marked);
                 
                 if items_marked > 0 {
-                    info!("Marked {} rated items as watched via mark_rated_as_watched feature (added to resolved watch history)", items_marked);
+                    info!("Marked {} rated items as watched (added to resolved watch history)", items_marked);
                     info!("Updated resolved watch history count: {} (was {})", 
                         resolved_data.watch_history.len(),
                         resolved_data.watch_history.len() - items_marked);
                 } else {
                     info!("No new items to mark as watched (all rated items already in watch history)");
                 }
-            } else if config_sync_options.mark_rated_as_watched {
-                info!("mark_rated_as_watched is enabled but no ratings are available to process");
+            } else if from_ratings.is_enabled() {
+                info!("watch_history.from_ratings is {:?} but no ratings are available to process", from_ratings);
             }
         }
 
         // PHASE 3: DISTRIBUTE - Push resolved data to all sources (filtered to only new/changed items)
-        let items_synced = match self.distribute_resolved_data(&resolved_data, &collected_data, &cache_manager, &mut errors).await {
-            Ok(count) => count,
+        // A run with the same inputs as one that had nothing to write has nothing to write either
+        let distribution_hash = if errors.is_empty() {
+            self.distribution_input_hash(&collected_data, &resolved_data)
+        } else {
+            None
+        };
+        let distribution_unchanged = distribution_hash.is_some() && cache_manager.load_distribution_hash() == distribution_hash;
+        let distributed = if distribution_unchanged {
+            info!(operation = "distribute", "Distribution skipped (cache unchanged since a run with nothing to write)");
+            self.planned_sources.clear();
+            Ok((0, 0))
+        } else {
+            self.distribute_resolved_data(&resolved_data, &collected_data, &cache_manager, &mut errors).await
+        };
+        let (items_synced, items_deferred) = match distributed {
+            Ok(counts) => counts,
             Err(e) => {
                 errors.push(format!("Failed to distribute data: {}", e));
-                0
+                (0, 0)
             }
         };
+        if !distribution_unchanged {
+            let nothing_to_write = errors.is_empty()
+                && items_deferred == 0
+                && self.planned_sources.iter().all(SourcePlan::is_empty);
+            let hash = distribution_hash.as_deref().filter(|_| nothing_to_write);
+            if let Err(e) = cache_manager.save_distribution_hash(hash) {
+                warn!("Failed to record distribution inputs: {}", e);
+            }
+        }
+
+        let items_synced = if self.sync_options.sync_lists {
+            items_synced + self.sync_lists(&mut errors).await
+        } else {
+            items_synced
+        };
+        let items_synced = if self.sync_options.sync_collection {
+            items_synced + self.sync_collection(&mut errors).await
+        } else {
+            items_synced
+        };
+        let items_synced = if self.sync_options.sync_show_progress {
+            items_synced + self.sync_show_progress(&mut errors).await
+        } else {
+            items_synced
+        };
 
         // Save ID resolver cache if dirty
         if let Err(e) = id_resolver.lock().await.save_if_dirty() {
@@ -427,6 +1007,7 @@ impl SyncOrchestrator {
             operation = "sync_complete",
             duration_ms = duration.as_millis(),
             items_synced = items_synced,
+            items_deferred = items_deferred,
             "Sync operation completed"
         );
 
@@ -436,17 +1017,170 @@ impl SyncOrchestrator {
             let mut source = source_arc.write().await;
             if let Err(e) = source.as_mut().cleanup().await {
                 warn!("Failed to cleanup source {}: {}", source.source_name(), e);
-                errors.push(format!("Failed to cleanup source {}: {}", source.source_name(), e));
+                let error = format!("Failed to cleanup source {}: {}", source.source_name(), e);
+                self.record_source_errors(source.source_name(), std::slice::from_ref(&error), false);
+                errors.push(error);
             }
         }
 
         Ok(SyncResult {
             items_synced,
+            items_deferred,
+            resolution_skipped,
             duration,
             errors,
+            sources: self.source_outcomes(),
+            per_source: self.run_metrics(),
         })
     }
     
+    /// Fingerprint of what distribution reads: every source's collected data and the
+    /// resolved data, both after ID resolution, the options and the crate version. None
+    /// when distribution has to run whatever the data says: with --force-full-sync,
+    /// --since, a dry run, age-based watchlist removals or buffered webhook events, none
+    /// of which the data reflects.
+    fn distribution_input_hash(&self, collected_data: &CollectedData, resolved_data: &ResolvedData) -> Option<String> {
+        use std::hash::{Hash, Hasher};
+        let remove_by_age = self.config_sync_options.as_ref()
+            .is_some_and(|opts| opts.remove_watchlist_items_older_than_days.is_some());
+        if self.sync_options.force_full_sync || self.sync_options.since.is_some() || !self.dry_run_sources.is_empty() || remove_by_age {
+            return None;
+        }
+
+        let event_buffer = EventBuffer::new(&self.path_manager);
+        let mut sources: Vec<&str> = collected_data.sources.iter().map(|(name, _)| name.as_str()).collect();
+        if self.from_hub {
+            sources.push(HUB_SOURCE);
+        }
+        sources.sort_unstable();
+        let nothing_to_merge = |events: BufferedEvents| {
+            (!self.sync_options.sync_watch_history || events.watch_history.is_empty())
+                && (!self.sync_options.sync_ratings || events.ratings.is_empty())
+        };
+        if sources.iter().any(|source| !event_buffer.load(source).is_ok_and(nothing_to_merge)) {
+            return None;
+        }
+
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        env!("CARGO_PKG_VERSION").hash(&mut hasher);
+        serde_json::to_string(&self.sync_options).unwrap_or_default().hash(&mut hasher);
+        serde_json::to_string(&self.config_sync_options).unwrap_or_default().hash(&mut hasher);
+        serde_json::to_string(&self.resolution_config).unwrap_or_default().hash(&mut hasher);
+        // The data after ID resolution, not the cache files: a changed ID cache or
+        // media type filter changes what is distributed without touching them
+        let collected: Vec<(&str, &SourceData)> = collected_data.sources.iter()
+            .map(|(name, data)| (name.as_str(), data))
+            .collect();
+        crate::resolution::resolution_input_hash(&collected, &self.resolution_config).hash(&mut hasher);
+        serde_json::to_string(resolved_data).unwrap_or_default().hash(&mut hasher);
+        Some(format!("{:016x}", hasher.finish()))
+    }
+
+    /// Turn off data types the source doesn't support, so collection and distribution
+    /// skip them instead of calling unsupported get/set methods and collecting errors
+    fn gate_sync_options_for_source(sync_options: &SyncOptions, source_name: &str, supported: &[DataType]) -> SyncOptions {
+        let mut gated = sync_options.clone();
+        let flags = [
+            (DataType::Watchlist, &mut gated.sync_watchlist),
+            (DataType::Ratings, &mut gated.sync_ratings),
+            (DataType::Reviews, &mut gated.sync_reviews),
+            (DataType::WatchHistory, &mut gated.sync_watch_history),
+        ];
+        for (data_type, enabled) in flags {
+            if *enabled && !supported.contains(&data_type) {
+                info!(source = source_name, data_type = %data_type, "Skipping {} for {} (not supported by source)", data_type, source_name);
+                *enabled = false;
+            }
+        }
+        gated
+    }
+
+    /// The removals to send to `source_name`: all of `removal_list`, or none when the
+    /// source can't remove or with `never_remove`, which are only logged
+    fn removals_to_send(removal_list: &[WatchlistItem], source_name: &str, accepts_removals: bool, sync_options: &SyncOptions) -> Vec<WatchlistItem> {
+        if !accepts_removals {
+            if !removal_list.is_empty() {
+                debug!("{} can't remove watchlist items; skipping {} removals", source_name, removal_list.len());
+            }
+            return Vec::new();
+        }
+        if sync_options.never_remove {
+            if !removal_list.is_empty() {
+                info!("Would remove {} items from {} watchlist, skipped due to never_remove", removal_list.len(), source_name);
+            }
+            return Vec::new();
+        }
+        removal_list.to_vec()
+    }
+
+    /// Apply `watchlist.max_items_to_add_per_run`, keeping the first `cap` additions.
+    /// Returns how many were deferred; they are still missing from the target, so the
+    /// next run picks them up again.
+    fn cap_watchlist_additions(items: &mut Vec<WatchlistItem>, cap: Option<usize>, source_name: &str) -> usize {
+        let cap = match cap {
+            Some(cap) if items.len() > cap => cap,
+            _ => return 0,
+        };
+        let deferred = items.len() - cap;
+        items.truncate(cap);
+        warn!(
+            source = source_name,
+            deferred = deferred,
+            "Watchlist additions to {} capped at {} (watchlist.max_items_to_add_per_run), deferring {} to later runs",
+            source_name, cap, deferred
+        );
+        deferred
+    }
+
+    /// Fit `plan` into the `remaining` writes of the run's `--max-items` budget and take
+    /// what it keeps from it. Returns the deferred writes; like capped watchlist additions
+    /// they are still missing from the target, so a later run sends them.
+    fn take_write_budget(plan: &mut SourcePlan, remaining: &mut usize, metrics: &std::sync::Mutex<MetricsAccumulator>) -> SourcePlan {
+        let dropped = plan.truncate_to(*remaining);
+        *remaining -= plan.total_items();
+        let deferred = dropped.total_items();
+        if deferred > 0 {
+            let mut metrics = metrics.lock().unwrap();
+            for (data_type, count) in dropped.counts_by_data_type() {
+                metrics.entry(&plan.source, data_type).skipped += count;
+            }
+            warn!(
+                source = plan.source.as_str(),
+                deferred = deferred,
+                "Write budget of this run (--max-items) used up, deferring {} writes to {} to later runs",
+                deferred, plan.source
+            );
+        }
+        dropped
+    }
+
+    /// Apply `exclude_tags`: find the IMDB IDs of watchlist items tagged with any of
+    /// `exclude_tags` in any source and drop those titles from every data type.
+    /// Returns the filtered data and the excluded IDs.
+    fn exclude_tagged_items(
+        resolved: &ResolvedData,
+        sources: &[(String, SourceData)],
+        exclude_tags: &[String],
+    ) -> (ResolvedData, std::collections::HashSet<String>) {
+        let excluded: std::collections::HashSet<String> = sources.iter()
+            .flat_map(|(_, data)| data.watchlist.iter())
+            .chain(resolved.watchlist.iter())
+            .filter(|item| !item.imdb_id.is_empty() && item.has_any_tag(exclude_tags))
+            .map(|item| item.imdb_id.clone())
+            .collect();
+
+        let mut filtered = resolved.clone();
+        if !excluded.is_empty() {
+            filtered.watchlist.retain(|item| !excluded.contains(&item.imdb_id));
+            filtered.ratings.retain(|item| !excluded.contains(&item.imdb_id));
+            filtered.reviews.retain(|item| !excluded.contains(&item.imdb_id));
+            filtered.watch_history.retain(|item| !excluded.contains(&item.imdb_id));
+            filtered.playback_progress.retain(|item| !excluded.contains(&item.imdb_id));
+            info!("Excluding {} tagged titles from distribution (exclude_tags: {})", excluded.len(), exclude_tags.join(", "));
+        }
+        (filtered, excluded)
+    }
+
     // Utility function for client-side timestamp filtering
     fn filter_by_timestamp<T>(
         items: Vec<T>,
@@ -478,7 +1212,158 @@ impl SyncOrchestrator {
         }
     }
 
+    /// Build watch history entries for rated items that have no watch history yet.
+    /// Used by both the generic pipeline and `sync_imdb` so they apply
+    /// `watch_history.from_ratings` identically: shows, placeholder episodes and items
+    /// without an IMDB ID are never marked, and each IMDB ID is marked at most once. A
+    /// marked episode keeps its season and episode numbers.
+    fn watch_history_from_ratings(
+        ratings: &[Rating],
+        watched_ids: &std::collections::HashSet<String>,
+        mode: media_sync_config::FromRatingsMode,
+    ) -> Vec<WatchHistory> {
+        let mut marked_ids = std::collections::HashSet::new();
+        let mut history = Vec::new();
+        for rating in ratings {
+            if rating.imdb_id.is_empty() || !mode.applies_to(&rating.media_type) {
+                continue;
+            }
+            if watched_ids.contains(&rating.imdb_id) || !marked_ids.insert(rating.imdb_id.clone()) {
+                continue;
+            }
+            debug!(
+                imdb_id = %rating.imdb_id,
+                rating = rating.rating,
+                media_type = ?rating.media_type,
+                "Marking rated item as watched (watch_history.from_ratings)"
+            );
+            history.push(WatchHistory {
+                imdb_id: rating.imdb_id.clone(),
+                ids: rating.ids.clone(),
+                title: None,
+                year: None,
+                watched_at: rating.date_added,
+                media_type: rating.media_type.clone(),
+                source: "rated".to_string(),
+                watched_percent: None,
+                show_ids: None,
+            });
+        }
+        history
+    }
+
+    /// Whether the source's last fetch of `data_type` returned only what changed since the
+    /// previous sync (e.g. Simkl's `date_from` fetches)
+    async fn fetched_changes_only(client: &Arc<RwLock<Box<dyn MediaSource<Error = SourceError>>>>, data_type: DataType) -> bool {
+        let mut source_guard = client.write().await;
+        source_guard.as_mut().as_incremental_sync()
+            .is_some_and(|incremental| incremental.fetched_changes_only(data_type))
+    }
+
+    /// Tell the source whether the collect cache holds its `data_type`, before fetching it
+    async fn set_cached(client: &Arc<RwLock<Box<dyn MediaSource<Error = SourceError>>>>, cache_manager: &CacheManager, source: &str, data_type: DataType, file: &str) {
+        let cached = cache_manager.cache_exists(source, file);
+        let mut source_guard = client.write().await;
+        if let Some(incremental) = source_guard.as_mut().as_incremental_sync() {
+            incremental.set_cached(data_type, cached);
+        }
+    }
+
+    /// Tell the source its last fetch of `data_type` is saved to the collect cache
+    async fn commit_fetch(client: &Arc<RwLock<Box<dyn MediaSource<Error = SourceError>>>>, data_type: DataType) {
+        let mut source_guard = client.write().await;
+        if let Some(incremental) = source_guard.as_mut().as_incremental_sync() {
+            incremental.commit_fetch(data_type);
+        }
+    }
+
+    /// Merge a changes-only fetch into the cached full list: a changed item replaces the
+    /// cached item with the same key, anything else is added. Items without a key are
+    /// always added.
+    fn merge_changes<T>(cached: Vec<T>, changes: Vec<T>, key: impl Fn(&T) -> Option<String>) -> Vec<T> {
+        let changed_keys: std::collections::HashSet<String> = changes.iter().filter_map(&key).collect();
+        cached.into_iter()
+            .filter(|item| key(item).is_none_or(|k| !changed_keys.contains(&k)))
+            .chain(changes)
+            .collect()
+    }
+
+    /// Key for `merge_changes`: the IMDB ID, or the source's own ID when there is none
+    fn change_key(imdb_id: &str, ids: &Option<MediaIds>) -> Option<String> {
+        if !imdb_id.is_empty() {
+            return Some(imdb_id.to_string());
+        }
+        ids.as_ref().and_then(|ids| {
+            ids.simkl_id.map(|id| format!("simkl:{}", id))
+                .or_else(|| ids.trakt_id.map(|id| format!("trakt:{}", id)))
+        })
+    }
+
+    /// Date the ratings a source returned without one (`sync.ratings.default_date`), all with
+    /// the same date so resolution doesn't depend on when each one was parsed
+    fn date_undated_ratings(ratings: &mut [Rating], default_date: DefaultRatingDate, fetched_at: DateTime<Utc>) {
+        let date = match default_date {
+            DefaultRatingDate::SourceImportTime => fetched_at,
+            DefaultRatingDate::Epoch => Rating::UNDATED,
+        };
+        for rating in ratings.iter_mut().filter(|rating| rating.is_undated()) {
+            rating.date_added = date;
+        }
+    }
+
+    /// Ratings outside the source's native scale for their media type (or 0), which no
+    /// source reports and so can only come from a cache written with the wrong scale
+    fn implausible_ratings(ratings: &[Rating], scale: impl Fn(&MediaType) -> u8) -> usize {
+        ratings.iter()
+            .filter(|rating| rating.rating == 0 || rating.rating > scale(&rating.media_type))
+            .count()
+    }
+
+    /// Check `source`'s cached ratings once per version (every run with `--revalidate-cache`)
+    /// and discard them when any is out of scale, so they are fetched again in full instead
+    /// of a mis-scaled cache being merged into and distributed. Returns whether they were discarded.
+    async fn discard_misscaled_ratings(
+        client: &Arc<RwLock<Box<dyn MediaSource<Error = SourceError>>>>,
+        cache_manager: &CacheManager,
+        source: &str,
+        revalidate: bool,
+    ) -> bool {
+        if !revalidate && cache_manager.ratings_validated(source) {
+            return false;
+        }
+        let Ok(Some(cached)) = cache_manager.load_ratings(source) else {
+            return false;
+        };
+        let implausible = {
+            let source_guard = client.read().await;
+            match source_guard.as_rating_normalization() {
+                Some(normalizer) => Self::implausible_ratings(&cached, |media_type| normalizer.native_rating_scale(media_type)),
+                None => Self::implausible_ratings(&cached, |_| 10),
+            }
+        };
+        let discarded = implausible > 0;
+        if discarded {
+            warn!(
+                "{} of {} cached {} ratings are outside its rating scale (likely cached by an older version); fetching {} ratings fresh",
+                implausible, cached.len(), source, source
+            );
+            if let Err(e) = cache_manager.discard_source_data(source, "ratings") {
+                warn!("{}", e);
+                return false;
+            }
+        } else {
+            debug!("Cached {} ratings are within its rating scale", source);
+        }
+        // The fresh fetch is trusted, so a source that really reports such ratings isn't refetched every run
+        if let Err(e) = cache_manager.mark_ratings_validated(source) {
+            warn!("{}", e);
+        }
+        discarded
+    }
+
     // Helper functions to fetch or load from cache (shared between collect_all_data and sync_imdb)
+    // These are the collection boundary: every item's IMDB IDs are canonicalized here,
+    // before caching, so diffing and resolution never see differently formatted IDs.
     async fn fetch_or_cache_watchlist(
         client: Arc<RwLock<Box<dyn MediaSource<Error = SourceError>>>>,
         cache_manager: &Arc<CacheManager>,
@@ -489,18 +1374,20 @@ impl SyncOrchestrator {
     ) -> Vec<WatchlistItem> {
         if use_cache.contains(&source.to_lowercase()) {
             // When using cache, only use cache - never fetch from API
-            if let Ok(Some(cached)) = cache_manager.load_watchlist(source) {
+            if let Ok(Some(mut cached)) = cache_manager.load_watchlist(source) {
+                cached.iter_mut().for_each(|item| item.normalize_ids());
                 return cached;
             }
             // Cache miss with use_cache: return empty (testing mode, no upstream fetch)
             warn!("Cache miss for {} watchlist with --use-cache enabled, returning empty list", source);
             return Vec::new();
         }
+        Self::set_cached(&client, cache_manager, source, DataType::Watchlist, "watchlist").await;
         // Normal mode: fetch from API and save to cache
         // Cache ALL data to maintain complete upstream state for accurate filtering
         // Call get_watchlist on trait object - handle Error type by converting to string
         let source_guard = client.read().await;
-        let data = match source_guard.get_watchlist().await {
+        let mut data = match source_guard.get_watchlist().await {
             Ok(data) => data,
             Err(e) => {
                 errors.lock().await.push(format!("Failed to fetch {} watchlist: {}", source, e));
@@ -508,10 +1395,16 @@ impl SyncOrchestrator {
             }
         };
         drop(source_guard);
+        data.iter_mut().for_each(|item| item.normalize_ids());
+        if Self::fetched_changes_only(&client, DataType::Watchlist).await {
+            let cached = cache_manager.load_watchlist(source).ok().flatten().unwrap_or_default();
+            data = Self::merge_changes(cached, data, |item| Self::change_key(&item.imdb_id, &item.ids));
+        }
         
         // Save complete data to cache (no filtering - cache represents full upstream state)
-        if let Err(e) = cache_manager.save_watchlist(source, &data) {
-            warn!("Failed to save {} watchlist to cache: {}", source, e);
+        match cache_manager.save_watchlist(source, &data) {
+            Ok(()) => Self::commit_fetch(&client, DataType::Watchlist).await,
+            Err(e) => warn!("Failed to save {} watchlist to cache: {}", source, e),
         }
         data
     }
@@ -522,21 +1415,25 @@ impl SyncOrchestrator {
         source: &str,
         use_cache: &std::collections::HashSet<String>,
         force_full_sync: bool,
+        default_date: DefaultRatingDate,
         errors: Arc<tokio::sync::Mutex<Vec<String>>>,
     ) -> Vec<Rating> {
         if use_cache.contains(&source.to_lowercase()) {
             // When using cache, only use cache - never fetch from API
-            if let Ok(Some(cached)) = cache_manager.load_ratings(source) {
+            if let Ok(Some(mut cached)) = cache_manager.load_ratings(source) {
+                cached.iter_mut().for_each(|item| item.normalize_ids());
                 return cached;
             }
             // Cache miss with use_cache: return empty (testing mode, no upstream fetch)
             warn!("Cache miss for {} ratings with --use-cache enabled, returning empty list", source);
             return Vec::new();
         }
+        Self::set_cached(&client, cache_manager, source, DataType::Ratings, "ratings").await;
         // Normal mode: fetch from API and save to cache
         // Cache ALL data to maintain complete upstream state for accurate filtering
         let source_guard = client.read().await;
-        let data = match source_guard.get_ratings().await {
+        let fetched_at = Utc::now();
+        let mut data = match source_guard.get_ratings().await {
             Ok(data) => data,
             Err(e) => {
                 errors.lock().await.push(format!("Failed to fetch {} ratings: {}", source, e));
@@ -544,16 +1441,23 @@ impl SyncOrchestrator {
             }
         };
         drop(source_guard);
+        data.iter_mut().for_each(|item| item.normalize_ids());
+        // Before caching, so runs reusing the cache see the same dates
+        Self::date_undated_ratings(&mut data, default_date, fetched_at);
+        if Self::fetched_changes_only(&client, DataType::Ratings).await {
+            let cached = cache_manager.load_ratings(source).ok().flatten().unwrap_or_default();
+            data = Self::merge_changes(cached, data, |rating| Self::change_key(&rating.imdb_id, &rating.ids));
+        }
         
         // Save complete data to cache (no filtering - cache represents full upstream state)
-        if let Err(e) = cache_manager.save_ratings(source, &data) {
-            warn!("Failed to save {} ratings to cache: {}", source, e);
+        match cache_manager.save_ratings(source, &data) {
+            Ok(()) => Self::commit_fetch(&client, DataType::Ratings).await,
+            Err(e) => warn!("Failed to save {} ratings to cache: {}", source, e),
         }
         
         // For IMDB, also generate CSV file from collected data
         if source.to_lowercase() == "imdb" && !data.is_empty() {
-            let path_manager = PathManager::default();
-            let csv_dir = path_manager.cache_csv_dir("imdb");
+            let csv_dir = cache_manager.paths().cache_csv_dir("imdb");
             if let Err(e) = std::fs::create_dir_all(&csv_dir) {
                 warn!("Failed to create CSV directory {:?}: {}", csv_dir, e);
             } else {
@@ -579,7 +1483,8 @@ impl SyncOrchestrator {
     ) -> Vec<Review> {
         if use_cache.contains(&source.to_lowercase()) {
             // When using cache, only use cache - never fetch from API
-            if let Ok(Some(cached)) = cache_manager.load_reviews(source) {
+            if let Ok(Some(mut cached)) = cache_manager.load_reviews(source) {
+                cached.iter_mut().for_each(|item| item.normalize_ids());
                 return cached;
             }
             // Cache miss with use_cache: return empty (testing mode, no upstream fetch)
@@ -588,7 +1493,7 @@ impl SyncOrchestrator {
         }
         // Normal mode: fetch from API and save to cache
         let source_guard = client.read().await;
-        let data = match source_guard.get_reviews().await {
+        let mut data = match source_guard.get_reviews().await {
             Ok(data) => data,
             Err(e) => {
                 errors.lock().await.push(format!("Failed to fetch {} reviews: {}", source, e));
@@ -596,6 +1501,7 @@ impl SyncOrchestrator {
             }
         };
         drop(source_guard);
+        data.iter_mut().for_each(|item| item.normalize_ids());
         if let Err(e) = cache_manager.save_reviews(source, &data) {
             warn!("Failed to save {} reviews to cache: {}", source, e);
         }
@@ -612,20 +1518,21 @@ impl SyncOrchestrator {
     ) -> Vec<WatchHistory> {
         if use_cache.contains(&source.to_lowercase()) {
             // When using cache, only use cache - never fetch from API
-            if let Ok(Some(cached)) = cache_manager.load_watch_history(source) {
+            if let Ok(Some(mut cached)) = cache_manager.load_watch_history(source) {
+                cached.iter_mut().for_each(|item| item.normalize_ids());
                 return cached;
             }
             
             // Cache miss: For IMDB, try to regenerate from CSV if available
             if source.to_lowercase() == "imdb" {
-                let path_manager = PathManager::default();
-                let cache_dir = path_manager.cache_dir();
+                let cache_dir = cache_manager.paths().cache_dir();
                 let csv_path = cache_dir.join("imdb_checkins.csv");
                 
                 if csv_path.exists() {
                     info!("IMDB watch history cache miss, regenerating from CSV: {:?}", csv_path);
                     match media_sync_sources::imdb::parser::parse_checkins_csv(&csv_path) {
-                        Ok(history) => {
+                        Ok(mut history) => {
+                            history.iter_mut().for_each(|item| item.normalize_ids());
                             info!("Regenerated {} IMDB watch history items from CSV", history.len());
                             // Save to JSON cache for next time
                             if let Err(e) = cache_manager.save_watch_history(source, &history) {
@@ -647,10 +1554,11 @@ impl SyncOrchestrator {
             warn!("Cache miss for {} watch history with --use-cache enabled, returning empty list", source);
             return Vec::new();
         }
+        Self::set_cached(&client, cache_manager, source, DataType::WatchHistory, "watch_history").await;
         // Normal mode: fetch from API and save to cache
         // Cache ALL data to maintain complete upstream state for accurate filtering
         let source_guard = client.read().await;
-        let data = match source_guard.get_watch_history().await {
+        let mut data = match source_guard.get_watch_history().await {
             Ok(data) => data,
             Err(e) => {
                 errors.lock().await.push(format!("Failed to fetch {} watch history: {}", source, e));
@@ -658,152 +1566,370 @@ impl SyncOrchestrator {
             }
         };
         drop(source_guard);
+        data.iter_mut().for_each(|item| item.normalize_ids());
+        if Self::fetched_changes_only(&client, DataType::WatchHistory).await {
+            let cached = cache_manager.load_watch_history(source).ok().flatten().unwrap_or_default();
+            data = Self::merge_changes(cached, data, |history| {
+                Self::change_key(&history.imdb_id, &history.ids)
+                    .map(|k| format!("{}|{:?}|{}", k, history.media_type, history.watched_at.timestamp()))
+            });
+        }
         
         // Save complete data to cache (no filtering - cache represents full upstream state)
-        if let Err(e) = cache_manager.save_watch_history(source, &data) {
-            warn!("Failed to save {} watch history to cache: {}", source, e);
+        match cache_manager.save_watch_history(source, &data) {
+            Ok(()) => Self::commit_fetch(&client, DataType::WatchHistory).await,
+            Err(e) => warn!("Failed to save {} watch history to cache: {}", source, e),
+        }
+        data
+    }
+
+    /// Normalize the collected ratings and resolve conflicts across sources, reusing last
+    /// run's resolution when its inputs are unchanged. Returns the resolved data and the
+    /// resolution's conflict report, which is None when resolution was skipped.
+    async fn resolve_collected_data(&self, collected_data: &CollectedData, cache_manager: &CacheManager) -> (ResolvedData, Option<ResolutionReport>) {
+        // Normalize all ratings to 1-10 scale before resolution
+        // This ensures ratings from different sources are compared on the same scale
+        let mut normalized_source_data: Vec<(String, SourceData)> = Vec::new();
+        for (source_name, data) in &collected_data.sources {
+            // Find the source to get its normalizer
+            let source_index = self.find_source_index(source_name);
+            let normalized_ratings = if let Some(idx) = source_index {
+                if let Some(source_arc) = self.sources.get(idx) {
+                    let source_guard = source_arc.read().await;
+                    if let Some(normalizer) = source_guard.as_rating_normalization() {
+                        // Normalize each rating to 1-10 scale
+                        data.ratings.iter()
+                            .map(|r| {
+                                // Unrounded, so Plex's 4.5 of 5 stars stays 9
+                                let mut normalized = r.clone();
+                                normalized.set_value(normalizer.normalize_rating_f64(r.value(), 10, &r.media_type));
+                                normalized
+                            })
+                            .collect()
+                    } else {
+                        // No normalizer - assume already 1-10 scale
+                        data.ratings.clone()
+                    }
+                } else {
+                    data.ratings.clone()
+                }
+            } else {
+                data.ratings.clone()
+            };
+            
+            let mut contributed = SourceData {
+                watchlist: data.watchlist.clone(),
+                ratings: normalized_ratings,
+                reviews: data.reviews.clone(),
+                watch_history: data.watch_history.clone(),
+            };
+            // Only what the source contributes is filtered; its collected data stays whole,
+            // so writes to it are still diffed against everything it has
+            if let Some(media_types) = self.media_types.get(source_name) {
+                let dropped = contributed.retain_media_types(media_types);
+                if dropped > 0 {
+                    info!(source = %source_name, "Left {} items of media types not in sources.media_types.{} out of resolution", dropped, source_name);
+                }
+            }
+            normalized_source_data.push((source_name.clone(), contributed));
+        }
+        
+        let source_data_refs: Vec<(&str, &SourceData)> = normalized_source_data
+            .iter()
+            .map(|(name, data)| (name.as_str(), data))
+            .collect();
+
+        // Reuse last run's resolution when the collected data and resolution config are unchanged
+        let input_hash = crate::resolution::resolution_input_hash(&source_data_refs, &self.resolution_config);
+        // --report-conflicts wants this run's decisions, so it always resolves
+        let cached_resolution = if self.sync_options.force_full_sync || self.report_conflicts {
+            None
+        } else {
+            cache_manager.load_resolved(&input_hash)
+        };
+        match cached_resolution {
+            Some(resolved) => {
+                info!(operation = "resolve", "Resolution skipped (unchanged inputs since last run)");
+                (resolved, None)
+            }
+            None => {
+                let (resolved, report) = crate::resolution::preview(
+                    &source_data_refs,
+                    &self.resolution_config,
+                );
+                if let Err(e) = cache_manager.save_resolved(&input_hash, &resolved) {
+                    warn!("Failed to cache resolved data: {}", e);
+                }
+                (resolved, Some(report))
+            }
         }
-        data
+    }
+
+    /// Save resolved data to the collect cache as the synthetic hub source
+    fn export_hub(cache_manager: &CacheManager, resolved: &ResolvedData) {
+        let results = [
+            cache_manager.save_watchlist(HUB_SOURCE, &resolved.watchlist),
+            cache_manager.save_ratings(HUB_SOURCE, &resolved.ratings),
+            cache_manager.save_reviews(HUB_SOURCE, &resolved.reviews),
+            cache_manager.save_watch_history(HUB_SOURCE, &resolved.watch_history),
+        ];
+        match results.into_iter().find_map(|result| result.err()) {
+            Some(e) => warn!("Failed to export resolved data as the {} source: {}", HUB_SOURCE, e),
+            None => info!(operation = "hub_export", "Exported resolved data as the {} source", HUB_SOURCE),
+        }
+    }
+
+    /// Load the hub exported by an earlier sync as resolved data
+    async fn load_hub(cache_manager: &CacheManager) -> Result<ResolvedData> {
+        let hub = FileSource::new(HUB_SOURCE, cache_manager.collect_source_dir(HUB_SOURCE));
+        if !hub.exists() {
+            return Err(anyhow::anyhow!(
+                "No {} source in the cache; run a sync with sync.hub.export = true first",
+                HUB_SOURCE
+            ));
+        }
+        let resolved = ResolvedData {
+            watchlist: hub.get_watchlist().await?,
+            ratings: hub.get_ratings().await?,
+            reviews: hub.get_reviews().await?,
+            watch_history: hub.get_watch_history().await?,
+            playback_progress: Vec::new(),
+        };
+        info!(operation = "resolve", "Using the {} source instead of resolving", HUB_SOURCE);
+        Ok(resolved)
+    }
+
+    /// Span around one source's work, so `--verbose-source <name>` can raise the
+    /// log level for everything logged inside it
+    fn source_span(source_name: &str) -> tracing::Span {
+        info_span!("source", source = %source_name)
     }
 
     async fn collect_all_data(&mut self, errors: &mut Vec<String>, cache_manager: &Arc<CacheManager>, id_resolver: &Arc<Mutex<IdResolver>>) -> Result<CollectedData> {
-        // Use thread-safe error collection
-        let errors_arc = Arc::new(tokio::sync::Mutex::new(Vec::<String>::new()));
+        // Events pushed between syncs (e.g. Plex webhooks) are merged into each source's data
+        let event_buffer = EventBuffer::new(&self.path_manager);
         
-        // Collect from all sources concurrently
-        let collection_futures: Vec<_> = self.resolution_config.source_preference
+        // Fetch from all sources concurrently, started in fetch order
+        let collection_order = self.resolution_config.collection_order();
+        let phase = Phase::start(&self.progress, &self.sources, "Collecting", collection_order.len()).await;
+        let collection_futures: Vec<_> = collection_order
             .iter()
             .map(|source_name| {
                 let source_name = source_name.clone();
                 let source_index = self.find_source_index(&source_name);
                 let sources = self.sources.clone();
                 let sync_options = self.sync_options.clone();
-                let use_cache = self.use_cache.clone();
+                let mut use_cache = self.use_cache.clone();
+                let revalidate_cache = self.revalidate_cache;
+                let default_rating_date = self.config_sync_options.as_ref()
+                    .map(|o| o.ratings.default_date)
+                    .unwrap_or_default();
                 let cache_manager = cache_manager.clone();
-                let errors_arc = errors_arc.clone();
-                let id_resolver = id_resolver.clone();
+                let event_buffer = event_buffer.clone();
+                let progress = self.progress.clone();
+                let collect_timeout = self.config_sync_options.as_ref()
+                    .and_then(|o| o.collect_timeout_secs)
+                    .map(std::time::Duration::from_secs);
+                let span = Self::source_span(&source_name);
                 
                 async move {
-                    let source_index = match source_index {
-                        Some(idx) => idx,
-                        None => {
-                            errors_arc.lock().await.push(format!("Source '{}' not found in registry", source_name));
-                            return Err(anyhow::anyhow!("Source '{}' not found", source_name));
-                        }
-                    };
-                    
-                    // Handle sources that support incremental sync
-                    if let Some(source_arc) = sources.get(source_index) {
-                        Self::set_force_full_sync_for_source(&sources, source_index, sync_options.force_full_sync).await;
-                    }
+                    // Errors are kept per source so the run can report each source's status
+                    let errors_arc = Arc::new(tokio::sync::Mutex::new(Vec::<String>::new()));
+                    let collected = async {
+                        let source_index = match source_index {
+                            Some(idx) => idx,
+                            None => {
+                                errors_arc.lock().await.push(format!("Source '{}' not found in registry", source_name));
+                                return Err(anyhow::anyhow!("Source '{}' not found", source_name));
+                            }
+                        };
                     
-                    // Get the source for data collection
-                    let source_arc = match sources.get(source_index) {
-                        Some(s) => s.clone(),
-                        None => {
-                            errors_arc.lock().await.push(format!("Source '{}' not found at index {}", source_name, source_index));
-                            return Err(anyhow::anyhow!("Source '{}' not found at index", source_name));
+                        // Handle sources that support incremental sync. A changes-only fetch is
+                        // merged into the cached list, so without one the source fetches in full.
+                        if let Some(source_arc) = sources.get(source_index) {
+                            // A discarded ratings cache is missing below, so the source fetches in full
+                            if sync_options.sync_ratings
+                                && Self::discard_misscaled_ratings(source_arc, &cache_manager, &source_name, revalidate_cache).await
+                            {
+                                use_cache.remove(&source_name.to_lowercase());
+                            }
+                            let cache_missing = [
+                                (sync_options.sync_watchlist, "watchlist"),
+                                (sync_options.sync_ratings, "ratings"),
+                                (sync_options.sync_watch_history, "watch_history"),
+                            ]
+                            .iter()
+                            .any(|(enabled, data_type)| *enabled && !cache_manager.cache_exists(&source_name, data_type));
+                            Self::set_force_full_sync_for_source(&sources, source_index, sync_options.force_full_sync || cache_missing).await;
                         }
-                    };
                     
-                    // Fetch all data types concurrently within this source
-                    let (watchlist_result, ratings_result, reviews_result, watch_history_result) = futures::try_join!(
-                        async {
-                            if sync_options.sync_watchlist {
-                                Ok::<_, anyhow::Error>(Self::fetch_or_cache_watchlist(
-                                    source_arc.clone(),
-                                    &cache_manager,
-                                    &source_name,
-                                    &use_cache,
-                                    sync_options.force_full_sync,
-                                    errors_arc.clone(),
-                                ).await)
-                            } else {
-                                Ok(Vec::new())
-                            }
-                        },
-                        async {
-                            if sync_options.sync_ratings {
-                                Ok::<_, anyhow::Error>(Self::fetch_or_cache_ratings(
-                                    source_arc.clone(),
-                                    &cache_manager,
-                                    &source_name,
-                                    &use_cache,
-                                    sync_options.force_full_sync,
-                                    errors_arc.clone(),
-                                ).await)
-                            } else {
-                                Ok(Vec::new())
+                        // Get the source for data collection
+                        let source_arc = match sources.get(source_index) {
+                            Some(s) => s.clone(),
+                            None => {
+                                errors_arc.lock().await.push(format!("Source '{}' not found at index {}", source_name, source_index));
+                                return Err(anyhow::anyhow!("Source '{}' not found at index", source_name));
                             }
-                        },
-                        async {
-                            if sync_options.sync_reviews {
-                                Ok::<_, anyhow::Error>(Self::fetch_or_cache_reviews(
-                                    source_arc.clone(),
-                                    &cache_manager,
-                                    &source_name,
-                                    &use_cache,
-                                    sync_options.force_full_sync,
-                                    errors_arc.clone(),
-                                ).await)
-                            } else {
-                                Ok(Vec::new())
-                            }
-                        },
-                        async {
-                            if sync_options.sync_watch_history {
-                                Ok::<_, anyhow::Error>(Self::fetch_or_cache_watch_history(
-                                    source_arc.clone(),
-                                    &cache_manager,
-                                    &source_name,
-                                    &use_cache,
-                                    sync_options.force_full_sync,
-                                    errors_arc.clone(),
-                                ).await)
-                            } else {
-                                Ok(Vec::new())
+                        };
+                    
+                        // Only collect data types this source supports
+                        let sync_options = {
+                            let source_guard = source_arc.read().await;
+                            Self::gate_sync_options_for_source(&sync_options, &source_name, source_guard.supported_data_types())
+                        };
+                    
+                        // Fetch all data types concurrently within this source
+                        let (watchlist_result, ratings_result, reviews_result, watch_history_result) = futures::try_join!(
+                            async {
+                                if sync_options.sync_watchlist {
+                                    Ok::<_, anyhow::Error>(Self::fetch_or_cache_watchlist(
+                                        source_arc.clone(),
+                                        &cache_manager,
+                                        &source_name,
+                                        &use_cache,
+                                        sync_options.force_full_sync,
+                                        errors_arc.clone(),
+                                    ).await)
+                                } else {
+                                    Ok(Vec::new())
+                                }
+                            },
+                            async {
+                                if sync_options.sync_ratings {
+                                    Ok::<_, anyhow::Error>(Self::fetch_or_cache_ratings(
+                                        source_arc.clone(),
+                                        &cache_manager,
+                                        &source_name,
+                                        &use_cache,
+                                        sync_options.force_full_sync,
+                                        default_rating_date,
+                                        errors_arc.clone(),
+                                    ).await)
+                                } else {
+                                    Ok(Vec::new())
+                                }
+                            },
+                            async {
+                                if sync_options.sync_reviews {
+                                    Ok::<_, anyhow::Error>(Self::fetch_or_cache_reviews(
+                                        source_arc.clone(),
+                                        &cache_manager,
+                                        &source_name,
+                                        &use_cache,
+                                        sync_options.force_full_sync,
+                                        errors_arc.clone(),
+                                    ).await)
+                                } else {
+                                    Ok(Vec::new())
+                                }
+                            },
+                            async {
+                                if sync_options.sync_watch_history {
+                                    Ok::<_, anyhow::Error>(Self::fetch_or_cache_watch_history(
+                                        source_arc.clone(),
+                                        &cache_manager,
+                                        &source_name,
+                                        &use_cache,
+                                        sync_options.force_full_sync,
+                                        errors_arc.clone(),
+                                    ).await)
+                                } else {
+                                    Ok(Vec::new())
+                                }
                             }
-                        }
-                    )?;
+                        )?;
                     
-                    let mut source_data = SourceData {
-                        watchlist: watchlist_result,
-                        ratings: ratings_result,
-                        reviews: reviews_result,
-                        watch_history: watch_history_result,
-                    };
+                        let mut source_data = SourceData {
+                            watchlist: watchlist_result,
+                            ratings: ratings_result,
+                            reviews: reviews_result,
+                            watch_history: watch_history_result,
+                        };
                     
-                    // Resolve IDs for items with empty imdb_id
-                    Self::resolve_missing_ids(&mut source_data, &id_resolver, &sources, &errors_arc).await;
+                        if let Err(e) = event_buffer.merge_into(&source_name, &mut source_data, sync_options.sync_watch_history, sync_options.sync_ratings) {
+                            errors_arc.lock().await.push(format!("Failed to merge buffered {} events: {}", source_name, e));
+                        }
                     
-                    Ok((source_name, source_data))
+                        Ok(source_data)
+                    };
+                    // A hung source is abandoned after sync.collect_timeout_secs so the others still sync
+                    let collected = match collect_timeout {
+                        Some(limit) => tokio::time::timeout(limit, collected).await
+                            .unwrap_or_else(|_| Err(anyhow::anyhow!("timed out after {}s", limit.as_secs()))),
+                        None => collected.await,
+                    };
+                    progress.advance(1);
+                    let source_errors = std::mem::take(&mut *errors_arc.lock().await);
+                    (source_name, collected, source_errors)
                 }
+                .instrument(span)
             })
             .collect();
         
         // Execute all collections concurrently
         let results = join_all(collection_futures).await;
+        drop(phase);
         
-        // Collect results and errors
+        // Collect results and errors. IDs are resolved one source at a time in fetch
+        // order, so sources early in fetch_order warm the ID cache for slower lookups later;
+        // only then is the data put in source_preference order.
         let mut source_data = Vec::new();
-        for result in results {
+        let _phase = Phase::start(&self.progress, &self.sources, "Resolving IDs", results.len()).await;
+        for (source_name, result, source_errors) in results {
+            let errors_arc = Arc::new(tokio::sync::Mutex::new(source_errors));
             match result {
-                Ok(data) => source_data.push(data),
+                Ok(mut data) => {
+                    Self::resolve_episode_checkins_for_source(&source_name, self.find_source_index(&source_name), &mut data, &self.sources)
+                        .instrument(Self::source_span(&source_name))
+                        .await;
+                    Self::resolve_missing_ids(&mut data, id_resolver, &self.sources, &errors_arc)
+                        .instrument(Self::source_span(&source_name))
+                        .await;
+                    source_data.push((source_name.clone(), data));
+                }
                 Err(e) => {
-                    errors_arc.lock().await.push(format!("Failed to collect data: {}", e));
+                    errors_arc.lock().await.push(format!("Failed to collect data from {}: {}", source_name, e));
                 }
             }
+            let source_errors = std::mem::take(&mut *errors_arc.lock().await);
+            self.record_source_errors(&source_name, &source_errors, false);
+            errors.extend(source_errors);
+            self.progress.advance(1);
         }
-        
-        // Merge errors back into main errors vector
-        let collected_errors = errors_arc.lock().await.clone();
-        errors.extend(collected_errors);
+        let preference = &self.resolution_config.source_preference;
+        source_data.sort_by_key(|(source_name, _)| preference.iter().position(|s| s == source_name));
         
         Ok(CollectedData {
             sources: source_data,
         })
     }
     
+    /// Upgrade show-level history to episodes when the source asks for it
+    /// (`imdb.resolve_episode_checkins`), using the first source with EpisodeLookup
+    async fn resolve_episode_checkins_for_source(
+        source_name: &str,
+        source_index: Option<usize>,
+        data: &mut SourceData,
+        sources: &[Arc<RwLock<Box<dyn MediaSource<Error = SourceError>>>>],
+    ) {
+        let Some(source_arc) = source_index.and_then(|idx| sources.get(idx)) else {
+            return;
+        };
+        if !source_arc.read().await.resolves_episode_checkins()
+            || !data.watch_history.iter().any(|item| item.media_type == media_sync_models::media::MediaType::Show)
+        {
+            return;
+        }
+        for lookup_arc in sources {
+            let lookup_guard = lookup_arc.read().await;
+            if let Some(lookup) = lookup_guard.as_episode_lookup() {
+                crate::episode_checkins::resolve_episode_checkins(&mut data.watch_history, lookup).await;
+                return;
+            }
+        }
+        warn!("Resolving {} episode check-ins needs a source that lists episodes (Trakt); leaving them as shows", source_name);
+    }
+
     /// Resolve missing IDs for items using IdResolver
     /// Always populates ids field, even when imdb_id exists
     async fn resolve_missing_ids(
@@ -975,6 +2101,20 @@ impl SyncOrchestrator {
                         // Always cache the merged result (collected + cached)
                         rating.ids = Some(resolved_ids.clone());
                         id_resolver.lock().await.cache_ids(resolved_ids);
+                    } else if let Some(title) = resolved_ids.title.clone() {
+                        // Only a title (e.g. Letterboxd): look the IDs up like a watchlist item
+                        match id_resolver.lock().await.resolve_ids_for_item(sources, &title, resolved_ids.year, &rating.media_type, None).await {
+                            Ok((ids, _)) if !ids.is_empty() => {
+                                resolved_ids.merge(&ids);
+                                if let Some(imdb) = resolved_ids.imdb_id.clone() {
+                                    rating.imdb_id = imdb;
+                                }
+                            }
+                            Ok(_) => trace!("ID resolution returned empty IDs for rated '{}' (year: {:?})", title, resolved_ids.year),
+                            Err(e) => warn!("Failed to resolve IDs for rated '{}' (year: {:?}): {}", title, resolved_ids.year, e),
+                        }
+                        rating.ids = Some(resolved_ids.clone());
+                        id_resolver.lock().await.cache_ids(resolved_ids);
                     } else {
                         // MediaIds exists but is empty - still cache it
                         rating.ids = Some(resolved_ids.clone());
@@ -1343,47 +2483,52 @@ impl SyncOrchestrator {
         }
     }
     
-    /// Write distribute data files for a source (split by type)
+    /// Write distribute data files for a source (split by type, in `dry_run_format`)
     fn write_dry_run_json(
         &self,
         source_name: &str,
         data: &DryRunData,
     ) -> Result<()> {
-        let path_manager = PathManager::default();
+        let path_manager = self.path_manager.clone();
         let cache_manager = CacheManager::new(&path_manager)
             .map_err(|e| anyhow::anyhow!("Failed to initialize cache manager: {}", e))?;
         
         // Write separate files per data type
         if !data.watchlist.is_empty() {
-            cache_manager.save_distribute_data(source_name, "watchlist", &data.watchlist)?;
+            cache_manager.save_dry_run_data(source_name, "watchlist", &data.watchlist, self.dry_run_format)?;
             info!("Distribute data written: {} watchlist ({} items)", source_name, data.watchlist.len());
         }
         
         if !data.watchlist_to_history.is_empty() {
-            cache_manager.save_distribute_data(source_name, "watchlist_to_history", &data.watchlist_to_history)?;
+            cache_manager.save_dry_run_data(source_name, "watchlist_to_history", &data.watchlist_to_history, self.dry_run_format)?;
             info!("Distribute data written: {} watchlist_to_history ({} items)", source_name, data.watchlist_to_history.len());
         }
         
         if !data.ratings.is_empty() {
-            cache_manager.save_distribute_data(source_name, "ratings", &data.ratings)?;
+            cache_manager.save_dry_run_data(source_name, "ratings", &data.ratings, self.dry_run_format)?;
             info!("Distribute data written: {} ratings ({} items)", source_name, data.ratings.len());
         }
         
         if !data.reviews.is_empty() {
-            cache_manager.save_distribute_data(source_name, "reviews", &data.reviews)?;
+            cache_manager.save_dry_run_data(source_name, "reviews", &data.reviews, self.dry_run_format)?;
             info!("Distribute data written: {} reviews ({} items)", source_name, data.reviews.len());
         }
         
         if !data.watch_history.is_empty() {
-            cache_manager.save_distribute_data(source_name, "watch_history", &data.watch_history)?;
+            cache_manager.save_dry_run_data(source_name, "watch_history", &data.watch_history, self.dry_run_format)?;
             info!("Distribute data written: {} watch_history ({} items)", source_name, data.watch_history.len());
         }
         
         if !data.removal_list.is_empty() {
-            cache_manager.save_distribute_data(source_name, "removal_list", &data.removal_list)?;
+            cache_manager.save_dry_run_data(source_name, "removal_list", &data.removal_list, self.dry_run_format)?;
             info!("Distribute data written: {} removal_list ({} items)", source_name, data.removal_list.len());
         }
         
+        if !data.over_limit.is_empty() {
+            cache_manager.save_dry_run_data(source_name, "over_limit", &data.over_limit, self.dry_run_format)?;
+            info!("Distribute data written: {} over_limit ({} items)", source_name, data.over_limit.len());
+        }
+        
         Ok(())
     }
 
@@ -1412,7 +2557,7 @@ impl SyncOrchestrator {
         // Get removal list for this source (used for filtering and output)
         // Include all watched items that are in the target source's watchlist, regardless of their original source
         // If an item is watched, it should be removed from the watchlist even if it originally came from that source
-        let removal_list = removal_lists.get(source_name).cloned().unwrap_or_default();
+        let mut removal_list = removal_lists.get(source_name).cloned().unwrap_or_default();
         
         // Prepare all data types using the distribution strategy
         let remove_watched_from_watchlists = self.config_sync_options.as_ref()
@@ -1448,7 +2593,7 @@ impl SyncOrchestrator {
             }
         }
 
-        let ratings = if self.sync_options.sync_ratings {
+        let mut ratings = if self.sync_options.sync_ratings {
             strategy.prepare_ratings(&resolved.ratings, existing, self.sync_options.force_full_sync)
                 .unwrap_or_else(|e| {
                     warn!("Failed to prepare ratings for {}: {}", source_name, e);
@@ -1458,8 +2603,11 @@ impl SyncOrchestrator {
             Vec::new()
         };
 
-        let reviews = if self.sync_options.sync_reviews {
-            strategy.prepare_reviews(&resolved.reviews, existing, self.sync_options.force_full_sync)
+        let mut reviews = if self.sync_options.sync_reviews {
+            let mut long_enough = resolved.reviews.clone();
+            let min_review_length = self.config_sync_options.as_ref().and_then(|opts| opts.min_review_length(source_name));
+            crate::review_text::remove_short_reviews(&mut long_enough, min_review_length, source_name);
+            strategy.prepare_reviews(&long_enough, existing, self.sync_options.force_full_sync)
                 .unwrap_or_else(|e| {
                     warn!("Failed to prepare reviews for {}: {}", source_name, e);
                     Vec::new()
@@ -1468,7 +2616,7 @@ impl SyncOrchestrator {
             Vec::new()
         };
 
-        let watch_history = if self.sync_options.sync_watch_history {
+        let mut watch_history = if self.sync_options.sync_watch_history {
             strategy.prepare_watch_history(&resolved.watch_history, existing, self.sync_options.force_full_sync)
                 .unwrap_or_else(|e| {
                     warn!("Failed to prepare watch history for {}: {}", source_name, e);
@@ -1478,17 +2626,35 @@ impl SyncOrchestrator {
             Vec::new()
         };
         
+        // Same plan, same files: sorted by IMDB ID then date, so two dry runs diff cleanly
+        let mut watchlist = watchlist_result.for_watchlist;
+        let mut watchlist_to_history = watchlist_result.for_watch_history;
+        for items in [&mut watchlist, &mut removal_list] {
+            items.sort_by(|a, b| (&a.imdb_id, a.date_added, &a.title).cmp(&(&b.imdb_id, b.date_added, &b.title)));
+        }
+        for items in [&mut watchlist_to_history, &mut watch_history] {
+            items.sort_by(|a, b| (&a.imdb_id, a.watched_at, &a.title).cmp(&(&b.imdb_id, b.watched_at, &b.title)));
+        }
+        ratings.sort_by(|a, b| (&a.imdb_id, a.date_added).cmp(&(&b.imdb_id, b.date_added)));
+        reviews.sort_by(|a, b| (&a.imdb_id, a.date_added).cmp(&(&b.imdb_id, b.date_added)));
+        
+        let accepts_removals = match self.find_source_index(source_name).and_then(|idx| self.sources.get(idx)) {
+            Some(source_arc) => source_arc.read().await.accepts_watchlist_removals(),
+            None => true,
+        };
+        
         // Build resolved data structure
         Ok(DryRunData {
             source: source_name.to_string(),
             timestamp: Utc::now(),
             sync_options: self.sync_options.clone(),
-            watchlist: watchlist_result.for_watchlist,
-            watchlist_to_history: watchlist_result.for_watch_history,
+            watchlist,
+            watchlist_to_history,
             ratings,
             reviews,
             watch_history,
-            removal_list: removal_list.clone(),
+            removal_list: Self::removals_to_send(&removal_list, source_name, accepts_removals, &self.sync_options),
+            over_limit: strategy.take_over_limit(),
         })
     }
 
@@ -1525,16 +2691,216 @@ impl SyncOrchestrator {
         Ok(())
     }
 
+    /// Fetch the custom lists of every source that keeps them, then add to each the
+    /// lists and items it lacks (see `lists`). Returns the number of items added.
+    async fn sync_lists(&self, errors: &mut Vec<String>) -> usize {
+        let mut collected = Vec::new();
+        for source_name in &self.resolution_config.source_preference {
+            let auth_failed = self.source_outcomes.lock().unwrap()
+                .get(source_name)
+                .is_some_and(|outcome| outcome.auth_failed);
+            let Some(source_arc) = self.find_source_index(source_name).and_then(|index| self.sources.get(index)) else {
+                continue;
+            };
+            let source = source_arc.read().await;
+            if auth_failed || !source.supports_lists() {
+                continue;
+            }
+            match source.get_lists().instrument(Self::source_span(source_name)).await {
+                Ok(mut lists) => {
+                    lists.iter_mut().for_each(CustomList::normalize_ids);
+                    info!("Fetched {} lists from {}", lists.len(), source_name);
+                    collected.push((source_name.clone(), lists));
+                }
+                Err(e) => {
+                    let error = format!("Failed to fetch {} lists: {}", source_name, e);
+                    self.record_source_errors(source_name, std::slice::from_ref(&error), false);
+                    errors.push(error);
+                }
+            }
+        }
+
+        let resolved = crate::lists::resolve_lists(&collected);
+        let mut items_synced = 0;
+        for (source_name, existing) in &collected {
+            let missing = crate::lists::missing_from(&resolved, existing);
+            let item_count: usize = missing.iter().map(|list| list.items.len()).sum();
+            if item_count == 0 {
+                debug!("{} lists are up to date", source_name);
+                continue;
+            }
+            if self.sync_options.no_write || self.dry_run_sources.contains(&source_name.to_lowercase()) {
+                info!("Not writing {} list items in {} lists to {} (dry run)", item_count, missing.len(), source_name);
+                continue;
+            }
+
+            info!("Adding {} items in {} lists to {}", item_count, missing.len(), source_name);
+            let Some(source_arc) = self.find_source_index(source_name).and_then(|index| self.sources.get(index)) else {
+                continue;
+            };
+            let source = source_arc.read().await;
+            match source.set_lists(&missing).instrument(Self::source_span(source_name)).await {
+                Ok(()) => items_synced += item_count,
+                Err(e) => {
+                    let error = format!("Failed to update {} lists: {}", source_name, e);
+                    self.record_source_errors(source_name, std::slice::from_ref(&error), false);
+                    errors.push(error);
+                }
+            }
+        }
+        items_synced
+    }
+
+    /// Fetch the collection of every source that keeps one, then add to each writable
+    /// collection the titles it lacks (see `collection`). Returns the number of items added.
+    async fn sync_collection(&self, errors: &mut Vec<String>) -> usize {
+        let mut collected = Vec::new();
+        for source_name in &self.resolution_config.source_preference {
+            let auth_failed = self.source_outcomes.lock().unwrap()
+                .get(source_name)
+                .is_some_and(|outcome| outcome.auth_failed);
+            let Some(source_arc) = self.find_source_index(source_name).and_then(|index| self.sources.get(index)) else {
+                continue;
+            };
+            let source = source_arc.read().await;
+            let Some(collection_source) = source.as_collection_source().filter(|_| !auth_failed) else {
+                continue;
+            };
+            match collection_source.get_collection().instrument(Self::source_span(source_name)).await {
+                Ok(mut items) => {
+                    items.iter_mut().for_each(CollectionItem::normalize_ids);
+                    info!("Fetched {} collection items from {}", items.len(), source_name);
+                    collected.push((source_name.clone(), items, collection_source.accepts_collection_writes()));
+                }
+                Err(e) => {
+                    let error = format!("Failed to fetch {} collection: {}", source_name, e);
+                    self.record_source_errors(source_name, std::slice::from_ref(&error), false);
+                    errors.push(error);
+                }
+            }
+        }
+
+        let by_source: Vec<(String, Vec<CollectionItem>)> = collected.iter()
+            .map(|(source_name, items, _)| (source_name.clone(), items.clone()))
+            .collect();
+        let resolved = crate::collection::resolve_collection(&by_source);
+        let mut items_synced = 0;
+        for (source_name, existing, writable) in &collected {
+            if !writable {
+                continue;
+            }
+            let missing = crate::collection::missing_from(&resolved, existing);
+            if missing.is_empty() {
+                debug!("{} collection is up to date", source_name);
+                continue;
+            }
+            if self.sync_options.no_write || self.dry_run_sources.contains(&source_name.to_lowercase()) {
+                info!("Not adding {} items to the {} collection (dry run)", missing.len(), source_name);
+                continue;
+            }
+
+            info!("Adding {} items to the {} collection", missing.len(), source_name);
+            let Some(source_arc) = self.find_source_index(source_name).and_then(|index| self.sources.get(index)) else {
+                continue;
+            };
+            let source = source_arc.read().await;
+            let Some(collection_source) = source.as_collection_source() else {
+                continue;
+            };
+            match collection_source.add_to_collection(&missing).instrument(Self::source_span(source_name)).await {
+                Ok(()) => items_synced += missing.len(),
+                Err(e) => {
+                    items_synced += e.committed();
+                    let error = format!("Failed to update {} collection: {}", source_name, e);
+                    self.record_source_errors(source_name, std::slice::from_ref(&error), false);
+                    errors.push(error);
+                }
+            }
+        }
+        items_synced
+    }
+
+    /// Sync show progress between the sources that keep it
+    async fn sync_show_progress(&self, errors: &mut Vec<String>) -> usize {
+        let mut collected = Vec::new();
+        for source_name in &self.resolution_config.source_preference {
+            let auth_failed = self.source_outcomes.lock().unwrap()
+                .get(source_name)
+                .is_some_and(|outcome| outcome.auth_failed);
+            if auth_failed {
+                continue;
+            }
+            let Some(source_arc) = self.find_source_index(source_name).and_then(|index| self.sources.get(index)) else {
+                continue;
+            };
+            let source = source_arc.read().await;
+            if !source.supports_watch_progress() {
+                continue;
+            }
+            match source.get_watch_progress().instrument(Self::source_span(source_name)).await {
+                Ok(mut shows) => {
+                    shows.iter_mut().for_each(WatchProgress::normalize_ids);
+                    info!("Fetched progress of {} shows from {}", shows.len(), source_name);
+                    collected.push((source_name.clone(), shows));
+                }
+                Err(e) => {
+                    let error = format!("Failed to fetch {} show progress: {}", source_name, e);
+                    self.record_source_errors(source_name, std::slice::from_ref(&error), false);
+                    errors.push(error);
+                }
+            }
+        }
+
+        let resolved = crate::show_progress::resolve_progress(&collected);
+        let mut items_synced = 0;
+        for (source_name, existing) in &collected {
+            let behind = crate::show_progress::ahead_of(&resolved, existing);
+            if behind.is_empty() {
+                debug!("{} show progress is up to date", source_name);
+                continue;
+            }
+            if self.sync_options.no_write || self.dry_run_sources.contains(&source_name.to_lowercase()) {
+                info!("Not updating the progress of {} shows on {} (dry run)", behind.len(), source_name);
+                continue;
+            }
+
+            info!("Updating the progress of {} shows on {}", behind.len(), source_name);
+            let Some(source_arc) = self.find_source_index(source_name).and_then(|index| self.sources.get(index)) else {
+                continue;
+            };
+            let source = source_arc.read().await;
+            match source.set_watch_progress(&behind).instrument(Self::source_span(source_name)).await {
+                Ok(()) => items_synced += behind.len(),
+                Err(e) => {
+                    items_synced += e.committed();
+                    let error = format!("Failed to update {} show progress: {}", source_name, e);
+                    self.record_source_errors(source_name, std::slice::from_ref(&error), false);
+                    errors.push(error);
+                }
+            }
+        }
+        items_synced
+    }
+
     async fn distribute_resolved_data(
         &mut self,
         resolved: &ResolvedData,
         collected_data: &CollectedData,
         cache_manager: &CacheManager,
         errors: &mut Vec<String>,
-    ) -> Result<usize> {
-        // Use thread-safe counters for concurrent distribution
-        let items_synced_arc = Arc::new(Mutex::new(0usize));
-        let errors_arc = Arc::new(tokio::sync::Mutex::new(Vec::<String>::new()));
+    ) -> Result<(usize, usize)> {
+        // Counters and ledgers shared by the concurrent distribution to every target
+        let items_deferred = Mutex::new(0usize);
+        let write_budget = Mutex::new(self.sync_options.max_items);
+        let deferred = tokio::sync::Mutex::new(Vec::<SourcePlan>::new());
+        let failures = tokio::sync::Mutex::new(Vec::<SourcePlan>::new());
+        
+        // Leave out items the user tagged with one of `exclude_tags` in any source
+        let exclude_tags = self.config_sync_options.as_ref()
+            .map(|o| o.exclude_tags.clone())
+            .unwrap_or_default();
+        let (tag_filtered, tag_excluded_ids) = Self::exclude_tagged_items(resolved, &collected_data.sources, &exclude_tags);
+        let resolved = &tag_filtered;
         
         // Build set of watched IMDB IDs if remove_watched_from_watchlists is enabled
         let watched_ids: std::collections::HashSet<String> = if let Some(ref config_sync_options) = self.config_sync_options {
@@ -1602,44 +2968,87 @@ impl SyncOrchestrator {
             }
         }
         
-        // Add Simkl Dropped items to removal lists for all other sources
-        if let Some((_, simkl_data)) = collected_data.sources.iter().find(|(name, _)| name == "simkl") {
-            let dropped_items: Vec<WatchlistItem> = simkl_data.watchlist
+        // Propagate the removals of the authoritative sources to every other source: the
+        // items they mark Dropped, and the titles that left their watchlist since the last
+        // sync (see `removed_since`)
+        let authoritative_sources = self.config_sync_options.as_ref()
+            .map(|o| o.removals.authoritative_sources.clone())
+            .unwrap_or_else(|| media_sync_config::RemovalsOptions::default().authoritative_sources);
+        let mut withdrawn: Vec<WatchlistItem> = Vec::new();
+        for authority in &authoritative_sources {
+            let Some((_, authority_data)) = collected_data.sources.iter().find(|(name, _)| name == authority) else {
+                continue;
+            };
+            let mut removed_items: Vec<WatchlistItem> = authority_data.watchlist
                 .iter()
                 .filter(|item| item.status == Some(NormalizedStatus::Dropped))
                 .cloned()
                 .collect();
+            if !removed_items.is_empty() {
+                info!("Found {} Dropped items in {} watchlist", removed_items.len(), authority);
+            }
+            if self.sync_options.sync_watchlist {
+                if let Some(previous) = cache_manager.load_watchlist_snapshot(authority) {
+                    let removed = Self::removed_since(&previous, &authority_data.watchlist, authority);
+                    if !removed.is_empty() {
+                        info!("Found {} items removed from {} watchlist since the last sync", removed.len(), authority);
+                    }
+                    withdrawn.extend(removed.iter().cloned());
+                    removed_items.extend(removed);
+                }
+            }
             
-            if !dropped_items.is_empty() {
-                info!("Found {} Dropped items in Simkl watchlist, adding to removal lists for all other sources", dropped_items.len());
-                
+            if !removed_items.is_empty() {
                 // Add to removal list for all other sources
                 for (source_name, _) in &collected_data.sources {
-                    if source_name != "simkl" {
-                        let removal_list = removal_lists.entry(source_name.clone()).or_insert_with(Vec::new);
+                    if source_name != authority {
+                        let removal_list = removal_lists.entry(source_name.clone()).or_default();
                         let before_count = removal_list.len();
                         
-                        // Add dropped items, avoiding duplicates
+                        // Add removed items, avoiding duplicates
                         let existing_ids: std::collections::HashSet<String> = removal_list.iter()
                             .map(|item| item.imdb_id.clone())
                             .collect();
                         
-                        for dropped_item in &dropped_items {
-                            if !existing_ids.contains(&dropped_item.imdb_id) {
-                                removal_list.push(dropped_item.clone());
+                        for removed_item in &removed_items {
+                            if !existing_ids.contains(&removed_item.imdb_id) {
+                                removal_list.push(removed_item.clone());
                             }
                         }
                         
                         let added_count = removal_list.len() - before_count;
                         if added_count > 0 {
-                            info!("Added {} Simkl Dropped items to {} removal list (total: {})", 
-                                added_count, source_name, removal_list.len());
+                            info!("Added {} items removed in {} to {} removal list (total: {})", 
+                                added_count, authority, source_name, removal_list.len());
                         }
                     }
                 }
             }
         }
         
+        // A title removed from an authority's watchlist leaves the resolved watchlist, so
+        // no target, the authority included, gets it added back from the others
+        let without_withdrawn;
+        let resolved = if withdrawn.is_empty() {
+            resolved
+        } else {
+            without_withdrawn = ResolvedData {
+                watchlist: resolved.watchlist.iter()
+                    .filter(|item| !withdrawn.iter().any(|removed| Self::same_watchlist_title(removed, item)))
+                    .cloned()
+                    .collect(),
+                ..resolved.clone()
+            };
+            &without_withdrawn
+        };
+        
+        // Tagged items are not removed either
+        if !tag_excluded_ids.is_empty() {
+            for removal_list in removal_lists.values_mut() {
+                removal_list.retain(|item| !tag_excluded_ids.contains(&item.imdb_id));
+            }
+        }
+        
         // Helper to get existing data for a source
         let get_existing_data = |source_name: &str| -> Option<&SourceData> {
             collected_data.sources.iter()
@@ -1647,85 +3056,154 @@ impl SyncOrchestrator {
                 .map(|(_, data)| data)
         };
         
-        // Helper to create distribution strategy for a target source by name
-        // In the future, sources could provide their own strategy via distribution_strategy_name()
-        let create_strategy_by_name = |source_name: &str, cache_manager: &CacheManager| -> Result<Box<dyn DistributionStrategy>> {
-            let cache_manager_clone = cache_manager.clone();
-            
-            match source_name {
-                "trakt" => Ok(Box::new(TraktDistributionStrategy::new()?.with_cache_manager(cache_manager_clone))),
-                "imdb" => Ok(Box::new(ImdbDistributionStrategy::new()?.with_cache_manager(cache_manager_clone))),
-                "simkl" => Ok(Box::new(SimklDistributionStrategy::new()?)),
-                "plex" => Ok(Box::new(PlexDistributionStrategy::new()?.with_cache_manager(cache_manager_clone))),
-                _ => Ok(Box::new(DefaultDistributionStrategy::new(source_name)?.with_cache_manager(cache_manager_clone))),
-            }
-        };
-        
         // Distribute to all sources concurrently
+        let run = DistributionRun {
+            sources: &self.sources,
+            sync_options: &self.sync_options,
+            config_sync_options: &self.config_sync_options,
+            dry_run_sources: &self.dry_run_sources,
+            resolved,
+            collected_data,
+            removal_lists: &removal_lists,
+            cache_manager,
+            metrics: &self.metrics,
+            items_deferred: &items_deferred,
+            write_budget: &write_budget,
+            deferred: &deferred,
+            failures: &failures,
+        };
+        let run = &run;
+        let phase = Phase::start(&self.progress, &self.sources, "Distributing", self.resolution_config.source_preference.len()).await;
         let distribution_futures: Vec<_> = self.resolution_config.source_preference
             .iter()
             .map(|source_name| {
                 let source_name = source_name.clone();
-                let sources = self.sources.clone();
-                let sync_options = self.sync_options.clone();
-                let config_sync_options = self.config_sync_options.clone();
-                let dry_run_sources = self.dry_run_sources.clone();
-                let resolution_config = self.resolution_config.clone();
-                let resolved = resolved.clone();
-                let collected_data = collected_data.clone();
-                let removal_lists = removal_lists.clone();
-                let watched_ids = watched_ids.clone();
-                let cache_manager = cache_manager.clone();
-                let items_synced_arc = items_synced_arc.clone();
-                let errors_arc = errors_arc.clone();
+                let progress = self.progress.clone();
+                let span = Self::source_span(&source_name);
                 
                 async move {
-                    Self::distribute_to_single_source(
-                        &sources,
-                        &source_name,
-                        &sync_options,
-                        &config_sync_options,
-                        &dry_run_sources,
-                        &resolved,
-                        &collected_data,
-                &removal_lists,
-                        &watched_ids,
-                        &cache_manager,
-                        &items_synced_arc,
-                        &errors_arc,
-                    ).await
+                    let errors_arc = Arc::new(tokio::sync::Mutex::new(Vec::<String>::new()));
+                    let result = Self::distribute_to_single_source(run, &source_name, &errors_arc).await;
+                    progress.advance(1);
+                    let source_errors = std::mem::take(&mut *errors_arc.lock().await);
+                    (source_name, result, source_errors)
                 }
+                .instrument(span)
             })
-                                                .collect();
+            .collect();
         
         // Execute all distributions concurrently
         let results = join_all(distribution_futures).await;
+        drop(phase);
         
-        // Collect errors from all distributions
-        let mut distribution_errors = errors_arc.lock().await;
-        errors.append(&mut *distribution_errors);
+        let failures = failures.into_inner();
+        let mut unwritten = failures.clone();
+        unwritten.extend(deferred.into_inner());
+        self.save_failures(failures);
+
+        // Keep each source's prepared writes for `plan`
+        self.planned_sources.clear();
+        let mut every_target_distributed = true;
+        for (source_name, result, mut source_errors) in results {
+            match result {
+                Ok(Some(source_plan)) => self.planned_sources.push(source_plan),
+                Ok(None) => every_target_distributed = false,
+                Err(e) => {
+                    every_target_distributed = false;
+                    source_errors.push(format!("Distribution to {} failed: {}", source_name, e));
+                }
+            }
+            self.record_source_errors(&source_name, &source_errors, false);
+            errors.extend(source_errors);
+        }
+
+        // Buffered events are done once every target has them. A target that failed to
+        // authenticate or to distribute got none of them, so all are kept for the next run.
+        // Events of a data type this run didn't sync were never merged, so they stay too.
+        let auth_failed = self.source_outcomes().iter().any(|outcome| outcome.auth_failed);
+        if self.dry_run_sources.is_empty() && !self.sync_options.no_write && every_target_distributed && !auth_failed {
+            let event_buffer = EventBuffer::new(&self.path_manager);
+            for (source_name, _) in &collected_data.sources {
+                if let Err(e) = event_buffer.clear_written(source_name, &unwritten, self.sync_options.sync_watch_history, self.sync_options.sync_ratings).await {
+                    warn!("Failed to clear {} event buffer: {}", source_name, e);
+                }
+            }
+
+            // The next run finds the authorities' removals against what they hold now
+            if self.sync_options.sync_watchlist {
+                for (source_name, data) in &collected_data.sources {
+                    if !authoritative_sources.contains(source_name) || data.watchlist.is_empty() {
+                        continue;
+                    }
+                    if let Err(e) = cache_manager.save_watchlist_snapshot(source_name, &data.watchlist) {
+                        warn!("Failed to save {} watchlist snapshot: {}", source_name, e);
+                    }
+                }
+            }
+        }
         
-        // Get total items synced
-        let items_synced = *items_synced_arc.lock().await;
+        // Get total items synced and deferred
+        let items_synced = self.metrics.lock().unwrap().total_added();
+        let items_deferred = items_deferred.into_inner();
         
-        Ok(items_synced)
+        Ok((items_synced, items_deferred))
     }
     
+    fn same_watchlist_title(a: &WatchlistItem, b: &WatchlistItem) -> bool {
+        crate::lists::same_title((&a.imdb_id, &a.ids, &a.media_type), (&b.imdb_id, &b.ids, &b.media_type))
+    }
+
+    /// Titles of an authority's `previous` watchlist that `current` no longer has. An
+    /// empty `current` after a non-empty one is taken for a failed fetch, not for the
+    /// user clearing the watchlist, and removes nothing.
+    fn removed_since(previous: &[WatchlistItem], current: &[WatchlistItem], authority: &str) -> Vec<WatchlistItem> {
+        if current.is_empty() && !previous.is_empty() {
+            warn!("{} watchlist is empty but held {} items at the last sync; not propagating removals", authority, previous.len());
+            return Vec::new();
+        }
+        previous.iter()
+            .filter(|item| !current.iter().any(|now| Self::same_watchlist_title(item, now)))
+            .cloned()
+            .collect()
+    }
+
+    /// Distribution strategy for a target source by name, sending only items dated `since`
+    /// or later when it is set (`--since`)
+    /// In the future, sources could provide their own strategy via distribution_strategy_name()
+    fn create_distribution_strategy(source_name: &str, cache_manager: &CacheManager, since: Option<DateTime<Utc>>) -> Result<Box<dyn DistributionStrategy>> {
+        let cache_manager_clone = cache_manager.clone();
+        match source_name {
+            "trakt" => Ok(Box::new(TraktDistributionStrategy::new()?.with_cache_manager(cache_manager_clone).with_since(since))),
+            "imdb" => Ok(Box::new(ImdbDistributionStrategy::new()?.with_cache_manager(cache_manager_clone).with_since(since))),
+            "simkl" => Ok(Box::new(SimklDistributionStrategy::new()?.with_since(since))),
+            "plex" => Ok(Box::new(PlexDistributionStrategy::new()?.with_cache_manager(cache_manager_clone).with_since(since))),
+            "letterboxd" => Ok(Box::new(LetterboxdDistributionStrategy::new()?.with_cache_manager(cache_manager_clone).with_since(since))),
+            "anilist" => Ok(Box::new(AniListDistributionStrategy::new()?.with_cache_manager(cache_manager_clone).with_since(since))),
+            _ => Ok(Box::new(DefaultDistributionStrategy::new(source_name)?.with_cache_manager(cache_manager_clone).with_since(since))),
+        }
+    }
+
     /// Distribute resolved data to a single source (helper for concurrent distribution)
     async fn distribute_to_single_source(
-        sources: &[Arc<RwLock<Box<dyn MediaSource<Error = SourceError>>>>],
+        run: &DistributionRun<'_>,
         source_name: &str,
-        sync_options: &SyncOptions,
-        config_sync_options: &Option<media_sync_config::SyncOptions>,
-        dry_run_sources: &std::collections::HashSet<String>,
-        resolved: &ResolvedData,
-        collected_data: &CollectedData,
-        removal_lists: &std::collections::HashMap<String, Vec<WatchlistItem>>,
-        watched_ids: &std::collections::HashSet<String>,
-        cache_manager: &CacheManager,
-        items_synced_arc: &Arc<Mutex<usize>>,
         errors_arc: &Arc<tokio::sync::Mutex<Vec<String>>>,
-    ) -> Result<()> {
+    ) -> Result<Option<SourcePlan>> {
+        let DistributionRun {
+            sources,
+            sync_options,
+            config_sync_options,
+            dry_run_sources,
+            resolved,
+            collected_data,
+            removal_lists,
+            cache_manager,
+            metrics,
+            items_deferred: items_deferred_arc,
+            write_budget,
+            deferred: deferred_arc,
+            failures: failures_arc,
+        } = *run;
         // Helper to get existing data for a source
         let get_existing_data = |source_name: &str| -> Option<&SourceData> {
             collected_data.sources.iter()
@@ -1733,28 +3211,15 @@ impl SyncOrchestrator {
                 .map(|(_, data)| data)
         };
         
-        // Helper to create distribution strategy for a target source by name
-        let create_strategy_by_name = |source_name: &str, cache_manager: &CacheManager| -> Result<Box<dyn DistributionStrategy>> {
-            let cache_manager_clone = cache_manager.clone();
-            
-            match source_name {
-                "trakt" => Ok(Box::new(TraktDistributionStrategy::new()?.with_cache_manager(cache_manager_clone))),
-                "imdb" => Ok(Box::new(ImdbDistributionStrategy::new()?.with_cache_manager(cache_manager_clone))),
-                "simkl" => Ok(Box::new(SimklDistributionStrategy::new()?)),
-                "plex" => Ok(Box::new(PlexDistributionStrategy::new()?.with_cache_manager(cache_manager_clone))),
-                _ => Ok(Box::new(DefaultDistributionStrategy::new(source_name)?.with_cache_manager(cache_manager_clone))),
-            }
-        };
-        
             // Check if this source is in dry-run mode
         let is_dry_run = dry_run_sources.contains(&source_name.to_lowercase());
             
         // Create distribution strategy
-            let strategy = match create_strategy_by_name(source_name, cache_manager) {
+            let strategy = match Self::create_distribution_strategy(source_name, cache_manager, sync_options.since) {
                 Ok(s) => s,
                                     Err(e) => {
                 errors_arc.lock().await.push(format!("Failed to create distribution strategy for {}: {}", source_name, e));
-                return Ok(());
+                return Ok(None);
             }
         };
         
@@ -1775,7 +3240,7 @@ impl SyncOrchestrator {
             Some(idx) => idx,
             None => {
                 errors_arc.lock().await.push(format!("Source '{}' not found in sources", source_name));
-                return Ok(());
+                return Ok(None);
             }
         };
         
@@ -1784,10 +3249,16 @@ impl SyncOrchestrator {
             Some(s) => s.clone(),
             None => {
                 errors_arc.lock().await.push(format!("Source '{}' not found at index {}", source_name, source_index));
-                return Ok(());
+                return Ok(None);
             }
         };
         
+        // Skip data types this source can't receive
+        let sync_options = &{
+            let source_guard = source_arc.read().await;
+            Self::gate_sync_options_for_source(sync_options, source_name, source_guard.supported_data_types())
+        };
+        
         // Prepare resolved data inline (since we can't call instance methods)
         let empty_data = SourceData {
                                 watchlist: Vec::new(),
@@ -1840,7 +3311,29 @@ impl SyncOrchestrator {
             }
         }
 
-        let ratings = if sync_options.sync_ratings {
+        // Cap watchlist additions per run; the overflow is deferred, not dropped
+        let max_watchlist_additions = config_sync_options.as_ref()
+            .and_then(|opts| opts.watchlist.max_items_to_add_per_run);
+        let watchlist_deferred = Self::cap_watchlist_additions(
+            &mut watchlist_result.for_watchlist,
+            max_watchlist_additions,
+            source_name,
+        );
+        *items_deferred_arc.lock().await += watchlist_deferred;
+        if watchlist_deferred > 0 {
+            metrics.lock().unwrap().entry(source_name, DataType::Watchlist).skipped += watchlist_deferred;
+        }
+
+        // ratings.no_cross_sync keeps each source's ratings to itself. Every rating left after
+        // the strategy's source filter came from another source, so nothing is pushed.
+        let no_cross_sync_ratings = config_sync_options.as_ref()
+            .map(|opts| opts.ratings.no_cross_sync)
+            .unwrap_or(false);
+        if sync_options.sync_ratings && no_cross_sync_ratings {
+            info!("Not pushing ratings to {} (ratings.no_cross_sync)", source_name);
+        }
+
+        let ratings = if sync_options.sync_ratings && !no_cross_sync_ratings {
             strategy.prepare_ratings(&resolved.ratings, existing, sync_options.force_full_sync)
                 .unwrap_or_else(|e| {
                     warn!("Failed to prepare ratings for {}: {}", source_name, e);
@@ -1851,11 +3344,26 @@ impl SyncOrchestrator {
         };
 
         let reviews = if sync_options.sync_reviews {
-            strategy.prepare_reviews(&resolved.reviews, existing, sync_options.force_full_sync)
+            // Sanitize per target before deduplication so content matches what the target stores
+            let review_options = config_sync_options.as_ref()
+                .map(|opts| opts.reviews.clone())
+                .unwrap_or_default();
+            let mut sanitized = crate::review_text::sanitize_reviews(&resolved.reviews, &review_options, source_name);
+            let min_review_length = config_sync_options.as_ref().and_then(|opts| opts.min_review_length(source_name));
+            crate::review_text::remove_short_reviews(&mut sanitized, min_review_length, source_name);
+            let prepared = strategy.prepare_reviews(&sanitized, existing, sync_options.force_full_sync)
                 .unwrap_or_else(|e| {
                     warn!("Failed to prepare reviews for {}: {}", source_name, e);
                     Vec::new()
-                })
+                });
+            let links = ReviewLinks::load(&cache_manager.paths().cache_review_links_dir());
+            let prepared = if sync_options.force_full_sync {
+                prepared
+            } else {
+                links.filter_for_target(prepared, source_name)
+            };
+            let grace = chrono::Duration::minutes(review_options.indexing_grace_minutes as i64);
+            links.filter_recently_written(prepared, source_name, grace)
                                             } else {
             Vec::new()
         };
@@ -1869,24 +3377,64 @@ impl SyncOrchestrator {
                                             } else {
             Vec::new()
         };
+
+        // Partial plays only go to targets that track playback progress, and only with
+        // watch_history.sync_progress; everywhere else they are left out
+        let sync_progress = config_sync_options.as_ref()
+            .map(|opts| opts.watch_history.sync_progress)
+            .unwrap_or(false);
+        let tracks_progress = source_arc.read().await.as_playback_progress().is_some();
+        let playback_progress = if sync_options.sync_watch_history && sync_progress && tracks_progress {
+            strategy.prepare_watch_history(&resolved.playback_progress, existing, sync_options.force_full_sync)
+                .unwrap_or_else(|e| {
+                    warn!("Failed to prepare playback progress for {}: {}", source_name, e);
+                    Vec::new()
+                })
+        } else {
+            Vec::new()
+        };
         
+        // Removals still kept the items out of the additions above
+        let accepts_removals = source_arc.read().await.accepts_watchlist_removals();
+        let removal_list = Self::removals_to_send(&removal_list, source_name, accepts_removals, sync_options);
+        let mut source_plan = SourcePlan {
+            source: source_name.to_string(),
+            watchlist_add: watchlist_result.for_watchlist,
+            watchlist_remove: removal_list,
+            watchlist_to_history: watchlist_result.for_watch_history,
+            ratings,
+            reviews,
+            watch_history_add: watch_history,
+            playback_progress,
+            watchlist_deferred,
+            deferred: 0,
+        };
+
+        // Dry-run sources write nothing, so only the others draw on the --max-items budget
+        if !is_dry_run {
+            if let Some(remaining) = write_budget.lock().await.as_mut() {
+                let deferred = Self::take_write_budget(&mut source_plan, remaining, metrics);
+                *items_deferred_arc.lock().await += deferred.total_items();
+                deferred_arc.lock().await.push(deferred);
+            }
+        }
+
         // Write dry-run JSON (inline the logic)
         let dry_run_data = DryRunData {
             source: source_name.to_string(),
             timestamp: Utc::now(),
             sync_options: sync_options.clone(),
-            watchlist: watchlist_result.for_watchlist.clone(),
-            watchlist_to_history: watchlist_result.for_watch_history.clone(),
-            ratings: ratings.clone(),
-            reviews: reviews.clone(),
-            watch_history: watch_history.clone(),
-            removal_list: removal_list.clone(),
+            watchlist: source_plan.watchlist_add.clone(),
+            watchlist_to_history: source_plan.watchlist_to_history.clone(),
+            ratings: source_plan.ratings.clone(),
+            reviews: source_plan.reviews.clone(),
+            watch_history: source_plan.watch_history_add.clone(),
+            removal_list: source_plan.watchlist_remove.clone(),
+            over_limit: strategy.take_over_limit(),
         };
         
         // Write dry-run JSON files
-        let path_manager = PathManager::default();
-        let cache_manager_for_json = CacheManager::new(&path_manager)
-            .map_err(|e| anyhow::anyhow!("Failed to initialize cache manager: {}", e))?;
+        let cache_manager_for_json = cache_manager;
         
         if !dry_run_data.watchlist.is_empty() {
             cache_manager_for_json.save_distribute_data(source_name, "watchlist", &dry_run_data.watchlist)?;
@@ -1906,6 +3454,9 @@ impl SyncOrchestrator {
         if !dry_run_data.removal_list.is_empty() {
             cache_manager_for_json.save_distribute_data(source_name, "removal_list", &dry_run_data.removal_list)?;
         }
+        if !dry_run_data.over_limit.is_empty() {
+            cache_manager_for_json.save_distribute_data(source_name, "over_limit", &dry_run_data.over_limit)?;
+        }
         
         // Handle dry-run mode: skip actual writes for dry-run sources
         if is_dry_run {
@@ -1918,108 +3469,279 @@ impl SyncOrchestrator {
                 dry_run_data.watch_history.len(),
                 dry_run_data.removal_list.len()
             );
-            return Ok(());
+            return Ok(Some(source_plan));
         }
         
+        // A source with nothing to write is the steady state, so it only shows at debug level
+        if source_plan.is_empty() {
+            debug!("{} is up to date, nothing to write", source_name);
+        } else if !sync_options.no_write {
+            info!("Writing {} changes to {} (watchlist: +{} -{}, ratings: {}, reviews: {}, watch_history: {})",
+                source_plan.total_items(),
+                source_name,
+                source_plan.watchlist_add.len() + source_plan.watchlist_to_history.len(),
+                source_plan.watchlist_remove.len(),
+                source_plan.ratings.len(),
+                source_plan.reviews.len(),
+                source_plan.watch_history_add.len() + source_plan.playback_progress.len()
+            );
+        }
+
         // Now do the actual distribution using the source
-        // Use the prepared data we already have (watchlist_result, ratings, reviews, watch_history)
-        // Distribute based on source type
+        let chunk_by_year = config_sync_options.as_ref()
+            .is_some_and(|opts| opts.watch_history.chunk_by_year);
+        if let Some(failed) = Self::apply_source_plan(&source_arc, &source_plan, sync_options, chunk_by_year, strategy.as_ref(), cache_manager, metrics, errors_arc).await {
+            failures_arc.lock().await.push(failed);
+        }
+
+        Ok(Some(source_plan))
+    }
+
+    /// Send one source's planned writes and record sync timestamps for what was sent.
+    /// Shared by the distribute phase and `apply`. Returns the items whose writes failed,
+    /// or None when nothing was attempted (`--no-write`).
+    async fn apply_source_plan(
+        source_arc: &Arc<RwLock<Box<dyn MediaSource<Error = SourceError>>>>,
+        plan: &SourcePlan,
+        sync_options: &SyncOptions,
+        chunk_by_year: bool,
+        strategy: &dyn DistributionStrategy,
+        cache_manager: &CacheManager,
+        metrics: &std::sync::Mutex<MetricsAccumulator>,
+        errors_arc: &Arc<tokio::sync::Mutex<Vec<String>>>,
+    ) -> Option<SourcePlan> {
+        let source_name = plan.source.as_str();
+        // Every write goes through here, so this is the one place --no-write has to hold
+        if sync_options.no_write {
+            if !plan.is_empty() {
+                info!("Not writing {} changes to {} (--no-write; watchlist: +{} -{}, ratings: {}, reviews: {}, watch_history: {})",
+                    plan.total_items(),
+                    source_name,
+                    plan.watchlist_add.len() + plan.watchlist_to_history.len(),
+                    plan.watchlist_remove.len(),
+                    plan.ratings.len(),
+                    plan.reviews.len(),
+                    plan.watch_history_add.len() + plan.playback_progress.len()
+                );
+            }
+            return None;
+        }
+
+        let mut failed = SourcePlan::new(source_name);
         match source_name {
-            "trakt" | "imdb" | "simkl" | "plex" => {
+            name if media_sync_config::is_known_source(name) => {
                 // Distribute watchlist
-                if !watchlist_result.for_watchlist.is_empty() && sync_options.sync_watchlist {
+                if !plan.watchlist_add.is_empty() && sync_options.sync_watchlist {
                     let source_guard = source_arc.read().await;
-                    if let Err(e) = source_guard.add_to_watchlist(&watchlist_result.for_watchlist).await {
+                    if let Err(e) = source_guard.add_to_watchlist(&plan.watchlist_add).await {
                         errors_arc.lock().await.push(format!("Failed to add watchlist to {}: {}", source_name, e));
+                        // Items of chunks sent before the failure are on the source already
+                        let committed = e.committed().min(plan.watchlist_add.len());
+                        failed.watchlist_add = plan.watchlist_add[committed..].to_vec();
+                        count(metrics, source_name, DataType::Watchlist, |m| {
+                            m.added += committed;
+                            m.errors += plan.watchlist_add.len() - committed;
+                        });
                                             } else {
-                        *items_synced_arc.lock().await += watchlist_result.for_watchlist.len();
-                        if let Err(e) = strategy.on_sync_complete("watchlist", watchlist_result.for_watchlist.len()) {
-                                                    warn!("Failed to update sync timestamp: {}", e);
-                                                }
+                        count(metrics, source_name, DataType::Watchlist, |m| m.added += plan.watchlist_add.len());
+                        // Leave the sync timestamp alone while items are deferred, so the
+                        // incremental filter doesn't hide them from the next run
+                        if plan.watchlist_deferred == 0 && plan.deferred == 0 {
+                            if let Err(e) = strategy.on_sync_complete("watchlist", plan.watchlist_add.len()) {
+                                warn!("Failed to update sync timestamp: {}", e);
+                            }
+                        }
                                             }
                                         }
                                         
                 // Distribute watchlist items that should go to watch history
-                if !watchlist_result.for_watch_history.is_empty() && sync_options.sync_watch_history {
+                if !plan.watchlist_to_history.is_empty() && sync_options.sync_watch_history {
                     let source_guard = source_arc.read().await;
-                    if let Err(e) = source_guard.add_watch_history(&watchlist_result.for_watch_history).await {
+                    if let Err(e) = source_guard.add_watch_history(&plan.watchlist_to_history).await {
                         errors_arc.lock().await.push(format!("Failed to add watch history to {}: {}", source_name, e));
+                        let committed = e.committed().min(plan.watchlist_to_history.len());
+                        failed.watchlist_to_history = plan.watchlist_to_history[committed..].to_vec();
+                        count(metrics, source_name, DataType::WatchHistory, |m| {
+                            m.added += committed;
+                            m.errors += plan.watchlist_to_history.len() - committed;
+                        });
                                             } else {
-                        *items_synced_arc.lock().await += watchlist_result.for_watch_history.len();
-                        if let Err(e) = strategy.on_sync_complete("watch_history", watchlist_result.for_watch_history.len()) {
-                                                    warn!("Failed to update sync timestamp: {}", e);
-                                                }
+                        count(metrics, source_name, DataType::WatchHistory, |m| m.added += plan.watchlist_to_history.len());
+                        if plan.deferred == 0 {
+                            if let Err(e) = strategy.on_sync_complete("watch_history", plan.watchlist_to_history.len()) {
+                                warn!("Failed to update sync timestamp: {}", e);
+                            }
+                        }
                                             }
                                         }
                                         
                 // Remove items from watchlist
-                                        if !removal_list.is_empty() {
+                if !plan.watchlist_remove.is_empty() && sync_options.never_remove {
+                    info!("Would remove {} items from {} watchlist, skipped due to never_remove", plan.watchlist_remove.len(), source_name);
+                } else if !plan.watchlist_remove.is_empty() {
                     let source_guard = source_arc.read().await;
-                    if let Err(e) = source_guard.remove_from_watchlist(&removal_list).await {
+                    if let Err(e) = source_guard.remove_from_watchlist(&plan.watchlist_remove).await {
                         errors_arc.lock().await.push(format!("Failed to remove items from {} watchlist: {}", source_name, e));
+                        failed.watchlist_remove = plan.watchlist_remove.clone();
+                        count(metrics, source_name, DataType::Watchlist, |m| m.errors += plan.watchlist_remove.len());
+                    } else {
+                        count(metrics, source_name, DataType::Watchlist, |m| m.removed += plan.watchlist_remove.len());
                     }
                 }
                 
                 // Distribute ratings
-                if !ratings.is_empty() && sync_options.sync_ratings {
+                if !plan.ratings.is_empty() && sync_options.sync_ratings {
                     let source_guard = source_arc.read().await;
                     // Use RatingNormalization trait to denormalize from 1-10 scale to source's native scale
                     let ratings_to_set = if let Some(normalizer) = source_guard.as_rating_normalization() {
-                        ratings.iter()
+                        let mut ratings = plan.ratings.iter()
                             .map(|r| {
                                 // Denormalize from 1-10 scale (stored) to source's native scale
-                                // The second parameter (10) is the source scale of the input rating
-                                let denormalized = normalizer.denormalize_rating(r.rating, 10) as u8;
-                                Rating { rating: denormalized, ..r.clone() }
+                                // The second parameter (10) is the source scale of the input rating.
+                                // A fraction (half stars) is kept for sources that take one.
+                                let mut denormalized = r.clone();
+                                denormalized.set_value(normalizer.denormalize_rating_f64(r.value(), 10, &r.media_type));
+                                denormalized
                             })
-                            .collect::<Vec<_>>()
+                            .collect::<Vec<_>>();
+                        // A hand-edited plan or a rounding slip must not reach the API out of range
+                        for rating in ratings.iter_mut() {
+                            let scale = normalizer.native_rating_scale(&rating.media_type);
+                            clamp_ratings_to_scale(std::slice::from_mut(rating), scale, source_name);
+                        }
+                        ratings
                     } else {
                         // No normalizer - assume already in correct scale
-                        ratings.clone()
+                        plan.ratings.clone()
                     };
                     
                     if let Err(e) = source_guard.set_ratings(&ratings_to_set).await {
                         errors_arc.lock().await.push(format!("Failed to set ratings on {}: {}", source_name, e));
+                        // Kept on the normalized scale, like a plan
+                        let committed = e.committed().min(plan.ratings.len());
+                        failed.ratings = plan.ratings[committed..].to_vec();
+                        count(metrics, source_name, DataType::Ratings, |m| {
+                            m.added += committed;
+                            m.errors += plan.ratings.len() - committed;
+                        });
                                             } else {
-                        *items_synced_arc.lock().await += ratings_to_set.len();
-                        if let Err(e) = strategy.on_sync_complete("ratings", ratings_to_set.len()) {
-                                                    warn!("Failed to update sync timestamp: {}", e);
+                        count(metrics, source_name, DataType::Ratings, |m| m.added += ratings_to_set.len());
+                        if plan.deferred == 0 {
+                            if let Err(e) = strategy.on_sync_complete("ratings", ratings_to_set.len()) {
+                                warn!("Failed to update sync timestamp: {}", e);
+                            }
                         }
                     }
                 }
                 
                 // Distribute reviews
-                if !reviews.is_empty() && sync_options.sync_reviews {
+                if !plan.reviews.is_empty() && sync_options.sync_reviews {
                     let source_guard = source_arc.read().await;
-                    if let Err(e) = source_guard.set_reviews(&reviews).await {
-                        errors_arc.lock().await.push(format!("Failed to set reviews on {}: {}", source_name, e));
-                                            } else {
-                        *items_synced_arc.lock().await += reviews.len();
-                        if let Err(e) = strategy.on_sync_complete("reviews", reviews.len()) {
-                                                    warn!("Failed to update sync timestamp: {}", e);
-                                                }
-                                            }
+                    match source_guard.set_reviews(&plan.reviews).await {
+                        Err(e) => {
+                            errors_arc.lock().await.push(format!("Failed to set reviews on {}: {}", source_name, e));
+                            failed.reviews = plan.reviews.clone();
+                            count(metrics, source_name, DataType::Reviews, |m| m.errors += plan.reviews.len());
+                        }
+                        Ok(written) => {
+                            // Reviews the source rejected or already had are not linked to it
+                            let written_reviews: Vec<Review> = written.iter()
+                                .filter_map(|copy| plan.reviews.get(copy.index).cloned())
+                                .collect();
+                            count(metrics, source_name, DataType::Reviews, |m| m.added += written_reviews.len());
+                            if let Err(e) = ReviewLinks::record(&cache_manager.paths().cache_review_links_dir(), source_name, &plan.reviews, &written) {
+                                warn!("Failed to record review links for {}: {}", source_name, e);
+                            }
+                            // Counted as present until the source's API lists them (Trakt lags minutes)
+                            if let Err(e) = cache_manager.record_submitted_reviews(source_name, &written_reviews) {
+                                warn!("{}", e);
+                            }
+                            if plan.deferred == 0 {
+                                if let Err(e) = strategy.on_sync_complete("reviews", written_reviews.len()) {
+                                    warn!("Failed to update sync timestamp: {}", e);
+                                }
+                            }
+                        }
+                    }
                 }
                 
-                // Distribute watch history
-                if !watch_history.is_empty() && sync_options.sync_watch_history {
+                // Distribute watch history, one watched year at a time with watch_history.chunk_by_year
+                if !plan.watch_history_add.is_empty() && sync_options.sync_watch_history {
                     let source_guard = source_arc.read().await;
-                    if let Err(e) = source_guard.add_watch_history(&watch_history).await {
-                        errors_arc.lock().await.push(format!("Failed to add watch history to {}: {}", source_name, e));
-                                            } else {
-                        *items_synced_arc.lock().await += watch_history.len();
-                        if let Err(e) = strategy.on_sync_complete("watch_history", watch_history.len()) {
-                                                    warn!("Failed to update sync timestamp: {}", e);
+                    let chunks = if chunk_by_year {
+                        chunk_watch_history_by_year(&plan.watch_history_add)
+                    } else {
+                        vec![plan.watch_history_add.clone()]
+                    };
+                    let chunk_count = chunks.len();
+                    let mut synced_up_to: Option<DateTime<Utc>> = None;
+                    for (index, chunk) in chunks.iter().enumerate() {
+                        if let Err(e) = source_guard.add_watch_history(chunk).await {
+                            let error = match synced_up_to {
+                                Some(boundary) => format!(
+                                    "Failed to add watch history to {}: {} (synced up to {}, the next run resumes from there)",
+                                    source_name, e, boundary.year()
+                                ),
+                                None => format!("Failed to add watch history to {}: {}", source_name, e),
+                            };
+                            errors_arc.lock().await.push(error);
+                            // The rest of this chunk and the years after it weren't sent
+                            let committed = e.committed().min(chunk.len());
+                            failed.watch_history_add = [&chunk[committed..], &chunks[index + 1..].concat()].concat();
+                            count(metrics, source_name, DataType::WatchHistory, |m| {
+                                m.added += committed;
+                                m.errors += failed.watch_history_add.len();
+                            });
+                            break;
+                        }
+                        count(metrics, source_name, DataType::WatchHistory, |m| m.added += chunk.len());
+                        if index + 1 < chunk_count && plan.deferred == 0 {
+                            let boundary = chunk.iter().map(|item| item.watched_at).max();
+                            if let Some(boundary) = boundary {
+                                info!(source = source_name, "Watch history synced to {} up to {} ({} of {} years)", source_name, boundary.year(), index + 1, chunk_count);
+                                if let Err(e) = strategy.on_watch_history_synced_up_to(boundary) {
+                                    warn!("Failed to update sync timestamp: {}", e);
+                                }
+                                synced_up_to = Some(boundary);
+                            }
+                        }
+                    }
+                    if failed.watch_history_add.is_empty() && plan.deferred == 0 {
+                        if let Err(e) = strategy.on_sync_complete("watch_history", plan.watch_history_add.len()) {
+                            warn!("Failed to update sync timestamp: {}", e);
+                        }
+                    }
+                }
+
+                // Send partial plays as playback progress
+                if !plan.playback_progress.is_empty() && sync_options.sync_watch_history {
+                    let source_guard = source_arc.read().await;
+                    if let Some(playback) = source_guard.as_playback_progress() {
+                        for item in &plan.playback_progress {
+                            let progress = item.watched_percent.unwrap_or(0.0);
+                            match playback.scrobble(item, progress).await {
+                                Ok(()) => count(metrics, source_name, DataType::WatchHistory, |m| m.added += 1),
+                                Err(e) => {
+                                    count(metrics, source_name, DataType::WatchHistory, |m| m.errors += 1);
+                                    errors_arc.lock().await.push(format!(
+                                        "Failed to send playback progress for {} to {}: {}",
+                                        item.imdb_id, source_name, e
+                                    ));
+                                    failed.playback_progress.push(item.clone());
                                 }
                             }
                         }
+                    } else {
+                        warn!("{} does not track playback progress, skipping {} partial plays", source_name, plan.playback_progress.len());
+                        count(metrics, source_name, DataType::WatchHistory, |m| m.skipped += plan.playback_progress.len());
+                    }
+                }
                 }
                 _ => {
                 errors_arc.lock().await.push(format!("Unknown source in source_preference: {}", source_name));
             }
         }
-        
-        Ok(())
+        Some(failed)
     }
     
     async fn sync_source_ratings_static(
@@ -2051,7 +3773,7 @@ impl SyncOrchestrator {
             source_ratings
                 .into_iter()
                 .map(|r| Rating {
-                    rating: normalizer.normalize_rating(r.rating as f64, trakt_scale),
+                    rating: normalizer.normalize_rating(r.rating as f64, trakt_scale, &r.media_type),
                     ..r
                 })
                 .collect()
@@ -2073,11 +3795,10 @@ impl SyncOrchestrator {
 
         // Replicate Trakt ratings back to source (denormalize from Trakt format)
         let trakt_ratings_for_source: Vec<Rating> = if let Some(normalizer) = source.as_rating_normalization() {
-            let source_scale = normalizer.native_rating_scale();
             trakt_ratings
                 .iter()
                 .map(|r| Rating {
-                    rating: normalizer.denormalize_rating(r.rating, source_scale) as u8,
+                    rating: normalizer.denormalize_rating(r.rating, normalizer.native_rating_scale(&r.media_type), &r.media_type) as u8,
                     ..r.clone()
                 })
                 .collect()
@@ -2124,6 +3845,8 @@ impl SyncOrchestrator {
 
         info!("Starting IMDB sync");
 
+        let from_ratings = config_sync_options.from_ratings_mode();
+
         // Fetch all data (only what's needed based on sync options)
         // When specific flags are passed, only fetch data for those flags
         // Advanced features (remove_watched_from_watchlists, mark_rated_as_watched) require additional data
@@ -2179,12 +3902,12 @@ impl SyncOrchestrator {
             }
         }
 
-        if sync_options.sync_ratings || (config_sync_options.mark_rated_as_watched && !any_specific_sync) {
-            info!("Fetching ratings data (sync_ratings={}, mark_rated_as_watched={}, any_specific_sync={})", 
-                sync_options.sync_ratings, config_sync_options.mark_rated_as_watched, any_specific_sync);
+        if sync_options.sync_ratings || (from_ratings.is_enabled() && !any_specific_sync) {
+            info!("Fetching ratings data (sync_ratings={}, from_ratings={:?}, any_specific_sync={})", 
+                sync_options.sync_ratings, from_ratings, any_specific_sync);
             let errors_arc = Arc::new(tokio::sync::Mutex::new(Vec::<String>::new()));
             imdb_ratings = filter_missing_imdb_ids(
-                Self::fetch_or_cache_ratings(imdb.clone(), cache_manager, "imdb", use_cache, sync_options.force_full_sync, errors_arc.clone()).await
+                Self::fetch_or_cache_ratings(imdb.clone(), cache_manager, "imdb", use_cache, sync_options.force_full_sync, config_sync_options.ratings.default_date, errors_arc.clone()).await
             );
             info!("Fetched {} IMDB ratings", imdb_ratings.len());
             // Debug: Log first few ratings from each source
@@ -2198,13 +3921,13 @@ impl SyncOrchestrator {
             }
             
             trakt_ratings = filter_missing_imdb_ids(
-                Self::fetch_or_cache_ratings(trakt.clone(), cache_manager, "trakt", use_cache, sync_options.force_full_sync, errors_arc.clone()).await
+                Self::fetch_or_cache_ratings(trakt.clone(), cache_manager, "trakt", use_cache, sync_options.force_full_sync, config_sync_options.ratings.default_date, errors_arc.clone()).await
             );
             info!("Fetched {} Trakt ratings", trakt_ratings.len());
             info!("Total: {} IMDB ratings, {} Trakt ratings", imdb_ratings.len(), trakt_ratings.len());
         } else {
-            info!("Skipping ratings fetch (sync_ratings={}, mark_rated_as_watched={}, any_specific_sync={})", 
-                sync_options.sync_ratings, config_sync_options.mark_rated_as_watched, any_specific_sync);
+            info!("Skipping ratings fetch (sync_ratings={}, from_ratings={:?}, any_specific_sync={})", 
+                sync_options.sync_ratings, from_ratings, any_specific_sync);
         }
 
         if sync_options.sync_reviews {
@@ -2222,10 +3945,10 @@ impl SyncOrchestrator {
         // Only fetch watch history if explicitly requested OR if advanced feature needs it (and we're doing a full sync)
         let should_fetch_watch_history = sync_options.sync_watch_history
             || (config_sync_options.remove_watched_from_watchlists && !any_specific_sync)
-            || (config_sync_options.mark_rated_as_watched && !any_specific_sync);
+            || (from_ratings.is_enabled() && !any_specific_sync);
         if should_fetch_watch_history {
-            info!("Fetching watch history data (sync_watch_history={}, remove_watched_from_watchlists={}, mark_rated_as_watched={}, any_specific_sync={})", 
-                sync_options.sync_watch_history, config_sync_options.remove_watched_from_watchlists, config_sync_options.mark_rated_as_watched, any_specific_sync);
+            info!("Fetching watch history data (sync_watch_history={}, remove_watched_from_watchlists={}, from_ratings={:?}, any_specific_sync={})", 
+                sync_options.sync_watch_history, config_sync_options.remove_watched_from_watchlists, from_ratings, any_specific_sync);
             let errors_arc = Arc::new(tokio::sync::Mutex::new(Vec::<String>::new()));
             imdb_history = filter_missing_imdb_ids(
                 Self::fetch_or_cache_watch_history(imdb.clone(), cache_manager, "imdb", use_cache, sync_options.force_full_sync, errors_arc.clone()).await
@@ -2237,14 +3960,10 @@ impl SyncOrchestrator {
             );
             info!("Fetched {} Trakt watch history items", trakt_history.len());
         } else {
-            info!("Skipping watch history fetch (sync_watch_history={}, remove_watched_from_watchlists={}, mark_rated_as_watched={}, any_specific_sync={})",
-                sync_options.sync_watch_history, config_sync_options.remove_watched_from_watchlists, config_sync_options.mark_rated_as_watched, any_specific_sync);
+            info!("Skipping watch history fetch (sync_watch_history={}, remove_watched_from_watchlists={}, from_ratings={:?}, any_specific_sync={})",
+                sync_options.sync_watch_history, config_sync_options.remove_watched_from_watchlists, from_ratings, any_specific_sync);
         }
 
-        // Check IMDB limits
-        let imdb_watchlist_limit_reached = imdb_watchlist.len() >= 10_000;
-        let imdb_watch_history_limit_reached = imdb_history.len() >= 10_000;
-
         // Remove duplicates from watch history
         trakt_history = crate::diff::remove_duplicates_by_imdb_id(trakt_history);
 
@@ -2314,14 +4033,12 @@ impl SyncOrchestrator {
             let before_filter = items.len();
             let filtered_items: Vec<_> = items.into_iter()
                 .filter(|rating| {
-                    if let media_sync_models::media::MediaType::Episode { season, episode } = rating.media_type {
-                        if season == 0 && episode == 0 {
-                            debug!(
-                                "Skipping Episode rating with placeholder season/episode (0, 0): imdb_id={}",
-                                rating.imdb_id
-                            );
-                            return false;
-                        }
+                    if rating.media_type.is_placeholder_episode() {
+                        debug!(
+                            "Skipping Episode rating with placeholder season/episode (0, 0): imdb_id={}",
+                            rating.imdb_id
+                        );
+                        return false;
                     }
                     true
                 })
@@ -2362,7 +4079,7 @@ impl SyncOrchestrator {
 
         let mut imdb_reviews_to_set = if sync_options.sync_reviews {
             // Use content-aware filtering for reviews to prevent duplicates
-            let items = crate::diff::filter_reviews_by_imdb_id_and_content(&imdb_reviews, &trakt_reviews);
+            let items = crate::diff::filter_reviews_by_imdb_id_and_content(&imdb_reviews, &trakt_reviews, &cache_manager.recently_submitted_reviews("trakt"));
             info!("Found {} IMDB reviews to add to Trakt (after filtering against {} Trakt reviews by IMDB ID and content)", items.len(), trakt_reviews.len());
             if items.is_empty() && !imdb_reviews.is_empty() {
                 info!("All {} IMDB reviews already exist in Trakt", imdb_reviews.len());
@@ -2373,7 +4090,7 @@ impl SyncOrchestrator {
         };
         let mut trakt_reviews_to_set = if sync_options.sync_reviews {
             // Use content-aware filtering for reviews to prevent duplicates
-            let items = crate::diff::filter_reviews_by_imdb_id_and_content(&trakt_reviews, &imdb_reviews);
+            let items = crate::diff::filter_reviews_by_imdb_id_and_content(&trakt_reviews, &imdb_reviews, &cache_manager.recently_submitted_reviews("imdb"));
             info!("Found {} Trakt reviews to add to IMDB (after filtering against {} IMDB reviews by IMDB ID and content)", items.len(), imdb_reviews.len());
             if items.is_empty() && !trakt_reviews.is_empty() {
                 info!("All {} Trakt reviews already exist in IMDB", trakt_reviews.len());
@@ -2429,14 +4146,12 @@ impl SyncOrchestrator {
             // Filter out episodes with placeholder season/episode numbers (0, 0)
             let before_episode_filter = filtered_items.len();
             filtered_items.retain(|item| {
-                if let media_sync_models::media::MediaType::Episode { season, episode } = item.media_type {
-                    if season == 0 && episode == 0 {
-                        debug!(
-                            "Skipping Episode with placeholder season/episode (0, 0) from IMDB watch history: imdb_id={}",
-                            item.imdb_id
-                        );
-                        return false;
-                    }
+                if item.media_type.is_placeholder_episode() {
+                    debug!(
+                        "Skipping Episode with placeholder season/episode (0, 0) from IMDB watch history: imdb_id={}",
+                        item.imdb_id
+                    );
+                    return false;
                 }
                 true
             });
@@ -2471,76 +4186,54 @@ impl SyncOrchestrator {
             Vec::new()
         };
 
-        // Advanced feature: Mark rated as watched
+        // Advanced feature: Mark rated as watched (watch_history.from_ratings)
         // This feature creates watch history entries from ratings, and should run whenever:
         // 1. The feature is enabled in config
         // 2. Ratings are available (either synced or fetched for this feature)
         // The created watch history entries will be synced if sync_watch_history is enabled
-        if config_sync_options.mark_rated_as_watched 
+        if from_ratings.is_enabled()
             && (!imdb_ratings.is_empty() || !trakt_ratings.is_empty()) {
-            info!("Running mark_rated_as_watched feature (ratings available: {} IMDB, {} Trakt)", 
-                imdb_ratings.len(), trakt_ratings.len());
+            info!("Creating watch history from ratings (mode={:?}, ratings available: {} IMDB, {} Trakt)", 
+                from_ratings, imdb_ratings.len(), trakt_ratings.len());
             
             // Ensure we have watch history data to check against (fetch if needed)
             if trakt_history.is_empty() && imdb_history.is_empty() {
-                info!("Fetching watch history to check for existing entries (needed for mark_rated_as_watched)");
+                info!("Fetching watch history to check for existing entries (needed for watch_history.from_ratings)");
                 let errors_arc = Arc::new(tokio::sync::Mutex::new(Vec::<String>::new()));
                 imdb_history = filter_missing_imdb_ids(
                     Self::fetch_or_cache_watch_history(imdb.clone(), cache_manager, "imdb", use_cache, sync_options.force_full_sync, errors_arc.clone()).await
                 );
-                info!("Fetched {} IMDB watch history items for mark_rated_as_watched check", imdb_history.len());
+                info!("Fetched {} IMDB watch history items for watch_history.from_ratings check", imdb_history.len());
                 
                 trakt_history = filter_missing_imdb_ids(
                     Self::fetch_or_cache_watch_history(trakt.clone(), cache_manager, "trakt", use_cache, sync_options.force_full_sync, errors_arc.clone()).await
                 );
-                info!("Fetched {} Trakt watch history items for mark_rated_as_watched check", trakt_history.len());
+                info!("Fetched {} Trakt watch history items for watch_history.from_ratings check", trakt_history.len());
             }
             
             let mut combined_ratings = trakt_ratings.clone();
             combined_ratings.extend(imdb_ratings.clone());
             let combined_ratings = crate::diff::remove_duplicates_by_imdb_id(combined_ratings);
 
-            let trakt_history_ids: HashSet<String> = trakt_history.iter().map(|h| h.imdb_id.clone()).collect();
-            let imdb_history_ids: HashSet<String> = imdb_history.iter().map(|h| h.imdb_id.clone()).collect();
-
-            let mut items_marked = 0;
-            for rating in &combined_ratings {
-                // Skip shows (cannot be marked as watched on Trakt)
-                if matches!(rating.media_type, media_sync_models::media::MediaType::Show) {
-                    continue;
-                }
-
-                if !trakt_history_ids.contains(&rating.imdb_id)
-                    && !imdb_history_ids.contains(&rating.imdb_id)
-                {
-                    info!(
-                        imdb_id = %rating.imdb_id,
-                        rating = rating.rating,
-                        media_type = ?rating.media_type,
-                        "Marking rated item as watched (mark_rated_as_watched feature)"
-                    );
-                    let history_item = WatchHistory {
-                        imdb_id: rating.imdb_id.clone(),
-                        ids: rating.ids.clone(),
-                        title: None,
-                        year: None,
-                        watched_at: rating.date_added,
-                        media_type: rating.media_type.clone(),
-                        source: "rated".to_string(),
-                    };
+            let watched_ids: HashSet<String> = trakt_history.iter()
+                .chain(imdb_history.iter())
+                .map(|h| h.imdb_id.clone())
+                .filter(|id| !id.is_empty())
+                .collect();
 
-                    imdb_history_to_set.push(history_item.clone());
-                    trakt_history_to_set.push(history_item.clone());
+            let marked = Self::watch_history_from_ratings(&combined_ratings, &watched_ids, from_ratings);
+            let items_marked = marked.len();
+            for history_item in marked {
+                imdb_history_to_set.push(history_item.clone());
+                trakt_history_to_set.push(history_item.clone());
 
-                    // Add to history lists to prevent re-adding
-                    imdb_history.push(history_item.clone());
-                    trakt_history.push(history_item);
-                    items_marked += 1;
-                }
+                // Add to history lists to prevent re-adding
+                imdb_history.push(history_item.clone());
+                trakt_history.push(history_item);
             }
 
             if items_marked > 0 {
-                info!("Marked {} rated items as watched via mark_rated_as_watched feature", items_marked);
+                info!("Marked {} rated items as watched (watch_history.from_ratings)", items_marked);
             } else {
                 info!("No new items to mark as watched (all rated items already in watch history)");
             }
@@ -2548,8 +4241,8 @@ impl SyncOrchestrator {
             // Remove duplicates
             imdb_history = crate::diff::remove_duplicates_by_imdb_id(imdb_history);
             trakt_history = crate::diff::remove_duplicates_by_imdb_id(trakt_history);
-        } else if config_sync_options.mark_rated_as_watched {
-            info!("mark_rated_as_watched is enabled but no ratings are available to process");
+        } else if from_ratings.is_enabled() {
+            info!("watch_history.from_ratings is {:?} but no ratings are available to process", from_ratings);
         }
 
         // Advanced feature: Rating updates (prefer more recent rating)
@@ -2681,53 +4374,15 @@ impl SyncOrchestrator {
             trakt_watchlist_to_set.retain(|item| !old_ids.contains(&item.imdb_id));
         }
 
-        // Advanced feature: Filter reviews by length (minimum 600 characters)
+        // Advanced feature: Filter reviews by length (sync.min_review_length)
         if sync_options.sync_reviews {
-            let before_count = imdb_reviews_to_set.len();
-            let mut filtered_count = 0;
-            let mut filtered_reviews = Vec::new();
-            
-            imdb_reviews_to_set.retain(|review| {
-                if review.content.len() >= 600 {
-                    true
-                } else {
-                    filtered_count += 1;
-                    filtered_reviews.push((review.imdb_id.clone(), review.content.len()));
-                    false
-                }
-            });
-            
-            if filtered_count > 0 {
-                warn!(
-                    "Filtered out {} IMDB reviews shorter than 600 characters ({} remaining). Reviews filtered: {:?}",
-                    filtered_count,
-                    imdb_reviews_to_set.len(),
-                    filtered_reviews.iter().take(5).map(|(id, len)| format!("{} ({} chars)", id, len)).collect::<Vec<_>>()
-                );
-            } else if before_count > 0 {
-                info!("All {} IMDB reviews meet the 600 character minimum", before_count);
-            }
-            
-            // Also filter Trakt reviews going to IMDB
-            let trakt_before_count = trakt_reviews_to_set.len();
-            let mut trakt_filtered_count = 0;
-            trakt_reviews_to_set.retain(|review| {
-                if review.content.len() >= 600 {
-                    true
-                } else {
-                    trakt_filtered_count += 1;
-                    false
+            for (target, reviews) in [("trakt", &mut imdb_reviews_to_set), ("imdb", &mut trakt_reviews_to_set)] {
+                let min_review_length = config_sync_options.min_review_length(target);
+                let before_count = reviews.len();
+                let filtered_count = crate::review_text::remove_short_reviews(reviews, min_review_length, target);
+                if let Some(min_length) = min_review_length.filter(|_| filtered_count == 0 && before_count > 0) {
+                    info!("All {} reviews for {} meet the {} character minimum", before_count, target, min_length);
                 }
-            });
-            
-            if trakt_filtered_count > 0 {
-                warn!(
-                    "Filtered out {} Trakt reviews shorter than 600 characters ({} remaining)",
-                    trakt_filtered_count,
-                    trakt_reviews_to_set.len()
-                );
-            } else if trakt_before_count > 0 {
-                info!("All {} Trakt reviews meet the 600 character minimum", trakt_before_count);
             }
         }
 
@@ -2742,17 +4397,20 @@ impl SyncOrchestrator {
         imdb_history_to_set.sort_by_key(|h| h.watched_at);
         trakt_history_to_set.sort_by_key(|h| h.watched_at);
 
-        // Apply IMDB limits
-        if sync_options.sync_watchlist && imdb_watchlist_limit_reached {
-            imdb_watchlist_to_set.clear();
-            warn!("IMDB watchlist limit (10,000) reached, skipping additions");
+        // Apply IMDB limits, adding the oldest items that fit
+        let watchlist_over_limit = fit_to_limit(&mut trakt_watchlist_to_set, imdb_watchlist.len(), IMDB_ITEM_LIMIT, |item| item.date_added);
+        if !watchlist_over_limit.is_empty() {
+            warn!("IMDB watchlist limit ({}) reached, leaving out the {} newest additions", IMDB_ITEM_LIMIT, watchlist_over_limit.len());
         }
-
-        if (sync_options.sync_watch_history || config_sync_options.mark_rated_as_watched)
-            && imdb_watch_history_limit_reached
-        {
-            imdb_history_to_set.clear();
-            warn!("IMDB watch history limit (10,000) reached, skipping additions");
+        for item in &watchlist_over_limit {
+            info!(imdb_id = %item.imdb_id, "Left out of the IMDB watchlist (limit reached): {}", item.title);
+        }
+        let history_over_limit = fit_to_limit(&mut trakt_history_to_set, imdb_history.len(), IMDB_ITEM_LIMIT, |item| item.watched_at);
+        if !history_over_limit.is_empty() {
+            warn!("IMDB watch history limit ({}) reached, leaving out the {} newest additions", IMDB_ITEM_LIMIT, history_over_limit.len());
+        }
+        for item in &history_over_limit {
+            info!(imdb_id = %item.imdb_id, "Left out of the IMDB watch history (limit reached): {}", item.title.as_deref().unwrap_or(&item.imdb_id));
         }
 
         // Track total items synced
@@ -2780,7 +4438,7 @@ impl SyncOrchestrator {
                 info!("No IMDB watchlist items to add to Trakt (all items already exist in Trakt)");
             }
             
-            if !trakt_watchlist_to_set.is_empty() && !imdb_watchlist_limit_reached {
+            if !trakt_watchlist_to_set.is_empty() {
                 for item in &trakt_watchlist_to_set {
                     debug!(
                         imdb_id = %item.imdb_id,
@@ -2797,7 +4455,13 @@ impl SyncOrchestrator {
             }
 
             // Remove from watchlists
-            if !trakt_watchlist_to_remove.is_empty() {
+            if sync_options.never_remove {
+                info!(
+                    "Would remove {} items from Trakt and {} from IMDB watchlist, skipped due to never_remove",
+                    trakt_watchlist_to_remove.len(),
+                    imdb_watchlist_to_remove.len()
+                );
+            } else if !trakt_watchlist_to_remove.is_empty() {
                 for item in &trakt_watchlist_to_remove {
                     debug!(
                         imdb_id = %item.imdb_id,
@@ -2809,7 +4473,7 @@ impl SyncOrchestrator {
                 trakt.read().await.remove_from_watchlist(&trakt_watchlist_to_remove).await?;
                 info!("Removed {} items from Trakt watchlist", trakt_watchlist_to_remove.len());
             }
-            if !imdb_watchlist_to_remove.is_empty() && !imdb_watchlist_limit_reached {
+            if !imdb_watchlist_to_remove.is_empty() && !sync_options.never_remove {
                 for item in &imdb_watchlist_to_remove {
                     debug!(
                         imdb_id = %item.imdb_id,
@@ -2831,7 +4495,7 @@ impl SyncOrchestrator {
                 imdb_ratings_to_set
                     .iter()
                     .map(|r| Rating {
-                        rating: normalizer.normalize_rating(r.rating as f64, trakt_scale),
+                        rating: normalizer.normalize_rating(r.rating as f64, trakt_scale, &r.media_type),
                         ..r.clone()
                     })
                     .collect()
@@ -2903,7 +4567,11 @@ impl SyncOrchestrator {
                 trakt.read().await.set_reviews(&imdb_reviews_to_set).await?;
                 items_synced += imdb_reviews_to_set.len();
                 info!("Successfully added {} reviews to Trakt", imdb_reviews_to_set.len());
-                warn!("Newly added Trakt reviews may take a few minutes to appear in the API. If you sync again immediately, they may appear as duplicates until the API indexes them.");
+                // Trakt takes a few minutes to list new comments; until then the ledger keeps
+                // an immediate re-sync from sending them again
+                if let Err(e) = cache_manager.record_submitted_reviews("trakt", &imdb_reviews_to_set) {
+                    warn!("{}", e);
+                }
             } else {
                 info!("No IMDB reviews to sync to Trakt (all reviews already exist or were filtered out)");
             }
@@ -2923,6 +4591,9 @@ impl SyncOrchestrator {
                 imdb.read().await.set_reviews(&trakt_reviews_to_set).await?;
                 items_synced += trakt_reviews_to_set.len();
                 info!("Successfully added {} reviews to IMDB", trakt_reviews_to_set.len());
+                if let Err(e) = cache_manager.record_submitted_reviews("imdb", &trakt_reviews_to_set) {
+                    warn!("{}", e);
+                }
             } else {
                 info!("No Trakt reviews to sync to IMDB (all reviews already exist or were filtered out)");
             }
@@ -2961,7 +4632,7 @@ impl SyncOrchestrator {
                     added_ids
                 );
             }
-            if !trakt_history_to_set.is_empty() && !imdb_watch_history_limit_reached {
+            if !trakt_history_to_set.is_empty() {
                 for item in &trakt_history_to_set {
                     debug!(
                         imdb_id = %item.imdb_id,
@@ -2983,3 +4654,707 @@ impl SyncOrchestrator {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all_data_types() -> SyncOptions {
+        SyncOptions {
+            sync_watchlist: true,
+            sync_ratings: true,
+            sync_reviews: true,
+            sync_watch_history: true,
+            force_full_sync: false,
+            since: None,
+            no_write: false,
+            skip_unconfigured_sources: false,
+            sync_lists: false,
+            sync_collection: false,
+            sync_show_progress: false,
+            never_remove: false,
+            max_items: None,
+        }
+    }
+
+    #[test]
+    fn test_gate_sync_options_skips_plex_reviews() {
+        let plex = media_sync_sources::plex::PlexClient::new(
+            "token".to_string(),
+            media_sync_config::default_plex_status_mapping(),
+        );
+        let gated = SyncOrchestrator::gate_sync_options_for_source(&all_data_types(), "plex", plex.supported_data_types());
+        assert!(!gated.sync_reviews);
+        assert!(gated.sync_watchlist);
+        assert!(gated.sync_ratings);
+        assert!(gated.sync_watch_history);
+    }
+
+    #[test]
+    fn test_gate_sync_options_keeps_supported_and_disabled_types() {
+        let gated = SyncOrchestrator::gate_sync_options_for_source(&all_data_types(), "trakt", &DataType::ALL);
+        assert!(gated.sync_watchlist && gated.sync_ratings && gated.sync_reviews && gated.sync_watch_history);
+
+        // Types already disabled stay disabled
+        let options = SyncOptions { sync_ratings: false, ..all_data_types() };
+        let gated = SyncOrchestrator::gate_sync_options_for_source(&options, "trakt", &DataType::ALL);
+        assert!(!gated.sync_ratings);
+    }
+
+    #[test]
+    fn test_rated_movies_and_episodes_are_marked_watched_but_not_shows() {
+        let rating = |imdb_id: &str, media_type: MediaType| Rating {
+            imdb_id: imdb_id.to_string(),
+            ids: None,
+            rating: 8,
+            rating_f64: None,
+            date_added: Utc::now(),
+            media_type,
+            source: media_sync_models::RatingSource::Imdb,
+            inferred: false,
+        };
+        let ratings = vec![
+            rating("tt0133093", MediaType::Movie),
+            rating("tt0959621", MediaType::Episode { season: 1, episode: 1 }),
+            rating("tt0903747", MediaType::Show),
+            rating("tt1234567", MediaType::Episode { season: 0, episode: 0 }),
+        ];
+        let watched = std::collections::HashSet::new();
+
+        let marked = SyncOrchestrator::watch_history_from_ratings(&ratings, &watched, media_sync_config::FromRatingsMode::AllNonShow);
+        let marked: Vec<_> = marked.iter().map(|item| (item.imdb_id.as_str(), item.media_type.clone())).collect();
+        assert_eq!(marked, vec![
+            ("tt0133093", MediaType::Movie),
+            ("tt0959621", MediaType::Episode { season: 1, episode: 1 }),
+        ]);
+
+        let marked = SyncOrchestrator::watch_history_from_ratings(&ratings, &watched, media_sync_config::FromRatingsMode::MoviesOnly);
+        assert_eq!(marked.len(), 1);
+        assert_eq!(marked[0].media_type, MediaType::Movie);
+    }
+
+    #[test]
+    fn test_exit_code_reflects_source_outcomes() {
+        let outcome = |source: &str, auth_failed: bool, errors: &[&str]| SourceOutcome {
+            source: source.to_string(),
+            auth_failed,
+            errors: errors.iter().map(|e| e.to_string()).collect(),
+        };
+        let result = |sources: Vec<SourceOutcome>| SyncResult {
+            items_synced: 0,
+            items_deferred: 0,
+            resolution_skipped: false,
+            duration: Duration::ZERO,
+            errors: sources.iter().flat_map(|s| s.errors.clone()).collect(),
+            sources,
+            per_source: Vec::new(),
+        };
+
+        assert_eq!(result(vec![outcome("trakt", false, &[]), outcome("plex", false, &[])]).exit_code(), 0);
+        assert_eq!(result(vec![outcome("trakt", false, &[]), outcome("plex", false, &["timeout"])]).exit_code(), EXIT_PARTIAL);
+        assert_eq!(result(vec![outcome("trakt", false, &["503"]), outcome("plex", false, &["timeout"])]).exit_code(), EXIT_FAILED);
+        assert_eq!(result(vec![outcome("trakt", true, &["401"]), outcome("plex", false, &[])]).exit_code(), EXIT_CONFIG);
+    }
+
+    #[test]
+    fn test_cap_watchlist_additions_defers_overflow() {
+        let items: Vec<WatchlistItem> = (1..=5)
+            .map(|i| WatchlistItem {
+                imdb_id: format!("tt{:07}", i),
+                ids: None,
+                title: format!("Movie {}", i),
+                year: None,
+                media_type: media_sync_models::MediaType::Movie,
+                date_added: Utc::now(),
+                source: "imdb".to_string(),
+                status: None,
+                tags: Vec::new(),
+            })
+            .collect();
+
+        let mut capped = items.clone();
+        assert_eq!(SyncOrchestrator::cap_watchlist_additions(&mut capped, Some(2), "trakt"), 3);
+        assert_eq!(capped, items[..2].to_vec());
+
+        let mut uncapped = items.clone();
+        assert_eq!(SyncOrchestrator::cap_watchlist_additions(&mut uncapped, Some(5), "trakt"), 0);
+        assert_eq!(SyncOrchestrator::cap_watchlist_additions(&mut uncapped, None, "trakt"), 0);
+        assert_eq!(uncapped.len(), 5);
+    }
+
+    #[test]
+    fn test_write_budget_is_shared_across_sources_and_defers_the_rest() {
+        let items: Vec<WatchlistItem> = (1..=3)
+            .map(|i| WatchlistItem {
+                imdb_id: format!("tt{:07}", i),
+                ids: None,
+                title: format!("Movie {}", i),
+                year: None,
+                media_type: media_sync_models::MediaType::Movie,
+                date_added: Utc::now(),
+                source: "imdb".to_string(),
+                status: None,
+                tags: Vec::new(),
+            })
+            .collect();
+        let metrics = std::sync::Mutex::new(MetricsAccumulator::default());
+        let mut remaining = 4;
+
+        let mut trakt = SourcePlan::new("trakt");
+        trakt.watchlist_add = items.clone();
+        assert!(SyncOrchestrator::take_write_budget(&mut trakt, &mut remaining, &metrics).is_empty());
+        assert_eq!(remaining, 1);
+
+        let mut simkl = SourcePlan::new("simkl");
+        simkl.watchlist_add = items.clone();
+        simkl.watchlist_remove = items[..1].to_vec();
+        assert_eq!(SyncOrchestrator::take_write_budget(&mut simkl, &mut remaining, &metrics).total_items(), 3);
+        assert_eq!(remaining, 0);
+        assert_eq!(simkl.watchlist_add, items[..1].to_vec());
+        assert!(simkl.watchlist_remove.is_empty());
+        assert_eq!(simkl.deferred, 3);
+        assert_eq!(metrics.lock().unwrap().entry("simkl", DataType::Watchlist).skipped, 3);
+        assert_eq!(metrics.lock().unwrap().entry("trakt", DataType::Watchlist).skipped, 0);
+    }
+
+    #[test]
+    fn test_merge_changes_replaces_changed_items_in_cached_list() {
+        let rating = |imdb_id: &str, value: u8| media_sync_models::Rating {
+            imdb_id: imdb_id.to_string(),
+            ids: None,
+            rating: value,
+            rating_f64: None,
+            date_added: Utc::now(),
+            media_type: media_sync_models::MediaType::Movie,
+            source: media_sync_models::RatingSource::Trakt,
+            inferred: false,
+        };
+        let cached = vec![rating("tt0000001", 7), rating("tt0000002", 5)];
+        let changes = vec![rating("tt0000002", 9), rating("tt0000003", 6)];
+
+        let merged = SyncOrchestrator::merge_changes(cached, changes, |r| SyncOrchestrator::change_key(&r.imdb_id, &r.ids));
+        let values: Vec<(&str, u8)> = merged.iter().map(|r| (r.imdb_id.as_str(), r.rating)).collect();
+        assert_eq!(values, vec![("tt0000001", 7), ("tt0000002", 9), ("tt0000003", 6)]);
+    }
+
+    #[test]
+    fn test_implausible_ratings_follow_the_source_scale() {
+        use media_sync_sources::RatingNormalization;
+        let rating = |value: u8, media_type: MediaType| media_sync_models::Rating {
+            imdb_id: "tt0000001".to_string(),
+            ids: None,
+            rating: value,
+            rating_f64: None,
+            date_added: Utc::now(),
+            media_type,
+            source: media_sync_models::RatingSource::Plex,
+            inferred: false,
+        };
+        let plex = media_sync_sources::plex::PlexClient::new("token".to_string(), media_sync_config::default_plex_status_mapping())
+            .with_rating_scales(media_sync_config::RatingScales { show: Some(5), ..Default::default() });
+        let scale = |media_type: &MediaType| plex.native_rating_scale(media_type);
+
+        // A show cached as 8 out of 10 before shows were rated 1-5, and a 0
+        let cached = vec![rating(10, MediaType::Movie), rating(5, MediaType::Show), rating(8, MediaType::Show), rating(0, MediaType::Movie)];
+        assert_eq!(SyncOrchestrator::implausible_ratings(&cached, scale), 2);
+        assert_eq!(SyncOrchestrator::implausible_ratings(&cached[..2], scale), 0);
+    }
+
+    #[test]
+    fn test_undated_ratings_resolve_by_default_date() {
+        use chrono::TimeZone;
+        let rating = |value: u8, date_added: DateTime<Utc>| media_sync_models::Rating {
+            imdb_id: "tt0113277".to_string(),
+            ids: None,
+            rating: value,
+            rating_f64: None,
+            date_added,
+            media_type: MediaType::Movie,
+            source: media_sync_models::RatingSource::Trakt,
+            inferred: false,
+        };
+        let source = |ratings| SourceData { watchlist: vec![], ratings, reviews: vec![], watch_history: vec![] };
+        let trakt = source(vec![rating(7, Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap())]);
+        let fetched_at = Utc.with_ymd_and_hms(2024, 6, 1, 12, 0, 0).unwrap();
+        let config = media_sync_config::ResolutionConfig {
+            strategy: media_sync_config::ResolutionStrategy::Newest,
+            ..Default::default()
+        };
+
+        let resolve = |default_date| {
+            let mut plex_ratings = vec![rating(9, Rating::UNDATED), rating(9, Rating::UNDATED)];
+            SyncOrchestrator::date_undated_ratings(&mut plex_ratings, default_date, fetched_at);
+            assert_eq!(plex_ratings[0].date_added, plex_ratings[1].date_added);
+            plex_ratings.truncate(1);
+            let plex = source(plex_ratings);
+            crate::resolution::resolve_all_conflicts(&[("trakt", &trakt), ("plex", &plex)], &config).ratings
+        };
+
+        // An undated rating dated at import is the newest; at the epoch it never is
+        let resolved = resolve(DefaultRatingDate::SourceImportTime);
+        assert_eq!((resolved[0].rating, resolved[0].date_added), (9, fetched_at));
+        assert_eq!(resolve(DefaultRatingDate::Epoch)[0].rating, 7);
+    }
+
+    #[test]
+    fn test_removed_since_finds_titles_that_left_the_watchlist() {
+        let item = |imdb_id: &str| WatchlistItem {
+            imdb_id: imdb_id.to_string(),
+            ids: None,
+            title: imdb_id.to_string(),
+            year: None,
+            media_type: media_sync_models::MediaType::Movie,
+            date_added: Utc::now(),
+            source: "trakt".to_string(),
+            status: None,
+            tags: Vec::new(),
+        };
+        let previous = vec![item("tt0000001"), item("tt0000002")];
+        let removed = SyncOrchestrator::removed_since(&previous, &[item("tt0000002"), item("tt0000003")], "trakt");
+        let ids: Vec<&str> = removed.iter().map(|i| i.imdb_id.as_str()).collect();
+        assert_eq!(ids, ["tt0000001"]);
+
+        // An empty watchlist is more likely a failed fetch than a cleared one
+        assert!(SyncOrchestrator::removed_since(&previous, &[], "trakt").is_empty());
+    }
+
+    #[test]
+    fn test_exclude_tagged_items_drops_title_everywhere() {
+        let item = |imdb_id: &str, tags: Vec<String>| WatchlistItem {
+            imdb_id: imdb_id.to_string(),
+            ids: None,
+            title: imdb_id.to_string(),
+            year: None,
+            media_type: media_sync_models::MediaType::Movie,
+            date_added: Utc::now(),
+            source: "trakt".to_string(),
+            status: None,
+            tags,
+        };
+        let trakt = SourceData {
+            watchlist: vec![item("tt0000001", vec!["No-Sync".to_string()]), item("tt0000002", Vec::new())],
+            ratings: Vec::new(),
+            reviews: Vec::new(),
+            watch_history: Vec::new(),
+        };
+        let rating = |imdb_id: &str| media_sync_models::Rating {
+            imdb_id: imdb_id.to_string(),
+            ids: None,
+            rating: 8,
+            rating_f64: None,
+            date_added: Utc::now(),
+            media_type: media_sync_models::MediaType::Movie,
+            source: media_sync_models::RatingSource::Imdb,
+            inferred: false,
+        };
+        let resolved = ResolvedData {
+            watchlist: vec![item("tt0000001", Vec::new()), item("tt0000002", Vec::new())],
+            ratings: vec![rating("tt0000001"), rating("tt0000002")],
+            reviews: Vec::new(),
+            watch_history: Vec::new(),
+            playback_progress: Vec::new(),
+        };
+        let sources = vec![("trakt".to_string(), trakt)];
+
+        let (filtered, excluded) = SyncOrchestrator::exclude_tagged_items(&resolved, &sources, &["no-sync".to_string()]);
+        assert_eq!(excluded.len(), 1);
+        assert_eq!(filtered.watchlist.len(), 1);
+        assert_eq!(filtered.ratings[0].imdb_id, "tt0000002");
+
+        let (unfiltered, excluded) = SyncOrchestrator::exclude_tagged_items(&resolved, &sources, &[]);
+        assert!(excluded.is_empty());
+        assert_eq!(unfiltered.ratings.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_no_write_blocks_every_write() {
+        let dir = tempfile::tempdir().unwrap();
+        // A read-only source: any write that gets through fails and is reported
+        let source: Box<dyn MediaSource<Error = SourceError>> = Box::new(FileSource::new("trakt", dir.path()));
+        let source_arc = Arc::new(RwLock::new(source));
+        let strategy = DefaultDistributionStrategy::new("trakt").unwrap();
+        let cache_manager = CacheManager::new(&PathManager::with_base_dir(dir.path())).unwrap();
+        let mut plan = SourcePlan::new("trakt");
+        plan.watchlist_add.push(WatchlistItem {
+            imdb_id: "tt0111161".to_string(),
+            ids: None,
+            title: "The Shawshank Redemption".to_string(),
+            year: Some(1994),
+            media_type: media_sync_models::MediaType::Movie,
+            date_added: Utc::now(),
+            source: "imdb".to_string(),
+            status: None,
+            tags: Vec::new(),
+        });
+
+        let send = |no_write| {
+            let source_arc = source_arc.clone();
+            let plan = plan.clone();
+            let strategy = &strategy;
+            let cache_manager = &cache_manager;
+            async move {
+                let options = SyncOptions { no_write, ..all_data_types() };
+                let errors = Arc::new(tokio::sync::Mutex::new(Vec::new()));
+                let metrics = std::sync::Mutex::new(MetricsAccumulator::default());
+                SyncOrchestrator::apply_source_plan(&source_arc, &plan, &options, false, strategy, &cache_manager, &metrics, &errors).await;
+                let failed = errors.lock().await.len();
+                failed
+            }
+        };
+        assert_eq!(send(true).await, 0);
+        assert_eq!(send(false).await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_never_remove_only_reports_removals() {
+        let dir = tempfile::tempdir().unwrap();
+        // Read-only, so a removal that gets through fails and is reported
+        let source: Box<dyn MediaSource<Error = SourceError>> = Box::new(FileSource::new("trakt", dir.path()));
+        let source_arc = Arc::new(RwLock::new(source));
+        let strategy = DefaultDistributionStrategy::new("trakt").unwrap();
+        let cache_manager = CacheManager::new(&PathManager::with_base_dir(dir.path())).unwrap();
+        let removal = WatchlistItem {
+            imdb_id: "tt0111161".to_string(),
+            ids: None,
+            title: "The Shawshank Redemption".to_string(),
+            year: Some(1994),
+            media_type: MediaType::Movie,
+            date_added: Utc::now(),
+            source: "simkl".to_string(),
+            status: Some(NormalizedStatus::Dropped),
+            tags: Vec::new(),
+        };
+
+        let never_remove = SyncOptions { never_remove: true, ..all_data_types() };
+        assert!(SyncOrchestrator::removals_to_send(std::slice::from_ref(&removal), "trakt", true, &never_remove).is_empty());
+        assert_eq!(SyncOrchestrator::removals_to_send(std::slice::from_ref(&removal), "trakt", true, &all_data_types()).len(), 1);
+        // Letterboxd can't remove, so it gets no removals to fail on
+        assert!(SyncOrchestrator::removals_to_send(std::slice::from_ref(&removal), "letterboxd", false, &all_data_types()).is_empty());
+
+        // A saved plan with removals is held back too
+        let mut plan = SourcePlan::new("trakt");
+        plan.watchlist_remove.push(removal);
+        for (options, expected_errors) in [(never_remove, 0), (all_data_types(), 1)] {
+            let errors = Arc::new(tokio::sync::Mutex::new(Vec::new()));
+            let metrics = std::sync::Mutex::new(MetricsAccumulator::default());
+            SyncOrchestrator::apply_source_plan(&source_arc, &plan, &options, false, &strategy, &cache_manager, &metrics, &errors).await;
+            assert_eq!(errors.lock().await.len(), expected_errors);
+        }
+    }
+
+    /// A source whose watchlist takes `delay` to fetch
+    struct SlowSource {
+        name: String,
+        delay: Duration,
+    }
+
+    #[async_trait::async_trait]
+    impl MediaSource for SlowSource {
+        type Error = SourceError;
+
+        fn source_name(&self) -> &str {
+            &self.name
+        }
+
+        async fn authenticate(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn is_authenticated(&self) -> bool {
+            true
+        }
+
+        async fn get_watchlist(&self) -> Result<Vec<WatchlistItem>, Self::Error> {
+            tokio::time::sleep(self.delay).await;
+            Ok(vec![WatchlistItem {
+                imdb_id: "tt0111161".to_string(),
+                ids: None,
+                title: "The Shawshank Redemption".to_string(),
+                year: Some(1994),
+                media_type: MediaType::Movie,
+                date_added: Utc::now(),
+                source: self.name.clone(),
+                status: None,
+                tags: Vec::new(),
+            }])
+        }
+
+        async fn get_ratings(&self) -> Result<Vec<Rating>, Self::Error> {
+            Ok(Vec::new())
+        }
+
+        async fn get_reviews(&self) -> Result<Vec<Review>, Self::Error> {
+            Ok(Vec::new())
+        }
+
+        async fn get_watch_history(&self) -> Result<Vec<WatchHistory>, Self::Error> {
+            Ok(Vec::new())
+        }
+
+        async fn add_to_watchlist(&self, _items: &[WatchlistItem]) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        async fn remove_from_watchlist(&self, _items: &[WatchlistItem]) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        async fn set_ratings(&self, _ratings: &[Rating]) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        async fn set_reviews(&self, reviews: &[Review]) -> Result<Vec<media_sync_sources::WrittenReview>, Self::Error> {
+            Ok(media_sync_sources::WrittenReview::all(reviews))
+        }
+
+        async fn add_watch_history(&self, _items: &[WatchHistory]) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    impl media_sync_sources::CapabilityRegistry for SlowSource {
+        fn as_incremental_sync(&mut self) -> Option<&mut dyn media_sync_sources::IncrementalSync> {
+            None
+        }
+
+        fn as_rating_normalization(&self) -> Option<&dyn media_sync_sources::RatingNormalization> {
+            None
+        }
+
+        fn as_status_mapping(&self) -> Option<&dyn media_sync_sources::StatusMapping> {
+            None
+        }
+
+        fn as_id_extraction(&self) -> Option<&dyn media_sync_sources::IdExtraction> {
+            None
+        }
+
+        fn as_id_lookup_provider(&self) -> Option<&dyn media_sync_sources::IdLookupProvider> {
+            None
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sources_are_collected_concurrently_in_preference_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let path_manager = PathManager::with_base_dir(dir.path());
+        let slow = |name: &str, millis| -> Box<dyn MediaSource<Error = SourceError>> {
+            Box::new(SlowSource { name: name.to_string(), delay: Duration::from_millis(millis) })
+        };
+        let config = media_sync_config::ResolutionConfig {
+            source_preference: vec!["first".to_string(), "second".to_string()],
+            // Fetched in the opposite order to source_preference
+            fetch_order: vec!["second".to_string()],
+            ..Default::default()
+        };
+        let mut orchestrator = SyncOrchestrator::new(vec![slow("first", 400), slow("second", 300)], config)
+            .unwrap()
+            .with_sync_options(SyncOptions { sync_watchlist: true, ..Default::default() });
+        let cache_manager = Arc::new(CacheManager::new(&path_manager).unwrap());
+        let id_resolver = Arc::new(Mutex::new(
+            IdResolver::new(&path_manager.cache_id_dir(), &orchestrator.sources, IdResolverConfig::default()).await.unwrap(),
+        ));
+
+        let started = Instant::now();
+        let mut errors = Vec::new();
+        let collected = orchestrator.collect_all_data(&mut errors, &cache_manager, &id_resolver).await.unwrap();
+        let elapsed = started.elapsed();
+
+        assert!(errors.is_empty(), "{:?}", errors);
+        // max(400, 300), not 400 + 300
+        assert!(elapsed < Duration::from_millis(650), "collect took {:?}", elapsed);
+        let names: Vec<_> = collected.sources.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["first", "second"]);
+        assert_eq!(collected.sources[1].1.watchlist[0].source, "second");
+    }
+
+    #[tokio::test]
+    async fn test_buffered_events_are_read_from_the_orchestrators_data_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let path_manager = PathManager::with_base_dir(dir.path());
+        let rating = Rating {
+            imdb_id: "tt0113277".to_string(),
+            ids: None,
+            rating: 8,
+            rating_f64: None,
+            date_added: Utc::now(),
+            media_type: MediaType::Movie,
+            source: media_sync_models::RatingSource::Plex,
+            inferred: false,
+        };
+        EventBuffer::new(&path_manager)
+            .push("plex", media_sync_sources::plex::WebhookEvent::Rated(Box::new(rating)))
+            .await
+            .unwrap();
+
+        let source: Box<dyn MediaSource<Error = SourceError>> = Box::new(SlowSource { name: "plex".to_string(), delay: Duration::ZERO });
+        let config = media_sync_config::ResolutionConfig {
+            source_preference: vec!["plex".to_string()],
+            ..Default::default()
+        };
+        let mut orchestrator = SyncOrchestrator::new(vec![source], config)
+            .unwrap()
+            .with_sync_options(SyncOptions { sync_ratings: true, ..Default::default() })
+            .with_path_manager(path_manager.clone());
+        let cache_manager = Arc::new(CacheManager::new(&path_manager).unwrap());
+        let id_resolver = Arc::new(Mutex::new(
+            IdResolver::new(&path_manager.cache_id_dir(), &orchestrator.sources, IdResolverConfig::default()).await.unwrap(),
+        ));
+
+        let mut errors = Vec::new();
+        let collected = orchestrator.collect_all_data(&mut errors, &cache_manager, &id_resolver).await.unwrap();
+
+        assert!(errors.is_empty(), "{:?}", errors);
+        assert_eq!(collected.sources[0].1.ratings.len(), 1);
+        assert_eq!(collected.sources[0].1.ratings[0].imdb_id, "tt0113277");
+    }
+
+    #[tokio::test]
+    async fn test_a_source_collecting_past_the_timeout_is_abandoned() {
+        let dir = tempfile::tempdir().unwrap();
+        let path_manager = PathManager::with_base_dir(dir.path());
+        let slow = |name: &str, delay| -> Box<dyn MediaSource<Error = SourceError>> {
+            Box::new(SlowSource { name: name.to_string(), delay })
+        };
+        let config = media_sync_config::ResolutionConfig {
+            source_preference: vec!["hung".to_string(), "quick".to_string()],
+            ..Default::default()
+        };
+        let config_sync_options: media_sync_config::SyncOptions =
+            serde_json::from_value(serde_json::json!({ "collect_timeout_secs": 1 })).unwrap();
+        let mut orchestrator = SyncOrchestrator::new(vec![slow("hung", Duration::from_secs(60)), slow("quick", Duration::ZERO)], config)
+            .unwrap()
+            .with_sync_options(SyncOptions { sync_watchlist: true, ..Default::default() })
+            .with_config_sync_options(config_sync_options);
+        let cache_manager = Arc::new(CacheManager::new(&path_manager).unwrap());
+        let id_resolver = Arc::new(Mutex::new(
+            IdResolver::new(&path_manager.cache_id_dir(), &orchestrator.sources, IdResolverConfig::default()).await.unwrap(),
+        ));
+
+        let started = Instant::now();
+        let mut errors = Vec::new();
+        let collected = orchestrator.collect_all_data(&mut errors, &cache_manager, &id_resolver).await.unwrap();
+
+        assert!(started.elapsed() < Duration::from_secs(5), "collect took {:?}", started.elapsed());
+        assert_eq!(errors, vec!["Failed to collect data from hung: timed out after 1s".to_string()]);
+        let names: Vec<_> = collected.sources.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["quick"]);
+        assert_eq!(collected.sources[0].1.watchlist.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_media_types_filter_resolution_but_not_a_targets_existing_data() {
+        let dir = tempfile::tempdir().unwrap();
+        let path_manager = PathManager::with_base_dir(dir.path());
+        let shows_only: Box<dyn MediaSource<Error = SourceError>> =
+            Box::new(SlowSource { name: "trakt".to_string(), delay: Duration::ZERO });
+        let config = media_sync_config::ResolutionConfig {
+            source_preference: vec!["trakt".to_string()],
+            ..Default::default()
+        };
+        let mut orchestrator = SyncOrchestrator::new(vec![shows_only], config)
+            .unwrap()
+            .with_sync_options(SyncOptions { sync_watchlist: true, ..Default::default() })
+            .with_media_types([("trakt".to_string(), vec![media_sync_config::MediaTypeFilter::Show])].into());
+        let cache_manager = Arc::new(CacheManager::new(&path_manager).unwrap());
+        let id_resolver = Arc::new(Mutex::new(
+            IdResolver::new(&path_manager.cache_id_dir(), &orchestrator.sources, IdResolverConfig::default()).await.unwrap(),
+        ));
+
+        let mut errors = Vec::new();
+        let collected = orchestrator.collect_all_data(&mut errors, &cache_manager, &id_resolver).await.unwrap();
+        let (resolved, _) = orchestrator.resolve_collected_data(&collected, &cache_manager).await;
+
+        // The movie doesn't win resolution, but writes to trakt still see that it has it
+        assert!(resolved.watchlist.is_empty());
+        assert_eq!(collected.sources[0].1.watchlist.len(), 1);
+    }
+
+    /// Records the phases it is told about
+    #[derive(Default)]
+    struct PhaseRecorder(std::sync::Mutex<Vec<String>>);
+
+    impl ProgressTracker for PhaseRecorder {
+        fn start_phase(&self, name: &str, total: usize) {
+            self.0.lock().unwrap().push(format!("{} 0/{}", name, total));
+        }
+
+        fn advance(&self, n: usize) {
+            self.0.lock().unwrap().push(format!("+{}", n));
+        }
+
+        fn finish_phase(&self) {
+            self.0.lock().unwrap().push("done".to_string());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_collect_reports_a_phase_per_step() {
+        let dir = tempfile::tempdir().unwrap();
+        let path_manager = PathManager::with_base_dir(dir.path());
+        let slow = |name: &str| -> Box<dyn MediaSource<Error = SourceError>> {
+            Box::new(SlowSource { name: name.to_string(), delay: Duration::ZERO })
+        };
+        let config = media_sync_config::ResolutionConfig {
+            source_preference: vec!["first".to_string(), "second".to_string()],
+            ..Default::default()
+        };
+        let recorder = Arc::new(PhaseRecorder::default());
+        let mut orchestrator = SyncOrchestrator::new(vec![slow("first"), slow("second")], config)
+            .unwrap()
+            .with_sync_options(SyncOptions { sync_watchlist: true, ..Default::default() })
+            .with_progress_tracker(recorder.clone());
+        let cache_manager = Arc::new(CacheManager::new(&path_manager).unwrap());
+        let id_resolver = Arc::new(Mutex::new(
+            IdResolver::new(&path_manager.cache_id_dir(), &orchestrator.sources, IdResolverConfig::default()).await.unwrap(),
+        ));
+
+        orchestrator.collect_all_data(&mut Vec::new(), &cache_manager, &id_resolver).await.unwrap();
+
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            ["Collecting 0/2", "+1", "+1", "done", "Resolving IDs 0/2", "+1", "+1", "done"]
+        );
+    }
+
+    #[test]
+    fn test_unconfigured_sources_are_skipped_only_when_asked() {
+        let slow = |name: &str| -> Box<dyn MediaSource<Error = SourceError>> {
+            Box::new(SlowSource { name: name.to_string(), delay: Duration::ZERO })
+        };
+        let config = media_sync_config::ResolutionConfig {
+            source_preference: vec!["first".to_string(), "plex".to_string(), "second".to_string()],
+            fetch_order: vec!["plex".to_string()],
+            ratings_preference: vec!["plex".to_string(), "second".to_string()],
+            ..Default::default()
+        };
+
+        let error = SyncOrchestrator::new(vec![slow("first"), slow("second")], config.clone()).err().unwrap();
+        assert!(error.to_string().contains("'plex'"), "{}", error);
+
+        let skip = SyncOptions { skip_unconfigured_sources: true, ..Default::default() };
+        let orchestrator = SyncOrchestrator::new_with_options(vec![slow("first"), slow("second")], config.clone(), skip.clone()).unwrap();
+        assert_eq!(orchestrator.resolution_config.source_preference, vec!["first", "second"]);
+        assert!(orchestrator.resolution_config.fetch_order.is_empty());
+        assert_eq!(orchestrator.resolution_config.ratings_preference, vec!["second"]);
+        assert!(orchestrator.sync_options.skip_unconfigured_sources);
+
+        let error = SyncOrchestrator::new_with_options(vec![slow("other")], config, skip).err().unwrap();
+        assert!(error.to_string().contains("None of the sources"), "{}", error);
+    }
+
+    #[test]
+    fn test_parse_since_takes_durations_dates_and_rfc3339() {
+        use chrono::TimeZone;
+        let week_ago = parse_since("7d").unwrap();
+        let expected = Utc::now() - chrono::Duration::days(7);
+        assert!((week_ago - expected).num_seconds().abs() < 5, "{}", week_ago);
+        assert!(parse_since("36h").unwrap() > week_ago);
+
+        assert_eq!(parse_since("2024-01-01").unwrap(), Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap());
+        assert_eq!(parse_since(" 2024-03-10T12:30:00+02:00 ").unwrap(), Utc.with_ymd_and_hms(2024, 3, 10, 10, 30, 0).unwrap());
+
+        assert!(parse_since("last tuesday").unwrap_err().contains("neither a duration"));
+        assert!(parse_since("2024-13-01").is_err());
+        assert!(parse_since("300000years").unwrap_err().contains("too far back"));
+    }
+}
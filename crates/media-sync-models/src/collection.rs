@@ -0,0 +1,26 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::media_ids::MediaIds;
+
+/// An item the user owns, e.g. in their Trakt collection.
+///
+/// `collected_at` is when the item was acquired. It says nothing about whether or
+/// when the item was watched, so collection items are kept apart from `WatchHistory`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CollectionItem {
+    pub imdb_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ids: Option<MediaIds>,
+    pub title: String,
+    pub year: Option<u32>,
+    pub media_type: crate::media::MediaType,
+    pub collected_at: DateTime<Utc>,
+    pub source: String,
+}
+
+impl CollectionItem {
+    /// Canonicalize IMDB IDs (see `normalize_imdb_id`)
+    pub fn normalize_ids(&mut self) {
+        crate::media_ids::normalize_item_ids(&mut self.imdb_id, &mut self.ids);
+    }
+}
@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+use crate::watchlist::WatchlistItem;
+
+/// A named list the user keeps besides the watchlist, e.g. a Trakt personal list.
+///
+/// Lists are matched across sources by name (see `normalize_list_name`), so
+/// "Halloween  Picks" on one source and "halloween picks" on another are the same list.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CustomList {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub items: Vec<WatchlistItem>,
+    pub source: String, // Which source this list came from
+}
+
+impl CustomList {
+    /// The name lists are matched by
+    pub fn normalized_name(&self) -> String {
+        normalize_list_name(&self.name)
+    }
+
+    /// Canonicalize the IMDB IDs of the items (see `normalize_imdb_id`)
+    pub fn normalize_ids(&mut self) {
+        for item in &mut self.items {
+            item.normalize_ids();
+        }
+    }
+}
+
+/// Lower-case `name` with runs of whitespace collapsed to one space
+pub fn normalize_list_name(name: &str) -> String {
+    name.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_list_name() {
+        assert_eq!(normalize_list_name("  Halloween\tPicks "), "halloween picks");
+        assert_eq!(normalize_list_name("HALLOWEEN PICKS"), normalize_list_name("halloween  picks"));
+        assert_ne!(normalize_list_name("Halloween Picks"), normalize_list_name("Halloween Picks 2"));
+    }
+}
@@ -4,15 +4,21 @@ pub mod rating;
 pub mod review;
 pub mod status;
 pub mod watch_history;
+pub mod watch_progress;
 pub mod watchlist;
 pub mod excluded_item;
+pub mod collection;
+pub mod custom_list;
 
 pub use media::{MediaItem, MediaType};
-pub use media_ids::MediaIds;
+pub use media_ids::{MediaIds, normalize_imdb_id};
 pub use rating::{Rating, RatingSource};
 pub use review::Review;
 pub use status::NormalizedStatus;
 pub use watch_history::WatchHistory;
-pub use watchlist::WatchlistItem;
+pub use watch_progress::{WatchProgress, last_watched_episode, unwatched_progress};
+pub use watchlist::{WatchlistItem, tags_from_note};
 pub use excluded_item::ExcludedItem;
+pub use collection::CollectionItem;
+pub use custom_list::{CustomList, normalize_list_name};
 
@@ -8,6 +8,8 @@ pub struct MediaItem {
     pub year: Option<u32>,
     pub media_type: MediaType,
     pub date_added: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -17,3 +19,11 @@ pub enum MediaType {
     Episode { season: u32, episode: u32 },
 }
 
+impl MediaType {
+    /// An episode without real coordinates: IMDB reports episodes it can't place in
+    /// their show as season 0, episode 0
+    pub fn is_placeholder_episode(&self) -> bool {
+        matches!(self, MediaType::Episode { season: 0, episode: 0 })
+    }
+}
+
@@ -4,7 +4,7 @@ use crate::MediaType;
 
 /// Normalized media identifiers from all sources
 /// 
-/// This struct aggregates IDs from various sources (IMDB, Trakt, Simkl, TMDB, TVDB, etc.)
+/// This struct aggregates IDs from various sources (IMDB, Trakt, Simkl, TMDB, TVDB, AniList, etc.)
 /// to enable flexible matching and reconciliation across platforms.
 /// 
 /// Optionally includes title, year, and media_type for title-based cache lookups.
@@ -17,6 +17,11 @@ pub struct MediaIds {
     pub tvdb_id: Option<u32>,
     pub slug: Option<String>,
     pub plex_rating_key: Option<String>,
+    /// Anime IDs; AniList and MyAnimeList titles often have no IMDB ID
+    #[serde(default)]
+    pub anilist_id: Option<u64>,
+    #[serde(default)]
+    pub mal_id: Option<u64>,
     
     /// Optional metadata for title-based cache lookups
     /// These fields are not used for ID matching but enable efficient cache queries
@@ -39,6 +44,8 @@ impl MediaIds {
             tvdb_id: None,
             slug: None,
             plex_rating_key: None,
+            anilist_id: None,
+            mal_id: None,
             title: None,
             year: None,
             media_type: None,
@@ -54,6 +61,8 @@ impl MediaIds {
             .or_else(|| self.simkl_id.map(|id| format!("simkl:{}", id)))
             .or_else(|| self.tmdb_id.map(|id| format!("tmdb:{}", id)))
             .or_else(|| self.tvdb_id.map(|id| format!("tvdb:{}", id)))
+            .or_else(|| self.anilist_id.map(|id| format!("anilist:{}", id)))
+            .or_else(|| self.mal_id.map(|id| format!("mal:{}", id)))
             .or_else(|| self.slug.clone())
     }
 
@@ -83,6 +92,12 @@ impl MediaIds {
         if self.plex_rating_key.is_none() {
             self.plex_rating_key = other.plex_rating_key.clone();
         }
+        if self.anilist_id.is_none() {
+            self.anilist_id = other.anilist_id;
+        }
+        if self.mal_id.is_none() {
+            self.mal_id = other.mal_id;
+        }
         // Merge metadata (title, year, media_type) - prefer existing if present
         if self.title.is_none() {
             self.title = other.title.clone();
@@ -102,6 +117,8 @@ impl MediaIds {
             && self.simkl_id.is_none()
             && self.tmdb_id.is_none()
             && self.tvdb_id.is_none()
+            && self.anilist_id.is_none()
+            && self.mal_id.is_none()
             && self.slug.is_none()
     }
     
@@ -126,7 +143,9 @@ impl MediaIds {
     pub fn get_best_id_for_source(&self, source: &str) -> Option<String> {
         match source.to_lowercase().as_str() {
             "trakt" => {
+                // Trakt addresses items by slug as well as by its numeric ID
                 self.trakt_id.map(|id| format!("trakt:{}", id))
+                    .or_else(|| self.slug.clone())
                     .or_else(|| self.imdb_id.clone())
                     .or_else(|| self.get_any_id())
             }
@@ -150,6 +169,12 @@ impl MediaIds {
                     .or_else(|| self.imdb_id.clone())
                     .or_else(|| self.get_any_id())
             }
+            "anilist" => {
+                self.anilist_id.map(|id| format!("anilist:{}", id))
+                    .or_else(|| self.mal_id.map(|id| format!("mal:{}", id)))
+                    .or_else(|| self.imdb_id.clone())
+                    .or_else(|| self.get_any_id())
+            }
             _ => {
                 // For unknown sources or general use, prefer imdb_id
                 self.imdb_id.clone()
@@ -168,11 +193,15 @@ impl MediaIds {
             .or_else(|| self.simkl_id.map(|id| format!("simkl:{}", id)))
             .or_else(|| self.tmdb_id.map(|id| format!("tmdb:{}", id)))
             .or_else(|| self.tvdb_id.map(|id| format!("tvdb:{}", id)))
+            .or_else(|| self.anilist_id.map(|id| format!("anilist:{}", id)))
+            .or_else(|| self.mal_id.map(|id| format!("mal:{}", id)))
             .or_else(|| self.slug.clone())
-    }    /// Check if a specific ID type is available
+    }
+
+    /// Check if a specific ID type is available
     /// 
     /// # Arguments
-    /// * `id_type` - The ID type to check ("imdb", "trakt", "simkl", "tmdb", "tvdb", "slug")
+    /// * `id_type` - The ID type to check ("imdb", "trakt", "simkl", "tmdb", "tvdb", "anilist", "mal", "slug")
     /// 
     /// # Returns
     /// True if the specified ID type is available
@@ -183,6 +212,8 @@ impl MediaIds {
             "simkl" => self.simkl_id.is_some(),
             "tmdb" => self.tmdb_id.is_some(),
             "tvdb" => self.tvdb_id.is_some(),
+            "anilist" => self.anilist_id.is_some(),
+            "mal" => self.mal_id.is_some(),
             "slug" => self.slug.is_some(),
             "plex" | "plex_rating_key" => self.plex_rating_key.is_some(),
             _ => false,
@@ -190,6 +221,54 @@ impl MediaIds {
     }
 }
 
+/// Canonicalize an IMDB ID.
+///
+/// IDs reach us trimmed or untrimmed, with an upper-case prefix, or zero-padded to
+/// a different width depending on the source (e.g. `tt01234567` vs `tt1234567`).
+/// The canonical form is a lower-case `tt` followed by the number padded to at
+/// least 7 digits, which is what IMDB itself uses.
+///
+/// Returns `None` if the input is not an IMDB title ID.
+pub fn normalize_imdb_id(raw: &str) -> Option<String> {
+    let trimmed = raw.trim();
+    let prefix = trimmed.get(..2)?;
+    if !prefix.eq_ignore_ascii_case("tt") {
+        return None;
+    }
+
+    let digits = &trimmed[2..];
+    if digits.is_empty() || digits.len() > 10 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let number: u64 = digits.parse().ok()?;
+    if number == 0 {
+        return None;
+    }
+
+    Some(format!("tt{:07}", number))
+}
+
+/// Normalize the legacy `imdb_id` field and `MediaIds.imdb_id` of a data item in place.
+///
+/// Invalid IDs are cleared rather than kept, so downstream matching falls back
+/// to the other IDs instead of comparing against garbage.
+pub fn normalize_item_ids(imdb_id: &mut String, ids: &mut Option<MediaIds>) {
+    if !imdb_id.is_empty() {
+        *imdb_id = normalize_imdb_id(imdb_id).unwrap_or_default();
+    }
+    if let Some(ids) = ids {
+        ids.normalize_imdb_id();
+    }
+}
+
+impl MediaIds {
+    /// Canonicalize `imdb_id`, clearing it if it is not a valid IMDB ID
+    pub fn normalize_imdb_id(&mut self) {
+        self.imdb_id = self.imdb_id.as_deref().and_then(normalize_imdb_id);
+    }
+}
+
 impl Default for MediaIds {
     fn default() -> Self {
         Self::new()
@@ -205,5 +284,104 @@ impl Hash for MediaIds {
         self.tvdb_id.hash(state);
         self.slug.hash(state);
         self.plex_rating_key.hash(state);
+        self.anilist_id.hash(state);
+        self.mal_id.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_imdb_id_canonical() {
+        assert_eq!(normalize_imdb_id("tt1234567"), Some("tt1234567".to_string()));
+        assert_eq!(normalize_imdb_id("tt0123456"), Some("tt0123456".to_string()));
+        assert_eq!(normalize_imdb_id("tt10872600"), Some("tt10872600".to_string()));
+    }
+
+    #[test]
+    fn test_normalize_imdb_id_inconsistent_formatting() {
+        assert_eq!(normalize_imdb_id("  tt1234567\n"), Some("tt1234567".to_string()));
+        assert_eq!(normalize_imdb_id("TT1234567"), Some("tt1234567".to_string()));
+        assert_eq!(normalize_imdb_id("Tt1234567"), Some("tt1234567".to_string()));
+        assert_eq!(normalize_imdb_id("tt01234567"), Some("tt1234567".to_string()));
+        assert_eq!(normalize_imdb_id("tt00123456"), Some("tt0123456".to_string()));
+        assert_eq!(normalize_imdb_id("tt123"), Some("tt0000123".to_string()));
+    }
+
+    #[test]
+    fn test_normalize_imdb_id_malformed() {
+        assert_eq!(normalize_imdb_id(""), None);
+        assert_eq!(normalize_imdb_id("   "), None);
+        assert_eq!(normalize_imdb_id("tt"), None);
+        assert_eq!(normalize_imdb_id("1234567"), None);
+        assert_eq!(normalize_imdb_id("nm1234567"), None);
+        assert_eq!(normalize_imdb_id("tt12a4567"), None);
+        assert_eq!(normalize_imdb_id("tt 1234567"), None);
+        assert_eq!(normalize_imdb_id("tt-1234567"), None);
+        assert_eq!(normalize_imdb_id("tt0000000"), None);
+        assert_eq!(normalize_imdb_id("tt12345678901"), None);
+        assert_eq!(normalize_imdb_id("trakt:12345"), None);
+        assert_eq!(normalize_imdb_id("ťt1234567"), None);
+    }
+
+    #[test]
+    fn test_normalize_item_ids() {
+        let mut imdb_id = " tt01234567 ".to_string();
+        let mut ids = Some(MediaIds {
+            imdb_id: Some("TT1234567".to_string()),
+            trakt_id: Some(42),
+            ..MediaIds::default()
+        });
+        normalize_item_ids(&mut imdb_id, &mut ids);
+        assert_eq!(imdb_id, "tt1234567");
+        assert_eq!(ids.as_ref().unwrap().imdb_id.as_deref(), Some("tt1234567"));
+        assert_eq!(ids.as_ref().unwrap().trakt_id, Some(42));
+
+        // Invalid IDs are cleared, other IDs are kept
+        let mut imdb_id = "not-an-id".to_string();
+        let mut ids = Some(MediaIds {
+            imdb_id: Some("tt".to_string()),
+            tmdb_id: Some(7),
+            ..MediaIds::default()
+        });
+        normalize_item_ids(&mut imdb_id, &mut ids);
+        assert!(imdb_id.is_empty());
+        assert_eq!(ids.as_ref().unwrap().imdb_id, None);
+        assert_eq!(ids.as_ref().unwrap().tmdb_id, Some(7));
+    }
+
+    #[test]
+    fn test_trakt_ids_round_trip() {
+        let trakt_only = MediaIds { trakt_id: Some(1388), slug: Some("breaking-bad".to_string()), ..MediaIds::default() };
+        assert!(!trakt_only.is_empty());
+        assert!(!MediaIds { slug: Some("breaking-bad".to_string()), ..MediaIds::default() }.is_empty());
+        assert_eq!(trakt_only.get_any_id().as_deref(), Some("trakt:1388"));
+        assert_eq!(MediaIds { trakt_id: None, ..trakt_only.clone() }.get_best_id_for_source("trakt").as_deref(), Some("breaking-bad"));
+
+        let json = serde_json::to_string(&trakt_only).unwrap();
+        assert_eq!(serde_json::from_str::<MediaIds>(&json).unwrap(), trakt_only);
+
+        // An IMDB-only item picks up the Trakt IDs without losing its own
+        let mut ids = MediaIds { imdb_id: Some("tt0903747".to_string()), slug: Some("kept".to_string()), ..MediaIds::default() };
+        ids.merge(&trakt_only);
+        assert_eq!(ids.trakt_id, Some(1388));
+        assert_eq!(ids.slug.as_deref(), Some("kept"));
+        assert_eq!(ids.get_best_id_for_source("trakt").as_deref(), Some("trakt:1388"));
+    }
+
+    #[test]
+    fn test_anime_ids_without_imdb_id() {
+        let anime = MediaIds { anilist_id: Some(21), mal_id: Some(21), ..MediaIds::default() };
+        assert!(!anime.is_empty());
+        assert_eq!(anime.get_any_id().as_deref(), Some("anilist:21"));
+        assert_eq!(anime.get_best_id_for_source("anilist").as_deref(), Some("anilist:21"));
+        assert!(anime.has_id("mal"));
+
+        // JSON written before the anime IDs existed still loads
+        let old: MediaIds = serde_json::from_str(r#"{"imdb_id":"tt0388629","trakt_id":null,"simkl_id":null,"tmdb_id":37854,"tvdb_id":null,"slug":null,"plex_rating_key":null}"#).unwrap();
+        assert_eq!(old.anilist_id, None);
+        assert_eq!(old.mal_id, None);
     }
 }
@@ -8,9 +8,44 @@ pub struct Rating {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ids: Option<MediaIds>, // Normalized IDs from all sources
     pub rating: u8, // Normalized to Trakt format (1-10 integer)
+    /// `rating` before rounding, on the same scale, for sources rating in fractions
+    /// (Plex's 0.0-10.0, half stars on a 5 scale); None when `rating` is exact
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rating_f64: Option<f64>,
     pub date_added: DateTime<Utc>,
     pub media_type: crate::media::MediaType,
     pub source: RatingSource,
+    /// Not a rating the user gave but one made up from something else (a liked Trakt
+    /// comment as 10/10), so any real rating of the title wins over it
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub inferred: bool,
+}
+
+impl Rating {
+    /// `date_added` of a rating the source gave no date for. Replaced at collection
+    /// according to `sync.ratings.default_date`.
+    pub const UNDATED: DateTime<Utc> = DateTime::<Utc>::UNIX_EPOCH;
+
+    /// Whether the source gave no date for this rating
+    pub fn is_undated(&self) -> bool {
+        self.date_added == Self::UNDATED
+    }
+
+    /// The rating with its fraction, if it has one
+    pub fn value(&self) -> f64 {
+        self.rating_f64.unwrap_or(self.rating as f64)
+    }
+
+    /// Set the rating to `value`, keeping the fraction next to the rounded `rating`
+    pub fn set_value(&mut self, value: f64) {
+        self.rating = value.round().clamp(0.0, u8::MAX as f64) as u8;
+        self.rating_f64 = (value.fract() != 0.0).then_some(value);
+    }
+
+    /// Canonicalize IMDB IDs (see `normalize_imdb_id`)
+    pub fn normalize_ids(&mut self) {
+        crate::media_ids::normalize_item_ids(&mut self.imdb_id, &mut self.ids);
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -20,5 +55,7 @@ pub enum RatingSource {
     Netflix, // TBD (likely 1-5 stars or thumbs)
     Tmdb,    // TBD (likely 1-10 or 1-5)
     Plex,    // 0-10 scale (stored as 1-10, API uses 0-10)
+    Letterboxd, // 0.5-5 stars in half stars (stored as 1-10 half stars)
+    AniList, // 1-100 points (stored as 1-10)
 }
 
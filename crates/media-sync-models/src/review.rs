@@ -12,5 +12,19 @@ pub struct Review {
     pub media_type: crate::media::MediaType,
     pub source: String, // Which source this review came from
     pub is_spoiler: bool, // Whether this review contains spoilers
+    /// The reviewer's rating of the title (1-10) where the source shows it with the review,
+    /// as Trakt does on comments
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rating: Option<u8>,
+    /// The source's own ID for this review (IMDB `rw…`, Trakt comment ID), when it reports one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub review_id: Option<String>,
+}
+
+impl Review {
+    /// Canonicalize IMDB IDs (see `normalize_imdb_id`)
+    pub fn normalize_ids(&mut self) {
+        crate::media_ids::normalize_item_ids(&mut self.imdb_id, &mut self.ids);
+    }
 }
 
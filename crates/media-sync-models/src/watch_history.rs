@@ -14,5 +14,23 @@ pub struct WatchHistory {
     pub watched_at: DateTime<Utc>,
     pub media_type: crate::media::MediaType,
     pub source: String, // Which source this watch history came from
+    /// Share of the item played (0-100) for a partial play; `None` means fully watched
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub watched_percent: Option<f32>,
+    /// For an episode, the IDs of its series (when the source reports them)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub show_ids: Option<MediaIds>,
+}
+
+impl WatchHistory {
+    /// Canonicalize IMDB IDs (see `normalize_imdb_id`)
+    pub fn normalize_ids(&mut self) {
+        crate::media_ids::normalize_item_ids(&mut self.imdb_id, &mut self.ids);
+    }
+
+    /// A play that stopped before the end. Partial plays are never written as watched.
+    pub fn is_partial(&self) -> bool {
+        matches!(self.watched_percent, Some(percent) if percent < 100.0)
+    }
 }
 
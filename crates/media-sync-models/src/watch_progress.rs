@@ -0,0 +1,99 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use crate::media_ids::MediaIds;
+
+/// How far into a show the user is: the episode they watched last.
+///
+/// Unlike `WatchHistory`, which holds one entry per play, progress is one entry per
+/// show, so "on S3E4" survives sources that keep no per-episode history.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WatchProgress {
+    pub imdb_id: String,
+    /// IDs of the show
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ids: Option<MediaIds>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub year: Option<u32>,
+    pub last_watched_season: u32,
+    pub last_watched_episode: u32,
+    /// When the last watched episode was watched
+    pub watched_at: DateTime<Utc>,
+    pub source: String,
+}
+
+impl WatchProgress {
+    /// Canonicalize IMDB IDs (see `normalize_imdb_id`)
+    pub fn normalize_ids(&mut self) {
+        crate::media_ids::normalize_item_ids(&mut self.imdb_id, &mut self.ids);
+    }
+
+    /// `(season, episode)` of the last watched episode, ordered by position in the show
+    pub fn position(&self) -> (u32, u32) {
+        (self.last_watched_season, self.last_watched_episode)
+    }
+
+    /// Whether `ids` name this show, by any ID both have
+    pub fn is_show(&self, ids: &MediaIds) -> bool {
+        let own = self.ids.clone().unwrap_or_default();
+        let imdb_id = own.imdb_id.as_deref().filter(|id| !id.is_empty()).unwrap_or(&self.imdb_id);
+        (!imdb_id.is_empty() && ids.imdb_id.as_deref() == Some(imdb_id))
+            || (own.trakt_id.is_some() && own.trakt_id == ids.trakt_id)
+            || (own.simkl_id.is_some() && own.simkl_id == ids.simkl_id)
+            || (own.tmdb_id.is_some() && own.tmdb_id == ids.tmdb_id)
+            || (own.tvdb_id.is_some() && own.tvdb_id == ids.tvdb_id)
+    }
+}
+
+/// The shows of `progress` whose last watched episode a source hasn't watched yet.
+/// `watched` is the source's shows, each with the `(season, episode)`s watched there.
+pub fn unwatched_progress(progress: &[WatchProgress], watched: &[(MediaIds, HashSet<(u32, u32)>)]) -> Vec<WatchProgress> {
+    progress.iter()
+        .filter(|show| {
+            !watched.iter()
+                .any(|(ids, episodes)| show.is_show(ids) && episodes.contains(&show.position()))
+        })
+        .cloned()
+        .collect()
+}
+
+/// The most recently watched of `(season, episode, watched_at)` episodes, the one further
+/// into the show on a tie. Specials (season 0) are not progress and are left out.
+pub fn last_watched_episode(
+    episodes: impl IntoIterator<Item = (u32, u32, DateTime<Utc>)>,
+) -> Option<(u32, u32, DateTime<Utc>)> {
+    episodes.into_iter()
+        .filter(|(season, _, _)| *season > 0)
+        .max_by_key(|(season, episode, watched_at)| (*watched_at, *season, *episode))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unwatched_progress_skips_episodes_already_watched() {
+        let show = |imdb_id: &str, season: u32, episode: u32| WatchProgress {
+            imdb_id: imdb_id.to_string(),
+            ids: None,
+            title: None,
+            year: None,
+            last_watched_season: season,
+            last_watched_episode: episode,
+            watched_at: Utc::now(),
+            source: "simkl".to_string(),
+        };
+        let watched = vec![(
+            MediaIds { imdb_id: Some("tt0903747".to_string()), trakt_id: Some(1388), ..Default::default() },
+            HashSet::from([(3, 3), (3, 4)]),
+        )];
+        let progress = [show("tt0903747", 3, 4), show("tt0903747", 3, 5), show("tt0944947", 1, 1)];
+        let unwatched = unwatched_progress(&progress, &watched);
+        let unwatched: Vec<(&str, (u32, u32))> = unwatched.iter()
+            .map(|show| (show.imdb_id.as_str(), show.position()))
+            .collect();
+        assert_eq!(unwatched, [("tt0903747", (3, 5)), ("tt0944947", (1, 1))]);
+    }
+}
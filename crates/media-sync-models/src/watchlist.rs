@@ -14,5 +14,41 @@ pub struct WatchlistItem {
     pub date_added: DateTime<Utc>,
     pub source: String, // Which source this watchlist item came from
     pub status: Option<NormalizedStatus>, // Normalized status (Watchlist, Watching, Completed, Dropped, Hold)
+    /// Tags the user gave the item in its source (e.g. taken from a Trakt or IMDB list note)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+impl WatchlistItem {
+    /// Canonicalize IMDB IDs (see `normalize_imdb_id`)
+    pub fn normalize_ids(&mut self) {
+        crate::media_ids::normalize_item_ids(&mut self.imdb_id, &mut self.ids);
+    }
+
+    /// Whether the item carries any of `tags` (case-insensitive)
+    pub fn has_any_tag(&self, tags: &[String]) -> bool {
+        self.tags.iter().any(|tag| tags.iter().any(|t| t.eq_ignore_ascii_case(tag)))
+    }
+}
+
+/// Split a free-form item note into tags: words separated by whitespace or commas,
+/// with a leading `#` dropped, so "no-sync", "#no-sync" and "rewatch, no-sync" all tag "no-sync"
+pub fn tags_from_note(note: &str) -> Vec<String> {
+    note.split(|c: char| c.is_whitespace() || c == ',')
+        .map(|word| word.trim_start_matches('#'))
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tags_from_note() {
+        assert_eq!(tags_from_note("#no-sync, rewatch  later"), vec!["no-sync", "rewatch", "later"]);
+        assert!(tags_from_note("  ").is_empty());
+    }
 }
 
@@ -0,0 +1,635 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use media_sync_models::{MediaIds, MediaType, NormalizedStatus, Rating, RatingSource, WatchHistory, WatchProgress, WatchlistItem};
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::HashMap;
+use crate::retry::RetryPolicy;
+
+// AniList GraphQL endpoint
+const API_URL: &str = "https://graphql.anilist.co";
+
+pub const SOURCE_NAME: &str = "anilist";
+
+/// Entries per page of `MediaListCollection` (AniList's maximum)
+const ENTRIES_PER_CHUNK: u32 = 500;
+
+/// `SaveMediaListEntry` mutations sent per request, as aliases of one another
+const SAVE_BATCH_SIZE: usize = 20;
+
+const LIST_QUERY: &str = "query ($userName: String, $format: ScoreFormat, $chunk: Int, $perChunk: Int) {
+  MediaListCollection(userName: $userName, type: ANIME, chunk: $chunk, perChunk: $perChunk) {
+    hasNextChunk
+    lists {
+      isCustomList
+      entries {
+        id status score(format: $format) progress createdAt updatedAt
+        completedAt { year month day }
+        media { id idMal format episodes title { romaji english } startDate { year month day } }
+      }
+    }
+  }
+}";
+
+const SEARCH_QUERY: &str = "query ($search: String, $formats: [MediaFormat]) {
+  Page(perPage: 10) {
+    media(search: $search, type: ANIME, format_in: $formats) {
+      id idMal format episodes title { romaji english } startDate { year month day }
+      externalLinks { url }
+    }
+  }
+}";
+
+const MAL_QUERY: &str = "query ($ids: [Int], $perPage: Int) {
+  Page(perPage: $perPage) {
+    media(idMal_in: $ids, type: ANIME) { id idMal }
+  }
+}";
+
+/// MAL IDs looked up per request (AniList's maximum page size)
+const MAL_LOOKUP_BATCH_SIZE: usize = 50;
+
+const VIEWER_QUERY: &str = "query { Viewer { name } }";
+
+#[derive(Debug, Deserialize)]
+struct GraphQlResponse<T> {
+    data: Option<T>,
+    #[serde(default)]
+    errors: Vec<GraphQlError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlError {
+    message: String,
+}
+
+/// An AniList date; any part may be unknown
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct FuzzyDate {
+    pub year: Option<i32>,
+    pub month: Option<u32>,
+    pub day: Option<u32>,
+}
+
+impl FuzzyDate {
+    /// Midnight UTC of the date, when it is complete
+    fn to_datetime(&self) -> Option<DateTime<Utc>> {
+        let date = NaiveDate::from_ymd_opt(self.year?, self.month?, self.day?)?;
+        Some(Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0)?))
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct MediaTitle {
+    pub romaji: Option<String>,
+    pub english: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ExternalLink {
+    url: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Media {
+    pub id: u64,
+    pub id_mal: Option<u64>,
+    /// TV, TV_SHORT, MOVIE, SPECIAL, OVA, ONA, MUSIC
+    pub format: Option<String>,
+    /// Number of episodes, unknown while airing
+    pub episodes: Option<u32>,
+    #[serde(default)]
+    pub title: MediaTitle,
+    pub start_date: Option<FuzzyDate>,
+    external_links: Option<Vec<ExternalLink>>,
+}
+
+impl Media {
+    /// English title, or the romanized one
+    pub fn title(&self) -> String {
+        self.title.english.clone()
+            .or_else(|| self.title.romaji.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn year(&self) -> Option<u32> {
+        self.start_date.as_ref()?.year.and_then(|year| u32::try_from(year).ok())
+    }
+
+    /// Movies are movies; every other format (TV, OVA, ONA, ...) is a show
+    pub fn media_type(&self) -> MediaType {
+        match self.format.as_deref() {
+            Some("MOVIE") => MediaType::Movie,
+            _ => MediaType::Show,
+        }
+    }
+
+    /// AniList and MAL IDs, and the IMDB and TMDB IDs of the title's external links
+    pub fn ids(&self) -> MediaIds {
+        let mut ids = MediaIds {
+            anilist_id: Some(self.id),
+            mal_id: self.id_mal,
+            ..MediaIds::new()
+        }
+        .with_metadata(self.title(), self.year(), self.media_type());
+        for link in self.external_links.iter().flatten() {
+            if ids.imdb_id.is_none() {
+                ids.imdb_id = imdb_id_from_url(&link.url);
+            }
+            if ids.tmdb_id.is_none() {
+                ids.tmdb_id = tmdb_id_from_url(&link.url);
+            }
+        }
+        ids
+    }
+
+    fn matches_title(&self, title: &str) -> bool {
+        let wanted = comparable_title(title);
+        [&self.title.english, &self.title.romaji].into_iter()
+            .flatten()
+            .any(|candidate| comparable_title(candidate) == wanted)
+    }
+}
+
+/// `tt...` of an `imdb.com/title/tt.../` link
+fn imdb_id_from_url(url: &str) -> Option<String> {
+    let (_, rest) = url.split_once("imdb.com/title/")?;
+    media_sync_models::normalize_imdb_id(rest.split(['/', '?']).next()?)
+}
+
+/// The number of a `themoviedb.org/movie/...` or `themoviedb.org/tv/...` link
+fn tmdb_id_from_url(url: &str) -> Option<u32> {
+    let (_, rest) = url.split_once("themoviedb.org/")?;
+    let rest = rest.strip_prefix("movie/").or_else(|| rest.strip_prefix("tv/"))?;
+    let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+    digits.parse().ok()
+}
+
+/// Lowercase letters and digits only, so "Shingeki no Kyojin" matches "shingeki no kyojin!"
+fn comparable_title(title: &str) -> String {
+    title.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect()
+}
+
+/// One title on the user's anime list
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListEntry {
+    pub id: u64,
+    /// CURRENT, PLANNING, COMPLETED, DROPPED, PAUSED, REPEATING
+    pub status: Option<String>,
+    /// In the requested score format; 0 when unscored
+    pub score: Option<f64>,
+    /// Episodes watched
+    pub progress: Option<u32>,
+    pub created_at: Option<i64>,
+    pub updated_at: Option<i64>,
+    pub completed_at: Option<FuzzyDate>,
+    pub media: Media,
+}
+
+impl ListEntry {
+    pub fn normalized_status(&self) -> Option<NormalizedStatus> {
+        match self.status.as_deref()? {
+            "PLANNING" => Some(NormalizedStatus::Watchlist),
+            "CURRENT" | "REPEATING" => Some(NormalizedStatus::Watching),
+            "COMPLETED" => Some(NormalizedStatus::Completed),
+            "DROPPED" => Some(NormalizedStatus::Dropped),
+            "PAUSED" => Some(NormalizedStatus::Hold),
+            _ => None,
+        }
+    }
+
+    fn updated(&self) -> DateTime<Utc> {
+        self.updated_at.or(self.created_at)
+            .and_then(|seconds| DateTime::from_timestamp(seconds, 0))
+            .unwrap_or_else(Utc::now)
+    }
+
+    fn added(&self) -> DateTime<Utc> {
+        self.created_at
+            .and_then(|seconds| DateTime::from_timestamp(seconds, 0))
+            .unwrap_or_else(|| self.updated())
+    }
+
+    fn is_completed(&self) -> bool {
+        matches!(self.status.as_deref(), Some("COMPLETED" | "REPEATING"))
+    }
+}
+
+/// A change to one list entry, created when the title isn't on the list yet
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EntryUpdate {
+    pub media_id: u64,
+    pub status: Option<&'static str>,
+    /// Score on AniList's 100-point scale, whatever format the user displays
+    pub score_raw: Option<u32>,
+    pub progress: Option<u32>,
+    pub completed_at: Option<NaiveDate>,
+}
+
+/// AniList status of a normalized status; the watchlist when there is none
+pub fn anilist_status(status: Option<&NormalizedStatus>) -> &'static str {
+    match status {
+        None | Some(NormalizedStatus::Watchlist) => "PLANNING",
+        Some(NormalizedStatus::Watching) => "CURRENT",
+        Some(NormalizedStatus::Completed) => "COMPLETED",
+        Some(NormalizedStatus::Dropped) => "DROPPED",
+        Some(NormalizedStatus::Hold) => "PAUSED",
+    }
+}
+
+/// Score format asked for when reading scores on a `scale` of 10 or 100
+fn score_format(scale: u8) -> &'static str {
+    if scale == 10 { "POINT_10_DECIMAL" } else { "POINT_100" }
+}
+
+/// Send a GraphQL request and return its `data`, failing on any error AniList reports
+async fn graphql<T: DeserializeOwned>(
+    client: &Client,
+    access_token: Option<&str>,
+    what: &str,
+    query: &str,
+    variables: serde_json::Value,
+    retry: &RetryPolicy,
+) -> Result<T> {
+    let body = serde_json::json!({ "query": query, "variables": variables });
+    let response = retry.send(what, || {
+        let request = client
+            .post(API_URL)
+            .header("Accept", "application/json")
+            .json(&body);
+        match access_token {
+            Some(access_token) => request.bearer_auth(access_token),
+            None => request,
+        }
+    })
+    .await?;
+
+    let status = response.status();
+    let text = response.text().await?;
+    let parsed: GraphQlResponse<T> = serde_json::from_str(&text)
+        .map_err(|e| anyhow!("{} failed with status {}: {}", what, status, if status.is_success() { e.to_string() } else { text.clone() }))?;
+    if !parsed.errors.is_empty() {
+        let messages: Vec<String> = parsed.errors.into_iter().map(|error| error.message).collect();
+        return Err(anyhow!("{} failed with status {}: {}", what, status, messages.join("; ")));
+    }
+    parsed.data.ok_or_else(|| anyhow!("{} failed with status {}: no data in the response", what, status))
+}
+
+/// Name of the user the access token belongs to
+pub async fn get_viewer_name(client: &Client, access_token: &str, retry: &RetryPolicy) -> Result<String> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct Data {
+        viewer: Viewer,
+    }
+    #[derive(Deserialize)]
+    struct Viewer {
+        name: String,
+    }
+    let data: Data = graphql(client, Some(access_token), "AniList viewer", VIEWER_QUERY, serde_json::json!({}), retry).await?;
+    Ok(data.viewer.name)
+}
+
+/// Every entry of the user's anime list, with scores on a `score_scale` of 10 or 100.
+/// Titles on custom lists are also on a status list, so custom lists are skipped.
+pub async fn get_entries(
+    client: &Client,
+    access_token: Option<&str>,
+    user_name: &str,
+    score_scale: u8,
+    retry: &RetryPolicy,
+) -> Result<Vec<ListEntry>> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct Data {
+        media_list_collection: Collection,
+    }
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Collection {
+        has_next_chunk: Option<bool>,
+        lists: Option<Vec<List>>,
+    }
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct List {
+        is_custom_list: Option<bool>,
+        entries: Option<Vec<ListEntry>>,
+    }
+
+    let mut entries: Vec<ListEntry> = Vec::new();
+    let mut chunk = 1;
+    loop {
+        let variables = serde_json::json!({
+            "userName": user_name,
+            "format": score_format(score_scale),
+            "chunk": chunk,
+            "perChunk": ENTRIES_PER_CHUNK,
+        });
+        let data: Data = graphql(client, access_token, "AniList list", LIST_QUERY, variables, retry).await?;
+        let collection = data.media_list_collection;
+        for list in collection.lists.into_iter().flatten() {
+            if list.is_custom_list == Some(true) {
+                continue;
+            }
+            for entry in list.entries.into_iter().flatten() {
+                if !entries.iter().any(|existing| existing.id == entry.id) {
+                    entries.push(entry);
+                }
+            }
+        }
+        if collection.has_next_chunk != Some(true) {
+            break;
+        }
+        chunk += 1;
+    }
+    Ok(entries)
+}
+
+/// Every entry as a watchlist item, with its status
+pub fn watchlist_from_entries(entries: &[ListEntry]) -> Vec<WatchlistItem> {
+    entries.iter()
+        .map(|entry| WatchlistItem {
+            imdb_id: String::new(),
+            ids: Some(entry.media.ids()),
+            title: entry.media.title(),
+            year: entry.media.year(),
+            media_type: entry.media.media_type(),
+            date_added: entry.added(),
+            source: SOURCE_NAME.to_string(),
+            status: entry.normalized_status(),
+            tags: Vec::new(),
+        })
+        .collect()
+}
+
+/// Scored entries as ratings, on the scale the scores were read in. AniList doesn't date
+/// scores, so the ratings are undated.
+pub fn ratings_from_entries(entries: &[ListEntry]) -> Vec<Rating> {
+    entries.iter()
+        .filter_map(|entry| {
+            let score = entry.score.filter(|score| *score > 0.0)?;
+            let mut rating = Rating {
+                imdb_id: String::new(),
+                ids: Some(entry.media.ids()),
+                rating: 0,
+                rating_f64: None,
+                date_added: Rating::UNDATED,
+                media_type: entry.media.media_type(),
+                source: RatingSource::AniList,
+                inferred: false,
+            };
+            rating.set_value(score);
+            Some(rating)
+        })
+        .collect()
+}
+
+/// One play per completed entry, on the day it was completed (or when it was last
+/// updated, if no completion date was entered)
+pub fn watch_history_from_entries(entries: &[ListEntry]) -> Vec<WatchHistory> {
+    entries.iter()
+        .filter(|entry| entry.is_completed())
+        .map(|entry| WatchHistory {
+            imdb_id: String::new(),
+            ids: Some(entry.media.ids()),
+            title: Some(entry.media.title()),
+            year: entry.media.year(),
+            watched_at: entry.completed_at.as_ref()
+                .and_then(FuzzyDate::to_datetime)
+                .unwrap_or_else(|| entry.updated()),
+            media_type: entry.media.media_type(),
+            source: SOURCE_NAME.to_string(),
+            watched_percent: None,
+            show_ids: None,
+        })
+        .collect()
+}
+
+/// The episode count of each show that was started, as progress through season 1.
+/// AniList lists every season as a title of its own, so its episodes are numbered from 1.
+pub fn watch_progress_from_entries(entries: &[ListEntry]) -> Vec<WatchProgress> {
+    entries.iter()
+        .filter(|entry| entry.media.media_type() == MediaType::Show)
+        .filter_map(|entry| {
+            let progress = entry.progress.filter(|progress| *progress > 0)?;
+            Some(WatchProgress {
+                imdb_id: String::new(),
+                ids: Some(entry.media.ids()),
+                title: Some(entry.media.title()),
+                year: entry.media.year(),
+                last_watched_season: 1,
+                last_watched_episode: progress,
+                watched_at: entry.updated(),
+                source: SOURCE_NAME.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// The aliased `SaveMediaListEntry` mutations of one request
+fn save_mutation(updates: &[EntryUpdate]) -> String {
+    let mut mutation = String::from("mutation {");
+    for (index, update) in updates.iter().enumerate() {
+        let mut arguments = vec![format!("mediaId: {}", update.media_id)];
+        if let Some(status) = update.status {
+            arguments.push(format!("status: {}", status));
+        }
+        if let Some(score_raw) = update.score_raw {
+            arguments.push(format!("scoreRaw: {}", score_raw));
+        }
+        if let Some(progress) = update.progress {
+            arguments.push(format!("progress: {}", progress));
+        }
+        if let Some(date) = update.completed_at {
+            arguments.push(format!("completedAt: {{ year: {}, month: {}, day: {} }}", date.format("%Y"), date.format("%-m"), date.format("%-d")));
+        }
+        mutation.push_str(&format!(" e{}: SaveMediaListEntry({}) {{ id }}", index, arguments.join(", ")));
+    }
+    mutation.push_str(" }");
+    mutation
+}
+
+/// Create or change list entries, several per request
+pub async fn save_entries(client: &Client, access_token: &str, updates: &[EntryUpdate], retry: &RetryPolicy) -> Result<()> {
+    for chunk in updates.chunks(SAVE_BATCH_SIZE) {
+        let _: serde_json::Value = graphql(client, Some(access_token), "AniList list update", &save_mutation(chunk), serde_json::json!({}), retry).await?;
+    }
+    Ok(())
+}
+
+/// Remove list entries by their entry IDs
+pub async fn delete_entries(client: &Client, access_token: &str, entry_ids: &[u64], retry: &RetryPolicy) -> Result<()> {
+    for chunk in entry_ids.chunks(SAVE_BATCH_SIZE) {
+        let mut mutation = String::from("mutation {");
+        for (index, entry_id) in chunk.iter().enumerate() {
+            mutation.push_str(&format!(" e{}: DeleteMediaListEntry(id: {}) {{ deleted }}", index, entry_id));
+        }
+        mutation.push_str(" }");
+        let _: serde_json::Value = graphql(client, Some(access_token), "AniList list removal", &mutation, serde_json::json!({}), retry).await?;
+    }
+    Ok(())
+}
+
+/// AniList IDs of anime by their MAL IDs. MAL IDs AniList doesn't know are left out.
+pub async fn anilist_ids_by_mal_id(client: &Client, mal_ids: &[u64], retry: &RetryPolicy) -> Result<HashMap<u64, u64>> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct Data {
+        page: Page,
+    }
+    #[derive(Deserialize)]
+    struct Page {
+        media: Option<Vec<Media>>,
+    }
+
+    let mut ids = HashMap::new();
+    for chunk in mal_ids.chunks(MAL_LOOKUP_BATCH_SIZE) {
+        let variables = serde_json::json!({ "ids": chunk, "perPage": MAL_LOOKUP_BATCH_SIZE });
+        let data: Data = graphql(client, None, "AniList MAL ID lookup", MAL_QUERY, variables, retry).await?;
+        ids.extend(data.page.media.into_iter().flatten()
+            .filter_map(|media| Some((media.id_mal?, media.id))));
+    }
+    Ok(ids)
+}
+
+/// Find an anime by title. Only a title that matches exactly (ignoring case and
+/// punctuation) and, when `year` is given, started that year is returned, so a search
+/// for a title that isn't anime doesn't pick up an unrelated one.
+pub async fn search_by_title(
+    client: &Client,
+    title: &str,
+    year: Option<u32>,
+    media_type: &MediaType,
+    retry: &RetryPolicy,
+) -> Result<Option<MediaIds>> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct Data {
+        page: Page,
+    }
+    #[derive(Deserialize)]
+    struct Page {
+        media: Option<Vec<Media>>,
+    }
+
+    let formats: &[&str] = match media_type {
+        MediaType::Movie => &["MOVIE"],
+        MediaType::Show => &["TV", "TV_SHORT", "ONA", "OVA", "SPECIAL"],
+        MediaType::Episode { .. } => return Ok(None),
+    };
+    let variables = serde_json::json!({ "search": title, "formats": formats });
+    let data: Data = graphql(client, None, "AniList search", SEARCH_QUERY, variables, retry).await?;
+    Ok(data.page.media.into_iter().flatten()
+        .find(|media| media.matches_title(title) && (year.is_none() || media.year() == year))
+        .map(|media| media.ids()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries() -> Vec<ListEntry> {
+        let json = serde_json::json!([
+            {
+                "id": 1, "status": "COMPLETED", "score": 85, "progress": 26,
+                "createdAt": 1600000000, "updatedAt": 1700000000,
+                "completedAt": { "year": 2021, "month": 3, "day": 14 },
+                "media": {
+                    "id": 16498, "idMal": 16498, "format": "TV", "episodes": 25,
+                    "title": { "romaji": "Shingeki no Kyojin", "english": "Attack on Titan" },
+                    "startDate": { "year": 2013, "month": 4, "day": 7 }
+                }
+            },
+            {
+                "id": 2, "status": "PLANNING", "score": 0, "progress": 0,
+                "createdAt": 1650000000, "updatedAt": 1650000000,
+                "completedAt": { "year": null, "month": null, "day": null },
+                "media": {
+                    "id": 199, "idMal": 199, "format": "MOVIE", "episodes": 1,
+                    "title": { "romaji": "Sen to Chihiro no Kamikakushi", "english": null },
+                    "startDate": { "year": 2001, "month": 7, "day": 20 }
+                }
+            },
+            {
+                "id": 3, "status": "CURRENT", "score": 0, "progress": 7,
+                "createdAt": 1690000000, "updatedAt": 1710000000, "completedAt": null,
+                "media": {
+                    "id": 154587, "idMal": null, "format": "TV", "episodes": null,
+                    "title": { "romaji": "Sousou no Frieren", "english": "Frieren: Beyond Journey's End" },
+                    "startDate": { "year": 2023, "month": 9, "day": 29 }
+                }
+            }
+        ]);
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_entries_map_to_statuses_scores_plays_and_episode_counts() {
+        let entries = entries();
+
+        let watchlist = watchlist_from_entries(&entries);
+        assert_eq!(watchlist.len(), 3);
+        assert_eq!(watchlist[0].title, "Attack on Titan");
+        assert_eq!(watchlist[0].status, Some(NormalizedStatus::Completed));
+        assert_eq!(watchlist[1].title, "Sen to Chihiro no Kamikakushi");
+        assert_eq!(watchlist[1].media_type, MediaType::Movie);
+        assert_eq!(watchlist[1].status, Some(NormalizedStatus::Watchlist));
+        assert!(watchlist.iter().all(|item| item.imdb_id.is_empty()));
+        assert_eq!(watchlist[2].ids.as_ref().unwrap().anilist_id, Some(154587));
+        assert_eq!(watchlist[2].ids.as_ref().unwrap().mal_id, None);
+
+        let ratings = ratings_from_entries(&entries);
+        assert_eq!(ratings.len(), 1);
+        assert_eq!(ratings[0].rating, 85);
+        assert!(ratings[0].is_undated());
+
+        let history = watch_history_from_entries(&entries);
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].watched_at, Utc.with_ymd_and_hms(2021, 3, 14, 0, 0, 0).unwrap());
+
+        let progress = watch_progress_from_entries(&entries);
+        let positions: Vec<_> = progress.iter().map(|progress| (progress.ids.as_ref().unwrap().anilist_id, progress.position())).collect();
+        assert_eq!(positions, vec![(Some(16498), (1, 26)), (Some(154587), (1, 7))]);
+    }
+
+    #[test]
+    fn test_external_links_give_imdb_and_tmdb_ids() {
+        let media: Media = serde_json::from_value(serde_json::json!({
+            "id": 21, "idMal": 21, "format": "TV",
+            "title": { "romaji": "ONE PIECE", "english": "ONE PIECE" },
+            "startDate": { "year": 1999 },
+            "externalLinks": [
+                { "url": "https://www.crunchyroll.com/one-piece" },
+                { "url": "https://www.imdb.com/title/tt0388629/" },
+                { "url": "https://www.themoviedb.org/tv/37854-one-piece" }
+            ]
+        })).unwrap();
+        let ids = media.ids();
+        assert_eq!(ids.imdb_id.as_deref(), Some("tt0388629"));
+        assert_eq!(ids.tmdb_id, Some(37854));
+        assert!(media.matches_title("One Piece"));
+        assert!(!media.matches_title("One Piece Film: Red"));
+    }
+
+    #[test]
+    fn test_updates_are_sent_as_aliased_mutations() {
+        let updates = [
+            EntryUpdate { media_id: 21, score_raw: Some(90), ..EntryUpdate::default() },
+            EntryUpdate {
+                media_id: 199,
+                status: Some("COMPLETED"),
+                completed_at: NaiveDate::from_ymd_opt(2024, 2, 3),
+                ..EntryUpdate::default()
+            },
+        ];
+        assert_eq!(
+            save_mutation(&updates),
+            "mutation { e0: SaveMediaListEntry(mediaId: 21, scoreRaw: 90) { id } \
+             e1: SaveMediaListEntry(mediaId: 199, status: COMPLETED, completedAt: { year: 2024, month: 2, day: 3 }) { id } }"
+        );
+    }
+}
@@ -0,0 +1,452 @@
+use crate::anilist::api::{self, EntryUpdate, ListEntry, SOURCE_NAME};
+use crate::capabilities::{CapabilityRegistry, IdExtraction, IdLookupProvider, IncrementalSync, RatingNormalization, StatusMapping};
+use crate::error::SourceError;
+use crate::retry::RetryPolicy;
+use crate::traits::{DataType, MediaSource, WrittenReview};
+use async_trait::async_trait;
+use media_sync_models::{MediaIds, MediaType, Rating, Review, WatchHistory, WatchProgress, WatchlistItem};
+use reqwest::Client;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// AniList, through its GraphQL API. Lists are read by username, and need no token if
+/// the profile is public; writes need an access token. Titles are matched by AniList ID,
+/// since most anime have no IMDB ID.
+pub struct AniListClient {
+    client: Arc<Client>,
+    username: String,
+    access_token: Option<String>,
+    /// 10 or 100: the scale scores are read and written on
+    score_scale: u8,
+    authenticated: bool,
+    retry: RetryPolicy,
+}
+
+impl AniListClient {
+    pub fn new(username: String, access_token: Option<String>) -> Self {
+        Self {
+            client: Arc::new(create_anilist_client(None)),
+            username,
+            access_token: access_token.filter(|token| !token.is_empty()),
+            score_scale: 100,
+            authenticated: false,
+            retry: RetryPolicy::default(),
+        }
+    }
+
+    /// Read and write scores on AniList's 10-point scale instead of its 100-point one
+    /// (`sources.anilist.score_scale`)
+    pub fn with_score_scale(mut self, score_scale: u8) -> Self {
+        self.score_scale = if score_scale == 10 { 10 } else { 100 };
+        self
+    }
+
+    /// Retries of list reads and updates
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Give up on API requests that take longer than `timeout` (`sources.request_timeout_secs`)
+    pub fn with_request_timeout(mut self, timeout: Option<std::time::Duration>) -> Self {
+        self.client = Arc::new(create_anilist_client(timeout));
+        self
+    }
+
+    fn access_token(&self) -> Result<&str, SourceError> {
+        self.access_token.as_deref().ok_or_else(|| SourceError::new(
+            "Writing to AniList needs an access token; run 'totalrecall config anilist' to add one".to_string()
+        ))
+    }
+
+    async fn entries(&self) -> Result<Vec<ListEntry>, SourceError> {
+        api::get_entries(&self.client, self.access_token.as_deref(), &self.username, self.score_scale, &self.retry)
+            .await
+            .map_err(|e| SourceError::new(format!("{}", e)))
+    }
+
+    /// AniList IDs of the titles among `ids` that only have a MAL ID, by MAL ID
+    async fn ids_by_mal_id<'a>(&self, ids: impl IntoIterator<Item = Option<&'a MediaIds>>) -> Result<HashMap<u64, u64>, SourceError> {
+        let mut mal_ids: Vec<u64> = ids.into_iter()
+            .flatten()
+            .filter(|ids| ids.anilist_id.is_none())
+            .filter_map(|ids| ids.mal_id)
+            .collect();
+        if mal_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+        mal_ids.sort_unstable();
+        mal_ids.dedup();
+        api::anilist_ids_by_mal_id(&self.client, &mal_ids, &self.retry)
+            .await
+            .map_err(|e| SourceError::new(format!("{}", e)))
+    }
+
+    async fn save(&self, updates: &[EntryUpdate]) -> Result<(), SourceError> {
+        if updates.is_empty() {
+            return Ok(());
+        }
+        api::save_entries(&self.client, self.access_token()?, updates, &self.retry)
+            .await
+            .map_err(|e| SourceError::new(format!("{}", e)))
+    }
+
+    /// Score on AniList's 100-point scale of a rating on `score_scale`
+    fn score_raw(&self, rating: &Rating) -> u32 {
+        (rating.value() * 100.0 / self.score_scale as f64).round().clamp(1.0, 100.0) as u32
+    }
+}
+
+/// HTTP client for the AniList API, giving up on requests that take longer than `timeout`
+fn create_anilist_client(timeout: Option<std::time::Duration>) -> Client {
+    let mut builder = Client::builder();
+    if let Some(timeout) = timeout {
+        builder = builder.timeout(timeout);
+    }
+    builder.build().unwrap_or_else(|_| Client::new())
+}
+
+/// A title's AniList ID, or the one its MAL ID maps to
+fn anilist_id(ids: Option<&MediaIds>, by_mal_id: &HashMap<u64, u64>) -> Option<u64> {
+    let ids = ids?;
+    ids.anilist_id.or_else(|| by_mal_id.get(&ids.mal_id?).copied())
+}
+
+/// Updates raising each title's episode count to `progress` (AniList ID, episodes
+/// watched). Counts already at or past it are left alone, and titles not on the list yet
+/// are added as being watched.
+fn progress_updates(entries: &[ListEntry], progress: impl IntoIterator<Item = (u64, u32)>) -> Vec<EntryUpdate> {
+    let current: HashMap<u64, u32> = entries.iter()
+        .map(|entry| (entry.media.id, entry.progress.unwrap_or(0)))
+        .collect();
+    let mut wanted: Vec<(u64, u32)> = Vec::new();
+    for (media_id, episodes) in progress {
+        match wanted.iter_mut().find(|(id, _)| *id == media_id) {
+            Some((_, most)) => *most = (*most).max(episodes),
+            None => wanted.push((media_id, episodes)),
+        }
+    }
+    wanted.into_iter()
+        .filter_map(|(media_id, episodes)| match current.get(&media_id) {
+            Some(&watched) if watched >= episodes => None,
+            Some(_) => Some(EntryUpdate { media_id, progress: Some(episodes), ..EntryUpdate::default() }),
+            None => Some(EntryUpdate { media_id, status: Some("CURRENT"), progress: Some(episodes), ..EntryUpdate::default() }),
+        })
+        .collect()
+}
+
+#[async_trait]
+impl MediaSource for AniListClient {
+    type Error = SourceError;
+
+    fn source_name(&self) -> &str {
+        SOURCE_NAME
+    }
+
+    fn supported_data_types(&self) -> &'static [DataType] {
+        // AniList reviews are long-form articles with their own rules, not title notes
+        &[DataType::Watchlist, DataType::Ratings, DataType::WatchHistory]
+    }
+
+    async fn authenticate(&mut self) -> Result<(), Self::Error> {
+        if let Some(access_token) = &self.access_token {
+            let viewer = api::get_viewer_name(&self.client, access_token, &self.retry)
+                .await
+                .map_err(|e| SourceError::new(format!("AniList rejected the access token: {}", e)))?;
+            if self.username.is_empty() {
+                self.username = viewer;
+            } else if !self.username.eq_ignore_ascii_case(&viewer) {
+                warn!("The AniList access token belongs to '{}', but lists are read from '{}'", viewer, self.username);
+            }
+        }
+        if self.username.is_empty() {
+            return Err(SourceError::new(
+                "AniList needs a username or an access token; run 'totalrecall config anilist'".to_string()
+            ));
+        }
+        self.authenticated = true;
+        info!("Reading the AniList list of '{}'", self.username);
+        Ok(())
+    }
+
+    fn is_authenticated(&self) -> bool {
+        self.authenticated
+    }
+
+    async fn get_watchlist(&self) -> Result<Vec<WatchlistItem>, Self::Error> {
+        Ok(api::watchlist_from_entries(&self.entries().await?))
+    }
+
+    async fn get_ratings(&self) -> Result<Vec<Rating>, Self::Error> {
+        Ok(api::ratings_from_entries(&self.entries().await?))
+    }
+
+    async fn get_reviews(&self) -> Result<Vec<Review>, Self::Error> {
+        // Not in supported_data_types, so sync never asks
+        Ok(Vec::new())
+    }
+
+    async fn get_watch_history(&self) -> Result<Vec<WatchHistory>, Self::Error> {
+        Ok(api::watch_history_from_entries(&self.entries().await?))
+    }
+
+    async fn add_to_watchlist(&self, items: &[WatchlistItem]) -> Result<(), Self::Error> {
+        let by_mal_id = self.ids_by_mal_id(items.iter().map(|item| item.ids.as_ref())).await?;
+        let updates: Vec<EntryUpdate> = items.iter()
+            .filter_map(|item| Some(EntryUpdate {
+                media_id: anilist_id(item.ids.as_ref(), &by_mal_id)?,
+                status: Some(api::anilist_status(item.status.as_ref())),
+                ..EntryUpdate::default()
+            }))
+            .collect();
+        if updates.len() < items.len() {
+            info!("Skipping {} AniList watchlist additions without an AniList or MAL ID", items.len() - updates.len());
+        }
+        self.save(&updates).await
+    }
+
+    async fn remove_from_watchlist(&self, items: &[WatchlistItem]) -> Result<(), Self::Error> {
+        if items.is_empty() {
+            return Ok(());
+        }
+        // Only planned titles are removed; deleting any other entry would lose its progress
+        let entries = self.entries().await?;
+        let by_mal_id = self.ids_by_mal_id(items.iter().map(|item| item.ids.as_ref())).await?;
+        let entry_ids: Vec<u64> = items.iter()
+            .filter_map(|item| anilist_id(item.ids.as_ref(), &by_mal_id))
+            .filter_map(|media_id| entries.iter().find(|entry| entry.media.id == media_id && entry.status.as_deref() == Some("PLANNING")))
+            .map(|entry| entry.id)
+            .collect();
+        if entry_ids.is_empty() {
+            return Ok(());
+        }
+        api::delete_entries(&self.client, self.access_token()?, &entry_ids, &self.retry)
+            .await
+            .map_err(|e| SourceError::new(format!("{}", e)))
+    }
+
+    async fn set_ratings(&self, ratings: &[Rating]) -> Result<(), Self::Error> {
+        let by_mal_id = self.ids_by_mal_id(ratings.iter().map(|rating| rating.ids.as_ref())).await?;
+        let updates: Vec<EntryUpdate> = ratings.iter()
+            .filter(|rating| !matches!(rating.media_type, MediaType::Episode { .. }))
+            .filter_map(|rating| Some(EntryUpdate {
+                media_id: anilist_id(rating.ids.as_ref(), &by_mal_id)?,
+                score_raw: Some(self.score_raw(rating)),
+                ..EntryUpdate::default()
+            }))
+            .collect();
+        if updates.len() < ratings.len() {
+            info!("Skipping {} AniList ratings without an AniList or MAL ID or of episodes", ratings.len() - updates.len());
+        }
+        self.save(&updates).await
+    }
+
+    async fn set_reviews(&self, reviews: &[Review]) -> Result<Vec<WrittenReview>, Self::Error> {
+        // Fail rather than report reviews as written that AniList never received
+        if reviews.is_empty() {
+            return Ok(Vec::new());
+        }
+        Err(SourceError::new(format!(
+            "AniList reviews can't be written; {} reviews were not written",
+            reviews.len()
+        )))
+    }
+
+    async fn add_watch_history(&self, items: &[WatchHistory]) -> Result<(), Self::Error> {
+        if items.is_empty() {
+            return Ok(());
+        }
+        let entries = self.entries().await?;
+        let by_mal_id = self.ids_by_mal_id(items.iter().flat_map(|item| [item.ids.as_ref(), item.show_ids.as_ref()])).await?;
+        let mut updates = Vec::new();
+        let mut episodes = Vec::new();
+        let mut skipped = 0;
+        for item in items.iter().filter(|item| !item.is_partial()) {
+            match item.media_type {
+                MediaType::Movie | MediaType::Show => {
+                    let Some(media_id) = anilist_id(item.ids.as_ref(), &by_mal_id) else {
+                        skipped += 1;
+                        continue;
+                    };
+                    let completed = entries.iter().any(|entry| entry.media.id == media_id && entry.status.as_deref() == Some("COMPLETED"));
+                    if !completed && !updates.iter().any(|update: &EntryUpdate| update.media_id == media_id) {
+                        updates.push(EntryUpdate {
+                            media_id,
+                            status: Some("COMPLETED"),
+                            completed_at: Some(item.watched_at.date_naive()),
+                            ..EntryUpdate::default()
+                        });
+                    }
+                }
+                // AniList numbers the episodes of each season from 1, under a title of its own
+                MediaType::Episode { season: 1, episode } => match anilist_id(item.show_ids.as_ref(), &by_mal_id) {
+                    Some(media_id) => episodes.push((media_id, episode)),
+                    None => skipped += 1,
+                },
+                MediaType::Episode { .. } => skipped += 1,
+            }
+        }
+        if skipped > 0 {
+            info!("Skipping {} AniList plays without an AniList or MAL ID or past season 1", skipped);
+        }
+        updates.extend(progress_updates(&entries, episodes));
+        self.save(&updates).await
+    }
+
+    fn supports_watch_progress(&self) -> bool {
+        true
+    }
+
+    async fn get_watch_progress(&self) -> Result<Vec<WatchProgress>, Self::Error> {
+        Ok(api::watch_progress_from_entries(&self.entries().await?))
+    }
+
+    async fn set_watch_progress(&self, progress: &[WatchProgress]) -> Result<(), Self::Error> {
+        let by_mal_id = self.ids_by_mal_id(progress.iter().map(|progress| progress.ids.as_ref())).await?;
+        let wanted: Vec<(u64, u32)> = progress.iter()
+            .filter(|progress| progress.last_watched_season == 1)
+            .filter_map(|progress| Some((anilist_id(progress.ids.as_ref(), &by_mal_id)?, progress.last_watched_episode)))
+            .collect();
+        if wanted.is_empty() {
+            return Ok(());
+        }
+        let entries = self.entries().await?;
+        self.save(&progress_updates(&entries, wanted)).await
+    }
+}
+
+impl RatingNormalization for AniListClient {
+    fn normalize_rating(&self, rating: f64, target_scale: u8, _media_type: &MediaType) -> u8 {
+        (rating * target_scale as f64 / self.score_scale as f64).round() as u8
+    }
+
+    fn denormalize_rating(&self, rating: u8, source_scale: u8, _media_type: &MediaType) -> f64 {
+        rating as f64 * self.score_scale as f64 / source_scale as f64
+    }
+
+    fn normalize_rating_f64(&self, rating: f64, target_scale: u8, _media_type: &MediaType) -> f64 {
+        rating * target_scale as f64 / self.score_scale as f64
+    }
+
+    fn denormalize_rating_f64(&self, rating: f64, source_scale: u8, _media_type: &MediaType) -> f64 {
+        rating * self.score_scale as f64 / source_scale as f64
+    }
+
+    fn native_rating_scale(&self, _media_type: &MediaType) -> u8 {
+        self.score_scale
+    }
+}
+
+impl CapabilityRegistry for AniListClient {
+    fn as_incremental_sync(&mut self) -> Option<&mut dyn IncrementalSync> {
+        None
+    }
+
+    fn as_rating_normalization(&self) -> Option<&dyn RatingNormalization> {
+        Some(self)
+    }
+
+    fn as_status_mapping(&self) -> Option<&dyn StatusMapping> {
+        None
+    }
+
+    fn as_id_extraction(&self) -> Option<&dyn IdExtraction> {
+        Some(self)
+    }
+
+    fn as_id_lookup_provider(&self) -> Option<&dyn IdLookupProvider> {
+        Some(self)
+    }
+}
+
+impl IdExtraction for AniListClient {
+    fn extract_ids(&self, imdb_id: Option<&str>, native_ids: Option<&serde_json::Value>) -> Option<MediaIds> {
+        let mut media_ids = MediaIds {
+            imdb_id: imdb_id.filter(|id| !id.is_empty()).map(str::to_string),
+            ..MediaIds::default()
+        };
+        if let Some(native_ids) = native_ids {
+            media_ids.anilist_id = native_ids.get("anilist").and_then(|id| id.as_u64());
+            media_ids.mal_id = native_ids.get("mal").and_then(|id| id.as_u64());
+        }
+        (!media_ids.is_empty()).then_some(media_ids)
+    }
+
+    fn native_id_type(&self) -> &str {
+        "anilist"
+    }
+}
+
+#[async_trait]
+impl IdLookupProvider for AniListClient {
+    async fn lookup_ids(
+        &self,
+        title: &str,
+        year: Option<u32>,
+        media_type: &MediaType,
+    ) -> Result<Option<MediaIds>, Box<dyn std::error::Error + Send + Sync>> {
+        api::search_by_title(&self.client, title, year, media_type, &self.retry)
+            .await
+            .map_err(|e| Box::new(std::io::Error::other(format!("{}", e))) as Box<dyn std::error::Error + Send + Sync>)
+    }
+
+    fn lookup_priority(&self) -> u8 {
+        // Anime only, and mostly without IMDB IDs, so the general-purpose providers go first
+        10
+    }
+
+    fn lookup_provider_name(&self) -> &str {
+        SOURCE_NAME
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(media_id: u64, progress: u32) -> ListEntry {
+        serde_json::from_value(serde_json::json!({
+            "id": media_id * 10,
+            "status": "CURRENT",
+            "progress": progress,
+            "media": { "id": media_id, "format": "TV" },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_episode_counts_are_only_raised() {
+        let entries = [entry(21, 1000), entry(154587, 3)];
+        let updates = progress_updates(&entries, [(21, 12), (154587, 5), (154587, 7), (16498, 25)]);
+        assert_eq!(updates, vec![
+            EntryUpdate { media_id: 154587, progress: Some(7), ..EntryUpdate::default() },
+            EntryUpdate { media_id: 16498, status: Some("CURRENT"), progress: Some(25), ..EntryUpdate::default() },
+        ]);
+    }
+
+    #[test]
+    fn test_scores_are_sent_on_the_100_point_scale() {
+        let rating = |value: f64| {
+            let mut rating = Rating {
+                imdb_id: String::new(),
+                ids: None,
+                rating: 0,
+                rating_f64: None,
+                date_added: Rating::UNDATED,
+                media_type: MediaType::Show,
+                source: media_sync_models::RatingSource::Trakt,
+                inferred: false,
+            };
+            rating.set_value(value);
+            rating
+        };
+        let point_100 = AniListClient::new("user".to_string(), None);
+        assert_eq!(point_100.denormalize_rating_f64(8.5, 10, &MediaType::Show), 85.0);
+        assert_eq!(point_100.score_raw(&rating(85.0)), 85);
+
+        let point_10 = AniListClient::new("user".to_string(), None).with_score_scale(10);
+        assert_eq!(point_10.normalize_rating_f64(7.5, 10, &MediaType::Show), 7.5);
+        assert_eq!(point_10.score_raw(&rating(7.5)), 75);
+    }
+}
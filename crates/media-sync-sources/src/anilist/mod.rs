@@ -0,0 +1,4 @@
+pub mod api;
+pub mod client;
+
+pub use client::AniListClient;
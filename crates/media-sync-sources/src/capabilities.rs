@@ -4,7 +4,9 @@
 /// requiring string-based matching in the core pipeline.
 
 use async_trait::async_trait;
-use media_sync_models::{MediaIds, MediaType};
+use chrono::{DateTime, Utc};
+use media_sync_models::{CollectionItem, MediaIds, MediaType, WatchHistory};
+use crate::traits::DataType;
 
 /// Registry pattern for accessing capabilities without unsafe downcasting
 /// 
@@ -50,6 +52,99 @@ pub trait CapabilityRegistry: Send + Sync {
     fn supports_id_lookup(&self) -> bool {
         self.as_id_lookup_provider().is_some()
     }
+
+    /// Get a reference to PlaybackProgress capability if supported
+    fn as_playback_progress(&self) -> Option<&dyn PlaybackProgress> {
+        None
+    }
+
+    /// Get a reference to CollectionSource capability if supported
+    fn as_collection_source(&self) -> Option<&dyn CollectionSource> {
+        None
+    }
+
+    /// Get a reference to EpisodeLookup capability if supported
+    fn as_episode_lookup(&self) -> Option<&dyn EpisodeLookup> {
+        None
+    }
+
+    /// Get a reference to HistoryPruning capability if supported
+    fn as_history_pruning(&self) -> Option<&dyn HistoryPruning> {
+        None
+    }
+
+    /// Whether show-level watch history from this source should be resolved to
+    /// episodes by air date, through another source's EpisodeLookup
+    fn resolves_episode_checkins(&self) -> bool {
+        false
+    }
+
+    /// Whether `remove_from_watchlist` works here. Sources that can't remove get no
+    /// watchlist removals planned, instead of a plan that only fails.
+    fn accepts_watchlist_removals(&self) -> bool {
+        true
+    }
+
+    /// Report the item progress of this source's long operations (Plex writes, IMDB
+    /// exports, ...) to `observer`. Sources without such operations ignore it.
+    fn set_progress_observer(&mut self, _observer: Option<crate::ProgressObserver>) {}
+}
+
+/// Trait for sources that track playback progress separately from watch history
+/// 
+/// Partial plays are sent here instead of `add_watch_history`, so the source
+/// shows them as in progress rather than watched.
+#[async_trait]
+pub trait PlaybackProgress: Send + Sync {
+    /// Record that `item` was played up to `progress` percent (0-100)
+    async fn scrobble(&self, item: &WatchHistory, progress: f32) -> Result<(), crate::error::SourceError>;
+}
+
+/// Trait for sources that track owned items separately from watch history
+/// 
+/// Collection items carry `collected_at`, never `watched_at`, and are not merged
+/// into watch history: owning an item doesn't mean it was watched.
+#[async_trait]
+pub trait CollectionSource: Send + Sync {
+    /// Fetch every item in the user's collection
+    async fn get_collection(&self) -> Result<Vec<CollectionItem>, crate::error::SourceError>;
+
+    /// Whether `add_to_collection` writes. A read-only collection (a Plex library) only
+    /// feeds the others.
+    fn accepts_collection_writes(&self) -> bool {
+        false
+    }
+
+    /// Add items to the collection, keeping their `collected_at`
+    async fn add_to_collection(&self, _items: &[CollectionItem]) -> Result<(), crate::error::SourceError> {
+        Err(crate::error::SourceError::new("This collection is read-only".to_string()))
+    }
+}
+
+/// An episode of a show and when it first aired
+#[derive(Debug, Clone)]
+pub struct AiredEpisode {
+    pub season: u32,
+    pub episode: u32,
+    pub title: Option<String>,
+    pub ids: MediaIds,
+    pub first_aired: DateTime<Utc>,
+}
+
+/// Trait for sources that can list a show's episodes with their air dates
+#[async_trait]
+pub trait EpisodeLookup: Send + Sync {
+    /// Every episode of the show that has aired; `show_id` is the source's own ID or an IMDB ID
+    async fn aired_episodes(&self, show_id: &str) -> Result<Vec<AiredEpisode>, crate::error::SourceError>;
+}
+
+/// Trait for sources that can delete old plays from their watch history
+/// (`prune-history --remote`)
+#[async_trait]
+pub trait HistoryPruning: Send + Sync {
+    /// Remove every play watched before `cutoff`, or only count them with `dry_run`.
+    /// Returns the number of plays removed or that would be removed.
+    async fn prune_watch_history(&self, cutoff: DateTime<Utc>, dry_run: bool) -> Result<usize, crate::error::SourceError>;
 }
 
 /// Trait for sources that support native incremental sync
@@ -67,6 +162,22 @@ pub trait IncrementalSync: Send + Sync {
     fn supports_native_incremental_sync(&self) -> bool {
         false
     }
+
+    /// Whether the last fetch of `data_type` returned only what changed since the
+    /// previous sync rather than the full list. The caller merges such a result into
+    /// its cached copy of the list.
+    fn fetched_changes_only(&self, _data_type: DataType) -> bool {
+        false
+    }
+
+    /// Whether the caller holds a cached copy of `data_type` to merge a changes-only fetch
+    /// into; without one, the next fetch of it must return the full list
+    fn set_cached(&mut self, _data_type: DataType, _cached: bool) {}
+
+    /// Called once the last fetch of `data_type` is saved to the caller's cache. A source
+    /// keeping where its changes-only fetches left off moves that mark now, so changes
+    /// fetched into a cache that failed to save are fetched again.
+    fn commit_fetch(&self, _data_type: DataType) {}
 }
 
 /// Trait for sources that support status mapping
@@ -90,26 +201,40 @@ pub trait RatingNormalization: Send + Sync {
     /// # Arguments
     /// * `rating` - The rating in the source's native format (as f64)
     /// * `target_scale` - The target scale (e.g., 10 for 1-10 scale)
+    /// * `media_type` - What was rated, for sources whose scale depends on it
     /// 
     /// # Returns
     /// The normalized rating as a u8 in the target scale
-    fn normalize_rating(&self, rating: f64, target_scale: u8) -> u8;
+    fn normalize_rating(&self, rating: f64, target_scale: u8, media_type: &MediaType) -> u8;
     
     /// Denormalize a rating from a target format to the source's format
     /// 
     /// # Arguments
     /// * `rating` - The rating in the target format (as u8)
     /// * `target_scale` - The source scale (e.g., 10 for 1-10 scale)
+    /// * `media_type` - What was rated, for sources whose scale depends on it
     /// 
     /// # Returns
     /// The denormalized rating as f64 in the source's native format
-    fn denormalize_rating(&self, rating: u8, source_scale: u8) -> f64;
+    fn denormalize_rating(&self, rating: u8, source_scale: u8, media_type: &MediaType) -> f64;
+
+    /// `normalize_rating` without rounding, so fractional ratings (Plex's 0.0-10.0,
+    /// half stars) survive a round trip. Sources with integer scales keep the default.
+    fn normalize_rating_f64(&self, rating: f64, target_scale: u8, media_type: &MediaType) -> f64 {
+        self.normalize_rating(rating, target_scale, media_type) as f64
+    }
+
+    /// `denormalize_rating` of a fractional rating
+    fn denormalize_rating_f64(&self, rating: f64, source_scale: u8, media_type: &MediaType) -> f64 {
+        self.denormalize_rating(rating.round() as u8, source_scale, media_type)
+    }
     
-    /// Get the source's native rating scale
+    /// Get the source's native rating scale for `media_type`. Most sources use one
+    /// scale for everything.
     /// 
     /// # Returns
     /// The maximum value of the source's rating scale (e.g., 10 for 1-10, 5 for 1-5)
-    fn native_rating_scale(&self) -> u8;
+    fn native_rating_scale(&self, media_type: &MediaType) -> u8;
 }
 
 /// Trait for sources that can extract IDs from their native format
@@ -0,0 +1,160 @@
+//! Choosing one result when a title search returns several candidates.
+//!
+//! Remakes and same-named films all come back for one title. Instead of taking the
+//! first result, candidates are scored with the weights from `[id_matching]`: a year
+//! match, then popularity relative to the most popular candidate. Ties keep the
+//! search's own order. Titles are compared normalized (see [`normalize_title`]) and
+//! years within `year_tolerance`, since sources disagree on both.
+
+use media_sync_config::IdMatchingConfig;
+use tracing::debug;
+
+/// One search result to choose from
+#[derive(Debug, Clone)]
+pub struct TitleCandidate<T> {
+    /// The result's title, when the search returns one
+    pub title: Option<String>,
+    pub year: Option<u32>,
+    /// Votes or a popularity score, compared only within one search's results
+    pub popularity: Option<f64>,
+    pub item: T,
+}
+
+/// Articles dropped from the start (or a trailing ", The") of a title for fuzzy matching
+const ARTICLES: [&str; 3] = ["the", "a", "an"];
+
+/// Title for fuzzy matching: lowercase, without a leading article and without anything
+/// but letters and digits, so "The Matrix", "Matrix, The" and "the matrix!" all agree
+pub fn normalize_title(title: &str) -> String {
+    let lowercase = title.trim().to_lowercase();
+    let mut words: Vec<String> = lowercase
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+        .collect();
+    if words.len() > 1 {
+        let trailing_article = lowercase.rsplit_once(',')
+            .is_some_and(|(_, last)| ARTICLES.contains(&last.trim()));
+        if trailing_article {
+            words.pop();
+        } else if ARTICLES.contains(&words[0].as_str()) {
+            words.remove(0);
+        }
+    }
+    words.concat()
+}
+
+/// Whether `candidate_year` is at most `year_tolerance` years off `year`, or either is unknown
+pub fn within_year_tolerance(year: Option<u32>, candidate_year: Option<u32>, year_tolerance: u32) -> bool {
+    match (year, candidate_year) {
+        (Some(year), Some(candidate_year)) => year.abs_diff(candidate_year) <= year_tolerance,
+        _ => true,
+    }
+}
+
+/// Pick the best candidate for `title` (`year` when known), or `None` when there are none.
+/// When some candidates have the searched title, only those are considered. A year
+/// within `year_tolerance` scores the year weight, less the further off it is.
+pub fn pick_best_candidate<T>(
+    candidates: Vec<TitleCandidate<T>>,
+    title: &str,
+    year: Option<u32>,
+    weights: &IdMatchingConfig,
+) -> Option<T> {
+    if candidates.len() > 1 {
+        debug!("Title search for '{}' (year: {:?}) returned {} candidates, disambiguating", title, year, candidates.len());
+    }
+    let normalized = normalize_title(title);
+    let same_title = |candidate: &TitleCandidate<T>| {
+        candidate.title.as_deref().is_some_and(|candidate_title| normalize_title(candidate_title) == normalized)
+    };
+    let candidates: Vec<TitleCandidate<T>> = if candidates.iter().any(same_title) {
+        candidates.into_iter().filter(same_title).collect()
+    } else {
+        candidates
+    };
+    let max_popularity = candidates.iter()
+        .filter_map(|candidate| candidate.popularity)
+        .fold(0.0_f64, f64::max);
+
+    let mut best: Option<(f64, TitleCandidate<T>)> = None;
+    for candidate in candidates {
+        let mut score = 0.0;
+        if let (Some(year), Some(candidate_year)) = (year, candidate.year) {
+            let difference = year.abs_diff(candidate_year);
+            if difference <= weights.year_tolerance {
+                score += weights.year_match_weight / f64::from(difference + 1);
+            }
+        }
+        if max_popularity > 0.0 {
+            score += weights.popularity_weight * candidate.popularity.unwrap_or(0.0) / max_popularity;
+        }
+        // Strictly greater, so the earlier candidate wins a tie
+        if best.as_ref().is_none_or(|(best_score, _)| score > *best_score) {
+            best = Some((score, candidate));
+        }
+    }
+    best.map(|(_, candidate)| candidate.item)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(id: &str, year: u32, popularity: f64) -> TitleCandidate<String> {
+        TitleCandidate { title: None, year: Some(year), popularity: Some(popularity), item: id.to_string() }
+    }
+
+    #[test]
+    fn test_same_titled_films_pick_year_then_popularity() {
+        let weights = IdMatchingConfig::default();
+        // "Dune": the 1984 film, the 2021 film, and an obscure 2021 short
+        let dune = || vec![
+            candidate("tt0087182", 1984, 40.0),
+            candidate("tt9999999", 2021, 2.0),
+            candidate("tt1160419", 2021, 900.0),
+        ];
+
+        assert_eq!(pick_best_candidate(dune(), "Dune", Some(1984), &weights).as_deref(), Some("tt0087182"));
+        assert_eq!(pick_best_candidate(dune(), "Dune", Some(2021), &weights).as_deref(), Some("tt1160419"));
+        // No year: the more popular film rather than the first result
+        assert_eq!(pick_best_candidate(dune(), "Dune", None, &weights).as_deref(), Some("tt1160419"));
+
+        // Equal scores keep the search order
+        let tied = vec![candidate("tt0000001", 1990, 5.0), candidate("tt0000002", 1990, 5.0)];
+        assert_eq!(pick_best_candidate(tied, "Twins", Some(1990), &weights).as_deref(), Some("tt0000001"));
+
+        // Popularity can outweigh the year when configured to
+        let popularity_first = IdMatchingConfig { year_match_weight: 0.5, popularity_weight: 1.0, ..IdMatchingConfig::default() };
+        assert_eq!(pick_best_candidate(dune(), "Dune", Some(1984), &popularity_first).as_deref(), Some("tt1160419"));
+        assert_eq!(pick_best_candidate(Vec::<TitleCandidate<String>>::new(), "Dune", None, &weights), None);
+    }
+
+    #[test]
+    fn test_titles_match_normalized_and_years_within_the_tolerance() {
+        let weights = IdMatchingConfig::default();
+        let titled = |id: &str, title: &str, year: u32, popularity: f64| TitleCandidate {
+            title: Some(title.to_string()),
+            ..candidate(id, year, popularity)
+        };
+        let results = || vec![
+            titled("tt0234215", "The Matrix Reloaded", 2003, 500.0),
+            titled("tt0133093", "Matrix, The", 1999, 300.0),
+            titled("tt0000003", "The Matrix", 2012, 1.0),
+        ];
+
+        // A release year one off (a festival premiere) still counts as the year
+        assert_eq!(pick_best_candidate(results(), "the matrix!", Some(1998), &weights).as_deref(), Some("tt0133093"));
+        // Only the titles that match are considered, even when another is more popular
+        assert_eq!(pick_best_candidate(results(), "The Matrix", None, &weights).as_deref(), Some("tt0133093"));
+        // Two years off is outside the default tolerance of one
+        let strict = IdMatchingConfig { year_tolerance: 0, ..IdMatchingConfig::default() };
+        assert_eq!(pick_best_candidate(results(), "The Matrix", Some(2011), &strict).as_deref(), Some("tt0133093"));
+        assert_eq!(pick_best_candidate(results(), "The Matrix", Some(2011), &weights).as_deref(), Some("tt0000003"));
+
+        assert!(within_year_tolerance(Some(1999), Some(2000), 1));
+        assert!(!within_year_tolerance(Some(1999), Some(2001), 1));
+        assert!(within_year_tolerance(None, Some(2001), 0));
+        assert_eq!(normalize_title("Matrix, The"), normalize_title("the matrix!"));
+    }
+}
@@ -1,13 +1,56 @@
+use browser_debug::VerificationResult;
 use std::fmt;
 
 #[derive(Debug)]
 pub struct SourceError {
     message: String,
+    kind: SourceErrorKind,
+}
+
+/// What a [`SourceError`] is about, for callers that react differently per failure
+#[derive(Debug, Clone)]
+pub enum SourceErrorKind {
+    Other,
+    /// A step of IMDB browser automation failed
+    ImdbAutomation(ImdbAutomationError),
+    /// A write sent in chunks failed part way; the first `committed` items were written
+    PartialWrite { committed: usize },
 }
 
 impl SourceError {
     pub fn new(message: String) -> Self {
-        Self { message }
+        Self {
+            message,
+            kind: SourceErrorKind::Other,
+        }
+    }
+
+    /// A failed write of which the first `committed` items went through
+    pub fn partial_write(message: String, committed: usize) -> Self {
+        Self {
+            message,
+            kind: SourceErrorKind::PartialWrite { committed },
+        }
+    }
+
+    pub fn kind(&self) -> &SourceErrorKind {
+        &self.kind
+    }
+
+    pub fn as_imdb_automation(&self) -> Option<&ImdbAutomationError> {
+        match &self.kind {
+            SourceErrorKind::ImdbAutomation(e) => Some(e),
+            SourceErrorKind::Other | SourceErrorKind::PartialWrite { .. } => None,
+        }
+    }
+
+    /// Items written before the write failed, counted from the start of the slice passed
+    /// to it; 0 unless the write went out in chunks
+    pub fn committed(&self) -> usize {
+        match self.kind {
+            SourceErrorKind::PartialWrite { committed } => committed,
+            _ => 0,
+        }
     }
 }
 
@@ -19,4 +62,118 @@ impl fmt::Display for SourceError {
 
 impl std::error::Error for SourceError {}
 
+impl From<ImdbAutomationError> for SourceError {
+    fn from(error: ImdbAutomationError) -> Self {
+        Self {
+            message: error.to_string(),
+            kind: SourceErrorKind::ImdbAutomation(error),
+        }
+    }
+}
 
+/// Step of the IMDB browser automation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImdbStep {
+    /// Launching the browser or a health check on it
+    Browser,
+    Login,
+    /// Opening an IMDB page
+    Navigate,
+    /// Asking IMDB to generate an export
+    ExportTrigger,
+    /// Downloading a generated export
+    Download,
+    /// Parsing a downloaded export
+    Parse,
+}
+
+impl ImdbStep {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImdbStep::Browser => "browser",
+            ImdbStep::Login => "login",
+            ImdbStep::Navigate => "navigate",
+            ImdbStep::ExportTrigger => "export trigger",
+            ImdbStep::Download => "download",
+            ImdbStep::Parse => "parse",
+        }
+    }
+
+    /// Whether running the sync again may succeed without the user changing anything
+    pub fn is_retryable(&self) -> bool {
+        !matches!(self, ImdbStep::Login | ImdbStep::Parse)
+    }
+
+    fn hint(&self) -> &'static str {
+        match self {
+            ImdbStep::Login => "check the IMDB username and password in the credentials",
+            ImdbStep::Parse => "IMDB may have changed its export format; the raw CSV is kept in the cache",
+            ImdbStep::Browser => "check that Chromium can start, then retry",
+            ImdbStep::Navigate | ImdbStep::ExportTrigger | ImdbStep::Download => {
+                "IMDB exports are often flaky, retry the sync"
+            }
+        }
+    }
+}
+
+/// An IMDB automation failure with the step it happened in and, when available, what
+/// `browser_debug::verify_action_result` found when comparing the page state before
+/// and after the step
+#[derive(Debug, Clone)]
+pub struct ImdbAutomationError {
+    pub step: ImdbStep,
+    pub message: String,
+    pub verification: Option<VerificationResult>,
+}
+
+impl ImdbAutomationError {
+    pub fn new(step: ImdbStep, message: impl Into<String>) -> Self {
+        Self {
+            step,
+            message: message.into(),
+            verification: None,
+        }
+    }
+
+    pub fn with_verification(mut self, verification: VerificationResult) -> Self {
+        self.verification = Some(verification);
+        self
+    }
+}
+
+impl fmt::Display for ImdbAutomationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "IMDB {} failed: {}", self.step.as_str(), self.message)?;
+        match &self.verification {
+            Some(VerificationResult::Success) => write!(f, " (verification: the step took effect)")?,
+            Some(VerificationResult::Failed(details)) => write!(f, " (verification: {})", details)?,
+            Some(VerificationResult::Inconclusive(details)) => write!(f, " (verification inconclusive: {})", details)?,
+            None => {}
+        }
+        write!(f, "; {}", self.step.hint())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_imdb_automation_error_names_step_and_verification() {
+        let error: SourceError = ImdbAutomationError::new(ImdbStep::Login, "submit button not found")
+            .with_verification(VerificationResult::Failed("Expected changes not found: signed_in".to_string()))
+            .into();
+
+        let automation = error.as_imdb_automation().unwrap();
+        assert_eq!(automation.step, ImdbStep::Login);
+        assert!(!automation.step.is_retryable());
+        assert_eq!(
+            error.to_string(),
+            "IMDB login failed: submit button not found (verification: Expected changes not found: signed_in); \
+             check the IMDB username and password in the credentials"
+        );
+
+        assert!(SourceError::new("boom".to_string()).as_imdb_automation().is_none());
+        assert!(ImdbStep::Download.is_retryable());
+    }
+}
@@ -3,7 +3,7 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use media_sync_config::{Config, CredentialStore};
-use crate::{MediaSource, SourceError};
+use crate::{HttpCache, MediaSource, RateLimiter, RetryPolicy, SourceError, TmdbLookupProvider};
 
 /// Factory trait for creating media sources from configuration
 #[async_trait]
@@ -30,6 +30,16 @@ pub trait SourceFactory: Send + Sync {
     }
 }
 
+/// Conditional GET store of the API sources, in `cache/http/`
+fn http_cache() -> HttpCache {
+    HttpCache::new(media_sync_config::PathManager::default().cache_http_dir())
+}
+
+/// Per-request timeout of the API clients (`sources.request_timeout_secs`)
+fn request_timeout(config: &Config) -> Option<std::time::Duration> {
+    config.sources.request_timeout_secs.map(std::time::Duration::from_secs)
+}
+
 /// Registry of source factories
 pub struct SourceFactoryRegistry {
     factories: std::collections::HashMap<String, Box<dyn SourceFactory>>,
@@ -47,6 +57,9 @@ impl SourceFactoryRegistry {
         registry.register(Box::new(simkl::SimklSourceFactory));
         registry.register(Box::new(imdb::ImdbSourceFactory));
         registry.register(Box::new(plex::PlexSourceFactory));
+        registry.register(Box::new(letterboxd::LetterboxdSourceFactory));
+        registry.register(Box::new(anilist::AniListSourceFactory));
+        registry.register(Box::new(file::FileSourceFactory));
         
         registry
     }
@@ -135,7 +148,13 @@ mod trakt {
                     Ok(Some(Box::new(TraktClient::new(
                         trakt_config.client_id.clone(),
                         trakt_config.client_secret.clone(),
-                    ))))
+                    ).with_id_matching(config.id_matching.clone())
+                        .with_likes_as(trakt_config.likes_as)
+                        .with_batch_size(trakt_config.batch_size)
+                        .with_http_cache(http_cache())
+                        .with_request_timeout(request_timeout(config))
+                        .with_retry_policy(RetryPolicy::from(&trakt_config.retry)
+                            .with_rate_limiter(RateLimiter::from(&trakt_config.rate_limit))))))
                 } else {
                     Ok(None)
                 }
@@ -187,7 +206,11 @@ mod simkl {
                         simkl_config.client_id.clone(),
                         simkl_config.client_secret.clone(),
                     )
-                    .with_status_mapping(simkl_config.status_mapping.clone());
+                    .with_status_mapping(simkl_config.status_mapping.clone())
+                    .with_http_cache(http_cache())
+                    .with_request_timeout(request_timeout(config))
+                    .with_retry_policy(RetryPolicy::from(&simkl_config.retry)
+                        .with_rate_limiter(RateLimiter::from(&simkl_config.rate_limit)));
                     return Ok(Some(Box::new(client)));
                 }
             }
@@ -229,11 +252,13 @@ mod imdb {
         ) -> Result<Option<Box<dyn MediaSource<Error = SourceError>>>> {
             if let Some(imdb_config) = &config.sources.imdb {
                 if imdb_config.enabled {
+                    credentials.check_account("imdb")?;
                     let password = credentials.get_imdb_password()
                         .ok_or_else(|| anyhow::anyhow!("IMDB password not found in credentials. Run 'totalrecall config imdb' first"))?
                         .clone();
                     
-                    let client = ImdbClient::new(imdb_config.username.clone(), password).await?;
+                    let client = ImdbClient::new(imdb_config.username.clone(), password).await?
+                        .with_resolve_episode_checkins(imdb_config.resolve_episode_checkins);
                     return Ok(Some(Box::new(client)));
                 }
             }
@@ -273,6 +298,7 @@ mod plex {
             if let Some(plex_config) = &config.sources.plex {
                 if plex_config.enabled {
                     // Get Plex token from credentials
+                    credentials.check_account("plex")?;
                     let token = credentials.get_plex_token()
                         .ok_or_else(|| anyhow::anyhow!("Plex token not found in credentials. Run 'totalrecall config plex' first"))?
                         .clone();
@@ -283,7 +309,13 @@ mod plex {
                         Some(plex_config.server_url.clone())
                     };
                     
-                    let client = PlexClient::with_server_url(token, server_url, plex_config.status_mapping.clone());
+                    let client = PlexClient::with_server_url(token, server_url, plex_config.status_mapping.clone())
+                        .with_tmdb(TmdbLookupProvider::from_config(config))
+                        .with_skip_unmatched(plex_config.skip_unmatched)
+                        .with_rating_scales(plex_config.rating_scales.clone())
+                        .with_label_status_map(plex_config.label_status_map.clone())
+                        .with_http_cache(http_cache())
+                        .with_request_timeout(request_timeout(config));
                     return Ok(Some(Box::new(client)));
                 }
             }
@@ -302,3 +334,119 @@ mod plex {
     }
 }
 
+mod letterboxd {
+    use super::*;
+    use crate::letterboxd::LetterboxdClient;
+    use media_sync_config::PathManager;
+    
+    pub struct LetterboxdSourceFactory;
+    
+    #[async_trait::async_trait]
+    impl SourceFactory for LetterboxdSourceFactory {
+        fn source_name(&self) -> &str {
+            "letterboxd"
+        }
+        
+        async fn create_source(
+            &self,
+            config: &Config,
+            _credentials: &CredentialStore,
+        ) -> Result<Option<Box<dyn MediaSource<Error = SourceError>>>> {
+            if let Some(letterboxd_config) = &config.sources.letterboxd {
+                if letterboxd_config.enabled {
+                    let import_file = PathManager::default().cache_csv_dir("letterboxd").join("watchlist-import.csv");
+                    let client = LetterboxdClient::new(&letterboxd_config.export_path, import_file);
+                    return Ok(Some(Box::new(client)));
+                }
+            }
+            Ok(None)
+        }
+        
+        fn validate_config(&self, config: &Config) -> Result<()> {
+            if let Some(letterboxd_config) = &config.sources.letterboxd {
+                if letterboxd_config.enabled && letterboxd_config.export_path.is_empty() {
+                    return Err(anyhow::anyhow!("Letterboxd is enabled but export_path is not configured"));
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+mod anilist {
+    use super::*;
+    use crate::anilist::AniListClient;
+    
+    pub struct AniListSourceFactory;
+    
+    #[async_trait::async_trait]
+    impl SourceFactory for AniListSourceFactory {
+        fn source_name(&self) -> &str {
+            "anilist"
+        }
+        
+        async fn create_source(
+            &self,
+            config: &Config,
+            credentials: &CredentialStore,
+        ) -> Result<Option<Box<dyn MediaSource<Error = SourceError>>>> {
+            if let Some(anilist_config) = &config.sources.anilist {
+                if anilist_config.enabled {
+                    // The token is optional: without it the public list is read-only
+                    credentials.check_account("anilist")?;
+                    let token = credentials.get_anilist_access_token().cloned();
+                    let client = AniListClient::new(anilist_config.username.clone(), token)
+                        .with_score_scale(anilist_config.score_scale)
+                        .with_request_timeout(request_timeout(config));
+                    return Ok(Some(Box::new(client)));
+                }
+            }
+            Ok(None)
+        }
+        
+        fn validate_config(&self, config: &Config) -> Result<()> {
+            if let Some(anilist_config) = &config.sources.anilist {
+                if anilist_config.enabled && anilist_config.score_scale != 10 && anilist_config.score_scale != 100 {
+                    return Err(anyhow::anyhow!("AniList score_scale must be 10 or 100"));
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+mod file {
+    use super::*;
+    use crate::file::FileSource;
+    
+    pub struct FileSourceFactory;
+    
+    #[async_trait::async_trait]
+    impl SourceFactory for FileSourceFactory {
+        fn source_name(&self) -> &str {
+            "file"
+        }
+        
+        async fn create_source(
+            &self,
+            config: &Config,
+            _credentials: &CredentialStore,
+        ) -> Result<Option<Box<dyn MediaSource<Error = SourceError>>>> {
+            if let Some(file_config) = &config.sources.file {
+                if file_config.enabled {
+                    return Ok(Some(Box::new(FileSource::ndjson("file", &file_config.path))));
+                }
+            }
+            Ok(None)
+        }
+        
+        fn validate_config(&self, config: &Config) -> Result<()> {
+            if let Some(file_config) = &config.sources.file {
+                if file_config.enabled && file_config.path.is_empty() {
+                    return Err(anyhow::anyhow!("The file source is enabled but path is not configured"));
+                }
+            }
+            Ok(())
+        }
+    }
+}
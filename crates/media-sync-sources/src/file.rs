@@ -0,0 +1,277 @@
+//! Source backed by JSON files.
+//!
+//! [`FileSource::new`] serves `{dir}/{data_type}.json`, one JSON array per data type,
+//! which is the layout of the collect cache. Writes fail: those files are only ever
+//! produced by TotalRecall itself, e.g. the synthetic `hub` source holding the resolved
+//! data of an earlier sync (`sync.hub.export`).
+//!
+//! [`FileSource::ndjson`] serves `{dir}/{data_type}.ndjson`, one item per line, and takes
+//! writes, which makes it a backup that syncs like any other source (`[sources.file]`).
+//! Written items replace those with the same IMDB ID (the same IMDB ID and time for
+//! watch history) and the rest are appended.
+//!
+//! Either way a missing file is an empty list.
+
+use crate::capabilities::{CapabilityRegistry, IdExtraction, IdLookupProvider, IncrementalSync, RatingNormalization, StatusMapping};
+use crate::error::SourceError;
+use crate::traits::{DataType, MediaSource, WrittenReview};
+use async_trait::async_trait;
+use media_sync_models::{Rating, Review, WatchHistory, WatchlistItem};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FileFormat {
+    /// One JSON array per file, read-only
+    Json,
+    /// One JSON item per line, writable
+    Ndjson,
+}
+
+pub struct FileSource {
+    name: String,
+    dir: PathBuf,
+    format: FileFormat,
+}
+
+impl FileSource {
+    /// Read-only source over JSON arrays in the collect cache layout
+    pub fn new(name: impl Into<String>, dir: impl Into<PathBuf>) -> Self {
+        Self {
+            name: name.into(),
+            dir: dir.into(),
+            format: FileFormat::Json,
+        }
+    }
+
+    /// Writable source over NDJSON files; `dir` is created on the first write
+    pub fn ndjson(name: impl Into<String>, dir: impl Into<PathBuf>) -> Self {
+        Self {
+            format: FileFormat::Ndjson,
+            ..Self::new(name, dir)
+        }
+    }
+
+    /// Whether the directory holds a file for any data type
+    pub fn exists(&self) -> bool {
+        DataType::ALL.iter().any(|data_type| self.path(*data_type).exists())
+    }
+
+    fn path(&self, data_type: DataType) -> PathBuf {
+        let extension = match self.format {
+            FileFormat::Json => "json",
+            FileFormat::Ndjson => "ndjson",
+        };
+        self.dir.join(format!("{}.{}", data_type, extension))
+    }
+
+    fn load<T: DeserializeOwned>(&self, data_type: DataType) -> Result<Vec<T>, SourceError> {
+        let path = self.path(data_type);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| SourceError::new(format!("Failed to read {}: {}", path.display(), e)))?;
+        match self.format {
+            FileFormat::Json => serde_json::from_str(&content)
+                .map_err(|e| SourceError::new(format!("Failed to parse {}: {}", path.display(), e))),
+            FileFormat::Ndjson => content.lines()
+                .enumerate()
+                .filter(|(_, line)| !line.trim().is_empty())
+                .map(|(index, line)| {
+                    serde_json::from_str(line)
+                        .map_err(|e| SourceError::new(format!("Failed to parse {} line {}: {}", path.display(), index + 1, e)))
+                })
+                .collect(),
+        }
+    }
+
+    /// Rewrite the file of `data_type` with `items` merged in: an existing item with the
+    /// same `key` is replaced, the others are appended. Items without a key (no IMDB ID)
+    /// are only appended when no identical line is there yet.
+    fn merge<T: Serialize + DeserializeOwned + Clone>(
+        &self,
+        data_type: DataType,
+        items: &[T],
+        key: impl Fn(&T) -> Option<String>,
+    ) -> Result<(), SourceError> {
+        if self.format != FileFormat::Ndjson {
+            return Err(self.read_only());
+        }
+        let new_keys: HashSet<String> = items.iter().filter_map(&key).collect();
+        let mut merged: Vec<T> = self.load(data_type)?;
+        merged.retain(|item| key(item).is_none_or(|key| !new_keys.contains(&key)));
+        merged.extend(items.iter().cloned());
+        self.save(data_type, &merged)
+    }
+
+    fn save<T: Serialize>(&self, data_type: DataType, items: &[T]) -> Result<(), SourceError> {
+        let path = self.path(data_type);
+        let mut content = String::new();
+        let mut seen = HashSet::new();
+        for item in items {
+            let line = serde_json::to_string(item)
+                .map_err(|e| SourceError::new(format!("Failed to serialize {}: {}", data_type, e)))?;
+            if seen.insert(line.clone()) {
+                content.push_str(&line);
+                content.push('\n');
+            }
+        }
+        std::fs::create_dir_all(&self.dir)
+            .map_err(|e| SourceError::new(format!("Failed to create {}: {}", self.dir.display(), e)))?;
+        // Write aside and rename, so an interrupted write leaves the old file intact
+        let tmp = path.with_extension("ndjson.tmp");
+        std::fs::write(&tmp, content)
+            .and_then(|_| std::fs::rename(&tmp, &path))
+            .map_err(|e| SourceError::new(format!("Failed to write {}: {}", path.display(), e)))
+    }
+
+    fn read_only(&self) -> SourceError {
+        SourceError::new(format!("Source '{}' is read-only", self.name))
+    }
+}
+
+#[async_trait]
+impl MediaSource for FileSource {
+    type Error = SourceError;
+
+    fn source_name(&self) -> &str {
+        &self.name
+    }
+
+    async fn authenticate(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn is_authenticated(&self) -> bool {
+        true
+    }
+
+    async fn get_watchlist(&self) -> Result<Vec<WatchlistItem>, Self::Error> {
+        self.load(DataType::Watchlist)
+    }
+
+    async fn get_ratings(&self) -> Result<Vec<Rating>, Self::Error> {
+        self.load(DataType::Ratings)
+    }
+
+    async fn get_reviews(&self) -> Result<Vec<Review>, Self::Error> {
+        self.load(DataType::Reviews)
+    }
+
+    async fn get_watch_history(&self) -> Result<Vec<WatchHistory>, Self::Error> {
+        self.load(DataType::WatchHistory)
+    }
+
+    async fn add_to_watchlist(&self, items: &[WatchlistItem]) -> Result<(), Self::Error> {
+        self.merge(DataType::Watchlist, items, |item| imdb_key(&item.imdb_id))
+    }
+
+    async fn remove_from_watchlist(&self, items: &[WatchlistItem]) -> Result<(), Self::Error> {
+        if self.format != FileFormat::Ndjson {
+            return Err(self.read_only());
+        }
+        let removed: HashSet<&str> = items.iter()
+            .filter(|item| !item.imdb_id.is_empty())
+            .map(|item| item.imdb_id.as_str())
+            .collect();
+        let mut watchlist: Vec<WatchlistItem> = self.load(DataType::Watchlist)?;
+        watchlist.retain(|item| !removed.contains(item.imdb_id.as_str()));
+        self.save(DataType::Watchlist, &watchlist)
+    }
+
+    async fn set_ratings(&self, ratings: &[Rating]) -> Result<(), Self::Error> {
+        self.merge(DataType::Ratings, ratings, |rating| imdb_key(&rating.imdb_id))
+    }
+
+    async fn set_reviews(&self, reviews: &[Review]) -> Result<Vec<WrittenReview>, Self::Error> {
+        self.merge(DataType::Reviews, reviews, |review| imdb_key(&review.imdb_id))?;
+        Ok(WrittenReview::all(reviews))
+    }
+
+    async fn add_watch_history(&self, items: &[WatchHistory]) -> Result<(), Self::Error> {
+        // One line per play, so the same title watched twice keeps both
+        self.merge(DataType::WatchHistory, items, |item| {
+            imdb_key(&item.imdb_id).map(|key| format!("{}@{}", key, item.watched_at.to_rfc3339()))
+        })
+    }
+}
+
+fn imdb_key(imdb_id: &str) -> Option<String> {
+    (!imdb_id.is_empty()).then(|| imdb_id.to_string())
+}
+
+/// Ratings in the files are already on the normalized 1-10 scale, so no capabilities
+impl CapabilityRegistry for FileSource {
+    fn as_incremental_sync(&mut self) -> Option<&mut dyn IncrementalSync> {
+        None
+    }
+
+    fn as_rating_normalization(&self) -> Option<&dyn RatingNormalization> {
+        None
+    }
+
+    fn as_status_mapping(&self) -> Option<&dyn StatusMapping> {
+        None
+    }
+
+    fn as_id_extraction(&self) -> Option<&dyn IdExtraction> {
+        None
+    }
+
+    fn as_id_lookup_provider(&self) -> Option<&dyn IdLookupProvider> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_file_source_reads_cache_layout_and_rejects_writes() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = FileSource::new("hub", dir.path());
+        assert!(!source.exists());
+        assert!(source.get_watchlist().await.unwrap().is_empty());
+
+        std::fs::write(
+            dir.path().join("ratings.json"),
+            r#"[{"imdb_id":"tt0133093","rating":9,"date_added":"2024-01-01T00:00:00Z","media_type":"Movie","source":"Trakt"}]"#,
+        ).unwrap();
+        assert!(source.exists());
+        let ratings = source.get_ratings().await.unwrap();
+        assert_eq!(ratings.len(), 1);
+        assert_eq!(ratings[0].rating, 9);
+
+        let err = source.set_ratings(&ratings).await.unwrap_err();
+        assert_eq!(err.to_string(), "Source 'hub' is read-only");
+    }
+
+    #[tokio::test]
+    async fn test_ndjson_source_merges_writes_by_imdb_id() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = FileSource::ndjson("file", dir.path().join("backup"));
+        let rating = |imdb_id: &str, value| Rating {
+            imdb_id: imdb_id.to_string(),
+            ids: None,
+            rating: value,
+            rating_f64: None,
+            date_added: chrono::Utc::now(),
+            media_type: media_sync_models::MediaType::Movie,
+            source: media_sync_models::RatingSource::Trakt,
+            inferred: false,
+        };
+
+        source.set_ratings(&[rating("tt0133093", 9), rating("tt0068646", 7)]).await.unwrap();
+        source.set_ratings(&[rating("tt0068646", 10), rating("tt0110912", 8)]).await.unwrap();
+
+        let content = std::fs::read_to_string(dir.path().join("backup/ratings.ndjson")).unwrap();
+        assert_eq!(content.lines().count(), 3);
+        let ratings = source.get_ratings().await.unwrap();
+        let values: Vec<(&str, u8)> = ratings.iter().map(|r| (r.imdb_id.as_str(), r.rating)).collect();
+        assert_eq!(values, vec![("tt0133093", 9), ("tt0068646", 10), ("tt0110912", 8)]);
+    }
+}
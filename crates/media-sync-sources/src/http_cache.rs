@@ -0,0 +1,244 @@
+//! Conditional GETs for source APIs.
+//!
+//! An [`HttpCache`] keeps the `ETag` and `Last-Modified` of each JSON response on disk,
+//! keyed by URL, together with its body. The next GET of the URL sends them back as
+//! `If-None-Match` and `If-Modified-Since`; when the server answers 304 the stored body
+//! is used instead. Within one process the parsed value is kept too, so a daemon whose
+//! sources did not change neither downloads nor parses anything.
+//!
+//! Entries live in `cache/http/`; removing the directory only costs one full download
+//! per endpoint. Requests whose URL changes every time (a `date_from` of "now") are
+//! never stored, since no later request would read them back.
+
+use anyhow::{anyhow, Result};
+use reqwest::header::{HeaderValue, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::{RequestBuilder, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tracing::{debug, warn};
+
+/// A stored response: its validators and body
+#[derive(Debug, Serialize, Deserialize)]
+struct Entry {
+    url: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    body: String,
+}
+
+/// Query parameters that make a URL one-off, so its response is not stored
+const VOLATILE_QUERY_PARAMS: &[&str] = &["date_from"];
+
+/// Parsed values of this process, with the validator they were parsed for
+type Parsed = HashMap<String, (String, Arc<dyn Any + Send + Sync>)>;
+
+/// Store of conditional GET responses; clones share it. The default stores nothing.
+#[derive(Clone, Default)]
+pub struct HttpCache {
+    dir: Option<PathBuf>,
+    parsed: Arc<Mutex<Parsed>>,
+}
+
+impl std::fmt::Debug for HttpCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HttpCache").field("dir", &self.dir).finish()
+    }
+}
+
+impl HttpCache {
+    /// Keep responses in `dir`
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: Some(dir.into()), parsed: Arc::default() }
+    }
+
+    /// Send a GET and parse its JSON body, revalidating a stored response when there is
+    /// one. `what` names the data in errors ("Failed to fetch {what}: ...").
+    pub async fn get_json<T>(&self, what: &str, request: RequestBuilder) -> Result<T>
+    where
+        T: DeserializeOwned + Clone + Send + Sync + 'static,
+    {
+        let Some(dir) = &self.dir else {
+            return Self::fetch(what, request).await;
+        };
+
+        let (client, request) = request.build_split();
+        let mut request = request?;
+        if is_volatile(request.url()) {
+            return Self::parse(what, client.execute(request).await?).await;
+        }
+        let url = request.url().to_string();
+        let path = entry_path(dir, &url);
+        let stored = load(&path).filter(|entry| entry.url == url);
+        if let Some(entry) = &stored {
+            let headers = request.headers_mut();
+            if let Some(value) = entry.etag.as_deref().and_then(|v| HeaderValue::from_str(v).ok()) {
+                headers.insert(IF_NONE_MATCH, value);
+            }
+            if let Some(value) = entry.last_modified.as_deref().and_then(|v| HeaderValue::from_str(v).ok()) {
+                headers.insert(IF_MODIFIED_SINCE, value);
+            }
+        }
+
+        let response = client.execute(request).await?;
+        let status = response.status();
+        if status == StatusCode::NOT_MODIFIED {
+            if let Some(entry) = stored {
+                debug!("{} not modified, using the stored response", url);
+                return self.parsed_or_parse(&url, &entry);
+            }
+        }
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(anyhow!("Failed to fetch {}: {} - {}", what, status, error_text));
+        }
+
+        let header = |name| response.headers().get(name).and_then(|v: &HeaderValue| v.to_str().ok()).map(str::to_string);
+        let etag = header(ETAG);
+        let last_modified = header(LAST_MODIFIED);
+        let body = response.text().await?;
+        let value: T = serde_json::from_str(&body)?;
+
+        if etag.is_some() || last_modified.is_some() {
+            let entry = Entry { url: url.clone(), etag, last_modified, body };
+            self.remember(&url, &entry, value.clone());
+            if let Err(e) = save(&path, &entry) {
+                warn!("Failed to store the response of {}: {}", url, e);
+            }
+        }
+        Ok(value)
+    }
+
+    async fn fetch<T: DeserializeOwned>(what: &str, request: RequestBuilder) -> Result<T> {
+        Self::parse(what, request.send().await?).await
+    }
+
+    async fn parse<T: DeserializeOwned>(what: &str, response: reqwest::Response) -> Result<T> {
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(anyhow!("Failed to fetch {}: {} - {}", what, status, error_text));
+        }
+        Ok(response.json().await?)
+    }
+
+    /// The value parsed for `entry` earlier in this process, or `entry`'s body parsed now
+    fn parsed_or_parse<T>(&self, url: &str, entry: &Entry) -> Result<T>
+    where
+        T: DeserializeOwned + Clone + Send + Sync + 'static,
+    {
+        let validator = validator(entry);
+        if let Some((parsed_for, value)) = self.parsed.lock().unwrap().get(url) {
+            if *parsed_for == validator {
+                if let Some(value) = value.downcast_ref::<T>() {
+                    return Ok(value.clone());
+                }
+            }
+        }
+        let value: T = serde_json::from_str(&entry.body)?;
+        self.remember(url, entry, value.clone());
+        Ok(value)
+    }
+
+    fn remember<T: Send + Sync + 'static>(&self, url: &str, entry: &Entry, value: T) {
+        self.parsed.lock().unwrap().insert(url.to_string(), (validator(entry), Arc::new(value)));
+    }
+}
+
+fn is_volatile(url: &reqwest::Url) -> bool {
+    url.query_pairs().any(|(name, _)| VOLATILE_QUERY_PARAMS.contains(&name.as_ref()))
+}
+
+fn validator(entry: &Entry) -> String {
+    format!("{}|{}", entry.etag.as_deref().unwrap_or(""), entry.last_modified.as_deref().unwrap_or(""))
+}
+
+fn entry_path(dir: &Path, url: &str) -> PathBuf {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    url.hash(&mut hasher);
+    dir.join(format!("{:016x}.json", hasher.finish()))
+}
+
+fn load(path: &Path) -> Option<Entry> {
+    let content = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn save(path: &Path, entry: &Entry) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_vec(entry)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{serve, MockRequest, MockResponse};
+
+    /// Answer each request in turn with the given response; returns the URL to request
+    fn mock_server(responses: Vec<MockResponse>) -> (String, std::thread::JoinHandle<Vec<MockRequest>>) {
+        let (base, handle) = serve(responses);
+        (format!("{}/sync/all-items/", base), handle)
+    }
+
+    fn ok(body: &str) -> MockResponse {
+        MockResponse::new("200 OK").with_header("ETag", "\"v1\"").with_body(body)
+    }
+
+    fn not_modified() -> MockResponse {
+        MockResponse::new("304 Not Modified").with_header("ETag", "\"v1\"")
+    }
+
+    #[tokio::test]
+    async fn test_not_modified_uses_stored_response() {
+        let temp = tempfile::tempdir().unwrap();
+        let (url, server) = mock_server(vec![ok(r#"["tt0111161"]"#), not_modified(), not_modified()]);
+        let client = reqwest::Client::new();
+
+        let cache = HttpCache::new(temp.path());
+        let first: Vec<String> = cache.get_json("watchlist", client.get(&url)).await.unwrap();
+        let second: Vec<String> = cache.get_json("watchlist", client.get(&url)).await.unwrap();
+        // A new process only has the entry on disk
+        let third: Vec<String> = HttpCache::new(temp.path()).get_json("watchlist", client.get(&url)).await.unwrap();
+        assert_eq!(first, ["tt0111161"]);
+        assert_eq!(second, first);
+        assert_eq!(third, first);
+
+        let requests = server.join().unwrap();
+        assert!(!requests[0].headers.contains("if-none-match"));
+        assert!(requests[1].headers.contains("if-none-match: \"v1\""));
+        assert!(requests[2].headers.contains("if-none-match: \"v1\""));
+    }
+
+    #[tokio::test]
+    async fn test_date_from_requests_are_not_stored() {
+        let temp = tempfile::tempdir().unwrap();
+        let (url, server) = mock_server(vec![ok(r#"["tt0111161"]"#)]);
+
+        let cache = HttpCache::new(temp.path());
+        let request = reqwest::Client::new().get(format!("{}?date_from=2024-06-01T12:00:00Z", url));
+        let items: Vec<String> = cache.get_json("watchlist", request).await.unwrap();
+        assert_eq!(items, ["tt0111161"]);
+
+        server.join().unwrap();
+        assert_eq!(std::fs::read_dir(temp.path()).map(|entries| entries.count()).unwrap_or(0), 0);
+    }
+
+    #[tokio::test]
+    async fn test_errors_name_the_data() {
+        let (url, server) = mock_server(vec![MockResponse::new("401 Unauthorized")]);
+        let temp = tempfile::tempdir().unwrap();
+        let error = HttpCache::new(temp.path())
+            .get_json::<Vec<String>>("ratings", reqwest::Client::new().get(&url))
+            .await
+            .unwrap_err();
+        assert!(error.to_string().starts_with("Failed to fetch ratings: 401 Unauthorized"));
+        server.join().unwrap();
+    }
+}
@@ -1,14 +1,15 @@
 use anyhow::Result;
 use chromiumoxide::Page;
 use media_sync_models::{Rating, Review, WatchHistory, WatchlistItem};
-use crate::ProgressTracker;
+use crate::{ProgressObserver, ProgressTracker};
+use crate::traits::WrittenReview;
 use std::time::Duration;
 use tokio::time::sleep;
 use tracing::{debug, info, trace, warn};
 use browser_debug::PageInspector;
 
 /// Add items to IMDB watchlist
-pub async fn add_to_watchlist(page: &Page, items: &[WatchlistItem]) -> Result<()> {
+pub async fn add_to_watchlist(page: &Page, items: &[WatchlistItem], observer: Option<ProgressObserver>) -> Result<()> {
     if items.is_empty() {
         return Ok(());
     }
@@ -18,7 +19,7 @@ pub async fn add_to_watchlist(page: &Page, items: &[WatchlistItem]) -> Result<()
         items.len(),
         progress_interval,
         Some("IMDB watchlist add".to_string()),
-    );
+    ).with_observer(observer);
 
     for (idx, item) in items.iter().enumerate() {
         let current = idx + 1;
@@ -137,7 +138,7 @@ pub async fn add_to_watchlist(page: &Page, items: &[WatchlistItem]) -> Result<()
 }
 
 /// Remove items from IMDB watchlist
-pub async fn remove_from_watchlist(page: &Page, items: &[WatchlistItem]) -> Result<()> {
+pub async fn remove_from_watchlist(page: &Page, items: &[WatchlistItem], observer: Option<ProgressObserver>) -> Result<()> {
     if items.is_empty() {
         return Ok(());
     }
@@ -147,7 +148,7 @@ pub async fn remove_from_watchlist(page: &Page, items: &[WatchlistItem]) -> Resu
         items.len(),
         progress_interval,
         Some("IMDB watchlist remove".to_string()),
-    );
+    ).with_observer(observer);
 
     for (idx, item) in items.iter().enumerate() {
         let current = idx + 1;
@@ -270,6 +271,7 @@ pub async fn set_ratings(
     page: &Page,
     ratings: &[Rating],
     mut inspector: Option<&mut PageInspector>,
+    observer: Option<ProgressObserver>,
 ) -> Result<()> {
     if ratings.is_empty() {
         return Ok(());
@@ -280,7 +282,7 @@ pub async fn set_ratings(
         ratings.len(),
         progress_interval,
         Some("IMDB ratings set".to_string()),
-    );
+    ).with_observer(observer);
     
     for (idx, rating) in ratings.iter().enumerate() {
         let current = idx + 1;
@@ -781,7 +783,8 @@ pub async fn set_reviews(
     page: &Page,
     reviews: &[Review],
     last_submitted: Option<chrono::DateTime<chrono::Utc>>,
-) -> Result<()> {
+    observer: Option<ProgressObserver>,
+) -> Result<Vec<WrittenReview>> {
     // Check rate limiting (10 days)
     if let Some(last_date) = last_submitted {
         let days_since = (chrono::Utc::now() - last_date).num_days();
@@ -790,12 +793,12 @@ pub async fn set_reviews(
                 "Reviews were submitted {} days ago. Skipping review submission (10 day limit).",
                 days_since
             );
-            return Ok(());
+            return Ok(Vec::new());
         }
     }
 
     if reviews.is_empty() {
-        return Ok(());
+        return Ok(Vec::new());
     }
 
     let progress_interval = if reviews.len() < 25 { 10 } else { 25 };
@@ -803,8 +806,9 @@ pub async fn set_reviews(
         reviews.len(),
         progress_interval,
         Some("IMDB reviews set".to_string()),
-    );
+    ).with_observer(observer);
 
+    let mut written = Vec::new();
     for (idx, review) in reviews.iter().enumerate() {
         let current = idx + 1;
         trace!(
@@ -855,8 +859,13 @@ pub async fn set_reviews(
                     title_input.type_str("My Review").await?;
                     sleep(Duration::from_millis(500)).await;
 
-                    // Set review content
-                    review_input.type_str(&review.content).await?;
+                    // Set review content. Text the page can't take only skips this review.
+                    if let Err(e) = review_input.type_str(&review.content).await {
+                        warn!("Failed to enter review text for {}: {}", review.imdb_id, e);
+                        tracker.record_failed();
+                        tracker.log_progress(current);
+                        continue;
+                    }
                     sleep(Duration::from_millis(500)).await;
 
                     // Set spoiler radio button
@@ -883,6 +892,7 @@ pub async fn set_reviews(
                             sleep(Duration::from_secs(3)).await;
                             trace!("Submitted review for {} on IMDB", review.imdb_id);
                             tracker.record_added();
+                            written.push(WrittenReview { index: idx, review_id: None });
                         }
                         Err(e) => {
                             warn!("Failed to find submit button for {}: {}", review.imdb_id, e);
@@ -904,11 +914,11 @@ pub async fn set_reviews(
     }
 
     tracker.log_summary("IMDB reviews set");
-    Ok(())
+    Ok(written)
 }
 
 /// Add watch history (check-ins) on IMDB
-pub async fn add_watch_history(page: &Page, items: &[WatchHistory]) -> Result<()> {
+pub async fn add_watch_history(page: &Page, items: &[WatchHistory], observer: Option<ProgressObserver>) -> Result<()> {
     if items.is_empty() {
         return Ok(());
     }
@@ -918,7 +928,7 @@ pub async fn add_watch_history(page: &Page, items: &[WatchHistory]) -> Result<()
         items.len(),
         progress_interval,
         Some("IMDB check-ins add".to_string()),
-    );
+    ).with_observer(observer);
 
     for (idx, item) in items.iter().enumerate() {
         let current = idx + 1;
@@ -1,4 +1,4 @@
-use crate::traits::MediaSource;
+use crate::traits::{MediaSource, WrittenReview};
 use crate::capabilities::{RatingNormalization, CapabilityRegistry, StatusMapping, IncrementalSync, IdExtraction, IdLookupProvider};
 use crate::imdb::{auth, export, download, parser, actions, reviews};
 use anyhow::{anyhow, Result};
@@ -6,7 +6,7 @@ use chrono::Utc;
 use chromiumoxide::{Browser, BrowserConfig, Page};
 use chromiumoxide::fetcher::{BrowserFetcher, BrowserFetcherOptions};
 use media_sync_config;
-use media_sync_models::{Rating, Review, WatchHistory, WatchlistItem};
+use media_sync_models::{MediaType, Rating, Review, WatchHistory, WatchlistItem};
 use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH, Duration};
 use tokio::time::sleep;
@@ -15,7 +15,8 @@ use which::which;
 use futures::StreamExt;
 use futures::future::BoxFuture;
 use futures::FutureExt;
-use browser_debug::PageInspector;
+use browser_debug::{PageInspector, VerificationResult, verify_action_result};
+use crate::error::{ImdbAutomationError, ImdbStep};
 
 pub struct ImdbClient {
     browser: Option<Browser>,
@@ -28,6 +29,9 @@ pub struct ImdbClient {
     session_id: String,
     downloaded_files: std::sync::Mutex<std::collections::HashMap<String, PathBuf>>,
     debug_config: browser_debug::config::DebugConfig,
+    resolve_episode_checkins: bool,
+    // Told about the progress of exports and writes (see `CapabilityRegistry::set_progress_observer`)
+    progress_observer: Option<crate::ProgressObserver>,
 }
 
 impl ImdbClient {
@@ -55,8 +59,16 @@ impl ImdbClient {
             session_id,
             downloaded_files: std::sync::Mutex::new(std::collections::HashMap::new()),
             debug_config,
+            resolve_episode_checkins: false,
+            progress_observer: None,
         })
     }
+
+    /// Have the sync resolve show-level check-ins to episodes (`imdb.resolve_episode_checkins`)
+    pub fn with_resolve_episode_checkins(mut self, resolve: bool) -> Self {
+        self.resolve_episode_checkins = resolve;
+        self
+    }
     
     /// Internal helper to initialize the browser instance
     /// This is called both during construction and for lazy initialization
@@ -592,6 +604,24 @@ impl ImdbClient {
         self.authenticated
     }
     
+    /// After a failed login, check whether the session ended up signed in anyway
+    async fn verify_signed_in(&self) -> VerificationResult {
+        let Some(browser) = self.browser.as_ref() else {
+            return VerificationResult::Inconclusive("browser not running".to_string());
+        };
+        let signed_in = Self::with_page(browser, "about:blank", false, |page| async move {
+            auth::is_signed_in(page).await
+        }.boxed()).await;
+        match signed_in {
+            Ok(signed_in) => verify_action_result(
+                &serde_json::json!({ "signed_in": false }),
+                &serde_json::json!({ "signed_in": signed_in }),
+                &["signed_in"],
+            ),
+            Err(e) => VerificationResult::Inconclusive(format!("could not check sign-in state: {}", e)),
+        }
+    }
+    
     /// Explicitly shutdown the browser instance
     /// Should be called when sync job completes to free resources
     pub async fn shutdown(&mut self) -> Result<()> {
@@ -643,8 +673,8 @@ impl ImdbClient {
         
         let cache_path = csv_dir.join(cache_filename);
         
-        // Copy CSV to cache directory
-        match std::fs::copy(source_path, &cache_path) {
+        // Copy CSV to cache directory, never leaving a partial copy behind
+        match download::copy_atomically(source_path, &cache_path) {
             Ok(bytes) => {
                 info!("Cached IMDB CSV ({} bytes) to {:?} for debugging", bytes, cache_path);
             }
@@ -735,7 +765,7 @@ impl ImdbClient {
         F: for<'a> FnOnce(&'a Page) -> BoxFuture<'a, Result<R, crate::error::SourceError>>,
     {
         let page = browser.new_page(url).await
-            .map_err(|e| crate::error::SourceError::new(format!("Failed to create new page: {}", e)))?;
+            .map_err(|e| step_error(ImdbStep::Navigate, format!("Failed to create new page: {}", e)))?;
         
         // Configure resource blocking if requested
         if block_resources {
@@ -756,6 +786,32 @@ impl ImdbClient {
     }
 }
 
+/// Error for a failed automation step, so sync errors say which step to look at
+fn step_error(step: ImdbStep, message: impl Into<String>) -> crate::error::SourceError {
+    ImdbAutomationError::new(step, message).into()
+}
+
+/// Download directory state compared by `verify_action_result` after a failed download
+fn download_dir_state(dir: &Path) -> serde_json::Value {
+    let csv_files = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "csv"))
+                .count()
+        })
+        .unwrap_or(0);
+    serde_json::json!({ "csv_files": csv_files })
+}
+
+/// Download failure, noting whether any CSV reached the download directory
+fn download_error(message: String, download_dir: &Path, before: &serde_json::Value) -> crate::error::SourceError {
+    let verification = verify_action_result(before, &download_dir_state(download_dir), &["csv_files"]);
+    ImdbAutomationError::new(ImdbStep::Download, message)
+        .with_verification(verification)
+        .into()
+}
+
 impl Drop for ImdbClient {
     fn drop(&mut self) {
         // Close browser gracefully
@@ -785,9 +841,16 @@ impl MediaSource for ImdbClient {
     }
 
     async fn authenticate(&mut self) -> Result<(), Self::Error> {
+        self.ensure_browser_initialized().await
+            .map_err(|e| step_error(ImdbStep::Browser, format!("{}", e)))?;
         match self.authenticate().await {
             Ok(()) => Ok(()),
-            Err(e) => Err(crate::error::SourceError::new(format!("{}", e))),
+            Err(e) => {
+                let verification = self.verify_signed_in().await;
+                Err(ImdbAutomationError::new(ImdbStep::Login, format!("{}", e))
+                    .with_verification(verification)
+                    .into())
+            }
         }
     }
 
@@ -796,11 +859,11 @@ impl MediaSource for ImdbClient {
     }
 
     async fn get_watchlist(&self) -> Result<Vec<WatchlistItem>, Self::Error> {
-        let browser = self.browser.as_ref().ok_or_else(|| crate::error::SourceError::new("Browser not initialized".to_string()))?;
+        let browser = self.browser.as_ref().ok_or_else(|| step_error(ImdbStep::Browser, "Browser not initialized"))?;
         
         // Check browser health before starting
         Self::check_browser_health(browser).await
-            .map_err(|e| crate::error::SourceError::new(format!("Browser health check failed before watchlist download: {}", e)))?;
+            .map_err(|e| step_error(ImdbStep::Browser, format!("Browser health check failed before watchlist download: {}", e)))?;
         
         // Check if watchlist is empty first by navigating to the watchlist page
         let is_empty = Self::with_page_source_error(browser, "https://www.imdb.com/list/watchlist", false, |check_page| async move {
@@ -889,12 +952,12 @@ impl MediaSource for ImdbClient {
         }
         
         // Generate watchlist export (only if not empty)
-        export::generate_exports(browser, true, false, false, false, false).await
-            .map_err(|e| crate::error::SourceError::new(format!("Failed to generate IMDB watchlist export: {}", e)))?;
+        export::generate_exports(browser, &self.download_dir, true, false, false, false, false, self.progress_observer.clone()).await
+            .map_err(|e| step_error(ImdbStep::ExportTrigger, format!("Failed to generate IMDB watchlist export: {}", e)))?;
         
         // Check browser health before download
         Self::check_browser_health(browser).await
-            .map_err(|e| crate::error::SourceError::new(format!("Browser health check failed before watchlist download: {}", e)))?;
+            .map_err(|e| step_error(ImdbStep::Browser, format!("Browser health check failed before watchlist download: {}", e)))?;
         
         // Create a new page for downloading (exports page) with resource blocking
         let download_dir = self.download_dir.clone();
@@ -903,11 +966,12 @@ impl MediaSource for ImdbClient {
         let files = Self::with_page_source_error(browser, "https://www.imdb.com/exports/", true, |page| async move {
             // Configure downloads on THIS page before attempting download
             Self::configure_downloads(page, &download_dir).await
-                .map_err(|e| crate::error::SourceError::new(format!("Failed to configure downloads: {}", e)))?;
+                .map_err(|e| step_error(ImdbStep::Download, format!("Failed to configure downloads: {}", e)))?;
             
             // Wait a moment for the page to fully load and configuration to take effect
             sleep(Duration::from_secs(2)).await;
             
+            let before = download_dir_state(&download_dir);
             download::download_exports(page, &download_dir, true, false, false, false, false, &cache).await
                 .map_err(|e| {
                     let error_msg = format!("{}", e);
@@ -917,7 +981,7 @@ impl MediaSource for ImdbClient {
                         return crate::error::SourceError::new("EXPORT_NOT_AVAILABLE".to_string());
                     }
                     
-                    crate::error::SourceError::new(format!("Failed to download IMDB watchlist export: {}", e))
+                    download_error(format!("Failed to download IMDB watchlist export: {}", e), &download_dir, &before)
                 })
         }.boxed()).await?;
         
@@ -976,7 +1040,7 @@ impl MediaSource for ImdbClient {
                 Err(e) => {
                     let error_msg = format!("Failed to parse IMDB watchlist CSV: {}. Raw CSV cached to imdb_watchlist.csv for inspection.", e);
                     warn!("{}", error_msg);
-                    return Err(crate::error::SourceError::new(error_msg));
+                    return Err(step_error(ImdbStep::Parse, error_msg));
                 }
             };
             
@@ -995,23 +1059,23 @@ impl MediaSource for ImdbClient {
     }
 
     async fn get_ratings(&self) -> Result<Vec<Rating>, Self::Error> {
-        let browser = self.browser.as_ref().ok_or_else(|| crate::error::SourceError::new("Browser not initialized".to_string()))?;
+        let browser = self.browser.as_ref().ok_or_else(|| step_error(ImdbStep::Browser, "Browser not initialized"))?;
         
         // Check browser health before starting
         Self::check_browser_health(browser).await
-            .map_err(|e| crate::error::SourceError::new(format!("Browser health check failed before ratings download: {}", e)))?;
+            .map_err(|e| step_error(ImdbStep::Browser, format!("Browser health check failed before ratings download: {}", e)))?;
         
         // Generate ratings export
-        export::generate_exports(browser, false, true, false, false, false).await
-            .map_err(|e| crate::error::SourceError::new(format!("Failed to generate IMDB ratings export: {}", e)))?;
+        export::generate_exports(browser, &self.download_dir, false, true, false, false, false, self.progress_observer.clone()).await
+            .map_err(|e| step_error(ImdbStep::ExportTrigger, format!("Failed to generate IMDB ratings export: {}", e)))?;
         
         // Check browser health after export generation
         Self::check_browser_health(browser).await
-            .map_err(|e| crate::error::SourceError::new(format!("Browser health check failed after export generation: {}", e)))?;
+            .map_err(|e| step_error(ImdbStep::Browser, format!("Browser health check failed after export generation: {}", e)))?;
         
         // Check browser health before download
         Self::check_browser_health(browser).await
-            .map_err(|e| crate::error::SourceError::new(format!("Browser health check failed before download: {}", e)))?;
+            .map_err(|e| step_error(ImdbStep::Browser, format!("Browser health check failed before download: {}", e)))?;
         
         // Create a new page for downloading (exports page) with resource blocking
         let download_dir = self.download_dir.clone();
@@ -1020,14 +1084,15 @@ impl MediaSource for ImdbClient {
         let files = Self::with_page_source_error(browser, "https://www.imdb.com/exports/", true, |page| async move {
             // Configure downloads on THIS page before attempting download
             Self::configure_downloads(page, &download_dir).await
-                .map_err(|e| crate::error::SourceError::new(format!("Failed to configure downloads: {}", e)))?;
+                .map_err(|e| step_error(ImdbStep::Download, format!("Failed to configure downloads: {}", e)))?;
             
             // Wait a moment for the page to fully load and configuration to take effect
             sleep(Duration::from_secs(2)).await;
             
+            let before = download_dir_state(&download_dir);
             download::download_exports(page, &download_dir, false, true, false, false, false, &cache).await
                 .map_err(|e| {
-                    crate::error::SourceError::new(format!("Failed to download IMDB ratings export: {}", e))
+                    download_error(format!("Failed to download IMDB ratings export: {}", e), &download_dir, &before)
                 })
         }.boxed()).await?;
         
@@ -1080,7 +1145,7 @@ impl MediaSource for ImdbClient {
                 Err(e) => {
                     let error_msg = format!("Failed to parse IMDB ratings CSV: {}. Raw CSV cached to imdb_ratings.csv for inspection.", e);
                     warn!("{}", error_msg);
-                    return Err(crate::error::SourceError::new(error_msg));
+                    return Err(step_error(ImdbStep::Parse, error_msg));
                 }
             };
             
@@ -1099,32 +1164,32 @@ impl MediaSource for ImdbClient {
     }
 
     async fn get_reviews(&self) -> Result<Vec<Review>, Self::Error> {
-        let browser = self.browser.as_ref().ok_or_else(|| crate::error::SourceError::new("Browser not initialized".to_string()))?;
+        let browser = self.browser.as_ref().ok_or_else(|| step_error(ImdbStep::Browser, "Browser not initialized"))?;
         
         Self::with_page_source_error(browser, "about:blank", false, |page| async move {
             reviews::scrape_reviews(page).await
-                .map_err(|e| crate::error::SourceError::new(format!("Failed to scrape IMDB reviews: {}", e)))
+                .map_err(|e| step_error(ImdbStep::Navigate, format!("Failed to scrape IMDB reviews: {}", e)))
         }.boxed()).await
     }
 
     async fn get_watch_history(&self) -> Result<Vec<WatchHistory>, Self::Error> {
-        let browser = self.browser.as_ref().ok_or_else(|| crate::error::SourceError::new("Browser not initialized".to_string()))?;
+        let browser = self.browser.as_ref().ok_or_else(|| step_error(ImdbStep::Browser, "Browser not initialized"))?;
         
         // Check browser health before starting
         Self::check_browser_health(browser).await
-            .map_err(|e| crate::error::SourceError::new(format!("Browser health check failed before check-ins download: {}", e)))?;
+            .map_err(|e| step_error(ImdbStep::Browser, format!("Browser health check failed before check-ins download: {}", e)))?;
         
         // Generate check-ins export
-        export::generate_exports(browser, false, false, true, false, false).await
-            .map_err(|e| crate::error::SourceError::new(format!("Failed to generate IMDB check-ins export: {}", e)))?;
+        export::generate_exports(browser, &self.download_dir, false, false, true, false, false, self.progress_observer.clone()).await
+            .map_err(|e| step_error(ImdbStep::ExportTrigger, format!("Failed to generate IMDB check-ins export: {}", e)))?;
         
         // Check browser health after export generation
         Self::check_browser_health(browser).await
-            .map_err(|e| crate::error::SourceError::new(format!("Browser health check failed after check-ins export generation: {}", e)))?;
+            .map_err(|e| step_error(ImdbStep::Browser, format!("Browser health check failed after check-ins export generation: {}", e)))?;
         
         // Check browser health before download
         Self::check_browser_health(browser).await
-            .map_err(|e| crate::error::SourceError::new(format!("Browser health check failed before check-ins download: {}", e)))?;
+            .map_err(|e| step_error(ImdbStep::Browser, format!("Browser health check failed before check-ins download: {}", e)))?;
         
         // Create a new page for downloading (exports page) with resource blocking
         let download_dir = self.download_dir.clone();
@@ -1133,14 +1198,15 @@ impl MediaSource for ImdbClient {
         let files = Self::with_page_source_error(browser, "https://www.imdb.com/exports/", true, |page| async move {
             // Configure downloads on THIS page before attempting download
             Self::configure_downloads(page, &download_dir).await
-                .map_err(|e| crate::error::SourceError::new(format!("Failed to configure downloads: {}", e)))?;
+                .map_err(|e| step_error(ImdbStep::Download, format!("Failed to configure downloads: {}", e)))?;
             
             // Wait a moment for the page to fully load and configuration to take effect
             sleep(Duration::from_secs(2)).await;
             
+            let before = download_dir_state(&download_dir);
             download::download_exports(page, &download_dir, false, false, true, false, false, &cache).await
                 .map_err(|e| {
-                    crate::error::SourceError::new(format!("Failed to download IMDB check-ins export: {}", e))
+                    download_error(format!("Failed to download IMDB check-ins export: {}", e), &download_dir, &before)
                 })
         }.boxed()).await?;
         
@@ -1193,7 +1259,7 @@ impl MediaSource for ImdbClient {
                 Err(e) => {
                     let error_msg = format!("Failed to parse IMDB check-ins CSV: {}. Raw CSV cached to imdb_checkins.csv for inspection.", e);
                     warn!("{}", error_msg);
-                    return Err(crate::error::SourceError::new(error_msg));
+                    return Err(step_error(ImdbStep::Parse, error_msg));
                 }
             };
             
@@ -1212,30 +1278,33 @@ impl MediaSource for ImdbClient {
     }
 
     async fn add_to_watchlist(&self, items: &[WatchlistItem]) -> Result<(), Self::Error> {
-        let browser = self.browser.as_ref().ok_or_else(|| crate::error::SourceError::new("Browser not initialized".to_string()))?;
+        let browser = self.browser.as_ref().ok_or_else(|| step_error(ImdbStep::Browser, "Browser not initialized"))?;
         let items = items.to_vec();
+        let observer = self.progress_observer.clone();
         
         Self::with_page_source_error(browser, "about:blank", false, |page| async move {
-            actions::add_to_watchlist(page, &items).await
+            actions::add_to_watchlist(page, &items, observer).await
                 .map_err(|e| crate::error::SourceError::new(format!("{}", e)))
         }.boxed()).await
     }
 
     async fn remove_from_watchlist(&self, items: &[WatchlistItem]) -> Result<(), Self::Error> {
-        let browser = self.browser.as_ref().ok_or_else(|| crate::error::SourceError::new("Browser not initialized".to_string()))?;
+        let browser = self.browser.as_ref().ok_or_else(|| step_error(ImdbStep::Browser, "Browser not initialized"))?;
         let items = items.to_vec();
+        let observer = self.progress_observer.clone();
         
         Self::with_page_source_error(browser, "about:blank", false, |page| async move {
-            actions::remove_from_watchlist(page, &items).await
+            actions::remove_from_watchlist(page, &items, observer).await
                 .map_err(|e| crate::error::SourceError::new(format!("{}", e)))
         }.boxed()).await
     }
 
     async fn set_ratings(&self, ratings: &[Rating]) -> Result<(), Self::Error> {
-        let browser = self.browser.as_ref().ok_or_else(|| crate::error::SourceError::new("Browser not initialized".to_string()))?;
+        let browser = self.browser.as_ref().ok_or_else(|| step_error(ImdbStep::Browser, "Browser not initialized"))?;
         let ratings = ratings.to_vec();
         let debug_config = self.debug_config.clone();
         let debug_enabled = debug_config.is_enabled();
+        let observer = self.progress_observer.clone();
         
         Self::with_page_source_error(browser, "about:blank", false, |page| async move {
             // Initialize PageInspector if debug is enabled
@@ -1254,15 +1323,15 @@ impl MediaSource for ImdbClient {
                 None
             };
             
-            actions::set_ratings(page, &ratings, inspector_opt.as_mut()).await
+            actions::set_ratings(page, &ratings, inspector_opt.as_mut(), observer).await
                 .map_err(|e| crate::error::SourceError::new(format!("{}", e)))?;
             
             Ok(())
         }.boxed()).await
     }
 
-    async fn set_reviews(&self, reviews: &[Review]) -> Result<(), Self::Error> {
-        let browser = self.browser.as_ref().ok_or_else(|| crate::error::SourceError::new("Browser not initialized".to_string()))?;
+    async fn set_reviews(&self, reviews: &[Review]) -> Result<Vec<WrittenReview>, Self::Error> {
+        let browser = self.browser.as_ref().ok_or_else(|| step_error(ImdbStep::Browser, "Browser not initialized"))?;
         
         // Get last submitted date from credentials before page operation
         let path_manager = media_sync_config::PathManager::default();
@@ -1272,28 +1341,29 @@ impl MediaSource for ImdbClient {
             .and_then(|_| cred_store.get_imdb_reviews_last_submitted());
         
         let reviews = reviews.to_vec();
-        let reviews_empty = reviews.is_empty();
+        let observer = self.progress_observer.clone();
         
         Self::with_page_source_error(browser, "about:blank", false, |page| async move {
-            actions::set_reviews(page, &reviews, last_submitted).await
+            let written = actions::set_reviews(page, &reviews, last_submitted, observer).await
                 .map_err(|e| crate::error::SourceError::new(format!("{}", e)))?;
             
             // Update last submitted date if reviews were successfully submitted
-            if !reviews_empty {
+            if !written.is_empty() {
                 cred_store.set_imdb_reviews_last_submitted(chrono::Utc::now());
                 let _ = cred_store.save();
             }
             
-            Ok(())
+            Ok(written)
         }.boxed()).await
     }
 
     async fn add_watch_history(&self, items: &[WatchHistory]) -> Result<(), Self::Error> {
-        let browser = self.browser.as_ref().ok_or_else(|| crate::error::SourceError::new("Browser not initialized".to_string()))?;
+        let browser = self.browser.as_ref().ok_or_else(|| step_error(ImdbStep::Browser, "Browser not initialized"))?;
         let items = items.to_vec();
+        let observer = self.progress_observer.clone();
         
         Self::with_page_source_error(browser, "about:blank", false, |page| async move {
-            actions::add_watch_history(page, &items).await
+            actions::add_watch_history(page, &items, observer).await
                 .map_err(|e| crate::error::SourceError::new(format!("{}", e)))
         }.boxed()).await
     }
@@ -1306,22 +1376,26 @@ impl MediaSource for ImdbClient {
 }
 
 impl RatingNormalization for ImdbClient {
-    fn normalize_rating(&self, rating: f64, target_scale: u8) -> u8 {
+    fn normalize_rating(&self, rating: f64, target_scale: u8, _media_type: &MediaType) -> u8 {
         // IMDB uses 1-10 with 0.5 increments, round to integer for target scale
         rating.round() as u8
     }
     
-    fn denormalize_rating(&self, rating: u8, source_scale: u8) -> f64 {
+    fn denormalize_rating(&self, rating: u8, source_scale: u8, _media_type: &MediaType) -> f64 {
         // IMDB uses 1-10 with 0.5 increments, but we store as integer
         rating as f64
     }
     
-    fn native_rating_scale(&self) -> u8 {
+    fn native_rating_scale(&self, _media_type: &MediaType) -> u8 {
         10
     }
 }
 
 impl CapabilityRegistry for ImdbClient {
+    fn set_progress_observer(&mut self, observer: Option<crate::ProgressObserver>) {
+        self.progress_observer = observer;
+    }
+
     fn as_id_extraction(&self) -> Option<&dyn IdExtraction> {
         None // IMDB doesn't extract additional IDs
     }
@@ -1341,4 +1415,8 @@ impl CapabilityRegistry for ImdbClient {
     fn as_status_mapping(&self) -> Option<&dyn StatusMapping> {
         None
     }
+
+    fn resolves_episode_checkins(&self) -> bool {
+        self.resolve_episode_checkins
+    }
 }
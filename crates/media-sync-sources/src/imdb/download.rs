@@ -5,6 +5,7 @@ use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::time::sleep;
 use tracing::{debug, info, warn};
 use dirs;
+use crate::imdb::export::{export_status, ExportStatus};
 
 /// Download IMDB CSV exports and save to directory
 /// Returns paths to downloaded files
@@ -31,7 +32,8 @@ pub async fn download_exports(
     if let Ok(entries) = fs::read_dir(download_dir) {
         for entry in entries.flatten() {
             let path = entry.path();
-            if path.extension().and_then(|s| s.to_str()) == Some("csv") {
+            // Also partial copies left by a crash (see `copy_atomically`)
+            if matches!(path.extension().and_then(|s| s.to_str()), Some("csv" | "part")) {
                 let _ = fs::remove_file(&path); // Ignore errors
             }
         }
@@ -131,6 +133,12 @@ pub async fn download_exports(
         }
     }
 
+    let downloaded: Vec<&str> = [("watchlist", &files.watchlist), ("ratings", &files.ratings), ("check-ins", &files.checkins)]
+        .into_iter()
+        .filter_map(|(name, path)| path.as_ref().map(|_| name))
+        .collect();
+    crate::imdb::export::mark_exports_downloaded(download_dir, &downloaded);
+
     Ok(files)
 }
 
@@ -157,19 +165,16 @@ async fn download_csv(
         if text.to_lowercase().contains(file_type) {
             info!("Found matching export item for {}: {}", file_type, text);
             
-            // Check if export is unavailable (e.g., "no export available", "export not available")
-            let text_lower = text.to_lowercase();
-            if text_lower.contains("no export available") 
-                || text_lower.contains("export not available")
-                || text_lower.contains("unavailable") {
-                warn!("Export for {} is not available (likely empty list or export not ready). Item text: {}", file_type, text);
-                return Err(anyhow!("Export not available for {}: {}", file_type, text));
-            }
-            
-            // Check if export is still in progress
-            if text_lower.contains("in progress") {
-                warn!("Export for {} is still in progress. Item text: {}", file_type, text);
-                return Err(anyhow!("Export for {} is still in progress. Wait for it to complete before downloading.", file_type));
+            match export_status(&text) {
+                ExportStatus::Unavailable => {
+                    warn!("Export for {} is not available (likely empty list or export not ready). Item text: {}", file_type, text);
+                    return Err(anyhow!("Export not available for {}: {}", file_type, text));
+                }
+                ExportStatus::InProgress => {
+                    warn!("Export for {} is still in progress. Item text: {}", file_type, text);
+                    return Err(anyhow!("Export for {} is still in progress. Wait for it to complete before downloading.", file_type));
+                }
+                ExportStatus::Ready(_) => {}
             }
             
             // Find download button (matching Python: button[data-testid*='export-status-button'])
@@ -341,26 +346,13 @@ async fn download_csv(
                         fs::set_permissions(&csv_file, fs::Permissions::from_mode(0o777))?;
                     }
                     
-                    // If file is not already in the download directory, move it there
-                    // Otherwise just rename it
+                    // Move it under its final name atomically, so a crash never leaves a
+                    // half-written CSV where the parser looks
                     if csv_file.parent() != Some(download_dir) {
                         info!("Moving CSV file from {:?} to download directory {:?}", csv_file, download_dir);
-                        // Ensure download directory exists
                         fs::create_dir_all(download_dir)?;
-                        // Remove existing file if it exists
-                        if dest_path.exists() {
-                            fs::remove_file(&dest_path)?;
-                        }
-                        // Move file to download directory
-                        fs::rename(&csv_file, &dest_path)?;
-                    } else {
-                        // File is already in download directory, just rename it
-                        // Remove existing file if it exists
-                        if dest_path.exists() {
-                            fs::remove_file(&dest_path)?;
-                        }
-                        fs::rename(&csv_file, &dest_path)?;
                     }
+                    move_atomically(&csv_file, &dest_path)?;
                     
                     info!("Downloaded and renamed {} to {:?}", file_type, dest_path);
                     
@@ -420,6 +412,33 @@ fn find_latest_csv(dir: &Path) -> Result<PathBuf> {
     Ok(csv_files.into_iter().next().unwrap())
 }
 
+/// Copy `from` to `to` through a temporary file next to `to`, so `to` is either its old
+/// contents or the complete copy, never a partial one
+pub(crate) fn copy_atomically(from: &Path, to: &Path) -> std::io::Result<u64> {
+    let file_name = to.file_name().and_then(|name| name.to_str()).unwrap_or("download");
+    let temp_path = to.with_file_name(format!(".{}.part", file_name));
+    let result = std::fs::copy(from, &temp_path)
+        .and_then(|bytes| {
+            std::fs::File::open(&temp_path)?.sync_all()?;
+            std::fs::rename(&temp_path, to)?;
+            Ok(bytes)
+        });
+    if result.is_err() {
+        let _ = std::fs::remove_file(&temp_path);
+    }
+    result
+}
+
+/// Move `from` to `to`, replacing it: a rename on the same file system, otherwise an
+/// atomic copy (see `copy_atomically`) and removal of `from`
+pub(crate) fn move_atomically(from: &Path, to: &Path) -> std::io::Result<()> {
+    if std::fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    copy_atomically(from, to)?;
+    std::fs::remove_file(from)
+}
+
 #[derive(Debug)]
 pub struct DownloadedFiles {
     pub watchlist: Option<PathBuf>,
@@ -427,3 +446,33 @@ pub struct DownloadedFiles {
     pub checkins: Option<PathBuf>,
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_copy_atomically_replaces_whole_file_without_leftovers() {
+        let temp = tempfile::tempdir().unwrap();
+        let from = temp.path().join("ratings_1.csv");
+        let to = temp.path().join("imdb_ratings.csv");
+        std::fs::write(&from, "Const,Your Rating\ntt0113277,9\n").unwrap();
+        std::fs::write(&to, "stale").unwrap();
+
+        assert_eq!(copy_atomically(&from, &to).unwrap(), 30);
+        assert_eq!(std::fs::read_to_string(&to).unwrap(), "Const,Your Rating\ntt0113277,9\n");
+        assert!(from.exists());
+
+        // A failed copy leaves the destination as it was
+        assert!(copy_atomically(&temp.path().join("missing.csv"), &to).is_err());
+        assert_eq!(std::fs::read_to_string(&to).unwrap(), "Const,Your Rating\ntt0113277,9\n");
+
+        let moved = temp.path().join("ratings_2.csv");
+        move_atomically(&from, &moved).unwrap();
+        assert!(!from.exists());
+        let mut names: Vec<_> = std::fs::read_dir(temp.path()).unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["imdb_ratings.csv", "ratings_2.csv"]);
+    }
+}
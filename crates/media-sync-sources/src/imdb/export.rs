@@ -1,71 +1,129 @@
 use anyhow::Result;
+use chrono::NaiveDate;
 use chromiumoxide::{Browser, Page};
+use std::collections::HashMap;
+use std::path::Path;
 use std::time::Duration;
 use tokio::time::{sleep, timeout};
 use tracing::{info, warn, debug, error};
+use crate::{ProgressObserver, ProgressTracker};
+
+/// IMDB only shows an export's date, possibly in another time zone, so an export dated
+/// up to this many days before it was asked for may still be the one asked for
+const EXPORT_DATE_SLACK_DAYS: i64 = 1;
+
+/// Exports this tool asked IMDB for and hasn't downloaded yet, with the day each was
+/// asked for. Kept in the download directory.
+const PENDING_EXPORTS_FILE: &str = "pending_exports.json";
+
+/// State of an export on IMDB's exports page, read from its summary item
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ExportStatus {
+    InProgress,
+    /// Nothing to export (e.g. an empty list)
+    Unavailable,
+    /// Ready to download, with the date it was made if the item shows one
+    Ready(Option<NaiveDate>),
+}
 
 /// Generate IMDB CSV exports for watchlist, ratings, and check-ins
 /// Returns when all exports are ready (or timeout)
+///
+/// An export already in progress on IMDB's exports page is waited for rather than
+/// started again, and a ready one this tool asked for but never downloaded is reused,
+/// so a run that died mid-export picks up where it left off. Any other ready export may
+/// predate changes made since, so a new one is generated.
 pub async fn generate_exports(
     browser: &Browser,
+    download_dir: &Path,
     sync_watchlist: bool,
     sync_ratings: bool,
     sync_watch_history: bool,
     remove_watched_from_watchlists: bool,
     mark_rated_as_watched: bool,
+    observer: Option<ProgressObserver>,
 ) -> Result<()> {
-    // Create separate pages for each export type to avoid conflicts with concurrent operations
-    // This prevents race conditions when get_reviews() or other methods are called concurrently
-    
-    // Generate watchlist export if needed
+    let mut requested = Vec::new();
     if sync_watchlist || remove_watched_from_watchlists {
-        let page = browser.new_page("https://www.imdb.com").await?;
-        let result = async {
-            let _export_generated = generate_watchlist_export(&page).await?;
-            Ok::<(), anyhow::Error>(())
-        }.await;
-        if let Err(e) = page.close().await {
-            warn!("Failed to close watchlist export page: {}", e);
+        requested.push("watchlist");
+    }
+    if sync_ratings || mark_rated_as_watched {
+        requested.push("ratings");
+    }
+    if sync_watch_history || remove_watched_from_watchlists || mark_rated_as_watched {
+        requested.push("check-ins");
+    }
+    if requested.is_empty() {
+        return Ok(());
+    }
+
+    let mut tracker = ProgressTracker::with_operation_name(requested.len(), 1, Some("IMDB exports".to_string()))
+        .with_observer(observer);
+
+    // Exports already on IMDB, so an interrupted run doesn't start them over
+    let page = browser.new_page("about:blank").await?;
+    let existing = match export_statuses(&page, &requested).await {
+        Ok(existing) => existing,
+        Err(e) => {
+            warn!("Could not check IMDB's exports page for existing exports, generating new ones: {}", e);
+            Vec::new()
         }
-        result?;
+    };
+    if let Err(e) = page.close().await {
+        warn!("Failed to close existing exports check page: {}", e);
     }
+    let today = chrono::Local::now().date_naive();
+    let mut pending_exports = load_pending_exports(download_dir);
 
-    // Generate ratings export if needed
-    if sync_ratings || mark_rated_as_watched {
-        let page = browser.new_page("about:blank").await?;
-        let result = async {
-            generate_ratings_export(&page).await?;
-            Ok::<(), anyhow::Error>(())
-        }.await;
+    // Create separate pages for each export type to avoid conflicts with concurrent operations
+    // This prevents race conditions when get_reviews() or other methods are called concurrently
+    let mut pending = Vec::new();
+    for (done, name) in requested.iter().copied().enumerate() {
+        let status = existing.iter().find(|(existing_name, _)| *existing_name == name).map(|(_, status)| *status);
+        match status {
+            Some(ExportStatus::InProgress) => {
+                info!("IMDB {} export is already in progress, waiting for it instead of starting another", name);
+                pending.push(name);
+                continue;
+            }
+            Some(ExportStatus::Ready(Some(date))) if is_reusable_export(date, pending_exports.get(name).copied()) => {
+                info!("Reusing IMDB {} export from {}, asked for by an earlier run", name, date);
+                tracker.record_already_present();
+                tracker.log_progress(done + 1);
+                continue;
+            }
+            _ => {}
+        }
+
+        let page = browser.new_page(if name == "watchlist" { "https://www.imdb.com" } else { "about:blank" }).await?;
+        let result = match name {
+            "watchlist" => generate_watchlist_export(&page).await,
+            "ratings" => generate_ratings_export(&page).await.map(|_| true),
+            _ => generate_checkins_export(&page).await.map(|_| true),
+        };
         if let Err(e) = page.close().await {
-            warn!("Failed to close ratings export page: {}", e);
+            warn!("Failed to close {} export page: {}", name, e);
+        }
+        if result? {
+            pending_exports.insert(name.to_string(), today);
+            save_pending_exports(download_dir, &pending_exports);
+            pending.push(name);
+        } else {
+            tracker.record_skipped();
+            tracker.log_progress(done + 1);
         }
-        result?;
     }
 
-    // Generate check-ins export if needed
-    if sync_watch_history || remove_watched_from_watchlists || mark_rated_as_watched {
+    // Wait for exports to be ready using a dedicated page
+    if !pending.is_empty() {
         let page = browser.new_page("about:blank").await?;
-        let result = async {
-            generate_checkins_export(&page).await?;
-            Ok::<(), anyhow::Error>(())
-        }.await;
+        let result = wait_for_exports_ready(&page, &pending, requested.len() - pending.len(), &mut tracker).await;
         if let Err(e) = page.close().await {
-            warn!("Failed to close check-ins export page: {}", e);
+            warn!("Failed to close exports ready check page: {}", e);
         }
         result?;
     }
-
-    // Wait for exports to be ready using a dedicated page
-    let page = browser.new_page("about:blank").await?;
-    let result = async {
-        wait_for_exports_ready(&page).await?;
-        Ok::<(), anyhow::Error>(())
-    }.await;
-    if let Err(e) = page.close().await {
-        warn!("Failed to close exports ready check page: {}", e);
-    }
-    result?;
+    tracker.log_summary("IMDB exports");
 
     Ok(())
 }
@@ -574,49 +632,174 @@ async fn click_element_with_retry(page: &Page, element: chromiumoxide::Element,
     }
 }
 
-async fn wait_for_exports_ready(page: &Page) -> Result<()> {
+/// Poll the exports page until every export in `names` is no longer in progress,
+/// waiting twice as long after each check (up to a minute). A failed check is retried
+/// the same way; only several in a row give up before the overall time limit.
+/// `already_done` exports were reused or skipped, for the progress count.
+async fn wait_for_exports_ready(page: &Page, names: &[&str], already_done: usize, tracker: &mut ProgressTracker) -> Result<()> {
     const MAX_WAIT_TIME: Duration = Duration::from_secs(1200); // 20 minutes (matching Python: max_wait_time = 1200)
-    const CHECK_INTERVAL: Duration = Duration::from_secs(30); // Matching Python: time.sleep(30)
+    const FIRST_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+    const MAX_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+    const MAX_FAILED_CHECKS: u32 = 5;
     let start = std::time::Instant::now();
+    let mut waiting: Vec<&str> = names.to_vec();
+    let mut interval = FIRST_CHECK_INTERVAL;
+    let mut failed_checks = 0;
 
     loop {
-        // Load exports page (matching Python: EH.get_page_with_retries('https://www.imdb.com/exports/', driver, wait))
-        navigate_with_timeout(page, "https://www.imdb.com/exports/", 60).await?;
-        sleep(Duration::from_secs(2)).await;
-
-        // Locate all elements with the selector (matching Python: summary_items = wait.until(EC.presence_of_all_elements_located(...)))
-        let items = match page.find_elements(".ipc-metadata-list-summary-item").await {
-            Ok(items) => items,
-            Err(_) => {
-                // No items found (matching Python: except TimeoutException: print("No items found...") break)
-                info!("No items found when attempting to generate IMDB exports. Assuming no IMDB watchlist, ratings or check-ins to download.");
-                break;
+        match export_statuses(page, &waiting).await {
+            Ok(statuses) => {
+                failed_checks = 0;
+                waiting.retain(|name| {
+                    match statuses.iter().find(|(status_name, _)| status_name == name).map(|(_, status)| *status) {
+                        Some(ExportStatus::InProgress) => return true,
+                        Some(ExportStatus::Ready(_)) => tracker.record_added(),
+                        // Nothing to download (e.g. an empty list); the download step reports it
+                        Some(ExportStatus::Unavailable) | None => tracker.record_skipped(),
+                    }
+                    false
+                });
+                tracker.log_progress(already_done + names.len() - waiting.len());
+                if waiting.is_empty() {
+                    info!("All IMDB exports are ready");
+                    return Ok(());
+                }
+                info!("IMDB exports still in progress: {}", waiting.join(", "));
             }
-        };
-
-        // Check if any summary item contains "in progress" (matching Python: check_in_progress function)
-        let mut in_progress = false;
-        for item in items {
-            let text = item.inner_text().await?.unwrap_or_default();
-            if text.to_lowercase().contains("in progress") {
-                in_progress = true;
-                break;
+            Err(e) => {
+                failed_checks += 1;
+                if failed_checks >= MAX_FAILED_CHECKS {
+                    return Err(anyhow::anyhow!("Checking IMDB export status failed {} times in a row: {}", failed_checks, e));
+                }
+                warn!("Checking IMDB export status failed (attempt {}/{}), retrying: {}", failed_checks, MAX_FAILED_CHECKS, e);
             }
         }
 
-        if !in_progress {
-            info!("All IMDB exports are ready");
-            break;
+        if start.elapsed() + interval >= MAX_WAIT_TIME {
+            return Err(anyhow::anyhow!("IMDB export processing did not complete within 20 minutes"));
         }
+        sleep(interval).await;
+        interval = (interval * 2).min(MAX_CHECK_INTERVAL);
+    }
+}
 
-        if start.elapsed() >= MAX_WAIT_TIME {
-            return Err(anyhow::anyhow!("IMDB export processing did not complete within 20 minutes"));
+/// Status of the newest export named by each of `names` (as `download_exports` finds
+/// them); names without an item on the exports page are left out
+async fn export_statuses<'a>(page: &Page, names: &[&'a str]) -> Result<Vec<(&'a str, ExportStatus)>> {
+    // Load exports page (matching Python: EH.get_page_with_retries('https://www.imdb.com/exports/', driver, wait))
+    navigate_with_timeout(page, "https://www.imdb.com/exports/", 60).await?;
+    sleep(Duration::from_secs(2)).await;
+
+    let items = match page.find_elements(".ipc-metadata-list-summary-item").await {
+        Ok(items) => items,
+        Err(_) => {
+            debug!("No items on IMDB's exports page");
+            return Ok(Vec::new());
         }
+    };
+    let mut texts = Vec::with_capacity(items.len());
+    for item in items {
+        texts.push(item.inner_text().await?.unwrap_or_default().to_lowercase());
+    }
+
+    Ok(names.iter()
+        .filter_map(|name| {
+            let text = texts.iter().find(|text| text.contains(name))?;
+            Some((*name, export_status(text)))
+        })
+        .collect())
+}
 
-        info!("Exports still in progress, waiting...");
-        sleep(CHECK_INTERVAL).await;
+/// Read an export's state from the text of its item on the exports page
+pub(crate) fn export_status(text: &str) -> ExportStatus {
+    let text = text.to_lowercase();
+    if text.contains("no export available")
+        || text.contains("export not available")
+        || text.contains("unavailable") {
+        ExportStatus::Unavailable
+    } else if text.contains("in progress") {
+        ExportStatus::InProgress
+    } else {
+        ExportStatus::Ready(export_date(&text))
     }
+}
 
-    Ok(())
+/// The first date in an export item's text, e.g. "Jan 5, 2025", "5 Jan 2025" or "2025-01-05"
+fn export_date(text: &str) -> Option<NaiveDate> {
+    let words: Vec<&str> = text.split_whitespace()
+        .map(|word| word.trim_matches(|c: char| !c.is_ascii_alphanumeric() && c != ','))
+        .collect();
+    (0..words.len()).find_map(|i| {
+        let three = words.get(i..i + 3).map(|w| w.join(" ").trim_end_matches(',').to_string());
+        let spelled_out = three.and_then(|w| {
+            ["%b %d, %Y", "%B %d, %Y", "%d %b %Y", "%d %B %Y"].iter()
+                .find_map(|format| NaiveDate::parse_from_str(&w, format).ok())
+        });
+        spelled_out.or_else(|| NaiveDate::parse_from_str(words[i].trim_end_matches(','), "%Y-%m-%d").ok())
+    })
+}
+
+/// Whether a ready export made on `date` is the one this tool asked for on
+/// `asked_for`, and so can be downloaded instead of generating a new one. Without a
+/// pending request of its own, no export is reused.
+fn is_reusable_export(date: NaiveDate, asked_for: Option<NaiveDate>) -> bool {
+    asked_for.is_some_and(|asked_for| (asked_for - date).num_days() <= EXPORT_DATE_SLACK_DAYS)
+}
+
+fn load_pending_exports(download_dir: &Path) -> HashMap<String, NaiveDate> {
+    std::fs::read_to_string(download_dir.join(PENDING_EXPORTS_FILE)).ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_pending_exports(download_dir: &Path, pending: &HashMap<String, NaiveDate>) {
+    let result = std::fs::create_dir_all(download_dir)
+        .and_then(|_| std::fs::write(download_dir.join(PENDING_EXPORTS_FILE), serde_json::to_string(pending).unwrap_or_default()));
+    if let Err(e) = result {
+        warn!("Failed to record pending IMDB exports: {}", e);
+    }
 }
 
+/// Forget the requests for `names`, whose exports were downloaded, so the next run
+/// generates fresh ones
+pub(crate) fn mark_exports_downloaded(download_dir: &Path, names: &[&str]) {
+    let mut pending = load_pending_exports(download_dir);
+    let before = pending.len();
+    pending.retain(|name, _| !names.contains(&name.as_str()));
+    if pending.len() < before {
+        save_pending_exports(download_dir, &pending);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_status_from_item_text() {
+        assert_eq!(export_status("Your Ratings\nIn progress"), ExportStatus::InProgress);
+        assert_eq!(export_status("Watchlist\nNo export available"), ExportStatus::Unavailable);
+        let date = NaiveDate::from_ymd_opt(2025, 1, 5);
+        assert_eq!(export_status("Your Ratings\nReady · Jan 5, 2025\n1,204 titles"), ExportStatus::Ready(date));
+        assert_eq!(export_status("Check-ins exported 5 January 2025"), ExportStatus::Ready(date));
+        assert_eq!(export_status("Check-ins (2025-01-05)"), ExportStatus::Ready(date));
+        assert_eq!(export_status("Your Ratings\nReady"), ExportStatus::Ready(None));
+    }
+
+    #[test]
+    fn test_only_exports_asked_for_and_not_downloaded_are_reused() {
+        let today = NaiveDate::from_ymd_opt(2025, 1, 5).unwrap();
+        // Not asked for by this tool, or already downloaded: however recent, not reused
+        assert!(!is_reusable_export(today, None));
+        assert!(is_reusable_export(today, Some(today)));
+        assert!(is_reusable_export(today.pred_opt().unwrap(), Some(today)));
+        assert!(!is_reusable_export(NaiveDate::from_ymd_opt(2025, 1, 3).unwrap(), Some(today)));
+
+        let dir = tempfile::tempdir().unwrap();
+        save_pending_exports(dir.path(), &HashMap::from([("ratings".to_string(), today), ("watchlist".to_string(), today)]));
+        mark_exports_downloaded(dir.path(), &["ratings"]);
+        let pending = load_pending_exports(dir.path());
+        assert_eq!(pending.get("watchlist"), Some(&today));
+        assert!(!pending.contains_key("ratings"));
+    }
+}
@@ -114,6 +114,12 @@ pub fn parse_watchlist_csv<P: AsRef<Path>>(path: P) -> Result<Vec<WatchlistItem>
             }
         };
 
+        // The list item's note, which users can fill in on IMDB
+        let tags = header_map.get("Description")
+            .and_then(|&idx| record.get(idx))
+            .map(media_sync_models::tags_from_note)
+            .unwrap_or_default();
+
         watchlist.push(WatchlistItem {
             imdb_id: imdb_id.clone(),
             ids: None,
@@ -123,6 +129,7 @@ pub fn parse_watchlist_csv<P: AsRef<Path>>(path: P) -> Result<Vec<WatchlistItem>
             date_added,
             source: "imdb".to_string(),
             status: Some(media_sync_models::NormalizedStatus::Watchlist), // IMDB watchlist items are always "Watchlist" status
+            tags,
         });
         
         // Debug first few items added
@@ -199,14 +206,19 @@ pub fn parse_ratings_csv<P: AsRef<Path>>(path: P) -> Result<Vec<Rating>> {
             .map_err(|e| anyhow!("Failed to parse rating '{}': {}", rating_str, e))?;
 
         // Parse date: YYYY-MM-DD -> DateTime<Utc>
-        // Use NaiveDate first, then convert to DateTime<Utc>
-        let date_added = NaiveDate::parse_from_str(date_rated_str, "%Y-%m-%d")
-            .map_err(|e| anyhow!("Failed to parse date '{}': {}", date_rated_str, e))?
-            .and_hms_opt(0, 0, 0)
-            .ok_or_else(|| anyhow!("Failed to create time from date '{}'", date_rated_str))?
-            .and_local_timezone(Utc)
-            .earliest()
-            .ok_or_else(|| anyhow!("Failed to convert date '{}' to UTC", date_rated_str))?;
+        // Use NaiveDate first, then convert to DateTime<Utc>. An empty date is left for
+        // sync.ratings.default_date to fill in.
+        let date_added = if date_rated_str.trim().is_empty() {
+            Rating::UNDATED
+        } else {
+            NaiveDate::parse_from_str(date_rated_str, "%Y-%m-%d")
+                .map_err(|e| anyhow!("Failed to parse date '{}': {}", date_rated_str, e))?
+                .and_hms_opt(0, 0, 0)
+                .ok_or_else(|| anyhow!("Failed to create time from date '{}'", date_rated_str))?
+                .and_local_timezone(Utc)
+                .earliest()
+                .ok_or_else(|| anyhow!("Failed to convert date '{}' to UTC", date_rated_str))?
+        };
 
         // Map Title Type to MediaType
         let media_type = match title_type.as_str() {
@@ -225,9 +237,11 @@ pub fn parse_ratings_csv<P: AsRef<Path>>(path: P) -> Result<Vec<Rating>> {
             imdb_id: imdb_id.clone(),
             ids: None,
             rating,
+            rating_f64: None,
             date_added,
             media_type,
             source: media_sync_models::RatingSource::Imdb,
+            inferred: false,
         });
         
         // Debug first few items added
@@ -434,6 +448,8 @@ pub fn parse_checkins_csv<P: AsRef<Path>>(path: P) -> Result<Vec<WatchHistory>>
             watched_at,
             media_type,
             source: "imdb".to_string(),
+            watched_percent: None,
+            show_ids: None,
         });
     }
 
@@ -460,7 +476,7 @@ mod tests {
         ).unwrap();
         writeln!(
             file,
-            "2,tt0944947,2020-01-02,2020-01-02,,Game of Thrones,https://www.imdb.com/title/tt0944947/,TV Series,9.2,57,2011,Action Drama Fantasy,2000000,2011-04-17,"
+            "2,tt0944947,2020-01-02,2020-01-02,no-sync,Game of Thrones,https://www.imdb.com/title/tt0944947/,TV Series,9.2,57,2011,Action Drama Fantasy,2000000,2011-04-17,"
         ).unwrap();
         file
     }
@@ -507,6 +523,8 @@ mod tests {
         assert_eq!(items[0].media_type, MediaType::Movie);
         assert_eq!(items[1].imdb_id, "tt0944947");
         assert_eq!(items[1].media_type, MediaType::Show);
+        assert!(items[0].tags.is_empty());
+        assert_eq!(items[1].tags, vec!["no-sync"]);
     }
 
     #[test]
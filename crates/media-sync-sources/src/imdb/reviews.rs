@@ -67,7 +67,8 @@ pub async fn scrape_reviews(page: &Page) -> Result<Vec<Review>> {
                 Err(_) => None,
             };
 
-            // Extract IMDB_ID from link
+            // Extract IMDB_ID and the review ID from link
+            let mut review_id = None;
             let imdb_id = match element.find_element("div[data-testid='review-summary'] a").await {
                 Ok(link_elem) => {
                     link_elem.attribute("href").await.ok().flatten().and_then(|href| {
@@ -78,7 +79,8 @@ pub async fn scrape_reviews(page: &Page) -> Result<Vec<Review>> {
                         // parts[1] = "title"
                         // parts[2] = "tt1234567" (this is what we want!)
                         // parts[3] = "reviews"
-                        // parts[4] = "rw1234567" (this is the review ID, not what we want)
+                        // parts[4] = "rw1234567" (the review ID, kept to link synced copies)
+                        review_id = parts.get(4).filter(|id| id.starts_with("rw")).map(|id| id.to_string());
                         if parts.len() > 2 && parts[1] == "title" {
                             Some(parts[2].to_string()) // Title ID (tt...)
                         } else {
@@ -118,6 +120,8 @@ pub async fn scrape_reviews(page: &Page) -> Result<Vec<Review>> {
                 media_type,
                 source: "imdb".to_string(),
                 is_spoiler,
+                rating: None,
+                review_id,
             });
         }
 
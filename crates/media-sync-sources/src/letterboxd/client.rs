@@ -0,0 +1,215 @@
+use crate::capabilities::{CapabilityRegistry, IdExtraction, IdLookupProvider, IncrementalSync, RatingNormalization, StatusMapping};
+use crate::error::SourceError;
+use crate::letterboxd::export::{self, ExportLocation, SOURCE_NAME};
+use crate::traits::{DataType, MediaSource, WrittenReview};
+use async_trait::async_trait;
+use media_sync_models::{MediaType, Rating, Review, WatchHistory, WatchlistItem};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use tracing::info;
+
+/// Letterboxd, read from a data export. Letterboxd has no API for personal data, so the
+/// only write is adding to the watchlist, through a CSV file to import on letterboxd.com.
+pub struct LetterboxdClient {
+    export_path: PathBuf,
+    /// Watchlist additions are appended here for Letterboxd's importer
+    import_file: PathBuf,
+    /// Set by `authenticate` once the export is found
+    export: Option<ExportLocation>,
+}
+
+impl LetterboxdClient {
+    pub fn new(export_path: impl Into<PathBuf>, import_file: impl Into<PathBuf>) -> Self {
+        Self {
+            export_path: export_path.into(),
+            import_file: import_file.into(),
+            export: None,
+        }
+    }
+
+    fn export(&self) -> Result<&ExportLocation, SourceError> {
+        self.export.as_ref().ok_or_else(|| SourceError::new("Letterboxd export not loaded; authenticate first".to_string()))
+    }
+
+    fn read(&self, name: &str) -> Result<Option<String>, SourceError> {
+        self.export()?.read(name).map_err(|e| SourceError::new(format!("{}", e)))
+    }
+
+    fn unsupported(&self, what: &str, count: usize) -> Result<(), SourceError> {
+        // Fail rather than report writes as done that Letterboxd never received
+        if count == 0 {
+            return Ok(());
+        }
+        Err(SourceError::new(format!(
+            "Letterboxd can't be written to except for watchlist additions; {} {} were not written",
+            count, what
+        )))
+    }
+
+    /// Append `items` missing from the import file, as `imdbID,Title,Year` rows.
+    /// Returns how many were appended.
+    fn append_to_import_file(&self, items: &[WatchlistItem]) -> anyhow::Result<usize> {
+        let write_header = !self.import_file.exists();
+        let mut queued = HashSet::new();
+        if !write_header {
+            let mut reader = csv::Reader::from_path(&self.import_file)?;
+            for record in reader.records() {
+                let record = record?;
+                queued.insert((record.get(0).unwrap_or("").to_string(), record.get(1).unwrap_or("").to_lowercase()));
+            }
+        } else if let Some(parent) = self.import_file.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let file = std::fs::OpenOptions::new().create(true).append(true).open(&self.import_file)?;
+        let mut writer = csv::Writer::from_writer(file);
+        if write_header {
+            writer.write_record(["imdbID", "Title", "Year"])?;
+        }
+        let mut appended = 0;
+        for item in items {
+            if !queued.insert((item.imdb_id.clone(), item.title.to_lowercase())) {
+                continue;
+            }
+            let year = item.year.map(|year| year.to_string()).unwrap_or_default();
+            writer.write_record([item.imdb_id.as_str(), item.title.as_str(), year.as_str()])?;
+            appended += 1;
+        }
+        writer.flush()?;
+        Ok(appended)
+    }
+}
+
+#[async_trait]
+impl MediaSource for LetterboxdClient {
+    type Error = SourceError;
+
+    fn source_name(&self) -> &str {
+        SOURCE_NAME
+    }
+
+    fn supported_data_types(&self) -> &'static [DataType] {
+        // Reviews are in the export but could never be written back
+        &[DataType::Watchlist, DataType::Ratings, DataType::WatchHistory]
+    }
+
+    async fn authenticate(&mut self) -> Result<(), Self::Error> {
+        let export = ExportLocation::find(&self.export_path).map_err(|e| SourceError::new(format!("{}", e)))?;
+        info!("Reading Letterboxd export {:?}", export);
+        self.export = Some(export);
+        Ok(())
+    }
+
+    fn is_authenticated(&self) -> bool {
+        self.export.is_some()
+    }
+
+    async fn get_watchlist(&self) -> Result<Vec<WatchlistItem>, Self::Error> {
+        match self.read("watchlist.csv")? {
+            Some(csv) => export::parse_watchlist(&csv).map_err(|e| SourceError::new(format!("{}", e))),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    async fn get_ratings(&self) -> Result<Vec<Rating>, Self::Error> {
+        match self.read("ratings.csv")? {
+            Some(csv) => export::parse_ratings(&csv).map_err(|e| SourceError::new(format!("{}", e))),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    async fn get_reviews(&self) -> Result<Vec<Review>, Self::Error> {
+        // Not in supported_data_types, so sync never asks
+        Ok(Vec::new())
+    }
+
+    async fn get_watch_history(&self) -> Result<Vec<WatchHistory>, Self::Error> {
+        let diary = self.read("diary.csv")?;
+        let watched = self.read("watched.csv")?;
+        export::parse_watch_history(diary.as_deref(), watched.as_deref())
+            .map_err(|e| SourceError::new(format!("{}", e)))
+    }
+
+    async fn add_to_watchlist(&self, items: &[WatchlistItem]) -> Result<(), Self::Error> {
+        if items.is_empty() {
+            return Ok(());
+        }
+        let appended = self.append_to_import_file(items)
+            .map_err(|e| SourceError::new(format!("Failed to write Letterboxd import file {}: {}", self.import_file.display(), e)))?;
+        if appended > 0 {
+            info!(
+                "Queued {} titles for the Letterboxd watchlist in {}; import it on letterboxd.com to add them",
+                appended,
+                self.import_file.display()
+            );
+        }
+        Ok(())
+    }
+
+    async fn remove_from_watchlist(&self, items: &[WatchlistItem]) -> Result<(), Self::Error> {
+        self.unsupported("watchlist removals", items.len())
+    }
+
+    async fn set_ratings(&self, ratings: &[Rating]) -> Result<(), Self::Error> {
+        self.unsupported("ratings", ratings.len())
+    }
+
+    async fn set_reviews(&self, reviews: &[Review]) -> Result<Vec<WrittenReview>, Self::Error> {
+        self.unsupported("reviews", reviews.len())?;
+        Ok(Vec::new())
+    }
+
+    async fn add_watch_history(&self, items: &[WatchHistory]) -> Result<(), Self::Error> {
+        self.unsupported("plays", items.len())
+    }
+}
+
+impl RatingNormalization for LetterboxdClient {
+    fn normalize_rating(&self, rating: f64, target_scale: u8, _media_type: &MediaType) -> u8 {
+        // Collected in half stars, so 1-10 like the normalized scale
+        (rating * target_scale as f64 / 10.0).round() as u8
+    }
+
+    fn denormalize_rating(&self, rating: u8, source_scale: u8, _media_type: &MediaType) -> f64 {
+        rating as f64 * 10.0 / source_scale as f64
+    }
+
+    fn normalize_rating_f64(&self, rating: f64, target_scale: u8, _media_type: &MediaType) -> f64 {
+        rating * target_scale as f64 / 10.0
+    }
+
+    fn denormalize_rating_f64(&self, rating: f64, source_scale: u8, _media_type: &MediaType) -> f64 {
+        rating * 10.0 / source_scale as f64
+    }
+
+    fn native_rating_scale(&self, _media_type: &MediaType) -> u8 {
+        // 0.5-5 stars, counted in half stars
+        10
+    }
+}
+
+impl CapabilityRegistry for LetterboxdClient {
+    fn as_incremental_sync(&mut self) -> Option<&mut dyn IncrementalSync> {
+        None
+    }
+
+    fn as_rating_normalization(&self) -> Option<&dyn RatingNormalization> {
+        Some(self)
+    }
+
+    fn as_status_mapping(&self) -> Option<&dyn StatusMapping> {
+        None
+    }
+
+    fn as_id_extraction(&self) -> Option<&dyn IdExtraction> {
+        None
+    }
+
+    fn as_id_lookup_provider(&self) -> Option<&dyn IdLookupProvider> {
+        None
+    }
+
+    fn accepts_watchlist_removals(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,249 @@
+//! Reading a Letterboxd data export.
+//!
+//! The export is a zip of CSV files, one per list: `watchlist.csv`, `ratings.csv`,
+//! `diary.csv` (one row per logged viewing) and `watched.csv` (every film marked as
+//! watched, logged or not). Rows name films by title, year and Letterboxd URI only, so
+//! items are collected without IMDB IDs and resolved by title like any other.
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use media_sync_models::{MediaIds, MediaType, NormalizedStatus, Rating, RatingSource, WatchHistory, WatchlistItem};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+pub const SOURCE_NAME: &str = "letterboxd";
+
+/// Where the CSV files of an export are read from
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportLocation {
+    /// An unpacked export
+    Dir(PathBuf),
+    Zip(PathBuf),
+}
+
+impl ExportLocation {
+    /// The export at `path`: a zip, an unpacked export, or a folder of export zips, of
+    /// which the most recently modified is used
+    pub fn find(path: &Path) -> Result<Self> {
+        if path.is_file() {
+            return Ok(Self::Zip(path.to_path_buf()));
+        }
+        if !path.is_dir() {
+            return Err(anyhow!("Letterboxd export not found at {}", path.display()));
+        }
+        if path.join("watched.csv").exists() || path.join("diary.csv").exists() {
+            return Ok(Self::Dir(path.to_path_buf()));
+        }
+        let newest_zip = std::fs::read_dir(path)?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|file| file.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("zip")))
+            .filter_map(|file| Some((std::fs::metadata(&file).ok()?.modified().ok()?, file)))
+            .max();
+        newest_zip
+            .map(|(_, file)| Self::Zip(file))
+            .ok_or_else(|| anyhow!("No Letterboxd export (CSV files or zip) in {}", path.display()))
+    }
+
+    /// Contents of `name` in the export, or `None` if the export has no such file
+    pub fn read(&self, name: &str) -> Result<Option<String>> {
+        match self {
+            Self::Dir(dir) => {
+                let file = dir.join(name);
+                if !file.exists() {
+                    return Ok(None);
+                }
+                std::fs::read_to_string(&file)
+                    .map(Some)
+                    .map_err(|e| anyhow!("Failed to read {}: {}", file.display(), e))
+            }
+            Self::Zip(zip_path) => {
+                let file = std::fs::File::open(zip_path)
+                    .map_err(|e| anyhow!("Failed to open {}: {}", zip_path.display(), e))?;
+                let mut archive = zip::ZipArchive::new(file)
+                    .map_err(|e| anyhow!("{} is not a zip file: {}", zip_path.display(), e))?;
+                let mut entry = match archive.by_name(name) {
+                    Ok(entry) => entry,
+                    Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+                    Err(e) => return Err(anyhow!("Failed to read {} from {}: {}", name, zip_path.display(), e)),
+                };
+                let mut content = String::new();
+                entry.read_to_string(&mut content)
+                    .map_err(|e| anyhow!("Failed to read {} from {}: {}", name, zip_path.display(), e))?;
+                Ok(Some(content))
+            }
+        }
+    }
+}
+
+/// One row of any of the export's film lists; columns a list doesn't have stay empty
+#[derive(Debug, Deserialize)]
+struct FilmRow {
+    #[serde(rename = "Date")]
+    date: String,
+    #[serde(rename = "Name")]
+    name: String,
+    #[serde(rename = "Year")]
+    year: Option<u32>,
+    #[serde(rename = "Rating", default)]
+    rating: Option<f64>,
+    #[serde(rename = "Watched Date", default)]
+    watched_date: Option<String>,
+}
+
+impl FilmRow {
+    fn key(&self) -> (String, Option<u32>) {
+        (self.name.to_lowercase(), self.year)
+    }
+
+    fn ids(&self) -> MediaIds {
+        MediaIds::default().with_metadata(self.name.clone(), self.year, MediaType::Movie)
+    }
+}
+
+fn parse_rows(csv: &str, file: &str) -> Result<Vec<FilmRow>> {
+    csv::Reader::from_reader(csv.as_bytes())
+        .deserialize()
+        .collect::<Result<Vec<FilmRow>, _>>()
+        .map_err(|e| anyhow!("Failed to parse Letterboxd {}: {}", file, e))
+}
+
+fn parse_date(date: &str) -> Result<DateTime<Utc>> {
+    NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+        .map_err(|e| anyhow!("Failed to parse Letterboxd date '{}': {}", date, e))?
+        .and_hms_opt(0, 0, 0)
+        .map(|datetime| datetime.and_utc())
+        .ok_or_else(|| anyhow!("Failed to parse Letterboxd date '{}'", date))
+}
+
+/// A star rating (0.5 to 5 in half stars) in half stars, 1 to 10
+pub fn stars_to_half_stars(stars: f64) -> Option<u8> {
+    let half_stars = (stars * 2.0).round();
+    (1.0..=10.0).contains(&half_stars).then_some(half_stars as u8)
+}
+
+/// Parse `watchlist.csv`
+pub fn parse_watchlist(csv: &str) -> Result<Vec<WatchlistItem>> {
+    parse_rows(csv, "watchlist.csv")?
+        .into_iter()
+        .map(|row| {
+            Ok(WatchlistItem {
+                imdb_id: String::new(),
+                ids: Some(row.ids()),
+                date_added: parse_date(&row.date)?,
+                title: row.name,
+                year: row.year,
+                media_type: MediaType::Movie,
+                source: SOURCE_NAME.to_string(),
+                status: Some(NormalizedStatus::Watchlist),
+                tags: Vec::new(),
+            })
+        })
+        .collect()
+}
+
+/// Parse `ratings.csv`; ratings are kept in half stars
+pub fn parse_ratings(csv: &str) -> Result<Vec<Rating>> {
+    let mut ratings = Vec::new();
+    for row in parse_rows(csv, "ratings.csv")? {
+        let Some(rating) = row.rating.and_then(stars_to_half_stars) else {
+            tracing::debug!("Skipping Letterboxd rating of '{}' without a valid star rating", row.name);
+            continue;
+        };
+        ratings.push(Rating {
+            imdb_id: String::new(),
+            ids: Some(row.ids()),
+            rating,
+            rating_f64: None,
+            date_added: parse_date(&row.date)?,
+            media_type: MediaType::Movie,
+            source: RatingSource::Letterboxd,
+            inferred: false,
+        });
+    }
+    Ok(ratings)
+}
+
+/// Parse `diary.csv`, one play per logged viewing on its watched date, plus the films
+/// in `watched.csv` that were never logged, on the date they were marked as watched
+pub fn parse_watch_history(diary_csv: Option<&str>, watched_csv: Option<&str>) -> Result<Vec<WatchHistory>> {
+    let play = |row: &FilmRow, watched_at: DateTime<Utc>| WatchHistory {
+        imdb_id: String::new(),
+        ids: Some(row.ids()),
+        title: Some(row.name.clone()),
+        year: row.year,
+        watched_at,
+        media_type: MediaType::Movie,
+        source: SOURCE_NAME.to_string(),
+        watched_percent: None,
+        show_ids: None,
+    };
+
+    let mut history = Vec::new();
+    let mut logged = HashSet::new();
+    if let Some(diary_csv) = diary_csv {
+        for row in parse_rows(diary_csv, "diary.csv")? {
+            let watched_date = row.watched_date.as_deref().filter(|date| !date.trim().is_empty()).unwrap_or(&row.date);
+            history.push(play(&row, parse_date(watched_date)?));
+            logged.insert(row.key());
+        }
+    }
+    if let Some(watched_csv) = watched_csv {
+        for row in parse_rows(watched_csv, "watched.csv")? {
+            if !logged.contains(&row.key()) {
+                history.push(play(&row, parse_date(&row.date)?));
+            }
+        }
+    }
+    Ok(history)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    const DIARY: &str = "Date,Name,Year,Letterboxd URI,Rating,Rewatch,Tags,Watched Date\n\
+        2024-03-02,Heat,1995,https://boxd.it/abc1,4.5,,,2024-03-01\n\
+        2024-05-10,Heat,1995,https://boxd.it/abc2,5,Yes,,2024-05-09\n";
+    const WATCHED: &str = "Date,Name,Year,Letterboxd URI\n\
+        2023-01-15,Heat,1995,https://boxd.it/2bcA\n\
+        2023-01-15,Ran,1985,https://boxd.it/2aQk\n";
+    const RATINGS: &str = "Date,Name,Year,Letterboxd URI,Rating\n\
+        2024-03-02,Heat,1995,https://boxd.it/2bcA,4.5\n\
+        2024-03-03,Ran,1985,https://boxd.it/2aQk,0.5\n\
+        2024-03-04,Cats,2019,https://boxd.it/kQ2e,\n";
+
+    #[test]
+    fn test_export_is_read_from_zip_with_half_star_ratings() {
+        let dir = tempfile::tempdir().unwrap();
+        let zip_path = dir.path().join("letterboxd-user-2024-06-01.zip");
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(&zip_path).unwrap());
+        for (name, content) in [("diary.csv", DIARY), ("watched.csv", WATCHED), ("ratings.csv", RATINGS)] {
+            zip.start_file(name, zip::write::FileOptions::default()).unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+
+        // A folder of export zips reads the newest
+        let export = ExportLocation::find(dir.path()).unwrap();
+        assert_eq!(export, ExportLocation::Zip(zip_path));
+        assert!(export.read("watchlist.csv").unwrap().is_none());
+
+        let ratings = parse_ratings(&export.read("ratings.csv").unwrap().unwrap()).unwrap();
+        assert_eq!(ratings.iter().map(|r| r.rating).collect::<Vec<_>>(), vec![9, 1]);
+        assert_eq!(ratings[0].ids.as_ref().unwrap().title.as_deref(), Some("Heat"));
+
+        // Both logged viewings of Heat, and Ran, which was marked watched but never logged
+        let history = parse_watch_history(
+            export.read("diary.csv").unwrap().as_deref(),
+            export.read("watched.csv").unwrap().as_deref(),
+        ).unwrap();
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[0].watched_at, parse_date("2024-03-01").unwrap());
+        assert_eq!(history[2].title.as_deref(), Some("Ran"));
+        assert_eq!(history[2].watched_at, parse_date("2023-01-15").unwrap());
+    }
+}
@@ -0,0 +1,4 @@
+pub mod client;
+pub mod export;
+
+pub use client::LetterboxdClient;
@@ -5,13 +5,29 @@ pub mod imdb;
 pub mod trakt;
 pub mod plex;
 pub mod simkl;
+pub mod letterboxd;
+pub mod anilist;
+pub mod file;
+pub mod disambiguation;
 pub mod error;
 pub mod progress;
+pub mod retry;
+pub mod http_cache;
+pub mod rate_limit;
+pub mod oauth;
+pub mod tmdb;
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_support;
 
-pub use traits::MediaSource;
-pub use capabilities::{IncrementalSync, StatusMapping, RatingNormalization, CapabilityRegistry, IdExtraction, IdLookupProvider};
+pub use traits::{DataType, MediaSource, WrittenReview};
+pub use capabilities::{IncrementalSync, StatusMapping, RatingNormalization, CapabilityRegistry, IdExtraction, IdLookupProvider, PlaybackProgress, CollectionSource, EpisodeLookup, AiredEpisode, HistoryPruning};
 pub use factory::{SourceFactory, SourceFactoryRegistry};
-pub use error::SourceError;
+pub use error::{SourceError, SourceErrorKind, ImdbAutomationError, ImdbStep};
 pub use trakt::trakt_authenticate;
 pub use simkl::simkl_authenticate;
-pub use progress::ProgressTracker;
\ No newline at end of file
+pub use progress::{ProgressObserver, ProgressTracker};
+pub use retry::RetryPolicy;
+pub use http_cache::HttpCache;
+pub use rate_limit::RateLimiter;
+pub use file::FileSource;
+pub use tmdb::TmdbLookupProvider;
\ No newline at end of file
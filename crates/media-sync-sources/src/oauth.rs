@@ -0,0 +1,131 @@
+//! OAuth access tokens that are renewed when they stop working.
+//!
+//! Trakt and Simkl hand out an access token with a refresh token. A request answered
+//! 401 means the access token expired or was revoked: [`AccessToken::call`] then has the
+//! client exchange its refresh token for new tokens (and save them), and sends the
+//! request once more with the new access token. A second 401 is returned as is.
+//!
+//! Refresh tokens are single-use, so concurrent requests that all get a 401 share one
+//! exchange: whoever waits for it retries with the token it produced.
+
+use anyhow::{anyhow, Result};
+use std::future::Future;
+use std::sync::{Arc, RwLock};
+use tokio::sync::Mutex;
+use tracing::info;
+
+#[derive(Debug, Clone, Default)]
+pub struct AccessToken {
+    /// None until authenticated. Clones share it, so a refresh reaches all of them.
+    token: Arc<RwLock<Option<String>>>,
+    refresh_lock: Arc<Mutex<()>>,
+}
+
+impl AccessToken {
+    pub fn set(&self, token: String) {
+        *self.token.write().unwrap_or_else(|e| e.into_inner()) = Some(token);
+    }
+
+    pub fn get(&self) -> Result<String> {
+        self.token.read().unwrap_or_else(|e| e.into_inner())
+            .clone()
+            .ok_or_else(|| anyhow!("Not authenticated"))
+    }
+
+    pub fn is_set(&self) -> bool {
+        self.token.read().unwrap_or_else(|e| e.into_inner()).is_some()
+    }
+
+    /// Run `call` with the access token. When it fails with 401, `refresh` is asked for a
+    /// new access token and `call` runs once more with it.
+    pub async fn call<T, F, Fut, R, RFut>(&self, call: F, refresh: R) -> Result<T>
+    where
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = Result<T>>,
+        R: FnOnce() -> RFut,
+        RFut: Future<Output = Result<String>>,
+    {
+        let token = self.get()?;
+        match call(token.clone()).await {
+            Err(e) if is_unauthorized(&e) => {
+                let renewed = self.renew(&token, refresh).await
+                    .map_err(|refresh_error| anyhow!("{} (refreshing the access token failed: {})", e, refresh_error))?;
+                call(renewed).await
+            }
+            result => result,
+        }
+    }
+
+    /// A token to use instead of `rejected`: the one another request already renewed it
+    /// to, or a new one from `refresh`
+    async fn renew<R, RFut>(&self, rejected: &str, refresh: R) -> Result<String>
+    where
+        R: FnOnce() -> RFut,
+        RFut: Future<Output = Result<String>>,
+    {
+        let _guard = self.refresh_lock.lock().await;
+        let current = self.get()?;
+        if current != rejected {
+            return Ok(current);
+        }
+        info!("Access token was rejected, refreshing it");
+        let renewed = refresh().await?;
+        self.set(renewed.clone());
+        Ok(renewed)
+    }
+}
+
+/// Whether the request behind `error` was answered 401
+pub fn is_unauthorized(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| {
+        cause.downcast_ref::<reqwest::Error>().and_then(|e| e.status()) == Some(reqwest::StatusCode::UNAUTHORIZED)
+            || cause.to_string().contains("401 Unauthorized")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{serve, MockResponse};
+
+    #[tokio::test]
+    async fn test_unauthorized_call_is_retried_after_a_token_refresh() {
+        let (base, server) = serve(vec![
+            MockResponse::new("401 Unauthorized"),
+            MockResponse::json("200 OK", r#"{"access_token":"fresh","refresh_token":"rotated","expires_in":86400}"#),
+            MockResponse::json("200 OK", "[]"),
+        ]);
+        let client = reqwest::Client::new();
+        let token = AccessToken::default();
+        token.set("expired".to_string());
+
+        let refreshed = std::sync::Mutex::new(None);
+        let body = token.call(
+            |access_token| {
+                let request = client.get(format!("{}/sync/watchlist", base)).bearer_auth(access_token);
+                async move {
+                    let response = request.send().await?;
+                    if !response.status().is_success() {
+                        return Err(anyhow!("Failed to fetch watchlist: {}", response.status()));
+                    }
+                    Ok(response.text().await?)
+                }
+            },
+            || async {
+                let token_info = crate::trakt::auth::refresh_access_token(
+                    &client, &format!("{}/oauth/token", base), "id", "secret", "old-refresh",
+                ).await?;
+                *refreshed.lock().unwrap() = Some(token_info.refresh_token);
+                Ok(token_info.access_token)
+            },
+        ).await.unwrap();
+
+        assert_eq!(body, "[]");
+        assert_eq!(token.get().unwrap(), "fresh");
+        assert_eq!(refreshed.lock().unwrap().as_deref(), Some("rotated"));
+        let requests = server.join().unwrap();
+        assert!(requests[0].headers.contains("authorization: bearer expired"));
+        assert!(requests[1].line.starts_with("POST /oauth/token") && requests[1].body.contains("old-refresh"));
+        assert!(requests[2].headers.contains("authorization: bearer fresh"));
+    }
+}
@@ -3,7 +3,9 @@ use chrono::{DateTime, NaiveDate, Utc, TimeZone};
 use reqwest::Client;
 use serde::Deserialize;
 use serde_json::Value;
+use std::collections::HashMap;
 use tracing::{debug, info, warn};
+use crate::http_cache::HttpCache;
 
 const DISCOVER_BASE_URL: &str = "https://discover.provider.plex.tv";
 const PLEX_TV_BASE_URL: &str = "https://plex.tv";
@@ -27,6 +29,32 @@ pub struct Guid {
     pub id: String,
 }
 
+/// Whether Plex's primary GUID is the placeholder of an item no agent could match
+/// (`local://…` for the Plex agents, `com.plexapp.agents.none://…` for legacy ones)
+fn is_unmatched_guid(guid: &str) -> bool {
+    guid.starts_with("local://") || guid.starts_with("com.plexapp.agents.none://")
+}
+
+/// Movies (`plex_type` 1) or shows (2) of a library, only those rated at or after
+/// `rated_since` when given (`>>=` is Plex's greater-than-or-equal filter). Rating an
+/// item doesn't change its `updatedAt`, so ratings are filtered on `lastRatedAt`.
+fn library_items_url(server_url: &str, library_key: &str, plex_type: u8, rated_since: Option<DateTime<Utc>>) -> String {
+    let mut url = format!("{}/library/sections/{}/all?type={}&includeGuids=1", server_url, library_key, plex_type);
+    if let Some(since) = rated_since {
+        url.push_str(&format!("&lastRatedAt>>={}", since.timestamp()));
+    }
+    url
+}
+
+/// Play history of one account, since `since` when given, newest first
+fn account_play_history_url(server_url: &str, account_id: u64, since: Option<DateTime<Utc>>) -> String {
+    let mut url = format!("{}/status/sessions/history/all?sort=viewedAt:desc&accountID={}", server_url, account_id);
+    if let Some(since) = since {
+        url.push_str(&format!("&viewedAt>={}", since.timestamp()));
+    }
+    url
+}
+
 #[derive(Debug, Clone)]
 pub struct MovieMetadata {
     pub rating_key: String,
@@ -36,8 +64,14 @@ pub struct MovieMetadata {
     pub user_rating: Option<f64>,
     pub view_count: Option<u32>,
     pub last_viewed_at: Option<DateTime<Utc>>,
+    /// Plex's own GUID (`plex://movie/…`, or a legacy agent GUID)
+    pub guid: Option<String>,
     pub guids: Vec<Guid>,
     pub type_: String,
+    /// Labels the user put on the item in the library
+    pub labels: Vec<String>,
+    /// When the item was added to the library
+    pub added_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone)]
@@ -49,8 +83,14 @@ pub struct ShowMetadata {
     pub user_rating: Option<f64>,
     pub view_count: Option<u32>,
     pub last_viewed_at: Option<DateTime<Utc>>,
+    /// Plex's own GUID (`plex://show/…`, or a legacy agent GUID)
+    pub guid: Option<String>,
     pub guids: Vec<Guid>,
     pub type_: String,
+    /// Labels the user put on the item in the library
+    pub labels: Vec<String>,
+    /// When the item was added to the library
+    pub added_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone)]
@@ -84,16 +124,33 @@ pub struct PlayHistoryItem {
     pub season: Option<u32>,
     pub episode_number: Option<u32>,
     pub original_air_date: Option<DateTime<Utc>>,
+    pub guid: Option<String>,
+}
+
+impl PlayHistoryItem {
+    /// History entries carry no external GUIDs, so only a placeholder primary GUID tells
+    pub fn is_unmatched(&self) -> bool {
+        self.guid.as_deref().is_some_and(is_unmatched_guid)
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct RatingItem {
     pub rating_key: String,
     pub type_: String,
+    pub title: String,
     pub user_rating: f64,
+    pub guid: Option<String>,
     pub guids: Vec<Guid>,
 }
 
+impl RatingItem {
+    /// No external GUIDs and no legacy agent GUID that could carry an ID either
+    pub fn is_unmatched(&self) -> bool {
+        self.guids.is_empty() && self.guid.as_deref().is_none_or(is_unmatched_guid)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ReviewItem {
     pub rating_key: String,
@@ -122,11 +179,17 @@ pub struct PlexHttpClient {
     token: String,
     server_url: Option<String>,
     discover_base_url: String,
+    http_cache: HttpCache,
 }
 
 impl PlexHttpClient {
     pub fn new(token: String, server_url: Option<String>) -> Result<Self> {
-        let client = Client::builder()
+        Self::new_with_timeout(token, server_url, None)
+    }
+
+    /// Like [`PlexHttpClient::new`], giving up on requests that take longer than `timeout`
+    pub fn new_with_timeout(token: String, server_url: Option<String>, timeout: Option<std::time::Duration>) -> Result<Self> {
+        let mut builder = Client::builder()
             .default_headers({
                 let mut headers = reqwest::header::HeaderMap::new();
                 headers.insert(
@@ -143,7 +206,11 @@ impl PlexHttpClient {
                     reqwest::header::HeaderValue::from_static("totalrecall-cli"),
                 );
                 headers
-            })
+            });
+        if let Some(timeout) = timeout {
+            builder = builder.timeout(timeout);
+        }
+        let client = builder
             .build()
             .context("Failed to create HTTP client")?;
 
@@ -152,9 +219,16 @@ impl PlexHttpClient {
             token,
             server_url,
             discover_base_url: DISCOVER_BASE_URL.to_string(),
+            http_cache: HttpCache::default(),
         })
     }
 
+    /// Revalidate library and watchlist reads instead of downloading them again
+    pub fn with_http_cache(mut self, http_cache: HttpCache) -> Self {
+        self.http_cache = http_cache;
+        self
+    }
+
 
     pub async fn authenticate(&self) -> Result<()> {
         let url = format!("{}/api/v2/user", PLEX_TV_BASE_URL);
@@ -317,19 +391,12 @@ impl PlexHttpClient {
 
     pub async fn get_libraries(&self, server_url: &str) -> Result<Vec<LibraryInfo>> {
         let url = format!("{}/library/sections", server_url);
-        let response = self
+        let request = self
             .client
             .get(&url)
             .header("X-Plex-Token", &self.token)
-            .header("Accept", "application/json")
-            .send()
-            .await
-            .context("Failed to get libraries")?;
-
-        let json: Value = response
-            .json()
-            .await
-            .context("Failed to parse libraries response")?;
+            .header("Accept", "application/json");
+        let json: Value = self.http_cache.get_json("libraries", request).await?;
 
         let mut libraries = Vec::new();
         if let Some(media_container) = json.get("MediaContainer") {
@@ -403,7 +470,16 @@ impl PlexHttpClient {
         let user_rating = item.get("userRating").and_then(|r| r.as_f64());
         let view_count = item.get("viewCount").and_then(|v| v.as_u64()).map(|v| v as u32);
         let last_viewed_at = self.parse_timestamp(item.get("lastViewedAt"));
+        let added_at = self.parse_timestamp(item.get("addedAt"));
+        let guid = item.get("guid").and_then(|g| g.as_str()).map(|g| g.to_string());
         let guids = self.parse_guid_array(item.get("Guid").unwrap_or(&Value::Null));
+        let labels = item.get("Label")
+            .and_then(|labels| labels.as_array())
+            .map(|labels| labels.iter()
+                .filter_map(|label| label.get("tag").and_then(|t| t.as_str()))
+                .map(|tag| tag.to_string())
+                .collect())
+            .unwrap_or_default();
 
         Some(MovieMetadata {
             rating_key,
@@ -413,29 +489,27 @@ impl PlexHttpClient {
             user_rating,
             view_count,
             last_viewed_at,
+            guid,
             guids,
             type_: item_type.to_string(),
+            labels,
+            added_at,
         })
     }
 
     pub async fn get_movies(&self, server_url: &str, library_key: &str) -> Result<Vec<MovieMetadata>> {
-        let url = format!(
-            "{}/library/sections/{}/all?type=1&includeGuids=1",
-            server_url, library_key
-        );
-        let response = self
+        self.get_movies_rated_since(server_url, library_key, None).await
+    }
+
+    /// A movie library's items, only those rated at or after `since` when given
+    pub async fn get_movies_rated_since(&self, server_url: &str, library_key: &str, since: Option<DateTime<Utc>>) -> Result<Vec<MovieMetadata>> {
+        let url = library_items_url(server_url, library_key, 1, since);
+        let request = self
             .client
             .get(&url)
             .header("X-Plex-Token", &self.token)
-            .header("Accept", "application/json")
-            .send()
-            .await
-            .context("Failed to get movies")?;
-
-        let json: Value = response
-            .json()
-            .await
-            .context("Failed to parse movies response")?;
+            .header("Accept", "application/json");
+        let json: Value = self.http_cache.get_json("movies", request).await?;
 
         let mut movies = Vec::new();
         if let Some(media_container) = json.get("MediaContainer") {
@@ -471,23 +545,18 @@ impl PlexHttpClient {
     }
 
     pub async fn get_shows(&self, server_url: &str, library_key: &str) -> Result<Vec<ShowMetadata>> {
-        let url = format!(
-            "{}/library/sections/{}/all?type=2&includeGuids=1",
-            server_url, library_key
-        );
-        let response = self
+        self.get_shows_rated_since(server_url, library_key, None).await
+    }
+
+    /// A show library's items, only those rated at or after `since` when given
+    pub async fn get_shows_rated_since(&self, server_url: &str, library_key: &str, since: Option<DateTime<Utc>>) -> Result<Vec<ShowMetadata>> {
+        let url = library_items_url(server_url, library_key, 2, since);
+        let request = self
             .client
             .get(&url)
             .header("X-Plex-Token", &self.token)
-            .header("Accept", "application/json")
-            .send()
-            .await
-            .context("Failed to get shows")?;
-
-        let json: Value = response
-            .json()
-            .await
-            .context("Failed to parse shows response")?;
+            .header("Accept", "application/json");
+        let json: Value = self.http_cache.get_json("shows", request).await?;
 
         let mut shows = Vec::new();
         if let Some(media_container) = json.get("MediaContainer") {
@@ -506,8 +575,11 @@ impl PlexHttpClient {
                                 user_rating: movie.user_rating,
                                 view_count: movie.view_count,
                                 last_viewed_at: movie.last_viewed_at,
+                                guid: movie.guid,
                                 guids: movie.guids,
                                 type_: "show".to_string(),
+                                labels: movie.labels,
+                                added_at: movie.added_at,
                             });
                         } else {
                             skipped += 1;
@@ -591,19 +663,12 @@ impl PlexHttpClient {
 
     pub async fn get_watchlist(&self) -> Result<Vec<WatchlistItem>> {
         let url = format!("{}/library/sections/watchlist/all", self.discover_base_url);
-        let response = self
+        let request = self
             .client
             .get(&url)
             .header("X-Plex-Token", &self.token)
-            .header("Accept", "application/json")
-            .send()
-            .await
-            .context("Failed to get watchlist")?;
-
-        let json: Value = response
-            .json()
-            .await
-            .context("Failed to parse watchlist response")?;
+            .header("Accept", "application/json");
+        let json: Value = self.http_cache.get_json("watchlist", request).await?;
 
         let mut watchlist = Vec::new();
         if let Some(media_container) = json.get("MediaContainer") {
@@ -709,9 +774,13 @@ impl PlexHttpClient {
         }
     }
 
-    pub async fn get_ratings(&self, server_url: &str) -> Result<Vec<RatingItem>> {
+    /// Rated movies and shows of every library, along with the keys of the libraries
+    /// scanned. A library with an entry in `rated_since` only returns the items rated
+    /// since then.
+    pub async fn get_ratings(&self, server_url: &str, rated_since: &HashMap<String, DateTime<Utc>>) -> Result<(Vec<RatingItem>, Vec<String>)> {
         // Get all libraries and iterate through items to find ratings
         let libraries = self.get_libraries(server_url).await?;
+        let mut scanned = Vec::new();
         debug!("Plex get_ratings: Found {} libraries", libraries.len());
         let mut ratings = Vec::new();
         let mut total_movies = 0;
@@ -721,7 +790,8 @@ impl PlexHttpClient {
 
         for library in libraries {
             if library.type_ == "movie" {
-                let movies = self.get_movies(server_url, &library.key).await?;
+                let movies = self.get_movies_rated_since(server_url, &library.key, rated_since.get(&library.key).copied()).await?;
+                scanned.push(library.key.clone());
                 total_movies += movies.len();
                 debug!("Plex get_ratings: Library '{}' has {} movies", library.title, movies.len());
                 for movie in movies {
@@ -736,14 +806,17 @@ impl PlexHttpClient {
                             ratings.push(RatingItem {
                                 rating_key: movie.rating_key,
                                 type_: "movie".to_string(),
+                                title: movie.title,
                                 user_rating,
+                                guid: movie.guid,
                                 guids: movie.guids,
                             });
                         }
                     }
                 }
             } else if library.type_ == "show" {
-                let shows = self.get_shows(server_url, &library.key).await?;
+                let shows = self.get_shows_rated_since(server_url, &library.key, rated_since.get(&library.key).copied()).await?;
+                scanned.push(library.key.clone());
                 total_shows += shows.len();
                 debug!("Plex get_ratings: Library '{}' has {} shows", library.title, shows.len());
                 for show in shows {
@@ -758,7 +831,9 @@ impl PlexHttpClient {
                             ratings.push(RatingItem {
                                 rating_key: show.rating_key,
                                 type_: "show".to_string(),
+                                title: show.title,
                                 user_rating,
+                                guid: show.guid,
                                 guids: show.guids,
                             });
                         }
@@ -769,7 +844,7 @@ impl PlexHttpClient {
 
         debug!("Plex get_ratings: {} total movies, {} total shows, {} movies with ratings, {} shows with ratings, {} total ratings", 
                total_movies, total_shows, movies_with_ratings, shows_with_ratings, ratings.len());
-        Ok(ratings)
+        Ok((ratings, scanned))
     }
 
     pub async fn set_rating(&self, server_url: &str, rating_key: &str, rating: f64) -> Result<()> {
@@ -927,15 +1002,67 @@ impl PlexHttpClient {
 
     pub async fn get_play_history(&self, server_url: &str) -> Result<Vec<PlayHistoryItem>> {
         let url = format!("{}/status/sessions/history/all", server_url);
+        self.fetch_play_history(&url).await
+    }
+
+    /// Plays by one account, viewed at or after `since` when given, filtered by the
+    /// server (`accountID` and `viewedAt>=` on /status/sessions/history/all)
+    pub async fn get_account_play_history(&self, server_url: &str, account_id: u64, since: Option<DateTime<Utc>>) -> Result<Vec<PlayHistoryItem>> {
+        let url = account_play_history_url(server_url, account_id, since);
+        self.fetch_play_history(&url).await
+    }
+
+    /// The server's account ID for the token's user. The server owner is always account 1;
+    /// other users appear in the server's /accounts under their plex.tv ID.
+    pub async fn get_account_id(&self, server_url: &str) -> Result<u64> {
+        let user: Value = self
+            .client
+            .get(format!("{}/api/v2/user", PLEX_TV_BASE_URL))
+            .header("X-Plex-Token", &self.token)
+            .header("Accept", "application/json")
+            .send()
+            .await
+            .context("Failed to get Plex user")?
+            .error_for_status()?
+            .json()
+            .await
+            .context("Failed to parse Plex user response")?;
+        let user_id = user.get("id").and_then(|id| id.as_u64())
+            .ok_or_else(|| anyhow::anyhow!("Plex user response has no id"))?;
+
+        let accounts: Value = self
+            .client
+            .get(format!("{}/accounts", server_url))
+            .header("X-Plex-Token", &self.token)
+            .header("Accept", "application/json")
+            .send()
+            .await
+            .context("Failed to get Plex server accounts")?
+            .error_for_status()?
+            .json()
+            .await
+            .context("Failed to parse Plex server accounts response")?;
+        let is_server_account = accounts.get("MediaContainer")
+            .and_then(|container| container.get("Account"))
+            .and_then(|accounts| accounts.as_array())
+            .is_some_and(|accounts| accounts.iter().any(|account| account.get("id").and_then(|id| id.as_u64()) == Some(user_id)));
+        Ok(if is_server_account { user_id } else { 1 })
+    }
+
+    async fn fetch_play_history(&self, url: &str) -> Result<Vec<PlayHistoryItem>> {
         let response = self
             .client
-            .get(&url)
+            .get(url)
             .header("X-Plex-Token", &self.token)
             .header("Accept", "application/json")
             .send()
             .await
             .context("Failed to get play history")?;
 
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!("Plex play history request failed: {}", response.status()));
+        }
+
         let json: Value = response
             .json()
             .await
@@ -1000,6 +1127,7 @@ impl PlexHttpClient {
                                 .and_then(|d| d.and_hms_opt(0, 0, 0))
                                 .map(|dt| DateTime::from_utc(dt, Utc))
                         });
+                    let guid = item.get("guid").and_then(|g| g.as_str()).map(|g| g.to_string());
 
                     if idx < 3 {
                         debug!("Plex play history[{}]: rating_key={}, type={}, view_count={}, title={:?}, year={:?}", 
@@ -1018,6 +1146,7 @@ impl PlexHttpClient {
                         season,
                         episode_number,
                         original_air_date,
+                        guid,
                     });
                 }
             } else {
@@ -1509,3 +1638,52 @@ impl PlexHttpClient {
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unmatched_items_have_placeholder_or_no_guids() {
+        let rating = |guid: Option<&str>, guids: &[&str]| RatingItem {
+            rating_key: "1".to_string(),
+            type_: "movie".to_string(),
+            title: "Home Video".to_string(),
+            user_rating: 8.0,
+            guid: guid.map(|g| g.to_string()),
+            guids: guids.iter().map(|id| Guid { id: id.to_string() }).collect(),
+        };
+        assert!(rating(Some("local://1234"), &[]).is_unmatched());
+        assert!(rating(None, &[]).is_unmatched());
+        assert!(!rating(Some("plex://movie/5d776841e6d55c002040ee8b"), &["imdb://tt0133093"]).is_unmatched());
+        // Legacy agents carry the ID in the primary GUID only
+        assert!(!rating(Some("com.plexapp.agents.imdb://tt0133093?lang=en"), &[]).is_unmatched());
+        assert!(rating(Some("com.plexapp.agents.none://42?lang=xn"), &[]).is_unmatched());
+    }
+
+    #[test]
+    fn test_library_items_url_filters_on_last_rated_at() {
+        let since = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+        assert_eq!(
+            library_items_url("http://plex:32400", "2", 1, Some(since)),
+            "http://plex:32400/library/sections/2/all?type=1&includeGuids=1&lastRatedAt>>=1717200000"
+        );
+        assert_eq!(
+            library_items_url("http://plex:32400", "3", 2, None),
+            "http://plex:32400/library/sections/3/all?type=2&includeGuids=1"
+        );
+    }
+
+    #[test]
+    fn test_account_play_history_url_filters_on_server() {
+        let since = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+        assert_eq!(
+            account_play_history_url("http://plex:32400", 1, Some(since)),
+            "http://plex:32400/status/sessions/history/all?sort=viewedAt:desc&accountID=1&viewedAt>=1717200000"
+        );
+        assert_eq!(
+            account_play_history_url("http://plex:32400", 1, None),
+            "http://plex:32400/status/sessions/history/all?sort=viewedAt:desc&accountID=1"
+        );
+    }
+}
@@ -1,12 +1,17 @@
-use crate::traits::MediaSource;
-use crate::capabilities::{RatingNormalization, CapabilityRegistry, StatusMapping, IncrementalSync, IdExtraction, IdLookupProvider};
+use crate::traits::{DataType, MediaSource, WrittenReview};
+use crate::capabilities::{RatingNormalization, CapabilityRegistry, StatusMapping, IncrementalSync, IdExtraction, IdLookupProvider, CollectionSource};
+use crate::plex::library_cache::LibraryCache;
+use crate::plex::watermarks;
+use crate::http_cache::HttpCache;
+use crate::tmdb::TmdbLookupProvider;
 use crate::plex::api::{PlexHttpClient, MovieMetadata, ShowMetadata, WatchlistItem as ApiWatchlistItem, PlayHistoryItem, RatingItem, MetadataItem};
-use crate::ProgressTracker;
+use crate::{ProgressObserver, ProgressTracker};
 use anyhow::Result;
 use chrono::Utc;
-use media_sync_models::{Rating, Review, WatchHistory, WatchlistItem, MediaType, NormalizedStatus, MediaIds};
+use media_sync_models::{CollectionItem, ExcludedItem, Rating, Review, WatchHistory, WatchlistItem, MediaType, NormalizedStatus, MediaIds};
 use media_sync_config::StatusMapping as StatusMappingConfig;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, info, trace, warn};
@@ -20,14 +25,60 @@ pub struct PlexClient {
     // Cache mapping IMDB ID -> rating_key for efficient lookups
     imdb_to_rating_key_cache: Arc<RwLock<HashMap<String, String>>>,
     // Cache library contents to avoid repeated fetches
-    library_movies_cache: Arc<RwLock<HashMap<String, Vec<MovieMetadata>>>>,
-    library_shows_cache: Arc<RwLock<HashMap<String, Vec<ShowMetadata>>>>,
+    library_movies_cache: Arc<LibraryCache<MovieMetadata>>,
+    library_shows_cache: Arc<LibraryCache<ShowMetadata>>,
     // Cache discovered server URL to avoid repeated discovery
     discovered_server_url: Arc<RwLock<Option<String>>>,
-    // Track excluded items (items retrieved but not collected due to unsupported types)
-    excluded_items: Arc<RwLock<Vec<(String, Option<String>, String)>>>, // (title, rating_key, type_)
+    // Track excluded items (items retrieved but not collected: unsupported types, unmatched items)
+    excluded_items: Arc<RwLock<HashMap<DataType, Vec<ExcludedItem>>>>,
+    // Quarantine items Plex couldn't match (no GUIDs) instead of collecting them without IDs
+    skip_unmatched: bool,
+    // Per media type scales that differ from Plex's 0-10 (`sources.plex.rating_scales`)
+    rating_scales: media_sync_config::RatingScales,
+    // Library labels collected as watchlist statuses (`sources.plex.label_status_map`)
+    label_status_map: HashMap<String, NormalizedStatus>,
+    // Title -> IMDB ID fallback lookups, when `[sources.tmdb]` has an api_key
+    tmdb: Option<TmdbLookupProvider>,
+    force_full_sync: bool,
+    // Lists whose last fetch returned only changes (see `IncrementalSync::fetched_changes_only`)
+    changes_only: Arc<std::sync::Mutex<HashSet<DataType>>>,
+    // Per-server watermarks of the history and ratings fetches, the lock around their file,
+    // and the start of the last fetches until their data is cached (see `IncrementalSync::commit_fetch`)
+    watermarks_path: PathBuf,
+    watermarks_lock: Arc<std::sync::Mutex<()>>,
+    pending_history_watermark: Arc<std::sync::Mutex<Option<PendingWatermark>>>,
+    pending_ratings_watermark: Arc<std::sync::Mutex<Option<PendingRatingsWatermark>>>,
+    // Lists the caller has no cached copy of (see `IncrementalSync::set_cached`)
+    uncached: HashSet<DataType>,
+    // ETags of library and watchlist responses, for conditional GETs
+    http_cache: HttpCache,
+    // Give up on server and plex.tv requests taking longer (`sources.request_timeout_secs`)
+    request_timeout: Option<std::time::Duration>,
+    // Told about the progress of long writes (see `CapabilityRegistry::set_progress_observer`)
+    progress_observer: Option<ProgressObserver>,
 }
 
+/// Server URL and start of a history fetch whose plays aren't cached yet
+type PendingWatermark = (String, chrono::DateTime<Utc>);
+
+/// A ratings fetch whose ratings aren't cached yet
+#[derive(Debug, Clone)]
+struct PendingRatingsWatermark {
+    server_url: String,
+    fetch_started: chrono::DateTime<Utc>,
+    /// Libraries the fetch scanned
+    libraries: Vec<String>,
+    /// Whether every library was read in full
+    full: bool,
+}
+
+/// Fetch a little before the last fetch, for clock differences with the server;
+/// the overlap is merged away by the collect cache
+const FETCH_OVERLAP_HOURS: i64 = 1;
+
+/// Read every library in full this often, to drop ratings cleared in Plex from the cache
+const FULL_RATINGS_FETCH_DAYS: i64 = 7;
+
 impl PlexClient {
     pub fn new(token: String, status_mapping: StatusMappingConfig) -> Self {
         Self::with_server_url(token, None, status_mapping)
@@ -40,16 +91,150 @@ impl PlexClient {
             authenticated: false,
             status_mapping,
             imdb_to_rating_key_cache: Arc::new(RwLock::new(HashMap::new())),
-            library_movies_cache: Arc::new(RwLock::new(HashMap::new())),
-            library_shows_cache: Arc::new(RwLock::new(HashMap::new())),
+            library_movies_cache: Arc::new(LibraryCache::new()),
+            library_shows_cache: Arc::new(LibraryCache::new()),
             discovered_server_url: Arc::new(RwLock::new(None)),
-            excluded_items: Arc::new(RwLock::new(Vec::new())),
+            excluded_items: Arc::new(RwLock::new(HashMap::new())),
+            skip_unmatched: false,
+            rating_scales: media_sync_config::RatingScales::default(),
+            label_status_map: HashMap::new(),
+            tmdb: None,
+            force_full_sync: false,
+            changes_only: Arc::new(std::sync::Mutex::new(HashSet::new())),
+            watermarks_path: media_sync_config::PathManager::default().cache_collect_dir().join("plex").join("watermarks.json"),
+            watermarks_lock: Arc::new(std::sync::Mutex::new(())),
+            pending_history_watermark: Arc::new(std::sync::Mutex::new(None)),
+            pending_ratings_watermark: Arc::new(std::sync::Mutex::new(None)),
+            uncached: HashSet::new(),
+            http_cache: HttpCache::default(),
+            request_timeout: None,
+            progress_observer: None,
+        }
+    }
+
+    /// Look up items without an IMDB GUID on TMDB, by title
+    pub fn with_tmdb(mut self, tmdb: Option<TmdbLookupProvider>) -> Self {
+        self.tmdb = tmdb;
+        self
+    }
+
+    /// Quarantine ratings and history of items Plex couldn't match (`plex.skip_unmatched`)
+    pub fn with_skip_unmatched(mut self, skip_unmatched: bool) -> Self {
+        self.skip_unmatched = skip_unmatched;
+        self
+    }
+
+    /// Rate shows or episodes on a different scale than movies (`sources.plex.rating_scales`)
+    pub fn with_rating_scales(mut self, rating_scales: media_sync_config::RatingScales) -> Self {
+        self.rating_scales = rating_scales;
+        self
+    }
+
+    /// Collect library items with these labels as watchlist items with the mapped status
+    /// (`sources.plex.label_status_map`)
+    pub fn with_label_status_map(mut self, label_status_map: HashMap<String, NormalizedStatus>) -> Self {
+        self.label_status_map = label_status_map;
+        self
+    }
+
+    /// Revalidate library and watchlist reads instead of downloading them again
+    pub fn with_http_cache(mut self, http_cache: HttpCache) -> Self {
+        self.http_cache = http_cache;
+        self
+    }
+
+    /// Give up on requests that take longer than `timeout` (`sources.request_timeout_secs`)
+    pub fn with_request_timeout(mut self, timeout: Option<std::time::Duration>) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    /// Status of the first of `labels` in `label_status_map` (label names are case-insensitive)
+    fn label_status(&self, labels: &[String]) -> Option<NormalizedStatus> {
+        labels.iter().find_map(|label| {
+            self.label_status_map.iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(label))
+                .map(|(_, status)| status.clone())
+        })
+    }
+
+    /// A movie library's items, fetched once per run however many lookups need them at once
+    async fn cached_movies(&self, client: &PlexHttpClient, server_url: &str, library_key: &str) -> Result<Vec<MovieMetadata>> {
+        self.library_movies_cache.get_or_fetch(library_key, || client.get_movies(server_url, library_key)).await
+    }
+
+    /// A show library's items, fetched once per run however many lookups need them at once
+    async fn cached_shows(&self, client: &PlexHttpClient, server_url: &str, library_key: &str) -> Result<Vec<ShowMetadata>> {
+        self.library_shows_cache.get_or_fetch(library_key, || client.get_shows(server_url, library_key)).await
+    }
+
+    /// Library movies and shows carrying a label from `label_status_map`, as watchlist items
+    async fn labeled_items(&self, client: &PlexHttpClient, server_url: &str) -> Result<Vec<WatchlistItem>> {
+        let mut items = Vec::new();
+        for library in client.get_libraries(server_url).await? {
+            match library.type_.as_str() {
+                "movie" => items.extend(self.cached_movies(client, server_url, &library.key).await?
+                    .into_iter()
+                    .filter_map(|m| self.labeled_item(&m.labels, m.title, m.year, &m.guids, MediaType::Movie))),
+                "show" => items.extend(self.cached_shows(client, server_url, &library.key).await?
+                    .into_iter()
+                    .filter_map(|s| self.labeled_item(&s.labels, s.title, s.year, &s.guids, MediaType::Show))),
+                _ => {}
+            }
         }
+        Ok(items)
+    }
+
+    /// A library movie or show as a collection item dated by when it was added. Items
+    /// Plex couldn't match carry no IDs and are left out.
+    fn library_collection_item(
+        title: String,
+        year: Option<u32>,
+        guids: &[crate::plex::api::Guid],
+        added_at: Option<chrono::DateTime<Utc>>,
+        media_type: MediaType,
+    ) -> Option<CollectionItem> {
+        let media_ids = Self::extract_ids_from_guids(guids);
+        if media_ids.is_empty() {
+            return None;
+        }
+        Some(CollectionItem {
+            imdb_id: media_ids.imdb_id.clone().unwrap_or_default(),
+            ids: Some(media_ids),
+            title,
+            year,
+            media_type,
+            collected_at: added_at?,
+            source: "plex".to_string(),
+        })
+    }
+
+    fn labeled_item(
+        &self,
+        labels: &[String],
+        title: String,
+        year: Option<u32>,
+        guids: &[crate::plex::api::Guid],
+        media_type: MediaType,
+    ) -> Option<WatchlistItem> {
+        let status = self.label_status(labels)?;
+        let media_ids = Self::extract_ids_from_guids(guids);
+        Some(WatchlistItem {
+            imdb_id: media_ids.imdb_id.clone().unwrap_or_default(),
+            ids: if media_ids.is_empty() { None } else { Some(media_ids) },
+            title,
+            year,
+            media_type,
+            date_added: Utc::now(),
+            source: "plex".to_string(),
+            status: Some(status),
+            tags: Vec::new(),
+        })
     }
     
     /// Get excluded items from the last collection (items retrieved but not collected)
-    pub async fn get_excluded_items(&self) -> Vec<(String, Option<String>, String)> {
-        self.excluded_items.read().await.clone()
+    pub async fn get_excluded_items(&self) -> Vec<ExcludedItem> {
+        self.excluded_items.read().await.values().flatten().cloned().collect()
     }
     
     /// Clear excluded items (call before a new collection)
@@ -57,6 +242,158 @@ impl PlexClient {
         self.excluded_items.write().await.clear();
     }
 
+    fn unmatched_item(title: Option<String>, rating_key: &str, type_: &str) -> ExcludedItem {
+        ExcludedItem {
+            title,
+            imdb_id: None,
+            rating_key: if rating_key.is_empty() { None } else { Some(rating_key.to_string()) },
+            media_type: type_.to_string(),
+            reason: "Unmatched in Plex (no GUIDs)".to_string(),
+            source: "plex".to_string(),
+            date_added: None,
+        }
+    }
+
+    /// Replace the excluded items of one data type and save all of them to
+    /// cache/collect/plex/excluded.json, so ratings and history don't overwrite each other
+    async fn record_excluded_items(&self, data_type: DataType, items: Vec<ExcludedItem>) {
+        use media_sync_config::PathManager;
+        use std::fs;
+
+        let excluded = {
+            let mut excluded_items = self.excluded_items.write().await;
+            excluded_items.insert(data_type, items);
+            excluded_items.values().flatten().cloned().collect::<Vec<_>>()
+        };
+        if excluded.is_empty() {
+            return;
+        }
+
+        let path_manager = PathManager::default();
+        // Excluded items from collect phase go to cache/collect/plex/excluded.json
+        let excluded_path = path_manager.cache_collect_dir().join("plex").join("excluded.json");
+        
+        // Ensure parent directory exists
+        if let Some(parent) = excluded_path.parent() {
+            if let Err(e) = fs::create_dir_all(parent) {
+                warn!("Failed to create directory for Plex excluded items cache: {}", e);
+            }
+        }
+        
+        // Save excluded items to JSON file
+        if let Ok(json) = serde_json::to_string_pretty(&excluded) {
+            if let Err(e) = fs::write(&excluded_path, json) {
+                warn!("Failed to save Plex excluded items to cache: {}", e);
+            } else {
+                info!("Saved {} excluded items for Plex to {:?}", excluded.len(), excluded_path);
+            }
+        } else {
+            warn!("Failed to serialize Plex excluded items");
+        }
+    }
+
+    /// Watermarks of `server_url` for fetching `data_type`, none with `force_full_sync` or
+    /// without a cached copy of `data_type` to merge a changes-only fetch into
+    fn watermarks(&self, server_url: &str, data_type: DataType) -> watermarks::ServerWatermarks {
+        if self.force_full_sync || self.uncached.contains(&data_type) {
+            return watermarks::ServerWatermarks::default();
+        }
+        let _guard = self.watermarks_lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        watermarks::load(&self.watermarks_path).remove(server_url).unwrap_or_default()
+    }
+
+    fn update_watermarks(&self, server_url: &str, change: impl FnOnce(&mut watermarks::ServerWatermarks)) {
+        let _guard = self.watermarks_lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Err(e) = watermarks::update(&self.watermarks_path, server_url, change) {
+            warn!("Failed to save the Plex watermarks: {}", e);
+        }
+    }
+
+    fn set_changes_only(&self, data_type: DataType, changes_only: bool) {
+        let mut lists = self.changes_only.lock().unwrap();
+        if changes_only {
+            lists.insert(data_type);
+        } else {
+            lists.remove(&data_type);
+        }
+    }
+
+    /// The token account's play history, since the last fetch from this server or in full
+    /// on the first fetch, without a cached history and with `force_full_sync`. When the server rejects the filtered
+    /// request, falls back to the full, unfiltered history.
+    async fn fetch_play_history(&self, client: &PlexHttpClient, server_url: &str) -> Result<Vec<PlayHistoryItem>> {
+        let fetch_started = Utc::now();
+        self.pending_history_watermark.lock().unwrap().take();
+        let since = self.watermarks(server_url, DataType::WatchHistory).history
+            .map(|since| since - chrono::Duration::hours(FETCH_OVERLAP_HOURS));
+
+        let filtered = async {
+            let account_id = client.get_account_id(server_url).await?;
+            client.get_account_play_history(server_url, account_id, since).await
+        }.await;
+        let (items, changes_only) = match filtered {
+            Ok(items) => {
+                if let Some(since) = since {
+                    info!("Fetched {} Plex plays since {}", items.len(), since.to_rfc3339());
+                }
+                (items, since.is_some())
+            }
+            Err(e) => {
+                warn!("Plex filtered play history unavailable ({}), fetching the full history", e);
+                (client.get_play_history(server_url).await?, false)
+            }
+        };
+
+        self.set_changes_only(DataType::WatchHistory, changes_only);
+        // Saved by `commit_fetch` once the history is in the collect cache
+        *self.pending_history_watermark.lock().unwrap() = Some((server_url.to_string(), fetch_started));
+        Ok(items)
+    }
+
+    /// Rated library items, each library only with what was rated since its last fetch
+    /// from this server. Every library is read in full on its first fetch, without cached
+    /// ratings, with `force_full_sync` and once every `FULL_RATINGS_FETCH_DAYS`: clearing
+    /// a rating leaves nothing to filter on, so only a full read drops it from the cache.
+    async fn fetch_ratings(&self, client: &PlexHttpClient, server_url: &str) -> Result<Vec<RatingItem>> {
+        let fetch_started = Utc::now();
+        self.pending_ratings_watermark.lock().unwrap().take();
+        let watermarks = self.watermarks(server_url, DataType::Ratings);
+        let full = watermarks.ratings_full
+            .is_none_or(|last| fetch_started - last >= chrono::Duration::days(FULL_RATINGS_FETCH_DAYS));
+        let rated_since: HashMap<String, chrono::DateTime<Utc>> = if full {
+            HashMap::new()
+        } else {
+            watermarks.libraries.into_iter()
+                .map(|(library, since)| (library, since - chrono::Duration::hours(FETCH_OVERLAP_HOURS)))
+                .collect()
+        };
+
+        let (items, scanned) = client.get_ratings(server_url, &rated_since).await?;
+        let changes_only = scanned.iter().any(|library| rated_since.contains_key(library));
+        if changes_only {
+            info!("Fetched {} Plex ratings from items rated since the last fetch", items.len());
+        }
+
+        self.set_changes_only(DataType::Ratings, changes_only);
+        // Saved by `commit_fetch` once the ratings are in the collect cache
+        *self.pending_ratings_watermark.lock().unwrap() = Some(PendingRatingsWatermark {
+            server_url: server_url.to_string(),
+            fetch_started,
+            libraries: scanned,
+            full,
+        });
+        Ok(items)
+    }
+
+    fn warn_unmatched(count: usize, data_type: DataType) {
+        if count > 0 {
+            warn!(
+                "Plex {}: skipped {} items Plex couldn't match (no GUIDs). Fix their metadata in Plex (Fix Match) to sync them; they are listed in cache/collect/plex/excluded.json",
+                data_type, count
+            );
+        }
+    }
+
     pub async fn authenticate(&mut self) -> Result<()> {
         use media_sync_config::CredentialStore;
         use media_sync_config::PathManager;
@@ -75,7 +412,7 @@ impl PlexClient {
         };
 
         // Create HTTP client and authenticate to verify token
-        let api_client = PlexHttpClient::new(token.clone(), self.server_url.clone())?;
+        let api_client = PlexHttpClient::new_with_timeout(token.clone(), self.server_url.clone(), self.request_timeout)?;
         api_client.authenticate().await?;
         
         self.token = token;
@@ -94,7 +431,7 @@ impl PlexClient {
         if !self.authenticated {
             return Err(anyhow::anyhow!("Not authenticated to Plex"));
         }
-        PlexHttpClient::new(self.token.clone(), self.server_url.clone())
+        Ok(PlexHttpClient::new_with_timeout(self.token.clone(), self.server_url.clone(), self.request_timeout)?.with_http_cache(self.http_cache.clone()))
     }
 
     /// Get server URL - use configured URL or discover first available server
@@ -163,16 +500,10 @@ impl PlexClient {
                 let imdb_part = &guid[start + 7..]; // Skip "imdb://"
                 // Extract IMDB ID (format: tt followed by digits)
                 // May have query parameters like "?lang=en", so split on '?' or '&'
-                let imdb_id = imdb_part.split('?').next()
+                // Validate and canonicalize (tt followed by digits)
+                return imdb_part.split('?').next()
                     .and_then(|s| s.split('&').next())
-                    .map(|s| s.trim().to_string());
-                
-                // Validate it looks like an IMDB ID (starts with "tt" followed by digits)
-                if let Some(id) = imdb_id {
-                    if id.starts_with("tt") && id.len() >= 9 && id[2..].chars().all(|c| c.is_ascii_digit()) {
-                        return Some(id);
-                    }
-                }
+                    .and_then(media_sync_models::normalize_imdb_id);
             }
         }
         None
@@ -207,7 +538,7 @@ impl PlexClient {
     }
     
     /// Extract all IDs from GUID array
-    fn extract_ids_from_guids(guids: &[crate::plex::api::Guid]) -> MediaIds {
+    pub(crate) fn extract_ids_from_guids(guids: &[crate::plex::api::Guid]) -> MediaIds {
         let mut media_ids = MediaIds::default();
         
         for guid in guids {
@@ -261,56 +592,16 @@ impl PlexClient {
         Self::extract_imdb_id_from_guids(&item.guids)
     }
 
-    /// Look up IMDB ID via TMDB API when item is not found on Plex server
-    /// TMDB API is free and doesn't require an API key for basic searches
-    async fn lookup_imdb_id_via_tmdb(title: &str, year: Option<u32>) -> Option<String> {
-        use reqwest::Client;
-        use urlencoding::encode;
-        
-        let client = Client::new();
-        let encoded_title = encode(title);
-        let mut url = format!("https://api.themoviedb.org/3/search/movie?query={}&language=en-US", encoded_title);
-        if let Some(y) = year {
-            url.push_str(&format!("&year={}", y));
-        }
-        
-        match client.get(&url).send().await {
-            Ok(response) => {
-                if response.status().is_success() {
-                    if let Ok(json) = response.json::<serde_json::Value>().await {
-                        if let Some(results) = json.get("results").and_then(|r| r.as_array()) {
-                            if let Some(first_result) = results.first() {
-                                // TMDB returns imdb_id in the external_ids field, but we need to make another call
-                                // For now, try to get it from the first result's id and make a details call
-                                if let Some(id) = first_result.get("id").and_then(|i| i.as_u64()) {
-                                    let details_url = format!("https://api.themoviedb.org/3/movie/{}?append_to_response=external_ids", id);
-                                    if let Ok(details_response) = client.get(&details_url).send().await {
-                                        if details_response.status().is_success() {
-                                            if let Ok(details_json) = details_response.json::<serde_json::Value>().await {
-                                                if let Some(imdb_id) = details_json
-                                                    .get("external_ids")
-                                                    .and_then(|e| e.get("imdb_id"))
-                                                    .and_then(|i| i.as_str())
-                                                {
-                                                    if !imdb_id.is_empty() && imdb_id.starts_with("tt") {
-                                                        debug!("TMDB lookup found IMDB ID {} for '{}'", imdb_id, title);
-                                                        return Some(imdb_id.to_string());
-                                                    }
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+    /// Look up IMDB ID via TMDB when item is not found on Plex server
+    async fn lookup_imdb_id_via_tmdb(&self, title: &str, year: Option<u32>) -> Option<String> {
+        let tmdb = self.tmdb.as_ref()?;
+        match tmdb.lookup_ids(title, year, &MediaType::Movie).await {
+            Ok(ids) => ids.and_then(|ids| ids.imdb_id),
             Err(e) => {
                 debug!("TMDB API lookup failed for '{}': {}", title, e);
+                None
             }
         }
-        None
     }
 
     /// Match rating_key to library items to get IMDB ID
@@ -339,22 +630,8 @@ impl PlexClient {
             if let Ok(libraries) = client.get_libraries(server_url).await {
                 for library in libraries {
                     if library.type_ == "movie" {
-                        // Check cache first
-                        let movies = {
-                            let cache = self.library_movies_cache.read().await;
-                            if let Some(cached) = cache.get(&library.key) {
-                                cached.clone()
-                            } else {
-                                // Cache miss - fetch and cache
-                                drop(cache);
-                                if let Ok(fetched) = client.get_movies(server_url, &library.key).await {
-                                    let mut cache = self.library_movies_cache.write().await;
-                                    cache.insert(library.key.clone(), fetched.clone());
-                                    fetched
-                                } else {
-                                    continue;
-                                }
-                            }
+                        let Ok(movies) = self.cached_movies(&client, server_url, &library.key).await else {
+                            continue;
                         };
                         
                         for movie in movies {
@@ -377,22 +654,8 @@ impl PlexClient {
                             }
                         }
                     } else if library.type_ == "show" {
-                        // Check cache first
-                        let shows = {
-                            let cache = self.library_shows_cache.read().await;
-                            if let Some(cached) = cache.get(&library.key) {
-                                cached.clone()
-                            } else {
-                                // Cache miss - fetch and cache
-                                drop(cache);
-                                if let Ok(fetched) = client.get_shows(server_url, &library.key).await {
-                                    let mut cache = self.library_shows_cache.write().await;
-                                    cache.insert(library.key.clone(), fetched.clone());
-                                    fetched
-                                } else {
-                                    continue;
-                                }
-                            }
+                        let Ok(shows) = self.cached_shows(&client, server_url, &library.key).await else {
+                            continue;
                         };
                         
                         for show in shows {
@@ -634,7 +897,7 @@ impl PlexClient {
         if let Ok(libraries) = client.get_libraries(server_url).await {
             for library in libraries {
                 if library.type_ == "movie" {
-                    if let Ok(movies) = client.get_movies(server_url, &library.key).await {
+                    if let Ok(movies) = self.cached_movies(&client, server_url, &library.key).await {
                         for movie in movies {
                             let media_ids = Self::extract_ids_from_guids(&movie.guids);
                             if media_ids.tmdb_id == Some(tmdb_id) {
@@ -643,7 +906,7 @@ impl PlexClient {
                         }
                     }
                 } else if library.type_ == "show" {
-                    if let Ok(shows) = client.get_shows(server_url, &library.key).await {
+                    if let Ok(shows) = self.cached_shows(&client, server_url, &library.key).await {
                         for show in shows {
                             let media_ids = Self::extract_ids_from_guids(&show.guids);
                             if media_ids.tmdb_id == Some(tmdb_id) {
@@ -664,7 +927,7 @@ impl PlexClient {
         if let Ok(libraries) = client.get_libraries(server_url).await {
             for library in libraries {
                 if library.type_ == "show" {
-                    if let Ok(shows) = client.get_shows(server_url, &library.key).await {
+                    if let Ok(shows) = self.cached_shows(&client, server_url, &library.key).await {
                         for show in shows {
                             let media_ids = Self::extract_ids_from_guids(&show.guids);
                             if media_ids.tvdb_id == Some(tvdb_id) {
@@ -696,7 +959,7 @@ impl PlexClient {
             for library in libraries {
                 // Search movies
                 if library.type_ == "movie" {
-                    if let Ok(movies) = client.get_movies(server_url, &library.key).await {
+                    if let Ok(movies) = self.cached_movies(&client, server_url, &library.key).await {
                         for movie in movies {
                             if let Some(item_imdb_id) = Self::extract_imdb_id_from_metadata(&movie) {
                                 if item_imdb_id == imdb_id {
@@ -711,7 +974,7 @@ impl PlexClient {
                 }
                 // Search shows
                 else if library.type_ == "show" {
-                    if let Ok(shows) = client.get_shows(server_url, &library.key).await {
+                    if let Ok(shows) = self.cached_shows(&client, server_url, &library.key).await {
                         for show in shows {
                             if let Some(item_imdb_id) = Self::extract_imdb_id_from_show(&show) {
                                 if item_imdb_id == imdb_id {
@@ -766,6 +1029,7 @@ impl PlexClient {
             date_added: Utc::now(),
             source: "plex".to_string(),
             status: Some(NormalizedStatus::Watchlist),
+            tags: Vec::new(),
         }
     }
 
@@ -788,21 +1052,25 @@ impl PlexClient {
             _ => MediaType::Movie,
         };
 
-        // Convert 0.0-10.0 rating to 1-10 scale (Plex uses 0.0-10.0)
-        let rating_10 = item.user_rating.round() as u8;
-        if rating_10 == 0 {
+        // Plex rates 0.0-10.0 (or its rating_scales); the fraction is kept for normalization
+        if item.user_rating <= 0.0 {
             return None; // Skip zero ratings
         }
 
         // Always include rating, even if no IDs found - IDs can be resolved later
-        Some(Rating {
+        let mut rating = Rating {
             imdb_id,
             ids: Some(media_ids),
-            rating: rating_10,
-            date_added: Utc::now(),
+            rating: 0,
+            rating_f64: None,
+            // The ratings endpoint has no rating date
+            date_added: Rating::UNDATED,
             media_type,
             source: media_sync_models::RatingSource::Plex,
-        })
+            inferred: false,
+        };
+        rating.set_value(item.user_rating);
+        Some(rating)
     }
 
     /// Get all IDs from rating_key metadata (IMDB, TMDB, TVDB, etc.)
@@ -823,22 +1091,8 @@ impl PlexClient {
         if let Ok(libraries) = client.get_libraries(server_url).await {
             for library in libraries {
                 if library.type_ == "movie" {
-                    // Check cache first
-                    let movies = {
-                        let cache = self.library_movies_cache.read().await;
-                        if let Some(cached) = cache.get(&library.key) {
-                            cached.clone()
-                        } else {
-                            // Cache miss - fetch and cache
-                            drop(cache);
-                            if let Ok(fetched) = client.get_movies(server_url, &library.key).await {
-                                let mut cache = self.library_movies_cache.write().await;
-                                cache.insert(library.key.clone(), fetched.clone());
-                                fetched
-                            } else {
-                                continue;
-                            }
-                        }
+                    let Ok(movies) = self.cached_movies(&client, server_url, &library.key).await else {
+                        continue;
                     };
                     
                     for movie in movies {
@@ -847,22 +1101,8 @@ impl PlexClient {
                         }
                     }
                 } else if library.type_ == "show" {
-                    // Check cache first
-                    let shows = {
-                        let cache = self.library_shows_cache.read().await;
-                        if let Some(cached) = cache.get(&library.key) {
-                            cached.clone()
-                        } else {
-                            // Cache miss - fetch and cache
-                            drop(cache);
-                            if let Ok(fetched) = client.get_shows(server_url, &library.key).await {
-                                let mut cache = self.library_shows_cache.write().await;
-                                cache.insert(library.key.clone(), fetched.clone());
-                                fetched
-                            } else {
-                                continue;
-                            }
-                        }
+                    let Ok(shows) = self.cached_shows(&client, server_url, &library.key).await else {
+                        continue;
                     };
                     
                     for show in shows {
@@ -927,6 +1167,8 @@ impl PlexClient {
             watched_at: item.last_viewed_at,
             media_type,
             source: "plex".to_string(),
+            watched_percent: None,
+            show_ids: None,
         })
     }
 }
@@ -939,6 +1181,12 @@ impl MediaSource for PlexClient {
         "plex"
     }
 
+    fn supported_data_types(&self) -> &'static [crate::traits::DataType] {
+        use crate::traits::DataType;
+        // Plex has no review API (only an undocumented rateAndReview endpoint that rarely works)
+        &[DataType::Watchlist, DataType::Ratings, DataType::WatchHistory]
+    }
+
     async fn authenticate(&mut self) -> Result<(), Self::Error> {
         match self.authenticate().await {
             Ok(()) => Ok(()),
@@ -1027,27 +1275,11 @@ impl MediaSource for PlexClient {
                                 let mut found = false;
                                 for library in libraries {
                                 if library.type_ == "movie" {
-                                    // Check cache first
-                                    let movies = {
-                                        let cache = self.library_movies_cache.read().await;
-                                        if let Some(cached) = cache.get(&library.key) {
-                                            debug!("Plex watchlist: Cache hit for movies in library '{}'", library.title);
-                                            cached.clone()
-                                        } else {
-                                            // Cache miss - fetch and cache
-                                            drop(cache);
-                                            match client.get_movies(server_url, &library.key).await {
-                                                Ok(fetched) => {
-                                                    let mut cache = self.library_movies_cache.write().await;
-                                                    cache.insert(library.key.clone(), fetched.clone());
-                                                    debug!("Plex watchlist: Cache miss for movies in library '{}', fetched {} items", library.title, fetched.len());
-                                                    fetched
-                                                }
-                                                Err(e) => {
-                                                    debug!("Plex watchlist: Failed to get movies from library '{}': {}", library.title, e);
-                                                    continue;
-                                                }
-                                            }
+                                    let movies = match self.cached_movies(&client, server_url, &library.key).await {
+                                        Ok(movies) => movies,
+                                        Err(e) => {
+                                            debug!("Plex watchlist: Failed to get movies from library '{}': {}", library.title, e);
+                                            continue;
                                         }
                                     };
                                     
@@ -1091,27 +1323,11 @@ impl MediaSource for PlexClient {
                                         }
                                     }
                                 } else if library.type_ == "show" {
-                                    // Check cache first
-                                    let shows = {
-                                        let cache = self.library_shows_cache.read().await;
-                                        if let Some(cached) = cache.get(&library.key) {
-                                            debug!("Plex watchlist: Cache hit for shows in library '{}'", library.title);
-                                            cached.clone()
-                                        } else {
-                                            // Cache miss - fetch and cache
-                                            drop(cache);
-                                            match client.get_shows(server_url, &library.key).await {
-                                                Ok(fetched) => {
-                                                    let mut cache = self.library_shows_cache.write().await;
-                                                    cache.insert(library.key.clone(), fetched.clone());
-                                                    debug!("Plex watchlist: Cache miss for shows in library '{}', fetched {} items", library.title, fetched.len());
-                                                    fetched
-                                                }
-                                                Err(e) => {
-                                                    debug!("Plex watchlist: Failed to get shows from library '{}': {}", library.title, e);
-                                                    continue;
-                                                }
-                                            }
+                                    let shows = match self.cached_shows(&client, server_url, &library.key).await {
+                                        Ok(shows) => shows,
+                                        Err(e) => {
+                                            debug!("Plex watchlist: Failed to get shows from library '{}': {}", library.title, e);
+                                            continue;
                                         }
                                     };
                                     
@@ -1239,7 +1455,7 @@ impl MediaSource for PlexClient {
                                             trace!("Plex watchlist: Search API returned no results for '{}', trying TMDB lookup", item_with_guids.title);
                                             
                                             // Final fallback: Use TMDB API to look up IMDB ID by title/year
-                                            if let Some(imdb_id) = Self::lookup_imdb_id_via_tmdb(&item_with_guids.title, item_with_guids.year).await {
+                                            if let Some(imdb_id) = self.lookup_imdb_id_via_tmdb(&item_with_guids.title, item_with_guids.year).await {
                                                 // Create a fake GUID with the IMDB ID so it gets extracted
                                                 item_with_guids.guids.push(crate::plex::api::Guid {
                                                     id: format!("imdb://{}", imdb_id),
@@ -1252,7 +1468,7 @@ impl MediaSource for PlexClient {
                                         debug!("Plex watchlist: Search API failed for '{}': {}, trying TMDB lookup", item_with_guids.title, e);
                                         
                                         // Fallback: Use TMDB API to look up IMDB ID by title/year
-                                        if let Some(imdb_id) = Self::lookup_imdb_id_via_tmdb(&item_with_guids.title, item_with_guids.year).await {
+                                        if let Some(imdb_id) = self.lookup_imdb_id_via_tmdb(&item_with_guids.title, item_with_guids.year).await {
                                             // Create a fake GUID with the IMDB ID so it gets extracted
                                             item_with_guids.guids.push(crate::plex::api::Guid {
                                                 id: format!("imdb://{}", imdb_id),
@@ -1267,7 +1483,7 @@ impl MediaSource for PlexClient {
                                 debug!("Plex watchlist: Failed to get libraries from server: {}, trying TMDB lookup", e);
                                 
                                 // Fallback: Use TMDB API to look up IMDB ID by title/year
-                                if let Some(imdb_id) = Self::lookup_imdb_id_via_tmdb(&item_with_guids.title, item_with_guids.year).await {
+                                if let Some(imdb_id) = self.lookup_imdb_id_via_tmdb(&item_with_guids.title, item_with_guids.year).await {
                                     // Create a fake GUID with the IMDB ID so it gets extracted
                                     item_with_guids.guids.push(crate::plex::api::Guid {
                                         id: format!("imdb://{}", imdb_id),
@@ -1280,7 +1496,7 @@ impl MediaSource for PlexClient {
                     debug!("Plex watchlist: Item '{}' has no IMDB ID and no server URL available for lookup, trying TMDB lookup", item_with_guids.title);
                     
                     // Fallback: Use TMDB API to look up IMDB ID by title/year
-                    if let Some(imdb_id) = Self::lookup_imdb_id_via_tmdb(&item_with_guids.title, item_with_guids.year).await {
+                    if let Some(imdb_id) = self.lookup_imdb_id_via_tmdb(&item_with_guids.title, item_with_guids.year).await {
                         // Create a fake GUID with the IMDB ID so it gets extracted
                         item_with_guids.guids.push(crate::plex::api::Guid {
                             id: format!("imdb://{}", imdb_id),
@@ -1307,6 +1523,25 @@ impl MediaSource for PlexClient {
             watchlist.push(watchlist_item);
         }
         
+        if !self.label_status_map.is_empty() {
+            match server_url {
+                Some(ref server_url) => match self.labeled_items(&client, server_url).await {
+                    Ok(labeled) => {
+                        info!("Plex watchlist: {} library items with a status label", labeled.len());
+                        for item in labeled {
+                            // The label is the more specific status for an item also on the cloud watchlist
+                            match watchlist.iter_mut().find(|existing| !item.imdb_id.is_empty() && existing.imdb_id == item.imdb_id) {
+                                Some(existing) => existing.status = item.status,
+                                None => watchlist.push(item),
+                            }
+                        }
+                    }
+                    Err(e) => warn!("Plex watchlist: Failed to read library labels: {}", e),
+                },
+                None => warn!("Plex watchlist: sources.plex.label_status_map needs a server; labels were not read"),
+            }
+        }
+
         info!("Plex watchlist collection: {} items collected, {} items without IMDB ID", watchlist.len(), items_without_imdb);
         Ok(watchlist)
     }
@@ -1324,14 +1559,19 @@ impl MediaSource for PlexClient {
         let client = self.get_api_client().await
             .map_err(|e| crate::error::SourceError::new(format!("{}", e)))?;
         
-        let rating_items = client.get_ratings(&server_url).await
+        let rating_items = self.fetch_ratings(&client, &server_url).await
             .map_err(|e| crate::error::SourceError::new(format!("{}", e)))?;
         
         let mut ratings = Vec::new();
         let total_items = rating_items.len();
         let mut items_without_imdb = 0;
+        let mut unmatched = Vec::new();
         
         for item in rating_items {
+            if self.skip_unmatched && item.is_unmatched() {
+                unmatched.push(Self::unmatched_item(Some(item.title.clone()), &item.rating_key, &item.type_));
+                continue;
+            }
             if let Some(rating) = self.rating_item_to_rating(&item, &server_url).await {
                 // Cache the IMDB ID -> rating_key mapping if we have an IMDB ID
                 if !rating.imdb_id.is_empty() {
@@ -1347,8 +1587,10 @@ impl MediaSource for PlexClient {
             }
         }
         
-        info!("Plex ratings collection: {} total items, {} ratings collected, {} items without IMDB ID", 
-              total_items, ratings.len(), items_without_imdb);
+        Self::warn_unmatched(unmatched.len(), DataType::Ratings);
+        info!("Plex ratings collection: {} total items, {} ratings collected, {} items without IMDB ID, {} unmatched items skipped", 
+              total_items, ratings.len(), items_without_imdb, unmatched.len());
+        self.record_excluded_items(DataType::Ratings, unmatched).await;
         
         Ok(ratings)
     }
@@ -1371,18 +1613,29 @@ impl MediaSource for PlexClient {
         let client = self.get_api_client().await
             .map_err(|e| crate::error::SourceError::new(format!("{}", e)))?;
         
-        let play_history = client.get_play_history(&server_url).await
+        let play_history = self.fetch_play_history(&client, &server_url).await
             .map_err(|e| crate::error::SourceError::new(format!("{}", e)))?;
         
-        // Clear excluded items from previous collection
-        self.clear_excluded_items().await;
-        
         let mut history = Vec::new();
         let total_items = play_history.len();
         let mut items_without_imdb = 0;
         
         let mut items_filtered = 0;
-        for item in play_history {
+        let mut items_unmatched = 0;
+        let mut excluded = Vec::new();
+        let progress_interval = if total_items < 50 { 10 } else { 50 };
+        let mut tracker = ProgressTracker::with_operation_name(
+            total_items,
+            progress_interval,
+            Some("Plex watch history scan".to_string()),
+        ).with_observer(self.progress_observer.clone());
+        for (idx, item) in play_history.into_iter().enumerate() {
+            tracker.log_progress(idx + 1);
+            if self.skip_unmatched && item.is_unmatched() {
+                items_unmatched += 1;
+                excluded.push(Self::unmatched_item(item.title.clone(), &item.rating_key, &item.type_));
+                continue;
+            }
             if let Some(history_item) = self.play_history_to_watch_history(&item, &server_url).await {
                 // Cache the IMDB ID -> rating_key mapping if we have an IMDB ID
                 if !history_item.imdb_id.is_empty() {
@@ -1398,12 +1651,15 @@ impl MediaSource for PlexClient {
             } else {
                 items_filtered += 1;
                 // Track excluded items (unsupported media types like "track")
-                let excluded_title = item.title.clone().unwrap_or_else(|| "unknown".to_string());
-                let excluded_rating_key = if item.rating_key.is_empty() { None } else { Some(item.rating_key.clone()) };
-                {
-                    let mut excluded = self.excluded_items.write().await;
-                    excluded.push((excluded_title, excluded_rating_key, item.type_.clone()));
-                }
+                excluded.push(ExcludedItem {
+                    title: Some(item.title.clone().unwrap_or_else(|| "unknown".to_string())),
+                    imdb_id: None, // Excluded items are unsupported types, so they don't have IMDB IDs
+                    rating_key: if item.rating_key.is_empty() { None } else { Some(item.rating_key.clone()) },
+                    media_type: item.type_.clone(),
+                    reason: format!("Unsupported media type: {}", item.type_),
+                    source: "plex".to_string(),
+                    date_added: None, // Not a watchlist item, so no date_added
+                });
                 if items_filtered <= 5 {
                     debug!("Plex watch history: Item filtered out (type: '{}', rating_key: '{}', title: '{:?}')", 
                            item.type_, item.rating_key, item.title);
@@ -1415,52 +1671,12 @@ impl MediaSource for PlexClient {
             warn!("Plex watch history: {} items were filtered out (unsupported media types like 'track')", items_filtered);
         }
         
-        // Save excluded items to cache (collect phase - unsupported media types)
-        let excluded_raw = self.get_excluded_items().await;
-        if !excluded_raw.is_empty() {
-            use media_sync_config::PathManager;
-            use media_sync_models::ExcludedItem;
-            use serde_json;
-            use std::fs;
-            
-            let path_manager = PathManager::default();
-            // Excluded items from collect phase go to cache/collect/plex/excluded.json
-            let excluded_path = path_manager.cache_collect_dir().join("plex").join("excluded.json");
-            
-            let excluded: Vec<ExcludedItem> = excluded_raw
-                .into_iter()
-                .map(|(title, rating_key, type_)| ExcludedItem {
-                    title: Some(title),
-                    imdb_id: None, // Excluded items are unsupported types, so they don't have IMDB IDs
-                    rating_key,
-                    media_type: type_.clone(),
-                    reason: format!("Unsupported media type: {}", type_),
-                    source: "plex".to_string(),
-                    date_added: None, // Not a watchlist item, so no date_added
-                })
-                .collect();
-            
-            // Ensure parent directory exists
-            if let Some(parent) = excluded_path.parent() {
-                if let Err(e) = fs::create_dir_all(parent) {
-                    warn!("Failed to create directory for Plex excluded items cache: {}", e);
-                }
-            }
-            
-            // Save excluded items to JSON file
-            if let Ok(json) = serde_json::to_string_pretty(&excluded) {
-                if let Err(e) = fs::write(&excluded_path, json) {
-                    warn!("Failed to save Plex excluded items to cache: {}", e);
-                } else {
-                    info!("Saved {} excluded items for Plex to {:?} (unsupported media types)", excluded.len(), excluded_path);
-                }
-            } else {
-                warn!("Failed to serialize Plex excluded items");
-            }
-        }
+        Self::warn_unmatched(items_unmatched, DataType::WatchHistory);
+        // Save excluded items to cache (collect phase - unsupported media types, unmatched items)
+        self.record_excluded_items(DataType::WatchHistory, excluded).await;
         
         info!("Plex watch history collection: {} total items, {} history items collected, {} items without IMDB ID, {} items excluded", 
-              total_items, history.len(), items_without_imdb, items_filtered);
+              total_items, history.len(), items_without_imdb, items_filtered + items_unmatched);
         
         if history.is_empty() && total_items > 0 {
             warn!("Plex watch history: WARNING - {} play history items were fetched but 0 were converted to WatchHistory. This suggests play_history_to_watch_history is filtering out all items.", total_items);
@@ -1485,7 +1701,7 @@ impl MediaSource for PlexClient {
             items.len(),
             progress_interval,
             Some("Plex watchlist add".to_string()),
-        );
+        ).with_observer(self.progress_observer.clone());
         let mut added_count = 0;
         let mut not_found_count = 0;
         
@@ -1556,7 +1772,7 @@ impl MediaSource for PlexClient {
             items.len(),
             progress_interval,
             Some("Plex watchlist remove".to_string()),
-        );
+        ).with_observer(self.progress_observer.clone());
         
         for (idx, item) in items.iter().enumerate() {
             let current = idx + 1;
@@ -1621,7 +1837,7 @@ impl MediaSource for PlexClient {
             ratings.len(),
             progress_interval,
             Some("Plex ratings set".to_string()),
-        );
+        ).with_observer(self.progress_observer.clone());
         
         for (idx, rating) in ratings.iter().enumerate() {
             let current = idx + 1;
@@ -1650,13 +1866,8 @@ impl MediaSource for PlexClient {
                     trace!("Skipping rating for Discover provider item (rating_key={}): not supported by local server rating endpoint", rating_key);
                     tracker.record_skipped();
                 } else {
-                    // Convert from 1-10 scale (stored) to 0-10 scale (Plex API)
-                    // Plex API accepts 0-10, but we store as 1-10 to match other sources
-                    let rating_value = if rating.rating > 0 {
-                        (rating.rating - 1) as f64
-                    } else {
-                        0.0
-                    };
+                    // Already on Plex's scale (denormalized), fraction included
+                    let rating_value = rating.value();
                     
                     match client.set_rating(&server_url, &rating_key, rating_value).await {
                         Ok(_) => {
@@ -1681,7 +1892,7 @@ impl MediaSource for PlexClient {
         Ok(())
     }
 
-    async fn set_reviews(&self, reviews: &[Review]) -> Result<(), Self::Error> {
+    async fn set_reviews(&self, reviews: &[Review]) -> Result<Vec<WrittenReview>, Self::Error> {
         let server_url = self.get_server_url().await
             .map_err(|e| crate::error::SourceError::new(format!("{}", e)))?;
         
@@ -1690,11 +1901,11 @@ impl MediaSource for PlexClient {
         
         info!("Plex: Starting to process {} reviews", reviews.len());
         
-        let mut success_count = 0;
+        let mut written = Vec::new();
         let mut not_found_count = 0;
         let mut error_count = 0;
         
-        for review in reviews {
+        for (index, review) in reviews.iter().enumerate() {
             // Try to get rating_key from MediaIds (checks plex_rating_key first, then tries imdb, tmdb, tvdb, then discover provider)
             // Review has media_type field, and MediaIds may have title/year, so pass those as fallbacks
             // require_discover_provider_key=false because reviews can use local server keys
@@ -1723,7 +1934,7 @@ impl MediaSource for PlexClient {
                 match client.set_review(&server_url, &rating_key, review_text).await {
                     Ok(_) => {
                         info!("Plex: Successfully set review for '{}' (imdb_id={}) on Plex", review.imdb_id, review.imdb_id);
-                        success_count += 1;
+                        written.push(WrittenReview { index, review_id: None });
                     }
                     Err(e) => {
                         warn!("Plex: Failed to set review for '{}' (imdb_id={}) on Plex: {}", review.imdb_id, review.imdb_id, e);
@@ -1738,16 +1949,16 @@ impl MediaSource for PlexClient {
         }
         
         info!("Plex: Completed processing reviews: {} total items, {} succeeded, {} failed (not found), {} failed (API error)", 
-              reviews.len(), success_count, not_found_count, error_count);
+              reviews.len(), written.len(), not_found_count, error_count);
         
         // Return error only if ALL reviews failed
-        if success_count == 0 && !reviews.is_empty() {
+        if written.is_empty() && !reviews.is_empty() {
             Err(crate::error::SourceError::new(format!(
                 "Failed to set any reviews: {} not found, {} API errors",
                 not_found_count, error_count
             )))
         } else {
-            Ok(())
+            Ok(written)
         }
     }
 
@@ -1767,7 +1978,7 @@ impl MediaSource for PlexClient {
             items.len(),
             progress_interval,
             Some("Plex watch history add".to_string()),
-        );
+        ).with_observer(self.progress_observer.clone());
         let mut success_count = 0;
         let mut not_found_count = 0;
         let mut error_count = 0;
@@ -1863,26 +2074,36 @@ impl MediaSource for PlexClient {
 }
 
 impl RatingNormalization for PlexClient {
-    fn normalize_rating(&self, rating: f64, target_scale: u8) -> u8 {
-        // Plex uses 0.0-10.0 for ratings, convert to target scale
-        if target_scale == 10 {
+    fn normalize_rating(&self, rating: f64, target_scale: u8, media_type: &MediaType) -> u8 {
+        // Plex stores 0.0-10.0, or the scale in sources.plex.rating_scales for this media type
+        let native_scale = self.native_rating_scale(media_type);
+        if target_scale == native_scale {
             rating.round() as u8
         } else {
-            (rating * (target_scale as f64 / 10.0)).round() as u8
+            (rating * (target_scale as f64 / native_scale as f64)).round() as u8
         }
     }
     
-    fn denormalize_rating(&self, rating: u8, source_scale: u8) -> f64 {
-        // Convert from source scale back to 0.0-10.0
-        if source_scale == 10 {
+    fn denormalize_rating(&self, rating: u8, source_scale: u8, media_type: &MediaType) -> f64 {
+        // Convert from source scale back to this media type's native scale
+        let native_scale = self.native_rating_scale(media_type);
+        if source_scale == native_scale {
             rating as f64
         } else {
-            rating as f64 * 10.0 / source_scale as f64
+            rating as f64 * native_scale as f64 / source_scale as f64
         }
     }
     
-    fn native_rating_scale(&self) -> u8 {
-        10 // Plex uses 0.0-10.0 scale
+    fn normalize_rating_f64(&self, rating: f64, target_scale: u8, media_type: &MediaType) -> f64 {
+        rating * target_scale as f64 / self.native_rating_scale(media_type) as f64
+    }
+
+    fn denormalize_rating_f64(&self, rating: f64, source_scale: u8, media_type: &MediaType) -> f64 {
+        rating * self.native_rating_scale(media_type) as f64 / source_scale as f64
+    }
+    
+    fn native_rating_scale(&self, media_type: &MediaType) -> u8 {
+        self.rating_scales.get(media_type).unwrap_or(10) // Plex uses 0.0-10.0 scale
     }
 }
 
@@ -2043,9 +2264,58 @@ impl IdLookupProvider for PlexClient {
     }
 }
 
+impl IncrementalSync for PlexClient {
+    fn set_force_full_sync(&mut self, force: bool) {
+        self.force_full_sync = force;
+    }
+
+    fn fetched_changes_only(&self, data_type: DataType) -> bool {
+        self.changes_only.lock().unwrap().contains(&data_type)
+    }
+
+    fn set_cached(&mut self, data_type: DataType, cached: bool) {
+        if cached {
+            self.uncached.remove(&data_type);
+        } else {
+            self.uncached.insert(data_type);
+        }
+    }
+
+    fn commit_fetch(&self, data_type: DataType) {
+        match data_type {
+            DataType::WatchHistory => {
+                if let Some((server_url, fetch_started)) = self.pending_history_watermark.lock().unwrap().take() {
+                    self.update_watermarks(&server_url, |server| server.history = Some(fetch_started));
+                }
+            }
+            DataType::Ratings => {
+                if let Some(pending) = self.pending_ratings_watermark.lock().unwrap().take() {
+                    self.update_watermarks(&pending.server_url, |server| {
+                        for library in pending.libraries {
+                            server.libraries.insert(library, pending.fetch_started);
+                        }
+                        if pending.full {
+                            server.ratings_full = Some(pending.fetch_started);
+                        }
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
 impl CapabilityRegistry for PlexClient {
     fn as_incremental_sync(&mut self) -> Option<&mut dyn IncrementalSync> {
-        None
+        Some(self)
+    }
+
+    fn supports_incremental_sync(&self) -> bool {
+        true
+    }
+
+    fn set_progress_observer(&mut self, observer: Option<ProgressObserver>) {
+        self.progress_observer = observer;
     }
     
     fn as_rating_normalization(&self) -> Option<&dyn RatingNormalization> {
@@ -2063,4 +2333,167 @@ impl CapabilityRegistry for PlexClient {
     fn as_id_lookup_provider(&self) -> Option<&dyn IdLookupProvider> {
         Some(self)
     }
+
+    fn as_collection_source(&self) -> Option<&dyn CollectionSource> {
+        Some(self)
+    }
+}
+
+/// The server's movie and show libraries, dated by `addedAt`. Read-only: Plex can't add
+/// to a library what isn't on disk.
+#[async_trait]
+impl CollectionSource for PlexClient {
+    async fn get_collection(&self) -> Result<Vec<CollectionItem>, crate::error::SourceError> {
+        let collect = async {
+            let client = self.get_api_client().await?;
+            let server_url = self.get_server_url().await?;
+            let mut items = Vec::new();
+            for library in client.get_libraries(&server_url).await? {
+                match library.type_.as_str() {
+                    "movie" => items.extend(self.cached_movies(&client, &server_url, &library.key).await?
+                        .into_iter()
+                        .filter_map(|m| Self::library_collection_item(m.title, m.year, &m.guids, m.added_at, MediaType::Movie))),
+                    "show" => items.extend(self.cached_shows(&client, &server_url, &library.key).await?
+                        .into_iter()
+                        .filter_map(|s| Self::library_collection_item(s.title, s.year, &s.guids, s.added_at, MediaType::Show))),
+                    _ => {}
+                }
+            }
+            debug!("Plex collection: {} library items", items.len());
+            anyhow::Ok(items)
+        };
+        collect.await.map_err(|e| crate::error::SourceError::new(format!("{}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use media_sync_config::{default_plex_status_mapping, RatingScales};
+
+    #[test]
+    fn test_rating_scale_follows_media_type() {
+        let plex = PlexClient::new(String::new(), default_plex_status_mapping())
+            .with_rating_scales(RatingScales { show: Some(5), ..Default::default() });
+        let episode = MediaType::Episode { season: 1, episode: 3 };
+
+        // Movies and episodes keep Plex's 0-10
+        assert_eq!(plex.native_rating_scale(&MediaType::Movie), 10);
+        assert_eq!(plex.normalize_rating(7.0, 10, &MediaType::Movie), 7);
+        assert_eq!(plex.native_rating_scale(&episode), 10);
+        assert_eq!(plex.denormalize_rating(7, 10, &episode), 7.0);
+
+        // Shows are rated 1-5
+        assert_eq!(plex.native_rating_scale(&MediaType::Show), 5);
+        assert_eq!(plex.normalize_rating(4.0, 10, &MediaType::Show), 8);
+        assert_eq!(plex.denormalize_rating(8, 10, &MediaType::Show), 4.0);
+
+        // Without sources.plex.rating_scales there is one scale
+        let plex = PlexClient::new(String::new(), default_plex_status_mapping());
+        assert_eq!(plex.native_rating_scale(&MediaType::Show), 10);
+        assert_eq!(plex.normalize_rating(8.0, 10, &MediaType::Show), 8);
+    }
+
+    #[test]
+    fn test_half_stars_survive_a_round_trip() {
+        let plex = PlexClient::new(String::new(), default_plex_status_mapping())
+            .with_rating_scales(RatingScales { show: Some(5), ..Default::default() });
+        let rated = |value: f64, media_type: MediaType| {
+            let mut rating = Rating {
+                imdb_id: "tt0903747".to_string(),
+                ids: None,
+                rating: 0,
+                rating_f64: None,
+                date_added: Rating::UNDATED,
+                media_type,
+                source: media_sync_models::RatingSource::Plex,
+                inferred: false,
+            };
+            rating.set_value(value);
+            rating
+        };
+
+        // Collected, normalized to 1-10 for resolution, then denormalized to be sent back
+        for (stars, media_type, normalized) in [(4.5, MediaType::Show, 9.0), (7.5, MediaType::Movie, 7.5)] {
+            let mut rating = rated(stars, media_type.clone());
+            rating.set_value(plex.normalize_rating_f64(rating.value(), 10, &media_type));
+            assert_eq!(rating.value(), normalized);
+            rating.set_value(plex.denormalize_rating_f64(rating.value(), 10, &media_type));
+            assert_eq!(rating.value(), stars);
+        }
+        // The integer path rounds 4.5 stars up to 5 on the way in
+        assert_eq!(plex.normalize_rating(rated(4.5, MediaType::Show).rating as f64, 10, &MediaType::Show), 10);
+    }
+
+    #[test]
+    fn test_label_status_uses_first_mapped_label() {
+        let plex = PlexClient::new(String::new(), default_plex_status_mapping())
+            .with_label_status_map(HashMap::from([
+                ("Watchlist".to_string(), NormalizedStatus::Watchlist),
+                ("Gave Up".to_string(), NormalizedStatus::Dropped),
+            ]));
+        let labels = |names: &[&str]| names.iter().map(|name| name.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(plex.label_status(&labels(&["4K", "watchlist"])), Some(NormalizedStatus::Watchlist));
+        assert_eq!(plex.label_status(&labels(&["gave up", "Watchlist"])), Some(NormalizedStatus::Dropped));
+        assert_eq!(plex.label_status(&labels(&["4K"])), None);
+    }
+
+    #[test]
+    fn test_history_watermark_moves_only_once_the_fetch_is_committed() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut plex = PlexClient::new(String::new(), default_plex_status_mapping());
+        plex.watermarks_path = dir.path().join("watermarks.json");
+        let fetched = Utc::now();
+
+        *plex.pending_history_watermark.lock().unwrap() = Some(("http://home:32400".to_string(), fetched));
+        plex.commit_fetch(DataType::Ratings);
+        assert_eq!(plex.watermarks("http://home:32400", DataType::WatchHistory).history, None);
+
+        plex.commit_fetch(DataType::WatchHistory);
+        assert_eq!(plex.watermarks("http://home:32400", DataType::WatchHistory).history, Some(fetched));
+        assert!(plex.pending_history_watermark.lock().unwrap().is_none());
+
+        // Nothing cached to merge plays since the watermark into, so the next fetch is full
+        plex.set_cached(DataType::WatchHistory, false);
+        assert_eq!(plex.watermarks("http://home:32400", DataType::WatchHistory).history, None);
+        plex.set_cached(DataType::WatchHistory, true);
+        assert_eq!(plex.watermarks("http://home:32400", DataType::WatchHistory).history, Some(fetched));
+    }
+
+    #[test]
+    fn test_ratings_watermarks_move_only_once_the_fetch_is_committed() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut plex = PlexClient::new(String::new(), default_plex_status_mapping());
+        plex.watermarks_path = dir.path().join("watermarks.json");
+        let fetched = Utc::now();
+        let pending = |full| PendingRatingsWatermark {
+            server_url: "http://home:32400".to_string(),
+            fetch_started: fetched,
+            libraries: vec!["1".to_string(), "2".to_string()],
+            full,
+        };
+
+        *plex.pending_ratings_watermark.lock().unwrap() = Some(pending(true));
+        plex.commit_fetch(DataType::WatchHistory);
+        assert!(plex.watermarks("http://home:32400", DataType::Ratings).libraries.is_empty());
+
+        plex.commit_fetch(DataType::Ratings);
+        let committed = plex.watermarks("http://home:32400", DataType::Ratings);
+        assert_eq!((committed.libraries["1"], committed.libraries["2"]), (fetched, fetched));
+        assert_eq!(committed.ratings_full, Some(fetched));
+
+        // A changes-only fetch leaves the last full fetch where it was
+        *plex.pending_ratings_watermark.lock().unwrap() = Some(PendingRatingsWatermark {
+            fetch_started: fetched + chrono::Duration::hours(2),
+            ..pending(false)
+        });
+        plex.commit_fetch(DataType::Ratings);
+        let committed = plex.watermarks("http://home:32400", DataType::Ratings);
+        assert_eq!(committed.libraries["1"], fetched + chrono::Duration::hours(2));
+        assert_eq!(committed.ratings_full, Some(fetched));
+
+        plex.set_force_full_sync(true);
+        assert!(plex.watermarks("http://home:32400", DataType::Ratings).libraries.is_empty());
+    }
 }
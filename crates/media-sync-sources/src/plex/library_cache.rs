@@ -0,0 +1,78 @@
+//! Per-run cache of Plex library contents.
+//!
+//! Resolving a rating_key or IMDB ID can mean scanning a whole library. Each library is
+//! fetched at most once per run, and concurrent lookups for the same uncached library
+//! wait on the fetch already in flight instead of starting their own. A failed fetch is
+//! not cached, so the next lookup tries again.
+
+use anyhow::Result;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use tokio::sync::OnceCell;
+
+pub struct LibraryCache<T> {
+    libraries: Mutex<HashMap<String, Arc<OnceCell<Vec<T>>>>>,
+}
+
+impl<T: Clone> LibraryCache<T> {
+    pub fn new() -> Self {
+        Self {
+            libraries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The library's items, calling `fetch` only if no other lookup has fetched or is fetching them
+    pub async fn get_or_fetch<F, Fut>(&self, library_key: &str, fetch: F) -> Result<Vec<T>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<Vec<T>>>,
+    {
+        let cell = self.libraries.lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .entry(library_key.to_string())
+            .or_default()
+            .clone();
+        cell.get_or_try_init(fetch).await.cloned()
+    }
+}
+
+impl<T: Clone> Default for LibraryCache<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn test_concurrent_lookups_share_one_fetch() {
+        let cache = LibraryCache::new();
+        let fetches = AtomicUsize::new(0);
+        let fetch = || async {
+            fetches.fetch_add(1, Ordering::SeqCst);
+            // Stay in flight while the other lookups arrive
+            for _ in 0..10 {
+                tokio::task::yield_now().await;
+            }
+            Ok(vec!["tt0133093".to_string()])
+        };
+
+        let lookups = (0..8).map(|_| cache.get_or_fetch("1", fetch));
+        let results = futures::future::join_all(lookups).await;
+
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+        assert!(results.iter().all(|items| items.as_ref().unwrap() == &["tt0133093"]));
+    }
+
+    #[tokio::test]
+    async fn test_failed_fetch_is_retried() {
+        let cache: LibraryCache<String> = LibraryCache::new();
+        assert!(cache.get_or_fetch("1", || async { Err(anyhow::anyhow!("timed out")) }).await.is_err());
+        let items = cache.get_or_fetch("1", || async { Ok(vec!["tt0068646".to_string()]) }).await.unwrap();
+        assert_eq!(items, vec!["tt0068646".to_string()]);
+    }
+}
@@ -1,6 +1,10 @@
 pub mod client;
 pub mod auth;
 pub mod api;
+pub mod webhook;
+mod library_cache;
+mod watermarks;
 
 pub use client::PlexClient;
+pub use webhook::{PlexWebhookPayload, WebhookEvent};
 
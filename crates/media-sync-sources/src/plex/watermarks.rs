@@ -0,0 +1,78 @@
+//! Where the last incremental fetch from each Plex server left off.
+//!
+//! Kept in the collect cache (`collect/plex/watermarks.json`), by server URL since a
+//! library key or a play only means something on its own server. Clearing the cache drops them along
+//! with the data they were merged into, so the next fetch is full.
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use tracing::warn;
+
+/// Watermarks of one server
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ServerWatermarks {
+    /// Start of the last play history fetch (`viewedAt` filter)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub history: Option<DateTime<Utc>>,
+    /// Start of the last ratings fetch, by library key (`lastRatedAt` filter)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub libraries: HashMap<String, DateTime<Utc>>,
+    /// Start of the last ratings fetch that read every library in full
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ratings_full: Option<DateTime<Utc>>,
+}
+
+/// The watermarks of every server in `path`; none when the file is missing or unreadable
+pub fn load(path: &Path) -> HashMap<String, ServerWatermarks> {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return HashMap::new();
+    };
+    serde_json::from_str(&content).unwrap_or_else(|e| {
+        warn!("Ignoring unreadable Plex watermarks {} ({}), the next fetch is a full one", path.display(), e);
+        HashMap::new()
+    })
+}
+
+/// Change the watermarks of `server_url` in `path`
+pub fn update(path: &Path, server_url: &str, change: impl FnOnce(&mut ServerWatermarks)) -> Result<()> {
+    let mut servers = load(path);
+    change(servers.entry(server_url.to_string()).or_default());
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_string_pretty(&servers)
+        .map_err(|e| anyhow!("Failed to serialize Plex watermarks: {}", e))?;
+    std::fs::write(path, json)
+        .map_err(|e| anyhow!("Failed to write Plex watermarks {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_watermarks_are_kept_per_server() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("plex").join("watermarks.json");
+        let fetched = Utc.with_ymd_and_hms(2024, 6, 1, 12, 0, 0).unwrap();
+
+        assert!(load(&path).is_empty());
+        update(&path, "http://home:32400", |server| {
+            server.history = Some(fetched);
+            server.libraries.insert("1".to_string(), fetched);
+        }).unwrap();
+        update(&path, "http://cabin:32400", |server| {
+            server.libraries.insert("1".to_string(), fetched - chrono::Duration::days(3));
+        }).unwrap();
+
+        let servers = load(&path);
+        assert_eq!(servers["http://home:32400"].history, Some(fetched));
+        assert_eq!(servers["http://home:32400"].libraries["1"], fetched);
+        assert_eq!(servers["http://cabin:32400"].history, None);
+        assert_eq!(servers["http://cabin:32400"].libraries["1"], fetched - chrono::Duration::days(3));
+    }
+}
@@ -0,0 +1,302 @@
+//! Plex webhook event model.
+//!
+//! Plex Media Server (with Plex Pass) POSTs a `multipart/form-data` request for
+//! playback and library events. The JSON document in its `payload` part looks like:
+//!
+//! ```json
+//! {
+//!   "event": "media.scrobble",
+//!   "user": true,
+//!   "owner": true,
+//!   "rating": 8,
+//!   "Account": { "id": 1, "title": "username" },
+//!   "Server": { "title": "My Server", "uuid": "..." },
+//!   "Player": { "local": true, "title": "Living Room TV", "uuid": "..." },
+//!   "Metadata": {
+//!     "ratingKey": "12345",
+//!     "type": "episode",
+//!     "title": "Pilot",
+//!     "grandparentTitle": "Some Show",
+//!     "parentIndex": 1,
+//!     "index": 1,
+//!     "year": 2008,
+//!     "lastViewedAt": 1700000000,
+//!     "lastRatedAt": 1700000000,
+//!     "userRating": 8.0,
+//!     "viewOffset": 1260000,
+//!     "duration": 2700000,
+//!     "Guid": [{ "id": "imdb://tt0959621" }, { "id": "tmdb://62085" }, { "id": "tvdb://349232" }]
+//!   }
+//! }
+//! ```
+//!
+//! Only the fields above are read; everything else in the payload is ignored.
+//! `media.scrobble` (fired once playback passes ~90%) becomes a [`WatchHistory`]
+//! entry and `media.rate` becomes a [`Rating`]. A `media.stop` or `media.pause`
+//! partway through a movie or episode (`viewOffset` / `duration`, in ms) becomes
+//! a partial play: a [`WatchHistory`] with `watched_percent` set, which is never
+//! written as watched. `media.play`, `media.resume` and other stops are ignored,
+//! as is a `media.rate` with a rating of 0 or -1 (rating removed).
+
+use chrono::{DateTime, TimeZone, Utc};
+use media_sync_models::{MediaType, Rating, RatingSource, WatchHistory};
+use serde::Deserialize;
+
+use crate::plex::api::Guid;
+use crate::plex::PlexClient;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PlexWebhookPayload {
+    pub event: String,
+    /// Top-level rating sent with `media.rate` (0-10, -1 when the rating is removed)
+    #[serde(default)]
+    pub rating: Option<f64>,
+    #[serde(rename = "Metadata")]
+    pub metadata: Option<PlexWebhookMetadata>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlexWebhookMetadata {
+    #[serde(default)]
+    pub rating_key: Option<String>,
+    #[serde(rename = "type")]
+    pub type_: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub grandparent_title: Option<String>,
+    #[serde(default)]
+    pub parent_index: Option<u32>,
+    #[serde(default)]
+    pub index: Option<u32>,
+    #[serde(default)]
+    pub year: Option<u32>,
+    #[serde(default)]
+    pub last_viewed_at: Option<i64>,
+    #[serde(default)]
+    pub last_rated_at: Option<i64>,
+    #[serde(default)]
+    pub user_rating: Option<f64>,
+    /// Playback position in milliseconds
+    #[serde(default)]
+    pub view_offset: Option<u64>,
+    /// Runtime in milliseconds
+    #[serde(default)]
+    pub duration: Option<u64>,
+    #[serde(rename = "Guid", default)]
+    pub guids: Vec<PlexWebhookGuid>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PlexWebhookGuid {
+    pub id: String,
+}
+
+/// Result of translating a webhook payload
+#[derive(Debug, Clone, PartialEq)]
+pub enum WebhookEvent {
+    Watched(Box<WatchHistory>),
+    Rated(Box<Rating>),
+    /// Event that carries nothing to sync (playback state, unrate, unsupported type)
+    Ignored(String),
+}
+
+impl PlexWebhookPayload {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Translate the payload into a sync event, using `now` when Plex omits a timestamp
+    pub fn into_event(self, now: DateTime<Utc>) -> WebhookEvent {
+        let metadata = match self.metadata {
+            Some(metadata) => metadata,
+            None => return WebhookEvent::Ignored(format!("{} has no Metadata", self.event)),
+        };
+
+        let media_type = match metadata.type_.as_str() {
+            "movie" => MediaType::Movie,
+            "episode" => match (metadata.parent_index, metadata.index) {
+                (Some(season), Some(episode)) => MediaType::Episode { season, episode },
+                _ => return WebhookEvent::Ignored("episode without season/episode numbers".to_string()),
+            },
+            "show" => MediaType::Show,
+            other => return WebhookEvent::Ignored(format!("unsupported media type '{}'", other)),
+        };
+
+        let guids: Vec<Guid> = metadata.guids.iter().map(|g| Guid { id: g.id.clone() }).collect();
+        let mut ids = PlexClient::extract_ids_from_guids(&guids);
+        ids.plex_rating_key = metadata.rating_key.clone();
+        let imdb_id = ids.imdb_id.clone().unwrap_or_default();
+        let ids = if ids.is_empty() && ids.plex_rating_key.is_none() { None } else { Some(ids) };
+
+        let title = match media_type {
+            MediaType::Episode { .. } => metadata.grandparent_title.or(metadata.title),
+            _ => metadata.title,
+        };
+
+        match self.event.as_str() {
+            "media.scrobble" => {
+                WebhookEvent::Watched(Box::new(WatchHistory {
+                    imdb_id,
+                    ids,
+                    title,
+                    year: metadata.year,
+                    watched_at: timestamp_or(metadata.last_viewed_at, now),
+                    media_type,
+                    source: "plex".to_string(),
+                    watched_percent: None,
+                    show_ids: None,
+                }))
+            }
+            "media.rate" => {
+                let rating = self.rating.or(metadata.user_rating).unwrap_or(0.0);
+                if rating <= 0.0 {
+                    return WebhookEvent::Ignored("rating removed".to_string());
+                }
+                let mut rated = Rating {
+                    imdb_id,
+                    ids,
+                    rating: 0,
+                    rating_f64: None,
+                    date_added: timestamp_or(metadata.last_rated_at, now),
+                    media_type,
+                    source: RatingSource::Plex,
+                    inferred: false,
+                };
+                rated.set_value(rating.clamp(1.0, 10.0));
+                WebhookEvent::Rated(Box::new(rated))
+            }
+            "media.stop" | "media.pause" if !matches!(media_type, MediaType::Show) => {
+                let percent = match (metadata.view_offset, metadata.duration) {
+                    (Some(offset), Some(duration)) if duration > 0 => offset as f32 / duration as f32 * 100.0,
+                    _ => return WebhookEvent::Ignored(format!("{} without playback position", self.event)),
+                };
+                if percent <= 0.0 || percent >= 100.0 {
+                    return WebhookEvent::Ignored(format!("{} at {:.0}% is not a partial play", self.event, percent));
+                }
+                WebhookEvent::Watched(Box::new(WatchHistory {
+                    imdb_id,
+                    ids,
+                    title,
+                    year: metadata.year,
+                    watched_at: now,
+                    media_type,
+                    source: "plex".to_string(),
+                    watched_percent: Some(percent),
+                    show_ids: None,
+                }))
+            }
+            other => WebhookEvent::Ignored(format!("{} does not change watch history or ratings", other)),
+        }
+    }
+}
+
+fn timestamp_or(seconds: Option<i64>, fallback: DateTime<Utc>) -> DateTime<Utc> {
+    seconds
+        .and_then(|s| Utc.timestamp_opt(s, 0).single())
+        .unwrap_or(fallback)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        Utc.timestamp_opt(1_800_000_000, 0).single().unwrap()
+    }
+
+    #[test]
+    fn test_scrobble_episode_to_watch_history() {
+        let json = r#"{
+            "event": "media.scrobble",
+            "user": true,
+            "Metadata": {
+                "ratingKey": "12345",
+                "type": "episode",
+                "title": "Pilot",
+                "grandparentTitle": "Some Show",
+                "parentIndex": 1,
+                "index": 2,
+                "lastViewedAt": 1700000000,
+                "Guid": [{ "id": "imdb://tt0959621" }, { "id": "tvdb://349232" }]
+            }
+        }"#;
+        let event = PlexWebhookPayload::from_json(json).unwrap().into_event(now());
+        match event {
+            WebhookEvent::Watched(history) => {
+                assert_eq!(history.imdb_id, "tt0959621");
+                assert_eq!(history.media_type, MediaType::Episode { season: 1, episode: 2 });
+                assert_eq!(history.title.as_deref(), Some("Some Show"));
+                assert_eq!(history.watched_at.timestamp(), 1_700_000_000);
+                let ids = history.ids.unwrap();
+                assert_eq!(ids.tvdb_id, Some(349232));
+                assert_eq!(ids.plex_rating_key.as_deref(), Some("12345"));
+            }
+            other => panic!("expected Watched, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_rate_movie_to_rating() {
+        let json = r#"{
+            "event": "media.rate",
+            "rating": 7.6,
+            "Metadata": {
+                "ratingKey": "99",
+                "type": "movie",
+                "title": "Heat",
+                "year": 1995,
+                "Guid": [{ "id": "imdb://tt0113277" }]
+            }
+        }"#;
+        let event = PlexWebhookPayload::from_json(json).unwrap().into_event(now());
+        match event {
+            WebhookEvent::Rated(rating) => {
+                assert_eq!(rating.imdb_id, "tt0113277");
+                assert_eq!(rating.rating, 8);
+                assert_eq!(rating.media_type, MediaType::Movie);
+                assert_eq!(rating.date_added, now());
+                assert_eq!(rating.source, RatingSource::Plex);
+            }
+            other => panic!("expected Rated, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_playback_and_unrate_events_are_ignored() {
+        for event in ["media.play", "media.pause", "media.resume", "media.stop"] {
+            let json = format!(r#"{{ "event": "{}", "Metadata": {{ "type": "movie", "title": "Heat" }} }}"#, event);
+            let payload = PlexWebhookPayload::from_json(&json).unwrap();
+            assert!(matches!(payload.into_event(now()), WebhookEvent::Ignored(_)), "{} should be ignored", event);
+        }
+
+        let json = r#"{ "event": "media.rate", "rating": -1, "Metadata": { "type": "movie", "title": "Heat" } }"#;
+        let payload = PlexWebhookPayload::from_json(json).unwrap();
+        assert!(matches!(payload.into_event(now()), WebhookEvent::Ignored(_)));
+    }
+
+    #[test]
+    fn test_stop_partway_is_partial_play() {
+        let json = r#"{
+            "event": "media.stop",
+            "Metadata": {
+                "type": "movie",
+                "title": "Heat",
+                "viewOffset": 2550000,
+                "duration": 10200000,
+                "Guid": [{ "id": "imdb://tt0113277" }]
+            }
+        }"#;
+        let event = PlexWebhookPayload::from_json(json).unwrap().into_event(now());
+        match event {
+            WebhookEvent::Watched(history) => {
+                assert_eq!(history.imdb_id, "tt0113277");
+                assert_eq!(history.watched_percent, Some(25.0));
+                assert!(history.is_partial());
+                assert_eq!(history.watched_at, now());
+            }
+            other => panic!("expected partial Watched, got {:?}", other),
+        }
+    }
+}
@@ -1,6 +1,12 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 use tracing::{info, warn};
 
+/// Receives a tracker's progress as (operation name, items done, total), so a progress
+/// UI can show what sources are doing inside a phase (see
+/// `CapabilityRegistry::set_progress_observer`)
+pub type ProgressObserver = Arc<dyn Fn(&str, usize, usize) + Send + Sync>;
+
 /// Progress tracker for operations that process multiple items
 /// Provides periodic progress updates and final summaries to reduce log noise
 pub struct ProgressTracker {
@@ -14,6 +20,7 @@ pub struct ProgressTracker {
     last_progress_log: usize,
     error_counts: HashMap<String, usize>, // Track errors by category
     operation_name: Option<String>, // Optional operation name for context in logs
+    observer: Option<ProgressObserver>, // Also told about every item, for progress UIs
 }
 
 impl ProgressTracker {
@@ -53,9 +60,16 @@ impl ProgressTracker {
             last_progress_log: 0,
             error_counts: HashMap::new(),
             operation_name,
+            observer: None,
         }
     }
 
+    /// Also report the progress of every item to `observer`, when there is one
+    pub fn with_observer(mut self, observer: Option<ProgressObserver>) -> Self {
+        self.observer = observer;
+        self
+    }
+
     /// Record that an item was successfully added
     pub fn record_added(&mut self) {
         self.added += 1;
@@ -89,6 +103,9 @@ impl ProgressTracker {
     /// # Arguments
     /// * `current` - Current item index (1-based, e.g., idx + 1 from enumerate)
     pub fn log_progress(&mut self, current: usize) {
+        if let Some(observer) = &self.observer {
+            observer(self.operation_name.as_deref().unwrap_or("Processing"), current, self.total);
+        }
         if current - self.last_progress_log >= self.progress_interval || current == self.total {
             let elapsed = self.start_time.elapsed();
             let rate = if elapsed.as_secs_f64() > 0.0 {
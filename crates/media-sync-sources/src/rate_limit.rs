@@ -0,0 +1,107 @@
+//! Pacing of outbound requests.
+//!
+//! A [`RateLimiter`] is a token bucket: it holds up to `burst` tokens, refilled at
+//! `requests_per_second`, and each request takes one, waiting for it when the bucket is
+//! empty. Clones share the bucket, so every request of a client draws from the same one.
+//! An unconfigured limiter never waits.
+
+use media_sync_config::RateLimitConfig;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+#[derive(Debug, Clone, Default)]
+pub struct RateLimiter {
+    /// None when unlimited
+    bucket: Option<Arc<Mutex<Bucket>>>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    requests_per_second: f64,
+    burst: f64,
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl From<&RateLimitConfig> for RateLimiter {
+    fn from(config: &RateLimitConfig) -> Self {
+        match config.requests_per_second {
+            Some(requests_per_second) => Self::new(requests_per_second, config.burst),
+            None => Self::unlimited(),
+        }
+    }
+}
+
+impl RateLimiter {
+    /// Start with a full bucket of `burst` tokens
+    pub fn new(requests_per_second: f64, burst: u32) -> Self {
+        if !(requests_per_second > 0.0 && requests_per_second.is_finite()) {
+            return Self::unlimited();
+        }
+        let burst = burst.max(1) as f64;
+        Self {
+            bucket: Some(Arc::new(Mutex::new(Bucket {
+                requests_per_second,
+                burst,
+                tokens: burst,
+                refilled_at: Instant::now(),
+            }))),
+        }
+    }
+
+    pub fn unlimited() -> Self {
+        Self { bucket: None }
+    }
+
+    /// Wait until a request may go out. Waiters are served in order, since the next one
+    /// only gets the bucket once the current one has its token.
+    pub async fn acquire(&self) {
+        let Some(bucket) = &self.bucket else {
+            return;
+        };
+        let mut bucket = bucket.lock().await;
+        bucket.refill();
+        if bucket.tokens < 1.0 {
+            let wait = Duration::from_secs_f64((1.0 - bucket.tokens) / bucket.requests_per_second);
+            tokio::time::sleep(wait).await;
+            bucket.refill();
+        }
+        bucket.tokens = (bucket.tokens - 1.0).max(0.0);
+    }
+}
+
+impl Bucket {
+    fn refill(&mut self) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.refilled_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.requests_per_second).min(self.burst);
+        self.refilled_at = now;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_requests_are_paced_after_the_burst() {
+        let limiter = RateLimiter::new(5.0, 5);
+        let start = std::time::Instant::now();
+        for _ in 0..20 {
+            limiter.clone().acquire().await;
+        }
+        // The first 5 go out at once, the other 15 at 5 per second
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(2_900), "{:?}", elapsed);
+        assert!(elapsed < Duration::from_secs(5), "{:?}", elapsed);
+
+        let unlimited = RateLimiter::from(&RateLimitConfig::default());
+        let start = std::time::Instant::now();
+        for _ in 0..100 {
+            unlimited.acquire().await;
+        }
+        assert!(start.elapsed() < Duration::from_millis(100));
+    }
+}
@@ -0,0 +1,213 @@
+//! Retries of HTTP requests that fail transiently.
+//!
+//! A [`RetryPolicy`] sends a request again when the server answers 429 or 5xx, or the
+//! connection fails, waiting longer before each attempt: `base_delay`, then twice that,
+//! and so on, varied by `jitter`. A `Retry-After` header on the response is waited out
+//! instead. Other failures, such as 401 for an expired token, are returned at once.
+//!
+//! Writes that must not be applied twice, such as plays added to a history, go through
+//! [`RetryPolicy::send_non_idempotent`]. A 5xx or a dropped connection may come after the
+//! server applied the write, so those are retried only when the request never reached
+//! it: the connection couldn't be made, or the rate limiter turned it away with 429.
+//!
+//! Every attempt, the first included, first waits for the policy's [`RateLimiter`], so
+//! the writes of a client are paced by it.
+
+use crate::rate_limit::RateLimiter;
+use media_sync_config::RetryConfig;
+use reqwest::{RequestBuilder, Response, StatusCode};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+use tracing::warn;
+
+/// Longest `Retry-After` that is waited out; a server asking for more gets this
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(120);
+
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Attempts per request, the first included
+    max_attempts: u32,
+    base_delay: Duration,
+    /// Random variation of each delay, as a fraction of it
+    jitter: f64,
+    rate_limiter: RateLimiter,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::from(&RetryConfig::default())
+    }
+}
+
+impl From<&RetryConfig> for RetryPolicy {
+    fn from(config: &RetryConfig) -> Self {
+        Self::new(config.max_attempts, Duration::from_millis(config.base_delay_ms)).with_jitter(config.jitter)
+    }
+}
+
+impl RetryPolicy {
+    pub fn new(max_attempts: u32, base_delay: Duration) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            base_delay,
+            jitter: 0.0,
+            rate_limiter: RateLimiter::unlimited(),
+        }
+    }
+
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = rate_limiter;
+        self
+    }
+
+    /// Send the request `build` makes, and a fresh one after each transient failure.
+    /// Returns the last response whatever its status once attempts run out, so callers
+    /// report it like any other failed response.
+    pub async fn send(&self, what: &str, build: impl Fn() -> RequestBuilder) -> reqwest::Result<Response> {
+        self.send_retrying(what, build, true).await
+    }
+
+    /// Like [`send`](Self::send), for writes that would be applied again if repeated:
+    /// only retried on connect errors and 429
+    pub async fn send_non_idempotent(&self, what: &str, build: impl Fn() -> RequestBuilder) -> reqwest::Result<Response> {
+        self.send_retrying(what, build, false).await
+    }
+
+    async fn send_retrying(&self, what: &str, build: impl Fn() -> RequestBuilder, idempotent: bool) -> reqwest::Result<Response> {
+        let mut attempt = 1;
+        loop {
+            self.rate_limiter.acquire().await;
+            let retry_after = match build().send().await {
+                Ok(response) if attempt < self.max_attempts
+                    && ((idempotent && is_retryable_status(response.status())) || response.status() == StatusCode::TOO_MANY_REQUESTS) =>
+                {
+                    warn!("{} failed with {} (attempt {}/{}), retrying", what, response.status(), attempt, self.max_attempts);
+                    retry_after(&response)
+                }
+                Err(e) if attempt < self.max_attempts && ((idempotent && is_retryable_error(&e)) || e.is_connect()) => {
+                    warn!("{} failed: {} (attempt {}/{}), retrying", what, e, attempt, self.max_attempts);
+                    None
+                }
+                result => return result,
+            };
+            tokio::time::sleep(retry_after.unwrap_or_else(|| self.backoff(attempt))).await;
+            attempt += 1;
+        }
+    }
+
+    /// Delay after the `attempt`th attempt failed
+    fn backoff(&self, attempt: u32) -> Duration {
+        let delay = self.base_delay.saturating_mul(1 << (attempt - 1).min(16));
+        if self.jitter == 0.0 {
+            return delay;
+        }
+        // A random factor in [1 - jitter, 1 + jitter]
+        let random = RandomState::new().build_hasher().finish() as f64 / u64::MAX as f64;
+        delay.mul_f64(1.0 + self.jitter * (2.0 * random - 1.0))
+    }
+}
+
+/// Rate limited or a server error
+pub fn is_retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// The connection failed, timed out or was dropped
+fn is_retryable_error(error: &reqwest::Error) -> bool {
+    if error.is_connect() || error.is_timeout() {
+        return true;
+    }
+    let mut source = std::error::Error::source(error);
+    while let Some(cause) = source {
+        if let Some(io) = cause.downcast_ref::<std::io::Error>() {
+            return matches!(
+                io.kind(),
+                std::io::ErrorKind::ConnectionReset
+                    | std::io::ErrorKind::ConnectionAborted
+                    | std::io::ErrorKind::BrokenPipe
+                    | std::io::ErrorKind::UnexpectedEof
+            );
+        }
+        source = cause.source();
+    }
+    false
+}
+
+/// `Retry-After` in seconds or as an HTTP date
+fn retry_after(response: &Response) -> Option<Duration> {
+    let value = response.headers().get(reqwest::header::RETRY_AFTER)?.to_str().ok()?.trim();
+    let delay = match value.parse::<u64>() {
+        Ok(seconds) => Duration::from_secs(seconds),
+        Err(_) => {
+            let at = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+            (at.with_timezone(&chrono::Utc) - chrono::Utc::now()).to_std().unwrap_or_default()
+        }
+    };
+    Some(delay.min(MAX_RETRY_AFTER))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{serve, MockRequest, MockResponse};
+
+    /// Answer one request per canned status line, in order
+    fn mock_server(statuses: Vec<&str>) -> (String, std::thread::JoinHandle<Vec<MockRequest>>) {
+        serve(statuses.into_iter().map(|status| MockResponse::new(status).with_header("Retry-After", "0")).collect())
+    }
+
+    #[tokio::test]
+    async fn test_transient_failures_are_retried_and_auth_failures_are_not() {
+        let client = reqwest::Client::new();
+        let policy = RetryPolicy::new(3, Duration::from_millis(1));
+
+        let (base, server) = mock_server(vec!["429 Too Many Requests", "503 Service Unavailable", "201 Created"]);
+        let response = policy.send("Test add", || client.post(format!("{}/sync", base))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(server.join().unwrap().len(), 3);
+
+        // The third 503 is returned once attempts run out
+        let (base, server) = mock_server(vec!["502 Bad Gateway", "503 Service Unavailable", "503 Service Unavailable"]);
+        let response = policy.send("Test add", || client.post(format!("{}/sync", base))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(server.join().unwrap().len(), 3);
+
+        let (base, server) = mock_server(vec!["401 Unauthorized"]);
+        let response = policy.send("Test add", || client.post(format!("{}/sync", base))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(server.join().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_non_idempotent_writes_are_retried_only_when_turned_away() {
+        let client = reqwest::Client::new();
+        let policy = RetryPolicy::new(3, Duration::from_millis(1));
+
+        let (base, server) = mock_server(vec!["429 Too Many Requests", "201 Created"]);
+        let response = policy.send_non_idempotent("Test history add", || client.post(format!("{}/sync/history", base))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(server.join().unwrap().len(), 2);
+
+        // The plays may have been added before the 502
+        let (base, server) = mock_server(vec!["502 Bad Gateway"]);
+        let response = policy.send_non_idempotent("Test history add", || client.post(format!("{}/sync/history", base))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_GATEWAY);
+        assert_eq!(server.join().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_backoff_doubles_within_jitter() {
+        let policy = RetryPolicy::new(5, Duration::from_millis(100));
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(3), Duration::from_millis(400));
+
+        let jittered = policy.with_jitter(0.2).backoff(2);
+        assert!(jittered >= Duration::from_millis(160) && jittered <= Duration::from_millis(240), "{:?}", jittered);
+    }
+}
@@ -1,13 +1,16 @@
 use anyhow::{anyhow, Result};
-use media_sync_models::{Rating, Review, WatchHistory, WatchlistItem, MediaType};
+use media_sync_models::{Rating, WatchHistory, WatchProgress, WatchlistItem, MediaType};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use crate::http_cache::HttpCache;
+use crate::retry::RetryPolicy;
+use crate::traits::DataType;
 
 // Simkl API base URL
 const API_BASE: &str = "https://api.simkl.com";
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct SimklIds {
     #[serde(rename = "imdb")]
     imdb: Option<String>,
@@ -15,21 +18,21 @@ struct SimklIds {
     simkl: Option<u64>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct SimklMovie {
     title: String,
     year: Option<u32>,
     ids: SimklIds,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct SimklShow {
     title: String,
     year: Option<u32>,
     ids: SimklIds,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct SimklWatchlistItem {
     #[serde(rename = "added_to_watchlist_at")]
     added_to_watchlist_at: Option<String>,
@@ -45,14 +48,14 @@ struct SimklWatchlistItem {
     anime: Option<SimklShow>, // Anime uses same structure as show
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct SimklAllItemsResponse {
     shows: Option<Vec<SimklWatchlistItem>>,
     anime: Option<Vec<SimklWatchlistItem>>,
     movies: Option<Vec<SimklWatchlistItem>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct SimklRatingItem {
     #[serde(rename = "last_watched_at")]
     last_watched_at: Option<String>,
@@ -66,17 +69,44 @@ struct SimklRatingItem {
     anime: Option<SimklShow>, // Anime uses same structure as show
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct SimklRatingsResponse {
     shows: Option<Vec<SimklRatingItem>>,
     anime: Option<Vec<SimklRatingItem>>,
     movies: Option<Vec<SimklRatingItem>>,
 }
 
+/// A show from `/sync/all-items/{type}` with `episode_watched_at=yes`
+#[derive(Debug, Clone, Deserialize)]
+struct SimklProgressItem {
+    show: Option<SimklShow>,
+    #[serde(default)]
+    seasons: Vec<SimklProgressSeason>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct SimklProgressSeason {
+    number: u32,
+    #[serde(default)]
+    episodes: Vec<SimklProgressEpisode>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct SimklProgressEpisode {
+    number: u32,
+    watched_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct SimklProgressResponse {
+    shows: Option<Vec<SimklProgressItem>>,
+    anime: Option<Vec<SimklProgressItem>>,
+}
+
 // History items use the same structure as watchlist items
 // They're identified by having last_watched_at field set
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SimklActivities {
     #[serde(rename = "all")]
     pub all: Option<String>,
@@ -86,13 +116,13 @@ pub struct SimklActivities {
     pub movies: Option<SimklMediaActivities>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SimklSettingsActivities {
     #[serde(rename = "all")]
     pub all: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SimklMediaActivities {
     #[serde(rename = "all")]
     pub all: Option<String>,
@@ -108,6 +138,53 @@ pub struct SimklMediaActivities {
     pub removed_from_list: Option<String>,
 }
 
+/// How much of a list to fetch, decided by comparing `/sync/activities` with the
+/// activities saved after the previous fetch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListFetch {
+    /// Nothing changed since the previous fetch
+    Unchanged,
+    /// Only items changed since this time (`date_from`)
+    Since(DateTime<Utc>),
+    Full,
+}
+
+impl SimklActivities {
+    fn media(&self) -> [Option<&SimklMediaActivities>; 3] {
+        [self.tv_shows.as_ref(), self.anime.as_ref(), self.movies.as_ref()]
+    }
+
+    /// Decide how to fetch `data_type` given the activities saved after its last fetch.
+    /// Removals can't be expressed by a `date_from` fetch, so a list something was
+    /// removed from is fetched in full.
+    pub fn plan_fetch(&self, saved: Option<&SimklActivities>, data_type: DataType) -> ListFetch {
+        let Some(saved) = saved else {
+            return ListFetch::Full;
+        };
+        let field: fn(&SimklMediaActivities) -> &Option<String> = match data_type {
+            DataType::Ratings => |m| &m.rated_at,
+            DataType::Watchlist | DataType::WatchHistory => |m| &m.all,
+            DataType::Reviews => return ListFetch::Full,
+        };
+        let changed = |field: fn(&SimklMediaActivities) -> &Option<String>| {
+            self.media().iter().zip(saved.media()).any(|(current, saved)| {
+                current.and_then(|m| field(m).as_ref()) != saved.and_then(|m| field(m).as_ref())
+            })
+        };
+
+        if !changed(field) {
+            return ListFetch::Unchanged;
+        }
+        if data_type != DataType::Ratings && changed(|m| &m.removed_from_list) {
+            return ListFetch::Full;
+        }
+        saved.all.as_deref()
+            .and_then(|all| DateTime::parse_from_rfc3339(all).ok())
+            .map(|since| ListFetch::Since(since.with_timezone(&Utc)))
+            .unwrap_or(ListFetch::Full)
+    }
+}
+
 /// Remove slashes from IMDB ID (if present)
 fn remove_slashes(s: Option<String>) -> String {
     s.unwrap_or_default().replace('/', "")
@@ -130,7 +207,17 @@ pub async fn get_activities(
     access_token: &str,
     client_id: &str,
 ) -> Result<SimklActivities> {
-    let url = format!("{}/sync/activities", API_BASE);
+    get_activities_at(client, API_BASE, access_token, client_id).await
+}
+
+/// Fetch `{api_base}/sync/activities`
+pub(crate) async fn get_activities_at(
+    client: &Client,
+    api_base: &str,
+    access_token: &str,
+    client_id: &str,
+) -> Result<SimklActivities> {
+    let url = format!("{}/sync/activities", api_base);
     
     let response = client
         .post(&url)
@@ -153,6 +240,7 @@ pub async fn get_activities(
 /// Fetch watchlist from Simkl
 pub async fn get_watchlist(
     client: &Client,
+    http_cache: &HttpCache,
     access_token: &str,
     client_id: &str,
     date_from: Option<DateTime<Utc>>,
@@ -164,22 +252,13 @@ pub async fn get_watchlist(
         url.push_str(&format!("?date_from={}", date.to_rfc3339()));
     }
 
-    let response = client
+    let request = client
         .get(&url)
         .header("Authorization", format!("Bearer {}", access_token))
         .header("simkl-api-key", client_id)
         .header("Accept", "application/json")
-        .header("Content-Type", "application/json")
-        .send()
-        .await?;
-
-    if !response.status().is_success() {
-        let status = response.status();
-        let error_text = response.text().await.unwrap_or_default();
-        return Err(anyhow!("Failed to fetch watchlist: {} - {}", status, error_text));
-    }
-
-    let response_data: SimklAllItemsResponse = response.json().await?;
+        .header("Content-Type", "application/json");
+    let response_data: SimklAllItemsResponse = http_cache.get_json("watchlist", request).await?;
 
     let mut watchlist = Vec::new();
 
@@ -221,6 +300,7 @@ pub async fn get_watchlist(
                     date_added,
                     source: "simkl".to_string(),
                     status: normalized_status,
+                    tags: Vec::new(),
                 });
             }
         }
@@ -264,6 +344,7 @@ pub async fn get_watchlist(
                     date_added,
                     source: "simkl".to_string(),
                     status: normalized_status,
+                    tags: Vec::new(),
                 });
             }
         }
@@ -307,6 +388,7 @@ pub async fn get_watchlist(
                     date_added,
                     source: "simkl".to_string(),
                     status: normalized_status,
+                    tags: Vec::new(),
                 });
             }
         }
@@ -369,15 +451,17 @@ pub async fn get_ratings(
                         item.user_rated_at.as_ref()
                             .and_then(|d| DateTime::parse_from_str(d, "%Y-%m-%d %H:%M:%S").ok().map(|dt| dt.with_timezone(&Utc)))
                     })
-                    .unwrap_or_else(|| Utc::now());
+                    .unwrap_or(Rating::UNDATED);
 
                 ratings.push(Rating {
                     imdb_id,
                     ids: Some(media_ids),
                     rating: item.user_rating,
+                    rating_f64: None,
                     date_added,
                     media_type: MediaType::Show,
                     source: media_sync_models::RatingSource::Trakt, // Simkl uses same 1-10 scale
+                    inferred: false,
                 });
             }
         }
@@ -404,15 +488,17 @@ pub async fn get_ratings(
                         item.user_rated_at.as_ref()
                             .and_then(|d| DateTime::parse_from_str(d, "%Y-%m-%d %H:%M:%S").ok().map(|dt| dt.with_timezone(&Utc)))
                     })
-                    .unwrap_or_else(|| Utc::now());
+                    .unwrap_or(Rating::UNDATED);
 
                 ratings.push(Rating {
                     imdb_id,
                     ids: Some(media_ids),
                     rating: item.user_rating,
+                    rating_f64: None,
                     date_added,
                     media_type: MediaType::Show,
                     source: media_sync_models::RatingSource::Trakt,
+                    inferred: false,
                 });
             }
         }
@@ -439,15 +525,17 @@ pub async fn get_ratings(
                         item.user_rated_at.as_ref()
                             .and_then(|d| DateTime::parse_from_str(d, "%Y-%m-%d %H:%M:%S").ok().map(|dt| dt.with_timezone(&Utc)))
                     })
-                    .unwrap_or_else(|| Utc::now());
+                    .unwrap_or(Rating::UNDATED);
 
                 ratings.push(Rating {
                     imdb_id,
                     ids: Some(media_ids),
                     rating: item.user_rating,
+                    rating_f64: None,
                     date_added,
                     media_type: MediaType::Movie,
                     source: media_sync_models::RatingSource::Trakt,
+                    inferred: false,
                 });
             }
         }
@@ -456,20 +544,10 @@ pub async fn get_ratings(
     Ok(ratings)
 }
 
-/// Fetch reviews from Simkl
-pub async fn get_reviews(
-    _client: &Client,
-    _access_token: &str,
-    _client_id: &str,
-) -> Result<Vec<Review>> {
-    // Simkl may not have a reviews/comments API endpoint
-    // Return empty for now - can be implemented if Simkl adds this feature
-    Ok(Vec::new())
-}
-
 /// Fetch watch history from Simkl
 pub async fn get_watch_history(
     client: &Client,
+    http_cache: &HttpCache,
     access_token: &str,
     client_id: &str,
     date_from: Option<DateTime<Utc>>,
@@ -481,22 +559,13 @@ pub async fn get_watch_history(
         url.push_str(&format!("?date_from={}", date.to_rfc3339()));
     }
 
-    let response = client
+    let request = client
         .get(&url)
         .header("Authorization", format!("Bearer {}", access_token))
         .header("simkl-api-key", client_id)
         .header("Accept", "application/json")
-        .header("Content-Type", "application/json")
-        .send()
-        .await?;
-
-    if !response.status().is_success() {
-        let status = response.status();
-        let error_text = response.text().await.unwrap_or_default();
-        return Err(anyhow!("Failed to fetch watch history: {} - {}", status, error_text));
-    }
-
-    let response_data: SimklAllItemsResponse = response.json().await?;
+        .header("Content-Type", "application/json");
+    let response_data: SimklAllItemsResponse = http_cache.get_json("watch history", request).await?;
 
     let mut history = Vec::new();
 
@@ -531,6 +600,8 @@ pub async fn get_watch_history(
                         watched_at,
                         media_type: MediaType::Show,
                         source: "simkl".to_string(),
+                        watched_percent: None,
+                        show_ids: None,
                     });
                 }
             }
@@ -568,6 +639,8 @@ pub async fn get_watch_history(
                         watched_at,
                         media_type: MediaType::Show,
                         source: "simkl".to_string(),
+                        watched_percent: None,
+                        show_ids: None,
                     });
                 }
             }
@@ -605,6 +678,8 @@ pub async fn get_watch_history(
                         watched_at,
                         media_type: MediaType::Movie,
                         source: "simkl".to_string(),
+                        watched_percent: None,
+                        show_ids: None,
                     });
                 }
             }
@@ -614,6 +689,142 @@ pub async fn get_watch_history(
     Ok(history)
 }
 
+/// Fetch the last watched episode of every show and anime with watched episodes
+pub async fn get_watch_progress(
+    client: &Client,
+    http_cache: &HttpCache,
+    access_token: &str,
+    client_id: &str,
+) -> Result<Vec<WatchProgress>> {
+    Ok(show_progress(get_progress_items(client, http_cache, access_token, client_id).await?))
+}
+
+async fn get_progress_items(
+    client: &Client,
+    http_cache: &HttpCache,
+    access_token: &str,
+    client_id: &str,
+) -> Result<Vec<SimklProgressItem>> {
+    let mut progress = Vec::new();
+    for kind in ["shows", "anime"] {
+        let request = client
+            .get(format!("{}/sync/all-items/{}/?extended=full&episode_watched_at=yes", API_BASE, kind))
+            .header("Authorization", format!("Bearer {}", access_token))
+            .header("simkl-api-key", client_id)
+            .header("Accept", "application/json")
+            .header("Content-Type", "application/json");
+
+        // Simkl answers `null` when the list is empty
+        let response_data: Option<SimklProgressResponse> = http_cache.get_json(&format!("{} progress", kind), request).await?;
+        let items = response_data
+            .and_then(|data| data.shows.or(data.anime))
+            .unwrap_or_default();
+        progress.extend(items);
+    }
+    Ok(progress)
+}
+
+/// Each show's IDs with the `(season, episode)`s watched, dated or not
+fn watched_episodes(items: &[SimklProgressItem]) -> Vec<(media_sync_models::MediaIds, std::collections::HashSet<(u32, u32)>)> {
+    items.iter()
+        .filter_map(|item| {
+            let show = item.show.as_ref()?;
+            let episodes = item.seasons.iter()
+                .flat_map(|season| season.episodes.iter().map(|episode| (season.number, episode.number)))
+                .collect();
+            Some((extract_media_ids_from_simkl_ids(&show.ids), episodes))
+        })
+        .collect()
+}
+
+fn show_progress(items: Vec<SimklProgressItem>) -> Vec<WatchProgress> {
+    items.into_iter()
+        .filter_map(|item| {
+            let show = item.show?;
+            let episodes = item.seasons.iter().flat_map(|season| {
+                season.episodes.iter().filter_map(|episode| {
+                    let watched_at = DateTime::parse_from_rfc3339(episode.watched_at.as_deref()?).ok()?;
+                    Some((season.number, episode.number, watched_at.with_timezone(&Utc)))
+                })
+            });
+            let (season, episode, watched_at) = media_sync_models::last_watched_episode(episodes)?;
+            let media_ids = extract_media_ids_from_simkl_ids(&show.ids);
+            if media_ids.is_empty() {
+                return None;
+            }
+            Some(WatchProgress {
+                imdb_id: remove_slashes(show.ids.imdb.clone()),
+                ids: Some(media_ids),
+                title: Some(show.title),
+                year: show.year,
+                last_watched_season: season,
+                last_watched_episode: episode,
+                watched_at,
+                source: "simkl".to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Mark each show's last watched episode as watched at its `watched_at`, unless it is
+/// already watched: POST /sync/history adds a play every time, not just progress
+pub async fn set_watch_progress(
+    client: &Client,
+    http_cache: &HttpCache,
+    access_token: &str,
+    client_id: &str,
+    progress: &[WatchProgress],
+    retry: &RetryPolicy,
+) -> Result<()> {
+    let watched = watched_episodes(&get_progress_items(client, http_cache, access_token, client_id).await?);
+    let unwatched = media_sync_models::unwatched_progress(progress, &watched);
+    if unwatched.len() < progress.len() {
+        tracing::debug!("Skipping {} Simkl shows whose episode is already watched", progress.len() - unwatched.len());
+    }
+    let shows: Vec<serde_json::Value> = unwatched.iter()
+        .filter_map(|show| {
+            let ids = history_ids(&show.imdb_id, &show.ids);
+            if ids.is_empty() {
+                tracing::warn!("Skipping Simkl show progress without any usable ID: {:?}", show.title);
+                return None;
+            }
+            Some(serde_json::json!({
+                "ids": ids,
+                "seasons": [{
+                    "number": show.last_watched_season,
+                    "episodes": [{
+                        "number": show.last_watched_episode,
+                        "watched_at": show.watched_at.to_rfc3339(),
+                    }],
+                }],
+            }))
+        })
+        .collect();
+    if shows.is_empty() {
+        return Ok(());
+    }
+
+    let payload = serde_json::json!({ "shows": shows });
+    let response = retry.send_non_idempotent("Simkl show progress", || {
+        client
+            .post(format!("{}/sync/history", API_BASE))
+            .header("Authorization", format!("Bearer {}", access_token))
+            .header("simkl-api-key", client_id)
+            .header("Accept", "application/json")
+            .header("Content-Type", "application/json")
+            .json(&payload)
+    })
+    .await?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(anyhow!("Failed to set show progress: {} - {}", status, error_text));
+    }
+    tracing::info!("Set progress of {} shows on Simkl", shows.len());
+    Ok(())
+}
+
 /// Add items to Simkl watchlist
 pub async fn add_to_watchlist(
     client: &Client,
@@ -621,6 +832,7 @@ pub async fn add_to_watchlist(
     client_id: &str,
     items: &[WatchlistItem],
     status_mapping: &std::collections::HashMap<media_sync_models::NormalizedStatus, String>,
+    retry: &RetryPolicy,
 ) -> Result<()> {
     let mut movies = Vec::new();
     let mut shows = Vec::new();
@@ -677,15 +889,16 @@ pub async fn add_to_watchlist(
         "shows": shows
     });
 
-    let response = client
-        .post(format!("{}/sync/add-to-list", API_BASE))
-        .header("Authorization", format!("Bearer {}", access_token))
-        .header("simkl-api-key", client_id)
-        .header("Accept", "application/json")
-        .header("Content-Type", "application/json")
-        .json(&payload)
-        .send()
-        .await?;
+    let response = retry.send("Simkl watchlist add", || {
+        client
+            .post(format!("{}/sync/add-to-list", API_BASE))
+            .header("Authorization", format!("Bearer {}", access_token))
+            .header("simkl-api-key", client_id)
+            .header("Accept", "application/json")
+            .header("Content-Type", "application/json")
+            .json(&payload)
+    })
+    .await?;
 
     let status = response.status();
     if !status.is_success() {
@@ -766,6 +979,7 @@ pub async fn set_ratings(
     access_token: &str,
     client_id: &str,
     ratings: &[Rating],
+    retry: &RetryPolicy,
 ) -> Result<()> {
     let mut movies = Vec::new();
     let mut shows = Vec::new();
@@ -807,15 +1021,16 @@ pub async fn set_ratings(
         "shows": shows
     });
 
-    let response = client
-        .post(format!("{}/sync/ratings", API_BASE))
-        .header("Authorization", format!("Bearer {}", access_token))
-        .header("simkl-api-key", client_id)
-        .header("Accept", "application/json")
-        .header("Content-Type", "application/json")
-        .json(&payload)
-        .send()
-        .await?;
+    let response = retry.send("Simkl ratings", || {
+        client
+            .post(format!("{}/sync/ratings", API_BASE))
+            .header("Authorization", format!("Bearer {}", access_token))
+            .header("simkl-api-key", client_id)
+            .header("Accept", "application/json")
+            .header("Content-Type", "application/json")
+            .json(&payload)
+    })
+    .await?;
 
     let status = response.status();
     if !status.is_success() {
@@ -829,57 +1044,150 @@ pub async fn set_ratings(
     Ok(())
 }
 
-/// Set reviews on Simkl (if supported)
-pub async fn set_reviews(
-    _client: &Client,
-    _access_token: &str,
-    _client_id: &str,
-    _reviews: &[Review],
-) -> Result<()> {
-    // Simkl may not have a reviews/comments API endpoint
-    // Return Ok for now - can be implemented if Simkl adds this feature
-    Ok(())
-}
-
 /// Add watch history to Simkl
 pub async fn add_watch_history(
     client: &Client,
     access_token: &str,
     client_id: &str,
     items: &[WatchHistory],
+    retry: &RetryPolicy,
 ) -> Result<()> {
+    add_watch_history_at(client, API_BASE, access_token, client_id, items, retry).await?;
+    Ok(())
+}
+
+/// Outcome of a `/sync/history` call, as reported by Simkl
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct HistoryAddSummary {
+    pub added: usize,
+    /// Sent items Simkl neither added nor reported as not found (already in history)
+    pub already_present: usize,
+    pub not_found: usize,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct SimklHistoryCounts {
+    #[serde(default)]
+    movies: usize,
+    #[serde(default)]
+    shows: usize,
+    #[serde(default)]
+    episodes: usize,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct SimklHistoryNotFound {
+    #[serde(default)]
+    movies: Vec<serde_json::Value>,
+    #[serde(default)]
+    shows: Vec<serde_json::Value>,
+    #[serde(default)]
+    episodes: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct SimklHistoryResponse {
+    #[serde(default)]
+    added: SimklHistoryCounts,
+    #[serde(default)]
+    not_found: SimklHistoryNotFound,
+}
+
+/// IDs Simkl can match history entries on: simkl, imdb, tmdb and tvdb
+fn history_ids(imdb_id: &str, ids: &Option<media_sync_models::MediaIds>) -> serde_json::Map<String, serde_json::Value> {
+    let mut ids_obj = serde_json::Map::new();
+    let imdb = ids.as_ref()
+        .and_then(|ids| ids.imdb_id.clone())
+        .unwrap_or_else(|| imdb_id.to_string());
+    if !imdb.is_empty() {
+        ids_obj.insert("imdb".to_string(), serde_json::json!(imdb));
+    }
+    if let Some(ids) = ids {
+        if let Some(simkl) = ids.simkl_id {
+            ids_obj.insert("simkl".to_string(), serde_json::json!(simkl));
+        }
+        if let Some(tmdb) = ids.tmdb_id {
+            ids_obj.insert("tmdb".to_string(), serde_json::json!(tmdb));
+        }
+        if let Some(tvdb) = ids.tvdb_id {
+            ids_obj.insert("tvdb".to_string(), serde_json::json!(tvdb));
+        }
+    }
+    ids_obj
+}
+
+/// Build the `/sync/history` body. Movies and shows go in `movies`/`shows`; episodes go in
+/// `episodes` keyed by their own IDs. Every entry carries its `watched_at`.
+/// Returns the payload, the number of entries in it, and the number skipped for lack of IDs.
+fn build_history_payload(items: &[WatchHistory]) -> (serde_json::Value, usize, usize) {
     let mut movies = Vec::new();
     let mut shows = Vec::new();
+    let mut episodes = Vec::new();
+    let mut skipped = 0;
 
     for item in items {
-        let item_obj = serde_json::json!({
-            "ids": {
-                "imdb": item.imdb_id
-            },
+        let ids = history_ids(&item.imdb_id, &item.ids);
+        if ids.is_empty() {
+            skipped += 1;
+            continue;
+        }
+
+        let mut item_obj = serde_json::json!({
+            "ids": ids,
             "watched_at": item.watched_at.to_rfc3339()
         });
 
         match &item.media_type {
-            MediaType::Movie => movies.push(item_obj),
+            MediaType::Movie => {
+                if let Some(ref title) = item.title {
+                    item_obj["title"] = serde_json::json!(title);
+                }
+                if let Some(year) = item.year {
+                    item_obj["year"] = serde_json::json!(year);
+                }
+                movies.push(item_obj);
+            }
             MediaType::Show => shows.push(item_obj),
-            MediaType::Episode { .. } => continue,
+            MediaType::Episode { .. } => episodes.push(item_obj),
         }
     }
 
+    let total = movies.len() + shows.len() + episodes.len();
     let payload = serde_json::json!({
         "movies": movies,
-        "shows": shows
+        "shows": shows,
+        "episodes": episodes
     });
+    (payload, total, skipped)
+}
 
-    let response = client
-        .post(format!("{}/sync/history", API_BASE))
-        .header("Authorization", format!("Bearer {}", access_token))
-        .header("simkl-api-key", client_id)
-        .header("Accept", "application/json")
-        .header("Content-Type", "application/json")
-        .json(&payload)
-        .send()
-        .await?;
+/// POST watch history to `{api_base}/sync/history`
+pub(crate) async fn add_watch_history_at(
+    client: &Client,
+    api_base: &str,
+    access_token: &str,
+    client_id: &str,
+    items: &[WatchHistory],
+    retry: &RetryPolicy,
+) -> Result<HistoryAddSummary> {
+    let (payload, total, skipped) = build_history_payload(items);
+    if skipped > 0 {
+        tracing::warn!("Skipped {} Simkl watch history items without any usable ID", skipped);
+    }
+    if total == 0 {
+        return Ok(HistoryAddSummary::default());
+    }
+
+    let response = retry.send_non_idempotent("Simkl history add", || {
+        client
+            .post(format!("{}/sync/history", api_base))
+            .header("Authorization", format!("Bearer {}", access_token))
+            .header("simkl-api-key", client_id)
+            .header("Accept", "application/json")
+            .header("Content-Type", "application/json")
+            .json(&payload)
+    })
+    .await?;
 
     let status = response.status();
     if !status.is_success() {
@@ -887,10 +1195,34 @@ pub async fn add_watch_history(
         return Err(anyhow!("Failed to add watch history: {} - {}", status, error_text));
     }
 
-    let total_items = movies.len() + shows.len();
-    tracing::info!("Added {} items to Simkl watch history ({} movies, {} shows)", total_items, movies.len(), shows.len());
+    // Older responses may have an empty body; treat that as everything added
+    let body = response.text().await.unwrap_or_default();
+    let summary = if body.trim().is_empty() {
+        HistoryAddSummary { added: total, ..Default::default() }
+    } else {
+        let parsed: SimklHistoryResponse = serde_json::from_str(&body)
+            .map_err(|e| anyhow!("Failed to parse Simkl history response: {} - {}", e, body))?;
+        let added = parsed.added.movies + parsed.added.shows + parsed.added.episodes;
+        let not_found = parsed.not_found.movies.len() + parsed.not_found.shows.len() + parsed.not_found.episodes.len();
+        for item in parsed.not_found.movies.iter().chain(&parsed.not_found.shows).chain(&parsed.not_found.episodes).take(5) {
+            tracing::debug!("Simkl could not match watch history item: {}", item);
+        }
+        HistoryAddSummary {
+            added,
+            already_present: total.saturating_sub(added + not_found),
+            not_found,
+        }
+    };
 
-    Ok(())
+    tracing::info!(
+        "Added {} items to Simkl watch history ({} already present, {} not found)",
+        summary.added, summary.already_present, summary.not_found
+    );
+    if summary.not_found > 0 {
+        tracing::warn!("Simkl could not match {} watch history items", summary.not_found);
+    }
+
+    Ok(summary)
 }
 
 /// Search for media by title using Simkl API
@@ -994,3 +1326,154 @@ pub async fn search_by_title(
     Ok(None)
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use crate::test_support::{serve, MockRequest, MockResponse};
+
+    /// Serve one canned JSON response and hand back the request that was received
+    fn mock_simkl(status: &str, body: &str) -> (String, std::thread::JoinHandle<MockRequest>) {
+        let (base, server) = serve(vec![MockResponse::json(status, body)]);
+        (base, std::thread::spawn(move || server.join().unwrap().remove(0)))
+    }
+
+    fn history_items() -> Vec<WatchHistory> {
+        let watched_at = Utc.with_ymd_and_hms(2024, 5, 1, 20, 0, 0).unwrap();
+        vec![
+            WatchHistory {
+                imdb_id: "tt0113277".to_string(),
+                ids: Some(media_sync_models::MediaIds {
+                    imdb_id: Some("tt0113277".to_string()),
+                    tmdb_id: Some(949),
+                    ..Default::default()
+                }),
+                title: Some("Heat".to_string()),
+                year: Some(1995),
+                watched_at,
+                media_type: MediaType::Movie,
+                source: "trakt".to_string(),
+                watched_percent: None,
+                show_ids: None,
+            },
+            WatchHistory {
+                imdb_id: "tt0959621".to_string(),
+                ids: Some(media_sync_models::MediaIds {
+                    imdb_id: Some("tt0959621".to_string()),
+                    tvdb_id: Some(349232),
+                    ..Default::default()
+                }),
+                title: Some("Breaking Bad".to_string()),
+                year: Some(2008),
+                watched_at,
+                media_type: MediaType::Episode { season: 1, episode: 1 },
+                source: "plex".to_string(),
+                watched_percent: None,
+                show_ids: None,
+            },
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_add_watch_history_posts_movies_and_episodes() {
+        let (base, server) = mock_simkl(
+            "201 Created",
+            r#"{"added":{"movies":1,"shows":0,"episodes":1},"not_found":{"movies":[],"shows":[],"episodes":[]}}"#,
+        );
+
+        let summary = add_watch_history_at(&Client::new(), &base, "token", "client", &history_items(), &RetryPolicy::default()).await.unwrap();
+        assert_eq!(summary, HistoryAddSummary { added: 2, already_present: 0, not_found: 0 });
+
+        let request = server.join().unwrap();
+        assert!(request.line.starts_with("POST /sync/history "));
+        let body: serde_json::Value = serde_json::from_str(&request.body).unwrap();
+        assert_eq!(body["movies"][0]["ids"]["imdb"], "tt0113277");
+        assert_eq!(body["movies"][0]["ids"]["tmdb"], 949);
+        assert_eq!(body["movies"][0]["watched_at"], "2024-05-01T20:00:00+00:00");
+        assert_eq!(body["episodes"][0]["ids"]["tvdb"], 349232);
+        assert_eq!(body["episodes"][0]["watched_at"], "2024-05-01T20:00:00+00:00");
+    }
+
+    #[tokio::test]
+    async fn test_add_watch_history_already_in_history() {
+        let (base, server) = mock_simkl(
+            "201 Created",
+            r#"{"added":{"movies":0,"shows":0,"episodes":0},"not_found":{"movies":[],"shows":[],"episodes":[]}}"#,
+        );
+
+        let summary = add_watch_history_at(&Client::new(), &base, "token", "client", &history_items(), &RetryPolicy::default()).await.unwrap();
+        assert_eq!(summary, HistoryAddSummary { added: 0, already_present: 2, not_found: 0 });
+        server.join().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_activities_decide_which_lists_to_fetch() {
+        let saved: SimklActivities = serde_json::from_str(r#"{
+            "all": "2024-05-01T10:00:00Z",
+            "movies": { "all": "2024-05-01T10:00:00Z", "rated_at": "2024-04-01T00:00:00Z", "removed_from_list": "2024-03-01T00:00:00Z" },
+            "tv_shows": { "all": "2024-04-20T00:00:00Z", "rated_at": null }
+        }"#).unwrap();
+        let (base, server) = mock_simkl(
+            "200 OK",
+            r#"{
+                "all": "2024-05-02T08:00:00Z",
+                "movies": { "all": "2024-05-02T08:00:00Z", "rated_at": "2024-04-01T00:00:00Z", "removed_from_list": "2024-03-01T00:00:00Z" },
+                "tv_shows": { "all": "2024-04-20T00:00:00Z", "rated_at": null }
+            }"#,
+        );
+
+        let current = get_activities_at(&Client::new(), &base, "token", "client").await.unwrap();
+        assert!(server.join().unwrap().line.starts_with("POST /sync/activities "));
+
+        let since = Utc.with_ymd_and_hms(2024, 5, 1, 10, 0, 0).unwrap();
+        assert_eq!(current.plan_fetch(Some(&saved), DataType::Watchlist), ListFetch::Since(since));
+        assert_eq!(current.plan_fetch(Some(&saved), DataType::WatchHistory), ListFetch::Since(since));
+        assert_eq!(current.plan_fetch(Some(&saved), DataType::Ratings), ListFetch::Unchanged);
+        assert_eq!(current.plan_fetch(None, DataType::Ratings), ListFetch::Full);
+
+        let mut removed = serde_json::to_value(&saved).unwrap();
+        removed["movies"]["removed_from_list"] = "2024-02-01T00:00:00Z".into();
+        let removed: SimklActivities = serde_json::from_value(removed).unwrap();
+        assert_eq!(current.plan_fetch(Some(&removed), DataType::Watchlist), ListFetch::Full);
+    }
+
+    #[test]
+    fn test_build_history_payload_skips_items_without_ids() {
+        let mut items = history_items();
+        items[0].imdb_id.clear();
+        items[0].ids = None;
+
+        let (payload, total, skipped) = build_history_payload(&items);
+        assert_eq!((total, skipped), (1, 1));
+        assert!(payload["movies"].as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_show_progress_skips_specials_and_unwatched_shows() {
+        let items: Vec<SimklProgressItem> = serde_json::from_str(r#"[
+            {
+                "show": { "title": "Breaking Bad", "year": 2008, "ids": { "simkl": 11121, "imdb": "tt0903747" } },
+                "seasons": [
+                    { "number": 3, "episodes": [
+                        { "number": 4, "watched_at": "2024-05-03T20:00:00Z" },
+                        { "number": 5, "watched_at": null }
+                    ] },
+                    { "number": 0, "episodes": [{ "number": 1, "watched_at": "2024-06-01T20:00:00Z" }] }
+                ]
+            },
+            { "show": { "title": "Plan to Watch", "year": 2021, "ids": { "simkl": 5 } }, "seasons": [] }
+        ]"#).unwrap();
+
+        let watched = watched_episodes(&items);
+        let progress = show_progress(items);
+        assert_eq!(progress.len(), 1);
+        assert_eq!(progress[0].ids.as_ref().unwrap().simkl_id, Some(11121));
+        assert_eq!(progress[0].position(), (3, 4));
+
+        // An undated watched episode is still watched, so it isn't added as a play
+        let mut next = progress[0].clone();
+        next.last_watched_episode = 5;
+        assert!(media_sync_models::unwatched_progress(&[progress[0].clone(), next], &watched).is_empty());
+    }
+}
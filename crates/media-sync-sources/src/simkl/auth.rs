@@ -2,6 +2,7 @@ use anyhow::{anyhow, Result};
 use chrono::{DateTime, Duration, Utc};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use std::io::IsTerminal;
 use std::time::Duration as StdDuration;
 use tokio::time::sleep;
 
@@ -10,10 +11,17 @@ const PIN_URL: &str = "https://api.simkl.com/oauth/pin";
 
 /// Create a reqwest Client with browser-like headers
 pub fn create_simkl_client() -> Client {
-    Client::builder()
-        .user_agent("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
-        .build()
-        .unwrap_or_else(|_| Client::new())
+    create_simkl_client_with_timeout(None)
+}
+
+/// Like [`create_simkl_client`], giving up on requests that take longer than `timeout`
+pub fn create_simkl_client_with_timeout(timeout: Option<std::time::Duration>) -> Client {
+    let mut builder = Client::builder()
+        .user_agent("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36");
+    if let Some(timeout) = timeout {
+        builder = builder.timeout(timeout);
+    }
+    builder.build().unwrap_or_else(|_| Client::new())
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -52,24 +60,38 @@ pub async fn authenticate(
 ) -> Result<TokenInfo> {
     let client = create_simkl_client();
 
+    let mut refresh_error = None;
     if let Some(refresh_token) = refresh_token {
         if !refresh_token.is_empty() {
         // Try to refresh the token
-        match refresh_access_token(&client, client_id, client_secret, refresh_token).await {
+        match refresh_access_token(&client, TOKEN_URL, client_id, client_secret, refresh_token).await {
             Ok(token_info) => return Ok(token_info),
-            Err(_) => {
+            Err(e) => {
                 // Refresh failed, fall through to new authorization
+                refresh_error = Some(e);
                 }
             }
         }
     }
 
-    // New authorization flow using device code
+    // New authorization flow using device code, which needs someone to enter the code
+    if !std::io::stdin().is_terminal() {
+        return Err(anyhow!(
+            "Simkl token could not be refreshed{}; run 'totalrecall config simkl' to sign in again",
+            refresh_error.map(|e| format!(" ({})", e)).unwrap_or_default()
+        ));
+    }
     authorize_with_device_code(client_id).await
 }
 
+/// Exchange `refresh_token` for new tokens
+pub async fn refresh(client_id: &str, client_secret: &str, refresh_token: &str) -> Result<TokenInfo> {
+    refresh_access_token(&create_simkl_client(), TOKEN_URL, client_id, client_secret, refresh_token).await
+}
+
 async fn refresh_access_token(
     client: &Client,
+    token_url: &str,
     client_id: &str,
     client_secret: &str,
     refresh_token: &str,
@@ -82,7 +104,7 @@ async fn refresh_access_token(
     });
 
     let response = client
-        .post(TOKEN_URL)
+        .post(token_url)
         .json(&payload)
         .header("Accept", "application/json")
         .header("Content-Type", "application/json")
@@ -1,32 +1,45 @@
-use crate::traits::MediaSource;
+use crate::traits::{MediaSource, WrittenReview};
 use crate::capabilities::{IncrementalSync, RatingNormalization, CapabilityRegistry, StatusMapping, IdExtraction, IdLookupProvider};
-use crate::simkl::api;
+use crate::oauth::AccessToken;
+use crate::http_cache::HttpCache;
+use crate::retry::RetryPolicy;
+use crate::simkl::api::{self, ListFetch};
+use crate::traits::DataType;
 use crate::simkl::auth;
 use anyhow::Result;
 use chrono::{DateTime, Duration, Utc};
-use media_sync_models::{Rating, Review, WatchHistory, WatchlistItem, MediaIds, MediaType};
+use media_sync_models::{Rating, Review, WatchHistory, WatchProgress, WatchlistItem, MediaIds, MediaType};
 use media_sync_config::StatusMapping as StatusMappingConfig;
 use reqwest::Client;
 use std::sync::Arc;
-use std::collections::HashMap;
-use tracing::info;
+use std::collections::{HashMap, HashSet};
+use tracing::{info, warn};
 use async_trait::async_trait;
 
-#[derive(Clone)]
 pub struct SimklClient {
     client: Arc<Client>,
-    access_token: Option<String>,
+    access_token: AccessToken,
     client_id: String,
     client_secret: String,
     force_full_sync: bool,
     status_mapping: StatusMappingConfig,
+    /// Lists whose last fetch returned only changes (see `IncrementalSync::fetched_changes_only`)
+    changes_only: std::sync::Mutex<HashSet<DataType>>,
+    /// Lists the caller has no cached copy of (see `IncrementalSync::set_cached`)
+    uncached: HashSet<DataType>,
+    /// Activities of each list's last fetch, saved by `commit_fetch` once the list is cached
+    pending_activities: std::sync::Mutex<HashMap<DataType, PendingActivities>>,
+    /// Serializes the read-modify-write of saved activities between concurrent commits
+    activities_lock: std::sync::Mutex<()>,
+    retry: RetryPolicy,
+    http_cache: HttpCache,
 }
 
 impl SimklClient {
     pub fn new(client_id: String, client_secret: String) -> Self {
         Self {
             client: Arc::new(auth::create_simkl_client()),
-            access_token: None,
+            access_token: AccessToken::default(),
             client_id,
             client_secret,
             force_full_sync: false,
@@ -34,6 +47,12 @@ impl SimklClient {
                 to_normalized: HashMap::new(),
                 from_normalized: HashMap::new(),
             },
+            changes_only: std::sync::Mutex::new(HashSet::new()),
+            uncached: HashSet::new(),
+            pending_activities: std::sync::Mutex::new(HashMap::new()),
+            activities_lock: std::sync::Mutex::new(()),
+            retry: RetryPolicy::default(),
+            http_cache: HttpCache::default(),
         }
     }
 
@@ -42,6 +61,24 @@ impl SimklClient {
         self
     }
 
+    /// Retries of watchlist, rating and history writes (`simkl.retry`)
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Revalidate watchlist, history and progress reads instead of downloading them again
+    pub fn with_http_cache(mut self, http_cache: HttpCache) -> Self {
+        self.http_cache = http_cache;
+        self
+    }
+
+    /// Give up on API requests that take longer than `timeout` (`sources.request_timeout_secs`)
+    pub fn with_request_timeout(mut self, timeout: Option<std::time::Duration>) -> Self {
+        self.client = Arc::new(auth::create_simkl_client_with_timeout(timeout));
+        self
+    }
+
     pub fn set_force_full_sync(&mut self, force: bool) {
         self.force_full_sync = force;
     }
@@ -54,6 +91,7 @@ impl SimklClient {
         let path_manager = PathManager::default();
         let mut cred_store = CredentialStore::new(path_manager.credentials_file());
         cred_store.load()?;
+        cred_store.check_account("simkl")?;
 
         // Check if we have a valid access token
         // According to Simkl API docs, access tokens never expire, so if we have a token, use it
@@ -65,20 +103,20 @@ impl SimklClient {
                 if let Some(expires_at) = cred_store.get_simkl_token_expires() {
                     if expires_at > Utc::now() + Duration::minutes(5) {
                         // Token has expiration set and is still valid
-                        self.access_token = Some(saved_token.clone());
+                        self.access_token.set(saved_token.clone());
                         info!("Using saved Simkl access token (expires at {})", expires_at);
                         return Ok(());
                     } else {
                         // Token has expiration set but it's in the past
                         // Per API docs, tokens never expire, so use it anyway
                         // This handles tokens saved with incorrect expiration times from previous code
-                        self.access_token = Some(saved_token.clone());
+                        self.access_token.set(saved_token.clone());
                         info!("Using saved Simkl access token (expiration was set but tokens never expire per API)");
                         return Ok(());
                     }
                 } else {
                     // No expiration set - treat as never expiring (per Simkl API docs)
-                    self.access_token = Some(saved_token.clone());
+                    self.access_token.set(saved_token.clone());
                     info!("Using saved Simkl access token (never expires)");
                     return Ok(());
                 }
@@ -90,24 +128,40 @@ impl SimklClient {
 
         let token_info = simkl_authenticate(&self.client_id, &self.client_secret, refresh_token).await?;
 
-        self.access_token = Some(token_info.access_token.clone());
+        self.access_token.set(token_info.access_token.clone());
 
-        // Save tokens
-        cred_store.set_simkl_access_token(token_info.access_token);
-        cred_store.set_simkl_refresh_token(token_info.refresh_token);
-        cred_store.set_simkl_token_expires(token_info.expires_at);
-        cred_store.save()?;
+        save_tokens(&mut cred_store, token_info)?;
 
         info!("Authenticated to Simkl");
         Ok(())
     }
 
     pub fn is_authenticated(&self) -> bool {
-        self.access_token.is_some()
+        self.access_token.is_set()
     }
 
-    fn access_token(&self) -> Result<&str> {
-        self.access_token.as_deref().ok_or_else(|| anyhow::anyhow!("Not authenticated"))
+    /// Run `call` with the access token; when Simkl answers 401, once more after a refresh
+    async fn authorized<T, F, Fut>(&self, call: F) -> Result<T>
+    where
+        F: Fn(String) -> Fut,
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        self.access_token.call(call, || self.refresh_tokens()).await
+    }
+
+    /// Exchange the saved refresh token for new tokens, save them and return the access token
+    async fn refresh_tokens(&self) -> Result<String> {
+        let (mut cred_store, _) = self.get_credential_store()?;
+        let refresh_token = cred_store.get_simkl_refresh_token().filter(|token| !token.is_empty()).cloned()
+            .ok_or_else(|| anyhow::anyhow!("No Simkl refresh token saved; run 'totalrecall config simkl' to sign in again"))?;
+        let mut token_info = auth::refresh(&self.client_id, &self.client_secret, &refresh_token).await?;
+        if token_info.refresh_token.is_empty() {
+            token_info.refresh_token = refresh_token;
+        }
+        let access_token = token_info.access_token.clone();
+        save_tokens(&mut cred_store, token_info)?;
+        info!("Refreshed the Simkl access token");
+        Ok(access_token)
     }
 
     /// Simkl ratings are already in the target format (1-10 integer), same as Trakt
@@ -127,162 +181,88 @@ impl SimklClient {
         Ok((cred_store, path_manager))
     }
     
-    async fn check_activities_changed(
-        &self,
-        data_type: &str, // "watchlist", "ratings", "watch_history"
-        force_full_sync: bool,
-    ) -> Result<Option<DateTime<Utc>>> {
-        if force_full_sync {
-            return Ok(None); // Fetch all
+    /// How much of `data_type` to fetch: all of it with `force_full_sync`, without a
+    /// cached copy to merge changes into, when activities are unavailable and once every
+    /// `FULL_FETCH_INTERVAL_DAYS` (for removals `date_from` doesn't return); otherwise
+    /// what `/sync/activities` says changed
+    fn plan_list_fetch(&self, data_type: DataType, current: Option<&api::SimklActivities>, cred_store: &media_sync_config::CredentialStore) -> ListFetch {
+        let full_fetch_due = cred_store.get_last_sync_timestamp("simkl", &full_fetch_key(data_type))
+            .is_none_or(|fetched| Utc::now() - fetched > Duration::days(FULL_FETCH_INTERVAL_DAYS));
+        match current {
+            Some(_) if self.force_full_sync || self.uncached.contains(&data_type) || full_fetch_due => ListFetch::Full,
+            Some(current) => {
+                let saved = cred_store.get_simkl_last_activities(data_type.as_str())
+                    .and_then(|json| serde_json::from_str::<api::SimklActivities>(&json).ok());
+                current.plan_fetch(saved.as_ref(), data_type)
+            }
+            None => ListFetch::Full,
         }
+    }
 
-        let (mut cred_store, _) = self.get_credential_store()?;
-        
-        // Fetch current activities
-        let current_activities = api::get_activities(
-            &self.client,
-            &self.access_token()?,
-            &self.client_id,
-        ).await?;
-        
-        // Load saved activities
-        let saved_activities_json = cred_store.get_simkl_last_activities().unwrap_or_default();
-        
-        if saved_activities_json.is_empty() {
-            // First sync - save activities and return None (fetch all)
-            cred_store.set_simkl_last_activities(serde_json::to_string(&current_activities)?);
-            cred_store.save()?;
-            return Ok(None);
-        }
-        
-        let saved_activities: api::SimklActivities = serde_json::from_str(&saved_activities_json)?;
-        
-        // Check if specific data type changed
-        let changed = match data_type {
-            "watchlist" => {
-                // Check tv_shows.all, anime.all, movies.all
-                let tv_changed = current_activities.tv_shows.as_ref()
-                    .and_then(|s| s.all.as_ref())
-                    .map(|current| {
-                        saved_activities.tv_shows.as_ref()
-                            .and_then(|s| s.all.as_ref())
-                            .map(|saved| current != saved)
-                            .unwrap_or(true)
-                    })
-                    .unwrap_or(false);
-                
-                let anime_changed = current_activities.anime.as_ref()
-                    .and_then(|s| s.all.as_ref())
-                    .map(|current| {
-                        saved_activities.anime.as_ref()
-                            .and_then(|s| s.all.as_ref())
-                            .map(|saved| current != saved)
-                            .unwrap_or(true)
-                    })
-                    .unwrap_or(false);
-                
-                let movies_changed = current_activities.movies.as_ref()
-                    .and_then(|s| s.all.as_ref())
-                    .map(|current| {
-                        saved_activities.movies.as_ref()
-                            .and_then(|s| s.all.as_ref())
-                            .map(|saved| current != saved)
-                            .unwrap_or(true)
-                    })
-                    .unwrap_or(false);
-                
-                tv_changed || anime_changed || movies_changed
-            },
-            "ratings" => {
-                // Check rated_at timestamps
-                let tv_changed = current_activities.tv_shows.as_ref()
-                    .and_then(|s| s.rated_at.as_ref())
-                    .map(|current| {
-                        saved_activities.tv_shows.as_ref()
-                            .and_then(|s| s.rated_at.as_ref())
-                            .map(|saved| current != saved)
-                            .unwrap_or(true)
-                    })
-                    .unwrap_or(false);
-                
-                let anime_changed = current_activities.anime.as_ref()
-                    .and_then(|s| s.rated_at.as_ref())
-                    .map(|current| {
-                        saved_activities.anime.as_ref()
-                            .and_then(|s| s.rated_at.as_ref())
-                            .map(|saved| current != saved)
-                            .unwrap_or(true)
-                    })
-                    .unwrap_or(false);
-                
-                let movies_changed = current_activities.movies.as_ref()
-                    .and_then(|s| s.rated_at.as_ref())
-                    .map(|current| {
-                        saved_activities.movies.as_ref()
-                            .and_then(|s| s.rated_at.as_ref())
-                            .map(|saved| current != saved)
-                            .unwrap_or(true)
-                    })
-                    .unwrap_or(false);
-                
-                tv_changed || anime_changed || movies_changed
-            },
-            "watch_history" => {
-                // Check playback timestamps
-                let tv_changed = current_activities.tv_shows.as_ref()
-                    .and_then(|s| s.playback.as_ref())
-                    .map(|current| {
-                        saved_activities.tv_shows.as_ref()
-                            .and_then(|s| s.playback.as_ref())
-                            .map(|saved| current != saved)
-                            .unwrap_or(true)
-                    })
-                    .unwrap_or(false);
-                
-                let anime_changed = current_activities.anime.as_ref()
-                    .and_then(|s| s.playback.as_ref())
-                    .map(|current| {
-                        saved_activities.anime.as_ref()
-                            .and_then(|s| s.playback.as_ref())
-                            .map(|saved| current != saved)
-                            .unwrap_or(true)
-                    })
-                    .unwrap_or(false);
-                
-                let movies_changed = current_activities.movies.as_ref()
-                    .and_then(|s| s.playback.as_ref())
-                    .map(|current| {
-                        saved_activities.movies.as_ref()
-                            .and_then(|s| s.playback.as_ref())
-                            .map(|saved| current != saved)
-                            .unwrap_or(true)
-                    })
-                    .unwrap_or(false);
-                
-                tv_changed || anime_changed || movies_changed
-            },
-            _ => false,
+    /// Fetch one list, only what changed since its last fetch when `/sync/activities`
+    /// allows it. The activities are saved for the next sync by `commit_fetch`, once the
+    /// fetched list is cached.
+    async fn fetch_list<T, F, Fut>(&self, data_type: DataType, fetch: F) -> Result<Vec<T>>
+    where
+        F: FnOnce(Option<DateTime<Utc>>) -> Fut,
+        Fut: std::future::Future<Output = Result<Vec<T>>>,
+    {
+        self.pending_activities.lock().unwrap().remove(&data_type);
+        let current = match self.authorized(|access_token| async move { api::get_activities(&self.client, &access_token, &self.client_id).await }).await {
+            Ok(current) => Some(current),
+            Err(e) => {
+                warn!("Failed to check Simkl activities, falling back to a full {} fetch: {}", data_type, e);
+                None
+            }
         };
-        
-        if changed {
-            // Use saved "all" timestamp as date_from
-            let date_from = saved_activities.all
-                .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-                .map(|dt| dt.with_timezone(&Utc));
-            
-            // Save new activities
-            cred_store.set_simkl_last_activities(serde_json::to_string(&current_activities)?);
-            cred_store.save()?;
-            
-            Ok(date_from)
-        } else {
-            // No changes, return special marker to indicate empty result
-            // Using epoch as marker - caller should check for this and return empty
-            Ok(Some(DateTime::from_timestamp(0, 0).unwrap().with_timezone(&Utc)))
+
+        let (cred_store, _) = self.get_credential_store()?;
+        let plan = self.plan_list_fetch(data_type, current.as_ref(), &cred_store);
+
+        let items = match plan {
+            ListFetch::Unchanged => {
+                info!("Simkl {} unchanged since the last sync, skipping fetch", data_type);
+                Vec::new()
+            }
+            ListFetch::Since(date_from) => {
+                info!("Fetching Simkl {} changed since {}", data_type, date_from.to_rfc3339());
+                fetch(Some(date_from)).await?
+            }
+            ListFetch::Full => fetch(None).await?,
+        };
+
+        {
+            let mut changes_only = self.changes_only.lock().unwrap();
+            if plan == ListFetch::Full {
+                changes_only.remove(&data_type);
+            } else {
+                changes_only.insert(data_type);
+            }
         }
+
+        if let Some(activities) = current {
+            let full_fetch = (plan == ListFetch::Full).then(Utc::now);
+            self.pending_activities.lock().unwrap().insert(data_type, PendingActivities { activities, full_fetch });
+        }
+        Ok(items)
     }
 }
 
+/// Refetch a list in full after this many days, for the removals a `date_from` fetch
+/// doesn't return
+const FULL_FETCH_INTERVAL_DAYS: i64 = 7;
+
+/// Where the last full fetch of a list is stored (`simkl_last_sync_full_<list>`)
+fn full_fetch_key(data_type: DataType) -> String {
+    format!("full_{}", data_type.as_str())
+}
+
+/// Activities of a fetch not saved yet, and when it was a full fetch
+struct PendingActivities {
+    activities: api::SimklActivities,
+    full_fetch: Option<DateTime<Utc>>,
+}
+
 #[async_trait::async_trait]
 impl MediaSource for SimklClient {
     type Error = crate::error::SourceError;
@@ -291,6 +271,12 @@ impl MediaSource for SimklClient {
         "simkl"
     }
 
+    fn supported_data_types(&self) -> &'static [crate::traits::DataType] {
+        use crate::traits::DataType;
+        // Simkl has no reviews/comments API
+        &[DataType::Watchlist, DataType::Ratings, DataType::WatchHistory]
+    }
+
     async fn authenticate(&mut self) -> Result<(), Self::Error> {
         match self.authenticate().await {
             Ok(()) => Ok(()),
@@ -303,127 +289,92 @@ impl MediaSource for SimklClient {
     }
 
     async fn get_watchlist(&self) -> Result<Vec<WatchlistItem>, Self::Error> {
-        let access_token = self.access_token().map_err(|e| crate::error::SourceError::new(format!("{}", e)))?;
-        
         // Check activities to determine if we need incremental sync
-        let date_from = match self.check_activities_changed("watchlist", self.force_full_sync).await {
-            Ok(Some(date)) => {
-                // Check if it's the special "no changes" marker (epoch)
-                if date.timestamp() == 0 {
-                    // No changes, return empty
-                    return Ok(Vec::new());
-                }
-                Some(date)
-            },
-            Ok(None) => None, // First sync or force full sync
-            Err(e) => {
-                // If activities check fails, fall back to full sync
-                tracing::warn!("Failed to check Simkl activities, falling back to full sync: {}", e);
-                None
-            },
-        };
-        
-        api::get_watchlist(&self.client, access_token, &self.client_id, date_from, &self.status_mapping.to_normalized)
+        self.fetch_list(DataType::Watchlist, |date_from| self.authorized(move |access_token| async move { api::get_watchlist(&self.client, &self.http_cache, &access_token, &self.client_id, date_from, &self.status_mapping.to_normalized).await }))
             .await
             .map_err(|e| crate::error::SourceError::new(format!("{}", e)))
     }
 
     async fn get_ratings(&self) -> Result<Vec<Rating>, Self::Error> {
-        let access_token = self.access_token().map_err(|e| crate::error::SourceError::new(format!("{}", e)))?;
-        
         // Check activities to determine if we need incremental sync
-        let date_from = match self.check_activities_changed("ratings", self.force_full_sync).await {
-            Ok(Some(date)) => {
-                // Check if it's the special "no changes" marker (epoch)
-                if date.timestamp() == 0 {
-                    // No changes, return empty
-                    return Ok(Vec::new());
-                }
-                Some(date)
-            },
-            Ok(None) => None, // First sync or force full sync
-            Err(e) => {
-                // If activities check fails, fall back to full sync
-                tracing::warn!("Failed to check Simkl activities, falling back to full sync: {}", e);
-                None
-            },
-        };
-        
-        api::get_ratings(&self.client, access_token, &self.client_id, date_from)
+        self.fetch_list(DataType::Ratings, |date_from| self.authorized(move |access_token| async move { api::get_ratings(&self.client, &access_token, &self.client_id, date_from).await }))
             .await
             .map_err(|e| crate::error::SourceError::new(format!("{}", e)))
     }
 
     async fn get_reviews(&self) -> Result<Vec<Review>, Self::Error> {
-        let access_token = self.access_token().map_err(|e| crate::error::SourceError::new(format!("{}", e)))?;
-        api::get_reviews(&self.client, access_token, &self.client_id)
-            .await
-            .map_err(|e| crate::error::SourceError::new(format!("{}", e)))
+        // No reviews API, and not in supported_data_types, so sync never asks
+        Ok(Vec::new())
     }
 
     async fn get_watch_history(&self) -> Result<Vec<WatchHistory>, Self::Error> {
-        let access_token = self.access_token().map_err(|e| crate::error::SourceError::new(format!("{}", e)))?;
-        
         // Check activities to determine if we need incremental sync
-        let date_from = match self.check_activities_changed("watch_history", self.force_full_sync).await {
-            Ok(Some(date)) => {
-                // Check if it's the special "no changes" marker (epoch)
-                if date.timestamp() == 0 {
-                    // No changes, return empty
-                    return Ok(Vec::new());
-                }
-                Some(date)
-            },
-            Ok(None) => None, // First sync or force full sync
-            Err(e) => {
-                // If activities check fails, fall back to full sync
-                tracing::warn!("Failed to check Simkl activities, falling back to full sync: {}", e);
-                None
-            },
-        };
-        
-        api::get_watch_history(&self.client, access_token, &self.client_id, date_from)
+        self.fetch_list(DataType::WatchHistory, |date_from| self.authorized(move |access_token| async move { api::get_watch_history(&self.client, &self.http_cache, &access_token, &self.client_id, date_from).await }))
             .await
             .map_err(|e| crate::error::SourceError::new(format!("{}", e)))
     }
 
     async fn add_to_watchlist(&self, items: &[WatchlistItem]) -> Result<(), Self::Error> {
-        let access_token = self.access_token().map_err(|e| crate::error::SourceError::new(format!("{}", e)))?;
-        api::add_to_watchlist(&self.client, access_token, &self.client_id, items, &self.status_mapping.from_normalized)
+        self.authorized(move |access_token| async move { api::add_to_watchlist(&self.client, &access_token, &self.client_id, items, &self.status_mapping.from_normalized, &self.retry).await })
             .await
             .map_err(|e| crate::error::SourceError::new(format!("{}", e)))
     }
 
     async fn remove_from_watchlist(&self, items: &[WatchlistItem]) -> Result<(), Self::Error> {
-        let access_token = self.access_token().map_err(|e| crate::error::SourceError::new(format!("{}", e)))?;
-        api::remove_from_watchlist(&self.client, access_token, &self.client_id, items)
+        self.authorized(move |access_token| async move { api::remove_from_watchlist(&self.client, &access_token, &self.client_id, items).await })
             .await
             .map_err(|e| crate::error::SourceError::new(format!("{}", e)))
     }
 
     async fn set_ratings(&self, ratings: &[Rating]) -> Result<(), Self::Error> {
-        let access_token = self.access_token().map_err(|e| crate::error::SourceError::new(format!("{}", e)))?;
-        api::set_ratings(&self.client, access_token, &self.client_id, ratings)
+        self.authorized(move |access_token| async move { api::set_ratings(&self.client, &access_token, &self.client_id, ratings, &self.retry).await })
             .await
             .map_err(|e| crate::error::SourceError::new(format!("{}", e)))
     }
 
-    async fn set_reviews(&self, reviews: &[Review]) -> Result<(), Self::Error> {
-        let access_token = self.access_token().map_err(|e| crate::error::SourceError::new(format!("{}", e)))?;
-        api::set_reviews(&self.client, access_token, &self.client_id, reviews)
+    async fn set_reviews(&self, reviews: &[Review]) -> Result<Vec<WrittenReview>, Self::Error> {
+        // Fail rather than report reviews as written that Simkl never received
+        if reviews.is_empty() {
+            return Ok(Vec::new());
+        }
+        Err(crate::error::SourceError::new(format!(
+            "Simkl has no API for reviews; {} reviews were not written",
+            reviews.len()
+        )))
+    }
+
+    async fn add_watch_history(&self, items: &[WatchHistory]) -> Result<(), Self::Error> {
+        self.authorized(move |access_token| async move { api::add_watch_history(&self.client, &access_token, &self.client_id, items, &self.retry).await })
             .await
             .map_err(|e| crate::error::SourceError::new(format!("{}", e)))
     }
 
-    async fn add_watch_history(&self, items: &[WatchHistory]) -> Result<(), Self::Error> {
-        let access_token = self.access_token().map_err(|e| crate::error::SourceError::new(format!("{}", e)))?;
-        api::add_watch_history(&self.client, access_token, &self.client_id, items)
+    fn supports_watch_progress(&self) -> bool {
+        true
+    }
+
+    async fn get_watch_progress(&self) -> Result<Vec<WatchProgress>, Self::Error> {
+        self.authorized(move |access_token| async move { api::get_watch_progress(&self.client, &self.http_cache, &access_token, &self.client_id).await })
+            .await
+            .map_err(|e| crate::error::SourceError::new(format!("{}", e)))
+    }
+
+    async fn set_watch_progress(&self, progress: &[WatchProgress]) -> Result<(), Self::Error> {
+        self.authorized(move |access_token| async move { api::set_watch_progress(&self.client, &self.http_cache, &access_token, &self.client_id, progress, &self.retry).await })
             .await
             .map_err(|e| crate::error::SourceError::new(format!("{}", e)))
     }
 
 }
 
+/// Save new tokens
+fn save_tokens(cred_store: &mut media_sync_config::CredentialStore, token_info: auth::TokenInfo) -> Result<()> {
+    cred_store.set_simkl_access_token(token_info.access_token);
+    cred_store.set_simkl_refresh_token(token_info.refresh_token);
+    cred_store.set_simkl_token_expires(token_info.expires_at);
+    cred_store.save()
+}
+
 impl IncrementalSync for SimklClient {
     fn set_force_full_sync(&mut self, force: bool) {
         self.force_full_sync = force;
@@ -432,21 +383,52 @@ impl IncrementalSync for SimklClient {
     fn supports_native_incremental_sync(&self) -> bool {
         true
     }
+
+    fn fetched_changes_only(&self, data_type: DataType) -> bool {
+        self.changes_only.lock().unwrap().contains(&data_type)
+    }
+
+    fn set_cached(&mut self, data_type: DataType, cached: bool) {
+        if cached {
+            self.uncached.remove(&data_type);
+        } else {
+            self.uncached.insert(data_type);
+        }
+    }
+
+    fn commit_fetch(&self, data_type: DataType) {
+        let Some(pending) = self.pending_activities.lock().unwrap().remove(&data_type) else {
+            return;
+        };
+        let saved = (|| -> Result<()> {
+            // Reload so concurrent commits of other lists don't overwrite each other
+            let _guard = self.activities_lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            let (mut cred_store, _) = self.get_credential_store()?;
+            cred_store.set_simkl_last_activities(data_type.as_str(), serde_json::to_string(&pending.activities)?);
+            if let Some(fetched) = pending.full_fetch {
+                cred_store.set_last_sync_timestamp("simkl", &full_fetch_key(data_type), fetched);
+            }
+            cred_store.save()
+        })();
+        if let Err(e) = saved {
+            warn!("Failed to save Simkl activities for {}: {}", data_type, e);
+        }
+    }
 }
 
 impl RatingNormalization for SimklClient {
-    fn normalize_rating(&self, rating: f64, target_scale: u8) -> u8 {
+    fn normalize_rating(&self, rating: f64, target_scale: u8, _media_type: &MediaType) -> u8 {
         // Simkl uses 1-10 scale, same as Trakt
         // For now, we assume target_scale is 10 (Trakt scale)
         rating.round() as u8
     }
     
-    fn denormalize_rating(&self, rating: u8, source_scale: u8) -> f64 {
+    fn denormalize_rating(&self, rating: u8, source_scale: u8, _media_type: &MediaType) -> f64 {
         // Simkl uses 1-10 scale, same as Trakt
         rating as f64
     }
     
-    fn native_rating_scale(&self) -> u8 {
+    fn native_rating_scale(&self, _media_type: &MediaType) -> u8 {
         10
     }
 }
@@ -520,11 +502,8 @@ impl IdLookupProvider for SimklClient {
         year: Option<u32>,
         media_type: &MediaType,
     ) -> Result<Option<MediaIds>, Box<dyn std::error::Error + Send + Sync>> {
-        let access_token = self.access_token()
-            .map_err(|e| Box::new(std::io::Error::new(std::io::ErrorKind::Other, format!("{}", e))) as Box<dyn std::error::Error + Send + Sync>)?;
-        
         // Simkl doesn't have a public search API, so this will return None
-        api::search_by_title(&self.client, access_token, &self.client_id, title, year, media_type)
+        self.authorized(move |access_token| async move { api::search_by_title(&self.client, &access_token, &self.client_id, title, year, media_type).await })
             .await
             .map_err(|e| Box::new(std::io::Error::new(std::io::ErrorKind::Other, format!("{}", e))) as Box<dyn std::error::Error + Send + Sync>)
     }
@@ -542,3 +521,55 @@ impl IdLookupProvider for SimklClient {
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_reviews_are_unsupported() {
+        let simkl = SimklClient::new("id".to_string(), "secret".to_string());
+        assert!(!simkl.supported_data_types().contains(&DataType::Reviews));
+        assert!(simkl.get_reviews().await.unwrap().is_empty());
+
+        // Nothing to write is not an error; anything else is never reported as written
+        assert!(simkl.set_reviews(&[]).await.is_ok());
+        let review = Review {
+            imdb_id: "tt0111161".to_string(),
+            ids: None,
+            content: "Hope is a good thing.".to_string(),
+            date_added: Utc::now(),
+            media_type: MediaType::Movie,
+            source: "imdb".to_string(),
+            is_spoiler: false,
+            rating: None,
+            review_id: None,
+        };
+        assert!(simkl.set_reviews(&[review]).await.is_err());
+    }
+
+    #[test]
+    fn test_lists_are_fetched_in_full_without_a_cache_and_periodically() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut cred_store = media_sync_config::CredentialStore::new(dir.path().join("credentials.toml"));
+        let activities: api::SimklActivities = serde_json::from_str(r#"{
+            "all": "2024-05-01T10:00:00Z",
+            "movies": { "all": "2024-05-01T10:00:00Z", "rated_at": "2024-04-01T00:00:00Z", "removed_from_list": "2024-03-01T00:00:00Z" }
+        }"#).unwrap();
+        cred_store.set_simkl_last_activities("ratings", serde_json::to_string(&activities).unwrap());
+        let mut simkl = SimklClient::new("id".to_string(), "secret".to_string());
+
+        // Never fetched in full by this version
+        assert_eq!(simkl.plan_list_fetch(DataType::Ratings, Some(&activities), &cred_store), ListFetch::Full);
+
+        cred_store.set_last_sync_timestamp("simkl", &full_fetch_key(DataType::Ratings), Utc::now() - Duration::days(1));
+        assert_eq!(simkl.plan_list_fetch(DataType::Ratings, Some(&activities), &cred_store), ListFetch::Unchanged);
+
+        simkl.set_cached(DataType::Ratings, false);
+        assert_eq!(simkl.plan_list_fetch(DataType::Ratings, Some(&activities), &cred_store), ListFetch::Full);
+        simkl.set_cached(DataType::Ratings, true);
+
+        cred_store.set_last_sync_timestamp("simkl", &full_fetch_key(DataType::Ratings), Utc::now() - Duration::days(FULL_FETCH_INTERVAL_DAYS + 1));
+        assert_eq!(simkl.plan_list_fetch(DataType::Ratings, Some(&activities), &cred_store), ListFetch::Full);
+    }
+}
@@ -0,0 +1,130 @@
+//! A canned HTTP server for tests of code that calls a web API.
+//!
+//! Each request gets a connection of its own (`Connection: close`), so the server reads
+//! one request per accepted connection and answers it before taking the next.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::thread::JoinHandle;
+
+/// A request the server received
+#[derive(Debug, Clone)]
+pub struct MockRequest {
+    /// Request line, such as `POST /sync/history HTTP/1.1`
+    pub line: String,
+    /// Header lines, lowercased
+    pub headers: String,
+    pub body: String,
+}
+
+impl MockRequest {
+    /// Path and query of the request
+    pub fn path(&self) -> &str {
+        self.line.split_whitespace().nth(1).unwrap_or_default()
+    }
+}
+
+/// A response to send back
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    status: String,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+impl MockResponse {
+    /// Empty response with `status`, such as `"503 Service Unavailable"`
+    pub fn new(status: &str) -> Self {
+        Self { status: status.to_string(), headers: Vec::new(), body: String::new() }
+    }
+
+    /// Response with `status` and a JSON body
+    pub fn json(status: &str, body: &str) -> Self {
+        Self::new(status).with_header("Content-Type", "application/json").with_body(body)
+    }
+
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn with_body(mut self, body: &str) -> Self {
+        self.body = body.to_string();
+        self
+    }
+
+    fn to_http(&self) -> String {
+        let headers: String = self.headers.iter().map(|(name, value)| format!("{}: {}\r\n", name, value)).collect();
+        format!(
+            "HTTP/1.1 {}\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.status, headers, self.body.len(), self.body
+        )
+    }
+}
+
+/// Answer one request per response, in order. Returns the base URL (`http://127.0.0.1:port`)
+/// and a handle yielding the requests served.
+pub fn serve(responses: Vec<MockResponse>) -> (String, JoinHandle<Vec<MockRequest>>) {
+    let count = responses.len();
+    let mut responses = responses.into_iter();
+    serve_with(count, move |_| responses.next().unwrap())
+}
+
+/// Answer `count` requests with whatever `respond` makes of each
+pub fn serve_with(
+    count: usize,
+    mut respond: impl FnMut(&MockRequest) -> MockResponse + Send + 'static,
+) -> (String, JoinHandle<Vec<MockRequest>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    let handle = std::thread::spawn(move || {
+        let mut requests = Vec::new();
+        for _ in 0..count {
+            let Ok((stream, _)) = listener.accept() else { break };
+            let mut reader = BufReader::new(stream);
+            let Some(request) = read_request(&mut reader) else { break };
+            let response = respond(&request);
+            reader.get_mut().write_all(response.to_http().as_bytes()).unwrap();
+            requests.push(request);
+        }
+        requests
+    });
+    (base, handle)
+}
+
+/// Answer one request per route with the JSON body of the first route whose path
+/// prefix matches the request's, or `{}` if none does
+pub fn serve_json_routes(routes: Vec<(&'static str, &'static str)>) -> (String, JoinHandle<Vec<MockRequest>>) {
+    serve_with(routes.len(), move |request| {
+        let body = routes.iter()
+            .find(|(prefix, _)| request.path().starts_with(prefix))
+            .map(|(_, body)| *body)
+            .unwrap_or("{}");
+        MockResponse::json("200 OK", body)
+    })
+}
+
+fn read_request(reader: &mut impl BufRead) -> Option<MockRequest> {
+    let mut line = String::new();
+    reader.read_line(&mut line).ok()?;
+    let mut headers = String::new();
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).ok()? == 0 || header.trim().is_empty() {
+            break;
+        }
+        let header = header.to_lowercase();
+        if let Some(value) = header.strip_prefix("content-length:") {
+            content_length = value.trim().parse().unwrap_or(0);
+        }
+        headers.push_str(&header);
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).ok()?;
+    Some(MockRequest {
+        line: line.trim_end().to_string(),
+        headers,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
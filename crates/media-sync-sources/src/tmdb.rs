@@ -0,0 +1,192 @@
+//! TMDB lookups, shared by the title → IMDB ID fallback of ID resolution
+//! (`[sources.tmdb] id_lookup`) and Plex items without an IMDB GUID.
+
+use crate::capabilities::IdLookupProvider;
+use crate::disambiguation::{pick_best_candidate, TitleCandidate};
+use async_trait::async_trait;
+use media_sync_config::{Config, IdMatchingConfig};
+use media_sync_models::{MediaIds, MediaType};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+/// Title → IMDB ID lookups on TMDB
+///
+/// Requests carry `[sources.tmdb] api_key`: a v3 key as the `api_key` parameter, a v4
+/// read access token as a bearer token.
+#[derive(Clone)]
+pub struct TmdbLookupProvider {
+    client: reqwest::Client,
+    base_url: String,
+    api_key: Option<String>,
+    id_matching: IdMatchingConfig,
+    /// A rejected key (or missing one) is warned about once, not for every title
+    warned_unauthorized: Arc<AtomicBool>,
+}
+
+type LookupError = Box<dyn std::error::Error + Send + Sync>;
+
+impl TmdbLookupProvider {
+    /// The lookups `[sources.tmdb]` configures, or `None` without an `api_key`: TMDB
+    /// rejects requests that carry none
+    pub fn from_config(config: &Config) -> Option<Self> {
+        let api_key = config.tmdb_api_key()?;
+        Some(Self::new(config.tmdb_base_url(), Some(api_key)).with_id_matching(config.id_matching.clone()))
+    }
+
+    pub fn new(base_url: &str, api_key: Option<&str>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key: api_key.map(str::to_string),
+            id_matching: IdMatchingConfig::default(),
+            warned_unauthorized: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Weights for choosing among several search results
+    pub fn with_id_matching(mut self, id_matching: IdMatchingConfig) -> Self {
+        self.id_matching = id_matching;
+        self
+    }
+
+    async fn get_json(&self, path: &str, query: &[(&str, String)]) -> Result<serde_json::Value, LookupError> {
+        let mut request = self.client.get(format!("{}{}", self.base_url, path)).query(query);
+        match self.api_key.as_deref() {
+            // v4 tokens are JWTs
+            Some(token) if token.starts_with("eyJ") => request = request.bearer_auth(token),
+            Some(key) => request = request.query(&[("api_key", key)]),
+            None => {}
+        }
+        let response = request.send().await?;
+        let status = response.status();
+        if status == reqwest::StatusCode::UNAUTHORIZED && !self.warned_unauthorized.swap(true, Ordering::Relaxed) {
+            warn!("TMDB rejected an ID lookup (401 Unauthorized); set a valid [sources.tmdb] api_key to resolve titles on TMDB");
+        }
+        if !status.is_success() {
+            return Err(format!("TMDB {} returned {}", path, status).into());
+        }
+        Ok(response.json().await?)
+    }
+}
+
+/// The year of a TMDB `release_date` or `first_air_date`
+fn tmdb_year(result: &serde_json::Value, field: &str) -> Option<u32> {
+    result.get(field)?.as_str()?.get(..4)?.parse().ok()
+}
+
+#[async_trait]
+impl IdLookupProvider for TmdbLookupProvider {
+    async fn lookup_ids(
+        &self,
+        title: &str,
+        year: Option<u32>,
+        media_type: &MediaType,
+    ) -> Result<Option<MediaIds>, LookupError> {
+        let (kind, year_param, date_field) = match media_type {
+            MediaType::Movie => ("movie", "year", "release_date"),
+            MediaType::Show => ("tv", "first_air_date_year", "first_air_date"),
+            // Episodes are resolved through their show
+            MediaType::Episode { .. } => return Ok(None),
+        };
+        let mut query = vec![("query", title.to_string()), ("language", "en-US".to_string())];
+        if let Some(year) = year {
+            query.push((year_param, year.to_string()));
+        }
+        let search = self.get_json(&format!("/search/{}", kind), &query).await?;
+        let candidates = search.get("results").and_then(|results| results.as_array())
+            .map(|results| results.iter()
+                .map(|result| TitleCandidate {
+                    // Movies have a `title`, shows a `name`
+                    title: result.get("title").or_else(|| result.get("name"))
+                        .and_then(|title| title.as_str())
+                        .map(str::to_string),
+                    year: tmdb_year(result, date_field),
+                    popularity: result.get("popularity").and_then(|p| p.as_f64()),
+                    item: result,
+                })
+                .collect())
+            .unwrap_or_default();
+        let Some(best) = pick_best_candidate(candidates, title, year, &self.id_matching) else {
+            return Ok(None);
+        };
+        let Some(tmdb_id) = best.get("id").and_then(|id| id.as_u64()) else {
+            return Ok(None);
+        };
+
+        let external_ids = self.get_json(&format!("/{}/{}/external_ids", kind, tmdb_id), &[]).await?;
+        let imdb_id = external_ids.get("imdb_id").and_then(|id| id.as_str())
+            .and_then(media_sync_models::normalize_imdb_id);
+        debug!("TMDB lookup for '{}' (year: {:?}) found tmdb={} imdb={:?}", title, year, tmdb_id, imdb_id);
+        Ok(Some(MediaIds {
+            imdb_id,
+            tmdb_id: u32::try_from(tmdb_id).ok(),
+            tvdb_id: external_ids.get("tvdb_id").and_then(|id| id.as_u64()).and_then(|id| u32::try_from(id).ok()),
+            title: Some(title.to_string()),
+            year: tmdb_year(best, date_field).or(year),
+            media_type: Some(media_type.clone()),
+            ..MediaIds::default()
+        }))
+    }
+
+    fn lookup_provider_name(&self) -> &str {
+        "tmdb"
+    }
+
+    async fn lookup_by_imdb_id(
+        &self,
+        imdb_id: &str,
+        media_type: &MediaType,
+    ) -> Result<Option<(String, Option<u32>, MediaIds)>, LookupError> {
+        let (results_field, title_field, date_field) = match media_type {
+            MediaType::Movie => ("movie_results", "title", "release_date"),
+            MediaType::Show => ("tv_results", "name", "first_air_date"),
+            MediaType::Episode { .. } => return Ok(None),
+        };
+        let found = self.get_json(&format!("/find/{}", imdb_id), &[("external_source", "imdb_id".to_string())]).await?;
+        let Some(result) = found.get(results_field).and_then(|results| results.as_array()).and_then(|results| results.first()) else {
+            return Ok(None);
+        };
+        let Some(title) = result.get(title_field).and_then(|title| title.as_str()) else {
+            return Ok(None);
+        };
+        let year = tmdb_year(result, date_field);
+        let ids = MediaIds {
+            imdb_id: Some(imdb_id.to_string()),
+            tmdb_id: result.get("id").and_then(|id| id.as_u64()).and_then(|id| u32::try_from(id).ok()),
+            title: Some(title.to_string()),
+            year,
+            media_type: Some(media_type.clone()),
+            ..MediaIds::default()
+        };
+        Ok(Some((title.to_string(), year, ids)))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::serve_json_routes;
+
+    #[tokio::test]
+    async fn test_tmdb_lookup_picks_the_year_and_reads_external_ids() {
+        let (base, server) = serve_json_routes(vec![
+            ("/3/search/movie", r#"{"results": [
+                {"id": 1, "title": "Dune", "release_date": "1984-12-14", "popularity": 20.0},
+                {"id": 438631, "title": "Dune", "release_date": "2021-09-15", "popularity": 90.0}
+            ]}"#),
+            ("/3/movie/438631/external_ids", r#"{"imdb_id": "tt1160419", "tvdb_id": null}"#),
+        ]);
+        let provider = TmdbLookupProvider::new(&format!("{}/3", base), Some("secret"));
+
+        let ids = provider.lookup_ids("Dune", Some(2021), &MediaType::Movie).await.unwrap().unwrap();
+        assert_eq!(ids.imdb_id.as_deref(), Some("tt1160419"));
+        assert_eq!(ids.tmdb_id, Some(438631));
+        assert_eq!(ids.year, Some(2021));
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].path().contains("query=Dune") && requests[0].path().contains("year=2021"), "{}", requests[0].line);
+        assert!(requests.iter().all(|request| request.path().contains("api_key=secret")), "{:?}", requests);
+    }
+}
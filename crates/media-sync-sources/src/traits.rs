@@ -1,7 +1,56 @@
 use async_trait::async_trait;
-use media_sync_models::{Rating, Review, WatchHistory, WatchlistItem};
+use media_sync_models::{CustomList, Rating, Review, WatchHistory, WatchProgress, WatchlistItem};
 use crate::capabilities::CapabilityRegistry;
 
+/// A review `set_reviews` wrote: its position in the slice it was given, and the ID the
+/// source stored it under, when the source reports one
+#[derive(Debug, Clone, PartialEq)]
+pub struct WrittenReview {
+    pub index: usize,
+    pub review_id: Option<String>,
+}
+
+impl WrittenReview {
+    /// Every one of `reviews`, for sources that write all or nothing and report no IDs
+    pub fn all(reviews: &[Review]) -> Vec<WrittenReview> {
+        (0..reviews.len()).map(|index| WrittenReview { index, review_id: None }).collect()
+    }
+}
+
+/// The kinds of data a source can hold and sync
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DataType {
+    Watchlist,
+    Ratings,
+    Reviews,
+    WatchHistory,
+}
+
+impl DataType {
+    pub const ALL: [DataType; 4] = [
+        DataType::Watchlist,
+        DataType::Ratings,
+        DataType::Reviews,
+        DataType::WatchHistory,
+    ];
+
+    /// Name used in cache files, timestamps and logs
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DataType::Watchlist => "watchlist",
+            DataType::Ratings => "ratings",
+            DataType::Reviews => "reviews",
+            DataType::WatchHistory => "watch_history",
+        }
+    }
+}
+
+impl std::fmt::Display for DataType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 #[async_trait]
 pub trait MediaSource: Send + Sync + CapabilityRegistry {
     type Error: std::error::Error + Send + Sync + 'static + std::fmt::Display;
@@ -33,6 +82,17 @@ pub trait MediaSource: Send + Sync + CapabilityRegistry {
         self.supports_status_mapping()
     }
 
+    /// Data types this source can collect and distribute.
+    /// The orchestrator skips (source, data type) pairs not listed here instead of
+    /// calling the corresponding get/set methods. Defaults to all data types.
+    fn supported_data_types(&self) -> &'static [DataType] {
+        &DataType::ALL
+    }
+
+    fn supports_data_type(&self, data_type: DataType) -> bool {
+        self.supported_data_types().contains(&data_type)
+    }
+
     // Authentication
     async fn authenticate(&mut self) -> Result<(), Self::Error>;
     fn is_authenticated(&self) -> bool;
@@ -47,8 +107,44 @@ pub trait MediaSource: Send + Sync + CapabilityRegistry {
     async fn add_to_watchlist(&self, items: &[WatchlistItem]) -> Result<(), Self::Error>;
     async fn remove_from_watchlist(&self, items: &[WatchlistItem]) -> Result<(), Self::Error>;
     async fn set_ratings(&self, ratings: &[Rating]) -> Result<(), Self::Error>;
-    async fn set_reviews(&self, reviews: &[Review]) -> Result<(), Self::Error>;
+    /// Write `reviews`, returning the ones written. A review the source rejected or
+    /// already had is left out; an error means none were written.
+    async fn set_reviews(&self, reviews: &[Review]) -> Result<Vec<WrittenReview>, Self::Error>;
     async fn add_watch_history(&self, items: &[WatchHistory]) -> Result<(), Self::Error>;
+
+    // Show progress (optional - sources without it have none and ignore writes)
+    /// Whether the source keeps show progress; only these take part in `sync_show_progress`
+    fn supports_watch_progress(&self) -> bool {
+        false
+    }
+
+    /// The last watched episode of each show
+    async fn get_watch_progress(&self) -> Result<Vec<WatchProgress>, Self::Error> {
+        Ok(Vec::new())
+    }
+
+    /// Mark the last watched episode of each show as watched, so the source shows the
+    /// same progress. An episode already watched there is not written again.
+    async fn set_watch_progress(&self, _progress: &[WatchProgress]) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    // Custom lists (optional - sources without them have none and ignore writes)
+    /// Whether the source keeps named lists; only these take part in `sync_lists`
+    fn supports_lists(&self) -> bool {
+        false
+    }
+
+    /// The user's named lists with their items
+    async fn get_lists(&self) -> Result<Vec<CustomList>, Self::Error> {
+        Ok(Vec::new())
+    }
+
+    /// Add the items of each list to the user's list of the same name, creating lists
+    /// that don't exist yet. Items are never removed.
+    async fn set_lists(&self, _lists: &[CustomList]) -> Result<(), Self::Error> {
+        Ok(())
+    }
     
     // Cleanup/shutdown (optional - default implementation does nothing)
     // Called when sync job completes to free resources (e.g., close browser instances)
@@ -1,9 +1,13 @@
 use anyhow::{anyhow, Result};
-use media_sync_models::{Rating, Review, WatchHistory, WatchlistItem, MediaType};
+use media_sync_models::{CollectionItem, CustomList, Rating, Review, WatchHistory, WatchProgress, WatchlistItem, MediaType};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 use tracing::{debug, warn};
+use crate::capabilities::AiredEpisode;
+use crate::http_cache::HttpCache;
+use crate::retry::RetryPolicy;
+use crate::traits::WrittenReview;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TraktIds {
@@ -19,14 +23,14 @@ pub struct TraktIds {
     pub slug: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct TraktMovie {
     title: String,
     year: Option<u32>,
     ids: TraktIds,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct TraktShow {
     title: String,
     year: Option<u32>,
@@ -36,7 +40,7 @@ struct TraktShow {
     aired_episodes: Option<u32>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct TraktEpisode {
     title: String,
     year: Option<u32>,
@@ -47,18 +51,20 @@ struct TraktEpisode {
     first_aired: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct TraktWatchlistItem {
     #[serde(rename = "listed_at")]
     listed_at: String,
     #[serde(rename = "type")]
     item_type: String,
+    #[serde(default)]
+    notes: Option<String>,
     movie: Option<TraktMovie>,
     show: Option<TraktShow>,
     episode: Option<TraktEpisode>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct TraktRatingItem {
     #[serde(rename = "rated_at")]
     rated_at: String,
@@ -70,7 +76,7 @@ struct TraktRatingItem {
     episode: Option<TraktEpisode>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct TraktComment {
     #[serde(rename = "type")]
     item_type: String,
@@ -78,17 +84,60 @@ struct TraktComment {
     show: Option<TraktShow>,
     episode: Option<TraktEpisode>,
     comment: TraktCommentDetails,
-    #[serde(default)]
-    spoiler: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct TraktCommentDetails {
     id: u64,
     comment: String,
+    #[serde(default)]
+    spoiler: bool,
+    /// The commenter's rating of the title when they wrote it
+    #[serde(default)]
+    user_stats: Option<TraktCommentUserStats>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TraktCommentUserStats {
+    #[serde(default)]
+    rating: Option<u8>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct TraktLike {
+    liked_at: String,
+    comment: Option<TraktLikedComment>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct TraktLikedComment {
+    id: u64,
+}
+
+/// What a comment is about (`/comments/{id}/item`)
+#[derive(Debug, Clone, Deserialize)]
+struct TraktCommentItem {
+    #[serde(rename = "type")]
+    item_type: String,
+    movie: Option<TraktMovie>,
+    show: Option<TraktShow>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Tag on watchlist items for liked titles with `trakt.likes_as = "tag"`
+pub const LIKED_TAG: &str = "liked";
+
+/// A movie or show the user liked a comment about
+#[derive(Debug, Clone)]
+pub struct LikedTitle {
+    pub imdb_id: String,
+    pub ids: media_sync_models::MediaIds,
+    pub title: String,
+    pub year: Option<u32>,
+    pub media_type: MediaType,
+    pub liked_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct TraktHistoryItem {
     #[serde(rename = "watched_at")]
     watched_at: String,
@@ -99,6 +148,67 @@ struct TraktHistoryItem {
     episode: Option<TraktEpisode>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TraktCollectedMovie {
+    #[serde(rename = "collected_at")]
+    collected_at: String,
+    movie: TraktMovie,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TraktCollectedShow {
+    #[serde(rename = "last_collected_at")]
+    last_collected_at: String,
+    show: TraktShow,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct TraktWatchedShow {
+    show: TraktShow,
+    #[serde(default)]
+    seasons: Vec<TraktWatchedSeason>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct TraktWatchedSeason {
+    number: u32,
+    #[serde(default)]
+    episodes: Vec<TraktWatchedEpisode>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct TraktWatchedEpisode {
+    number: u32,
+    last_watched_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct TraktSeason {
+    number: u32,
+    #[serde(default)]
+    episodes: Vec<TraktSeasonEpisode>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct TraktSeasonEpisode {
+    number: u32,
+    title: Option<String>,
+    ids: TraktIds,
+    first_aired: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct TraktList {
+    name: String,
+    description: Option<String>,
+    ids: TraktListIds,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct TraktListIds {
+    slug: String,
+}
+
 /// Remove slashes from IMDB ID (Trakt sometimes includes them)
 fn remove_slashes(s: Option<String>) -> String {
     s.unwrap_or_default().replace('/', "")
@@ -150,6 +260,7 @@ pub async fn get_encoded_username(client: &Client, access_token: &str, client_id
 /// Fetch watchlist from Trakt
 pub async fn get_watchlist(
     client: &Client,
+    http_cache: &HttpCache,
     access_token: &str,
     encoded_username: &str,
     client_id: &str,
@@ -159,7 +270,7 @@ pub async fn get_watchlist(
         encoded_username
     );
 
-    let response = client
+    let request = client
         .get(&url)
         .header("Authorization", format!("Bearer {}", access_token))
         .header("trakt-api-version", "2")
@@ -168,88 +279,87 @@ pub async fn get_watchlist(
         .header("Accept-Language", "en-US,en;q=0.9")
         .header("Content-Type", "application/json")
         .header("Origin", "https://trakt.tv")
-        .header("Referer", "https://trakt.tv/")
-        .send()
-        .await?;
-
-    if !response.status().is_success() {
-        let status = response.status();
-        let error_text = response.text().await.unwrap_or_default();
-        return Err(anyhow!("Failed to fetch watchlist: {} - {}", status, error_text));
-    }
-
-    let items: Vec<TraktWatchlistItem> = response.json().await?;
+        .header("Referer", "https://trakt.tv/");
+    let items: Vec<TraktWatchlistItem> = http_cache.get_json("watchlist", request).await?;
     let mut watchlist = Vec::new();
-
     for item in items {
-        let (trakt_ids, imdb_id, title, year, media_type) = match item.item_type.as_str() {
-            "movie" => {
-                let movie = item.movie.ok_or_else(|| anyhow!("Missing movie data"))?;
-                (
-                    movie.ids.clone(),
-                    remove_slashes(movie.ids.imdb.clone()),
-                    movie.title,
-                    movie.year,
-                    MediaType::Movie,
-                )
-            }
-            "show" => {
-                let show = item.show.ok_or_else(|| anyhow!("Missing show data"))?;
-                (
-                    show.ids.clone(),
-                    remove_slashes(show.ids.imdb.clone()),
-                    show.title,
-                    show.year,
-                    MediaType::Show,
-                )
-            }
-            "episode" => {
-                let episode = item.episode.ok_or_else(|| anyhow!("Missing episode data"))?;
-                let show = item.show.ok_or_else(|| anyhow!("Missing show data for episode"))?;
-                (
-                    episode.ids.clone(),
-                    remove_slashes(episode.ids.imdb.clone()),
-                    format!("{}: {}", show.title, episode.title),
-                    episode.year,
-                    MediaType::Episode {
-                        season: episode.season.unwrap_or(0),
-                        episode: episode.number.unwrap_or(0),
-                    },
-                )
-            }
-            _ => continue,
-        };
-
-        // Extract MediaIds from TraktIds
-        let media_ids = extract_media_ids_from_trakt_ids(&trakt_ids);
-        
-        // Don't skip items if they have any IDs (not just imdb_id)
-        if media_ids.is_empty() {
-            continue;
+        if let Some(item) = watchlist_item_from_trakt(item)? {
+            watchlist.push(item);
         }
+    }
 
-        let date_added = DateTime::parse_from_rfc3339(&item.listed_at)
-            .map_err(|e| anyhow!("Failed to parse date: {}", e))?
-            .with_timezone(&Utc);
+    Ok(watchlist)
+}
 
-        watchlist.push(WatchlistItem {
-            imdb_id: imdb_id.clone(),
-            ids: Some(media_ids),
-            title,
-            year,
-            media_type,
-            date_added,
-            source: "trakt".to_string(),
-            status: Some(media_sync_models::NormalizedStatus::Watchlist), // Trakt watchlist items are always "Watchlist" status
-        });
+/// Convert a watchlist or list entry; `None` for unsupported types and items without IDs
+fn watchlist_item_from_trakt(item: TraktWatchlistItem) -> Result<Option<WatchlistItem>> {
+    let (trakt_ids, imdb_id, title, year, media_type) = match item.item_type.as_str() {
+        "movie" => {
+            let movie = item.movie.ok_or_else(|| anyhow!("Missing movie data"))?;
+            (
+                movie.ids.clone(),
+                remove_slashes(movie.ids.imdb.clone()),
+                movie.title,
+                movie.year,
+                MediaType::Movie,
+            )
+        }
+        "show" => {
+            let show = item.show.ok_or_else(|| anyhow!("Missing show data"))?;
+            (
+                show.ids.clone(),
+                remove_slashes(show.ids.imdb.clone()),
+                show.title,
+                show.year,
+                MediaType::Show,
+            )
+        }
+        "episode" => {
+            let episode = item.episode.ok_or_else(|| anyhow!("Missing episode data"))?;
+            let show = item.show.ok_or_else(|| anyhow!("Missing show data for episode"))?;
+            (
+                episode.ids.clone(),
+                remove_slashes(episode.ids.imdb.clone()),
+                format!("{}: {}", show.title, episode.title),
+                episode.year,
+                MediaType::Episode {
+                    season: episode.season.unwrap_or(0),
+                    episode: episode.number.unwrap_or(0),
+                },
+            )
+        }
+        _ => return Ok(None),
+    };
+
+    // Extract MediaIds from TraktIds
+    let media_ids = extract_media_ids_from_trakt_ids(&trakt_ids);
+    
+    // Don't skip items if they have any IDs (not just imdb_id)
+    if media_ids.is_empty() {
+        return Ok(None);
     }
 
-    Ok(watchlist)
+    let date_added = DateTime::parse_from_rfc3339(&item.listed_at)
+        .map_err(|e| anyhow!("Failed to parse date: {}", e))?
+        .with_timezone(&Utc);
+
+    Ok(Some(WatchlistItem {
+        imdb_id: imdb_id.clone(),
+        ids: Some(media_ids),
+        title,
+        year,
+        media_type,
+        date_added,
+        source: "trakt".to_string(),
+        status: Some(media_sync_models::NormalizedStatus::Watchlist), // Trakt watchlist items are always "Watchlist" status
+        tags: item.notes.as_deref().map(media_sync_models::tags_from_note).unwrap_or_default(),
+    }))
 }
 
 /// Fetch ratings from Trakt
 pub async fn get_ratings(
     client: &Client,
+    http_cache: &HttpCache,
     access_token: &str,
     encoded_username: &str,
     client_id: &str,
@@ -260,7 +370,7 @@ pub async fn get_ratings(
         encoded_username
     );
 
-    let response = client
+    let request = client
         .get(&url)
         .header("Authorization", format!("Bearer {}", access_token))
         .header("trakt-api-version", "2")
@@ -269,17 +379,8 @@ pub async fn get_ratings(
         .header("Accept-Language", "en-US,en;q=0.9")
         .header("Content-Type", "application/json")
         .header("Origin", "https://trakt.tv")
-        .header("Referer", "https://trakt.tv/")
-        .send()
-        .await?;
-
-    if !response.status().is_success() {
-        let status = response.status();
-        let error_text = response.text().await.unwrap_or_default();
-        return Err(anyhow!("Failed to fetch ratings: {} - {}", status, error_text));
-    }
-
-    let items: Vec<TraktRatingItem> = response.json().await?;
+        .header("Referer", "https://trakt.tv/");
+    let items: Vec<TraktRatingItem> = http_cache.get_json("ratings", request).await?;
     let mut ratings = Vec::new();
     let mut items_with_empty_imdb = 0;
 
@@ -353,9 +454,11 @@ pub async fn get_ratings(
             imdb_id: imdb_id.clone(),
             ids: Some(media_ids),
             rating: item.rating,
+            rating_f64: None,
             date_added,
             media_type,
             source: media_sync_models::RatingSource::Trakt,
+            inferred: false,
         });
         
         // Log first few ratings being added
@@ -543,7 +646,9 @@ pub async fn get_comments(
                 date_added: Utc::now(),
                 media_type: media_type.clone(),
                 source: "trakt".to_string(),
-                is_spoiler: item.spoiler,
+                is_spoiler: item.comment.spoiler,
+                rating: item.comment.user_stats.as_ref().and_then(|stats| stats.rating),
+                review_id: Some(item.comment.id.to_string()),
             });
             
             if all_comments.len() <= 5 {
@@ -553,7 +658,7 @@ pub async fn get_comments(
                     imdb_id,
                     item.comment.comment.len(),
                     media_type,
-                    item.spoiler
+                    item.comment.spoiler
                 );
             }
         }
@@ -574,6 +679,138 @@ pub async fn get_comments(
     Ok(all_comments)
 }
 
+/// Titles of the comments the user liked. Likes on comments about episodes, seasons or
+/// lists, and likes on lists themselves, have no single title and are skipped.
+pub async fn get_liked_titles(
+    client: &Client,
+    access_token: &str,
+    encoded_username: &str,
+    client_id: &str,
+) -> Result<Vec<LikedTitle>> {
+    let mut likes = Vec::new();
+    let mut page = 1;
+    loop {
+        let url = format!(
+            "https://api.trakt.tv/users/{}/likes/comments?page={}&limit=100",
+            encoded_username, page
+        );
+        let response = client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", access_token))
+            .header("trakt-api-version", "2")
+            .header("trakt-api-key", client_id)
+            .header("Content-Type", "application/json")
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(anyhow!("Failed to fetch likes: {} - {}", status, error_text));
+        }
+        let total_pages: u32 = response
+            .headers()
+            .get("X-Pagination-Page-Count")
+            .and_then(|h| h.to_str().ok())
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(1);
+        let items: Vec<TraktLike> = response.json().await?;
+        likes.extend(items);
+        if page >= total_pages {
+            break;
+        }
+        page += 1;
+    }
+
+    let mut titles: Vec<LikedTitle> = Vec::new();
+    for like in likes {
+        let Some(comment) = like.comment else { continue };
+        let liked_at = DateTime::parse_from_rfc3339(&like.liked_at)
+            .map_err(|e| anyhow!("Failed to parse date: {}", e))?
+            .with_timezone(&Utc);
+        let response = client
+            .get(format!("https://api.trakt.tv/comments/{}/item", comment.id))
+            .header("trakt-api-version", "2")
+            .header("trakt-api-key", client_id)
+            .header("Content-Type", "application/json")
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            warn!("Trakt likes: failed to look up what comment {} is about: {}", comment.id, response.status());
+            continue;
+        }
+        let item: TraktCommentItem = response.json().await?;
+        let (ids, title, year, media_type) = match (item.item_type.as_str(), item.movie, item.show) {
+            ("movie", Some(movie), _) => (movie.ids, movie.title, movie.year, MediaType::Movie),
+            ("show", _, Some(show)) => (show.ids, show.title, show.year, MediaType::Show),
+            _ => continue,
+        };
+        let media_ids = extract_media_ids_from_trakt_ids(&ids);
+        // Several liked comments on one title count once, from the earliest like
+        if let Some(existing) = titles.iter_mut().find(|t| t.ids.trakt_id.is_some() && t.ids.trakt_id == media_ids.trakt_id) {
+            existing.liked_at = existing.liked_at.min(liked_at);
+            continue;
+        }
+        titles.push(LikedTitle {
+            imdb_id: remove_slashes(ids.imdb.clone()),
+            ids: media_ids,
+            title,
+            year,
+            media_type,
+            liked_at,
+        });
+    }
+    debug!("Fetched Trakt likes: liked_titles={}", titles.len());
+    Ok(titles)
+}
+
+fn is_same_title(liked: &LikedTitle, imdb_id: &str, ids: Option<&media_sync_models::MediaIds>) -> bool {
+    if !liked.imdb_id.is_empty() && liked.imdb_id == imdb_id {
+        return true;
+    }
+    liked.ids.trakt_id.is_some() && ids.is_some_and(|ids| ids.trakt_id == liked.ids.trakt_id)
+}
+
+/// A 10/10 rating, dated when it was liked, for each liked title without a rating. They
+/// are `inferred`, so a real rating of the title on any source wins over them.
+pub fn liked_as_ratings(liked: &[LikedTitle], ratings: &[Rating]) -> Vec<Rating> {
+    liked.iter()
+        .filter(|title| !ratings.iter().any(|rating| is_same_title(title, &rating.imdb_id, rating.ids.as_ref())))
+        .map(|title| Rating {
+            imdb_id: title.imdb_id.clone(),
+            ids: Some(title.ids.clone()),
+            rating: 10,
+            rating_f64: None,
+            date_added: title.liked_at,
+            media_type: title.media_type.clone(),
+            source: media_sync_models::RatingSource::Trakt,
+            inferred: true,
+        })
+        .collect()
+}
+
+/// Tag watchlist items for liked titles `liked`, adding the titles not on the watchlist
+pub fn merge_liked_into_watchlist(watchlist: &mut Vec<WatchlistItem>, liked: &[LikedTitle]) {
+    for title in liked {
+        if let Some(item) = watchlist.iter_mut().find(|item| is_same_title(title, &item.imdb_id, item.ids.as_ref())) {
+            if !item.tags.iter().any(|tag| tag == LIKED_TAG) {
+                item.tags.push(LIKED_TAG.to_string());
+            }
+            continue;
+        }
+        watchlist.push(WatchlistItem {
+            imdb_id: title.imdb_id.clone(),
+            ids: Some(title.ids.clone()),
+            title: title.title.clone(),
+            year: title.year,
+            media_type: title.media_type.clone(),
+            date_added: title.liked_at,
+            source: "trakt".to_string(),
+            status: Some(media_sync_models::NormalizedStatus::Watchlist),
+            tags: vec![LIKED_TAG.to_string()],
+        });
+    }
+}
+
 /// Fetch watch history from Trakt with pagination
 pub async fn get_watch_history(
     client: &Client,
@@ -620,7 +857,7 @@ pub async fn get_watch_history(
         let items: Vec<TraktHistoryItem> = response.json().await?;
 
         for item in items {
-            let (trakt_ids, imdb_id, media_type, _trakt_id) = match item.item_type.as_str() {
+            let (trakt_ids, imdb_id, media_type, _trakt_id, show_ids) = match item.item_type.as_str() {
                 "movie" => {
                     let movie = item.movie.ok_or_else(|| anyhow!("Missing movie data"))?;
                     let trakt_id = movie.ids.trakt;
@@ -635,6 +872,7 @@ pub async fn get_watch_history(
                         remove_slashes(movie.ids.imdb.clone()),
                         MediaType::Movie,
                         trakt_id,
+                        None,
                     )
                 }
                 "episode" => {
@@ -666,6 +904,7 @@ pub async fn get_watch_history(
                             episode: episode.number.unwrap_or(0),
                         },
                         episode_trakt_id,
+                        Some(extract_media_ids_from_trakt_ids(&show.ids)),
                     )
                 }
                 _ => continue,
@@ -707,6 +946,8 @@ pub async fn get_watch_history(
                 watched_at,
                 media_type,
                 source: "trakt".to_string(),
+                watched_percent: None,
+                show_ids,
             });
             
             // Log first few items being added
@@ -737,45 +978,43 @@ pub async fn get_watch_history(
     Ok(all_history)
 }
 
-/// Add items to Trakt watchlist
-pub async fn add_to_watchlist(
-    client: &Client,
-    access_token: &str,
-    items: &[WatchlistItem],
-    client_id: &str,
-) -> Result<()> {
+/// The `ids` object Trakt matches an item by: every known ID, or the IMDB ID alone
+fn trakt_ids_object(imdb_id: &str, ids: &Option<media_sync_models::MediaIds>) -> serde_json::Map<String, serde_json::Value> {
+    let mut ids_obj = serde_json::Map::new();
+
+    // Use MediaIds if available, otherwise fall back to imdb_id
+    if let Some(ref media_ids) = ids {
+        if let Some(ref imdb) = media_ids.imdb_id {
+            ids_obj.insert("imdb".to_string(), serde_json::Value::String(imdb.clone()));
+        }
+        if let Some(trakt) = media_ids.trakt_id {
+            ids_obj.insert("trakt".to_string(), serde_json::Value::Number(trakt.into()));
+        }
+        if let Some(tmdb) = media_ids.tmdb_id {
+            ids_obj.insert("tmdb".to_string(), serde_json::Value::Number(tmdb.into()));
+        }
+        if let Some(tvdb) = media_ids.tvdb_id {
+            ids_obj.insert("tvdb".to_string(), serde_json::Value::Number(tvdb.into()));
+        }
+        if let Some(ref slug) = media_ids.slug {
+            ids_obj.insert("slug".to_string(), serde_json::Value::String(slug.clone()));
+        }
+    } else {
+        // Fallback to imdb_id if MediaIds not available
+        ids_obj.insert("imdb".to_string(), serde_json::Value::String(imdb_id.to_string()));
+    }
+    ids_obj
+}
+
+/// The `{movies, shows, episodes}` body Trakt's watchlist and list endpoints take
+fn watchlist_payload(items: &[WatchlistItem]) -> serde_json::Value {
     let mut movies = Vec::new();
     let mut shows = Vec::new();
     let mut episodes = Vec::new();
 
     for item in items {
-        // Build IDs object with all available IDs from MediaIds
-        let mut ids_obj = serde_json::Map::new();
-        
-        // Use MediaIds if available, otherwise fall back to imdb_id
-        if let Some(ref media_ids) = item.ids {
-            if let Some(ref imdb) = media_ids.imdb_id {
-                ids_obj.insert("imdb".to_string(), serde_json::Value::String(imdb.clone()));
-            }
-            if let Some(trakt) = media_ids.trakt_id {
-                ids_obj.insert("trakt".to_string(), serde_json::Value::Number(trakt.into()));
-            }
-            if let Some(tmdb) = media_ids.tmdb_id {
-                ids_obj.insert("tmdb".to_string(), serde_json::Value::Number(tmdb.into()));
-            }
-            if let Some(tvdb) = media_ids.tvdb_id {
-                ids_obj.insert("tvdb".to_string(), serde_json::Value::Number(tvdb.into()));
-            }
-            if let Some(ref slug) = media_ids.slug {
-                ids_obj.insert("slug".to_string(), serde_json::Value::String(slug.clone()));
-            }
-        } else {
-            // Fallback to imdb_id if MediaIds not available
-            ids_obj.insert("imdb".to_string(), serde_json::Value::String(item.imdb_id.clone()));
-        }
-        
         let id_obj = serde_json::json!({
-            "ids": ids_obj
+            "ids": trakt_ids_object(&item.imdb_id, &item.ids)
         });
 
         match &item.media_type {
@@ -785,25 +1024,37 @@ pub async fn add_to_watchlist(
         }
     }
 
-    let payload = serde_json::json!({
+    serde_json::json!({
         "movies": movies,
         "shows": shows,
         "episodes": episodes
-    });
+    })
+}
 
-    let response = client
-        .post("https://api.trakt.tv/sync/watchlist")
-        .header("Authorization", format!("Bearer {}", access_token))
-        .header("trakt-api-version", "2")
-        .header("trakt-api-key", client_id)
-        .header("Accept", "application/json")
-        .header("Accept-Language", "en-US,en;q=0.9")
-        .header("Content-Type", "application/json")
-        .header("Origin", "https://trakt.tv")
-        .header("Referer", "https://trakt.tv/")
-        .json(&payload)
-        .send()
-        .await?;
+/// Add items to Trakt watchlist
+pub async fn add_to_watchlist(
+    client: &Client,
+    access_token: &str,
+    items: &[WatchlistItem],
+    client_id: &str,
+    retry: &RetryPolicy,
+) -> Result<()> {
+    let payload = watchlist_payload(items);
+
+    let response = retry.send("Trakt watchlist add", || {
+        client
+            .post("https://api.trakt.tv/sync/watchlist")
+            .header("Authorization", format!("Bearer {}", access_token))
+            .header("trakt-api-version", "2")
+            .header("trakt-api-key", client_id)
+            .header("Accept", "application/json")
+            .header("Accept-Language", "en-US,en;q=0.9")
+            .header("Content-Type", "application/json")
+            .header("Origin", "https://trakt.tv")
+            .header("Referer", "https://trakt.tv/")
+            .json(&payload)
+    })
+    .await?;
 
     let status = response.status();
     if !status.is_success() {
@@ -897,6 +1148,7 @@ pub async fn set_ratings(
     access_token: &str,
     ratings: &[Rating],
     client_id: &str,
+    retry: &RetryPolicy,
 ) -> Result<()> {
     let mut movies = Vec::new();
     let mut shows = Vec::new();
@@ -946,19 +1198,20 @@ pub async fn set_ratings(
         "episodes": episodes
     });
 
-    let response = client
-        .post("https://api.trakt.tv/sync/ratings")
-        .header("Authorization", format!("Bearer {}", access_token))
-        .header("trakt-api-version", "2")
-        .header("trakt-api-key", client_id)
-        .header("Accept", "application/json")
-        .header("Accept-Language", "en-US,en;q=0.9")
-        .header("Content-Type", "application/json")
-        .header("Origin", "https://trakt.tv")
-        .header("Referer", "https://trakt.tv/")
-        .json(&payload)
-        .send()
-        .await?;
+    let response = retry.send("Trakt ratings", || {
+        client
+            .post("https://api.trakt.tv/sync/ratings")
+            .header("Authorization", format!("Bearer {}", access_token))
+            .header("trakt-api-version", "2")
+            .header("trakt-api-key", client_id)
+            .header("Accept", "application/json")
+            .header("Accept-Language", "en-US,en;q=0.9")
+            .header("Content-Type", "application/json")
+            .header("Origin", "https://trakt.tv")
+            .header("Referer", "https://trakt.tv/")
+            .json(&payload)
+    })
+    .await?;
 
     let status = response.status();
     if !status.is_success() {
@@ -969,16 +1222,22 @@ pub async fn set_ratings(
     Ok(())
 }
 
-/// Add comments/reviews to Trakt
+/// Add comments/reviews to Trakt, one request per review.
+/// A rejected review is logged and skipped so it doesn't abort the rest of the
+/// batch; an error is returned only when every review was rejected.
 pub async fn add_comments(
     client: &Client,
     access_token: &str,
     reviews: &[Review],
     client_id: &str,
-) -> Result<()> {
-    for review in reviews {
+) -> Result<Vec<WrittenReview>> {
+    let mut written = Vec::new();
+    let mut failed: Vec<(String, String)> = Vec::new();
+    for (index, review) in reviews.iter().enumerate() {
+        // Trakt takes no rating with a comment; the title's rating is synced as a rating
         let mut payload = serde_json::json!({
-            "comment": review.content
+            "comment": review.content,
+            "spoiler": review.is_spoiler
         });
 
         match &review.media_type {
@@ -1017,16 +1276,50 @@ pub async fn add_comments(
             .header("Referer", "https://trakt.tv/")
             .json(&payload)
             .send()
-            .await?;
+            .await;
+
+        let response = match response {
+            Ok(response) => response,
+            Err(e) => {
+                warn!("Failed to add Trakt comment for {}: {}", review.imdb_id, e);
+                failed.push((review.imdb_id.clone(), e.to_string()));
+                continue;
+            }
+        };
 
         let status = response.status();
         if !status.is_success() {
             let error_text = response.text().await.unwrap_or_default();
-            return Err(anyhow!("Failed to add comment: {} - {}", status, error_text));
+            warn!("Trakt rejected comment for {}: {} - {}", review.imdb_id, status, error_text);
+            failed.push((review.imdb_id.clone(), format!("{} - {}", status, error_text)));
+            continue;
         }
+        // The new comment, whose ID `get_reviews` reports as the review ID
+        let review_id = response.json::<serde_json::Value>().await.ok()
+            .and_then(|comment| comment.get("id").and_then(|id| id.as_u64()))
+            .map(|id| id.to_string());
+        written.push(WrittenReview { index, review_id });
     }
 
-    Ok(())
+    if !failed.is_empty() {
+        let failed_ids: Vec<&str> = failed.iter().map(|(id, _)| id.as_str()).collect();
+        if failed.len() == reviews.len() {
+            return Err(anyhow!(
+                "Failed to add all {} comments (first error: {})",
+                reviews.len(),
+                failed[0].1
+            ));
+        }
+        warn!(
+            "Added {} of {} Trakt comments; skipped {}: {}",
+            reviews.len() - failed.len(),
+            reviews.len(),
+            failed.len(),
+            failed_ids.join(", ")
+        );
+    }
+
+    Ok(written)
 }
 
 /// Add watch history to Trakt
@@ -1035,6 +1328,7 @@ pub async fn add_watch_history(
     access_token: &str,
     items: &[WatchHistory],
     client_id: &str,
+    retry: &RetryPolicy,
 ) -> Result<()> {
     use tracing::{debug, warn};
     
@@ -1081,16 +1375,166 @@ pub async fn add_watch_history(
         "episodes": episodes
     });
 
+    let response = retry.send_non_idempotent("Trakt history add", || {
+        client
+            .post("https://api.trakt.tv/sync/history")
+            .header("Authorization", format!("Bearer {}", access_token))
+            .header("trakt-api-version", "2")
+            .header("trakt-api-key", client_id)
+            .header("Accept", "application/json")
+            .header("Accept-Language", "en-US,en;q=0.9")
+            .header("Content-Type", "application/json")
+            .header("Origin", "https://trakt.tv")
+            .header("Referer", "https://trakt.tv/")
+            .json(&payload)
+    })
+    .await?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(anyhow!("Failed to add watch history: {} - {}", status, error_text));
+    }
+
+    Ok(())
+}
+
+/// Plays removed per `/sync/history/remove` call
+const HISTORY_REMOVE_BATCH: usize = 500;
+
+#[derive(Debug, Clone, Deserialize)]
+struct TraktHistoryEntryId {
+    id: u64,
+}
+
+/// History IDs of every play watched before `cutoff`
+pub async fn get_history_ids_before(
+    client: &Client,
+    access_token: &str,
+    client_id: &str,
+    cutoff: DateTime<Utc>,
+) -> Result<Vec<u64>> {
+    let mut ids = Vec::new();
+    let mut page = 1;
+    loop {
+        let response = client
+            .get(history_before_url(cutoff, page))
+            .header("Authorization", format!("Bearer {}", access_token))
+            .header("trakt-api-version", "2")
+            .header("trakt-api-key", client_id)
+            .header("Content-Type", "application/json")
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!("Failed to fetch watch history: {}", response.status()));
+        }
+        let total_pages: u32 = response
+            .headers()
+            .get("X-Pagination-Page-Count")
+            .and_then(|h| h.to_str().ok())
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(1);
+        let entries: Vec<TraktHistoryEntryId> = response.json().await?;
+        ids.extend(entries.into_iter().map(|entry| entry.id));
+
+        if page >= total_pages {
+            break;
+        }
+        page += 1;
+    }
+    Ok(ids)
+}
+
+fn history_before_url(cutoff: DateTime<Utc>, page: u32) -> String {
+    format!(
+        "https://api.trakt.tv/sync/history?end_at={}&page={}&limit=100",
+        cutoff.format("%Y-%m-%dT%H:%M:%S%.3fZ"),
+        page
+    )
+}
+
+/// Remove plays by history ID. Only those plays go; other plays of the same titles stay.
+pub async fn remove_history_ids(
+    client: &Client,
+    access_token: &str,
+    client_id: &str,
+    ids: &[u64],
+) -> Result<()> {
+    for batch in ids.chunks(HISTORY_REMOVE_BATCH) {
+        let response = client
+            .post("https://api.trakt.tv/sync/history/remove")
+            .header("Authorization", format!("Bearer {}", access_token))
+            .header("trakt-api-version", "2")
+            .header("trakt-api-key", client_id)
+            .header("Content-Type", "application/json")
+            .json(&serde_json::json!({ "ids": batch }))
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(anyhow!("Failed to remove watch history: {} - {}", status, error_text));
+        }
+        debug!("Removed {} plays from Trakt watch history", batch.len());
+    }
+    Ok(())
+}
+
+/// Body for a `/scrobble/*` call. Shows can't be played, so they have none.
+fn scrobble_payload(item: &WatchHistory, progress: f32) -> Option<serde_json::Value> {
+    let mut ids = serde_json::Map::new();
+    let imdb = item.ids.as_ref()
+        .and_then(|ids| ids.imdb_id.clone())
+        .unwrap_or_else(|| item.imdb_id.clone());
+    if !imdb.is_empty() {
+        ids.insert("imdb".to_string(), serde_json::json!(imdb));
+    }
+    if let Some(media_ids) = &item.ids {
+        if let Some(trakt) = media_ids.trakt_id {
+            ids.insert("trakt".to_string(), serde_json::json!(trakt));
+        }
+        if let Some(tmdb) = media_ids.tmdb_id {
+            ids.insert("tmdb".to_string(), serde_json::json!(tmdb));
+        }
+        if let Some(tvdb) = media_ids.tvdb_id {
+            ids.insert("tvdb".to_string(), serde_json::json!(tvdb));
+        }
+    }
+    if ids.is_empty() {
+        return None;
+    }
+
+    let key = match item.media_type {
+        MediaType::Movie => "movie",
+        MediaType::Episode { .. } => "episode",
+        MediaType::Show => return None,
+    };
+    Some(serde_json::json!({
+        key: { "ids": ids },
+        "progress": progress.clamp(0.0, 100.0),
+    }))
+}
+
+/// Save playback progress for a partially watched movie or episode.
+/// Uses `/scrobble/pause`, which Trakt shows as in progress without marking it watched.
+pub async fn scrobble_pause(
+    client: &Client,
+    access_token: &str,
+    item: &WatchHistory,
+    progress: f32,
+    client_id: &str,
+) -> Result<()> {
+    let payload = scrobble_payload(item, progress)
+        .ok_or_else(|| anyhow!("Cannot scrobble {} ({:?}): no usable IDs", item.imdb_id, item.media_type))?;
+
     let response = client
-        .post("https://api.trakt.tv/sync/history")
+        .post("https://api.trakt.tv/scrobble/pause")
         .header("Authorization", format!("Bearer {}", access_token))
         .header("trakt-api-version", "2")
         .header("trakt-api-key", client_id)
-        .header("Accept", "application/json")
-        .header("Accept-Language", "en-US,en;q=0.9")
         .header("Content-Type", "application/json")
-        .header("Origin", "https://trakt.tv")
-        .header("Referer", "https://trakt.tv/")
         .json(&payload)
         .send()
         .await?;
@@ -1098,12 +1542,450 @@ pub async fn add_watch_history(
     let status = response.status();
     if !status.is_success() {
         let error_text = response.text().await.unwrap_or_default();
-        return Err(anyhow!("Failed to add watch history: {} - {}", status, error_text));
+        return Err(anyhow!("Failed to scrobble progress: {} - {}", status, error_text));
+    }
+
+    debug!("Saved {:.0}% progress for {} on Trakt", progress, item.imdb_id);
+    Ok(())
+}
+
+async fn fetch_lists(
+    client: &Client,
+    access_token: &str,
+    encoded_username: &str,
+    client_id: &str,
+) -> Result<Vec<TraktList>> {
+    let response = client
+        .get(format!("https://api.trakt.tv/users/{}/lists", encoded_username))
+        .header("Authorization", format!("Bearer {}", access_token))
+        .header("trakt-api-version", "2")
+        .header("trakt-api-key", client_id)
+        .header("Content-Type", "application/json")
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(anyhow!("Failed to fetch lists: {} - {}", status, error_text));
+    }
+    Ok(response.json().await?)
+}
+
+/// Fetch the user's personal lists with their items
+pub async fn get_lists(
+    client: &Client,
+    http_cache: &HttpCache,
+    access_token: &str,
+    encoded_username: &str,
+    client_id: &str,
+) -> Result<Vec<CustomList>> {
+    let mut lists = Vec::new();
+    for list in fetch_lists(client, access_token, encoded_username, client_id).await? {
+        let request = client
+            .get(format!("https://api.trakt.tv/users/{}/lists/{}/items", encoded_username, list.ids.slug))
+            .header("Authorization", format!("Bearer {}", access_token))
+            .header("trakt-api-version", "2")
+            .header("trakt-api-key", client_id)
+            .header("Content-Type", "application/json");
+        let entries: Vec<TraktWatchlistItem> = http_cache.get_json(&format!("list '{}'", list.ids.slug), request).await?;
+        let mut items = Vec::new();
+        for entry in entries {
+            if let Some(item) = watchlist_item_from_trakt(entry)? {
+                items.push(item);
+            }
+        }
+        debug!("Fetched Trakt list '{}': items={}", list.ids.slug, items.len());
+        lists.push(CustomList {
+            name: list.name,
+            description: list.description.filter(|d| !d.is_empty()),
+            items,
+            source: "trakt".to_string(),
+        });
+    }
+    Ok(lists)
+}
+
+/// Add each list's items to the personal list of the same (normalized) name,
+/// creating private lists that don't exist yet
+pub async fn add_to_lists(
+    client: &Client,
+    access_token: &str,
+    encoded_username: &str,
+    lists: &[CustomList],
+    client_id: &str,
+    retry: &RetryPolicy,
+) -> Result<()> {
+    let existing: std::collections::HashMap<String, String> = fetch_lists(client, access_token, encoded_username, client_id)
+        .await?
+        .into_iter()
+        .map(|list| (media_sync_models::normalize_list_name(&list.name), list.ids.slug))
+        .collect();
+
+    for list in lists {
+        if list.items.is_empty() {
+            continue;
+        }
+
+        let slug = match existing.get(&list.normalized_name()) {
+            Some(slug) => slug.clone(),
+            None => {
+                let body = serde_json::json!({
+                    "name": list.name,
+                    "description": list.description.clone().unwrap_or_default(),
+                    "privacy": "private",
+                });
+                let response = retry.send_non_idempotent("Trakt list create", || {
+                    client
+                        .post(format!("https://api.trakt.tv/users/{}/lists", encoded_username))
+                        .header("Authorization", format!("Bearer {}", access_token))
+                        .header("trakt-api-version", "2")
+                        .header("trakt-api-key", client_id)
+                        .header("Content-Type", "application/json")
+                        .json(&body)
+                })
+                .await?;
+
+                let status = response.status();
+                if !status.is_success() {
+                    let error_text = response.text().await.unwrap_or_default();
+                    return Err(anyhow!("Failed to create list '{}': {} - {}", list.name, status, error_text));
+                }
+                let created: TraktList = response.json().await?;
+                debug!("Created Trakt list '{}' as {}", list.name, created.ids.slug);
+                created.ids.slug
+            }
+        };
+
+        let payload = watchlist_payload(&list.items);
+        let response = retry.send("Trakt list add", || {
+            client
+                .post(format!("https://api.trakt.tv/users/{}/lists/{}/items", encoded_username, slug))
+                .header("Authorization", format!("Bearer {}", access_token))
+                .header("trakt-api-version", "2")
+                .header("trakt-api-key", client_id)
+                .header("Content-Type", "application/json")
+                .json(&payload)
+        })
+        .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(anyhow!("Failed to add to list '{}': {} - {}", slug, status, error_text));
+        }
+    }
+
+    Ok(())
+}
+
+/// Fetch the user's collection (owned movies and shows).
+/// Only `collected_at` is read: collecting is not watching, so nothing here becomes
+/// watch history. Shows are returned once, with their most recent collection date.
+pub async fn get_collection(
+    client: &Client,
+    http_cache: &HttpCache,
+    access_token: &str,
+    client_id: &str,
+) -> Result<Vec<CollectionItem>> {
+    let movies: Vec<TraktCollectedMovie> =
+        fetch_collection(client, http_cache, access_token, client_id, "movies").await?;
+    let shows: Vec<TraktCollectedShow> =
+        fetch_collection(client, http_cache, access_token, client_id, "shows").await?;
+
+    let items = collection_items(movies, shows)?;
+    debug!("Fetched Trakt collection: total_items={}", items.len());
+    Ok(items)
+}
+
+async fn fetch_collection<T: serde::de::DeserializeOwned + Clone + Send + Sync + 'static>(
+    client: &Client,
+    http_cache: &HttpCache,
+    access_token: &str,
+    client_id: &str,
+    kind: &str,
+) -> Result<Vec<T>> {
+    let request = client
+        .get(format!("https://api.trakt.tv/sync/collection/{}", kind))
+        .header("Authorization", format!("Bearer {}", access_token))
+        .header("trakt-api-version", "2")
+        .header("trakt-api-key", client_id)
+        .header("Content-Type", "application/json");
+    http_cache.get_json(&format!("{} collection", kind), request).await
+}
+
+fn collection_items(movies: Vec<TraktCollectedMovie>, shows: Vec<TraktCollectedShow>) -> Result<Vec<CollectionItem>> {
+    let parse_date = |date: &str| {
+        DateTime::parse_from_rfc3339(date)
+            .map(|d| d.with_timezone(&Utc))
+            .map_err(|e| anyhow!("Failed to parse collected_at '{}': {}", date, e))
+    };
+
+    let mut items = Vec::with_capacity(movies.len() + shows.len());
+    for entry in movies {
+        items.push(CollectionItem {
+            imdb_id: remove_slashes(entry.movie.ids.imdb.clone()),
+            ids: Some(extract_media_ids_from_trakt_ids(&entry.movie.ids)),
+            title: entry.movie.title,
+            year: entry.movie.year,
+            media_type: MediaType::Movie,
+            collected_at: parse_date(&entry.collected_at)?,
+            source: "trakt".to_string(),
+        });
+    }
+    for entry in shows {
+        items.push(CollectionItem {
+            imdb_id: remove_slashes(entry.show.ids.imdb.clone()),
+            ids: Some(extract_media_ids_from_trakt_ids(&entry.show.ids)),
+            title: entry.show.title,
+            year: entry.show.year,
+            media_type: MediaType::Show,
+            collected_at: parse_date(&entry.last_collected_at)?,
+            source: "trakt".to_string(),
+        });
+    }
+    Ok(items)
+}
+
+/// The body of POST /sync/collection: each item with its `collected_at`. A show
+/// collects all of its episodes.
+fn collection_payload(items: &[CollectionItem]) -> serde_json::Value {
+    let mut movies = Vec::new();
+    let mut shows = Vec::new();
+    for item in items {
+        let entry = serde_json::json!({
+            "collected_at": item.collected_at.to_rfc3339(),
+            "ids": trakt_ids_object(&item.imdb_id, &item.ids),
+        });
+        match &item.media_type {
+            MediaType::Movie => movies.push(entry),
+            MediaType::Show => shows.push(entry),
+            // Collections hold movies and shows only
+            MediaType::Episode { .. } => {}
+        }
+    }
+    serde_json::json!({ "movies": movies, "shows": shows })
+}
+
+/// Add items to the user's collection, dated by their `collected_at`
+pub async fn add_to_collection(
+    client: &Client,
+    access_token: &str,
+    items: &[CollectionItem],
+    client_id: &str,
+    retry: &RetryPolicy,
+) -> Result<()> {
+    let payload = collection_payload(items);
+
+    let response = retry.send("Trakt collection add", || {
+        client
+            .post("https://api.trakt.tv/sync/collection")
+            .header("Authorization", format!("Bearer {}", access_token))
+            .header("trakt-api-version", "2")
+            .header("trakt-api-key", client_id)
+            .header("Content-Type", "application/json")
+            .json(&payload)
+    })
+    .await?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(anyhow!("Failed to add to collection: {} - {}", status, error_text));
     }
 
     Ok(())
 }
 
+/// Fetch the last watched episode of every show with watched episodes.
+/// Uses GET /sync/watched/shows, which lists each show's watched episodes in one call.
+pub async fn get_watch_progress(
+    client: &Client,
+    access_token: &str,
+    client_id: &str,
+) -> Result<Vec<WatchProgress>> {
+    let progress = watched_show_progress(get_watched_shows(client, access_token, client_id).await?)?;
+    debug!("Fetched Trakt show progress: shows={}", progress.len());
+    Ok(progress)
+}
+
+async fn get_watched_shows(client: &Client, access_token: &str, client_id: &str) -> Result<Vec<TraktWatchedShow>> {
+    let response = client
+        .get("https://api.trakt.tv/sync/watched/shows")
+        .header("Authorization", format!("Bearer {}", access_token))
+        .header("trakt-api-version", "2")
+        .header("trakt-api-key", client_id)
+        .header("Content-Type", "application/json")
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(anyhow!("Failed to fetch watched shows: {}", response.status()));
+    }
+    Ok(response.json().await?)
+}
+
+/// Each watched show's IDs with the `(season, episode)`s watched
+fn watched_episodes(shows: &[TraktWatchedShow]) -> Vec<(media_sync_models::MediaIds, std::collections::HashSet<(u32, u32)>)> {
+    shows.iter()
+        .map(|entry| {
+            let ids = extract_media_ids_from_trakt_ids(&entry.show.ids);
+            let episodes = entry.seasons.iter()
+                .flat_map(|season| season.episodes.iter().map(|episode| (season.number, episode.number)))
+                .collect();
+            (ids, episodes)
+        })
+        .collect()
+}
+
+fn watched_show_progress(shows: Vec<TraktWatchedShow>) -> Result<Vec<WatchProgress>> {
+    let mut progress = Vec::with_capacity(shows.len());
+    for entry in shows {
+        let mut episodes = Vec::new();
+        for season in &entry.seasons {
+            for episode in &season.episodes {
+                let watched_at = DateTime::parse_from_rfc3339(&episode.last_watched_at)
+                    .map_err(|e| anyhow!("Failed to parse last_watched_at '{}': {}", episode.last_watched_at, e))?
+                    .with_timezone(&Utc);
+                episodes.push((season.number, episode.number, watched_at));
+            }
+        }
+        // Only specials watched
+        let Some((season, episode, watched_at)) = media_sync_models::last_watched_episode(episodes) else {
+            continue;
+        };
+        progress.push(WatchProgress {
+            imdb_id: remove_slashes(entry.show.ids.imdb.clone()),
+            ids: Some(extract_media_ids_from_trakt_ids(&entry.show.ids)),
+            title: Some(entry.show.title),
+            year: entry.show.year,
+            last_watched_season: season,
+            last_watched_episode: episode,
+            watched_at,
+            source: "trakt".to_string(),
+        });
+    }
+    Ok(progress)
+}
+
+/// Mark each show's last watched episode as watched at its `watched_at`, unless it is
+/// already watched: POST /sync/history adds a play every time, not just progress.
+/// The episode is given by show, season and number.
+pub async fn set_watch_progress(
+    client: &Client,
+    access_token: &str,
+    progress: &[WatchProgress],
+    client_id: &str,
+    retry: &RetryPolicy,
+) -> Result<()> {
+    let watched = watched_episodes(&get_watched_shows(client, access_token, client_id).await?);
+    let unwatched = media_sync_models::unwatched_progress(progress, &watched);
+    if unwatched.len() < progress.len() {
+        debug!("Skipping {} Trakt shows whose episode is already watched", progress.len() - unwatched.len());
+    }
+    let shows: Vec<serde_json::Value> = unwatched.iter()
+        .filter_map(|show| {
+            let mut ids = serde_json::Map::new();
+            let imdb = show.ids.as_ref()
+                .and_then(|ids| ids.imdb_id.clone())
+                .unwrap_or_else(|| show.imdb_id.clone());
+            if !imdb.is_empty() {
+                ids.insert("imdb".to_string(), serde_json::json!(imdb));
+            }
+            if let Some(media_ids) = &show.ids {
+                if let Some(trakt) = media_ids.trakt_id {
+                    ids.insert("trakt".to_string(), serde_json::json!(trakt));
+                }
+                if let Some(tmdb) = media_ids.tmdb_id {
+                    ids.insert("tmdb".to_string(), serde_json::json!(tmdb));
+                }
+                if let Some(tvdb) = media_ids.tvdb_id {
+                    ids.insert("tvdb".to_string(), serde_json::json!(tvdb));
+                }
+            }
+            if ids.is_empty() {
+                warn!("Skipping Trakt show progress without any usable ID: {:?}", show.title);
+                return None;
+            }
+            Some(serde_json::json!({
+                "ids": ids,
+                "seasons": [{
+                    "number": show.last_watched_season,
+                    "episodes": [{
+                        "number": show.last_watched_episode,
+                        "watched_at": show.watched_at.to_rfc3339(),
+                    }],
+                }],
+            }))
+        })
+        .collect();
+    if shows.is_empty() {
+        return Ok(());
+    }
+
+    let payload = serde_json::json!({ "shows": shows });
+    let response = retry.send_non_idempotent("Trakt show progress", || {
+        client
+            .post("https://api.trakt.tv/sync/history")
+            .header("Authorization", format!("Bearer {}", access_token))
+            .header("trakt-api-version", "2")
+            .header("trakt-api-key", client_id)
+            .header("Accept", "application/json")
+            .header("Content-Type", "application/json")
+            .json(&payload)
+    })
+    .await?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(anyhow!("Failed to set show progress: {} - {}", status, error_text));
+    }
+    Ok(())
+}
+
+/// Fetch a show's episodes with their air dates
+/// Uses GET /shows/{id}/seasons?extended=episodes,full; `show_id` may be a Trakt ID or an IMDB ID.
+/// Specials (season 0) and episodes without an air date are left out.
+pub async fn get_aired_episodes(
+    client: &Client,
+    client_id: &str,
+    show_id: &str,
+) -> Result<Vec<AiredEpisode>> {
+    let response = client
+        .get(format!("https://api.trakt.tv/shows/{}/seasons?extended=episodes,full", show_id))
+        .header("trakt-api-version", "2")
+        .header("trakt-api-key", client_id)
+        .header("Content-Type", "application/json")
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(anyhow!("Failed to fetch seasons for show {}: {}", show_id, response.status()));
+    }
+    let seasons: Vec<TraktSeason> = response.json().await?;
+    Ok(aired_episodes(seasons))
+}
+
+fn aired_episodes(seasons: Vec<TraktSeason>) -> Vec<AiredEpisode> {
+    seasons.into_iter()
+        .filter(|season| season.number > 0)
+        .flat_map(|season| {
+            let season_number = season.number;
+            season.episodes.into_iter().filter_map(move |episode| {
+                let first_aired = DateTime::parse_from_rfc3339(episode.first_aired.as_deref()?).ok()?;
+                Some(AiredEpisode {
+                    season: season_number,
+                    episode: episode.number,
+                    title: episode.title,
+                    ids: extract_media_ids_from_trakt_ids(&episode.ids),
+                    first_aired: first_aired.with_timezone(&Utc),
+                })
+            })
+        })
+        .collect()
+}
+
 /// Normalize title for Trakt API search
 /// Removes commas and normalizes whitespace to improve search matching
 fn normalize_title_for_search(title: &str) -> String {
@@ -1117,6 +1999,7 @@ fn normalize_title_for_search(title: &str) -> String {
 
 /// Search for media by title using Trakt API
 /// Uses Trakt API text query endpoint: GET /search/{type}?query={query}
+/// When several results match the title, `weights` pick one by year and vote count.
 /// Reference: https://trakt.docs.apiary.io/#reference/search/text-query/get-text-query-results
 pub async fn search_by_title(
     client: &Client,
@@ -1125,7 +2008,9 @@ pub async fn search_by_title(
     title: &str,
     year: Option<u32>,
     media_type: &MediaType,
+    weights: &media_sync_config::IdMatchingConfig,
 ) -> Result<Option<media_sync_models::MediaIds>> {
+    use crate::disambiguation::{normalize_title, pick_best_candidate, within_year_tolerance, TitleCandidate};
     use media_sync_models::MediaIds;
     
     let search_type = match media_type {
@@ -1138,7 +2023,8 @@ pub async fn search_by_title(
     let normalized_title = normalize_title_for_search(title);
     
     // Build URL according to Trakt API: /search/{type}?query={query}&year={year}
-    let mut url = format!("https://api.trakt.tv/search/{}?query={}", search_type, urlencoding::encode(&normalized_title));
+    // extended=full adds vote counts for disambiguation
+    let mut url = format!("https://api.trakt.tv/search/{}?query={}&extended=full", search_type, urlencoding::encode(&normalized_title));
     if let Some(y) = year {
         url.push_str(&format!("&year={}", y));
     }
@@ -1162,8 +2048,8 @@ pub async fn search_by_title(
     
     let items: Vec<serde_json::Value> = response.json().await?;
 
-    // Find best match with validation
-    let mut best_match: Option<&serde_json::Value> = None;
+    // Collect every title/year match, then pick one
+    let mut candidates = Vec::new();
     for item in &items {
         let item_type = item.get("type").and_then(|t| t.as_str());
         if item_type != Some(search_type) {
@@ -1183,20 +2069,20 @@ pub async fn search_by_title(
         
         let item_year = media_json.get("year").and_then(|y| y.as_u64()).map(|y| y as u32);
         
-        // Check title match
-        let title_match = item_title == title;
-        
-        // Check year match if both available
-        let year_match = match (year, item_year) {
-            (Some(search_year), Some(item_year)) => search_year == item_year,
-            (None, _) | (_, None) => true,
-        };
+        // Only the searched title, released within `year_tolerance` of the searched year
+        let title_match = normalize_title(item_title) == normalize_title(title);
+        let year_match = within_year_tolerance(year, item_year, weights.year_tolerance);
         
         if title_match && year_match {
-            best_match = Some(item);
-            break; // Exact match found
+            candidates.push(TitleCandidate {
+                title: Some(item_title.to_string()),
+                year: item_year,
+                popularity: media_json.get("votes").and_then(|v| v.as_f64()),
+                item,
+            });
         }
     }
+    let best_match = pick_best_candidate(candidates, title, year, weights);
     
     // If we got results, try to extract IDs
     if let Some(matched_item) = best_match {
@@ -1319,3 +2205,200 @@ pub async fn search_by_imdb_id(
     Ok(None)
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn partial(imdb_id: &str, media_type: MediaType) -> WatchHistory {
+        WatchHistory {
+            imdb_id: imdb_id.to_string(),
+            ids: None,
+            title: None,
+            year: None,
+            watched_at: Utc.with_ymd_and_hms(2024, 3, 1, 20, 0, 0).unwrap(),
+            media_type,
+            source: "plex".to_string(),
+            watched_percent: Some(42.5),
+            show_ids: None,
+        }
+    }
+
+    #[test]
+    fn test_comment_spoiler_and_rating_are_read_from_the_comment() {
+        let item: TraktComment = serde_json::from_value(serde_json::json!({
+            "type": "movie",
+            "movie": { "title": "Se7en", "year": 1995, "ids": { "trakt": 1, "imdb": "tt0114369" } },
+            "comment": {
+                "id": 267, "comment": "What's in the box?", "spoiler": true, "review": true,
+                "user_stats": { "rating": 9, "play_count": 2, "completed_count": 2 }
+            }
+        })).unwrap();
+        assert!(item.comment.spoiler);
+        assert_eq!(item.comment.user_stats.and_then(|stats| stats.rating), Some(9));
+
+        let unrated: TraktCommentDetails = serde_json::from_value(serde_json::json!({
+            "id": 268, "comment": "Fine.", "user_stats": { "rating": null }
+        })).unwrap();
+        assert!(!unrated.spoiler);
+        assert_eq!(unrated.user_stats.and_then(|stats| stats.rating), None);
+    }
+
+    #[test]
+    fn test_extracted_ids_keep_trakt_id_and_slug() {
+        let trakt_ids: TraktIds = serde_json::from_value(serde_json::json!({
+            "trakt": 1388, "slug": "breaking-bad", "imdb": "tt0903747", "tmdb": 1396, "tvdb": 81189
+        })).unwrap();
+        let ids = extract_media_ids_from_trakt_ids(&trakt_ids);
+        assert_eq!(ids.trakt_id, Some(1388));
+        assert_eq!(ids.slug.as_deref(), Some("breaking-bad"));
+        assert_eq!(ids.imdb_id.as_deref(), Some("tt0903747"));
+        assert_eq!(ids.get_best_id_for_source("trakt").as_deref(), Some("trakt:1388"));
+    }
+
+    #[test]
+    fn test_scrobble_payload() {
+        let movie = partial("tt0113277", MediaType::Movie);
+        assert_eq!(
+            scrobble_payload(&movie, 42.5).unwrap(),
+            serde_json::json!({ "movie": { "ids": { "imdb": "tt0113277" } }, "progress": 42.5 })
+        );
+
+        let mut episode = partial("tt0959621", MediaType::Episode { season: 1, episode: 1 });
+        episode.ids = Some(media_sync_models::MediaIds { tvdb_id: Some(349232), ..Default::default() });
+        assert_eq!(
+            scrobble_payload(&episode, 150.0).unwrap(),
+            serde_json::json!({ "episode": { "ids": { "imdb": "tt0959621", "tvdb": 349232 } }, "progress": 100.0 })
+        );
+
+        assert!(scrobble_payload(&partial("tt0903747", MediaType::Show), 10.0).is_none());
+        assert!(scrobble_payload(&partial("", MediaType::Movie), 10.0).is_none());
+    }
+
+    #[test]
+    fn test_collection_items_use_collected_at() {
+        let movies: Vec<TraktCollectedMovie> = serde_json::from_str(r#"[{
+            "collected_at": "2014-09-01T09:10:11.000Z",
+            "updated_at": "2014-09-01T09:10:11.000Z",
+            "movie": { "title": "Heat", "year": 1995, "ids": { "trakt": 117, "slug": "heat-1995", "imdb": "tt0113277", "tmdb": 949 } }
+        }]"#).unwrap();
+        let shows: Vec<TraktCollectedShow> = serde_json::from_str(r#"[{
+            "last_collected_at": "2014-11-20T06:00:00.000Z",
+            "last_updated_at": "2014-11-20T06:00:00.000Z",
+            "show": { "title": "Breaking Bad", "year": 2008, "ids": { "trakt": 1, "slug": "breaking-bad", "imdb": "tt0903747", "tvdb": 81189 } },
+            "seasons": [{ "number": 1, "episodes": [{ "number": 1, "collected_at": "2014-11-20T06:00:00.000Z" }] }]
+        }]"#).unwrap();
+
+        let items = collection_items(movies, shows).unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].imdb_id, "tt0113277");
+        assert_eq!(items[0].collected_at, Utc.with_ymd_and_hms(2014, 9, 1, 9, 10, 11).unwrap());
+        assert_eq!(items[1].media_type, MediaType::Show);
+        assert_eq!(items[1].collected_at, Utc.with_ymd_and_hms(2014, 11, 20, 6, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn test_collection_payload_keeps_collected_at() {
+        let collected_at = Utc.with_ymd_and_hms(2014, 9, 1, 9, 10, 11).unwrap();
+        let item = CollectionItem {
+            imdb_id: "tt0113277".to_string(),
+            ids: None,
+            title: "Heat".to_string(),
+            year: Some(1995),
+            media_type: MediaType::Movie,
+            collected_at,
+            source: "plex".to_string(),
+        };
+
+        let payload = collection_payload(&[item]);
+        assert_eq!(payload["movies"][0]["collected_at"], "2014-09-01T09:10:11+00:00");
+        assert_eq!(payload["movies"][0]["ids"]["imdb"], "tt0113277");
+        assert_eq!(payload["shows"].as_array().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_liked_titles_fill_gaps_only() {
+        let liked_at = Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+        let liked = |imdb_id: &str, trakt_id: u64| LikedTitle {
+            imdb_id: imdb_id.to_string(),
+            ids: media_sync_models::MediaIds { trakt_id: Some(trakt_id), ..Default::default() },
+            title: imdb_id.to_string(),
+            year: None,
+            media_type: MediaType::Movie,
+            liked_at,
+        };
+        let liked = vec![liked("tt0113277", 117), liked("tt0133093", 481)];
+
+        let rated = Rating {
+            imdb_id: "tt0113277".to_string(),
+            ids: None,
+            rating: 6,
+            rating_f64: None,
+            date_added: Utc::now(),
+            media_type: MediaType::Movie,
+            source: media_sync_models::RatingSource::Trakt,
+            inferred: false,
+        };
+        let ratings = liked_as_ratings(&liked, &[rated]);
+        assert_eq!(ratings.len(), 1);
+        assert_eq!(ratings[0].imdb_id, "tt0133093");
+        assert_eq!(ratings[0].rating, 10);
+        assert_eq!(ratings[0].date_added, liked_at);
+        assert!(ratings[0].inferred);
+
+        // Matched by Trakt ID when the watchlist item has no IMDB ID
+        let mut watchlist = vec![WatchlistItem {
+            imdb_id: String::new(),
+            ids: Some(media_sync_models::MediaIds { trakt_id: Some(481), ..Default::default() }),
+            title: "The Matrix".to_string(),
+            year: Some(1999),
+            media_type: MediaType::Movie,
+            date_added: Utc::now(),
+            source: "trakt".to_string(),
+            status: Some(media_sync_models::NormalizedStatus::Watchlist),
+            tags: vec![],
+        }];
+        merge_liked_into_watchlist(&mut watchlist, &liked);
+        merge_liked_into_watchlist(&mut watchlist, &liked);
+        assert_eq!(watchlist.len(), 2);
+        assert_eq!(watchlist[0].tags, vec![LIKED_TAG]);
+        assert_eq!(watchlist[1].imdb_id, "tt0113277");
+        assert_eq!(watchlist[1].tags, vec![LIKED_TAG]);
+    }
+
+    #[test]
+    fn test_show_progress_is_the_latest_watched_episode() {
+        let shows: Vec<TraktWatchedShow> = serde_json::from_str(r#"[
+            {
+                "show": { "title": "Breaking Bad", "year": 2008, "ids": { "trakt": 1388, "imdb": "tt0903747", "tvdb": 81189 } },
+                "seasons": [
+                    { "number": 0, "episodes": [{ "number": 1, "last_watched_at": "2024-06-01T20:00:00.000Z" }] },
+                    { "number": 3, "episodes": [
+                        { "number": 3, "last_watched_at": "2024-05-02T20:00:00.000Z" },
+                        { "number": 4, "last_watched_at": "2024-05-03T20:00:00.000Z" }
+                    ] },
+                    { "number": 1, "episodes": [{ "number": 7, "last_watched_at": "2024-01-10T20:00:00.000Z" }] }
+                ]
+            },
+            {
+                "show": { "title": "Only Specials", "year": 2020, "ids": { "trakt": 7 } },
+                "seasons": [{ "number": 0, "episodes": [{ "number": 1, "last_watched_at": "2024-01-01T00:00:00.000Z" }] }]
+            }
+        ]"#).unwrap();
+
+        let watched = watched_episodes(&shows);
+        let progress = watched_show_progress(shows).unwrap();
+        assert_eq!(progress.len(), 1);
+        assert_eq!(progress[0].imdb_id, "tt0903747");
+        assert_eq!(progress[0].position(), (3, 4));
+        assert_eq!(progress[0].watched_at, Utc.with_ymd_and_hms(2024, 5, 3, 20, 0, 0).unwrap());
+
+        // Progress Trakt already has is not written back as another play
+        let mut ahead = progress[0].clone();
+        ahead.last_watched_episode = 5;
+        let unwatched = media_sync_models::unwatched_progress(&[progress[0].clone(), ahead], &watched);
+        assert_eq!(unwatched.len(), 1);
+        assert_eq!(unwatched[0].position(), (3, 5));
+    }
+}
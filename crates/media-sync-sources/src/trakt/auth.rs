@@ -2,6 +2,7 @@ use anyhow::{anyhow, Result};
 use chrono::{DateTime, Duration, Utc};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use std::io::IsTerminal;
 
 const REDIRECT_URI: &str = "urn:ietf:wg:oauth:2.0:oob";
 const TOKEN_URL: &str = "https://api.trakt.tv/oauth/token";
@@ -9,10 +10,17 @@ const AUTHORIZE_URL: &str = "https://trakt.tv/oauth/authorize";
 
 /// Create a reqwest Client with browser-like headers to bypass Cloudflare
 pub fn create_trakt_client() -> Client {
-    Client::builder()
-        .user_agent("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
-        .build()
-        .unwrap_or_else(|_| Client::new())
+    create_trakt_client_with_timeout(None)
+}
+
+/// Like [`create_trakt_client`], giving up on requests that take longer than `timeout`
+pub fn create_trakt_client_with_timeout(timeout: Option<std::time::Duration>) -> Client {
+    let mut builder = Client::builder()
+        .user_agent("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36");
+    if let Some(timeout) = timeout {
+        builder = builder.timeout(timeout);
+    }
+    builder.build().unwrap_or_else(|_| Client::new())
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -36,22 +44,37 @@ pub async fn authenticate(
 ) -> Result<TokenInfo> {
     let client = create_trakt_client();
 
+    let mut refresh_error = None;
     if let Some(refresh_token) = refresh_token {
         // Try to refresh the token
-        match refresh_access_token(&client, client_id, client_secret, refresh_token).await {
+        match refresh_access_token(&client, TOKEN_URL, client_id, client_secret, refresh_token).await {
             Ok(token_info) => return Ok(token_info),
-            Err(_) => {
+            Err(e) => {
                 // Refresh failed, fall through to new authorization
+                refresh_error = Some(e);
             }
         }
     }
 
-    // New authorization flow
+    // New authorization flow, which needs someone to paste the code (not so in the daemon or cron)
+    if !std::io::stdin().is_terminal() {
+        return Err(anyhow!(
+            "Trakt token could not be refreshed{}; run 'totalrecall config trakt' to sign in again",
+            refresh_error.map(|e| format!(" ({})", e)).unwrap_or_default()
+        ));
+    }
     authorize_new(client_id, client_secret).await
 }
 
-async fn refresh_access_token(
+/// Exchange `refresh_token` for new tokens. Trakt rotates the refresh token, so the
+/// returned one has to be saved in place of it.
+pub async fn refresh(client_id: &str, client_secret: &str, refresh_token: &str) -> Result<TokenInfo> {
+    refresh_access_token(&create_trakt_client(), TOKEN_URL, client_id, client_secret, refresh_token).await
+}
+
+pub(crate) async fn refresh_access_token(
     client: &Client,
+    token_url: &str,
     client_id: &str,
     client_secret: &str,
     refresh_token: &str,
@@ -65,7 +88,7 @@ async fn refresh_access_token(
     });
 
     let response = client
-        .post(TOKEN_URL)
+        .post(token_url)
         .json(&payload)
         .header("Accept", "application/json")
         .header("Accept-Language", "en-US,en;q=0.9")
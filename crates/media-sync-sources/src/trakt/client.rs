@@ -1,9 +1,13 @@
-use crate::traits::MediaSource;
-use crate::capabilities::{RatingNormalization, CapabilityRegistry, StatusMapping, IncrementalSync, IdExtraction, IdLookupProvider};
+use crate::traits::{MediaSource, WrittenReview};
+use crate::capabilities::{RatingNormalization, CapabilityRegistry, StatusMapping, IncrementalSync, IdExtraction, IdLookupProvider, PlaybackProgress, CollectionSource, EpisodeLookup, AiredEpisode, HistoryPruning};
+use crate::oauth::AccessToken;
+use crate::http_cache::HttpCache;
+use crate::retry::RetryPolicy;
 use crate::trakt::api;
 use crate::trakt::auth;
 use anyhow::Result;
-use media_sync_models::{Rating, Review, WatchHistory, WatchlistItem, MediaIds, MediaType};
+use chrono::{DateTime, Utc};
+use media_sync_models::{CollectionItem, CustomList, Rating, Review, WatchHistory, WatchProgress, WatchlistItem, MediaIds, MediaType};
 use reqwest::Client;
 use std::sync::Arc;
 use tracing::info;
@@ -12,23 +16,69 @@ use async_trait::async_trait;
 #[derive(Clone)]
 pub struct TraktClient {
     client: Arc<Client>,
-    access_token: Option<String>,
+    access_token: AccessToken,
     client_id: String,
     client_secret: String,
     encoded_username: Option<String>,
+    id_matching: media_sync_config::IdMatchingConfig,
+    likes_as: Option<media_sync_config::TraktLikesAs>,
+    retry: RetryPolicy,
+    batch_size: usize,
+    http_cache: HttpCache,
 }
 
 impl TraktClient {
     pub fn new(client_id: String, client_secret: String) -> Self {
         Self {
             client: Arc::new(auth::create_trakt_client()), // Use client with browser-like headers
-            access_token: None,
+            access_token: AccessToken::default(),
             client_id,
             client_secret,
             encoded_username: None,
+            id_matching: media_sync_config::IdMatchingConfig::default(),
+            likes_as: None,
+            retry: RetryPolicy::default(),
+            batch_size: media_sync_config::DEFAULT_TRAKT_BATCH_SIZE,
+            http_cache: HttpCache::default(),
         }
     }
 
+    /// Weights for choosing between several title search results
+    pub fn with_id_matching(mut self, id_matching: media_sync_config::IdMatchingConfig) -> Self {
+        self.id_matching = id_matching;
+        self
+    }
+
+    /// Collect liked titles as ratings or tagged watchlist items (`trakt.likes_as`)
+    pub fn with_likes_as(mut self, likes_as: Option<media_sync_config::TraktLikesAs>) -> Self {
+        self.likes_as = likes_as;
+        self
+    }
+
+    /// Retries of watchlist, rating, history and collection writes (`trakt.retry`)
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Revalidate watchlist, rating, collection and list reads instead of downloading them again
+    pub fn with_http_cache(mut self, http_cache: HttpCache) -> Self {
+        self.http_cache = http_cache;
+        self
+    }
+
+    /// Give up on API requests that take longer than `timeout` (`sources.request_timeout_secs`)
+    pub fn with_request_timeout(mut self, timeout: Option<std::time::Duration>) -> Self {
+        self.client = Arc::new(auth::create_trakt_client_with_timeout(timeout));
+        self
+    }
+
+    /// Items per watchlist, rating and history write request (`trakt.batch_size`)
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
     pub async fn authenticate(&mut self) -> Result<()> {
         use crate::trakt::auth::authenticate as trakt_authenticate;
         use media_sync_config::CredentialStore;
@@ -38,6 +88,7 @@ impl TraktClient {
         let path_manager = PathManager::default();
         let mut cred_store = CredentialStore::new(path_manager.credentials_file());
         cred_store.load()?;
+        cred_store.check_account("trakt")?;
 
         // Check if we have a valid access token first
         if let Some(saved_token) = cred_store.get_trakt_access_token() {
@@ -45,7 +96,7 @@ impl TraktClient {
             if let Some(expires_at) = cred_store.get_trakt_token_expires() {
                 // Token is valid if it expires more than 5 minutes from now
                 if expires_at > Utc::now() + Duration::minutes(5) {
-                    self.access_token = Some(saved_token.clone());
+                    self.access_token.set(saved_token.clone());
                     
                     // Try to get encoded username with existing token
                     // If this fails, we'll need to re-authenticate
@@ -68,7 +119,7 @@ impl TraktClient {
                 // No expiration info, try to use the token
                 match api::get_encoded_username(&self.client, saved_token, &self.client_id).await {
                     Ok(encoded_username) => {
-                        self.access_token = Some(saved_token.clone());
+                        self.access_token.set(saved_token.clone());
                         self.encoded_username = Some(encoded_username);
                         info!("Using saved Trakt access token (no expiration info)");
                         return Ok(());
@@ -86,28 +137,43 @@ impl TraktClient {
 
         let token_info = trakt_authenticate(&self.client_id, &self.client_secret, refresh_token).await?;
 
-        self.access_token = Some(token_info.access_token.clone());
+        self.access_token.set(token_info.access_token.clone());
 
         // Get encoded username
         let encoded_username = api::get_encoded_username(&self.client, &token_info.access_token, &self.client_id).await?;
         self.encoded_username = Some(encoded_username);
 
-        // Save tokens
-        cred_store.set_trakt_access_token(token_info.access_token);
-        cred_store.set_trakt_refresh_token(token_info.refresh_token);
-        cred_store.set_trakt_token_expires(token_info.expires_at);
-        cred_store.save()?;
+        save_tokens(&mut cred_store, token_info)?;
 
         info!("Authenticated to Trakt");
         Ok(())
     }
 
     pub fn is_authenticated(&self) -> bool {
-        self.access_token.is_some() && self.encoded_username.is_some()
+        self.access_token.is_set() && self.encoded_username.is_some()
     }
 
-    fn access_token(&self) -> Result<&str> {
-        self.access_token.as_deref().ok_or_else(|| anyhow::anyhow!("Not authenticated"))
+    /// Run `call` with the access token; when Trakt answers 401, once more after a refresh
+    async fn authorized<T, F, Fut>(&self, call: F) -> Result<T>
+    where
+        F: Fn(String) -> Fut,
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        self.access_token.call(call, || self.refresh_tokens()).await
+    }
+
+    /// Exchange the saved refresh token for new tokens, save them and return the access token
+    async fn refresh_tokens(&self) -> Result<String> {
+        let path_manager = media_sync_config::PathManager::default();
+        let mut cred_store = media_sync_config::CredentialStore::new(path_manager.credentials_file());
+        cred_store.load()?;
+        let refresh_token = cred_store.get_trakt_refresh_token().cloned()
+            .ok_or_else(|| anyhow::anyhow!("No Trakt refresh token saved; run 'totalrecall config trakt' to sign in again"))?;
+        let token_info = auth::refresh(&self.client_id, &self.client_secret, &refresh_token).await?;
+        let access_token = token_info.access_token.clone();
+        save_tokens(&mut cred_store, token_info)?;
+        info!("Refreshed the Trakt access token");
+        Ok(access_token)
     }
 
     fn encoded_username(&self) -> Result<&str> {
@@ -145,68 +211,118 @@ impl MediaSource for TraktClient {
     }
 
     async fn get_watchlist(&self) -> Result<Vec<WatchlistItem>, Self::Error> {
-        let access_token = self.access_token().map_err(|e| crate::error::SourceError::new(format!("{}", e)))?;
         let encoded_username = self.encoded_username().map_err(|e| crate::error::SourceError::new(format!("{}", e)))?;
-        api::get_watchlist(&self.client, access_token, encoded_username, &self.client_id)
+        let mut watchlist = self.authorized(move |access_token| async move { api::get_watchlist(&self.client, &self.http_cache, &access_token, encoded_username, &self.client_id).await })
             .await
-            .map_err(|e| crate::error::SourceError::new(format!("{}", e)))
+            .map_err(|e| crate::error::SourceError::new(format!("{}", e)))?;
+        if self.likes_as == Some(media_sync_config::TraktLikesAs::Tag) {
+            let liked = self.authorized(move |access_token| async move { api::get_liked_titles(&self.client, &access_token, encoded_username, &self.client_id).await })
+                .await
+                .map_err(|e| crate::error::SourceError::new(format!("{}", e)))?;
+            api::merge_liked_into_watchlist(&mut watchlist, &liked);
+        }
+        Ok(watchlist)
     }
 
     async fn get_ratings(&self) -> Result<Vec<Rating>, Self::Error> {
-        let access_token = self.access_token().map_err(|e| crate::error::SourceError::new(format!("{}", e)))?;
         let encoded_username = self.encoded_username().map_err(|e| crate::error::SourceError::new(format!("{}", e)))?;
-        api::get_ratings(&self.client, access_token, encoded_username, &self.client_id)
+        let mut ratings = self.authorized(move |access_token| async move { api::get_ratings(&self.client, &self.http_cache, &access_token, encoded_username, &self.client_id).await })
             .await
-            .map_err(|e| crate::error::SourceError::new(format!("{}", e)))
+            .map_err(|e| crate::error::SourceError::new(format!("{}", e)))?;
+        if self.likes_as == Some(media_sync_config::TraktLikesAs::Rating) {
+            let liked = self.authorized(move |access_token| async move { api::get_liked_titles(&self.client, &access_token, encoded_username, &self.client_id).await })
+                .await
+                .map_err(|e| crate::error::SourceError::new(format!("{}", e)))?;
+            let liked_ratings = api::liked_as_ratings(&liked, &ratings);
+            info!("Collected {} liked Trakt titles as ratings", liked_ratings.len());
+            ratings.extend(liked_ratings);
+        }
+        Ok(ratings)
     }
 
     async fn get_reviews(&self) -> Result<Vec<Review>, Self::Error> {
-        let access_token = self.access_token().map_err(|e| crate::error::SourceError::new(format!("{}", e)))?;
         let encoded_username = self.encoded_username().map_err(|e| crate::error::SourceError::new(format!("{}", e)))?;
-        api::get_comments(&self.client, access_token, encoded_username, &self.client_id)
+        self.authorized(move |access_token| async move { api::get_comments(&self.client, &access_token, encoded_username, &self.client_id).await })
             .await
             .map_err(|e| crate::error::SourceError::new(format!("{}", e)))
     }
 
     async fn get_watch_history(&self) -> Result<Vec<WatchHistory>, Self::Error> {
-        let access_token = self.access_token().map_err(|e| crate::error::SourceError::new(format!("{}", e)))?;
         let encoded_username = self.encoded_username().map_err(|e| crate::error::SourceError::new(format!("{}", e)))?;
-        api::get_watch_history(&self.client, access_token, encoded_username, &self.client_id)
+        self.authorized(move |access_token| async move { api::get_watch_history(&self.client, &access_token, encoded_username, &self.client_id).await })
             .await
             .map_err(|e| crate::error::SourceError::new(format!("{}", e)))
     }
 
     async fn add_to_watchlist(&self, items: &[WatchlistItem]) -> Result<(), Self::Error> {
-        let access_token = self.access_token().map_err(|e| crate::error::SourceError::new(format!("{}", e)))?;
-        api::add_to_watchlist(&self.client, access_token, items, &self.client_id)
-            .await
-            .map_err(|e| crate::error::SourceError::new(format!("{}", e)))
+        write_in_chunks(items, self.batch_size, |chunk| {
+            self.authorized(move |access_token| async move {
+                api::add_to_watchlist(&self.client, &access_token, chunk, &self.client_id, &self.retry).await
+            })
+        })
+        .await
     }
 
     async fn remove_from_watchlist(&self, items: &[WatchlistItem]) -> Result<(), Self::Error> {
-        let access_token = self.access_token().map_err(|e| crate::error::SourceError::new(format!("{}", e)))?;
-        api::remove_from_watchlist(&self.client, access_token, items, &self.client_id)
+        self.authorized(move |access_token| async move { api::remove_from_watchlist(&self.client, &access_token, items, &self.client_id).await })
             .await
             .map_err(|e| crate::error::SourceError::new(format!("{}", e)))
     }
 
     async fn set_ratings(&self, ratings: &[Rating]) -> Result<(), Self::Error> {
-        let access_token = self.access_token().map_err(|e| crate::error::SourceError::new(format!("{}", e)))?;
-        api::set_ratings(&self.client, access_token, ratings, &self.client_id)
+        write_in_chunks(ratings, self.batch_size, |chunk| {
+            self.authorized(move |access_token| async move {
+                api::set_ratings(&self.client, &access_token, chunk, &self.client_id, &self.retry).await
+            })
+        })
+        .await
+    }
+
+    async fn set_reviews(&self, reviews: &[Review]) -> Result<Vec<WrittenReview>, Self::Error> {
+        self.authorized(move |access_token| async move { api::add_comments(&self.client, &access_token, reviews, &self.client_id).await })
             .await
             .map_err(|e| crate::error::SourceError::new(format!("{}", e)))
     }
 
-    async fn set_reviews(&self, reviews: &[Review]) -> Result<(), Self::Error> {
-        let access_token = self.access_token().map_err(|e| crate::error::SourceError::new(format!("{}", e)))?;
-        api::add_comments(&self.client, access_token, reviews, &self.client_id)
+    async fn add_watch_history(&self, items: &[WatchHistory]) -> Result<(), Self::Error> {
+        write_in_chunks(items, self.batch_size, |chunk| {
+            self.authorized(move |access_token| async move {
+                api::add_watch_history(&self.client, &access_token, chunk, &self.client_id, &self.retry).await
+            })
+        })
+        .await
+    }
+
+    fn supports_watch_progress(&self) -> bool {
+        true
+    }
+
+    async fn get_watch_progress(&self) -> Result<Vec<WatchProgress>, Self::Error> {
+        self.authorized(move |access_token| async move { api::get_watch_progress(&self.client, &access_token, &self.client_id).await })
             .await
             .map_err(|e| crate::error::SourceError::new(format!("{}", e)))
     }
 
-    async fn add_watch_history(&self, items: &[WatchHistory]) -> Result<(), Self::Error> {
-        let access_token = self.access_token().map_err(|e| crate::error::SourceError::new(format!("{}", e)))?;
-        api::add_watch_history(&self.client, access_token, items, &self.client_id)
+    async fn set_watch_progress(&self, progress: &[WatchProgress]) -> Result<(), Self::Error> {
+        self.authorized(move |access_token| async move { api::set_watch_progress(&self.client, &access_token, progress, &self.client_id, &self.retry).await })
+            .await
+            .map_err(|e| crate::error::SourceError::new(format!("{}", e)))
+    }
+
+    fn supports_lists(&self) -> bool {
+        true
+    }
+
+    async fn get_lists(&self) -> Result<Vec<CustomList>, Self::Error> {
+        let encoded_username = self.encoded_username().map_err(|e| crate::error::SourceError::new(format!("{}", e)))?;
+        self.authorized(move |access_token| async move { api::get_lists(&self.client, &self.http_cache, &access_token, encoded_username, &self.client_id).await })
+            .await
+            .map_err(|e| crate::error::SourceError::new(format!("{}", e)))
+    }
+
+    async fn set_lists(&self, lists: &[CustomList]) -> Result<(), Self::Error> {
+        let encoded_username = self.encoded_username().map_err(|e| crate::error::SourceError::new(format!("{}", e)))?;
+        self.authorized(move |access_token| async move { api::add_to_lists(&self.client, &access_token, encoded_username, lists, &self.client_id, &self.retry).await })
             .await
             .map_err(|e| crate::error::SourceError::new(format!("{}", e)))
     }
@@ -214,17 +330,17 @@ impl MediaSource for TraktClient {
 }
 
 impl RatingNormalization for TraktClient {
-    fn normalize_rating(&self, rating: f64, target_scale: u8) -> u8 {
+    fn normalize_rating(&self, rating: f64, target_scale: u8, _media_type: &MediaType) -> u8 {
         // Trakt uses 1-10 scale, same as target
         rating.round() as u8
     }
     
-    fn denormalize_rating(&self, rating: u8, source_scale: u8) -> f64 {
+    fn denormalize_rating(&self, rating: u8, source_scale: u8, _media_type: &MediaType) -> f64 {
         // Trakt uses 1-10 scale
         rating as f64
     }
     
-    fn native_rating_scale(&self) -> u8 {
+    fn native_rating_scale(&self, _media_type: &MediaType) -> u8 {
         10
     }
 }
@@ -278,10 +394,7 @@ impl IdLookupProvider for TraktClient {
         year: Option<u32>,
         media_type: &MediaType,
     ) -> Result<Option<MediaIds>, Box<dyn std::error::Error + Send + Sync>> {
-        let access_token = self.access_token()
-            .map_err(|e| Box::new(std::io::Error::new(std::io::ErrorKind::Other, format!("{}", e))) as Box<dyn std::error::Error + Send + Sync>)?;
-        
-        api::search_by_title(&self.client, access_token, &self.client_id, title, year, media_type)
+        self.authorized(move |access_token| async move { api::search_by_title(&self.client, &access_token, &self.client_id, title, year, media_type, &self.id_matching).await })
             .await
             .map_err(|e| Box::new(std::io::Error::new(std::io::ErrorKind::Other, format!("{}", e))) as Box<dyn std::error::Error + Send + Sync>)
     }
@@ -291,10 +404,7 @@ impl IdLookupProvider for TraktClient {
         imdb_id: &str,
         media_type: &MediaType,
     ) -> Result<Option<(String, Option<u32>, MediaIds)>, Box<dyn std::error::Error + Send + Sync>> {
-        let access_token = self.access_token()
-            .map_err(|e| Box::new(std::io::Error::new(std::io::ErrorKind::Other, format!("{}", e))) as Box<dyn std::error::Error + Send + Sync>)?;
-        
-        api::search_by_imdb_id(&self.client, access_token, &self.client_id, imdb_id, media_type)
+        self.authorized(move |access_token| async move { api::search_by_imdb_id(&self.client, &access_token, &self.client_id, imdb_id, media_type).await })
             .await
             .map_err(|e| Box::new(std::io::Error::new(std::io::ErrorKind::Other, format!("{}", e))) as Box<dyn std::error::Error + Send + Sync>)
     }
@@ -332,5 +442,136 @@ impl CapabilityRegistry for TraktClient {
     fn as_id_lookup_provider(&self) -> Option<&dyn IdLookupProvider> {
         Some(self)
     }
+
+    fn as_playback_progress(&self) -> Option<&dyn PlaybackProgress> {
+        Some(self)
+    }
+
+    fn as_collection_source(&self) -> Option<&dyn CollectionSource> {
+        Some(self)
+    }
+
+    fn as_episode_lookup(&self) -> Option<&dyn EpisodeLookup> {
+        Some(self)
+    }
+
+    fn as_history_pruning(&self) -> Option<&dyn HistoryPruning> {
+        Some(self)
+    }
+}
+
+#[async_trait]
+impl PlaybackProgress for TraktClient {
+    async fn scrobble(&self, item: &WatchHistory, progress: f32) -> Result<(), crate::error::SourceError> {
+        self.authorized(move |access_token| async move { api::scrobble_pause(&self.client, &access_token, item, progress, &self.client_id).await })
+            .await
+            .map_err(|e| crate::error::SourceError::new(format!("{}", e)))
+    }
+}
+
+#[async_trait]
+impl CollectionSource for TraktClient {
+    async fn get_collection(&self) -> Result<Vec<CollectionItem>, crate::error::SourceError> {
+        self.authorized(move |access_token| async move { api::get_collection(&self.client, &self.http_cache, &access_token, &self.client_id).await })
+            .await
+            .map_err(|e| crate::error::SourceError::new(format!("{}", e)))
+    }
+
+    fn accepts_collection_writes(&self) -> bool {
+        true
+    }
+
+    async fn add_to_collection(&self, items: &[CollectionItem]) -> Result<(), crate::error::SourceError> {
+        write_in_chunks(items, self.batch_size, |chunk| {
+            self.authorized(move |access_token| async move {
+                api::add_to_collection(&self.client, &access_token, chunk, &self.client_id, &self.retry).await
+            })
+        })
+        .await
+    }
+}
+
+#[async_trait]
+impl EpisodeLookup for TraktClient {
+    async fn aired_episodes(&self, show_id: &str) -> Result<Vec<AiredEpisode>, crate::error::SourceError> {
+        api::get_aired_episodes(&self.client, &self.client_id, show_id)
+            .await
+            .map_err(|e| crate::error::SourceError::new(format!("{}", e)))
+    }
 }
 
+#[async_trait]
+impl HistoryPruning for TraktClient {
+    async fn prune_watch_history(&self, cutoff: DateTime<Utc>, dry_run: bool) -> Result<usize, crate::error::SourceError> {
+        let ids = self.authorized(move |access_token| async move { api::get_history_ids_before(&self.client, &access_token, &self.client_id, cutoff).await })
+            .await
+            .map_err(|e| crate::error::SourceError::new(format!("{}", e)))?;
+        if !dry_run && !ids.is_empty() {
+            let ids = &ids;
+            self.authorized(move |access_token| async move { api::remove_history_ids(&self.client, &access_token, &self.client_id, ids).await })
+                .await
+                .map_err(|e| crate::error::SourceError::new(format!("{}", e)))?;
+            info!("Removed {} plays watched before {} from Trakt", ids.len(), cutoff.date_naive());
+        }
+        Ok(ids.len())
+    }
+}
+
+/// Save rotated tokens; the refresh token they replace no longer works
+fn save_tokens(cred_store: &mut media_sync_config::CredentialStore, token_info: auth::TokenInfo) -> Result<()> {
+    cred_store.set_trakt_access_token(token_info.access_token);
+    cred_store.set_trakt_refresh_token(token_info.refresh_token);
+    cred_store.set_trakt_token_expires(token_info.expires_at);
+    cred_store.save()
+}
+
+/// Send `items` in chunks of `batch_size`, one request after another. The first failed
+/// chunk ends the write; the items of the chunks before it are reported as committed.
+async fn write_in_chunks<'a, T, F, Fut>(items: &'a [T], batch_size: usize, mut write: F) -> Result<(), crate::error::SourceError>
+where
+    F: FnMut(&'a [T]) -> Fut,
+    Fut: std::future::Future<Output = Result<()>>,
+{
+    let batch_size = batch_size.max(1);
+    let chunk_count = items.len().div_ceil(batch_size);
+    let mut committed = 0;
+    for (index, chunk) in items.chunks(batch_size).enumerate() {
+        if let Err(e) = write(chunk).await {
+            let message = if chunk_count > 1 {
+                format!("{} (chunk {} of {}; {} of {} items written)", e, index + 1, chunk_count, committed, items.len())
+            } else {
+                format!("{}", e)
+            };
+            return Err(crate::error::SourceError::partial_write(message, committed));
+        }
+        committed += chunk.len();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+    use std::sync::Mutex;
+
+    #[tokio::test]
+    async fn test_write_in_chunks_reports_items_committed_before_a_failed_chunk() {
+        let items: Vec<u32> = (0..250).collect();
+        let sent = Mutex::new(Vec::new());
+        let result = write_in_chunks(&items, 100, |chunk| {
+            sent.lock().unwrap().push(chunk.len());
+            let fail = chunk[0] == 200;
+            async move { if fail { Err(anyhow!("503 Service Unavailable")) } else { Ok(()) } }
+        })
+        .await;
+
+        let error = result.unwrap_err();
+        assert_eq!(*sent.lock().unwrap(), vec![100, 100, 50]);
+        assert_eq!(error.committed(), 200);
+        assert_eq!(error.to_string(), "503 Service Unavailable (chunk 3 of 3; 200 of 250 items written)");
+
+        let result = write_in_chunks(&items, 100, |_| async { Ok(()) }).await;
+        assert!(result.is_ok());
+    }
+}
@@ -0,0 +1,132 @@
+use crate::output::Output;
+use clap::ValueEnum;
+use color_eyre::Result;
+use media_sync_config::PathManager;
+use media_sync_core::CacheManager;
+use owo_colors::OwoColorize;
+use serde_json::json;
+
+/// Data types stored in the collect cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CacheDataType {
+    Watchlist,
+    Ratings,
+    Reviews,
+    #[value(name = "watch-history")]
+    WatchHistory,
+    Excluded,
+}
+
+impl CacheDataType {
+    const ALL: [CacheDataType; 5] = [
+        CacheDataType::Watchlist,
+        CacheDataType::Ratings,
+        CacheDataType::Reviews,
+        CacheDataType::WatchHistory,
+        CacheDataType::Excluded,
+    ];
+
+    /// Name used for the cache file (`collect/{source}/{name}.json`)
+    fn cache_name(&self) -> &'static str {
+        match self {
+            CacheDataType::Watchlist => "watchlist",
+            CacheDataType::Ratings => "ratings",
+            CacheDataType::Reviews => "reviews",
+            CacheDataType::WatchHistory => "watch_history",
+            CacheDataType::Excluded => "excluded",
+        }
+    }
+}
+
+pub async fn run_cache(cmd: crate::CacheCommands, output: &Output) -> Result<()> {
+    match cmd {
+        crate::CacheCommands::Dump { source, data_type, sample } => dump_cache(&source, data_type, sample, output).await,
+    }
+}
+
+async fn dump_cache(source: &str, data_type: Option<CacheDataType>, sample: usize, output: &Output) -> Result<()> {
+    let source = source.to_lowercase();
+    if !media_sync_config::is_known_source(&source) {
+        return Err(color_eyre::eyre::eyre!(
+            "Unknown source '{}'. Valid sources: {}",
+            source,
+            media_sync_config::source_names().collect::<Vec<_>>().join(", ")
+        ));
+    }
+
+    let path_manager = PathManager::default();
+    let cache_manager = CacheManager::new(&path_manager)
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to open cache at {}: {}", path_manager.cache_dir().display(), e))?;
+
+    let data_types: Vec<CacheDataType> = match data_type {
+        Some(data_type) => vec![data_type],
+        None => CacheDataType::ALL.to_vec(),
+    };
+
+    let mut entries = Vec::new();
+    for data_type in data_types {
+        let name = data_type.cache_name();
+        let path = cache_manager.get_cache_path(&source, name);
+        let items = cache_manager.peek_source_data(&source, name)
+            .map_err(|e| color_eyre::eyre::eyre!("{}", e))?;
+        entries.push((name, path, items));
+    }
+
+    match output.format() {
+        crate::output::OutputFormat::Human => {
+            if output.is_quiet() {
+                return Ok(());
+            }
+
+            println!("\n{} {}", "Cache for".bright_cyan().bold(), source.bright_cyan().bold());
+            for (name, path, items) in &entries {
+                println!();
+                match items {
+                    Some(items) => {
+                        println!("{} {} ({})", name.bold(), format!("{} items", items.len()).green(), path.display().to_string().bright_black());
+                        let shown = if sample == 0 { items.len() } else { sample.min(items.len()) };
+                        for item in items.iter().take(shown) {
+                            println!("{}", serde_json::to_string_pretty(item).unwrap_or_default());
+                        }
+                        if shown < items.len() {
+                            println!("{}", format!("... {} more", items.len() - shown).bright_black());
+                        }
+                    }
+                    None => {
+                        println!("{} {}", name.bold(), "not cached".yellow());
+                    }
+                }
+            }
+            println!();
+        }
+        crate::output::OutputFormat::Json | crate::output::OutputFormat::JsonPretty => {
+            let mut data_types = serde_json::Map::new();
+            for (name, path, items) in entries {
+                let value = match items {
+                    Some(items) => {
+                        let shown = if sample == 0 { items.len() } else { sample.min(items.len()) };
+                        json!({
+                            "cached": true,
+                            "path": path.display().to_string(),
+                            "count": items.len(),
+                            "sample": items.into_iter().take(shown).collect::<Vec<_>>(),
+                        })
+                    }
+                    None => json!({
+                        "cached": false,
+                        "path": path.display().to_string(),
+                        "count": 0,
+                        "sample": [],
+                    }),
+                };
+                data_types.insert(name.to_string(), value);
+            }
+            output.json(&json!({
+                "source": source,
+                "data_types": data_types,
+            }));
+        }
+    }
+
+    Ok(())
+}
@@ -3,9 +3,10 @@ use color_eyre::eyre::Context;
 use color_eyre::Result;
 use media_sync_config::{CredentialStore, PathManager};
 use media_sync_core::CacheManager;
+use media_sync_core::id_cache_storage::IdCacheStorage;
 use std::fs;
 
-pub async fn run_clear(all: bool, cache: bool, credentials: bool, timestamps: bool, output: &Output) -> Result<()> {
+pub async fn run_clear(all: bool, cache: bool, credentials: bool, timestamps: bool, id_cache: Option<&str>, output: &Output) -> Result<()> {
     let path_manager = PathManager::default();
 
     if all {
@@ -34,8 +35,13 @@ pub async fn run_clear(all: bool, cache: bool, credentials: bool, timestamps: bo
         cleared_anything = true;
     }
 
+    if let Some(imdb_id) = id_cache {
+        clear_id_cache_entry(&path_manager, imdb_id, output)?;
+        cleared_anything = true;
+    }
+
     if !cleared_anything {
-        output.warn("No clear option specified. Use --cache, --credentials, --timestamps, or --all");
+        output.warn("No clear option specified. Use --cache, --credentials, --timestamps, --id-cache, or --all");
         output.println("\nExample: totalrecall clear --cache");
     }
 
@@ -107,25 +113,44 @@ async fn clear_timestamps(path_manager: &PathManager, output: &Output) -> Result
     cred_store.load()
         .map_err(|e| color_eyre::eyre::eyre!("Failed to load credentials: {}", e))?;
     
-    // Collect all timestamp-related keys
-    let keys_to_remove: Vec<String> = cred_store
-        .get_all_keys()
-        .into_iter()
-        .filter(|k| k.contains("_last_sync_") || k == "simkl_last_activities")
-        .collect();
+    // All timestamp-related keys, of every account
+    let removed = cred_store.remove_matching(|k| k.contains("_last_sync_") || k.starts_with("simkl_last_activities"));
     
-    if keys_to_remove.is_empty() {
+    if removed == 0 {
         output.info("No sync timestamps found to clear");
         return Ok(());
     }
     
-    for key in &keys_to_remove {
-        cred_store.remove(key);
-    }
-    
     cred_store.save()
         .map_err(|e| color_eyre::eyre::eyre!("Failed to save credentials: {}", e))?;
-    output.success(&format!("Cleared {} sync timestamp(s)", keys_to_remove.len()));
+    output.success(&format!("Cleared {} sync timestamp(s)", removed));
     Ok(())
 }
 
+/// Drop one title from the ID cache, leaving every other resolved ID in place
+fn clear_id_cache_entry(path_manager: &PathManager, imdb_id: &str, output: &Output) -> Result<()> {
+    let imdb_id = imdb_id.trim();
+    let is_imdb_id = imdb_id.strip_prefix("tt")
+        .is_some_and(|digits| !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()));
+    if !is_imdb_id {
+        return Err(color_eyre::eyre::eyre!("'{}' is not an IMDB ID (expected e.g. tt1234567)", imdb_id));
+    }
+
+    let cache_dir = path_manager.cache_id_dir();
+    let mut storage = IdCacheStorage::new(&cache_dir);
+    if !storage.cache_exists() {
+        output.info("No ID cache found, nothing to clear");
+        return Ok(());
+    }
+
+    let mut id_cache = storage.load()
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to load ID cache from {}: {}", cache_dir.display(), e))?;
+    if id_cache.invalidate(imdb_id).is_none() {
+        output.info(&format!("{} is not in the ID cache", imdb_id));
+        return Ok(());
+    }
+    storage.save(&id_cache)
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to save ID cache to {}: {}", cache_dir.display(), e))?;
+    output.success(&format!("Cleared {} from the ID cache; the next sync resolves it again", imdb_id));
+    Ok(())
+}
@@ -2,22 +2,26 @@ use super::prompts;
 use crate::output::Output;
 use color_eyre::Result;
 use comfy_table::{Cell, Table};
-use media_sync_config::{Config, CredentialStore, PathManager, SyncOptions, TraktConfig, SimklConfig, PlexConfig, default_plex_status_mapping, default_simkl_status_mapping};
+use media_sync_config::{Config, ConfigBundle, CredentialStore, PathManager, SecretsExport, SyncOptions, TraktConfig, SimklConfig, PlexConfig, AniListConfig, default_plex_status_mapping, default_simkl_status_mapping};
 use media_sync_sources::{trakt_authenticate, simkl_authenticate};
 use owo_colors::OwoColorize;
 use serde_json::json;
 use std::io::{self, Write};
+use std::path::Path;
 
 pub async fn run_config(cmd: crate::ConfigCommands, output: &Output) -> Result<()> {
     match cmd {
         crate::ConfigCommands::Show { full } => show_config(full, output).await,
-        crate::ConfigCommands::Trakt { client_id, client_secret } => configure_trakt(client_id, client_secret, output).await,
-        crate::ConfigCommands::Simkl { client_id, client_secret } => configure_simkl(client_id, client_secret, output).await,
-        crate::ConfigCommands::Imdb { username } => configure_imdb(username, output).await,
-        crate::ConfigCommands::Plex { token, server_url } => configure_plex(token, server_url, output).await,
+        crate::ConfigCommands::Trakt { client_id, client_secret, account } => configure_trakt(client_id, client_secret, account, output).await,
+        crate::ConfigCommands::Simkl { client_id, client_secret, account } => configure_simkl(client_id, client_secret, account, output).await,
+        crate::ConfigCommands::Imdb { username, account } => configure_imdb(username, account, output).await,
+        crate::ConfigCommands::Plex { token, server_url, account } => configure_plex(token, server_url, account, output).await,
+        crate::ConfigCommands::Anilist { username, token, account } => configure_anilist(username, token, account, output).await,
         crate::ConfigCommands::Sync { enable_watchlist, enable_ratings, enable_reviews, enable_watch_history } => {
             configure_sync(enable_watchlist, enable_ratings, enable_reviews, enable_watch_history, output).await
         }
+        crate::ConfigCommands::Export { file, include_secrets, encrypt } => export_config(&file, include_secrets, encrypt, output),
+        crate::ConfigCommands::Import { file } => import_config(&file, output),
     }
 }
 
@@ -78,6 +82,30 @@ async fn show_config(full: bool, output: &Output) -> Result<()> {
                     Cell::new("Client Secret"),
                     Cell::new(client_secret_display)
                 ]);
+                trakt_table.add_row(vec![
+                    Cell::new("Account"),
+                    Cell::new(trakt.account.as_deref().unwrap_or("default"))
+                ]);
+                trakt_table.add_row(vec![
+                    Cell::new("Media Types"),
+                    Cell::new(format_media_types(config.sources.media_types.get("trakt")))
+                ]);
+                trakt_table.add_row(vec![
+                    Cell::new("Likes As"),
+                    Cell::new(trakt.likes_as.map(|likes_as| format!("{:?}", likes_as)).unwrap_or_else(|| "Off".to_string()))
+                ]);
+                trakt_table.add_row(vec![
+                    Cell::new("Retries"),
+                    Cell::new(format_retry(&trakt.retry))
+                ]);
+                trakt_table.add_row(vec![
+                    Cell::new("Rate Limit"),
+                    Cell::new(format_rate_limit(&trakt.rate_limit))
+                ]);
+                trakt_table.add_row(vec![
+                    Cell::new("Batch Size"),
+                    Cell::new(trakt.batch_size.to_string())
+                ]);
                 trakt_table.load_preset(comfy_table::presets::UTF8_FULL);
                 trakt_table.apply_modifier(comfy_table::modifiers::UTF8_ROUND_CORNERS);
                 println!("{}", trakt_table);
@@ -107,6 +135,22 @@ async fn show_config(full: bool, output: &Output) -> Result<()> {
                     Cell::new("Client Secret"),
                     Cell::new(client_secret_display)
                 ]);
+                simkl_table.add_row(vec![
+                    Cell::new("Account"),
+                    Cell::new(simkl.account.as_deref().unwrap_or("default"))
+                ]);
+                simkl_table.add_row(vec![
+                    Cell::new("Media Types"),
+                    Cell::new(format_media_types(config.sources.media_types.get("simkl")))
+                ]);
+                simkl_table.add_row(vec![
+                    Cell::new("Retries"),
+                    Cell::new(format_retry(&simkl.retry))
+                ]);
+                simkl_table.add_row(vec![
+                    Cell::new("Rate Limit"),
+                    Cell::new(format_rate_limit(&simkl.rate_limit))
+                ]);
                 simkl_table.load_preset(comfy_table::presets::UTF8_FULL);
                 simkl_table.apply_modifier(comfy_table::modifiers::UTF8_ROUND_CORNERS);
                 println!("{}", simkl_table);
@@ -128,6 +172,18 @@ async fn show_config(full: bool, output: &Output) -> Result<()> {
                     Cell::new("Username"),
                     Cell::new(username_display)
                 ]);
+                imdb_table.add_row(vec![
+                    Cell::new("Resolve Episode Check-ins"),
+                    Cell::new(if imdb.resolve_episode_checkins { "✓".green().to_string() } else { "✗".red().to_string() })
+                ]);
+                imdb_table.add_row(vec![
+                    Cell::new("Account"),
+                    Cell::new(imdb.account.as_deref().unwrap_or("default"))
+                ]);
+                imdb_table.add_row(vec![
+                    Cell::new("Media Types"),
+                    Cell::new(format_media_types(config.sources.media_types.get("imdb")))
+                ]);
                 imdb_table.load_preset(comfy_table::presets::UTF8_FULL);
                 imdb_table.apply_modifier(comfy_table::modifiers::UTF8_ROUND_CORNERS);
                 println!("{}", imdb_table);
@@ -148,12 +204,123 @@ async fn show_config(full: bool, output: &Output) -> Result<()> {
                     Cell::new("Server URL"),
                     Cell::new(&plex.server_url)
                 ]);
+                plex_table.add_row(vec![
+                    Cell::new("Account"),
+                    Cell::new(plex.account.as_deref().unwrap_or("default"))
+                ]);
+                plex_table.add_row(vec![
+                    Cell::new("Media Types"),
+                    Cell::new(format_media_types(config.sources.media_types.get("plex")))
+                ]);
+                plex_table.add_row(vec![
+                    Cell::new("Skip Unmatched Items"),
+                    Cell::new(if plex.skip_unmatched { "✓".green().to_string() } else { "✗".red().to_string() })
+                ]);
+                let scales = &plex.rating_scales;
+                let scales = [("movie", scales.movie), ("show", scales.show), ("episode", scales.episode)]
+                    .into_iter()
+                    .filter_map(|(media_type, scale)| scale.map(|scale| format!("{} 1-{}", media_type, scale)))
+                    .collect::<Vec<_>>();
+                plex_table.add_row(vec![
+                    Cell::new("Rating Scales"),
+                    Cell::new(if scales.is_empty() { "1-10".to_string() } else { scales.join(", ") })
+                ]);
+                if !plex.label_status_map.is_empty() {
+                    let mut labels = plex.label_status_map.iter()
+                        .map(|(label, status)| format!("{} → {:?}", label, status))
+                        .collect::<Vec<_>>();
+                    labels.sort();
+                    plex_table.add_row(vec![
+                        Cell::new("Label Statuses"),
+                        Cell::new(labels.join(", "))
+                    ]);
+                }
                 plex_table.load_preset(comfy_table::presets::UTF8_FULL);
                 plex_table.apply_modifier(comfy_table::modifiers::UTF8_ROUND_CORNERS);
                 println!("{}", plex_table);
                 println!();
             }
 
+            // Letterboxd Configuration
+            if let Some(letterboxd) = &config.sources.letterboxd {
+                let mut letterboxd_table = Table::new();
+                letterboxd_table.set_header(vec![
+                    Cell::new("Letterboxd Configuration").fg(comfy_table::Color::Cyan).add_attribute(comfy_table::Attribute::Bold)
+                ]);
+                letterboxd_table.add_row(vec![
+                    Cell::new("Enabled"),
+                    Cell::new(if letterboxd.enabled { "✓".green().to_string() } else { "✗".red().to_string() })
+                ]);
+                letterboxd_table.add_row(vec![
+                    Cell::new("Export Path"),
+                    Cell::new(&letterboxd.export_path)
+                ]);
+                letterboxd_table.add_row(vec![
+                    Cell::new("Media Types"),
+                    Cell::new(format_media_types(config.sources.media_types.get("letterboxd")))
+                ]);
+                letterboxd_table.load_preset(comfy_table::presets::UTF8_FULL);
+                letterboxd_table.apply_modifier(comfy_table::modifiers::UTF8_ROUND_CORNERS);
+                println!("{}", letterboxd_table);
+                println!();
+            }
+
+            // AniList Configuration
+            if let Some(anilist) = &config.sources.anilist {
+                let mut anilist_table = Table::new();
+                anilist_table.set_header(vec![
+                    Cell::new("AniList Configuration").fg(comfy_table::Color::Cyan).add_attribute(comfy_table::Attribute::Bold)
+                ]);
+                anilist_table.add_row(vec![
+                    Cell::new("Enabled"),
+                    Cell::new(if anilist.enabled { "✓".green().to_string() } else { "✗".red().to_string() })
+                ]);
+                anilist_table.add_row(vec![
+                    Cell::new("Username"),
+                    Cell::new(if anilist.username.is_empty() { "(token's user)" } else { anilist.username.as_str() })
+                ]);
+                anilist_table.add_row(vec![
+                    Cell::new("Score Scale"),
+                    Cell::new(format!("1-{}", anilist.score_scale))
+                ]);
+                anilist_table.add_row(vec![
+                    Cell::new("Account"),
+                    Cell::new(anilist.account.as_deref().unwrap_or("default"))
+                ]);
+                anilist_table.add_row(vec![
+                    Cell::new("Media Types"),
+                    Cell::new(format_media_types(config.sources.media_types.get("anilist")))
+                ]);
+                anilist_table.load_preset(comfy_table::presets::UTF8_FULL);
+                anilist_table.apply_modifier(comfy_table::modifiers::UTF8_ROUND_CORNERS);
+                println!("{}", anilist_table);
+                println!();
+            }
+
+            // File Source Configuration
+            if let Some(file) = &config.sources.file {
+                let mut file_table = Table::new();
+                file_table.set_header(vec![
+                    Cell::new("File Source Configuration").fg(comfy_table::Color::Cyan).add_attribute(comfy_table::Attribute::Bold)
+                ]);
+                file_table.add_row(vec![
+                    Cell::new("Enabled"),
+                    Cell::new(if file.enabled { "✓".green().to_string() } else { "✗".red().to_string() })
+                ]);
+                file_table.add_row(vec![
+                    Cell::new("Path"),
+                    Cell::new(&file.path)
+                ]);
+                file_table.add_row(vec![
+                    Cell::new("Media Types"),
+                    Cell::new(format_media_types(config.sources.media_types.get("file")))
+                ]);
+                file_table.load_preset(comfy_table::presets::UTF8_FULL);
+                file_table.apply_modifier(comfy_table::modifiers::UTF8_ROUND_CORNERS);
+                println!("{}", file_table);
+                println!();
+            }
+
             // Resolution Configuration
             let mut resolution_table = Table::new();
             resolution_table.set_header(vec![
@@ -167,6 +334,10 @@ async fn show_config(full: bool, output: &Output) -> Result<()> {
                 Cell::new("Source Preference"),
                 Cell::new(format!("{:?}", config.resolution.source_preference))
             ]);
+            resolution_table.add_row(vec![
+                Cell::new("Fetch Order"),
+                Cell::new(format!("{:?}", config.resolution.collection_order()))
+            ]);
             resolution_table.add_row(vec![
                 Cell::new("Timestamp Tolerance"),
                 Cell::new(format!("{} seconds", config.resolution.timestamp_tolerance_seconds))
@@ -183,6 +354,18 @@ async fn show_config(full: bool, output: &Output) -> Result<()> {
                     Cell::new(format!("{:?}", watchlist_strategy))
                 ]);
             }
+            for (field, _) in config.resolution.preference_overrides() {
+                let label = match field {
+                    "watchlist_preference" => "Watchlist Preference",
+                    "ratings_preference" => "Ratings Preference",
+                    "reviews_preference" => "Reviews Preference",
+                    _ => "Watch History Preference",
+                };
+                resolution_table.add_row(vec![
+                    Cell::new(label),
+                    Cell::new(format!("{:?}", config.resolution.preference_for(field.trim_end_matches("_preference"))))
+                ]);
+            }
             resolution_table.load_preset(comfy_table::presets::UTF8_FULL);
             resolution_table.apply_modifier(comfy_table::modifiers::UTF8_ROUND_CORNERS);
             println!("{}", resolution_table);
@@ -209,6 +392,18 @@ async fn show_config(full: bool, output: &Output) -> Result<()> {
                 Cell::new("Sync Watch History"),
                 Cell::new(if config.sync.sync_watch_history { "✓".green().to_string() } else { "✗".red().to_string() })
             ]);
+            sync_table.add_row(vec![
+                Cell::new("Sync Lists"),
+                Cell::new(if config.sync.sync_lists { "✓".green().to_string() } else { "✗".red().to_string() })
+            ]);
+            sync_table.add_row(vec![
+                Cell::new("Sync Collection"),
+                Cell::new(if config.sync.sync_collection { "✓".green().to_string() } else { "✗".red().to_string() })
+            ]);
+            sync_table.add_row(vec![
+                Cell::new("Sync Show Progress"),
+                Cell::new(if config.sync.sync_show_progress { "✓".green().to_string() } else { "✗".red().to_string() })
+            ]);
             sync_table.add_row(vec![
                 Cell::new("Remove Watched from Watchlists"),
                 Cell::new(if config.sync.remove_watched_from_watchlists { "✓".green().to_string() } else { "✗".red().to_string() })
@@ -217,12 +412,87 @@ async fn show_config(full: bool, output: &Output) -> Result<()> {
                 Cell::new("Mark Rated as Watched"),
                 Cell::new(if config.sync.mark_rated_as_watched { "✓".green().to_string() } else { "✗".red().to_string() })
             ]);
+            sync_table.add_row(vec![
+                Cell::new("Watch History from Ratings"),
+                Cell::new(format!("{:?}", config.sync.from_ratings_mode()))
+            ]);
+            sync_table.add_row(vec![
+                Cell::new("Sync Playback Progress"),
+                Cell::new(if config.sync.watch_history.sync_progress { "✓".green().to_string() } else { "✗".red().to_string() })
+            ]);
+            sync_table.add_row(vec![
+                Cell::new("Chunk Watch History by Year"),
+                Cell::new(if config.sync.watch_history.chunk_by_year { "✓".green().to_string() } else { "✗".red().to_string() })
+            ]);
             if let Some(days) = config.sync.remove_watchlist_items_older_than_days {
                 sync_table.add_row(vec![
                     Cell::new("Remove Watchlist Items Older Than"),
                     Cell::new(format!("{} days", days))
                 ]);
             }
+            sync_table.add_row(vec![
+                Cell::new("Ratings Source-Local (No Cross Sync)"),
+                Cell::new(if config.sync.ratings.no_cross_sync { "✓".green().to_string() } else { "✗".red().to_string() })
+            ]);
+            sync_table.add_row(vec![
+                Cell::new("Undated Ratings Default Date"),
+                Cell::new(format!("{:?}", config.sync.ratings.default_date))
+            ]);
+            if let Some(max_items) = config.sync.watchlist.max_items_to_add_per_run {
+                sync_table.add_row(vec![
+                    Cell::new("Max Watchlist Additions per Run"),
+                    Cell::new(max_items.to_string())
+                ]);
+            }
+            let strip_emoji = if config.sync.reviews.strip_emoji {
+                "all targets".to_string()
+            } else if config.sync.reviews.strip_emoji_for.is_empty() {
+                "off".to_string()
+            } else {
+                config.sync.reviews.strip_emoji_for.join(", ")
+            };
+            sync_table.add_row(vec![
+                Cell::new("Strip Emoji from Reviews"),
+                Cell::new(strip_emoji)
+            ]);
+            sync_table.add_row(vec![
+                Cell::new("Review Indexing Grace"),
+                Cell::new(format!("{} min", config.sync.reviews.indexing_grace_minutes))
+            ]);
+            sync_table.add_row(vec![
+                Cell::new("Minimum Review Length"),
+                Cell::new(match config.sync.min_review_length {
+                    Some(0) => "none".to_string(),
+                    Some(min) => format!("{} characters", min),
+                    None => format!("{} characters (IMDB only)", media_sync_config::DEFAULT_MIN_REVIEW_LENGTH),
+                })
+            ]);
+            if !config.sync.exclude_tags.is_empty() {
+                sync_table.add_row(vec![
+                    Cell::new("Exclude Tags"),
+                    Cell::new(config.sync.exclude_tags.join(", "))
+                ]);
+            }
+            sync_table.add_row(vec![
+                Cell::new("When Another Sync Is Running"),
+                Cell::new(match (config.sync.lock.on_conflict, config.sync.lock.wait_timeout_seconds) {
+                    (media_sync_config::LockConflict::Exit, _) => "exit".to_string(),
+                    (media_sync_config::LockConflict::Wait, None) => "wait".to_string(),
+                    (media_sync_config::LockConflict::Wait, Some(secs)) => format!("wait up to {}s", secs),
+                })
+            ]);
+            sync_table.add_row(vec![
+                Cell::new("Authoritative Removal Sources"),
+                Cell::new(if config.sync.removals.authoritative_sources.is_empty() {
+                    "none".to_string()
+                } else {
+                    config.sync.removals.authoritative_sources.join(", ")
+                })
+            ]);
+            sync_table.add_row(vec![
+                Cell::new("Export Resolved Data as Hub"),
+                Cell::new(if config.sync.hub.export { "✓".green().to_string() } else { "✗".red().to_string() })
+            ]);
             sync_table.load_preset(comfy_table::presets::UTF8_FULL);
             sync_table.apply_modifier(comfy_table::modifiers::UTF8_ROUND_CORNERS);
             println!("{}", sync_table);
@@ -246,11 +516,32 @@ async fn show_config(full: bool, output: &Output) -> Result<()> {
                     Cell::new("Run on Startup"),
                     Cell::new(if scheduler.run_on_startup { "✓".green().to_string() } else { "✗".red().to_string() })
                 ]);
+                scheduler_table.add_row(vec![
+                    Cell::new("Allowed Hours"),
+                    Cell::new(scheduler.allowed_hours.as_deref().unwrap_or("any time"))
+                ]);
                 scheduler_table.load_preset(comfy_table::presets::UTF8_FULL);
                 scheduler_table.apply_modifier(comfy_table::modifiers::UTF8_ROUND_CORNERS);
                 println!("{}", scheduler_table);
                 println!();
             }
+
+            // Credentials Configuration
+            let mut credentials_table = Table::new();
+            credentials_table.set_header(vec![
+                Cell::new("Credentials Configuration").fg(comfy_table::Color::Cyan).add_attribute(comfy_table::Attribute::Bold)
+            ]);
+            credentials_table.add_row(vec![
+                Cell::new("Backend"),
+                Cell::new(match config.credentials.backend {
+                    media_sync_config::CredentialBackendKind::File => "file (credentials.toml)",
+                    media_sync_config::CredentialBackendKind::Keyring => "keyring (OS keychain)",
+                })
+            ]);
+            credentials_table.load_preset(comfy_table::presets::UTF8_FULL);
+            credentials_table.apply_modifier(comfy_table::modifiers::UTF8_ROUND_CORNERS);
+            println!("{}", credentials_table);
+            println!();
         }
         crate::output::OutputFormat::Json | crate::output::OutputFormat::JsonPretty => {
             let json_config = json!({
@@ -260,6 +551,11 @@ async fn show_config(full: bool, output: &Output) -> Result<()> {
                         "enabled": trakt.enabled,
                         "client_id": if full { trakt.client_id.clone() } else { mask_string(&trakt.client_id) },
                         "client_secret": if full { trakt.client_secret.clone() } else { mask_string(&trakt.client_secret) },
+                        "likes_as": trakt.likes_as,
+                        "account": trakt.account,
+                        "retry": trakt.retry,
+                        "rate_limit": trakt.rate_limit,
+                        "batch_size": trakt.batch_size,
                     })
                 } else {
                     json!(null)
@@ -269,6 +565,9 @@ async fn show_config(full: bool, output: &Output) -> Result<()> {
                         "enabled": simkl.enabled,
                         "client_id": if full { simkl.client_id.clone() } else { mask_string(&simkl.client_id) },
                         "client_secret": if full { simkl.client_secret.clone() } else { mask_string(&simkl.client_secret) },
+                        "account": simkl.account,
+                        "retry": simkl.retry,
+                        "rate_limit": simkl.rate_limit,
                     })
                 } else {
                     json!(null)
@@ -277,6 +576,8 @@ async fn show_config(full: bool, output: &Output) -> Result<()> {
                     json!({
                         "enabled": imdb.enabled,
                         "username": if full { imdb.username.clone() } else { mask_string(&imdb.username) },
+                        "resolve_episode_checkins": imdb.resolve_episode_checkins,
+                        "account": imdb.account,
                     })
                 } else {
                     json!(null)
@@ -285,35 +586,96 @@ async fn show_config(full: bool, output: &Output) -> Result<()> {
                     json!({
                         "enabled": plex.enabled,
                         "server_url": plex.server_url,
+                        "skip_unmatched": plex.skip_unmatched,
+                        "rating_scales": plex.rating_scales,
+                        "label_status_map": plex.label_status_map,
+                        "account": plex.account,
+                    })
+                } else {
+                    json!(null)
+                },
+                "letterboxd": if let Some(letterboxd) = &config.sources.letterboxd {
+                    json!({
+                        "enabled": letterboxd.enabled,
+                        "export_path": letterboxd.export_path,
+                    })
+                } else {
+                    json!(null)
+                },
+                "anilist": if let Some(anilist) = &config.sources.anilist {
+                    json!({
+                        "enabled": anilist.enabled,
+                        "username": anilist.username,
+                        "score_scale": anilist.score_scale,
+                        "account": anilist.account,
+                    })
+                } else {
+                    json!(null)
+                },
+                "file": if let Some(file) = &config.sources.file {
+                    json!({
+                        "enabled": file.enabled,
+                        "path": file.path,
                     })
                 } else {
                     json!(null)
                 },
+                "request_timeout_secs": config.sources.request_timeout_secs,
+                "media_types": config.sources.media_types,
                 "resolution": {
                     "strategy": format!("{:?}", config.resolution.strategy),
                     "source_preference": config.resolution.source_preference,
+                    "fetch_order": config.resolution.collection_order(),
                     "timestamp_tolerance_seconds": config.resolution.timestamp_tolerance_seconds,
                     "ratings_strategy": config.resolution.ratings_strategy.as_ref().map(|s| format!("{:?}", s)),
                     "watchlist_strategy": config.resolution.watchlist_strategy.as_ref().map(|s| format!("{:?}", s)),
+                    "watchlist_preference": config.resolution.preference_for("watchlist"),
+                    "ratings_preference": config.resolution.preference_for("ratings"),
+                    "reviews_preference": config.resolution.preference_for("reviews"),
+                    "watch_history_preference": config.resolution.preference_for("watch_history"),
                 },
                 "sync": {
                     "sync_watchlist": config.sync.sync_watchlist,
                     "sync_ratings": config.sync.sync_ratings,
                     "sync_reviews": config.sync.sync_reviews,
                     "sync_watch_history": config.sync.sync_watch_history,
+                    "sync_lists": config.sync.sync_lists,
+                    "sync_collection": config.sync.sync_collection,
+                    "sync_show_progress": config.sync.sync_show_progress,
+                    "never_remove": config.sync.never_remove,
+                    "collect_timeout_secs": config.sync.collect_timeout_secs,
                     "remove_watched_from_watchlists": config.sync.remove_watched_from_watchlists,
                     "mark_rated_as_watched": config.sync.mark_rated_as_watched,
+                    "watch_history_from_ratings": config.sync.from_ratings_mode(),
+                    "watch_history_sync_progress": config.sync.watch_history.sync_progress,
+                    "watch_history_chunk_by_year": config.sync.watch_history.chunk_by_year,
                     "remove_watchlist_items_older_than_days": config.sync.remove_watchlist_items_older_than_days,
+                    "watchlist_max_items_to_add_per_run": config.sync.watchlist.max_items_to_add_per_run,
+                    "ratings_no_cross_sync": config.sync.ratings.no_cross_sync,
+                    "ratings_default_date": config.sync.ratings.default_date,
+                    "reviews_strip_emoji": config.sync.reviews.strip_emoji,
+                    "reviews_strip_emoji_for": config.sync.reviews.strip_emoji_for,
+                    "reviews_indexing_grace_minutes": config.sync.reviews.indexing_grace_minutes,
+                    "min_review_length": config.sync.min_review_length,
+                    "removals_authoritative_sources": config.sync.removals.authoritative_sources,
+                    "exclude_tags": config.sync.exclude_tags,
+                    "lock_on_conflict": config.sync.lock.on_conflict,
+                    "lock_wait_timeout_seconds": config.sync.lock.wait_timeout_seconds,
+                    "hub_export": config.sync.hub.export,
                 },
                 "scheduler": if let Some(scheduler) = &config.scheduler {
                     json!({
                         "schedule": scheduler.schedule,
                         "timezone": scheduler.timezone,
                         "run_on_startup": scheduler.run_on_startup,
+                        "allowed_hours": scheduler.allowed_hours,
                     })
                 } else {
                     json!(null)
-                }
+                },
+                "credentials": {
+                    "backend": config.credentials.backend,
+                },
             });
             output.json(&json_config);
         }
@@ -322,7 +684,7 @@ async fn show_config(full: bool, output: &Output) -> Result<()> {
     Ok(())
 }
 
-async fn configure_trakt(client_id_arg: Option<String>, client_secret_arg: Option<String>, output: &Output) -> Result<()> {
+async fn configure_trakt(client_id_arg: Option<String>, client_secret_arg: Option<String>, account_arg: Option<String>, output: &Output) -> Result<()> {
     let path_manager = PathManager::default();
     path_manager.ensure_directories()
         .map_err(|e| color_eyre::eyre::eyre!("Failed to create configuration directories: {}", e))?;
@@ -339,6 +701,11 @@ async fn configure_trakt(client_id_arg: Option<String>, client_secret_arg: Optio
                 client_id: String::new(),
                 client_secret: String::new(),
                 status_mapping: media_sync_config::default_trakt_status_mapping(),
+                likes_as: None,
+                account: None,
+                retry: Default::default(),
+                rate_limit: Default::default(),
+                batch_size: media_sync_config::DEFAULT_TRAKT_BATCH_SIZE,
             }),
             simkl: None,
             resolution: media_sync_config::ResolutionConfig {
@@ -350,17 +717,39 @@ async fn configure_trakt(client_id_arg: Option<String>, client_secret_arg: Optio
                 plex: None,
                 tmdb: None,
                 netflix: None,
+                letterboxd: None,
+                anilist: None,
+                file: None,
+                request_timeout_secs: None,
+                media_types: std::collections::HashMap::new(),
             },
             sync: SyncOptions {
                 sync_watchlist: true,
                 sync_ratings: true,
                 sync_reviews: true,
                 sync_watch_history: true,
+                sync_lists: false,
+                sync_collection: false,
+                sync_show_progress: false,
+                never_remove: false,
+                collect_timeout_secs: None,
                 remove_watched_from_watchlists: false,
                 mark_rated_as_watched: false,
                 remove_watchlist_items_older_than_days: None,
+                watch_history: media_sync_config::WatchHistoryOptions::default(),
+                watchlist: media_sync_config::WatchlistOptions::default(),
+                ratings: media_sync_config::RatingsOptions::default(),
+                reviews: media_sync_config::ReviewsOptions::default(),
+                min_review_length: None,
+                removals: media_sync_config::RemovalsOptions::default(),
+                exclude_tags: Vec::new(),
+                lock: media_sync_config::SyncLockOptions::default(),
+                hub: media_sync_config::HubOptions::default(),
             },
             scheduler: Some(media_sync_config::default_scheduler_config()),
+            credentials: media_sync_config::CredentialsConfig::default(),
+            id_matching: media_sync_config::IdMatchingConfig::default(),
+            notifications: media_sync_config::NotificationsConfig::default(),
         };
         default_config
     };
@@ -382,6 +771,11 @@ async fn configure_trakt(client_id_arg: Option<String>, client_secret_arg: Optio
             client_secret: String::new(),
             // Explicitly write default status mappings for user visibility
             status_mapping: media_sync_config::default_trakt_status_mapping(),
+            likes_as: None,
+            account: None,
+            retry: Default::default(),
+            rate_limit: Default::default(),
+            batch_size: media_sync_config::DEFAULT_TRAKT_BATCH_SIZE,
         });
     }
     let trakt_config = config.trakt.as_mut().unwrap();
@@ -453,6 +847,9 @@ async fn configure_trakt(client_id_arg: Option<String>, client_secret_arg: Optio
     trakt_config.enabled = true;
     trakt_config.client_id = client_id.clone();
     trakt_config.client_secret = client_secret.clone();
+    if let Some(account) = account_arg {
+        trakt_config.account = Some(validate_account_name(account)?);
+    }
     // Only update status_mapping if it's empty (first time setup)
     if trakt_config.status_mapping.to_normalized.is_empty() && trakt_config.status_mapping.from_normalized.is_empty() {
         trakt_config.status_mapping = media_sync_config::default_trakt_status_mapping();
@@ -516,7 +913,7 @@ async fn configure_trakt(client_id_arg: Option<String>, client_secret_arg: Optio
     Ok(())
 }
 
-async fn configure_simkl(client_id_arg: Option<String>, client_secret_arg: Option<String>, output: &Output) -> Result<()> {
+async fn configure_simkl(client_id_arg: Option<String>, client_secret_arg: Option<String>, account_arg: Option<String>, output: &Output) -> Result<()> {
     let path_manager = PathManager::default();
     path_manager.ensure_directories()
         .map_err(|e| color_eyre::eyre::eyre!("Failed to create configuration directories: {}", e))?;
@@ -536,6 +933,11 @@ async fn configure_simkl(client_id_arg: Option<String>, client_secret_arg: Optio
                     to_normalized: std::collections::HashMap::new(),
                     from_normalized: std::collections::HashMap::new(),
                 },
+                likes_as: None,
+                account: None,
+                retry: Default::default(),
+                rate_limit: Default::default(),
+                batch_size: media_sync_config::DEFAULT_TRAKT_BATCH_SIZE,
             }),
             simkl: None,
             resolution: media_sync_config::ResolutionConfig {
@@ -547,17 +949,39 @@ async fn configure_simkl(client_id_arg: Option<String>, client_secret_arg: Optio
                 plex: None,
                 tmdb: None,
                 netflix: None,
+                letterboxd: None,
+                anilist: None,
+                file: None,
+                request_timeout_secs: None,
+                media_types: std::collections::HashMap::new(),
             },
             sync: SyncOptions {
                 sync_watchlist: true,
                 sync_ratings: true,
                 sync_reviews: true,
                 sync_watch_history: true,
+                sync_lists: false,
+                sync_collection: false,
+                sync_show_progress: false,
+                never_remove: false,
+                collect_timeout_secs: None,
                 remove_watched_from_watchlists: false,
                 mark_rated_as_watched: false,
                 remove_watchlist_items_older_than_days: None,
+                watch_history: media_sync_config::WatchHistoryOptions::default(),
+                watchlist: media_sync_config::WatchlistOptions::default(),
+                ratings: media_sync_config::RatingsOptions::default(),
+                reviews: media_sync_config::ReviewsOptions::default(),
+                min_review_length: None,
+                removals: media_sync_config::RemovalsOptions::default(),
+                exclude_tags: Vec::new(),
+                lock: media_sync_config::SyncLockOptions::default(),
+                hub: media_sync_config::HubOptions::default(),
             },
             scheduler: Some(media_sync_config::default_scheduler_config()),
+            credentials: media_sync_config::CredentialsConfig::default(),
+            id_matching: media_sync_config::IdMatchingConfig::default(),
+            notifications: media_sync_config::NotificationsConfig::default(),
         };
         default_config
     };
@@ -654,12 +1078,22 @@ async fn configure_simkl(client_id_arg: Option<String>, client_secret_arg: Optio
         return Err(color_eyre::eyre::eyre!("Client ID and Client Secret are required"));
     }
 
+    let account = match account_arg {
+        Some(account) => Some(validate_account_name(account)?),
+        None => config.simkl.as_ref().and_then(|simkl| simkl.account.clone()),
+    };
+
     // Update config with default status mappings (explicitly written for user visibility)
+    let retry = config.simkl.as_ref().map(|simkl| simkl.retry.clone()).unwrap_or_default();
+    let rate_limit = config.simkl.as_ref().map(|simkl| simkl.rate_limit.clone()).unwrap_or_default();
     config.simkl = Some(SimklConfig {
         enabled: true,
         client_id: client_id.clone(),
         client_secret: client_secret.clone(),
         status_mapping: default_simkl_status_mapping(),
+        account,
+        retry,
+        rate_limit,
     });
     config.save_to_file(&config_file)
         .map_err(|e| color_eyre::eyre::eyre!("Failed to save config to {}: {}", config_file.display(), e))?;
@@ -700,7 +1134,7 @@ async fn configure_simkl(client_id_arg: Option<String>, client_secret_arg: Optio
     Ok(())
 }
 
-async fn configure_imdb(username_arg: Option<String>, output: &Output) -> Result<()> {
+async fn configure_imdb(username_arg: Option<String>, account_arg: Option<String>, output: &Output) -> Result<()> {
     let path_manager = PathManager::default();
     path_manager.ensure_directories()
         .map_err(|e| color_eyre::eyre::eyre!("Failed to create configuration directories: {}", e))?;
@@ -717,6 +1151,11 @@ async fn configure_imdb(username_arg: Option<String>, output: &Output) -> Result
                 client_id: String::new(),
                 client_secret: String::new(),
                 status_mapping: media_sync_config::default_trakt_status_mapping(),
+                likes_as: None,
+                account: None,
+                retry: Default::default(),
+                rate_limit: Default::default(),
+                batch_size: media_sync_config::DEFAULT_TRAKT_BATCH_SIZE,
             }),
             simkl: None,
             resolution: media_sync_config::ResolutionConfig {
@@ -728,17 +1167,39 @@ async fn configure_imdb(username_arg: Option<String>, output: &Output) -> Result
                 plex: None,
                 tmdb: None,
                 netflix: None,
+                letterboxd: None,
+                anilist: None,
+                file: None,
+                request_timeout_secs: None,
+                media_types: std::collections::HashMap::new(),
             },
             sync: SyncOptions {
                 sync_watchlist: true,
                 sync_ratings: true,
                 sync_reviews: true,
                 sync_watch_history: true,
+                sync_lists: false,
+                sync_collection: false,
+                sync_show_progress: false,
+                never_remove: false,
+                collect_timeout_secs: None,
                 remove_watched_from_watchlists: false,
                 mark_rated_as_watched: false,
                 remove_watchlist_items_older_than_days: None,
+                watch_history: media_sync_config::WatchHistoryOptions::default(),
+                watchlist: media_sync_config::WatchlistOptions::default(),
+                ratings: media_sync_config::RatingsOptions::default(),
+                reviews: media_sync_config::ReviewsOptions::default(),
+                min_review_length: None,
+                removals: media_sync_config::RemovalsOptions::default(),
+                exclude_tags: Vec::new(),
+                lock: media_sync_config::SyncLockOptions::default(),
+                hub: media_sync_config::HubOptions::default(),
             },
             scheduler: Some(media_sync_config::default_scheduler_config()),
+            credentials: media_sync_config::CredentialsConfig::default(),
+            id_matching: media_sync_config::IdMatchingConfig::default(),
+            notifications: media_sync_config::NotificationsConfig::default(),
         };
         default_config
     };
@@ -798,11 +1259,18 @@ async fn configure_imdb(username_arg: Option<String>, output: &Output) -> Result
     // Ask if IMDB should be enabled
     let enabled = prompts::prompt_yes_no("Enable IMDB sync?", Some(true))?;
 
+    let account = match account_arg {
+        Some(account) => Some(validate_account_name(account)?),
+        None => config.sources.imdb.as_ref().and_then(|imdb| imdb.account.clone()),
+    };
+
     // Update config with default status mappings (explicitly written for user visibility)
     config.sources.imdb = Some(media_sync_config::ImdbConfig {
         enabled,
         username: username.clone(),
         status_mapping: media_sync_config::default_imdb_status_mapping(),
+        resolve_episode_checkins: false,
+        account,
     });
     config.save_to_file(&config_file)
         .map_err(|e| color_eyre::eyre::eyre!("Failed to save config to {}: {}", config_file.display(), e))?;
@@ -823,7 +1291,7 @@ async fn configure_imdb(username_arg: Option<String>, output: &Output) -> Result
     Ok(())
 }
 
-async fn configure_plex(token_arg: Option<String>, server_url_arg: Option<String>, output: &Output) -> Result<()> {
+async fn configure_plex(token_arg: Option<String>, server_url_arg: Option<String>, account_arg: Option<String>, output: &Output) -> Result<()> {
     let path_manager = PathManager::default();
     path_manager.ensure_directories()
         .map_err(|e| color_eyre::eyre::eyre!("Failed to create configuration directories: {}", e))?;
@@ -846,17 +1314,39 @@ async fn configure_plex(token_arg: Option<String>, server_url_arg: Option<String
                 plex: None,
                 tmdb: None,
                 netflix: None,
+                letterboxd: None,
+                anilist: None,
+                file: None,
+                request_timeout_secs: None,
+                media_types: std::collections::HashMap::new(),
             },
             sync: SyncOptions {
                 sync_watchlist: true,
                 sync_ratings: true,
                 sync_reviews: true,
                 sync_watch_history: true,
+                sync_lists: false,
+                sync_collection: false,
+                sync_show_progress: false,
+                never_remove: false,
+                collect_timeout_secs: None,
                 remove_watched_from_watchlists: false,
                 mark_rated_as_watched: false,
                 remove_watchlist_items_older_than_days: None,
+                watch_history: media_sync_config::WatchHistoryOptions::default(),
+                watchlist: media_sync_config::WatchlistOptions::default(),
+                ratings: media_sync_config::RatingsOptions::default(),
+                reviews: media_sync_config::ReviewsOptions::default(),
+                min_review_length: None,
+                removals: media_sync_config::RemovalsOptions::default(),
+                exclude_tags: Vec::new(),
+                lock: media_sync_config::SyncLockOptions::default(),
+                hub: media_sync_config::HubOptions::default(),
             },
             scheduler: Some(media_sync_config::default_scheduler_config()),
+            credentials: media_sync_config::CredentialsConfig::default(),
+            id_matching: media_sync_config::IdMatchingConfig::default(),
+            notifications: media_sync_config::NotificationsConfig::default(),
         };
         default_config
     };
@@ -878,9 +1368,17 @@ async fn configure_plex(token_arg: Option<String>, server_url_arg: Option<String
             enabled: true,
             server_url: String::new(),
             status_mapping: default_plex_status_mapping(),
+            skip_unmatched: false,
+            rating_scales: Default::default(),
+            label_status_map: Default::default(),
+            account: None,
         });
     }
     let plex_config = config.sources.plex.as_mut().unwrap();
+    if let Some(account) = account_arg {
+        plex_config.account = Some(validate_account_name(account)?);
+    }
+    let account = plex_config.account.clone();
 
     // Get token
     let token = if let Some(t) = token_arg {
@@ -889,6 +1387,8 @@ async fn configure_plex(token_arg: Option<String>, server_url_arg: Option<String
         // Check if we have an existing token in credentials
         let credentials_file = path_manager.credentials_file();
         let mut cred_store = CredentialStore::new(credentials_file.clone());
+        // config.toml isn't saved yet, so the store doesn't know about a new --account
+        cred_store.select_account("plex", account.clone());
         let existing_token = cred_store.load()
             .ok()
             .and_then(|_| cred_store.get_plex_token().cloned());
@@ -1010,6 +1510,136 @@ async fn configure_plex(token_arg: Option<String>, server_url_arg: Option<String
     Ok(())
 }
 
+async fn configure_anilist(username_arg: Option<String>, token_arg: Option<String>, account_arg: Option<String>, output: &Output) -> Result<()> {
+    let path_manager = PathManager::default();
+    path_manager.ensure_directories()
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to create configuration directories: {}", e))?;
+
+    let config_file = path_manager.config_file();
+    let mut config = if config_file.exists() {
+        Config::load_from_file(&config_file)
+            .map_err(|e| color_eyre::eyre::eyre!("Failed to load config from {}: {}", config_file.display(), e))?
+    } else {
+        output.info("Configuration file not found. Creating default configuration...");
+        Config {
+            trakt: None,
+            simkl: None,
+            resolution: media_sync_config::ResolutionConfig {
+                source_preference: Vec::new(),
+                ..media_sync_config::ResolutionConfig::default()
+            },
+            sources: media_sync_config::SourceConfig {
+                imdb: None,
+                plex: None,
+                tmdb: None,
+                netflix: None,
+                letterboxd: None,
+                anilist: None,
+                file: None,
+                request_timeout_secs: None,
+                media_types: std::collections::HashMap::new(),
+            },
+            sync: SyncOptions {
+                sync_watchlist: true,
+                sync_ratings: true,
+                sync_reviews: true,
+                sync_watch_history: true,
+                sync_lists: false,
+                sync_collection: false,
+                sync_show_progress: false,
+                never_remove: false,
+                collect_timeout_secs: None,
+                remove_watched_from_watchlists: false,
+                mark_rated_as_watched: false,
+                remove_watchlist_items_older_than_days: None,
+                watch_history: media_sync_config::WatchHistoryOptions::default(),
+                watchlist: media_sync_config::WatchlistOptions::default(),
+                ratings: media_sync_config::RatingsOptions::default(),
+                reviews: media_sync_config::ReviewsOptions::default(),
+                min_review_length: None,
+                removals: media_sync_config::RemovalsOptions::default(),
+                exclude_tags: Vec::new(),
+                lock: media_sync_config::SyncLockOptions::default(),
+                hub: media_sync_config::HubOptions::default(),
+            },
+            scheduler: Some(media_sync_config::default_scheduler_config()),
+            credentials: media_sync_config::CredentialsConfig::default(),
+            id_matching: media_sync_config::IdMatchingConfig::default(),
+            notifications: media_sync_config::NotificationsConfig::default(),
+        }
+    };
+
+    print_section_header("AniList Setup", output);
+    output.println("");
+    output.println("Configure AniList for your anime list:");
+    print_instruction_list(&[
+        "A public list can be read with just your AniList username",
+        "Writing to the list needs an access token from an AniList API client (Settings > Developer)",
+        "Leave the token empty to only read from AniList",
+    ], output);
+    output.println("");
+
+    // Initialize AniList config if it doesn't exist
+    if config.sources.anilist.is_none() {
+        config.sources.anilist = Some(AniListConfig {
+            enabled: true,
+            username: String::new(),
+            score_scale: 100,
+            account: None,
+        });
+    }
+    let anilist_config = config.sources.anilist.as_mut().unwrap();
+    if let Some(account) = account_arg {
+        anilist_config.account = Some(validate_account_name(account)?);
+    }
+    let username = match username_arg {
+        Some(username) => username,
+        None => {
+            let existing = (!anilist_config.username.is_empty()).then_some(anilist_config.username.as_str());
+            prompts::prompt_string("AniList username (optional with a token)", existing)?
+        }
+    };
+    let username = username.trim().to_string();
+
+    let token = match token_arg {
+        Some(token) => token,
+        None => prompts::prompt_password("AniList access token (press Enter to skip)")?,
+    };
+    let token = token.trim().to_string();
+
+    if username.is_empty() && token.is_empty() {
+        return Err(color_eyre::eyre::eyre!("AniList needs a username or an access token"));
+    }
+
+    let enabled = prompts::prompt_yes_no("Enable AniList sync?", Some(true))?;
+    {
+        let anilist_config = config.sources.anilist.as_mut().unwrap();
+        anilist_config.enabled = enabled;
+        anilist_config.username = username.clone();
+    }
+    config.save_to_file(&config_file)
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to save config to {}: {}", config_file.display(), e))?;
+
+    if !token.is_empty() {
+        let credentials_file = path_manager.credentials_file();
+        let mut cred_store = CredentialStore::new(credentials_file.clone());
+        cred_store.load()
+            .map_err(|e| color_eyre::eyre::eyre!("Failed to load credentials from {}: {}", credentials_file.display(), e))?;
+        cred_store.set_anilist_access_token(token);
+        cred_store.save()
+            .map_err(|e| color_eyre::eyre::eyre!("Failed to save credentials to {}: {}", credentials_file.display(), e))?;
+    }
+
+    output.println("");
+    output.success("AniList configuration saved!");
+    output.println(format!("  Enabled: {}", enabled));
+    if !username.is_empty() {
+        output.println(format!("  Username: {}", username));
+    }
+
+    Ok(())
+}
+
 async fn configure_sync(
     enable_watchlist: Option<bool>,
     enable_ratings: Option<bool>,
@@ -1124,6 +1754,100 @@ async fn configure_sync(
     Ok(())
 }
 
+fn export_config(file: &Path, include_secrets: bool, encrypt: bool, output: &Output) -> Result<()> {
+    let path_manager = PathManager::default();
+    let config_file = path_manager.config_file();
+    let config = Config::load_from_file(&config_file)
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to load config from {}: {}", config_file.display(), e))?;
+    let mut cred_store = CredentialStore::new(path_manager.credentials_file());
+    cred_store.load()
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to load credentials: {}", e))?;
+
+    let passphrase = if encrypt {
+        let passphrase = prompts::prompt_password("Passphrase to encrypt the secrets with")?;
+        if prompts::prompt_password("Repeat the passphrase")? != passphrase {
+            return Err(color_eyre::eyre::eyre!("The passphrases do not match"));
+        }
+        Some(passphrase)
+    } else {
+        None
+    };
+    let secrets = match (&passphrase, include_secrets) {
+        (Some(passphrase), _) => SecretsExport::Encrypted(passphrase),
+        (None, true) => SecretsExport::Plaintext,
+        (None, false) => SecretsExport::Exclude,
+    };
+
+    ConfigBundle::export(&config, &cred_store, secrets)
+        .and_then(|bundle| bundle.save(file))
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to export config: {}", e))?;
+
+    if include_secrets {
+        output.warn(format!("Exported config WITH PLAINTEXT SECRETS to {}. Keep this file private.", file.display()));
+    } else if encrypt {
+        output.success(format!("Exported config to {} (secrets encrypted with your passphrase)", file.display()));
+    } else {
+        output.success(format!("Exported config to {} (without secrets; safe to share)", file.display()));
+    }
+    Ok(())
+}
+
+fn import_config(file: &Path, output: &Output) -> Result<()> {
+    let bundle = ConfigBundle::load(file)
+        .map_err(|e| color_eyre::eyre::eyre!("{}", e))?;
+    let passphrase = if bundle.is_encrypted() {
+        Some(prompts::prompt_password("Passphrase the secrets were encrypted with")?)
+    } else {
+        None
+    };
+    let (mut config, secrets) = bundle.into_parts(passphrase.as_deref())
+        .map_err(|e| color_eyre::eyre::eyre!("{}", e))?;
+
+    // An export without secrets keeps the client secrets already configured here
+    let path_manager = PathManager::default();
+    let config_file = path_manager.config_file();
+    if let Ok(existing) = Config::load_from_file(&config_file) {
+        if let (Some(trakt), Some(existing)) = (config.trakt.as_mut(), existing.trakt) {
+            if trakt.client_secret.is_empty() {
+                trakt.client_secret = existing.client_secret;
+            }
+        }
+        if let (Some(simkl), Some(existing)) = (config.simkl.as_mut(), existing.simkl) {
+            if simkl.client_secret.is_empty() {
+                simkl.client_secret = existing.client_secret;
+            }
+        }
+    }
+
+    // Nothing is overwritten unless the imported config is usable
+    config.validate()
+        .map_err(|e| color_eyre::eyre::eyre!("{} is not a valid configuration: {}", file.display(), e))?;
+
+    if config_file.exists() {
+        let backup = config_file.with_extension(format!("toml.{}.bak", chrono::Utc::now().format("%Y%m%d%H%M%S")));
+        std::fs::copy(&config_file, &backup)
+            .map_err(|e| color_eyre::eyre::eyre!("Failed to back up {} to {}: {}", config_file.display(), backup.display(), e))?;
+        output.info(format!("Backed up the current config to {}", backup.display()));
+    }
+    config.save_to_file(&config_file)
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to save config to {}: {}", config_file.display(), e))?;
+
+    if !secrets.is_empty() {
+        // Opened after the config is written, so the imported credentials backend is used
+        let mut cred_store = CredentialStore::new(path_manager.credentials_file());
+        cred_store.load()
+            .map_err(|e| color_eyre::eyre::eyre!("Failed to load credentials: {}", e))?;
+        for (key, value) in &secrets {
+            cred_store.set(key.clone(), value.clone());
+        }
+        cred_store.save()
+            .map_err(|e| color_eyre::eyre::eyre!("Failed to save credentials: {}", e))?;
+    }
+
+    output.success(format!("Imported config from {} ({} credentials)", file.display(), secrets.len()));
+    Ok(())
+}
+
 fn mask_string(s: &str) -> String {
     if s.is_empty() || s == "YOUR_CLIENT_ID" || s == "YOUR_CLIENT_SECRET" {
         return "<not set>".to_string();
@@ -1134,6 +1858,32 @@ fn mask_string(s: &str) -> String {
     format!("{}***{}", &s[..2], &s[s.len() - 2..])
 }
 
+fn format_retry(retry: &media_sync_config::RetryConfig) -> String {
+    if retry.max_attempts <= 1 {
+        return "Off".to_string();
+    }
+    format!(
+        "{} attempts, from {} ms, ±{:.0}% jitter",
+        retry.max_attempts,
+        retry.base_delay_ms,
+        retry.jitter * 100.0
+    )
+}
+
+fn format_media_types(media_types: Option<&Vec<media_sync_config::MediaTypeFilter>>) -> String {
+    match media_types {
+        Some(media_types) => media_types.iter().map(|media_type| media_type.as_str()).collect::<Vec<_>>().join(", "),
+        None => "All".to_string(),
+    }
+}
+
+fn format_rate_limit(rate_limit: &media_sync_config::RateLimitConfig) -> String {
+    match rate_limit.requests_per_second {
+        Some(rps) => format!("{} requests/s, bursts of {}", rps, rate_limit.burst),
+        None => "Off".to_string(),
+    }
+}
+
 // Validation helpers
 
 /// Validates Trakt Client ID format
@@ -1147,6 +1897,14 @@ fn validate_client_id(input: &str) -> Result<(), &'static str> {
     Ok(())
 }
 
+/// Validates a credential account name for `--account`
+fn validate_account_name(account: String) -> Result<String> {
+    if !media_sync_config::is_valid_account_name(&account) {
+        return Err(color_eyre::eyre::eyre!("Account name '{}' may only contain letters, digits, '-' and '_'", account));
+    }
+    Ok(account)
+}
+
 /// Validates email or phone number format for IMDB username
 fn validate_email_or_phone(input: &str) -> Result<(), &'static str> {
     if input.is_empty() {
@@ -1194,41 +1952,9 @@ fn print_oauth_progress(message: &str, output: &Output) {
     output.println(&format!("{} {}", "→".bright_blue(), message.bright_white()));
 }
 
-/// Get list of configured/enabled services from config
-fn get_configured_services(config: &Config) -> Vec<String> {
-    let mut services = Vec::new();
-    
-    // Check Trakt
-    if let Some(ref trakt) = config.trakt {
-        if trakt.enabled && !trakt.client_id.is_empty() && trakt.client_id != "YOUR_CLIENT_ID" {
-            services.push("trakt".to_string());
-        }
-    }
-    
-    if let Some(ref simkl) = config.simkl {
-        if simkl.enabled && !simkl.client_id.is_empty() && simkl.client_id != "YOUR_CLIENT_ID" {
-            services.push("simkl".to_string());
-        }
-    }
-    
-    if let Some(ref imdb) = config.sources.imdb {
-        if imdb.enabled && !imdb.username.is_empty() {
-            services.push("imdb".to_string());
-        }
-    }
-    
-    if let Some(ref plex) = config.sources.plex {
-        if plex.enabled {
-            services.push("plex".to_string());
-        }
-    }
-    
-    services
-}
-
 /// Prompt user to set source_preference order interactively
 fn prompt_source_preference(config: &Config, output: &Output) -> Result<Vec<String>> {
-    let configured_services = get_configured_services(config);
+    let configured_services = config.default_source_preference();
     
     if configured_services.is_empty() {
         return Err(color_eyre::eyre::eyre!("No services are configured. Please configure at least one service first."));
@@ -1276,7 +2002,8 @@ fn prompt_source_preference(config: &Config, output: &Output) -> Result<Vec<Stri
     Ok(result)
 }
 
-/// Load config and prompt for source_preference if missing
+/// Load config, filling in source_preference if missing: prompted for and saved when run
+/// interactively, otherwise derived from the enabled sources for this run only
 pub fn load_config_or_prompt_source_preference(output: &Output) -> Result<Config> {
     let path_manager = PathManager::default();
     let config_file = path_manager.config_file();
@@ -1290,11 +2017,32 @@ pub fn load_config_or_prompt_source_preference(output: &Output) -> Result<Config
     
     // Check if source_preference is missing or empty
     if config.resolution.source_preference.is_empty() {
+        let default_preference = config.default_source_preference();
+        if default_preference.is_empty() {
+            return Err(color_eyre::eyre::eyre!("No sources are enabled. Please run 'totalrecall config' to set up at least one source."));
+        }
+
         output.warn("source_preference is not set in your configuration.");
+        if !can_prompt(output) {
+            // Cron jobs, containers and JSON output can't answer a prompt
+            output.warn(&format!(
+                "Using the enabled sources in default order for this run: {}. Run 'totalrecall config' to save a preference order.",
+                default_preference.join(" > ")
+            ));
+            config.resolution.source_preference = default_preference;
+            return Ok(config);
+        }
+
         output.info("You need to configure the source preference order for conflict resolution.");
-        
-        let source_preference = prompt_source_preference(&config, output)?;
-        config.resolution.source_preference = source_preference;
+        let use_default = prompts::prompt_yes_no(
+            &format!("Use {} (highest priority first)?", default_preference.join(" > ")),
+            Some(true),
+        )?;
+        config.resolution.source_preference = if use_default {
+            default_preference
+        } else {
+            prompt_source_preference(&config, output)?
+        };
         
         // Save updated config
         config.save_to_file(&config_file)
@@ -1307,6 +2055,14 @@ pub fn load_config_or_prompt_source_preference(output: &Output) -> Result<Config
     Ok(config)
 }
 
+/// Whether the user can answer prompts: human output on an interactive terminal
+fn can_prompt(output: &Output) -> bool {
+    use std::io::IsTerminal;
+    matches!(output.format(), crate::output::OutputFormat::Human)
+        && std::io::stdin().is_terminal()
+        && std::io::stdout().is_terminal()
+}
+
 /// Run interactive configuration wizard
 pub async fn run_interactive_config(output: &Output) -> Result<()> {
     let path_manager = PathManager::default();
@@ -1346,6 +2102,11 @@ pub async fn run_interactive_config(output: &Output) -> Result<()> {
                 client_id: String::new(),
                 client_secret: String::new(),
                 status_mapping: media_sync_config::default_trakt_status_mapping(),
+                likes_as: None,
+                account: None,
+                retry: Default::default(),
+                rate_limit: Default::default(),
+                batch_size: media_sync_config::DEFAULT_TRAKT_BATCH_SIZE,
             }),
             simkl: None,
             resolution: media_sync_config::ResolutionConfig {
@@ -1357,17 +2118,39 @@ pub async fn run_interactive_config(output: &Output) -> Result<()> {
                 plex: None,
                 tmdb: None,
                 netflix: None,
+                letterboxd: None,
+                anilist: None,
+                file: None,
+                request_timeout_secs: None,
+                media_types: std::collections::HashMap::new(),
             },
             sync: SyncOptions {
                 sync_watchlist: true,
                 sync_ratings: true,
                 sync_reviews: true,
                 sync_watch_history: true,
+                sync_lists: false,
+                sync_collection: false,
+                sync_show_progress: false,
+                never_remove: false,
+                collect_timeout_secs: None,
                 remove_watched_from_watchlists: false,
                 mark_rated_as_watched: false,
                 remove_watchlist_items_older_than_days: None,
+                watch_history: media_sync_config::WatchHistoryOptions::default(),
+                watchlist: media_sync_config::WatchlistOptions::default(),
+                ratings: media_sync_config::RatingsOptions::default(),
+                reviews: media_sync_config::ReviewsOptions::default(),
+                min_review_length: None,
+                removals: media_sync_config::RemovalsOptions::default(),
+                exclude_tags: Vec::new(),
+                lock: media_sync_config::SyncLockOptions::default(),
+                hub: media_sync_config::HubOptions::default(),
             },
             scheduler: Some(media_sync_config::default_scheduler_config()),
+            credentials: media_sync_config::CredentialsConfig::default(),
+            id_matching: media_sync_config::IdMatchingConfig::default(),
+            notifications: media_sync_config::NotificationsConfig::default(),
         }
     };
     
@@ -1384,7 +2167,7 @@ pub async fn run_interactive_config(output: &Output) -> Result<()> {
     // Trakt
     if prompts::prompt_yes_no("Enable Trakt?", Some(false))? {
         if config.trakt.is_none() || !config.trakt.as_ref().map(|t| t.enabled).unwrap_or(false) {
-            configure_trakt(None, None, output).await?;
+            configure_trakt(None, None, None, output).await?;
             config = Config::load_from_file(&config_file)
                 .map_err(|e| color_eyre::eyre::eyre!("Failed to reload config: {}", e))?;
         } else {
@@ -1396,7 +2179,7 @@ pub async fn run_interactive_config(output: &Output) -> Result<()> {
     // Simkl
     if prompts::prompt_yes_no("Enable Simkl?", Some(false))? {
         if config.simkl.is_none() || !config.simkl.as_ref().map(|s| s.enabled).unwrap_or(false) {
-            configure_simkl(None, None, output).await?;
+            configure_simkl(None, None, None, output).await?;
             config = Config::load_from_file(&config_file)
                 .map_err(|e| color_eyre::eyre::eyre!("Failed to reload config: {}", e))?;
         } else {
@@ -1408,7 +2191,7 @@ pub async fn run_interactive_config(output: &Output) -> Result<()> {
     // IMDB
     if prompts::prompt_yes_no("Enable IMDB?", Some(false))? {
         if config.sources.imdb.is_none() || !config.sources.imdb.as_ref().map(|i| i.enabled).unwrap_or(false) {
-            configure_imdb(None, output).await?;
+            configure_imdb(None, None, output).await?;
             config = Config::load_from_file(&config_file)
                 .map_err(|e| color_eyre::eyre::eyre!("Failed to reload config: {}", e))?;
         } else {
@@ -1420,7 +2203,7 @@ pub async fn run_interactive_config(output: &Output) -> Result<()> {
     // Plex
     if prompts::prompt_yes_no("Enable Plex?", Some(false))? {
         if config.sources.plex.is_none() || !config.sources.plex.as_ref().map(|p| p.enabled).unwrap_or(false) {
-            configure_plex(None, None, output).await?;
+            configure_plex(None, None, None, output).await?;
             config = Config::load_from_file(&config_file)
                 .map_err(|e| color_eyre::eyre::eyre!("Failed to reload config: {}", e))?;
         } else {
@@ -1,8 +1,11 @@
 use crate::output::Output;
 use crate::commands;
+use clap::ValueEnum;
 use color_eyre::Result;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::PathBuf;
 use media_sync_config::{Config, CredentialStore, PathManager};
-use media_sync_core::SyncOrchestrator;
+use media_sync_core::{Notifier, RunNotification, SyncOrchestrator};
 use media_sync_sources::SourceFactoryRegistry;
 use tokio_cron_scheduler::JobScheduler;
 use tracing::{error, info};
@@ -12,6 +15,12 @@ pub struct Scheduler {
     orchestrator: SyncOrchestrator,
     config: media_sync_config::SchedulerConfig,
     cred_store: media_sync_config::CredentialStore,
+    /// `scheduler.allowed_hours`: runs due outside it wait for it to open
+    sync_window: Option<media_sync_config::SyncWindow>,
+    /// `scheduler.timezone`: the zone `sync_window` is read in
+    time_zone: chrono_tz::Tz,
+    /// `[notifications]`: told about every run, successful or not
+    notifier: Notifier,
 }
 
 impl Scheduler {
@@ -21,15 +30,30 @@ impl Scheduler {
         cred_store: media_sync_config::CredentialStore,
     ) -> Result<Self> {
         let sched = JobScheduler::new().await?;
+        let sync_window = config.sync_window()
+            .map_err(|e| color_eyre::eyre::eyre!("{}", e))?;
+        // Only the sync window reads the zone, so an odd `TZ` can't stop an unwindowed daemon
+        let time_zone = match sync_window {
+            Some(_) => config.time_zone().map_err(|e| color_eyre::eyre::eyre!("{}", e))?,
+            None => chrono_tz::UTC,
+        };
 
         Ok(Self {
             scheduler: sched,
             orchestrator,
             config,
             cred_store,
+            sync_window,
+            time_zone,
+            notifier: Notifier::from(&media_sync_config::NotificationsConfig::default()),
         })
     }
 
+    pub fn with_notifier(mut self, notifier: Notifier) -> Self {
+        self.notifier = notifier;
+        self
+    }
+
     pub async fn start(&mut self) -> Result<()> {
         // Run sync immediately on startup if configured
         if self.config.run_on_startup {
@@ -56,6 +80,7 @@ impl Scheduler {
                 }
             };
             
+            self.wait_for_sync_window("startup").await;
             self.orchestrator.set_force_full_sync(should_force_full_sync);
             self.run_sync().await?;
             // Reset to incremental sync for scheduled runs
@@ -74,6 +99,7 @@ impl Scheduler {
         // The schedule parsing and proper cron execution will be added in a future iteration
         loop {
             tokio::time::sleep(tokio::time::Duration::from_secs(3600)).await; // Every hour as placeholder
+            self.wait_for_sync_window("scheduled").await;
             
             info!(operation = "scheduled_sync_start", "Starting scheduled sync");
             match self.run_sync().await {
@@ -81,6 +107,7 @@ impl Scheduler {
                     info!(
                         operation = "scheduled_sync_complete",
                         items_synced = result.items_synced,
+                        items_deferred = result.items_deferred,
                         duration_ms = result.duration.as_millis(),
                         "Scheduled sync completed successfully"
                     );
@@ -96,18 +123,53 @@ impl Scheduler {
         }
     }
 
+    /// Sleep until `scheduler.allowed_hours` allows a sync, logging that the run was deferred
+    async fn wait_for_sync_window(&self, run: &str) {
+        let Some(window) = self.sync_window else {
+            return;
+        };
+        let now = chrono::Utc::now().with_timezone(&self.time_zone);
+        let wait = window.wait_from(now.naive_local());
+        if wait.is_zero() {
+            return;
+        }
+        let resume_at = now + wait;
+        info!(
+            operation = "sync_deferred",
+            run,
+            resume_at = %resume_at.to_rfc3339(),
+            "Deferring {} sync to {}: outside scheduler.allowed_hours ({})",
+            run,
+            resume_at.format("%Y-%m-%d %H:%M"),
+            self.config.allowed_hours.as_deref().unwrap_or_default()
+        );
+        tokio::time::sleep(wait.to_std().unwrap_or_default()).await;
+    }
+
     async fn run_sync(&mut self) -> Result<media_sync_core::SyncResult> {
-        self.orchestrator.sync().await
-            .map_err(|e| color_eyre::eyre::eyre!("Sync operation failed in daemon: {}", e))
+        let started = std::time::Instant::now();
+        let result = match self.orchestrator.sync().await {
+            Ok(result) => result,
+            Err(e) => {
+                self.notifier.notify(RunNotification::from_error(&e.to_string(), started.elapsed()));
+                return Err(color_eyre::eyre::eyre!("Sync operation failed in daemon: {}", e));
+            }
+        };
+        self.notifier.notify(RunNotification::from_result(&result));
+        // A run that failed outright writes nothing, so monitoring sees `finished_at` go stale
+        let metrics_file = PathManager::default().cache_metrics_file();
+        if let Err(e) = media_sync_core::RunMetrics::from_result(&result).save(&metrics_file) {
+            error!("Failed to write run metrics: {}", e);
+        }
+        Ok(result)
     }
     
     /// Check if any sync timestamps exist in the credential store
     /// Returns true if at least one sync timestamp exists for any source/data_type combination
     fn has_any_sync_timestamps(&self) -> bool {
-        let sources = ["trakt", "imdb", "simkl", "plex"];
         let data_types = ["watchlist", "ratings", "reviews", "watch_history"];
         
-        for source in &sources {
+        for source in media_sync_config::source_names() {
             for data_type in &data_types {
                 if self.cred_store.get_last_sync_timestamp(source, data_type).is_some() {
                     return true;
@@ -191,6 +253,9 @@ pub async fn run_start(
     schedule_override: Option<String>,
     no_startup_sync: bool,
     foreground: bool,
+    no_write: bool,
+    never_remove: bool,
+    skip_unconfigured_sources: bool,
     output: &Output,
 ) -> Result<()> {
     let path_manager = PathManager::default();
@@ -239,7 +304,7 @@ pub async fn run_start(
     }
     
     // Now run the daemon (will run in foreground if in container or foreground flag is set)
-    run_daemon_internal(config, schedule_override, no_startup_sync, foreground || is_container(), output).await
+    run_daemon_internal(config, schedule_override, no_startup_sync, foreground || is_container(), no_write, never_remove, skip_unconfigured_sources, output).await
 }
 
 async fn run_daemon_internal(
@@ -247,6 +312,9 @@ async fn run_daemon_internal(
     schedule_override: Option<String>,
     no_startup_sync: bool,
     foreground: bool,
+    no_write: bool,
+    never_remove: bool,
+    skip_unconfigured_sources: bool,
     _output: &Output,
 ) -> Result<()> {
     // Load credentials first (before accessing config fields that might move)
@@ -286,6 +354,7 @@ async fn run_daemon_internal(
         timezone,
         run_on_startup,
         force_full_sync_on_startup: scheduler_config_from_file.force_full_sync_on_startup,
+        allowed_hours: scheduler_config_from_file.allowed_hours.clone(),
     };
     
     // Create sync options from config (same as manual sync command)
@@ -296,19 +365,30 @@ async fn run_daemon_internal(
         sync_reviews: config.sync.sync_reviews,
         sync_watch_history: config.sync.sync_watch_history,
         force_full_sync: false, // Will be set conditionally for startup sync, false for scheduled syncs
+        since: None,
+        no_write,
+        skip_unconfigured_sources,
+        sync_lists: config.sync.sync_lists,
+        sync_collection: config.sync.sync_collection,
+        sync_show_progress: config.sync.sync_show_progress,
+        never_remove: never_remove || config.sync.never_remove,
+        max_items: None,
     };
     
-    let orchestrator = SyncOrchestrator::new(
+    let orchestrator = SyncOrchestrator::new_with_options(
         sources,
         config.resolution.clone(),
+        sync_options,
     )
         .map_err(|e| color_eyre::eyre::eyre!("Failed to create sync orchestrator: {}", e))?
-        .with_sync_options(sync_options)
-        .with_config_sync_options(config.sync.clone());
+        .with_config_sync_options(config.sync.clone())
+        .with_media_types(config.media_type_filters())
+        .with_id_resolver_config((&config).into());
 
     // Create and start scheduler (pass credential store for timestamp checking)
     let mut scheduler = Scheduler::new(orchestrator, scheduler_config, cred_store).await
-        .map_err(|e| color_eyre::eyre::eyre!("Failed to create scheduler: {}", e))?;
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to create scheduler: {}", e))?
+        .with_notifier(Notifier::from(&config.notifications));
     scheduler.start().await
         .map_err(|e| color_eyre::eyre::eyre!("Failed to start scheduler: {}", e))?;
 
@@ -390,3 +470,127 @@ pub async fn run_stop(output: &Output) -> Result<()> {
     Ok(())
 }
 
+
+pub async fn run_daemon(cmd: crate::DaemonCommands, output: &Output) -> Result<()> {
+    match cmd {
+        crate::DaemonCommands::Logs { follow, level, lines } => run_logs(follow, level, lines, output).await,
+    }
+}
+
+/// Minimum level shown by `daemon logs --level`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    /// Level of a log line in either the text or the JSON (`RUST_LOG_JSON=true`) format
+    fn of_line(line: &str) -> Option<Self> {
+        let level = if line.trim_start().starts_with('{') {
+            serde_json::from_str::<serde_json::Value>(line).ok()?
+                .get("level")?
+                .as_str()?
+                .to_string()
+        } else {
+            // "<timestamp>  INFO target: message"
+            line.split_whitespace().nth(1)?.to_string()
+        };
+        match level.as_str() {
+            "TRACE" => Some(LogLevel::Trace),
+            "DEBUG" => Some(LogLevel::Debug),
+            "INFO" => Some(LogLevel::Info),
+            "WARN" => Some(LogLevel::Warn),
+            "ERROR" => Some(LogLevel::Error),
+            _ => None,
+        }
+    }
+}
+
+/// Newest file written by the daemon's rolling log appender (`totalrecall.<date>`)
+fn latest_daemon_log(path_manager: &PathManager) -> Option<PathBuf> {
+    let log_file = path_manager.daemon_log_file();
+    let prefix = log_file.file_stem()?.to_str()?.to_string();
+    std::fs::read_dir(path_manager.log_dir()).ok()?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with(&prefix))
+        .filter_map(|entry| Some((entry.metadata().ok()?.modified().ok()?, entry.path())))
+        .max()
+        .map(|(_, path)| path)
+}
+
+/// Print the lines that pass the level filter. Lines without a level (e.g. wrapped
+/// messages) follow the line before them.
+fn print_log_lines<'a>(lines: impl IntoIterator<Item = &'a str>, level: Option<LogLevel>, showing: &mut bool) {
+    for line in lines {
+        if let Some(line_level) = LogLevel::of_line(line) {
+            *showing = level.map_or(true, |min| line_level >= min);
+        }
+        if *showing {
+            println!("{}", line);
+        }
+    }
+}
+
+/// Print the end of the daemon log and optionally keep following it, across daily rotation
+async fn run_logs(follow: bool, level: Option<LogLevel>, lines: usize, output: &Output) -> Result<()> {
+    let path_manager = PathManager::default();
+    let Some(mut path) = latest_daemon_log(&path_manager) else {
+        output.warn(&format!("No daemon log file found in {}", path_manager.log_dir().display()));
+        output.info("The daemon only logs to a file when it runs in the background: start it with `totalrecall start` (without --foreground).");
+        output.info("In the foreground or in a container, logs go to stderr (e.g. `docker logs -f <container>`).");
+        return Ok(());
+    };
+
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to read {}: {}", path.display(), e))?;
+    let all_lines: Vec<&str> = content.lines().collect();
+    let mut showing = true;
+    print_log_lines(all_lines[all_lines.len().saturating_sub(lines)..].iter().copied(), level, &mut showing);
+
+    if !follow {
+        return Ok(());
+    }
+
+    let mut position = content.len() as u64;
+    let mut pending = String::new();
+    loop {
+        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+
+        // Switch to the next file once the appender rotates
+        if let Some(latest) = latest_daemon_log(&path_manager) {
+            if latest != path {
+                path = latest;
+                position = 0;
+                pending.clear();
+            }
+        }
+
+        let mut file = match std::fs::File::open(&path) {
+            Ok(file) => file,
+            Err(_) => continue,
+        };
+        let len = file.metadata()?.len();
+        if len < position {
+            // Truncated, start over
+            position = 0;
+        }
+        if len == position {
+            continue;
+        }
+        file.seek(SeekFrom::Start(position))?;
+        let mut chunk = String::new();
+        file.read_to_string(&mut chunk)?;
+        position = len;
+
+        pending.push_str(&chunk);
+        // Only print complete lines; keep a partially written one for the next read
+        if let Some(end) = pending.rfind('\n') {
+            let complete: String = pending.drain(..=end).collect();
+            print_log_lines(complete.lines(), level, &mut showing);
+        }
+    }
+}
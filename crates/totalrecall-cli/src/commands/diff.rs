@@ -0,0 +1,100 @@
+use super::config::load_config_or_prompt_source_preference;
+use super::sync::create_sources;
+use crate::output::Output;
+use color_eyre::Result;
+use media_sync_core::SyncOrchestrator;
+use owo_colors::OwoColorize;
+use serde_json::json;
+
+/// Collect and resolve like `sync`, then list what each source is missing and which
+/// ratings it disagrees on. Sends nothing.
+pub async fn run_diff(watchlist: bool, ratings: bool, skip_unconfigured_sources: bool, output: &Output) -> Result<()> {
+    let config = load_config_or_prompt_source_preference(output)?;
+
+    let any_flags_set = watchlist || ratings;
+    let sync_options = media_sync_core::SyncOptions {
+        sync_watchlist: if any_flags_set { watchlist } else { config.sync.sync_watchlist },
+        sync_ratings: if any_flags_set { ratings } else { config.sync.sync_ratings },
+        sync_reviews: false,
+        sync_watch_history: false,
+        force_full_sync: false,
+        since: None,
+        no_write: false,
+        skip_unconfigured_sources,
+        sync_lists: false,
+        sync_collection: false,
+        sync_show_progress: false,
+        never_remove: false,
+        max_items: None,
+    };
+
+    let sources = create_sources(&config).await?;
+    let media_types = config.media_type_filters();
+    let id_resolver_config = media_sync_core::id_resolver::IdResolverConfig::from(&config);
+    let mut orchestrator = SyncOrchestrator::new_with_options(sources, config.resolution, sync_options)
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to create sync orchestrator: {}", e))?
+        .with_config_sync_options(config.sync)
+        .with_media_types(media_types)
+        .with_id_resolver_config(id_resolver_config);
+
+    let (diffs, errors) = orchestrator.diff().await
+        .map_err(|e| color_eyre::eyre::eyre!("Diff failed: {}", e))?;
+
+    match output.format() {
+        crate::output::OutputFormat::Human => {
+            for error in &errors {
+                output.warn(error);
+            }
+            if !output.is_quiet() {
+                for diff in &diffs {
+                    if diff.is_empty() {
+                        println!("{} {}", diff.source.bold(), "up to date".green());
+                        continue;
+                    }
+                    println!("{}", diff.source.bold());
+                    if !diff.missing_watchlist.is_empty() {
+                        println!("  {}", format!("missing {} watchlist items", diff.missing_watchlist.len()).yellow());
+                        for item in &diff.missing_watchlist {
+                            let year = item.year.map(|year| format!(" ({})", year)).unwrap_or_default();
+                            println!("    {}{} {}", item.title, year, item.imdb_id.dimmed());
+                        }
+                    }
+                    if !diff.missing_ratings.is_empty() {
+                        println!("  {}", format!("missing {} ratings", diff.missing_ratings.len()).yellow());
+                        for rating in &diff.missing_ratings {
+                            let title = rating.ids.as_ref().and_then(|ids| ids.title.as_deref()).unwrap_or(&rating.imdb_id);
+                            println!("    {} {}/10 {}", title, rating.rating, rating.imdb_id.dimmed());
+                        }
+                    }
+                    if !diff.rating_conflicts.is_empty() {
+                        println!("  {}", format!("{} rating conflicts", diff.rating_conflicts.len()).red());
+                        for conflict in &diff.rating_conflicts {
+                            println!(
+                                "    {} {}/10 here, {}/10 resolved from {} {}",
+                                conflict.title.as_deref().unwrap_or(&conflict.imdb_id),
+                                conflict.rating,
+                                conflict.resolved_rating,
+                                format!("{:?}", conflict.resolved_source).to_lowercase(),
+                                conflict.imdb_id.dimmed()
+                            );
+                        }
+                    }
+                }
+            }
+            if diffs.iter().all(|diff| diff.is_empty()) {
+                output.success("All sources agree with the resolved data");
+            } else {
+                output.info("Run `totalrecall sync --dry-run` to see exactly what a sync would send");
+            }
+        }
+        crate::output::OutputFormat::Json | crate::output::OutputFormat::JsonPretty => {
+            output.json(&json!({
+                "success": errors.is_empty(),
+                "sources": diffs,
+                "errors": errors,
+            }));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use super::config::load_config_or_prompt_source_preference;
+use super::sync::create_sources;
+use crate::output::Output;
+use clap::ValueEnum;
+use color_eyre::Result;
+use media_sync_core::SyncOrchestrator;
+use serde_json::json;
+use std::path::Path;
+
+/// Format of the export file (`--format`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    /// Watch history and ratings as the bodies of Trakt's /sync/history and /sync/ratings
+    Trakt,
+}
+
+/// Collect and resolve like `sync`, then write the resolved watch history and ratings to
+/// `out` for other tools to import. Sends nothing.
+pub async fn run_export(format: ExportFormat, out: &Path, skip_unconfigured_sources: bool, output: &Output) -> Result<()> {
+    let config = load_config_or_prompt_source_preference(output)?;
+
+    let sync_options = media_sync_core::SyncOptions {
+        sync_watchlist: false,
+        sync_ratings: config.sync.sync_ratings,
+        sync_reviews: false,
+        sync_watch_history: config.sync.sync_watch_history,
+        force_full_sync: false,
+        since: None,
+        no_write: false,
+        skip_unconfigured_sources,
+        sync_lists: false,
+        sync_collection: false,
+        sync_show_progress: false,
+        never_remove: false,
+        max_items: None,
+    };
+
+    let sources = create_sources(&config).await?;
+    let media_types = config.media_type_filters();
+    let id_resolver_config = media_sync_core::id_resolver::IdResolverConfig::from(&config);
+    let mut orchestrator = SyncOrchestrator::new_with_options(sources, config.resolution, sync_options)
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to create sync orchestrator: {}", e))?
+        .with_config_sync_options(config.sync)
+        .with_media_types(media_types)
+        .with_id_resolver_config(id_resolver_config);
+
+    let (export, errors) = match format {
+        ExportFormat::Trakt => orchestrator.export_trakt().await
+            .map_err(|e| color_eyre::eyre::eyre!("Export failed: {}", e))?,
+    };
+    let content = serde_json::to_string_pretty(&export)?;
+    std::fs::write(out, content)
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to write {}: {}", out.display(), e))?;
+
+    match output.format() {
+        crate::output::OutputFormat::Human => {
+            for error in &errors {
+                output.warn(error);
+            }
+            if export.skipped > 0 {
+                output.warn(format!("Left out {} plays and ratings without any ID", export.skipped));
+            }
+            output.success(format!(
+                "Exported {} watched titles and {} ratings to {}",
+                export.history.len(),
+                export.ratings.len(),
+                out.display()
+            ));
+        }
+        crate::output::OutputFormat::Json | crate::output::OutputFormat::JsonPretty => {
+            output.json(&json!({
+                "success": errors.is_empty(),
+                "export_file": out.display().to_string(),
+                "watched_titles": export.history.len(),
+                "ratings": export.ratings.len(),
+                "skipped": export.skipped,
+                "errors": errors,
+            }));
+        }
+    }
+
+    Ok(())
+}
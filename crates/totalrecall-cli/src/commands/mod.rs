@@ -1,7 +1,15 @@
 pub mod sync;
+pub mod plan;
+pub mod prune;
+pub mod reconcile;
+pub mod diff;
+pub mod export;
 pub mod sync_ui;
 pub mod config;
 pub mod clear;
+pub mod cache;
+pub mod status;
+pub mod webhook;
 pub mod daemon;
 pub mod prompts;
 
@@ -0,0 +1,163 @@
+use super::config::load_config_or_prompt_source_preference;
+use super::sync::create_sources;
+use crate::output::Output;
+use color_eyre::Result;
+use media_sync_config::PathManager;
+use media_sync_core::{failures::load_failures, SyncOrchestrator, SyncPlan, SyncResult};
+use owo_colors::OwoColorize;
+use serde_json::json;
+use std::path::Path;
+
+/// Collect and resolve like `sync`, then write every planned write to `out` without sending it
+pub async fn run_plan(
+    watchlist: bool,
+    ratings: bool,
+    reviews: bool,
+    watch_history: bool,
+    all: bool,
+    force_full_sync: bool,
+    out: &Path,
+    skip_unconfigured_sources: bool,
+    output: &Output,
+) -> Result<()> {
+    let config = load_config_or_prompt_source_preference(output)?;
+
+    let any_flags_set = watchlist || ratings || reviews || watch_history;
+    let sync_options = media_sync_core::SyncOptions {
+        sync_watchlist: if all || !any_flags_set { config.sync.sync_watchlist } else { watchlist },
+        sync_ratings: if all || !any_flags_set { config.sync.sync_ratings } else { ratings },
+        sync_reviews: if all || !any_flags_set { config.sync.sync_reviews } else { reviews },
+        sync_watch_history: if all || !any_flags_set { config.sync.sync_watch_history } else { watch_history },
+        force_full_sync,
+        since: None,
+        no_write: false,
+        skip_unconfigured_sources,
+        sync_lists: false,
+        sync_collection: false,
+        sync_show_progress: false,
+        never_remove: config.sync.never_remove,
+        max_items: None,
+    };
+
+    let sources = create_sources(&config).await?;
+    let media_types = config.media_type_filters();
+    let id_resolver_config = media_sync_core::id_resolver::IdResolverConfig::from(&config);
+    let mut orchestrator = SyncOrchestrator::new_with_options(sources, config.resolution, sync_options)
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to create sync orchestrator: {}", e))?
+        .with_config_sync_options(config.sync)
+        .with_media_types(media_types)
+        .with_id_resolver_config(id_resolver_config);
+
+    let (plan, result) = orchestrator.plan().await
+        .map_err(|e| color_eyre::eyre::eyre!("Planning failed: {}", e))?;
+    plan.save(out)
+        .map_err(|e| color_eyre::eyre::eyre!("{}", e))?;
+
+    match output.format() {
+        crate::output::OutputFormat::Human => {
+            for error in &result.errors {
+                output.warn(error);
+            }
+            if !output.is_quiet() {
+                for source_plan in &plan.sources {
+                    println!(
+                        "{:<8} {} add, {} remove, {} watchlist→history, {} ratings, {} reviews, {} watch history",
+                        source_plan.source.bold(),
+                        source_plan.watchlist_add.len(),
+                        source_plan.watchlist_remove.len(),
+                        source_plan.watchlist_to_history.len(),
+                        source_plan.ratings.len(),
+                        source_plan.reviews.len(),
+                        source_plan.watch_history_add.len(),
+                    );
+                }
+            }
+            output.success(&format!("Plan with {} writes saved to {}", plan.total_items(), out.display()));
+            output.info(&format!("Review or edit it, then run: totalrecall apply {}", out.display()));
+        }
+        crate::output::OutputFormat::Json | crate::output::OutputFormat::JsonPretty => {
+            output.json(&json!({
+                "success": true,
+                "plan_file": out.display().to_string(),
+                "total_items": plan.total_items(),
+                "sources": plan.sources.iter().map(|s| json!({
+                    "source": s.source,
+                    "total_items": s.total_items(),
+                })).collect::<Vec<_>>(),
+                "errors": result.errors,
+            }));
+        }
+    }
+
+    Ok(())
+}
+
+/// Send exactly the writes in a plan file
+pub async fn run_apply(plan_file: &Path, no_write: bool, never_remove: bool, output: &Output) -> Result<()> {
+    let plan = SyncPlan::load(plan_file)
+        .map_err(|e| color_eyre::eyre::eyre!("{}", e))?;
+    let config = load_config_or_prompt_source_preference(output)?;
+
+    let sources = create_sources(&config).await?;
+    let mut orchestrator = SyncOrchestrator::new(sources, config.resolution)
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to create sync orchestrator: {}", e))?
+        .with_sync_options(media_sync_core::SyncOptions {
+            no_write,
+            never_remove: never_remove || config.sync.never_remove,
+            ..Default::default()
+        })
+        .with_config_sync_options(config.sync);
+
+    let result = orchestrator.apply(&plan).await
+        .map_err(|e| color_eyre::eyre::eyre!("Apply failed: {}", e))?;
+
+    if no_write {
+        output.warn("--no-write: nothing was written to any source");
+    }
+    report_apply(&plan, &result, plan_file, output);
+    if !result.errors.is_empty() {
+        return Err(color_eyre::eyre::eyre!("Apply finished with {} error(s)", result.errors.len()));
+    }
+    Ok(())
+}
+
+/// Apply the writes that failed in earlier runs (`sync --retry-failures`). Writes that
+/// go through are dropped from the failures file; the rest stay for the next retry.
+pub async fn run_retry_failures(no_write: bool, never_remove: bool, output: &Output) -> Result<()> {
+    let failures_file = PathManager::default().cache_failures_file();
+    let failures = load_failures(&failures_file)
+        .map_err(|e| color_eyre::eyre::eyre!("{}", e))?;
+    let Some(failures) = failures else {
+        output.success("No failed writes from earlier runs to retry");
+        return Ok(());
+    };
+    output.info(&format!("Retrying {} failed writes from earlier runs", failures.total_items()));
+    run_apply(&failures_file, no_write, never_remove, output).await
+}
+
+fn report_apply(plan: &SyncPlan, result: &SyncResult, plan_file: &Path, output: &Output) {
+    match output.format() {
+        crate::output::OutputFormat::Human => {
+            for error in &result.errors {
+                output.error(error);
+            }
+            output.success(&format!(
+                "Applied {}: {} of {} planned writes sent in {:?}",
+                plan_file.display(),
+                result.items_synced,
+                plan.total_items(),
+                result.duration
+            ));
+        }
+        crate::output::OutputFormat::Json | crate::output::OutputFormat::JsonPretty => {
+            output.json(&json!({
+                "success": result.errors.is_empty(),
+                "plan_file": plan_file.display().to_string(),
+                "planned_items": plan.total_items(),
+                "items_synced": result.items_synced,
+                "duration_seconds": result.duration.as_secs_f64(),
+                "errors": result.errors,
+            }));
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use super::config::load_config_or_prompt_source_preference;
+use super::sync::create_sources;
+use crate::output::Output;
+use chrono::{Duration, Utc};
+use color_eyre::Result;
+use media_sync_config::PathManager;
+use media_sync_core::{prune::prune_cached_history, CacheManager, SyncLock};
+use serde_json::json;
+
+/// Forget watch history older than `older_than_days` for one source. Only counts unless `apply`.
+pub async fn run_prune_history(
+    older_than_days: u32,
+    source: &str,
+    remote: bool,
+    apply: bool,
+    no_write: bool,
+    output: &Output,
+) -> Result<()> {
+    let source = source.to_lowercase();
+    if !media_sync_config::is_known_source(&source) {
+        return Err(color_eyre::eyre::eyre!(
+            "Unknown source '{}'. Valid sources: {}",
+            source,
+            media_sync_config::source_names().collect::<Vec<_>>().join(", ")
+        ));
+    }
+    let config = load_config_or_prompt_source_preference(output)?;
+    let cutoff = Utc::now() - Duration::days(older_than_days as i64);
+    let dry_run = !apply;
+
+    // Don't rewrite the cache under a running sync
+    let path_manager = PathManager::default();
+    let _lock = SyncLock::acquire(&path_manager.sync_lock_file(), &config.sync.lock).await
+        .map_err(|e| color_eyre::eyre::eyre!("{}", e))?;
+
+    // The source first, so a source that can't prune fails before the cache is touched
+    let remote_pruned = if remote {
+        let sources = create_sources(&config).await?;
+        let mut client = sources.into_iter()
+            .find(|client| client.source_name() == source)
+            .ok_or_else(|| color_eyre::eyre::eyre!("Source '{}' is not enabled", source))?;
+        client.authenticate().await
+            .map_err(|e| color_eyre::eyre::eyre!("Failed to authenticate to {}: {}", source, e))?;
+        let pruning = client.as_history_pruning()
+            .ok_or_else(|| color_eyre::eyre::eyre!("{} can't remove watch history; drop --remote to prune the cache only", source))?;
+        let pruned = pruning.prune_watch_history(cutoff, dry_run || no_write).await
+            .map_err(|e| color_eyre::eyre::eyre!("Failed to prune {} watch history: {}", source, e))?;
+        Some(pruned)
+    } else {
+        None
+    };
+
+    let cache_manager = CacheManager::new(&path_manager)
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to open cache at {}: {}", path_manager.cache_dir().display(), e))?;
+    let cached = prune_cached_history(&cache_manager, &source, cutoff, dry_run)
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to prune cached {} watch history: {}", source, e))?;
+
+    let cutoff_date = cutoff.date_naive();
+    match output.format() {
+        crate::output::OutputFormat::Human => {
+            let verb = if dry_run { "Would prune" } else { "Pruned" };
+            output.success(&format!("{} {} cached plays watched before {} for {}", verb, cached, cutoff_date, source));
+            if let Some(pruned) = remote_pruned {
+                if no_write && !dry_run {
+                    output.warn(&format!("--no-write: kept {} plays watched before {} on {}", pruned, cutoff_date, source));
+                } else {
+                    let verb = if dry_run { "Would remove" } else { "Removed" };
+                    output.success(&format!("{} {} plays watched before {} from {}", verb, pruned, cutoff_date, source));
+                }
+            }
+            if dry_run {
+                output.info("Dry run: nothing was changed. Pass --apply to prune");
+            }
+        }
+        crate::output::OutputFormat::Json | crate::output::OutputFormat::JsonPretty => {
+            output.json(&json!({
+                "success": true,
+                "source": source,
+                "cutoff": cutoff.to_rfc3339(),
+                "dry_run": dry_run,
+                "cached_pruned": cached,
+                "remote_pruned": remote_pruned,
+                "remote_blocked": remote && no_write && !dry_run,
+            }));
+        }
+    }
+
+    Ok(())
+}
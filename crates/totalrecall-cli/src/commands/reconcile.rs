@@ -0,0 +1,88 @@
+use super::config::load_config_or_prompt_source_preference;
+use super::sync::create_sources;
+use crate::output::Output;
+use color_eyre::Result;
+use media_sync_core::SyncOrchestrator;
+use owo_colors::OwoColorize;
+use serde_json::json;
+
+/// Collect and resolve like `sync`, then report what each source is missing. Sends nothing.
+pub async fn run_reconcile(
+    watchlist: bool,
+    ratings: bool,
+    reviews: bool,
+    watch_history: bool,
+    all: bool,
+    skip_unconfigured_sources: bool,
+    output: &Output,
+) -> Result<()> {
+    let config = load_config_or_prompt_source_preference(output)?;
+
+    let any_flags_set = watchlist || ratings || reviews || watch_history;
+    let sync_options = media_sync_core::SyncOptions {
+        sync_watchlist: if all || !any_flags_set { config.sync.sync_watchlist } else { watchlist },
+        sync_ratings: if all || !any_flags_set { config.sync.sync_ratings } else { ratings },
+        sync_reviews: if all || !any_flags_set { config.sync.sync_reviews } else { reviews },
+        sync_watch_history: if all || !any_flags_set { config.sync.sync_watch_history } else { watch_history },
+        force_full_sync: false,
+        since: None,
+        no_write: false,
+        skip_unconfigured_sources,
+        sync_lists: false,
+        sync_collection: false,
+        sync_show_progress: false,
+        never_remove: false,
+        max_items: None,
+    };
+
+    let sources = create_sources(&config).await?;
+    let media_types = config.media_type_filters();
+    let id_resolver_config = media_sync_core::id_resolver::IdResolverConfig::from(&config);
+    let mut orchestrator = SyncOrchestrator::new_with_options(sources, config.resolution, sync_options)
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to create sync orchestrator: {}", e))?
+        .with_config_sync_options(config.sync)
+        .with_media_types(media_types)
+        .with_id_resolver_config(id_resolver_config);
+
+    let (report, errors) = orchestrator.reconcile().await
+        .map_err(|e| color_eyre::eyre::eyre!("Reconcile failed: {}", e))?;
+
+    match output.format() {
+        crate::output::OutputFormat::Human => {
+            for error in &errors {
+                output.warn(error);
+            }
+            if !output.is_quiet() {
+                for data_type in &report.data_types {
+                    println!("{} ({} items)", data_type.data_type.bold(), data_type.total);
+                    for source in &data_type.sources {
+                        let line = format!(
+                            "  {:<8} missing {:>5} ({:.1}%)",
+                            source.source, source.missing, source.divergence_percent
+                        );
+                        if source.missing == 0 {
+                            println!("{}", line.green());
+                        } else {
+                            println!("{}", line.yellow());
+                        }
+                    }
+                }
+            }
+            if report.is_converged() {
+                output.success("All sources agree with the resolved data");
+            } else {
+                output.info("Run `totalrecall sync` to bring the sources in line");
+            }
+        }
+        crate::output::OutputFormat::Json | crate::output::OutputFormat::JsonPretty => {
+            output.json(&json!({
+                "success": errors.is_empty(),
+                "converged": report.is_converged(),
+                "data_types": report.data_types,
+                "errors": errors,
+            }));
+        }
+    }
+
+    Ok(())
+}
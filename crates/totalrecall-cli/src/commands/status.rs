@@ -0,0 +1,132 @@
+use crate::output::Output;
+use chrono::{DateTime, Utc};
+use color_eyre::Result;
+use comfy_table::{Cell, Table};
+use media_sync_config::{Config, CredentialStore, PathManager};
+use media_sync_core::CacheManager;
+use owo_colors::OwoColorize;
+use serde_json::json;
+
+/// Data types with a sync timestamp (`{source}_last_sync_{data_type}`)
+const TIMESTAMP_DATA_TYPES: [&str; 4] = ["watchlist", "ratings", "reviews", "watch_history"];
+
+/// What is known locally about one source
+struct SourceStatus {
+    source: String,
+    /// Latest sync timestamp over all data types
+    last_sync: Option<DateTime<Utc>>,
+    watchlist: Option<usize>,
+    ratings: Option<usize>,
+    history: Option<usize>,
+}
+
+/// Print when each source last synced and how much of it is cached. Only reads
+/// config.toml, the credential store and the collect cache: nothing is authenticated
+/// or fetched.
+pub async fn run_status(output: &Output) -> Result<()> {
+    let path_manager = PathManager::default();
+
+    // Enabled sources get a row even before their first sync
+    let config_file = path_manager.config_file();
+    let enabled = if config_file.exists() {
+        Config::load_from_file(&config_file)
+            .map_err(|e| color_eyre::eyre::eyre!("Failed to load config from {}: {}", config_file.display(), e))?
+            .default_source_preference()
+    } else {
+        Vec::new()
+    };
+
+    let mut cred_store = CredentialStore::new(path_manager.credentials_file());
+    if path_manager.credentials_file().exists() {
+        cred_store.load()
+            .map_err(|e| color_eyre::eyre::eyre!("Failed to load credentials: {}", e))?;
+    }
+    let cache_manager = CacheManager::new(&path_manager)
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to open cache at {}: {}", path_manager.cache_dir().display(), e))?;
+
+    let mut statuses = Vec::new();
+    for source in media_sync_config::source_names() {
+        let last_sync = TIMESTAMP_DATA_TYPES.iter()
+            .filter_map(|data_type| cred_store.get_last_sync_timestamp(source, data_type))
+            .max();
+        let cached = |data_type: &str| -> Result<Option<usize>> {
+            Ok(cache_manager.peek_source_data(source, data_type)
+                .map_err(|e| color_eyre::eyre::eyre!("{}", e))?
+                .map(|items| items.len()))
+        };
+        let status = SourceStatus {
+            source: source.to_string(),
+            last_sync,
+            watchlist: cached("watchlist")?,
+            ratings: cached("ratings")?,
+            history: cached("watch_history")?,
+        };
+        let has_state = status.last_sync.is_some() || status.watchlist.is_some() || status.ratings.is_some() || status.history.is_some();
+        if has_state || enabled.iter().any(|s| s == source) {
+            statuses.push(status);
+        }
+    }
+
+    match output.format() {
+        crate::output::OutputFormat::Human => {
+            if output.is_quiet() {
+                return Ok(());
+            }
+            if statuses.is_empty() {
+                output.info("No sources configured or synced yet");
+                return Ok(());
+            }
+
+            let mut table = Table::new();
+            table.set_header(vec![
+                Cell::new("Source").add_attribute(comfy_table::Attribute::Bold),
+                Cell::new("Last Sync").add_attribute(comfy_table::Attribute::Bold),
+                Cell::new("Watchlist").add_attribute(comfy_table::Attribute::Bold),
+                Cell::new("Ratings").add_attribute(comfy_table::Attribute::Bold),
+                Cell::new("History").add_attribute(comfy_table::Attribute::Bold),
+            ]);
+            let count = |count: Option<usize>| count.map(|count| count.to_string()).unwrap_or_else(|| "-".to_string());
+            for status in &statuses {
+                table.add_row(vec![
+                    Cell::new(&status.source),
+                    Cell::new(status.last_sync
+                        .map(|last_sync| format!("{} ({})", last_sync.format("%Y-%m-%d %H:%M UTC"), format_age(Utc::now() - last_sync)))
+                        .unwrap_or_else(|| "never".to_string())),
+                    Cell::new(count(status.watchlist)),
+                    Cell::new(count(status.ratings)),
+                    Cell::new(count(status.history)),
+                ]);
+            }
+            table.load_preset(comfy_table::presets::UTF8_FULL);
+            table.apply_modifier(comfy_table::modifiers::UTF8_ROUND_CORNERS);
+            println!("\n{}", "Status".bright_cyan().bold());
+            println!("{}", table);
+            println!("{}", format!("Cache: {}", path_manager.cache_collect_dir().display()).bright_black());
+        }
+        crate::output::OutputFormat::Json | crate::output::OutputFormat::JsonPretty => {
+            let sources: Vec<serde_json::Value> = statuses.iter()
+                .map(|status| json!({
+                    "source": status.source,
+                    "last_sync": status.last_sync.map(|last_sync| last_sync.to_rfc3339()),
+                    "watchlist_items_cached": status.watchlist,
+                    "ratings_cached": status.ratings,
+                    "history_cached": status.history,
+                }))
+                .collect();
+            output.json(&json!({ "sources": sources }));
+        }
+    }
+
+    Ok(())
+}
+
+/// `5m ago`, `3h ago`, `2d ago`
+fn format_age(age: chrono::Duration) -> String {
+    if age.num_days() > 0 {
+        format!("{}d ago", age.num_days())
+    } else if age.num_hours() > 0 {
+        format!("{}h ago", age.num_hours())
+    } else {
+        format!("{}m ago", age.num_minutes().max(0))
+    }
+}
@@ -1,54 +1,146 @@
 use super::sync_ui::SyncUI;
 use super::config::load_config_or_prompt_source_preference;
 use crate::output::Output;
+use chrono::{DateTime, Utc};
+use clap::ValueEnum;
 use color_eyre::eyre::Context;
 use color_eyre::Result;
 use media_sync_config::{Config, PathManager};
-use media_sync_core::SyncOrchestrator;
-use media_sync_sources::{SourceFactoryRegistry, MediaSource};
+use media_sync_core::{SyncOrchestrator, EXIT_CONFIG, EXIT_FAILED};
+use media_sync_sources::{SourceFactoryRegistry, MediaSource, SourceError};
 use serde_json::json;
+use std::sync::Arc;
+
+/// Load credentials and create every enabled source from the config
+pub(crate) async fn create_sources(config: &Config) -> Result<Vec<Box<dyn MediaSource<Error = SourceError>>>> {
+    let path_manager = PathManager::default();
+    let credentials_file = path_manager.credentials_file();
+    let mut cred_store = media_sync_config::CredentialStore::new(credentials_file.clone());
+    cred_store.load()
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to load credentials from {}: {}", credentials_file.display(), e))?;
+
+    // Create factory registry and validate configurations
+    let factory_registry = SourceFactoryRegistry::new();
+    factory_registry.validate_all_configs(config)
+        .map_err(|e| color_eyre::eyre::eyre!("Configuration validation failed: {}", e))?;
+
+    // Create all enabled sources using factories
+    factory_registry.create_all_sources(config, &cred_store).await
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to create sources: {}", e))
+}
+
+/// Format of the dry-run files (`--dry-run-format`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DryRunFormat {
+    Json,
+    Ndjson,
+}
+
+impl From<DryRunFormat> for media_sync_core::dry_run_compare::DryRunFormat {
+    fn from(format: DryRunFormat) -> Self {
+        match format {
+            DryRunFormat::Json => Self::Json,
+            DryRunFormat::Ndjson => Self::Ndjson,
+        }
+    }
+}
+
+/// Narrow `source_preference` (and the other preferences) to `--only-source`, or drop
+/// `--exclude-source`, for this run. Each named source must be in `source_preference`.
+fn select_sources(config: &mut Config, only_source: Option<&str>, exclude_source: Option<&str>) -> Result<()> {
+    let (list, flag, keep_listed) = match (only_source, exclude_source) {
+        (Some(list), _) => (list, "--only-source", true),
+        (None, Some(list)) => (list, "--exclude-source", false),
+        (None, None) => return Ok(()),
+    };
+    let named: Vec<String> = list
+        .split(',')
+        .map(|s| s.trim().to_lowercase())
+        .filter(|s| !s.is_empty())
+        .collect();
+    for source in &named {
+        if !config.resolution.source_preference.contains(source) {
+            return Err(color_eyre::eyre::eyre!(
+                "Source '{}' specified in {} is not in source_preference ({})",
+                source,
+                flag,
+                config.resolution.source_preference.join(", ")
+            ));
+        }
+    }
+    config.resolution.retain_sources(|source| named.iter().any(|n| n == source) == keep_listed);
+    if config.resolution.source_preference.is_empty() {
+        return Err(color_eyre::eyre::eyre!("{} leaves no sources to sync", flag));
+    }
+    Ok(())
+}
 
 pub async fn run_sync(
     watchlist: bool,
     ratings: bool,
     reviews: bool,
     watch_history: bool,
+    lists: bool,
+    collection: bool,
+    show_progress: bool,
     dry_run: Option<String>,
+    compare_previous: bool,
+    dry_run_format: DryRunFormat,
     all: bool,
     use_cache: Option<String>,
     force_full_sync: bool,
+    since: Option<DateTime<Utc>>,
+    force_unlock: bool,
+    from_hub: bool,
+    report_conflicts: bool,
+    revalidate_cache: bool,
+    retry_failures: bool,
+    strict: bool,
+    only_source: Option<String>,
+    exclude_source: Option<String>,
+    max_items: Option<usize>,
+    no_write: bool,
+    never_remove: bool,
+    skip_unconfigured_sources: bool,
     output: &Output,
 ) -> Result<()> {
     tracing::debug!("Sync command started");
 
+    if force_unlock {
+        let lock_file = PathManager::default().sync_lock_file();
+        let removed = media_sync_core::SyncLock::force_unlock(&lock_file)
+            .map_err(|e| color_eyre::eyre::eyre!("{}", e))?;
+        if removed {
+            output.warn(&format!("Removed sync lock {}", lock_file.display()));
+        }
+    }
+
+    if retry_failures {
+        return super::plan::run_retry_failures(no_write, never_remove, output).await;
+    }
+
     // Load config (prompt for source_preference if missing)
-    let config = load_config_or_prompt_source_preference(output)?;
+    let mut config = exit_if_strict(strict, EXIT_CONFIG, load_config_or_prompt_source_preference(output), output)?;
+    select_sources(&mut config, only_source.as_deref(), exclude_source.as_deref())?;
 
     // Determine sync options from flags or config
     // If --all is specified, use config defaults
     // If any individual flags are specified, use only those flags
     // Otherwise use config defaults
-    let any_flags_set = watchlist || ratings || reviews || watch_history;
+    let any_flags_set = watchlist || ratings || reviews || watch_history || lists || collection || show_progress;
     let sync_watchlist = if all || !any_flags_set { config.sync.sync_watchlist } else { watchlist };
     let sync_ratings = if all || !any_flags_set { config.sync.sync_ratings } else { ratings };
     let sync_reviews = if all || !any_flags_set { config.sync.sync_reviews } else { reviews };
     let sync_watch_history = if all || !any_flags_set { config.sync.sync_watch_history } else { watch_history };
+    let sync_lists = if all || !any_flags_set { config.sync.sync_lists } else { lists };
+    let sync_collection = if all || !any_flags_set { config.sync.sync_collection } else { collection };
+    let sync_show_progress = if all || !any_flags_set { config.sync.sync_show_progress } else { show_progress };
 
-    // Load credentials
-    let path_manager = PathManager::default();
-    let credentials_file = path_manager.credentials_file();
-    let mut cred_store = media_sync_config::CredentialStore::new(credentials_file.clone());
-    cred_store.load()
-        .map_err(|e| color_eyre::eyre::eyre!("Failed to load credentials from {}: {}", credentials_file.display(), e))?;
-
-    // Create factory registry and validate configurations
-    let factory_registry = SourceFactoryRegistry::new();
-    factory_registry.validate_all_configs(&config)
-        .map_err(|e| color_eyre::eyre::eyre!("Configuration validation failed: {}", e))?;
-
-    // Create all enabled sources using factories
-    let sources = factory_registry.create_all_sources(&config, &cred_store).await
-        .map_err(|e| color_eyre::eyre::eyre!("Failed to create sources: {}", e))?;
+    let mut sources = exit_if_strict(strict, EXIT_CONFIG, create_sources(&config).await, output)?;
+    if only_source.is_some() || exclude_source.is_some() {
+        let selected = &config.resolution.source_preference;
+        sources.retain(|source| selected.iter().any(|name| name.eq_ignore_ascii_case(source.source_name())));
+    }
 
     // Parse use_cache sources
     let use_cache_sources = if let Some(cache_list) = use_cache {
@@ -130,29 +222,56 @@ pub async fn run_sync(
         sync_reviews,
         sync_watch_history,
         force_full_sync,
+        since,
+        no_write,
+        skip_unconfigured_sources,
+        sync_lists,
+        sync_collection,
+        sync_show_progress,
+        never_remove: never_remove || config.sync.never_remove,
+        max_items,
     };
     
     let dry_run_sources_clone = dry_run_sources.clone();
-    let mut orchestrator = SyncOrchestrator::new(
-        sources,
-        config.resolution,
-    )
-        .map_err(|e| color_eyre::eyre::eyre!("Failed to create sync orchestrator: {}", e))?
-        .with_sync_options(sync_options)
+    let mut compared_sources: Vec<String> = dry_run_sources.iter().cloned().collect();
+    compared_sources.sort();
+    let distribute_dir = PathManager::default().cache_distribute_dir();
+    if compare_previous {
+        media_sync_core::dry_run_compare::rotate_dry_run_output(&distribute_dir, &compared_sources)
+            .map_err(|e| color_eyre::eyre::eyre!("Failed to keep the previous dry run: {}", e))?;
+    }
+    let source_count = sources.len();
+    let media_types = config.media_type_filters();
+    let id_resolver_config = media_sync_core::id_resolver::IdResolverConfig::from(&config);
+    let orchestrator = SyncOrchestrator::new_with_options(sources, config.resolution, sync_options)
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to create sync orchestrator: {}", e));
+    let mut orchestrator = exit_if_strict(strict, EXIT_CONFIG, orchestrator, output)?
         .with_config_sync_options(config.sync)
+        .with_media_types(media_types)
+        .with_id_resolver_config(id_resolver_config)
         .with_use_cache(use_cache_sources)
-        .with_dry_run(dry_run_sources);
-    let _ui = SyncUI::new();
+        .with_dry_run(dry_run_sources)
+        .with_dry_run_format(dry_run_format.into())
+        .with_from_hub(from_hub)
+        .with_report_conflicts(report_conflicts)
+        .with_revalidate_cache(revalidate_cache)
+        .with_progress_tracker(Arc::new(SyncUI::new(output)));
 
     let result = orchestrator.sync().await
-        .map_err(|e| color_eyre::eyre::eyre!("Sync operation failed: {}", e))?;
+        .map_err(|e| color_eyre::eyre::eyre!("Sync operation failed: {}", e));
+    let result = exit_if_strict(strict, EXIT_FAILED, result, output)?;
+
+    let deltas = if compare_previous {
+        Some(media_sync_core::dry_run_compare::compare_dry_run_output(&distribute_dir, &compared_sources)
+            .map_err(|e| color_eyre::eyre::eyre!("Failed to compare with the previous dry run: {}", e))?)
+    } else {
+        None
+    };
 
     // Output results based on format
     match output.format() {
         crate::output::OutputFormat::Human => {
             if !dry_run_sources_clone.is_empty() {
-                let path_manager = PathManager::default();
-                let distribute_dir = path_manager.cache_distribute_dir();
                 output.info(&format!(
                     "Dry-run mode: JSON files written to {}",
                     distribute_dir.display()
@@ -162,18 +281,150 @@ pub async fn run_sync(
                     dry_run_sources_clone.iter().map(|s| s.as_str()).collect::<Vec<_>>().join(", ")
                 ));
             }
+            if no_write {
+                output.warn("--no-write: nothing was written to any source");
+            }
             output.success(&format!("Sync completed: {} items synced in {:?}", result.items_synced, result.duration));
+            if result.resolution_skipped {
+                output.info("Resolution skipped (unchanged)");
+            }
+            if result.items_deferred > 0 {
+                output.warn(&format!(
+                    "{} items deferred to the next run (sync.watchlist.max_items_to_add_per_run, --max-items)",
+                    result.items_deferred
+                ));
+            }
+            if report_conflicts {
+                print_conflicts(orchestrator.conflicts(), output);
+            }
+            if let Some(deltas) = &deltas {
+                print_dry_run_deltas(deltas, output);
+            }
+            if strict {
+                for source in result.sources.iter().filter(|source| !source.is_ok()) {
+                    let problem = if source.auth_failed { "authentication failed".to_string() } else { format!("{} errors", source.errors.len()) };
+                    output.error(format!("{}: {}", source.source, problem));
+                }
+            }
         }
         crate::output::OutputFormat::Json | crate::output::OutputFormat::JsonPretty => {
-            let json_result = json!({
+            let mut json_result = json!({
                 "success": true,
                 "items_synced": result.items_synced,
+                "items_deferred": result.items_deferred,
+                "resolution_skipped": result.resolution_skipped,
                 "duration_seconds": result.duration.as_secs_f64(),
                 "duration": format!("{:?}", result.duration),
+                "sources": result.sources,
+                "per_source": result.per_source,
             });
+            if strict {
+                json_result["exit_code"] = json!(result.exit_code());
+            }
+            if report_conflicts {
+                json_result["conflicts"] = json!(orchestrator.conflicts());
+            }
+            if let Some(deltas) = &deltas {
+                json_result["dry_run_delta"] = json!(deltas);
+            }
             output.json(&json_result);
         }
     }
+    output.summary(result.items_synced, source_count, result.duration, result.errors.len());
 
+    if strict && result.exit_code() != 0 {
+        return Err(StrictExit(result.exit_code()).into());
+    }
     Ok(())
 }
+
+/// `sync --strict` ending with a nonzero exit code, after its output was printed. `main`
+/// exits with the code once everything is dropped.
+#[derive(Debug)]
+pub struct StrictExit(pub i32);
+
+impl std::fmt::Display for StrictExit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "sync --strict failed with exit code {}", self.0)
+    }
+}
+
+impl std::error::Error for StrictExit {}
+
+/// Under `--strict`, report an error that kept the sync from running and end with `code`
+fn exit_if_strict<T>(strict: bool, code: i32, result: Result<T>, output: &Output) -> Result<T> {
+    match result {
+        Err(e) if strict => {
+            match output.format() {
+                crate::output::OutputFormat::Human => output.error(format!("{:#}", e)),
+                crate::output::OutputFormat::Json | crate::output::OutputFormat::JsonPretty => {
+                    output.json(&json!({ "success": false, "error": format!("{:#}", e), "exit_code": code }));
+                }
+            }
+            Err(StrictExit(code).into())
+        }
+        result => result,
+    }
+}
+
+fn print_dry_run_deltas(deltas: &[media_sync_core::dry_run_compare::DryRunDelta], output: &Output) {
+    if deltas.is_empty() {
+        output.info("The dry run plans the same changes as the previous one");
+        return;
+    }
+    for delta in deltas {
+        output.info(&format!(
+            "{} {}: {} newly planned, {} no longer planned",
+            delta.source, delta.data_type, delta.added.len(), delta.removed.len()
+        ));
+        if output.is_quiet() {
+            continue;
+        }
+        for item in &delta.added {
+            println!("  + {}", describe_planned_item(item));
+        }
+        for item in &delta.removed {
+            println!("  - {}", describe_planned_item(item));
+        }
+    }
+}
+
+/// One line for a planned item from a dry-run file: its title or IMDB ID and the value written
+fn describe_planned_item(item: &serde_json::Value) -> String {
+    let text = |name: &str| item.get(name).and_then(|value| value.as_str()).filter(|s| !s.is_empty());
+    let mut line = text("title").or_else(|| text("imdb_id")).unwrap_or("(unknown)").to_string();
+    if let Some(year) = item.get("year").and_then(|year| year.as_u64()) {
+        line.push_str(&format!(" ({})", year));
+    }
+    if let Some(rating) = item.get("rating").and_then(|rating| rating.as_u64()) {
+        line.push_str(&format!(" rated {}", rating));
+    }
+    if let Some(watched_at) = text("watched_at") {
+        line.push_str(&format!(" watched {}", watched_at));
+    }
+    line
+}
+
+/// Characters of each review shown in the human conflict listing; the JSON has full texts
+const CONFLICT_PREVIEW_CHARS: usize = 200;
+
+fn print_conflicts(conflicts: &[media_sync_core::conflicts::ConflictRecord], output: &Output) {
+    if conflicts.is_empty() {
+        output.info("No review conflicts");
+        return;
+    }
+    output.warn(&format!(
+        "{} titles have different reviews on different sources (full texts in {})",
+        conflicts.len(),
+        PathManager::default().cache_conflicts_file().display()
+    ));
+    if output.is_quiet() {
+        return;
+    }
+    for conflict in conflicts {
+        println!("{}", conflict.imdb_id);
+        for entry in &conflict.entries {
+            println!("  {:<8} {:>5} chars  {}", entry.source, entry.length, entry.preview(CONFLICT_PREVIEW_CHARS).replace('\n', " "));
+        }
+    }
+}
@@ -1,110 +1,95 @@
-use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use crate::output::{Output, OutputFormat};
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
+use media_sync_core::ProgressTracker;
 use std::collections::HashMap;
 use std::io::IsTerminal;
+use std::sync::Mutex;
 
+/// Progress bars of a sync: one for the current phase, and one per source operation
+/// (e.g. "Plex ratings set") under it. Draws nothing for `--quiet`, JSON output or
+/// when not attached to a terminal. Console logs are written above the bars while
+/// they are drawn.
 pub struct SyncUI {
     multi: MultiProgress,
-    overall: ProgressBar,
-    source_bars: HashMap<String, ProgressBar>,
-    spinner: ProgressBar,
-    interactive: bool,
+    phase: Mutex<Option<ProgressBar>>,
+    operations: Mutex<HashMap<String, ProgressBar>>,
 }
 
 impl SyncUI {
-    pub fn new() -> Self {
-        let interactive = is_interactive();
-        let multi = MultiProgress::new();
-
-        let overall = multi.add(ProgressBar::new(100));
-        overall.set_style(
-            ProgressStyle::default_bar()
-                .template("{spinner:.green} [{elapsed_precise}] [{wide_bar:.cyan/blue}] {pos}/{len} ({percent}%) {msg}")
-                .unwrap()
-                .progress_chars("█▉▊▋▌▍▎▏  ")
-        );
-        overall.set_message("Starting sync...");
-
-        let spinner = multi.add(ProgressBar::new_spinner());
-        spinner.set_style(
-            ProgressStyle::default_spinner()
-                .template("{spinner:.green} {msg}")
-                .unwrap()
-                .tick_chars("⠋⠙⠹⠸⠼⠴⠦⠧⠇⠏")
-        );
-
-        if !interactive {
-            tracing::info!(
-                operation = "ui_init",
-                mode = "non_interactive",
-                "Running in non-interactive mode - progress bars disabled, using structured logging"
-            );
-        }
-
+    pub fn new(output: &Output) -> Self {
+        let visible = is_interactive() && !output.is_quiet() && output.format() == OutputFormat::Human;
+        let multi = if visible {
+            let multi = MultiProgress::new();
+            crate::logging::draw_logs_above(Some(multi.clone()));
+            multi
+        } else {
+            MultiProgress::with_draw_target(ProgressDrawTarget::hidden())
+        };
         Self {
             multi,
-            overall,
-            source_bars: HashMap::new(),
-            spinner,
-            interactive,
+            phase: Mutex::new(None),
+            operations: Mutex::new(HashMap::new()),
         }
     }
 
-    pub fn add_source(&mut self, name: &str) -> ProgressBar {
-        let pb = self.multi.add(ProgressBar::new(100));
-        pb.set_style(
+    fn bar(&self, total: usize, template: &str) -> ProgressBar {
+        let bar = self.multi.add(ProgressBar::new(total as u64));
+        bar.set_style(
             ProgressStyle::default_bar()
-                .template(&format!("  {{spinner:.yellow}} [{{elapsed_precise}}] [{{wide_bar:.yellow/blue}}] {{pos}}/{{len}} {{msg}}"))
+                .template(template)
                 .unwrap()
                 .progress_chars("█▉▊▋▌▍▎▏  ")
         );
-        pb.set_message(format!("{}: Initializing...", name));
-        self.source_bars.insert(name.to_string(), pb.clone());
-        pb
+        bar
     }
+}
 
-    pub fn set_spinner_message(&self, msg: String) {
-        if self.interactive {
-            self.spinner.set_message(msg);
-        } else {
-            tracing::info!(operation = "progress", message = %msg, "Progress update");
-        }
+impl Drop for SyncUI {
+    fn drop(&mut self) {
+        crate::logging::draw_logs_above(None);
     }
+}
 
-    pub fn finish_spinner(&self) {
-        if self.interactive {
-            self.spinner.finish_with_message("Done");
+impl ProgressTracker for SyncUI {
+    fn start_phase(&self, name: &str, total: usize) {
+        let bar = self.bar(total, "{spinner:.green} [{elapsed_precise}] [{wide_bar:.cyan/blue}] {pos}/{len} {msg}");
+        bar.set_message(name.to_string());
+        if let Some(previous) = self.phase.lock().unwrap().replace(bar) {
+            previous.finish_and_clear();
         }
     }
 
-    pub fn should_show_progress(&self) -> bool {
-        self.interactive
-    }
-
-    pub fn set_progress(&self, current: u64, total: u64, message: &str) {
-        if self.should_show_progress() {
-            // Progress bars will be updated individually
-        } else {
-            tracing::info!(
-                operation = "progress",
-                current = current,
-                total = total,
-                percent = (current as f64 / total as f64 * 100.0) as u8,
-                message = message,
-                "Sync progress update"
-            );
+    fn advance(&self, n: usize) {
+        if let Some(bar) = self.phase.lock().unwrap().as_ref() {
+            bar.inc(n as u64);
         }
     }
 
-    pub fn overall(&self) -> &ProgressBar {
-        &self.overall
+    fn finish_phase(&self) {
+        if let Some(bar) = self.phase.lock().unwrap().take() {
+            bar.finish_and_clear();
+        }
+        for (_, bar) in self.operations.lock().unwrap().drain() {
+            bar.finish_and_clear();
+        }
     }
 
-    pub fn source_bar(&self, name: &str) -> Option<&ProgressBar> {
-        self.source_bars.get(name)
+    fn operation_progress(&self, operation: &str, done: usize, total: usize) {
+        let mut operations = self.operations.lock().unwrap();
+        let bar = operations.entry(operation.to_string()).or_insert_with(|| {
+            let bar = self.bar(total, "  {spinner:.yellow} [{wide_bar:.yellow/blue}] {pos}/{len} {msg}");
+            bar.set_message(operation.to_string());
+            bar
+        });
+        bar.set_position(done as u64);
+        if done >= total {
+            if let Some(bar) = operations.remove(operation) {
+                bar.finish_and_clear();
+            }
+        }
     }
 }
 
 pub fn is_interactive() -> bool {
     std::io::stdout().is_terminal() && std::io::stderr().is_terminal()
 }
-
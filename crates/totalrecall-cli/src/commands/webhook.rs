@@ -0,0 +1,50 @@
+use crate::output::Output;
+use color_eyre::Result;
+use media_sync_config::PathManager;
+use media_sync_core::EventBuffer;
+use media_sync_sources::plex::{PlexWebhookPayload, WebhookEvent};
+use std::io::Read;
+use std::path::PathBuf;
+
+pub async fn run_webhook(cmd: crate::WebhookCommands, output: &Output) -> Result<()> {
+    match cmd {
+        crate::WebhookCommands::Plex { payload } => ingest_plex(payload, output).await,
+    }
+}
+
+/// Buffer a single Plex webhook payload for the next sync
+async fn ingest_plex(payload_path: Option<PathBuf>, output: &Output) -> Result<()> {
+    let json = match payload_path {
+        Some(path) => std::fs::read_to_string(&path)
+            .map_err(|e| color_eyre::eyre::eyre!("Failed to read payload from {}: {}", path.display(), e))?,
+        None => {
+            let mut buf = String::new();
+            std::io::stdin().read_to_string(&mut buf)
+                .map_err(|e| color_eyre::eyre::eyre!("Failed to read payload from stdin: {}", e))?;
+            buf
+        }
+    };
+
+    let payload = PlexWebhookPayload::from_json(&json)
+        .map_err(|e| color_eyre::eyre::eyre!("Invalid Plex webhook payload: {}", e))?;
+    let event_name = payload.event.clone();
+    let event = payload.into_event(chrono::Utc::now());
+
+    let description = match &event {
+        WebhookEvent::Watched(history) => format!("watch of {}", history.title.as_deref().unwrap_or(&history.imdb_id)),
+        WebhookEvent::Rated(rating) => format!("rating {} for {}", rating.rating, rating.imdb_id),
+        WebhookEvent::Ignored(reason) => reason.clone(),
+    };
+
+    let event_buffer = EventBuffer::new(&PathManager::default());
+    let buffered = event_buffer.push("plex", event).await
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to buffer Plex event: {}", e))?;
+
+    if buffered {
+        output.success(&format!("Buffered Plex {} ({}) for the next sync", event_name, description));
+    } else {
+        output.info(&format!("Ignored Plex {}: {}", event_name, description));
+    }
+
+    Ok(())
+}
@@ -1,21 +1,54 @@
 use anyhow::Result;
+use indicatif::MultiProgress;
 use std::io;
-use std::io::IsTerminal;
+use std::io::{IsTerminal, Write};
 use std::path::PathBuf;
+use std::sync::Mutex;
 use tracing_subscriber::{
     layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Registry,
 };
 use tracing_subscriber::fmt::{self, time::ChronoUtc};
 use tracing_appender::rolling::{RollingFileAppender, Rotation};
 
+/// Progress bars on the terminal, which console logs are written above
+static PROGRESS_BARS: Mutex<Option<MultiProgress>> = Mutex::new(None);
+
+/// Write console logs above `bars` while they are drawn instead of through them, or
+/// straight to stderr again with `None`
+pub fn draw_logs_above(bars: Option<MultiProgress>) {
+    *PROGRESS_BARS.lock().unwrap_or_else(|e| e.into_inner()) = bars;
+}
+
+/// stderr, with the progress bars (if any) hidden while a log line is written
+struct ConsoleWriter;
+
+impl Write for ConsoleWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let bars = PROGRESS_BARS.lock().unwrap_or_else(|e| e.into_inner()).clone();
+        match bars {
+            Some(bars) => bars.suspend(|| io::stderr().write(buf)),
+            None => io::stderr().write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        io::stderr().flush()
+    }
+}
+
 pub fn init_logging(verbose_level: u8, quiet: bool) -> Result<()> {
-    init_logging_with_file(verbose_level, quiet, None)
+    init_logging_with_file(verbose_level, quiet, None, &[])
 }
 
-pub fn init_logging_with_file(verbose_level: u8, quiet: bool, log_file: Option<PathBuf>) -> Result<()> {
+pub fn init_logging_with_file(
+    verbose_level: u8,
+    quiet: bool,
+    log_file: Option<PathBuf>,
+    verbose_sources: &[String],
+) -> Result<()> {
     // Determine log level from verbose count
     // 0 = info, 1 = debug (with hyper::proto::h1 suppressed), 2+ = trace (all logs)
-    let filter = if quiet {
+    let mut filter = if quiet {
         // In quiet mode, only show errors
         EnvFilter::new("error")
     } else if verbose_level > 0 {
@@ -37,6 +70,23 @@ pub fn init_logging_with_file(verbose_level: u8, quiet: bool, log_file: Option<P
             .unwrap_or_else(|_| EnvFilter::new("info"))
     };
 
+    // --verbose-source: debug (trace with -v) for one source's module and for
+    // everything logged inside its per-source spans, other sources stay as above
+    let source_level = if verbose_level > 0 { "trace" } else { "debug" };
+    for source in verbose_sources {
+        let source = source.to_lowercase();
+        if !media_sync_config::is_known_source(&source) {
+            return Err(anyhow::anyhow!(
+                "Unknown source '{}' for --verbose-source. Valid sources: {}",
+                source,
+                media_sync_config::source_names().collect::<Vec<_>>().join(", ")
+            ));
+        }
+        filter = filter
+            .add_directive(format!("media_sync_sources::{}={}", source, source_level).parse()?)
+            .add_directive(format!("[{{source={}}}]={}", source, source_level).parse()?);
+    }
+
     // Default to human-readable format; only use JSON if explicitly requested
     let json = std::env::var("RUST_LOG_JSON")
         .map(|v| v == "true")
@@ -91,13 +141,13 @@ pub fn init_logging_with_file(verbose_level: u8, quiet: bool, log_file: Option<P
             let json_layer = fmt::layer()
                 .json()
                 .with_timer(ChronoUtc::rfc_3339())
-                .with_writer(io::stderr);
+                .with_writer(|| ConsoleWriter);
 
             registry.with(json_layer).init();
         } else {
             let fmt_layer = fmt::layer()
                 .with_timer(ChronoUtc::rfc_3339())
-                .with_writer(io::stderr);
+                .with_writer(|| ConsoleWriter);
 
             registry.with(fmt_layer).init();
         }
@@ -1,6 +1,7 @@
 use clap::{ArgAction, Parser, Subcommand, ValueEnum};
 use color_eyre::eyre::Context;
-use commands::{clear, config, daemon as start, sync};
+use commands::{cache, clear, config, daemon as start, diff, export, plan, prune, reconcile, status, sync, webhook};
+use std::process::ExitCode;
 
 mod commands;
 mod logging;
@@ -19,10 +20,32 @@ struct Cli {
     #[arg(short, long, global = true)]
     quiet: bool,
 
+    /// Print one result line when a sync finishes, even with --quiet
+    #[arg(long, global = true)]
+    summary: bool,
+
+    /// Prepare and report every change but never write to any source, whatever the dry-run settings
+    #[arg(long, global = true)]
+    no_write: bool,
+
+    /// Only add and update: log the watchlist removals a sync would make but send none,
+    /// whatever the removal settings (same as sync.never_remove)
+    #[arg(long, global = true)]
+    never_remove: bool,
+
+    /// Leave out sources in source_preference that aren't configured on this machine,
+    /// with a warning, instead of refusing to run
+    #[arg(long, global = true)]
+    skip_unconfigured_sources: bool,
+
     /// Output format
     #[arg(long, global = true, default_value = "human", value_enum)]
     output: output::OutputFormat,
 
+    /// Raise log verbosity for one source only (trakt, imdb, plex, simkl, letterboxd, anilist, file); repeatable
+    #[arg(long = "verbose-source", value_name = "SOURCE", global = true)]
+    verbose_source: Vec<String>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -47,24 +70,173 @@ enum Commands {
         #[arg(long, action = ArgAction::SetTrue)]
         watch_history: bool,
 
+        /// Sync named custom lists (e.g. Trakt personal lists), matched by name across sources
+        #[arg(long, action = ArgAction::SetTrue, conflicts_with = "retry_failures")]
+        lists: bool,
+
+        /// Add owned titles (Plex libraries, Trakt collection) to the Trakt collection,
+        /// dated by when they were collected; never adds watch history
+        #[arg(long, action = ArgAction::SetTrue, conflicts_with = "retry_failures")]
+        collection: bool,
+
+        /// Bring show progress (last watched episode) on Trakt, Simkl and AniList up to
+        /// the furthest any of them reached; only episodes a source lacks are written
+        #[arg(long, action = ArgAction::SetTrue, conflicts_with = "retry_failures")]
+        show_progress: bool,
+
         /// Force a full sync, ignoring saved timestamps
         #[arg(long, action = ArgAction::SetTrue)]
         force_full_sync: bool,
 
+        /// Sync only items added, rated or watched since then instead of since the last
+        /// sync: a duration (7d, 36h) or a date (2024-01-01). Wins over --force-full-sync.
+        #[arg(long, value_name = "DURATION_OR_DATE", value_parser = media_sync_core::parse_since)]
+        since: Option<chrono::DateTime<chrono::Utc>>,
+
         /// Dry-run mode: preview what would be synced without making changes.
         /// Writes JSON files per-source with prepared data after distribution strategy.
         /// Defaults to all sources if no list provided: --dry-run=plex,imdb
         #[arg(long, value_name = "SOURCES", num_args = 0..=1, default_missing_value = "all")]
         dry_run: Option<String>,
 
+        /// With --dry-run: keep the last dry run's files in cache/distribute/previous and
+        /// print only what is newly planned or no longer planned since then
+        #[arg(long, action = ArgAction::SetTrue, requires = "dry_run")]
+        compare_previous: bool,
+
+        /// With --dry-run: write each file as a pretty-printed JSON array or as NDJSON,
+        /// one item per line. Items are sorted by IMDB ID and date either way.
+        #[arg(long, value_enum, value_name = "FORMAT", default_value = "json", requires = "dry_run")]
+        dry_run_format: sync::DryRunFormat,
+
         /// Sync all enabled data types (conflicts with individual flags)
-        #[arg(long, action = ArgAction::SetTrue, conflicts_with_all = ["watchlist", "ratings", "reviews", "watch_history"])]
+        #[arg(long, action = ArgAction::SetTrue, conflicts_with_all = ["watchlist", "ratings", "reviews", "watch_history", "lists", "collection", "show_progress"])]
         all: bool,
 
         /// Use cached source data instead of fetching fresh data (for testing Resolve/Distribute pipeline).
         /// Defaults to all configured sources. Can specify comma-separated list: --use-cache=imdb,trakt,simkl
         #[arg(long, value_name = "SOURCES", num_args = 0..=1, default_missing_value = "all")]
         use_cache: Option<String>,
+
+        /// Remove a stale sync lock left by a run that did not exit cleanly, then sync
+        #[arg(long, action = ArgAction::SetTrue)]
+        force_unlock: bool,
+
+        /// Distribute the resolved data exported by an earlier sync (sync.hub.export) instead of resolving again
+        #[arg(long, action = ArgAction::SetTrue)]
+        from_hub: bool,
+
+        /// List titles whose reviews differ between sources, with each source's text,
+        /// and add them to the conflict report in cache/resolve/conflicts.json
+        #[arg(long, action = ArgAction::SetTrue)]
+        report_conflicts: bool,
+
+        /// Check every source's cached ratings against its rating scale, and fetch any
+        /// source with out-of-scale ratings fresh. Runs once automatically after an upgrade.
+        #[arg(long, action = ArgAction::SetTrue)]
+        revalidate_cache: bool,
+
+        /// Only retry the writes that failed in earlier runs (cache/distribute/failures.json),
+        /// without collecting or resolving again
+        #[arg(long, alias = "only-failures-from-last-run", action = ArgAction::SetTrue,
+            conflicts_with_all = ["dry_run", "use_cache", "from_hub", "report_conflicts", "revalidate_cache", "strict", "since"])]
+        retry_failures: bool,
+
+        /// Exit nonzero when any source had errors: 1 if some sources failed, 2 if all
+        /// failed or the sync could not run, 3 for configuration or authentication errors
+        #[arg(long, action = ArgAction::SetTrue)]
+        strict: bool,
+
+        /// Sync only these sources of source_preference, e.g. --only-source=trakt,simkl.
+        /// The first of them in source_preference order must authenticate, as usual.
+        #[arg(long, value_name = "SOURCES", conflicts_with_all = ["exclude_source", "retry_failures"])]
+        only_source: Option<String>,
+
+        /// Sync every source of source_preference except these, e.g. --exclude-source=plex
+        #[arg(long, value_name = "SOURCES", conflicts_with = "retry_failures")]
+        exclude_source: Option<String>,
+
+        /// Make at most N writes in this run, across all sources and data types, and
+        /// defer the rest to the next run
+        #[arg(long, value_name = "N", conflicts_with = "retry_failures")]
+        max_items: Option<usize>,
+    },
+    /// Write a reviewable plan of everything a sync would change, without changing anything
+    Plan {
+        /// Plan watchlist items
+        #[arg(long, action = ArgAction::SetTrue)]
+        watchlist: bool,
+
+        /// Plan ratings
+        #[arg(long, action = ArgAction::SetTrue)]
+        ratings: bool,
+
+        /// Plan reviews/comments
+        #[arg(long, action = ArgAction::SetTrue)]
+        reviews: bool,
+
+        /// Plan watch history
+        #[arg(long, action = ArgAction::SetTrue)]
+        watch_history: bool,
+
+        /// Plan all enabled data types (conflicts with individual flags)
+        #[arg(long, action = ArgAction::SetTrue, conflicts_with_all = ["watchlist", "ratings", "reviews", "watch_history"])]
+        all: bool,
+
+        /// Plan a full sync, ignoring saved timestamps
+        #[arg(long, action = ArgAction::SetTrue)]
+        force_full_sync: bool,
+
+        /// File to write the plan to
+        #[arg(long, value_name = "FILE", default_value = "sync-plan.json")]
+        out: std::path::PathBuf,
+    },
+    /// Report how far each source has diverged from the resolved data, without syncing
+    Reconcile {
+        /// Compare watchlist items
+        #[arg(long, action = ArgAction::SetTrue)]
+        watchlist: bool,
+
+        /// Compare ratings
+        #[arg(long, action = ArgAction::SetTrue)]
+        ratings: bool,
+
+        /// Compare reviews/comments
+        #[arg(long, action = ArgAction::SetTrue)]
+        reviews: bool,
+
+        /// Compare watch history
+        #[arg(long, action = ArgAction::SetTrue)]
+        watch_history: bool,
+
+        /// Compare all enabled data types (conflicts with individual flags)
+        #[arg(long, action = ArgAction::SetTrue, conflicts_with_all = ["watchlist", "ratings", "reviews", "watch_history"])]
+        all: bool,
+    },
+    /// List what each source is missing and which ratings it disagrees on, without syncing
+    Diff {
+        /// Compare watchlist items
+        #[arg(long, action = ArgAction::SetTrue)]
+        watchlist: bool,
+
+        /// Compare ratings
+        #[arg(long, action = ArgAction::SetTrue)]
+        ratings: bool,
+    },
+    /// Write the resolved watch history and ratings to a file other tools can import, without syncing
+    Export {
+        /// Format of the file
+        #[arg(long, value_enum, default_value = "trakt")]
+        format: export::ExportFormat,
+
+        /// File to write the export to
+        #[arg(long, value_name = "FILE", default_value = "totalrecall-export.json")]
+        out: std::path::PathBuf,
+    },
+    /// Send exactly the writes in a plan file created by `plan`
+    Apply {
+        /// Plan file to apply
+        plan_file: std::path::PathBuf,
     },
     /// Start the daemon with internal scheduler
     Start {
@@ -82,6 +254,11 @@ enum Commands {
     },
     /// Stop the running daemon
     Stop,
+    /// Inspect the background daemon
+    Daemon {
+        #[command(subcommand)]
+        cmd: DaemonCommands,
+    },
     /// Configure credentials and settings
     Config {
         #[command(subcommand)]
@@ -104,6 +281,85 @@ enum Commands {
         /// Clear sync timestamps (forces full sync on next run)
         #[arg(long, action = ArgAction::SetTrue)]
         timestamps: bool,
+
+        /// Forget the cached IDs of one title so the next sync resolves it again
+        #[arg(long, value_name = "IMDB_ID")]
+        id_cache: Option<String>,
+    },
+    /// Forget watch history older than a retention window (dry run unless --apply)
+    PruneHistory {
+        /// Prune plays watched more than this many days ago
+        #[arg(long, value_name = "DAYS")]
+        older_than: u32,
+
+        /// Source whose history is pruned (trakt, imdb, plex, simkl, letterboxd, anilist, file)
+        #[arg(long)]
+        source: String,
+
+        /// Also remove the plays from the source itself, where it supports that
+        #[arg(long, action = ArgAction::SetTrue)]
+        remote: bool,
+
+        /// Prune for real instead of only reporting what would be pruned
+        #[arg(long, action = ArgAction::SetTrue)]
+        apply: bool,
+    },
+    /// Show when each source last synced and how much of it is cached (read-only)
+    Status,
+    /// Inspect cached source data (read-only)
+    Cache {
+        #[command(subcommand)]
+        cmd: CacheCommands,
+    },
+    /// Buffer pushed events (e.g. Plex webhooks) for the next sync
+    Webhook {
+        #[command(subcommand)]
+        cmd: WebhookCommands,
+    },
+}
+
+#[derive(Subcommand)]
+enum DaemonCommands {
+    /// Print the daemon log file (written when the daemon runs in the background)
+    Logs {
+        /// Keep printing new lines as they are written
+        #[arg(short, long, visible_alias = "tail", action = ArgAction::SetTrue)]
+        follow: bool,
+
+        /// Only show lines at this level or above
+        #[arg(long, value_enum)]
+        level: Option<start::LogLevel>,
+
+        /// Number of lines to show from the end of the log
+        #[arg(short = 'n', long, default_value_t = 50)]
+        lines: usize,
+    },
+}
+
+#[derive(Subcommand)]
+enum WebhookCommands {
+    /// Ingest a Plex webhook JSON payload (media.scrobble, media.rate)
+    Plex {
+        /// File containing the JSON `payload` part (reads stdin if omitted)
+        #[arg(long, value_name = "FILE")]
+        payload: Option<std::path::PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum CacheCommands {
+    /// Print what is cached for a source after the last collect phase
+    Dump {
+        /// Source name (trakt, imdb, plex, simkl, letterboxd, anilist, file)
+        source: String,
+
+        /// Only show one data type (defaults to all)
+        #[arg(long = "type", value_enum)]
+        data_type: Option<cache::CacheDataType>,
+
+        /// Number of items to show per data type (0 = all)
+        #[arg(long, default_value_t = 5)]
+        sample: usize,
     },
 }
 
@@ -125,6 +381,11 @@ enum ConfigCommands {
         /// Trakt Client Secret (if not provided, will prompt)
         #[arg(long)]
         client_secret: Option<String>,
+
+        /// Sign in to a named account kept alongside the others and use it for Trakt
+        /// (sets `account` in the Trakt config)
+        #[arg(long)]
+        account: Option<String>,
     },
 
     /// Configure IMDB credentials
@@ -132,6 +393,11 @@ enum ConfigCommands {
         /// IMDB Username (if not provided, will prompt)
         #[arg(long)]
         username: Option<String>,
+
+        /// Sign in to a named account kept alongside the others and use it for IMDB
+        /// (sets `account` in the IMDB config)
+        #[arg(long)]
+        account: Option<String>,
     },
 
     /// Configure Simkl (OAuth flow)
@@ -143,6 +409,11 @@ enum ConfigCommands {
         /// Simkl Client Secret (if not provided, will prompt)
         #[arg(long)]
         client_secret: Option<String>,
+
+        /// Sign in to a named account kept alongside the others and use it for Simkl
+        /// (sets `account` in the Simkl config)
+        #[arg(long)]
+        account: Option<String>,
     },
 
     /// Configure Plex (token-based authentication)
@@ -154,6 +425,27 @@ enum ConfigCommands {
         /// Plex Server URL (optional, for direct server access)
         #[arg(long)]
         server_url: Option<String>,
+
+        /// Sign in to a named account kept alongside the others and use it for Plex
+        /// (sets `account` in the Plex config)
+        #[arg(long)]
+        account: Option<String>,
+    },
+
+    /// Configure AniList (username, plus an access token for writing)
+    Anilist {
+        /// AniList username whose list is read (if not provided, will prompt)
+        #[arg(long)]
+        username: Option<String>,
+
+        /// AniList access token, needed to write to the list (if not provided, will prompt)
+        #[arg(long)]
+        token: Option<String>,
+
+        /// Sign in to a named account kept alongside the others and use it for AniList
+        /// (sets `account` in the AniList config)
+        #[arg(long)]
+        account: Option<String>,
     },
 
     /// Configure sync options
@@ -174,16 +466,37 @@ enum ConfigCommands {
         #[arg(long)]
         enable_watch_history: Option<bool>,
     },
+
+    /// Write the full configuration to a file, for moving to another machine or sharing
+    Export {
+        /// File to write
+        file: std::path::PathBuf,
+
+        /// Include client secrets, tokens and the IMDB password in plain text
+        #[arg(long, action = ArgAction::SetTrue)]
+        include_secrets: bool,
+
+        /// Include secrets encrypted with a passphrase (prompted for)
+        #[arg(long, action = ArgAction::SetTrue, conflicts_with = "include_secrets")]
+        encrypt: bool,
+    },
+
+    /// Replace the configuration with one written by `config export`.
+    /// The current config.toml is backed up first.
+    Import {
+        /// File written by `config export`
+        file: std::path::PathBuf,
+    },
 }
 
 #[tokio::main]
-async fn main() -> color_eyre::Result<()> {
+async fn main() -> color_eyre::Result<ExitCode> {
     color_eyre::install()?;
     
     let cli = Cli::parse();
     
     // Create output handler
-    let output = output::Output::new(cli.output, cli.quiet);
+    let output = output::Output::new(cli.output, cli.quiet).with_summary(cli.summary);
 
     // Determine if we need file logging (daemon mode, not foreground)
     let log_file = match &cli.command {
@@ -195,39 +508,92 @@ async fn main() -> color_eyre::Result<()> {
     };
 
     // Initialize logging (with file if daemon mode, otherwise stderr)
-    logging::init_logging_with_file(cli.verbose, cli.quiet, log_file)
+    logging::init_logging_with_file(cli.verbose, cli.quiet, log_file, &cli.verbose_source)
         .map_err(|e| color_eyre::eyre::eyre!("{}", e))?;
 
-    match cli.command {
+    let result = match cli.command {
         Commands::Sync {
             watchlist,
             ratings,
             reviews,
             watch_history,
+            lists,
+            collection,
+            show_progress,
             dry_run,
+            compare_previous,
+            dry_run_format,
             all,
             use_cache,
             force_full_sync,
+            since,
+            force_unlock,
+            from_hub,
+            report_conflicts,
+            revalidate_cache,
+            retry_failures,
+            strict,
+            only_source,
+            exclude_source,
+            max_items,
+        } => {
+            sync::run_sync(watchlist, ratings, reviews, watch_history, lists, collection, show_progress, dry_run, compare_previous, dry_run_format, all, use_cache, force_full_sync, since, force_unlock, from_hub, report_conflicts, revalidate_cache, retry_failures, strict, only_source, exclude_source, max_items, cli.no_write, cli.never_remove, cli.skip_unconfigured_sources, &output).await
+        }
+        Commands::Plan {
+            watchlist,
+            ratings,
+            reviews,
+            watch_history,
+            all,
+            force_full_sync,
+            out,
         } => {
-            sync::run_sync(watchlist, ratings, reviews, watch_history, dry_run, all, use_cache, force_full_sync, &output).await
+            plan::run_plan(watchlist, ratings, reviews, watch_history, all, force_full_sync, &out, cli.skip_unconfigured_sources, &output).await
         }
+        Commands::Apply { plan_file } => plan::run_apply(&plan_file, cli.no_write, cli.never_remove, &output).await,
+        Commands::Reconcile {
+            watchlist,
+            ratings,
+            reviews,
+            watch_history,
+            all,
+        } => {
+            reconcile::run_reconcile(watchlist, ratings, reviews, watch_history, all, cli.skip_unconfigured_sources, &output).await
+        }
+        Commands::Diff { watchlist, ratings } => diff::run_diff(watchlist, ratings, cli.skip_unconfigured_sources, &output).await,
+        Commands::Export { format, out } => export::run_export(format, &out, cli.skip_unconfigured_sources, &output).await,
         Commands::Start {
             schedule,
             no_startup_sync,
             foreground,
         } => {
-            start::run_start(schedule, no_startup_sync, foreground, &output).await
+            start::run_start(schedule, no_startup_sync, foreground, cli.no_write, cli.never_remove, cli.skip_unconfigured_sources, &output).await
         }
         Commands::Stop => {
             start::run_stop(&output).await
         }
+        Commands::Daemon { cmd } => start::run_daemon(cmd, &output).await,
         Commands::Config { cmd } => {
             match cmd {
                 Some(cmd) => config::run_config(cmd, &output).await,
                 None => config::run_interactive_config(&output).await,
             }
         },
-        Commands::Clear { all, cache, credentials, timestamps } => clear::run_clear(all, cache, credentials, timestamps, &output).await,
+        Commands::Clear { all, cache, credentials, timestamps, id_cache } => clear::run_clear(all, cache, credentials, timestamps, id_cache.as_deref(), &output).await,
+        Commands::PruneHistory { older_than, source, remote, apply } => {
+            prune::run_prune_history(older_than, &source, remote, apply, cli.no_write, &output).await
+        }
+        Commands::Status => status::run_status(&output).await,
+        Commands::Cache { cmd } => cache::run_cache(cmd, &output).await,
+        Commands::Webhook { cmd } => webhook::run_webhook(cmd, &output).await,
+    };
+    match result {
+        Ok(()) => Ok(ExitCode::SUCCESS),
+        // `sync --strict` has reported the failure already
+        Err(e) => match e.downcast_ref::<sync::StrictExit>() {
+            Some(sync::StrictExit(code)) => Ok(ExitCode::from(*code as u8)),
+            None => Err(e),
+        },
     }
 }
 
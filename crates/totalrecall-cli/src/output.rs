@@ -25,11 +25,18 @@ impl OutputFormat {
 pub struct Output {
     format: OutputFormat,
     quiet: bool,
+    summary: bool,
 }
 
 impl Output {
     pub fn new(format: OutputFormat, quiet: bool) -> Self {
-        Self { format, quiet }
+        Self { format, quiet, summary: false }
+    }
+
+    /// Print the final result line of a sync (`--summary`)
+    pub fn with_summary(mut self, summary: bool) -> Self {
+        self.summary = summary;
+        self
     }
 
     pub fn format(&self) -> OutputFormat {
@@ -149,6 +156,37 @@ impl Output {
         }
     }
 
+    /// One machine-parseable result line for cron and monitoring. Printed only with
+    /// `--summary`, and then even in quiet mode.
+    pub fn summary(&self, items_synced: usize, sources: usize, duration: std::time::Duration, errors: usize) {
+        if !self.summary {
+            return;
+        }
+
+        match self.format {
+            OutputFormat::Human => {
+                println!(
+                    "synced {} items across {} sources in {:.1}s, {} errors",
+                    items_synced,
+                    sources,
+                    duration.as_secs_f64(),
+                    errors
+                );
+            }
+            OutputFormat::Json | OutputFormat::JsonPretty => {
+                // Always a single line, even for json-pretty
+                let json = json!({
+                    "type": "summary",
+                    "items_synced": items_synced,
+                    "sources": sources,
+                    "duration_seconds": duration.as_secs_f64(),
+                    "errors": errors
+                });
+                println!("{}", serde_json::to_string(&json).unwrap_or_default());
+            }
+        }
+    }
+
     pub fn json(&self, data: &serde_json::Value) {
         if self.quiet && self.format != OutputFormat::Human {
             return;